@@ -0,0 +1,126 @@
+//! End-to-end latency benchmark
+//!
+//! Sends a series of self-transfers through the same `TxSender` path as the
+//! crank's deploys and reports build, sign, send and confirm latencies as
+//! percentiles, so operators can compare RPCs, fanout and priority fees.
+
+use std::time::Duration;
+
+use tracing::{info, warn};
+
+use crate::crank::Crank;
+
+/// Time spent in each stage of one transaction
+#[derive(Debug, Clone, Copy, Default)]
+pub struct StageTimings {
+    /// Blockhash fetch and instruction/message assembly
+    pub build: Duration,
+    pub sign: Duration,
+    /// Until the RPC returned a signature
+    pub send: Duration,
+    /// From the send returning until the signature confirmed
+    pub confirm: Duration,
+}
+
+impl StageTimings {
+    pub fn total(&self) -> Duration {
+        self.build + self.sign + self.send + self.confirm
+    }
+}
+
+/// Timings of every confirmed benchmark transaction, plus how many failed
+#[derive(Debug, Clone, Default)]
+pub struct BenchmarkReport {
+    pub samples: Vec<StageTimings>,
+    pub failures: usize,
+}
+
+impl BenchmarkReport {
+    /// Nearest-rank percentile (0-100) of one stage across the samples, None when empty
+    pub fn percentile(&self, stage: impl Fn(&StageTimings) -> Duration, pct: u32) -> Option<Duration> {
+        let mut values: Vec<Duration> = self.samples.iter().map(stage).collect();
+        if values.is_empty() {
+            return None;
+        }
+        values.sort();
+        let rank = (values.len() * pct.min(100) as usize).div_ceil(100).max(1);
+        Some(values[rank - 1])
+    }
+
+    /// Log p50/p90/p99 for every stage and the total
+    pub fn log(&self) {
+        info!(
+            "Benchmark: {} confirmed, {} failed",
+            self.samples.len(), self.failures
+        );
+        let stages: [(&str, fn(&StageTimings) -> Duration); 5] = [
+            ("build", |t| t.build),
+            ("sign", |t| t.sign),
+            ("send", |t| t.send),
+            ("confirm", |t| t.confirm),
+            ("total", |t| t.total()),
+        ];
+        for (name, stage) in stages {
+            if let (Some(p50), Some(p90), Some(p99)) = (
+                self.percentile(stage, 50),
+                self.percentile(stage, 90),
+                self.percentile(stage, 99),
+            ) {
+                info!(
+                    "  {:<8} p50 {:>8.1}ms  p90 {:>8.1}ms  p99 {:>8.1}ms",
+                    name,
+                    p50.as_secs_f64() * 1000.0,
+                    p90.as_secs_f64() * 1000.0,
+                    p99.as_secs_f64() * 1000.0,
+                );
+            }
+        }
+    }
+}
+
+/// Send `count` benchmark transactions one after another
+pub async fn run(crank: &Crank, count: u32) -> BenchmarkReport {
+    let mut report = BenchmarkReport::default();
+    for seq in 0..count {
+        match crank.benchmark_transaction(seq).await {
+            Ok(timings) => {
+                info!(
+                    "  #{} confirmed in {:.1}ms",
+                    seq + 1, timings.total().as_secs_f64() * 1000.0
+                );
+                report.samples.push(timings);
+            }
+            Err(e) => {
+                warn!("  #{} failed: {}", seq + 1, e);
+                report.failures += 1;
+            }
+        }
+    }
+    report
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_report_keeps_per_stage_timings() {
+        let ms = Duration::from_millis;
+        let report = BenchmarkReport {
+            samples: (1..=10)
+                .map(|i| StageTimings { build: ms(i), sign: ms(1), send: ms(10 * i), confirm: ms(400 + i) })
+                .collect(),
+            ..Default::default()
+        };
+
+        // Each stage is ranked on its own, not by the sample's total
+        assert_eq!(report.percentile(|t| t.build, 50), Some(ms(5)));
+        assert_eq!(report.percentile(|t| t.sign, 99), Some(ms(1)));
+        assert_eq!(report.percentile(|t| t.send, 90), Some(ms(90)));
+        assert_eq!(report.percentile(|t| t.confirm, 99), Some(ms(410)));
+        assert_eq!(report.percentile(|t| t.total(), 50), Some(ms(5 + 1 + 50 + 405)));
+        assert_eq!(report.percentile(|t| t.build, 0), Some(ms(1)));
+
+        assert_eq!(BenchmarkReport::default().percentile(|t| t.send, 50), None);
+    }
+}
@@ -1,8 +1,14 @@
 //! Configuration for the crank program
 
-use clap::{Parser, Subcommand};
-use solana_sdk::{pubkey::Pubkey, signature::Keypair};
+use clap::{Parser, Subcommand, ValueEnum};
+use solana_client::rpc_client::{RpcClient, RpcClientConfig};
+use solana_rpc_client::http_sender::HttpSender;
+use solana_sdk::{commitment_config::CommitmentConfig, pubkey::Pubkey, signature::Keypair};
 use std::path::PathBuf;
+use std::time::Duration;
+
+use crate::pot_scaling::PotScaling;
+use crate::strategy_chain::{DeployStrategy, StrategyChain};
 
 /// Evore Autodeploy Crank
 #[derive(Parser, Debug, Clone)]
@@ -17,10 +23,46 @@ pub struct Config {
     #[arg(long, env = "RPC_URL", default_value = "https://api.mainnet-beta.solana.com")]
     pub rpc_url: String,
     
+    /// Websocket URL the pipeline subscribes to board and slot updates on (unset = poll the board
+    /// every POLL_INTERVAL_MS)
+    #[arg(long, env = "WS_URL")]
+    pub ws_url: Option<String>,
+    
+    /// Extra RPC endpoints every transaction is also sent to, comma-separated (unset = primary only)
+    /// Improves landing odds near round end; confirmation is still tracked once per signature
+    #[arg(long, env = "SEND_FANOUT_URLS", value_delimiter = ',')]
+    pub send_fanout: Vec<String>,
+    
+    /// Per-request RPC timeout in milliseconds
+    #[arg(long, env = "RPC_TIMEOUT_MS", default_value = "30000")]
+    pub rpc_timeout_ms: u64,
+    
+    /// TCP keepalive interval in seconds for pooled RPC connections (0 = disabled)
+    /// While enabled, idle connections stay pooled instead of being dropped and re-dialed
+    #[arg(long, env = "RPC_KEEPALIVE_SECS", default_value = "15")]
+    pub rpc_keepalive_secs: u64,
+    
     /// Deploy authority keypair path
     #[arg(long, env = "DEPLOY_AUTHORITY_KEYPAIR")]
     pub keypair_path: PathBuf,
     
+    /// Squares to deploy to in the run loop: comma-separated indices 0-24 (e.g. "0,3,7,12") or "all"
+    #[arg(long = "squares", env = "SQUARES", value_name = "INDICES", default_value = "all", value_parser = parse_squares)]
+    pub squares_mask: u32,
+    
+    /// Squares the run loop never deploys to, even if SQUARES includes them: comma-separated indices 0-24 or "none"
+    #[arg(long = "blocked-squares", env = "BLOCKED_SQUARES", value_name = "INDICES", default_value = "none", value_parser = parse_blocked_squares)]
+    pub blocked_squares_mask: u32,
+    
+    /// Deploy strategies the run loop tries per miner, in order, comma-separated (fixed, ev,
+    /// percentage); a strategy that finds nothing to deploy falls through to the next one
+    #[arg(long, env = "STRATEGY_CHAIN", value_enum, value_delimiter = ',', default_value = "fixed")]
+    pub strategy_chain: Vec<DeployStrategy>,
+    
+    /// Largest share (basis points) of a square's total the percentage strategy deploys into
+    #[arg(long, env = "PERCENTAGE_MAX_SHARE_BPS", default_value = "1000")]
+    pub percentage_max_share_bps: u64,
+    
     /// Database path
     #[arg(long, env = "DATABASE_PATH", default_value = "crank.db")]
     pub db_path: PathBuf,
@@ -29,26 +71,235 @@ pub struct Config {
     #[arg(long, env = "PRIORITY_FEE", default_value = "100000")]
     pub priority_fee: u64,
     
-    /// Poll interval in milliseconds
+    /// When the run loop deploys: a fixed number of slots before round end, or as soon as
+    /// the round's entropy var is committed
+    #[arg(long, env = "DEPLOY_TRIGGER", value_enum, default_value = "slots-before-end")]
+    pub deploy_trigger: DeployTrigger,
+    
+    /// Randomize the slots-before-end trigger by up to this many slots either way, drawn
+    /// afresh each round so deploy timing isn't predictable (0 = fixed trigger, run loop only)
+    #[arg(long, env = "DEPLOY_WINDOW_JITTER_SLOTS", default_value = "0")]
+    pub deploy_window_jitter_slots: u64,
+    
+    /// Skip checkpoints that no deploy depends on while deploying, and run them once the
+    /// round is past its deploy window instead (run loop only)
+    #[arg(long, env = "DEFER_OPTIONAL_CHECKPOINTS")]
+    pub defer_optional_checkpoints: bool,
+    
+    /// Skip deploying into rounds whose total deployed (all miners) is below this many lamports,
+    /// saving fees for worthwhile pots (0 = deploy into any round, run loop only)
+    #[arg(long, env = "MIN_BOARD_TOTAL_LAMPORTS", default_value = "0")]
+    pub min_board_total_lamports: u64,
+    
+    /// Grow the run loop's per-square deploy amount by this many basis points of it for every
+    /// SOL already deployed into the round (0 = always deploy the base amount)
+    #[arg(long, env = "POT_SCALE_BPS_PER_SOL", default_value = "0")]
+    pub pot_scale_bps_per_sol: u64,
+    
+    /// Largest pot-scaled deploy amount, in basis points of the base amount
+    #[arg(long, env = "POT_SCALE_MAX_BPS", default_value = "30000")]
+    pub pot_scale_max_bps: u64,
+    
+    /// Most deploys one manager may place in a single batch transaction; the rest of its miners
+    /// move to later batches so other managers aren't crowded out (0 = no limit)
+    #[arg(long, env = "MAX_DEPLOYS_PER_MANAGER", default_value = "0")]
+    pub max_deploys_per_manager: usize,
+    
+    /// Make one last deploy attempt per round once it is inside MIN_SLOTS_TO_DEPLOY of its end:
+    /// a single transaction on freshly fetched miner data (run loop only)
+    #[arg(long, env = "FINAL_SWEEP")]
+    pub final_sweep: bool,
+    
+    /// Priority fee in microlamports per compute unit for the final sweep transaction
+    #[arg(long, env = "FINAL_SWEEP_PRIORITY_FEE", default_value = "1000000")]
+    pub final_sweep_priority_fee: u64,
+    
+    /// Log a one-line spend summary (miners, lamports, fees, confirmations, failures, latency)
+    /// for each round as the next one starts (pipeline only)
+    #[arg(long, env = "ROUND_SUMMARY")]
+    pub round_summary: bool,
+    
+    /// Run a single strategy pass against the current round and exit (for cron)
+    /// Exit code: 0 = deploys confirmed, 1 = error, 2 = nothing to deploy, 3 = deploys failed
+    #[arg(long, env = "RUN_ONCE")]
+    pub once: bool,
+    
+    /// Seconds a deployer discovery scan is reused before the next getProgramAccounts (0 = always rescan)
+    #[arg(long, env = "DEPLOYER_CACHE_TTL_SECS", default_value = "30")]
+    pub deployer_cache_ttl_secs: u64,
+    
+    /// Poll interval in milliseconds (inside the deploy window when POLL_INTERVAL_IDLE_MS is set)
     #[arg(long, env = "POLL_INTERVAL_MS", default_value = "400")]
     pub poll_interval_ms: u64,
     
+    /// Poll interval in milliseconds while the round is still before its deploy window; the
+    /// last idle sleep is cut short as the window opens (0 = always POLL_INTERVAL_MS, run loop only)
+    #[arg(long, env = "POLL_INTERVAL_IDLE_MS", default_value = "0")]
+    pub poll_interval_idle_ms: u64,
+    
+    /// Add a random 0..=this many milliseconds to every poll sleep, drawn afresh each pass, so
+    /// cranks started together don't poll the RPC in lockstep (0 = no jitter, run loop only)
+    #[arg(long, env = "POLL_JITTER_MS", default_value = "0")]
+    pub poll_jitter_ms: u64,
+    
+    /// Minimum SOL (lamports) the deploy authority must keep after paying for a send (0 = disabled)
+    /// Sends that would breach the reserve are skipped and sending pauses briefly
+    #[arg(long, env = "MIN_AUTHORITY_RESERVE_LAMPORTS", default_value = "0")]
+    pub min_authority_reserve_lamports: u64,
+    
+    /// Webhook URL that receives JSON events when the deploy authority drops below / recovers above
+    /// MIN_AUTHORITY_RESERVE_LAMPORTS (unset = log only)
+    #[arg(long, env = "ALERT_WEBHOOK_URL")]
+    pub alert_webhook_url: Option<String>,
+    
+    /// Raise an alert (log + ALERT_WEBHOOK_URL) once a round reaches this many failed
+    /// transactions (0 = disabled, pipeline only)
+    #[arg(long, env = "ALERT_FAILURES_PER_ROUND", default_value = "0")]
+    pub alert_failures_per_round: u64,
+    
+    /// Raise an alert once this many transactions in a row have failed; re-arms after the next
+    /// confirmation (0 = disabled, pipeline only)
+    #[arg(long, env = "ALERT_CONSECUTIVE_FAILURES", default_value = "0")]
+    pub alert_consecutive_failures: u64,
+    
+    /// Milliseconds a miner whose deploy failed waits before it is retried this round
+    /// (0 = retry immediately, pipeline only)
+    #[arg(long, env = "DEPLOY_FAILURE_COOLDOWN_MS", default_value = "2000")]
+    pub deploy_failure_cooldown_ms: u64,
+    
+    /// Exit cleanly once deploys have confirmed in this many rounds, for capital-limited
+    /// sessions and smoke runs (0 = run forever)
+    #[arg(long, env = "MAX_ROUNDS", default_value = "0")]
+    pub max_rounds: u64,
+    
+    /// Balance (lamports, on top of rent) to keep in each managed_miner_auth PDA
+    /// Anything above it is swept to PROFIT_DESTINATION after claims (pipeline only)
+    #[arg(long, env = "PROFIT_TARGET_LAMPORTS", default_value = "0")]
+    pub profit_target_lamports: u64,
+    
+    /// Cold wallet that receives swept profits (unset = profit sweeping disabled)
+    /// Only managers whose authority is the deploy authority can be swept
+    #[arg(long, env = "PROFIT_DESTINATION")]
+    pub profit_destination: Option<Pubkey>,
+    
+    /// Managers whose settled SOL rewards are claimed back into their autodeploy balance
+    /// after each checkpoint, comma-separated (unset = compounding disabled, pipeline only)
+    #[arg(long, env = "COMPOUND_MANAGERS", value_delimiter = ',')]
+    pub compound_managers: Vec<Pubkey>,
+    
+    /// Keep transaction history for this many rounds behind the newest one (0 = keep everything)
+    #[arg(long, env = "DB_RETENTION_ROUNDS", default_value = "0")]
+    pub db_retention_rounds: u64,
+    
+    /// Seconds between database maintenance runs (retention + vacuum, 0 = disabled)
+    #[arg(long, env = "DB_VACUUM_INTERVAL_SECS", default_value = "0")]
+    pub db_vacuum_interval_secs: u64,
+    
+    /// When checkpoints also recycle claimable SOL: always, never, or threshold
+    /// (only once rewards reach AUTO_RECYCLE_THRESHOLD_LAMPORTS, skipping dust recycles)
+    #[arg(long, value_enum, env = "AUTO_RECYCLE", default_value = "always")]
+    pub auto_recycle: AutoRecycle,
+    
+    /// Least claimable SOL (lamports) worth a recycle when AUTO_RECYCLE=threshold
+    #[arg(long, env = "AUTO_RECYCLE_THRESHOLD_LAMPORTS", default_value = "0")]
+    pub auto_recycle_threshold_lamports: u64,
+    
+    /// Most miner LUTs the run loop creates per round (0 = no cap)
+    /// Missing LUTs beyond the cap are created in later rounds, highest balances first
+    #[arg(long, env = "MAX_LUT_CREATIONS_PER_ROUND", default_value = "0")]
+    pub max_lut_creations_per_round: usize,
+    
+    /// Consecutive miner LUT-creation failures after which the run loop deploys the round
+    /// without LUTs, in MAX_BATCH_SIZE_NO_LUT batches (0 = never fall back)
+    #[arg(long, env = "LUT_FAILURE_THRESHOLD", default_value = "3")]
+    pub lut_failure_threshold: usize,
+    
+    /// File caching the LUT registry between runs (unset = scan on-chain every load)
+    /// Only LUTs whose on-chain header changed are re-read; delete it to force a full scan
+    #[arg(long, env = "LUT_CACHE_PATH")]
+    pub lut_cache_path: Option<PathBuf>,
+    
     /// [LEGACY] Address Lookup Table for manual LUT commands (show-lut, deactivate-lut, close-lut)
     /// Not needed for 'run' - the crank auto-discovers and creates LUTs as needed
     #[arg(long, env = "LUT_ADDRESS")]
     pub lut_address: Option<Pubkey>,
 }
 
+/// What makes the run loop deploy in a round
+#[derive(ValueEnum, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DeployTrigger {
+    /// Deploy once the round is within DEPLOY_SLOTS_BEFORE_END slots of its end
+    SlotsBeforeEnd,
+    /// Deploy as soon as the entropy var is committed for the current round
+    EntropyReady,
+}
+
+/// Whether checkpoints recycle claimable SOL
+#[derive(ValueEnum, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AutoRecycle {
+    /// Recycle whenever there is SOL to claim
+    Always,
+    /// Checkpoint only, leave SOL rewards unclaimed
+    Never,
+    /// Recycle once rewards reach AUTO_RECYCLE_THRESHOLD_LAMPORTS
+    Threshold,
+}
+
+/// Recycle decision for checkpoints, copied out of `Config` for the systems that need it
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RecyclePolicy {
+    pub mode: AutoRecycle,
+    pub threshold_lamports: u64,
+}
+
+impl RecyclePolicy {
+    /// Whether a miner holding `rewards_sol` claimable lamports should be recycled
+    pub fn should_recycle(&self, rewards_sol: u64) -> bool {
+        match self.mode {
+            AutoRecycle::Always => rewards_sol > 0,
+            AutoRecycle::Never => false,
+            AutoRecycle::Threshold => rewards_sol > 0 && rewards_sol >= self.threshold_lamports,
+        }
+    }
+}
+
 #[derive(Subcommand, Debug, Clone)]
 pub enum Command {
     /// Run the main crank loop (auto-discovers/creates LUTs)
     Run,
     /// Run the new pipeline architecture (experimental)
     Pipeline,
+    /// First-run setup: create a manager + deployer owned by the deploy authority, the shared LUT,
+    /// and write a .env for the other commands
+    Init {
+        /// Deployer fee in basis points
+        #[arg(long, default_value = "0")]
+        bps_fee: u64,
+        /// Deployer flat fee in lamports (the pipeline only cranks deployers charging 715)
+        #[arg(long, default_value = "715")]
+        flat_fee: u64,
+        /// Most lamports the deployer may deploy per round (0 = unlimited)
+        #[arg(long, default_value = "0")]
+        max_per_round: u64,
+        /// Where to write the generated config
+        #[arg(long, default_value = ".env")]
+        out: PathBuf,
+        /// Overwrite the config file if it exists
+        #[arg(long)]
+        force: bool,
+    },
     /// Send a test transaction to verify connectivity
     Test,
+    /// Send a series of test transactions and report build/sign/send/confirm latency percentiles
+    Benchmark {
+        /// Number of transactions to send
+        #[arg(long, default_value = "10")]
+        count: u32,
+    },
     /// Show deployer accounts we manage and their LUT status
     List,
+    /// Report deployers whose current fees exceed the expected fees we agreed to
+    VerifyFees,
     /// Update expected fees for all deployers (as deploy_authority)
     SetExpectedFees {
         /// Expected BPS fee (0 = accept any)
@@ -68,12 +319,89 @@ pub enum Command {
     DeactivateLut,
     /// [LEGACY] Close LUT and reclaim rent (requires LUT_ADDRESS)
     CloseLut,
+    /// Check the shared LUT and every deployer's miner LUT for missing or unexpected addresses
+    VerifyLut,
     /// Deactivate LUTs that don't match current required accounts (wrong automation address, etc)
     DeactivateUnused,
     /// Show deactivating LUTs status and close any that are ready
     CleanupDeactivated,
     /// Check all Evore program accounts
     CheckAccounts,
+    /// Summarize recorded per-square outcomes: win rate, average deployed and miners per square
+    SquareStats,
+    /// Re-send a failed or expired transaction from the database with a fresh blockhash
+    Replay {
+        /// Signature of the failed transaction (base58)
+        signature: String,
+    },
+    /// Dump a manager's full on-chain state (manager, deployers, miners, LUTs) as JSON
+    Snapshot {
+        /// Manager account address
+        manager: Pubkey,
+        /// Output file (unset = print to stdout)
+        #[arg(long)]
+        out: Option<PathBuf>,
+        /// Number of auth_ids to read managed miners for, starting at 0
+        #[arg(long, default_value = "1")]
+        auth_ids: u64,
+    },
+    /// Deposit into every managed miner we own the manager of until its autodeploy balance
+    /// reaches the target, as far as the deploy authority's balance allows
+    TopUp {
+        /// Autodeploy balance (lamports) to bring each managed_miner_auth up to
+        #[arg(long)]
+        target_lamports: u64,
+    },
+    /// Run one miner through the pipeline's deploy gates and print each gate's result
+    TraceMiner {
+        /// Manager account address
+        manager: Pubkey,
+        /// Managed miner to trace
+        #[arg(long, default_value = "0")]
+        auth_id: u64,
+    },
+    /// Simulate an autodeploy for each deployer and print the per-square amounts it would land
+    SimulateDeploy {
+        /// Lamports per square to request
+        #[arg(long, default_value = "2800")]
+        amount: u64,
+        /// Squares bitmask (bit i = square i)
+        #[arg(long, default_value = "33554431")]
+        squares_mask: u32,
+    },
+}
+
+/// Parse a comma-separated square index list (or "all") into a squares mask
+fn parse_squares(value: &str) -> Result<u32, String> {
+    let value = value.trim();
+    if value.eq_ignore_ascii_case("all") {
+        return Ok(evore::squares::ALL_SQUARES_MASK as u32);
+    }
+    
+    let mask = parse_square_indices(value)?;
+    if mask == 0 {
+        return Err("at least one square is required".to_string());
+    }
+    Ok(mask)
+}
+
+/// Parse a comma-separated square index list (or "none") into a blocked squares mask
+fn parse_blocked_squares(value: &str) -> Result<u32, String> {
+    let value = value.trim();
+    if value.is_empty() || value.eq_ignore_ascii_case("none") {
+        return Ok(0);
+    }
+    parse_square_indices(value)
+}
+
+fn parse_square_indices(value: &str) -> Result<u32, String> {
+    let indices = value
+        .split(',')
+        .map(|s| s.trim().parse::<usize>().map_err(|_| format!("invalid square index '{}'", s.trim())))
+        .collect::<Result<Vec<_>, _>>()?;
+    
+    evore::squares::indices_to_mask(&indices)
+        .map_err(|_| format!("square indices must be between 0 and {}", evore::squares::BOARD_SQUARES - 1))
 }
 
 impl Config {
@@ -83,6 +411,56 @@ impl Config {
         let keypair_bytes: Vec<u8> = serde_json::from_str(&keypair_data)?;
         Ok(Keypair::from_bytes(&keypair_bytes)?)
     }
+    
+    /// SQUARES minus BLOCKED_SQUARES (0 if every chosen square is blocked)
+    pub fn deploy_squares_mask(&self) -> u32 {
+        self.squares_mask & !self.blocked_squares_mask
+    }
+    
+    /// STRATEGY_CHAIN / PERCENTAGE_MAX_SHARE_BPS as a chain
+    pub fn strategy_chain(&self) -> StrategyChain {
+        StrategyChain {
+            strategies: self.strategy_chain.clone(),
+            percentage_max_share_bps: self.percentage_max_share_bps,
+        }
+    }
+    
+    /// POT_SCALE_BPS_PER_SOL / POT_SCALE_MAX_BPS as a scaling
+    pub fn pot_scaling(&self) -> PotScaling {
+        PotScaling {
+            bps_per_sol: self.pot_scale_bps_per_sol,
+            max_bps: self.pot_scale_max_bps,
+        }
+    }
+    
+    /// AUTO_RECYCLE / AUTO_RECYCLE_THRESHOLD_LAMPORTS as a policy
+    pub fn recycle_policy(&self) -> RecyclePolicy {
+        RecyclePolicy {
+            mode: self.auto_recycle,
+            threshold_lamports: self.auto_recycle_threshold_lamports,
+        }
+    }
+    
+    /// HTTP client for RPC requests with the configured timeout and keepalive
+    pub fn rpc_http_client(&self) -> reqwest::Client {
+        let mut builder = reqwest::Client::builder()
+            .default_headers(HttpSender::default_headers())
+            .timeout(Duration::from_millis(self.rpc_timeout_ms));
+        if self.rpc_keepalive_secs > 0 {
+            builder = builder
+                .tcp_keepalive(Duration::from_secs(self.rpc_keepalive_secs))
+                .pool_idle_timeout(None);
+        }
+        builder.build().expect("build rpc http client")
+    }
+    
+    /// RPC client (confirmed commitment) on top of `rpc_http_client`
+    pub fn rpc_client(&self) -> RpcClient {
+        RpcClient::new_sender(
+            HttpSender::new_with_client(self.rpc_url.clone(), self.rpc_http_client()),
+            RpcClientConfig::with_commitment(CommitmentConfig::confirmed()),
+        )
+    }
 }
 
 /// Information about a deployer the crank is managing
@@ -103,3 +481,93 @@ pub struct DeployerInfo {
     /// Maximum lamports to deploy per round (0 = unlimited)
     pub max_per_round: u64,
 }
+
+/// Fees a deployer currently charges above what the deploy authority expects
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FeeMismatch {
+    /// bps_fee is above a nonzero expected_bps_fee
+    pub bps_exceeded: bool,
+    /// flat_fee is above a nonzero expected_flat_fee
+    pub flat_exceeded: bool,
+}
+
+impl DeployerInfo {
+    /// Fees raised beyond the stored expectations (an expected fee of 0 accepts any value)
+    pub fn fee_mismatch(&self) -> Option<FeeMismatch> {
+        let mismatch = FeeMismatch {
+            bps_exceeded: self.expected_bps_fee > 0 && self.bps_fee > self.expected_bps_fee,
+            flat_exceeded: self.expected_flat_fee > 0 && self.flat_fee > self.expected_flat_fee,
+        };
+        (mismatch.bps_exceeded || mismatch.flat_exceeded).then_some(mismatch)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rpc_client_applies_configured_timeout() {
+        // Accepts connections and never answers
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let rpc_url = format!("http://{}", listener.local_addr().unwrap());
+        std::thread::spawn(move || {
+            let _held: Vec<_> = listener.incoming().collect();
+        });
+
+        let config = Config::try_parse_from([
+            "evore-crank",
+            "--keypair-path", "unused.json",
+            "--rpc-url", &rpc_url,
+            "--rpc-timeout-ms", "200",
+        ])
+        .unwrap();
+        assert_eq!(config.rpc_timeout_ms, 200);
+
+        let started = std::time::Instant::now();
+        let err = config.rpc_client().get_slot().unwrap_err();
+        let elapsed = started.elapsed();
+        assert!(
+            matches!(err.kind(), solana_client::client_error::ClientErrorKind::Reqwest(e) if e.is_timeout()),
+            "unexpected error: {}", err
+        );
+        assert!(elapsed >= Duration::from_millis(200), "gave up after {:?}", elapsed);
+        assert!(elapsed < Duration::from_secs(5), "default timeout used: {:?}", elapsed);
+    }
+
+    #[test]
+    fn test_parse_squares_index_list() {
+        assert_eq!(parse_squares("0,3,7,12"), Ok((1 << 0) | (1 << 3) | (1 << 7) | (1 << 12)));
+        assert_eq!(parse_squares(" 24 , 0 "), Ok((1 << 24) | 1));
+        assert_eq!(parse_squares("all"), Ok(0x1FFFFFF));
+
+        assert!(parse_squares("0,25").is_err(), "off the board");
+        assert!(parse_squares("0,-1").is_err());
+        assert!(parse_squares("0,,3").is_err());
+        assert!(parse_squares("").is_err());
+    }
+
+    #[test]
+    fn test_threshold_policy_skips_small_recycles() {
+        let policy = |mode| RecyclePolicy { mode, threshold_lamports: 50_000 };
+
+        assert!(!policy(AutoRecycle::Threshold).should_recycle(49_999), "dust is left for later");
+        assert!(policy(AutoRecycle::Threshold).should_recycle(50_000));
+        assert!(policy(AutoRecycle::Threshold).should_recycle(2_000_000));
+
+        assert!(policy(AutoRecycle::Always).should_recycle(1));
+        assert!(!policy(AutoRecycle::Never).should_recycle(2_000_000));
+        for mode in [AutoRecycle::Always, AutoRecycle::Never, AutoRecycle::Threshold] {
+            assert!(!policy(mode).should_recycle(0), "nothing to claim");
+        }
+
+        let config = Config::try_parse_from([
+            "evore-crank",
+            "--keypair-path", "unused.json",
+            "--auto-recycle", "threshold",
+            "--auto-recycle-threshold-lamports", "50000",
+        ])
+        .unwrap();
+        assert_eq!(config.recycle_policy(), policy(AutoRecycle::Threshold));
+    }
+}
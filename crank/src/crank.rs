@@ -3,34 +3,50 @@
 //! Finds deployers where we are the deploy_authority and executes autodeploys
 
 use evore::{
+    compute::{strategy_cu_limit, MAX_COMPUTE_UNIT_LIMIT},
     consts::DEPLOY_FEE,
     instruction::{
-        mm_full_autodeploy,
+        mm_create_miner, mm_full_autodeploy,
         // Legacy instructions (kept for backward compatibility)
         mm_autodeploy, mm_autocheckpoint, recycle_sol,
     },
-    ore_api::{board_pda, miner_pda, round_pda, Board, Miner, Round},
-    state::{managed_miner_auth_pda, Deployer},
+    ore_api::{board_pda, round_pda, Board, Miner, Round},
+    state::{Deployer, Manager, StrategyDeployer},
+    validation::StrategyType,
+};
+use solana_account_decoder::UiAccountEncoding;
+use solana_client::{
+    rpc_client::RpcClient,
+    rpc_config::{RpcSimulateTransactionAccountsConfig, RpcSimulateTransactionConfig},
+    rpc_response::RpcSimulateTransactionResult,
 };
-use solana_client::rpc_client::RpcClient;
 use solana_sdk::{
     commitment_config::CommitmentConfig,
     compute_budget::ComputeBudgetInstruction,
     hash::Hash,
+    instruction::Instruction,
+    message::VersionedMessage,
     pubkey::Pubkey,
     signature::{Keypair, Signer},
     system_instruction,
-    transaction::Transaction,
+    transaction::{Transaction, VersionedTransaction},
 };
 use sqlx::{Pool, Sqlite};
-use std::time::{SystemTime, UNIX_EPOCH};
+use std::sync::Arc;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+use tokio::sync::RwLock;
 use steel::AccountDeserialize;
 use tracing::{debug, error, info, warn};
 
 use crate::{
-    config::{Config, DeployerInfo},
+    config::{Config, DeployerInfo, RecyclePolicy},
     db,
+    deploy_plan::DeployPlan,
     lut::{LutManager, LutRegistry, get_miner_accounts, get_miner_auth_pda},
+    pda_cache::{self, MinerPdas},
+    rpc::RpcProvider,
+    pipeline::shared_state::{DeployerCache, SharedDeployerCache},
+    benchmark::StageTimings,
     sender::TxSender,
 };
 
@@ -41,6 +57,7 @@ pub struct Crank {
     deploy_authority: Keypair,
     sender: TxSender,
     db_pool: Pool<Sqlite>,
+    deployers: SharedDeployerCache,
 }
 
 impl Crank {
@@ -48,12 +65,13 @@ impl Crank {
         let deploy_authority = config.load_keypair()
             .map_err(|e| CrankError::KeypairLoad(e.to_string()))?;
         
-        let rpc_client = RpcClient::new_with_commitment(
-            config.rpc_url.clone(),
-            CommitmentConfig::confirmed(),
-        );
+        let rpc_client = config.rpc_client();
+        let deployer_cache_ttl_secs = config.deployer_cache_ttl_secs;
         
-        let sender = TxSender::new(config.rpc_url.clone());
+        let sender = TxSender::new(config.rpc_url.clone())
+            .with_min_reserve(config.min_authority_reserve_lamports)
+            .with_alert_webhook(config.alert_webhook_url.clone())
+            .with_fanout(config.send_fanout.clone());
         
         Ok(Self {
             config,
@@ -61,6 +79,7 @@ impl Crank {
             deploy_authority,
             sender,
             db_pool,
+            deployers: Arc::new(RwLock::new(DeployerCache::new(Duration::from_secs(deployer_cache_ttl_secs)))),
         })
     }
     
@@ -101,6 +120,40 @@ impl Crank {
         }
     }
     
+    /// Send one self-transfer like `send_test_transaction`, timing each stage
+    /// `seq` goes into the CU limit so back-to-back runs under one blockhash don't share a signature
+    pub async fn benchmark_transaction(&self, seq: u32) -> Result<StageTimings, CrankError> {
+        let payer = &self.deploy_authority;
+        
+        let started = Instant::now();
+        let recent_blockhash = self.rpc_client
+            .get_latest_blockhash()
+            .map_err(|e| CrankError::Rpc(e.to_string()))?;
+        let instructions = vec![
+            ComputeBudgetInstruction::set_compute_unit_limit(5000 + seq),
+            ComputeBudgetInstruction::set_compute_unit_price(self.config.priority_fee),
+            system_instruction::transfer(&payer.pubkey(), &payer.pubkey(), 0),
+        ];
+        let mut tx = Transaction::new_with_payer(&instructions, Some(&payer.pubkey()));
+        let build = started.elapsed();
+        
+        let started = Instant::now();
+        tx.sign(&[payer], recent_blockhash);
+        let sign = started.elapsed();
+        
+        let started = Instant::now();
+        let signature = self.sender.send_rpc(&tx).await
+            .map_err(|e| CrankError::Send(e.to_string()))?;
+        let send = started.elapsed();
+        
+        let started = Instant::now();
+        self.sender.confirm_rpc(&tx, signature, 60).await
+            .map_err(|e| CrankError::Send(e.to_string()))?;
+        let confirm = started.elapsed();
+        
+        Ok(StageTimings { build, sign, send, confirm })
+    }
+    
     /// Send and confirm a transaction via standard RPC (for debugging)
     pub async fn send_and_confirm(&self, tx: &Transaction) -> Result<String, CrankError> {
         match self.sender.send_and_confirm_rpc(tx, 60).await {
@@ -115,85 +168,20 @@ impl Crank {
     }
     
     /// Find all deployer accounts where we are the deploy_authority
-    /// Uses optimized GPA with data size filter for efficient bulk fetching
+    /// Served from the shared deployer cache while it is fresh
     pub async fn find_deployers(&self) -> Result<Vec<DeployerInfo>, CrankError> {
-        let deploy_authority_pubkey = self.deploy_authority.pubkey();
-        
-        // Deployer size: 8 discriminator + 32 manager_key + 32 deploy_authority + 8 bps_fee + 8 flat_fee + 8 expected_bps_fee + 8 expected_flat_fee + 8 max_per_round = 112
-        const DEPLOYER_SIZE: u64 = 112;
-        
-        info!("Scanning for deployers with deploy_authority: {} (data_size={})", 
-            deploy_authority_pubkey, DEPLOYER_SIZE);
-        
-        // Use getProgramAccounts with optimized filters:
-        // 1. Data size filter - most efficient, filters on server side
-        // 2. Discriminator filter - ensures we get Deployer accounts
-        // 3. Deploy authority filter - only accounts we manage
-        let accounts = self.rpc_client.get_program_accounts_with_config(
-            &evore::id(),
-            solana_client::rpc_config::RpcProgramAccountsConfig {
-                filters: Some(vec![
-                    // Filter by data size first (most efficient filter)
-                    solana_client::rpc_filter::RpcFilterType::DataSize(DEPLOYER_SIZE),
-                    // Filter by account discriminator (Deployer = 101)
-                    solana_client::rpc_filter::RpcFilterType::Memcmp(
-                        solana_client::rpc_filter::Memcmp::new_base58_encoded(
-                            0,
-                            &[101, 0, 0, 0, 0, 0, 0, 0], // EvoreAccount::Deployer discriminator
-                        ),
-                    ),
-                    // Filter by deploy_authority (offset: 8 discriminator + 32 manager_key = 40)
-                    solana_client::rpc_filter::RpcFilterType::Memcmp(
-                        solana_client::rpc_filter::Memcmp::new_base58_encoded(
-                            40,
-                            deploy_authority_pubkey.as_ref(),
-                        ),
-                    ),
-                ]),
-                account_config: solana_client::rpc_config::RpcAccountInfoConfig {
-                    encoding: Some(solana_account_decoder::UiAccountEncoding::Base64),
-                    ..Default::default()
-                },
-                ..Default::default()
-            },
-        ).map_err(|e| CrankError::Rpc(e.to_string()))?;
-        
-        info!("GPA returned {} deployer accounts", accounts.len());
-        
-        let mut deployers = Vec::new();
-        
-        for (deployer_address, account) in accounts {
-            match Deployer::try_from_bytes(&account.data) {
-                Ok(deployer) => {
-                    let manager_address = deployer.manager_key;
-                    let fee_str = format!("{} bps + {} lamports flat", deployer.bps_fee, deployer.flat_fee);
-                    let expected_str = format!("expected: {} bps + {} lamports", deployer.expected_bps_fee, deployer.expected_flat_fee);
-
-                    deployers.push(DeployerInfo {
-                        deployer_address,
-                        manager_address,
-                        bps_fee: deployer.bps_fee,
-                        flat_fee: deployer.flat_fee,
-                        expected_bps_fee: deployer.expected_bps_fee,
-                        expected_flat_fee: deployer.expected_flat_fee,
-                        max_per_round: deployer.max_per_round,
-                    });
-                    
-                    debug!(
-                        "Found deployer: {} for manager: {} (fee: {}, {}, max_per_round: {})",
-                        deployer_address, manager_address, fee_str, expected_str, deployer.max_per_round
-                    );
-                }
-                Err(e) => {
-                    warn!(
-                        "Failed to parse deployer {}: {:?}",
-                        deployer_address, e
-                    );
-                }
-            }
-        }
-        
-        Ok(deployers)
+        discover_deployers(&self.deployers, &self.rpc_client, self.deploy_authority.pubkey()).await
+    }
+    
+    /// Drop the cached deployer scan and discover again
+    pub async fn refresh_deployers(&self) -> Result<Vec<DeployerInfo>, CrankError> {
+        self.deployers.write().await.invalidate();
+        self.find_deployers().await
+    }
+    
+    /// Deployer cache handle, shared with the pipeline
+    pub fn deployer_cache(&self) -> SharedDeployerCache {
+        self.deployers.clone()
     }
     
     /// Check all Evore program accounts
@@ -201,12 +189,16 @@ impl Crank {
         info!("Loading all accounts for Evore program {}...", evore::id());
         
         // Account sizes
-        const MANAGER_SIZE: usize = 40;     // 8 discriminator + 32 authority
+        const MANAGER_SIZE: usize = 8 + std::mem::size_of::<evore::state::Manager>();
+        const LEGACY_MANAGER_SIZE: usize = evore::state::LEGACY_MANAGER_SIZE; // needs MigrateManager
         const DEPLOYER_SIZE: usize = 112;   // 8 + 32 + 32 + 8 + 8 + 8 + 8 + 8 (with max_per_round)
+        const STRATEGY_DEPLOYER_SIZE: usize = 8 + std::mem::size_of::<StrategyDeployer>();
+        const LEGACY_STRATEGY_DEPLOYER_SIZE: usize = evore::state::LEGACY_STRATEGY_DEPLOYER_SIZE; // no max_loss_per_round yet
         
         // Discriminators
         const MANAGER_DISCRIMINATOR: u8 = 100;
         const DEPLOYER_DISCRIMINATOR: u8 = 101;
+        const STRATEGY_DEPLOYER_DISCRIMINATOR: u8 = 102;
         
         // Get all accounts owned by the Evore program
         let accounts = self.rpc_client.get_program_accounts(&evore::id())
@@ -215,7 +207,9 @@ impl Crank {
         info!("Found {} total accounts", accounts.len());
         
         let mut managers = Vec::new();
+        let mut legacy_managers = Vec::new();
         let mut deployers = Vec::new();
+        let mut strategy_deployers = Vec::new();
         let mut unknown = Vec::new();
         
         for (address, account) in &accounts {
@@ -229,9 +223,16 @@ impl Crank {
                 (d, s) if d == MANAGER_DISCRIMINATOR && s == MANAGER_SIZE => {
                     managers.push(*address);
                 }
+                (d, s) if d == MANAGER_DISCRIMINATOR && s == LEGACY_MANAGER_SIZE => {
+                    legacy_managers.push(*address);
+                }
                 (d, s) if d == DEPLOYER_DISCRIMINATOR && s == DEPLOYER_SIZE => {
                     deployers.push(*address);
                 }
+                (d, s) if d == STRATEGY_DEPLOYER_DISCRIMINATOR
+                    && (s == STRATEGY_DEPLOYER_SIZE || s == LEGACY_STRATEGY_DEPLOYER_SIZE) => {
+                    strategy_deployers.push(*address);
+                }
                 _ => {
                     unknown.push((*address, discriminator, size));
                 }
@@ -240,8 +241,23 @@ impl Crank {
         
         // Print summary
         info!("\n=== Evore Program Account Summary ===");
-        info!("Manager accounts (40 bytes): {}", managers.len());
+        info!("Manager accounts ({} bytes): {}", MANAGER_SIZE, managers.len());
+        info!("Legacy manager accounts (40 bytes, need MigrateManager): {}", legacy_managers.len());
         info!("Deployer accounts (112 bytes): {}", deployers.len());
+        info!("Strategy deployer accounts ({} bytes): {}", STRATEGY_DEPLOYER_SIZE, strategy_deployers.len());
+        
+        for addr in &legacy_managers {
+            warn!("  - legacy manager {}", addr);
+        }
+        
+        let dual = dual_deployer_managers(&accounts);
+        if !dual.is_empty() {
+            warn!("\n⚠ Found {} managers with both a Deployer and a StrategyDeployer:", dual.len());
+            warn!("  The crank only runs the legacy Deployer; the StrategyDeployer is left idle");
+            for manager in &dual {
+                warn!("  - manager {}", manager);
+            }
+        }
         
         if !unknown.is_empty() {
             warn!("\n⚠ Found {} unknown/unexpected accounts:", unknown.len());
@@ -250,7 +266,7 @@ impl Crank {
             }
         }
         
-        if unknown.is_empty() {
+        if unknown.is_empty() && legacy_managers.is_empty() && dual.is_empty() {
             info!("\n✓ All accounts are in expected format!");
         }
         
@@ -273,6 +289,32 @@ impl Crank {
         Ok((*board, current_slot))
     }
     
+    /// Get current ORE board state along with the slot the RPC served it at
+    /// Returns (board, context_slot, current_slot)
+    pub fn get_board_with_context(&self) -> Result<(Board, u64, u64), CrankError> {
+        let (board_address, _) = board_pda();
+        
+        let response = self.rpc_client
+            .get_account_with_commitment(&board_address, self.rpc_client.commitment())
+            .map_err(|e| CrankError::Rpc(e.to_string()))?;
+        let context_slot = response.context.slot;
+        let account = response.value
+            .ok_or_else(|| CrankError::Rpc(format!("Board account {} not found", board_address)))?;
+        
+        let board = Board::try_from_bytes(&account.data)
+            .map_err(|e| CrankError::Deserialize(format!("{:?}", e)))?;
+        
+        let current_slot = self.rpc_client.get_slot()
+            .map_err(|e| CrankError::Rpc(e.to_string()))?;
+        
+        Ok((*board, context_slot, current_slot))
+    }
+    
+    /// How many slots a read served at `context_slot` trails the cluster's `current_slot`
+    pub fn board_lag_slots(context_slot: u64, current_slot: u64) -> u64 {
+        current_slot.saturating_sub(context_slot)
+    }
+    
     /// Get current ORE round state
     pub fn get_round(&self, round_id: u64) -> Result<Round, CrankError> {
         let (round_address, _) = round_pda(round_id);
@@ -286,9 +328,33 @@ impl Crank {
         Ok(*round)
     }
     
+    /// Store a resolved round's per-square deployed amounts, miner counts and winner
+    /// Returns false (nothing recorded) while the round's slot hash isn't written yet
+    pub async fn record_round_squares(&self, round_id: u64) -> Result<bool, CrankError> {
+        let round = self.get_round(round_id)?;
+        let Some(rng) = round.rng() else {
+            return Ok(false);
+        };
+        let winning_square = round.winning_square(rng);
+        
+        for square in 0..25 {
+            db::record_square_outcome(
+                &self.db_pool,
+                round_id,
+                square as u8,
+                round.deployed[square],
+                round.count[square],
+                square == winning_square,
+            )
+            .await
+            .map_err(|e| CrankError::Database(e.to_string()))?;
+        }
+        Ok(true)
+    }
+    
     /// Get balance for a managed miner auth PDA
     pub fn get_miner_balance(&self, deployer: &DeployerInfo, auth_id: u64) -> Result<u64, CrankError> {
-        let (managed_miner_auth, _) = managed_miner_auth_pda(deployer.manager_address, auth_id);
+        let managed_miner_auth = get_miner_auth_pda(deployer.manager_address, auth_id);
         self.rpc_client.get_balance(&managed_miner_auth)
             .map_err(|e| CrankError::Rpc(e.to_string()))
     }
@@ -316,11 +382,11 @@ impl Crank {
         let protocol_fee = DEPLOY_FEE;
         
         // Check managed_miner_auth balance
-        let (managed_miner_auth, _) = managed_miner_auth_pda(deployer.manager_address, auth_id);
+        let MinerPdas { managed_miner_auth, ore_miner: ore_miner_address } =
+            pda_cache::miner_pdas(deployer.manager_address, auth_id);
         let current_auth_balance = self.rpc_client.get_balance(&managed_miner_auth).unwrap_or(0);
         
         // Check if ORE miner exists
-        let (ore_miner_address, _) = miner_pda(managed_miner_auth);
         let miner_exists = self.rpc_client.get_account(&ore_miner_address).is_ok();
         
         // Calculate miner rent if account doesn't exist
@@ -379,8 +445,7 @@ impl Crank {
     /// Get miner checkpoint status for a manager/auth_id
     /// Returns (checkpoint_id, last_played_round_id) or None if the miner account doesn't exist yet
     pub fn get_miner_checkpoint_status(&self, manager: Pubkey, auth_id: u64) -> Result<Option<(u64, u64)>, CrankError> {
-        let (managed_miner_auth, _) = managed_miner_auth_pda(manager, auth_id);
-        let (ore_miner_address, _) = miner_pda(managed_miner_auth);
+        let ore_miner_address = pda_cache::miner_pdas(manager, auth_id).ore_miner;
         
         match self.rpc_client.get_account(&ore_miner_address) {
             Ok(account) => {
@@ -399,6 +464,24 @@ impl Crank {
         }
     }
     
+    /// Get unclaimed miner rewards for a manager/auth_id
+    /// Returns (rewards_sol, rewards_ore) or None if the miner account doesn't exist yet
+    pub fn get_miner_rewards(&self, manager: Pubkey, auth_id: u64) -> Result<Option<(u64, u64)>, CrankError> {
+        let ore_miner_address = pda_cache::miner_pdas(manager, auth_id).ore_miner;
+        
+        let response = self.rpc_client
+            .get_account_with_commitment(&ore_miner_address, self.rpc_client.commitment())
+            .map_err(|e| CrankError::Rpc(e.to_string()))?;
+        match response.value {
+            Some(account) => {
+                let miner = Miner::try_from_bytes(&account.data)
+                    .map_err(|e| CrankError::Deserialize(format!("{:?}", e)))?;
+                Ok(Some((miner.rewards_sol, miner.rewards_ore)))
+            }
+            None => Ok(None),
+        }
+    }
+    
     /// Check if a deployer needs checkpointing
     pub fn needs_checkpoint(&self, deployer: &DeployerInfo, auth_id: u64) -> Result<Option<u64>, CrankError> {
         match self.get_miner_checkpoint_status(deployer.manager_address, auth_id)? {
@@ -415,7 +498,7 @@ impl Crank {
     
     /// Execute checkpoint and optionally recycle (no deploy)
     /// Use this when balance is too low to deploy but we still want to checkpoint/claim winnings
-    /// Only includes recycle if should_recycle is true (i.e., miner has SOL rewards to claim)
+    /// Only includes recycle if should_recycle is true (i.e., miner has SOL rewards the recycle policy wants claimed)
     pub async fn execute_checkpoint_recycle(
         &self,
         deployer: &DeployerInfo,
@@ -477,6 +560,140 @@ impl Crank {
         }
     }
     
+    /// Create the ORE miner (MMCreateMiner) for a manager that doesn't have one yet
+    /// MMCreateMiner must be signed by the manager authority, so managers owned by anyone
+    /// other than the deploy authority return Ok(None) and are left to their owner
+    pub async fn create_managed_miner(
+        &self,
+        deployer: &DeployerInfo,
+        auth_id: u64,
+    ) -> Result<Option<String>, CrankError> {
+        let payer = &self.deploy_authority;
+        
+        let account = self.rpc_client
+            .get_account(&deployer.manager_address)
+            .map_err(|e| CrankError::Rpc(e.to_string()))?;
+        let manager = Manager::try_from_bytes(&account.data)
+            .map_err(|e| CrankError::Deserialize(format!("{:?}", e)))?;
+        if manager.authority != payer.pubkey() {
+            return Ok(None);
+        }
+        
+        info!("Creating ORE miner for manager {} auth_id {}", deployer.manager_address, auth_id);
+        
+        let (recent_blockhash, _) = self.rpc_client
+            .get_latest_blockhash_with_commitment(CommitmentConfig::confirmed())
+            .map_err(|e| CrankError::Rpc(e.to_string()))?;
+        
+        let instructions = vec![
+            ComputeBudgetInstruction::set_compute_unit_limit(150_000),
+            ComputeBudgetInstruction::set_compute_unit_price(self.config.priority_fee),
+            mm_create_miner(payer.pubkey(), deployer.manager_address, auth_id),
+        ];
+        
+        let mut tx = Transaction::new_with_payer(&instructions, Some(&payer.pubkey()));
+        tx.sign(&[payer], recent_blockhash);
+        
+        match self.sender.send_and_confirm_rpc(&tx, 60).await {
+            Ok(sig) => Ok(Some(sig.to_string())),
+            Err(e) => Err(CrankError::Send(e.to_string())),
+        }
+    }
+    
+    /// Create a manager owned by our deploy authority along with its deployer, in one transaction
+    ///
+    /// The deployer's deploy_authority is also us, so the new manager is immediately crankable.
+    pub async fn create_manager_and_deployer(
+        &self,
+        manager: &Keypair,
+        bps_fee: u64,
+        flat_fee: u64,
+        max_per_round: u64,
+    ) -> Result<String, CrankError> {
+        let payer = &self.deploy_authority;
+        
+        let (recent_blockhash, _) = self.rpc_client
+            .get_latest_blockhash_with_commitment(CommitmentConfig::confirmed())
+            .map_err(|e| CrankError::Rpc(e.to_string()))?;
+        
+        let mut instructions = vec![
+            ComputeBudgetInstruction::set_compute_unit_limit(100_000),
+            ComputeBudgetInstruction::set_compute_unit_price(self.config.priority_fee),
+        ];
+        instructions.extend(create_manager_and_deployer_ixs(
+            payer.pubkey(),
+            manager.pubkey(),
+            bps_fee,
+            flat_fee,
+            max_per_round,
+        ));
+        
+        let mut tx = Transaction::new_with_payer(&instructions, Some(&payer.pubkey()));
+        tx.sign(&[payer, manager], recent_blockhash);
+        
+        match self.sender.send_and_confirm_rpc(&tx, 60).await {
+            Ok(sig) => Ok(sig.to_string()),
+            Err(e) => Err(CrankError::Send(e.to_string())),
+        }
+    }
+    
+    /// Managers among `deployers` whose authority is our deploy authority
+    ///
+    /// DepositAutodeployBalance must be signed by the manager authority, so these are the
+    /// only managers the crank can fund
+    pub fn owned_managers(&self, deployers: &[DeployerInfo]) -> Result<Vec<Pubkey>, CrankError> {
+        let authority = self.deploy_authority.pubkey();
+        let addresses: Vec<Pubkey> = deployers.iter().map(|d| d.manager_address).collect();
+        let mut owned = Vec::new();
+        for chunk in addresses.chunks(100) {
+            let accounts = self.rpc_client
+                .get_multiple_accounts(chunk)
+                .map_err(|e| CrankError::Rpc(format!("Failed to fetch managers: {}", e)))?;
+            for (address, account) in chunk.iter().zip(accounts.iter()) {
+                let is_owned = account
+                    .as_ref()
+                    .and_then(|a| Manager::try_from_bytes(&a.data).ok())
+                    .is_some_and(|m| m.authority == authority);
+                if is_owned {
+                    owned.push(*address);
+                }
+            }
+        }
+        Ok(owned)
+    }
+    
+    /// Deposit each `(manager, lamports)` into its managed_miner_auth from the deploy authority,
+    /// TOP_UP_BATCH_SIZE deposits per transaction
+    ///
+    /// Stops at the first batch that fails. Returns the signatures of the batches that landed.
+    pub async fn execute_top_ups(&self, top_ups: &[(Pubkey, u64)], auth_id: u64) -> Result<Vec<String>, CrankError> {
+        let payer = &self.deploy_authority;
+        let mut signatures = Vec::new();
+        
+        for batch in top_ups.chunks(TOP_UP_BATCH_SIZE) {
+            let (recent_blockhash, _) = self.rpc_client
+                .get_latest_blockhash_with_commitment(CommitmentConfig::confirmed())
+                .map_err(|e| CrankError::Rpc(e.to_string()))?;
+            
+            let mut instructions = vec![
+                ComputeBudgetInstruction::set_compute_unit_limit(10_000 * batch.len() as u32),
+                ComputeBudgetInstruction::set_compute_unit_price(self.config.priority_fee),
+            ];
+            instructions.extend(batch.iter().map(|(manager, lamports)| {
+                evore::instruction::deposit_autodeploy_balance(payer.pubkey(), *manager, auth_id, *lamports)
+            }));
+            
+            let mut tx = Transaction::new_with_payer(&instructions, Some(&payer.pubkey()));
+            tx.sign(&[payer], recent_blockhash);
+            
+            let sig = self.sender.send_and_confirm_rpc(&tx, 60).await
+                .map_err(|e| CrankError::Send(e.to_string()))?;
+            signatures.push(sig.to_string());
+        }
+        
+        Ok(signatures)
+    }
+    
     /// Execute batched checkpoint+recycle for multiple deployers
     pub async fn execute_batched_checkpoint_recycle(
         &self,
@@ -562,6 +779,87 @@ impl Crank {
         }
     }
     
+    /// Simulate a single autodeploy and report the exact per-square lamports it would land
+    ///
+    /// Runs the same full autodeploy transaction the crank would send through `simulateTransaction`,
+    /// asking the RPC for the post-simulation ORE miner account. The returned array is the
+    /// `Miner.deployed` delta against the current on-chain miner, i.e. the 25-square plan the
+    /// program actually applied after fees, deploy caps and board mutation.
+    pub fn simulate_autodeploy_landed(
+        &self,
+        deployer: &DeployerInfo,
+        auth_id: u64,
+        round_id: u64,
+        amount: u64,
+        squares_mask: u32,
+    ) -> Result<[u64; 25], CrankError> {
+        let payer = &self.deploy_authority;
+        let ore_miner_address = pda_cache::miner_pdas(deployer.manager_address, auth_id).ore_miner;
+        
+        // Pre-state: a missing miner account means nothing has been deployed yet
+        let pre_miner = match self.rpc_client.get_account(&ore_miner_address) {
+            Ok(account) => Some(
+                *Miner::try_from_bytes(&account.data)
+                    .map_err(|e| CrankError::Deserialize(format!("{:?}", e)))?,
+            ),
+            Err(e) if e.to_string().contains("AccountNotFound") => None,
+            Err(e) => return Err(CrankError::Rpc(e.to_string())),
+        };
+        
+        let checkpoint_round = self.needs_checkpoint(deployer, auth_id)?.unwrap_or(round_id);
+        
+        let instructions = vec![
+            ComputeBudgetInstruction::set_compute_unit_limit(1_400_000),
+            ComputeBudgetInstruction::set_compute_unit_price(self.config.priority_fee),
+            mm_full_autodeploy(
+                payer.pubkey(),
+                deployer.manager_address,
+                auth_id,
+                round_id,
+                checkpoint_round,
+                amount,
+                squares_mask,
+            ),
+        ];
+        
+        let (recent_blockhash, _) = self.rpc_client
+            .get_latest_blockhash_with_commitment(CommitmentConfig::confirmed())
+            .map_err(|e| CrankError::Rpc(e.to_string()))?;
+        let mut tx = Transaction::new_with_payer(&instructions, Some(&payer.pubkey()));
+        tx.sign(&[payer], recent_blockhash);
+        
+        let sim_config = RpcSimulateTransactionConfig {
+            sig_verify: false,
+            replace_recent_blockhash: true,
+            commitment: Some(CommitmentConfig::confirmed()),
+            accounts: Some(RpcSimulateTransactionAccountsConfig {
+                encoding: Some(UiAccountEncoding::Base64),
+                addresses: vec![ore_miner_address.to_string()],
+            }),
+            ..RpcSimulateTransactionConfig::default()
+        };
+        
+        let result = self.rpc_client
+            .simulate_transaction_with_config(&tx, sim_config)
+            .map_err(|e| CrankError::Rpc(e.to_string()))?
+            .value;
+        
+        let landed = landed_from_simulation(result, pre_miner.as_ref(), round_id)?;
+        
+        info!(
+            "Simulated deploy for manager {} round {}: {} lamports landed across {} squares",
+            deployer.manager_address,
+            round_id,
+            landed.iter().sum::<u64>(),
+            landed.iter().filter(|&&l| l > 0).count(),
+        );
+        for (square, lamports) in landed.iter().enumerate().filter(|(_, &l)| l > 0) {
+            debug!("  square {:>2}: {} lamports", square, lamports);
+        }
+        
+        Ok(landed)
+    }
+    
     /// Execute batched autodeploys WITHOUT checkpoint (checkpoint done separately)
     pub async fn execute_batched_autodeploys_no_checkpoint(
         &self,
@@ -609,11 +907,16 @@ impl Crank {
     /// Each autodeploy uses ~60k CU, so we can fit ~10 in one tx
     pub async fn execute_batched_autodeploys(
         &self,
-        deploys: Vec<(&DeployerInfo, u64, u64, u64, u32, Option<u64>)>, // (deployer, auth_id, round_id, amount, mask, checkpoint_round)
+        deploys: Vec<DeployPlan>,
     ) -> Result<String, CrankError> {
         if deploys.is_empty() {
             return Err(CrankError::Send("No deploys to batch".to_string()));
         }
+
+        let (deploys, duplicates) = dedup_by_miner_auth(deploys, DeployPlan::miner_auth);
+        if duplicates > 0 {
+            warn!("Dropped {} duplicate miner(s) from deploy batch", duplicates);
+        }
         
         let payer = &self.deploy_authority;
         
@@ -624,42 +927,17 @@ impl Crank {
         
         let mut instructions = Vec::new();
         
-        // Calculate CU needed: ~60k per deploy, ~100k for checkpoint+recycle if needed
-        let has_checkpoint = deploys.iter().any(|(_, _, _, _, _, cp)| cp.is_some());
-        let cu_per_deploy = 70_000u32; // ~60k actual + buffer
-        let checkpoint_cu = if has_checkpoint { 150_000u32 } else { 0 };
-        let total_cu = checkpoint_cu + (deploys.len() as u32 * cu_per_deploy) + 50_000; // +50k buffer
-        
-        instructions.push(ComputeBudgetInstruction::set_compute_unit_limit(total_cu.min(1_400_000)));
+        instructions.push(ComputeBudgetInstruction::set_compute_unit_limit(full_autodeploy_cu_limit(&deploys)));
         instructions.push(ComputeBudgetInstruction::set_compute_unit_price(self.config.priority_fee));
         
         // Add checkpoint + recycle for each deployer that needs it, then all deploys
-        for (deployer, auth_id, _, _, _, checkpoint_round) in &deploys {
-            if let Some(round_to_checkpoint) = checkpoint_round {
-                instructions.push(mm_autocheckpoint(
-                    payer.pubkey(),
-                    deployer.manager_address,
-                    *round_to_checkpoint,
-                    *auth_id,
-                ));
-                instructions.push(recycle_sol(
-                    payer.pubkey(),
-                    deployer.manager_address,
-                    *auth_id,
-                ));
-            }
+        for plan in &deploys {
+            instructions.extend(plan.settle_ixs(payer.pubkey()));
         }
         
         // Add all deploy instructions
-        for (deployer, auth_id, round_id, amount, squares_mask, _) in &deploys {
-            instructions.push(mm_autodeploy(
-                payer.pubkey(),
-                deployer.manager_address,
-                *auth_id,
-                *round_id,
-                *amount,
-                *squares_mask,
-            ));
+        for plan in &deploys {
+            instructions.push(plan.autodeploy_ix(payer.pubkey()));
         }
         
         let mut tx = Transaction::new_with_payer(&instructions, Some(&payer.pubkey()));
@@ -673,24 +951,19 @@ impl Crank {
             .unwrap()
             .as_secs() as i64;
         
-        for (deployer, auth_id, round_id, amount, squares_mask, _) in &deploys {
-            let num_squares = squares_mask.count_ones();
-            let total_deployed = amount * num_squares as u64;
-            let bps_fee_amount = total_deployed * deployer.bps_fee / 10_000;
-            let deployer_fee = bps_fee_amount + deployer.flat_fee;
-            
+        for plan in &deploys {
             db::insert_tx(
                 &self.db_pool,
                 &signature,
-                &deployer.manager_address.to_string(),
-                &deployer.deployer_address.to_string(),
-                *auth_id,
-                *round_id,
-                *amount,
-                *squares_mask,
-                num_squares,
-                total_deployed,
-                deployer_fee,
+                &plan.deployer.manager_address.to_string(),
+                &plan.deployer.deployer_address.to_string(),
+                plan.auth_id,
+                plan.round_id,
+                plan.amount,
+                plan.squares_mask,
+                plan.num_squares(),
+                plan.total_deployed(),
+                plan.deployer_fee(),
                 DEPLOY_FEE,
                 self.config.priority_fee,
                 0, // No Jito tip
@@ -699,6 +972,8 @@ impl Crank {
             ).await.ok(); // Ignore duplicate key errors for batched txs
         }
         
+        self.record_tx_message(&signature, &VersionedMessage::Legacy(tx.message.clone())).await;
+        
         match self.sender.send_and_confirm_rpc(&tx, 60).await {
             Ok(sig) => {
                 info!("✓ Batched autodeploy ({} deploys) confirmed: {}", deploys.len(), sig);
@@ -706,7 +981,7 @@ impl Crank {
             }
             Err(e) => {
                 error!("✗ Batched autodeploy failed: {}", e);
-                for (_, _, _, _, _, _) in &deploys {
+                for _ in &deploys {
                     db::update_tx_failed(&self.db_pool, &signature, &e.to_string())
                         .await
                         .ok();
@@ -788,6 +1063,8 @@ impl Crank {
             now,
         ).await.map_err(|e| CrankError::Database(e.to_string()))?;
         
+        self.record_tx_message(&signature, &VersionedMessage::Legacy(tx.message.clone())).await;
+        
         // Send and confirm transaction
         match self.sender.send_and_confirm_rpc(&tx, 60).await {
             Ok(sig) => {
@@ -886,6 +1163,8 @@ impl Crank {
             .unwrap()
             .as_secs() as i64;
         
+        let pending_txs = gc_dropped_txs(&self.db_pool, &self.rpc_client, pending_txs, current_blockheight, now).await;
+        
         for tx in pending_txs {
             let signature = solana_sdk::signature::Signature::from_str(&tx.signature)
                 .map_err(|e| CrankError::Parse(e.to_string()))?;
@@ -951,11 +1230,104 @@ impl Crank {
         Ok(())
     }
     
+    /// Store the message of a sent transaction so it can be replayed later
+    /// Failures are logged and ignored - replay is best-effort and must not block sending
+    async fn record_tx_message(&self, signature: &str, message: &VersionedMessage) {
+        let bytes = match bincode::serialize(message) {
+            Ok(bytes) => bytes,
+            Err(e) => {
+                warn!("Failed to serialize message for {}: {}", signature, e);
+                return;
+            }
+        };
+        if let Err(e) = db::insert_tx_message(&self.db_pool, signature, &bytes).await {
+            warn!("Failed to store message for {}: {}", signature, e);
+        }
+    }
+    
+    /// Re-send a failed or expired transaction from the database with a fresh blockhash
+    ///
+    /// The stored message is re-signed by the deploy authority and recorded as a new
+    /// pending transaction. Returns the new signature.
+    pub async fn replay_tx(&self, signature: &str) -> Result<String, CrankError> {
+        let record = db::get_tx_by_signature(&self.db_pool, signature)
+            .await
+            .map_err(|e| CrankError::Database(e.to_string()))?
+            .ok_or_else(|| CrankError::Database(format!("No transaction found for {}", signature)))?;
+        
+        let status = db::TxStatus::from_i32(record.status);
+        if !matches!(status, db::TxStatus::Failed | db::TxStatus::Expired) {
+            return Err(CrankError::Send(format!(
+                "Transaction {} is {}, only failed or expired transactions can be replayed",
+                signature, status.as_str()
+            )));
+        }
+        
+        let bytes = db::get_tx_message(&self.db_pool, signature)
+            .await
+            .map_err(|e| CrankError::Database(e.to_string()))?
+            .ok_or_else(|| CrankError::Database(format!("No stored instructions for {}", signature)))?;
+        
+        let (recent_blockhash, last_valid_blockheight) = self.rpc_client
+            .get_latest_blockhash_with_commitment(CommitmentConfig::confirmed())
+            .map_err(|e| CrankError::Rpc(e.to_string()))?;
+        let tx = rebuild_replay_tx(&bytes, &self.deploy_authority, recent_blockhash, signature)?;
+        let new_signature = tx.signatures[0].to_string();
+        
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_secs() as i64;
+        
+        db::insert_tx(
+            &self.db_pool,
+            &new_signature,
+            &record.manager_key,
+            &record.deployer_key,
+            record.auth_id as u64,
+            record.round_id as u64,
+            record.amount_per_square as u64,
+            record.squares_mask as u32,
+            record.num_squares as u32,
+            record.total_deployed as u64,
+            record.deployer_fee as u64,
+            record.protocol_fee as u64,
+            record.priority_fee as u64,
+            record.jito_tip as u64,
+            last_valid_blockheight,
+            now,
+        ).await.map_err(|e| CrankError::Database(e.to_string()))?;
+        
+        self.record_tx_message(&new_signature, &tx.message).await;
+        
+        info!("Replaying {} as {}", signature, new_signature);
+        
+        match self.sender.send_and_confirm_versioned_rpc(&tx, 60).await {
+            Ok(sig) => Ok(sig.to_string()),
+            Err(e) => {
+                db::update_tx_failed(&self.db_pool, &new_signature, &e.to_string())
+                    .await
+                    .ok();
+                Err(CrankError::Send(e.to_string()))
+            }
+        }
+    }
+    
+    /// When checkpoints should also recycle claimable SOL (AUTO_RECYCLE)
+    pub fn recycle_policy(&self) -> RecyclePolicy {
+        self.config.recycle_policy()
+    }
+    
     /// Get the deploy authority public key
     pub fn deploy_authority_pubkey(&self) -> Pubkey {
         self.deploy_authority.pubkey()
     }
     
+    /// Re-read the deploy authority balance the sender's reserve guard checks against
+    pub async fn refresh_authority_balance(&self) {
+        self.sender.refresh_payer_balance(&self.deploy_authority.pubkey()).await;
+    }
+    
     /// Update expected fees for a deployer (as deploy_authority)
     /// This allows the deploy_authority to protect itself from fee changes by the manager
     /// Returns Ok(None) if the expected fees are already set correctly (no tx needed)
@@ -1299,26 +1671,29 @@ impl Crank {
         Ok(lut_address)
     }
 
-    /// Ensure all deployers have miner LUTs
-    /// Returns count of new LUTs created
+    /// Ensure deployers have miner LUTs, creating at most `max_creations` (0 = no cap)
+    /// Deployers are handled in the order given, so callers put the most urgent first;
+    /// the rest are left for the next call. Returns count of new LUTs created
     pub async fn ensure_all_miner_luts(
         &self,
         registry: &mut LutRegistry,
-        deployers: &[DeployerInfo],
+        deployers: &[&DeployerInfo],
         auth_id: u64,
+        max_creations: usize,
     ) -> Result<usize, CrankError> {
-        let mut created = 0;
-
-        for deployer in deployers {
-            let miner_auth = get_miner_auth_pda(deployer.manager_address, auth_id);
+        let (to_create, missing) = capped_lut_creations(registry, deployers, auth_id, max_creations);
+        if to_create.len() < missing {
+            info!(
+                "Creating {} of {} missing miner LUTs this round (cap {}), the rest wait for later rounds",
+                to_create.len(), missing, max_creations
+            );
+        }
 
-            if !registry.has_miner_lut(&miner_auth) {
-                self.ensure_miner_lut(registry, deployer, auth_id).await?;
-                created += 1;
-            }
+        for deployer in &to_create {
+            self.ensure_miner_lut(registry, deployer, auth_id).await?;
         }
 
-        Ok(created)
+        Ok(to_create.len())
     }
 
     /// Execute batched autodeploys using LutRegistry (multiple LUTs)
@@ -1326,12 +1701,19 @@ impl Crank {
     pub async fn execute_batched_autodeploys_multi_lut(
         &self,
         registry: &LutRegistry,
-        deploys: Vec<(&DeployerInfo, u64, u64, u64, u32, Option<u64>)>, // (deployer, auth_id, round_id, amount, mask, checkpoint_round)
+        deploys: Vec<DeployPlan>,
+        priority_fee: Option<u64>, // overrides the configured priority fee
     ) -> Result<String, CrankError> {
+        let priority_fee = priority_fee.unwrap_or(self.config.priority_fee);
         if deploys.is_empty() {
             return Err(CrankError::Send("No deploys to batch".to_string()));
         }
 
+        let (deploys, duplicates) = dedup_by_miner_auth(deploys, DeployPlan::miner_auth);
+        if duplicates > 0 {
+            warn!("Dropped {} duplicate miner(s) from deploy batch", duplicates);
+        }
+
         let payer = &self.deploy_authority;
 
         let (recent_blockhash, last_valid_blockheight) = self.rpc_client
@@ -1339,37 +1721,27 @@ impl Crank {
             .map_err(|e| CrankError::Rpc(e.to_string()))?;
 
         // Collect miner_auths for LUT lookup
-        let miner_auths: Vec<Pubkey> = deploys.iter()
-            .map(|(d, auth_id, _, _, _, _)| get_miner_auth_pda(d.manager_address, *auth_id))
-            .collect();
+        let miner_auths: Vec<Pubkey> = deploys.iter().map(DeployPlan::miner_auth).collect();
+
+        // Every miner needs a LUT, otherwise the batch won't fit and should go out without LUTs
+        require_miner_luts(registry, &miner_auths)?;
 
         // Get all relevant LUTs
         let lut_accounts = registry.get_luts_for_miners(&miner_auths);
 
         let mut instructions = Vec::new();
 
-        instructions.push(ComputeBudgetInstruction::set_compute_unit_limit(1_400_000));
-        instructions.push(ComputeBudgetInstruction::set_compute_unit_price(self.config.priority_fee));
+        instructions.push(ComputeBudgetInstruction::set_compute_unit_limit(full_autodeploy_cu_limit(&deploys)));
+        instructions.push(ComputeBudgetInstruction::set_compute_unit_price(priority_fee));
 
         // Add mm_full_autodeploy instructions for each deploy
-        for (deployer, auth_id, round_id, amount, squares_mask, checkpoint_round) in &deploys {
-            // checkpoint_round_id: if checkpoint needed, use that round; otherwise use current round
-            let checkpoint_round_id = checkpoint_round.unwrap_or(*round_id);
-            
-            instructions.push(mm_full_autodeploy(
-                payer.pubkey(),
-                deployer.manager_address,
-                *auth_id,
-                *round_id,
-                checkpoint_round_id,
-                *amount,
-                *squares_mask,
-            ));
+        for plan in &deploys {
+            instructions.push(plan.full_autodeploy_ix(payer.pubkey()));
         }
         
         // Build versioned transaction with multiple LUTs
         let tx = registry.build_versioned_tx(payer, instructions, lut_accounts, recent_blockhash)
-            .map_err(|e| CrankError::Send(e.to_string()))?;
+            .map_err(|e| CrankError::Lut(e.to_string()))?;
         
         // Log transaction size and account count
         let tx_bytes = bincode::serialize(&tx).unwrap_or_default();
@@ -1390,32 +1762,29 @@ impl Crank {
             .unwrap()
             .as_secs() as i64;
         
-        for (deployer, auth_id, round_id, amount, squares_mask, _) in &deploys {
-            let num_squares = squares_mask.count_ones();
-            let total_deployed = amount * num_squares as u64;
-            let bps_fee_amount = total_deployed * deployer.bps_fee / 10_000;
-            let deployer_fee = bps_fee_amount + deployer.flat_fee;
-            
+        for plan in &deploys {
             db::insert_tx(
                 &self.db_pool,
                 &signature,
-                &deployer.manager_address.to_string(),
-                &deployer.deployer_address.to_string(),
-                *auth_id,
-                *round_id,
-                *amount,
-                *squares_mask,
-                num_squares,
-                total_deployed,
-                deployer_fee,
+                &plan.deployer.manager_address.to_string(),
+                &plan.deployer.deployer_address.to_string(),
+                plan.auth_id,
+                plan.round_id,
+                plan.amount,
+                plan.squares_mask,
+                plan.num_squares(),
+                plan.total_deployed(),
+                plan.deployer_fee(),
                 DEPLOY_FEE,
-                self.config.priority_fee,
+                priority_fee,
                 0, // No Jito tip
                 last_valid_blockheight,
                 now,
             ).await.ok();
         }
         
+        self.record_tx_message(&signature, &tx.message).await;
+        
         // Send transaction
         match self.sender.send_and_confirm_versioned_rpc(&tx, 60).await {
             Ok(sig) => {
@@ -1430,7 +1799,11 @@ impl Crank {
                         .await
                         .ok();
                 }
-                Err(CrankError::Send(e.to_string()))
+                if is_lut_failure(&e.to_string()) {
+                    Err(CrankError::Lut(e.to_string()))
+                } else {
+                    Err(CrankError::Send(e.to_string()))
+                }
             }
         }
     }
@@ -1488,7 +1861,7 @@ impl Crank {
     pub async fn execute_batched_autodeploys_versioned(
         &self,
         lut_manager: &LutManager,
-        deploys: Vec<(&DeployerInfo, u64, u64, u64, u32, Option<u64>)>, // (deployer, auth_id, round_id, amount, mask, checkpoint_round)
+        deploys: Vec<DeployPlan>,
     ) -> Result<String, CrankError> {
         if deploys.is_empty() {
             return Err(CrankError::Send("No deploys to batch".to_string()));
@@ -1502,36 +1875,17 @@ impl Crank {
         
         let mut instructions = Vec::new();
         
-        instructions.push(ComputeBudgetInstruction::set_compute_unit_limit(1_400_000));
+        instructions.push(ComputeBudgetInstruction::set_compute_unit_limit(full_autodeploy_cu_limit(&deploys)));
         instructions.push(ComputeBudgetInstruction::set_compute_unit_price(self.config.priority_fee));
         
         // Add checkpoint + recycle instructions for deployers that need it
-        for (deployer, auth_id, _, _, _, checkpoint_round) in &deploys {
-            if let Some(cp_round) = checkpoint_round {
-                instructions.push(mm_autocheckpoint(
-                    payer.pubkey(),
-                    deployer.manager_address,
-                    *cp_round,
-                    *auth_id,
-                ));
-                instructions.push(recycle_sol(
-                    payer.pubkey(),
-                    deployer.manager_address,
-                    *auth_id,
-                ));
-            }
+        for plan in &deploys {
+            instructions.extend(plan.settle_ixs(payer.pubkey()));
         }
         
         // Add all deploy instructions (mm_autodeploy with LUT compression)
-        for (deployer, auth_id, round_id, amount, squares_mask, _) in &deploys {
-            instructions.push(mm_autodeploy(
-                payer.pubkey(),
-                deployer.manager_address,
-                *auth_id,
-                *round_id,
-                *amount,
-                *squares_mask,
-            ));
+        for plan in &deploys {
+            instructions.push(plan.autodeploy_ix(payer.pubkey()));
         }
         
         // Build versioned transaction with LUT
@@ -1546,24 +1900,19 @@ impl Crank {
             .unwrap()
             .as_secs() as i64;
         
-        for (deployer, auth_id, round_id, amount, squares_mask, _) in &deploys {
-            let num_squares = squares_mask.count_ones();
-            let total_deployed = amount * num_squares as u64;
-            let bps_fee_amount = total_deployed * deployer.bps_fee / 10_000;
-            let deployer_fee = bps_fee_amount + deployer.flat_fee;
-            
+        for plan in &deploys {
             db::insert_tx(
                 &self.db_pool,
                 &signature,
-                &deployer.manager_address.to_string(),
-                &deployer.deployer_address.to_string(),
-                *auth_id,
-                *round_id,
-                *amount,
-                *squares_mask,
-                num_squares,
-                total_deployed,
-                deployer_fee,
+                &plan.deployer.manager_address.to_string(),
+                &plan.deployer.deployer_address.to_string(),
+                plan.auth_id,
+                plan.round_id,
+                plan.amount,
+                plan.squares_mask,
+                plan.num_squares(),
+                plan.total_deployed(),
+                plan.deployer_fee(),
                 DEPLOY_FEE,
                 self.config.priority_fee,
                 0, // No Jito tip
@@ -1572,6 +1921,8 @@ impl Crank {
             ).await.ok();
         }
         
+        self.record_tx_message(&signature, &tx.message).await;
+        
         // Send versioned transaction
         match self.sender.send_and_confirm_versioned_rpc(&tx, 60).await {
             Ok(sig) => {
@@ -1591,8 +1942,433 @@ impl Crank {
     }
 }
 
+/// Scan for all deployer accounts where `deploy_authority_pubkey` is the deploy_authority
+/// Uses optimized GPA with data size filter for efficient bulk fetching
+pub fn scan_deployers(rpc_client: &dyn RpcProvider, deploy_authority_pubkey: Pubkey) -> Result<Vec<DeployerInfo>, CrankError> {
+    // Deployer size: 8 discriminator + 32 manager_key + 32 deploy_authority + 8 bps_fee + 8 flat_fee + 8 expected_bps_fee + 8 expected_flat_fee + 8 max_per_round = 112
+    const DEPLOYER_SIZE: u64 = 112;
+    
+    info!("Scanning for deployers with deploy_authority: {} (data_size={})", 
+        deploy_authority_pubkey, DEPLOYER_SIZE);
+    
+    // Use getProgramAccounts with optimized filters:
+    // 1. Data size filter - most efficient, filters on server side
+    // 2. Discriminator filter - ensures we get Deployer accounts
+    // 3. Deploy authority filter - only accounts we manage
+    let accounts = rpc_client.get_program_accounts(
+        &evore::id(),
+        solana_client::rpc_config::RpcProgramAccountsConfig {
+            filters: Some(vec![
+                // Filter by data size first (most efficient filter)
+                solana_client::rpc_filter::RpcFilterType::DataSize(DEPLOYER_SIZE),
+                // Filter by account discriminator (Deployer = 101)
+                solana_client::rpc_filter::RpcFilterType::Memcmp(
+                    solana_client::rpc_filter::Memcmp::new_base58_encoded(
+                        0,
+                        &[101, 0, 0, 0, 0, 0, 0, 0], // EvoreAccount::Deployer discriminator
+                    ),
+                ),
+                // Filter by deploy_authority (offset: 8 discriminator + 32 manager_key = 40)
+                solana_client::rpc_filter::RpcFilterType::Memcmp(
+                    solana_client::rpc_filter::Memcmp::new_base58_encoded(
+                        40,
+                        deploy_authority_pubkey.as_ref(),
+                    ),
+                ),
+            ]),
+            account_config: solana_client::rpc_config::RpcAccountInfoConfig {
+                encoding: Some(solana_account_decoder::UiAccountEncoding::Base64),
+                ..Default::default()
+            },
+            ..Default::default()
+        },
+    )?;
+    
+    info!("GPA returned {} deployer accounts", accounts.len());
+    
+    let mut deployers = Vec::new();
+    
+    for (deployer_address, account) in accounts {
+        match Deployer::try_from_bytes(&account.data) {
+            Ok(deployer) => {
+                let manager_address = deployer.manager_key;
+                let fee_str = format!("{} bps + {} lamports flat", deployer.bps_fee, deployer.flat_fee);
+                let expected_str = format!("expected: {} bps + {} lamports", deployer.expected_bps_fee, deployer.expected_flat_fee);
+
+                deployers.push(DeployerInfo {
+                    deployer_address,
+                    manager_address,
+                    bps_fee: deployer.bps_fee,
+                    flat_fee: deployer.flat_fee,
+                    expected_bps_fee: deployer.expected_bps_fee,
+                    expected_flat_fee: deployer.expected_flat_fee,
+                    max_per_round: deployer.max_per_round,
+                });
+                
+                debug!(
+                    "Found deployer: {} for manager: {} (fee: {}, {}, max_per_round: {})",
+                    deployer_address, manager_address, fee_str, expected_str, deployer.max_per_round
+                );
+            }
+            Err(e) => {
+                warn!(
+                    "Failed to parse deployer {}: {:?}",
+                    deployer_address, e
+                );
+            }
+        }
+    }
+    
+    Ok(deployers)
+}
+
+/// Deployers from the shared cache, scanning via RPC when it is empty or past its TTL
+pub async fn discover_deployers(
+    cache: &SharedDeployerCache,
+    rpc_client: &dyn RpcProvider,
+    deploy_authority_pubkey: Pubkey,
+) -> Result<Vec<DeployerInfo>, CrankError> {
+    if let Some(deployers) = cache.read().await.get() {
+        debug!("Using {} cached deployers", deployers.len());
+        return Ok(deployers);
+    }
+    
+    let deployers = scan_deployers(rpc_client, deploy_authority_pubkey)?;
+    cache.write().await.store(deployers.clone());
+    Ok(deployers)
+}
+
+/// Pending rows older than this (seconds) are candidates for pending GC
+pub const PENDING_TX_MAX_AGE_SECS: i64 = 600;
+
+/// Max signatures per getSignatureStatuses request
+const MAX_SIGNATURE_STATUSES: usize = 256;
+
+/// Pending row past `PENDING_TX_MAX_AGE_SECS` whose blockhash can no longer land
+pub fn is_stale_pending(tx: &db::AutodeployTx, current_blockheight: u64, now: i64) -> bool {
+    now.saturating_sub(tx.sent_at) > PENDING_TX_MAX_AGE_SECS
+        && current_blockheight > tx.last_valid_blockheight as u64
+}
+
+/// Remove pending rows the cluster has no record of, sent more than
+/// `PENDING_TX_MAX_AGE_SECS` ago with an expired blockhash. These were dropped
+/// and can never land, so keeping them only grows the pending-tx table.
+/// Returns the rows that are still worth checking.
+pub async fn gc_dropped_txs(
+    db_pool: &Pool<Sqlite>,
+    rpc_client: &RpcClient,
+    pending_txs: Vec<db::AutodeployTx>,
+    current_blockheight: u64,
+    now: i64,
+) -> Vec<db::AutodeployTx> {
+    let (stale, mut keep): (Vec<_>, Vec<_>) = pending_txs
+        .into_iter()
+        .partition(|tx| is_stale_pending(tx, current_blockheight, now));
+    if stale.is_empty() {
+        return keep;
+    }
+    
+    for chunk in stale.chunks(MAX_SIGNATURE_STATUSES) {
+        let Ok(signatures) = chunk
+            .iter()
+            .map(|tx| solana_sdk::signature::Signature::from_str(&tx.signature))
+            .collect::<Result<Vec<_>, _>>()
+        else {
+            // The normal path reports the unparsable signature
+            keep.extend(chunk.iter().cloned());
+            continue;
+        };
+        let statuses = match rpc_client.get_signature_statuses_with_history(&signatures) {
+            Ok(response) => response.value,
+            Err(e) => {
+                warn!("Error fetching signature statuses for pending GC: {}", e);
+                keep.extend(chunk.iter().cloned());
+                continue;
+            }
+        };
+        
+        for (tx, status) in chunk.iter().zip(statuses) {
+            // Anything the cluster knows about goes through the normal status path
+            if status.is_some() {
+                keep.push(tx.clone());
+                continue;
+            }
+            match db::delete_dropped_tx(db_pool, &tx.signature).await {
+                Ok(true) => info!(
+                    "Transaction {} dropped (sent {}s ago, never landed), removed from pending",
+                    tx.signature, now - tx.sent_at
+                ),
+                Ok(false) => {}
+                Err(e) => warn!("Failed to remove dropped tx {}: {}", tx.signature, e),
+            }
+        }
+    }
+    
+    keep
+}
+
+/// Rebuild a stored transaction message with a fresh blockhash, signed by `payer`
+///
+/// Rejects messages paid by anyone else, since only the fee payer's signature can be replaced.
+pub fn rebuild_replay_tx(
+    stored: &[u8],
+    payer: &Keypair,
+    recent_blockhash: Hash,
+    signature: &str,
+) -> Result<VersionedTransaction, CrankError> {
+    let mut message: VersionedMessage = bincode::deserialize(stored)
+        .map_err(|e| CrankError::Deserialize(e.to_string()))?;
+    
+    if message.static_account_keys().first() != Some(&payer.pubkey()) {
+        return Err(CrankError::Send(format!(
+            "Transaction {} was not paid by the current deploy authority", signature
+        )));
+    }
+    
+    message.set_recent_blockhash(recent_blockhash);
+    VersionedTransaction::try_new(message, &[payer])
+        .map_err(|e| CrankError::Send(e.to_string()))
+}
+
+/// Per-square lamports landed by a deploy, given the miner before and after it
+///
+/// ORE resets `Miner.deployed` when a miner first deploys into a new round, so a pre-state
+/// from an older round (or no miner at all) counts as an empty board.
+pub fn landed_per_square(pre: Option<&Miner>, post: &Miner, round_id: u64) -> [u64; 25] {
+    let before = match pre {
+        Some(miner) if miner.round_id == round_id => miner.deployed,
+        _ => [0u64; 25],
+    };
+    
+    let mut landed = [0u64; 25];
+    for (i, slot) in landed.iter_mut().enumerate() {
+        *slot = post.deployed[i].saturating_sub(before[i]);
+    }
+    landed
+}
+
+/// Per-square lamports landed according to a deploy simulation
+///
+/// `result` must carry the post-simulation ORE miner as its first requested account.
+pub fn landed_from_simulation(
+    result: RpcSimulateTransactionResult,
+    pre: Option<&Miner>,
+    round_id: u64,
+) -> Result<[u64; 25], CrankError> {
+    if let Some(err) = result.err {
+        let logs = result.logs.unwrap_or_default().join("\n");
+        return Err(CrankError::Send(format!("Simulation failed: {:?}\n{}", err, logs)));
+    }
+    
+    let post_data = result.accounts
+        .and_then(|accounts| accounts.into_iter().next().flatten())
+        .and_then(|ui_account| ui_account.data.decode())
+        .ok_or_else(|| CrankError::Deserialize("Simulation returned no miner account".to_string()))?;
+    let post_miner = Miner::try_from_bytes(&post_data)
+        .map_err(|e| CrankError::Deserialize(format!("{:?}", e)))?;
+    
+    Ok(landed_per_square(pre, post_miner, round_id))
+}
+
+/// Drops later entries whose managed_miner_auth was already seen, keeping the first one
+///
+/// A miner can only deploy once per round, so a second entry in the same transaction would
+/// fail the whole batch. Returns the deduplicated items and how many were removed.
+pub fn dedup_by_miner_auth<T>(items: Vec<T>, miner_auth: impl Fn(&T) -> Pubkey) -> (Vec<T>, usize) {
+    let mut seen = std::collections::HashSet::new();
+    let before = items.len();
+    let kept: Vec<T> = items.into_iter().filter(|item| seen.insert(miner_auth(item))).collect();
+    let removed = before - kept.len();
+    (kept, removed)
+}
+
+/// Deposits per top-up transaction
+pub const TOP_UP_BATCH_SIZE: usize = 10;
+
+/// `(manager, lamports)` deposits that bring each `(manager, balance)` up to `target_lamports`
+///
+/// Pays out of `available` lamports, emptiest miners first; once funds run short the next miner
+/// gets whatever is left and the rest get nothing. Miners at or above the target are skipped.
+pub fn plan_top_ups(balances: &[(Pubkey, u64)], target_lamports: u64, available: u64) -> Vec<(Pubkey, u64)> {
+    let mut needy: Vec<(Pubkey, u64)> = balances
+        .iter()
+        .filter(|(_, balance)| *balance < target_lamports)
+        .copied()
+        .collect();
+    needy.sort_by_key(|(_, balance)| *balance);
+    
+    let mut remaining = available;
+    let mut top_ups = Vec::new();
+    for (manager, balance) in needy {
+        let amount = (target_lamports - balance).min(remaining);
+        if amount == 0 {
+            break;
+        }
+        remaining -= amount;
+        top_ups.push((manager, amount));
+    }
+    top_ups
+}
+
+/// Indices of the items that make up the next batch: taken in order up to `batch_size`,
+/// skipping items whose manager already has `max_per_manager` in the batch (0 = no limit)
+pub fn next_batch_indices<T>(
+    items: &[T],
+    batch_size: usize,
+    max_per_manager: usize,
+    manager: impl Fn(&T) -> Pubkey,
+) -> Vec<usize> {
+    let mut per_manager = std::collections::HashMap::new();
+    let mut picked = Vec::new();
+    for (i, item) in items.iter().enumerate() {
+        if picked.len() >= batch_size {
+            break;
+        }
+        let count = per_manager.entry(manager(item)).or_insert(0usize);
+        if max_per_manager > 0 && *count >= max_per_manager {
+            continue;
+        }
+        *count += 1;
+        picked.push(i);
+    }
+    picked
+}
+
+/// Removes the next batch (see `next_batch_indices`) from `pending`, leaving the deferred
+/// items in their original order for the batches after it
+pub fn take_batch<T>(
+    pending: &mut Vec<T>,
+    batch_size: usize,
+    max_per_manager: usize,
+    manager: impl Fn(&T) -> Pubkey,
+) -> Vec<T> {
+    let picked = next_batch_indices(pending, batch_size, max_per_manager, manager);
+    let mut batch = Vec::with_capacity(picked.len());
+    let mut rest = Vec::with_capacity(pending.len() - picked.len());
+    for (i, item) in std::mem::take(pending).into_iter().enumerate() {
+        if picked.binary_search(&i).is_ok() {
+            batch.push(item);
+        } else {
+            rest.push(item);
+        }
+    }
+    *pending = rest;
+    batch
+}
+
+/// Splits `items` into batches of up to `batch_size`, in order, with no manager holding more
+/// than `max_per_manager` entries of any one batch (0 = plain chunking)
+pub fn batch_by_manager<T>(
+    mut items: Vec<T>,
+    batch_size: usize,
+    max_per_manager: usize,
+    manager: impl Fn(&T) -> Pubkey,
+) -> Vec<Vec<T>> {
+    let batch_size = batch_size.max(1);
+    let mut batches = Vec::new();
+    while !items.is_empty() {
+        batches.push(take_batch(&mut items, batch_size, max_per_manager, &manager));
+    }
+    batches
+}
+
+/// create_manager + create_deployer for a new manager, with `authority` as both its
+/// authority and the deployer's deploy_authority
+pub fn create_manager_and_deployer_ixs(
+    authority: Pubkey,
+    manager: Pubkey,
+    bps_fee: u64,
+    flat_fee: u64,
+    max_per_round: u64,
+) -> Vec<Instruction> {
+    vec![
+        evore::instruction::create_manager(authority, manager),
+        evore::instruction::create_deployer(authority, manager, authority, bps_fee, flat_fee, max_per_round),
+    ]
+}
+
+/// Managers that hold both a legacy `Deployer` and a `StrategyDeployer`
+///
+/// The two live at different PDAs, so nothing stops a manager from creating both. The crank
+/// only discovers and runs the legacy `Deployer`, which is worth flagging to the operator.
+/// Returned in order of first appearance.
+pub fn dual_deployer_managers(accounts: &[(Pubkey, solana_sdk::account::Account)]) -> Vec<Pubkey> {
+    let mut legacy = std::collections::HashSet::new();
+    let mut strategy = std::collections::HashSet::new();
+    let mut order = Vec::new();
+    for (_, account) in accounts {
+        let manager = if let Ok(deployer) = Deployer::try_from_bytes(&account.data) {
+            legacy.insert(deployer.manager_key);
+            deployer.manager_key
+        } else if let Ok(deployer) = StrategyDeployer::from_account_data(&account.data) {
+            strategy.insert(deployer.manager_key);
+            deployer.manager_key
+        } else {
+            continue;
+        };
+        if !order.contains(&manager) {
+            order.push(manager);
+        }
+    }
+    order.into_iter().filter(|m| legacy.contains(m) && strategy.contains(m)).collect()
+}
+
+/// CU for a checkpoint + recycle ahead of a deploy
+const CHECKPOINT_RECYCLE_CU: u32 = 150_000;
+
+/// CU for a SOL recycle alone (mm_full_autodeploy claims whenever rewards are pending)
+const RECYCLE_CU: u32 = 50_000;
+
+/// Compute unit limit for a batch of mm_full_autodeploys
+///
+/// Legacy deployers deploy the same amount to every masked square, so each deploy costs
+/// the Manual preset plus whatever checkpoint/recycle work runs ahead of it.
+pub fn full_autodeploy_cu_limit(deploys: &[DeployPlan]) -> u32 {
+    deploys
+        .iter()
+        .map(|plan| {
+            let deploy = strategy_cu_limit(StrategyType::Manual, u64::from(plan.num_squares()));
+            let settle = if plan.needs_checkpoint() { CHECKPOINT_RECYCLE_CU } else { RECYCLE_CU };
+            deploy + settle
+        })
+        .sum::<u32>()
+        .min(MAX_COMPUTE_UNIT_LIMIT)
+}
+
 use std::str::FromStr;
 
+/// Deployers (in the order given) to create miner LUTs for, at most `max_creations` (0 = no cap),
+/// along with how many are missing one in total
+pub fn capped_lut_creations<'a>(
+    registry: &LutRegistry,
+    deployers: &[&'a DeployerInfo],
+    auth_id: u64,
+    max_creations: usize,
+) -> (Vec<&'a DeployerInfo>, usize) {
+    let missing: Vec<&DeployerInfo> = deployers
+        .iter()
+        .copied()
+        .filter(|d| !registry.has_miner_lut(&get_miner_auth_pda(d.manager_address, auth_id)))
+        .collect();
+    let total = missing.len();
+    let cap = if max_creations == 0 { total } else { total.min(max_creations) };
+    (missing.into_iter().take(cap).collect(), total)
+}
+
+/// Errors with `CrankError::Lut` when any miner has no cached LUT, sending callers to the no-LUT path
+pub fn require_miner_luts(registry: &LutRegistry, miner_auths: &[Pubkey]) -> Result<(), CrankError> {
+    match miner_auths.iter().find(|m| !registry.has_miner_lut(m)) {
+        Some(missing) => Err(CrankError::Lut(format!("no cached LUT for miner_auth {}", missing))),
+        None => Ok(()),
+    }
+}
+
+/// Whether a send error came from the runtime rejecting a lookup table (deactivated, closed, bad index)
+fn is_lut_failure(error: &str) -> bool {
+    let error = error.to_lowercase();
+    error.contains("address table") || error.contains("lookup table")
+}
+
 #[derive(Debug, thiserror::Error)]
 pub enum CrankError {
     #[error("Failed to load keypair: {0}")]
@@ -1607,4 +2383,368 @@ pub enum CrankError {
     Send(String),
     #[error("Parse error: {0}")]
     Parse(String),
+    #[error("LUT error: {0}")]
+    Lut(String),
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+    use std::time::Duration;
+
+    use evore::state::{Deployer, EvoreAccount};
+    use solana_account_decoder::encode_ui_account;
+    use solana_sdk::account::Account;
+    use solana_sdk::signature::Signature;
+    use solana_sdk::transaction::TransactionError;
+    use steel::Zeroable;
+    use tokio::sync::RwLock;
+
+    use super::*;
+    use crate::config::FeeMismatch;
+    use crate::pipeline::shared_state::DeployerCache;
+    use crate::rpc::mock::MockRpc;
+
+    fn deployer_account(manager_key: Pubkey, deploy_authority: Pubkey, bps_fee: u64) -> Account {
+        let deployer = Deployer {
+            manager_key,
+            deploy_authority,
+            bps_fee,
+            flat_fee: 715,
+            expected_bps_fee: 500,
+            expected_flat_fee: 715,
+            max_per_round: 0,
+        };
+        let mut data = (EvoreAccount::Deployer as u64).to_le_bytes().to_vec();
+        data.extend_from_slice(deployer.to_bytes());
+        Account { lamports: 1_000_000, data, owner: evore::id(), executable: false, rent_epoch: 0 }
+    }
+
+    #[tokio::test]
+    async fn test_find_deployers_filters_by_deploy_authority() {
+        let authority = Pubkey::new_unique();
+        let ours = Pubkey::new_unique();
+        let ours_manager = Pubkey::new_unique();
+        let rpc = MockRpc::default()
+            .with_account(ours, deployer_account(ours_manager, authority, 250))
+            .with_account(Pubkey::new_unique(), deployer_account(Pubkey::new_unique(), Pubkey::new_unique(), 0))
+            .with_account(Pubkey::new_unique(), Account {
+                lamports: 1,
+                data: vec![101, 0, 0, 0, 0, 0, 0, 0],
+                owner: evore::id(),
+                executable: false,
+                rent_epoch: 0,
+            });
+        let cache: SharedDeployerCache = Arc::new(RwLock::new(DeployerCache::new(Duration::from_secs(60))));
+
+        let deployers = discover_deployers(&cache, &rpc, authority).await.unwrap();
+
+        assert_eq!(deployers.len(), 1);
+        assert_eq!(deployers[0].deployer_address, ours);
+        assert_eq!(deployers[0].manager_address, ours_manager);
+        assert_eq!(deployers[0].bps_fee, 250);
+        assert_eq!(deployers[0].expected_flat_fee, 715);
+        assert!(cache.read().await.get().is_some());
+    }
+
+    fn miner_account(round_id: u64, deployed: [u64; 25]) -> Account {
+        let mut miner = Miner::zeroed();
+        miner.round_id = round_id;
+        miner.deployed = deployed;
+        let mut data = (evore::ore_api::OreAccount::Miner as u64).to_le_bytes().to_vec();
+        data.extend_from_slice(miner.to_bytes());
+        Account { lamports: 1_000_000, data, owner: evore::ore_api::PROGRAM_ID, executable: false, rent_epoch: 0 }
+    }
+
+    #[test]
+    fn test_simulated_deploy_reports_per_square_landed() {
+        let address = Pubkey::new_unique();
+        let mut pre = Miner::zeroed();
+        pre.round_id = 7;
+        pre.deployed[3] = 1_000;
+
+        let mut deployed = [0u64; 25];
+        deployed[0] = 2_800;
+        deployed[3] = 3_800;
+        deployed[24] = 1_400;
+        let simulated = |err, account: Option<Account>| RpcSimulateTransactionResult {
+            err,
+            logs: Some(vec!["Program log: AutoDeploy".to_string()]),
+            accounts: Some(vec![account.map(|a| {
+                encode_ui_account(&address, &a, UiAccountEncoding::Base64, None, None)
+            })]),
+            units_consumed: None,
+            loaded_accounts_data_size: None,
+            return_data: None,
+            inner_instructions: None,
+            replacement_blockhash: None,
+        };
+
+        // Same round: only the delta over what was already deployed counts
+        let landed = landed_from_simulation(simulated(None, Some(miner_account(7, deployed))), Some(&pre), 7).unwrap();
+        let mut expected = [0u64; 25];
+        expected[0] = 2_800;
+        expected[3] = 2_800;
+        expected[24] = 1_400;
+        assert_eq!(landed, expected);
+
+        // A pre-state from an older round was reset by the deploy
+        let landed = landed_from_simulation(simulated(None, Some(miner_account(8, deployed))), Some(&pre), 8).unwrap();
+        assert_eq!(landed, deployed);
+
+        assert!(matches!(
+            landed_from_simulation(simulated(Some(TransactionError::AccountNotFound), None), None, 7),
+            Err(CrankError::Send(_))
+        ));
+        assert!(matches!(
+            landed_from_simulation(simulated(None, None), None, 7),
+            Err(CrankError::Deserialize(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_replay_resigns_stored_message_with_fresh_blockhash() {
+        let db_path = std::env::temp_dir().join(format!("evore-crank-replay-{}.db", Pubkey::new_unique()));
+        let pool = db::init_db(&db_path).await.unwrap();
+        let payer = Keypair::new();
+
+        let ix = system_instruction::transfer(&payer.pubkey(), &Pubkey::new_unique(), 1_000);
+        let original = Transaction::new_signed_with_payer(&[ix], Some(&payer.pubkey()), &[&payer], Hash::new_unique());
+        let original_signature = original.signatures[0].to_string();
+        let message = VersionedMessage::Legacy(original.message.clone());
+        db::insert_tx_message(&pool, &original_signature, &bincode::serialize(&message).unwrap()).await.unwrap();
+
+        let stored = db::get_tx_message(&pool, &original_signature).await.unwrap().unwrap();
+        let fresh_blockhash = Hash::new_unique();
+        let replayed = rebuild_replay_tx(&stored, &payer, fresh_blockhash, &original_signature).unwrap();
+
+        assert_ne!(replayed.signatures[0].to_string(), original_signature, "a new signature is produced");
+        assert_eq!(*replayed.message.recent_blockhash(), fresh_blockhash);
+        assert_eq!(replayed.message.instructions(), message.instructions(), "same instructions are re-sent");
+        assert!(replayed.verify_with_results().iter().all(|ok| *ok));
+
+        // Only the current deploy authority can re-sign
+        assert!(matches!(
+            rebuild_replay_tx(&stored, &Keypair::new(), fresh_blockhash, &original_signature),
+            Err(CrankError::Send(_))
+        ));
+
+        pool.close().await;
+        std::fs::remove_file(&db_path).ok();
+    }
+
+    #[test]
+    fn test_missing_lut_takes_the_no_lut_path() {
+        let mut registry = LutRegistry::new(RpcClient::new_mock("succeeds".to_string()), Pubkey::new_unique());
+        let (with_lut, without_lut) = (Pubkey::new_unique(), Pubkey::new_unique());
+        registry.register_miner_lut(with_lut, Pubkey::new_unique(), vec![with_lut]);
+
+        assert!(require_miner_luts(&registry, &[with_lut]).is_ok());
+        assert!(matches!(
+            require_miner_luts(&registry, &[with_lut, without_lut]),
+            Err(CrankError::Lut(e)) if e.contains(&without_lut.to_string())
+        ));
+
+        // Runtime rejections of a deactivated/closed LUT fall back too, other send errors don't
+        assert!(is_lut_failure("Transaction loads an address table account that doesn't exist"));
+        assert!(is_lut_failure("Transaction address table lookup uses an invalid index"));
+        assert!(!is_lut_failure("Blockhash not found"));
+    }
+
+    #[test]
+    fn test_verify_fees_reports_raised_fees() {
+        let authority = Pubkey::new_unique();
+        let (within, raised) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let rpc = MockRpc::default()
+            .with_account(Pubkey::new_unique(), deployer_account(within, authority, 500))
+            .with_account(Pubkey::new_unique(), deployer_account(raised, authority, 750));
+
+        let report: Vec<(Pubkey, FeeMismatch)> = scan_deployers(&rpc, authority)
+            .unwrap()
+            .iter()
+            .filter_map(|d| d.fee_mismatch().map(|m| (d.manager_address, m)))
+            .collect();
+
+        assert_eq!(report, vec![(raised, FeeMismatch { bps_exceeded: true, flat_exceeded: false })]);
+    }
+
+    #[test]
+    fn test_duplicate_miner_collapsed_to_one_deploy() {
+        let (a, b, c) = (Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique());
+        // Overlapping deployer lists put miner `a` in the batch twice
+        let batch = vec![(a, 2_800), (b, 2_800), (a, 5_600), (c, 2_800), (b, 2_800)];
+
+        let (deduped, removed) = dedup_by_miner_auth(batch, |(miner_auth, _)| *miner_auth);
+
+        assert_eq!(removed, 2);
+        assert_eq!(deduped, vec![(a, 2_800), (b, 2_800), (c, 2_800)], "first entry per miner is kept, in order");
+
+        let (unique, removed) = dedup_by_miner_auth(vec![a, b], |m| *m);
+        assert_eq!((unique.len(), removed), (2, 0));
+    }
+
+    #[tokio::test]
+    async fn test_second_discovery_within_ttl_hits_cache() {
+        let authority = Pubkey::new_unique();
+        let manager = Pubkey::new_unique();
+        let rpc = MockRpc::default().with_account(Pubkey::new_unique(), deployer_account(manager, authority, 0));
+        let empty = MockRpc::default();
+        let cache: SharedDeployerCache = Arc::new(RwLock::new(DeployerCache::new(Duration::from_secs(60))));
+
+        assert_eq!(discover_deployers(&cache, &rpc, authority).await.unwrap().len(), 1);
+
+        // The chain no longer has the deployer, but the cached scan is still served
+        let again = discover_deployers(&cache, &empty, authority).await.unwrap();
+        assert_eq!(again.len(), 1);
+        assert_eq!(again[0].manager_address, manager);
+
+        // Past the TTL, or after an explicit refresh, discovery scans again
+        let scanned_at = Instant::now();
+        assert!(cache.read().await.get_at(scanned_at + Duration::from_secs(61)).is_none());
+        cache.write().await.invalidate();
+        assert!(discover_deployers(&cache, &empty, authority).await.unwrap().is_empty());
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_old_unresolved_pending_tx_is_gcd() {
+        let db_path = std::env::temp_dir().join(format!("evore-crank-gc-{}.db", Pubkey::new_unique()));
+        let pool = db::init_db(&db_path).await.unwrap();
+        let (old, recent) = (Signature::new_unique().to_string(), Signature::new_unique().to_string());
+        // Sent at t=0 with a blockhash valid until height 100, and just now at height 150
+        for (signature, last_valid_blockheight, sent_at) in [(&old, 100, 0), (&recent, 300, 9_900)] {
+            db::insert_tx(&pool, signature, "manager", "deployer", 0, 10, 2_800, 1, 1, 2_800, 0, 0, 0, 0, last_valid_blockheight, sent_at)
+                .await
+                .unwrap();
+        }
+        let pending = db::get_pending_txs(&pool).await.unwrap();
+        let (current_blockheight, now) = (200, 10_000);
+
+        // Cluster knows the old signature: it stays pending for the normal status path
+        let kept = gc_dropped_txs(&pool, &RpcClient::new_mock("succeeds".to_string()), pending.clone(), current_blockheight, now).await;
+        assert_eq!(kept.len(), 2);
+
+        // Never seen by the cluster: dropped and removed, the recent row is untouched
+        let kept = gc_dropped_txs(&pool, &RpcClient::new_mock("sig_not_found".to_string()), pending, current_blockheight, now).await;
+        assert_eq!(kept.iter().map(|tx| &tx.signature).collect::<Vec<_>>(), vec![&recent]);
+        assert!(db::get_tx_by_signature(&pool, &old).await.unwrap().is_none());
+        assert_eq!(db::get_pending_txs(&pool).await.unwrap().len(), 1);
+
+        pool.close().await;
+        std::fs::remove_file(&db_path).ok();
+    }
+
+    #[test]
+    fn test_lut_creations_capped_per_round() {
+        let mut registry = LutRegistry::new(RpcClient::new_mock("succeeds".to_string()), Pubkey::new_unique());
+        let deployers: Vec<DeployerInfo> = (0..8)
+            .map(|_| DeployerInfo {
+                deployer_address: Pubkey::new_unique(),
+                manager_address: Pubkey::new_unique(),
+                bps_fee: 0,
+                flat_fee: 0,
+                expected_bps_fee: 0,
+                expected_flat_fee: 0,
+                max_per_round: 0,
+            })
+            .collect();
+        // The first (most urgent) miner already has its LUT
+        let miner_auth = get_miner_auth_pda(deployers[0].manager_address, 0);
+        registry.register_miner_lut(miner_auth, Pubkey::new_unique(), vec![miner_auth]);
+        let by_urgency: Vec<&DeployerInfo> = deployers.iter().collect();
+
+        let (this_round, missing) = capped_lut_creations(&registry, &by_urgency, 0, 3);
+        assert_eq!(missing, 7);
+        let created: Vec<Pubkey> = this_round.iter().map(|d| d.manager_address).collect();
+        let expected: Vec<Pubkey> = deployers[1..4].iter().map(|d| d.manager_address).collect();
+        assert_eq!(created, expected, "only the cap, most urgent first");
+
+        // No cap creates every missing LUT
+        assert_eq!(capped_lut_creations(&registry, &by_urgency, 0, 0).0.len(), 7);
+    }
+
+    #[test]
+    fn test_manager_cap_leaves_room_for_other_managers() {
+        let (busy, quiet_a, quiet_b) = (Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique());
+        // The busy manager's 10 miners sort ahead of everyone else's
+        let mut miners: Vec<(Pubkey, u64)> = (0..10).map(|auth_id| (busy, auth_id)).collect();
+        miners.push((quiet_a, 0));
+        miners.push((quiet_b, 0));
+
+        // Without a cap the busy manager takes the whole first batch
+        let uncapped = batch_by_manager(miners.clone(), 5, 0, |(m, _)| *m);
+        assert!(uncapped[0].iter().all(|(m, _)| *m == busy));
+
+        let batches = batch_by_manager(miners, 5, 2, |(m, _)| *m);
+        assert_eq!(batches[0], vec![(busy, 0), (busy, 1), (quiet_a, 0), (quiet_b, 0)]);
+        assert!(batches.iter().all(|b| b.iter().filter(|(m, _)| *m == busy).count() <= 2));
+
+        // Nothing is dropped, the busy manager's remaining miners fill later batches in order
+        let later: Vec<u64> = batches[1..].iter().flatten().map(|(_, auth_id)| *auth_id).collect();
+        assert_eq!(later, (2..10).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn test_dual_deployer_manager_flagged() {
+        let (dual, legacy_only, strategy_only) = (Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique());
+        let strategy_deployer_account = |manager_key| {
+            let mut deployer = StrategyDeployer::zeroed();
+            deployer.manager_key = manager_key;
+            let mut data = (EvoreAccount::StrategyDeployer as u64).to_le_bytes().to_vec();
+            data.extend_from_slice(deployer.to_bytes());
+            Account { lamports: 1_000_000, data, owner: evore::id(), executable: false, rent_epoch: 0 }
+        };
+        let accounts = vec![
+            (Pubkey::new_unique(), deployer_account(legacy_only, Pubkey::new_unique(), 0)),
+            (Pubkey::new_unique(), strategy_deployer_account(dual)),
+            (Pubkey::new_unique(), strategy_deployer_account(strategy_only)),
+            (Pubkey::new_unique(), deployer_account(dual, Pubkey::new_unique(), 0)),
+        ];
+
+        assert_eq!(dual_deployer_managers(&accounts), vec![dual]);
+        assert!(dual_deployer_managers(&accounts[..3]).is_empty());
+    }
+
+    #[test]
+    fn test_deploy_batch_cu_limit_follows_presets() {
+        let deployer = DeployerInfo {
+            deployer_address: Pubkey::new_unique(),
+            manager_address: Pubkey::new_unique(),
+            bps_fee: 0,
+            flat_fee: 0,
+            expected_bps_fee: 0,
+            expected_flat_fee: 0,
+            max_per_round: 0,
+        };
+        let plan = |squares_mask, checkpoint_round| DeployPlan::new(deployer.clone(), 0, 7, 1_000, squares_mask)
+            .with_checkpoint(checkpoint_round);
+        let full_board = (1u32 << 25) - 1;
+
+        // Manual preset (50k + 1k per square) plus the recycle or checkpoint+recycle ahead of it
+        assert_eq!(full_autodeploy_cu_limit(&[plan(full_board, None)]), 75_000 + RECYCLE_CU);
+        assert_eq!(full_autodeploy_cu_limit(&[plan(0b1, None)]), 51_000 + RECYCLE_CU);
+        assert_eq!(
+            full_autodeploy_cu_limit(&[plan(0b1, None), plan(full_board, Some(6))]),
+            51_000 + RECYCLE_CU + 75_000 + CHECKPOINT_RECYCLE_CU,
+        );
+
+        // A batch never asks for more than a transaction can have
+        let many: Vec<DeployPlan> = (0..20).map(|_| plan(full_board, Some(6))).collect();
+        assert_eq!(full_autodeploy_cu_limit(&many), MAX_COMPUTE_UNIT_LIMIT);
+    }
+
+    #[test]
+    fn test_plan_top_ups_fills_deficits_within_available() {
+        let (empty, low, full, over) = (Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique());
+        let balances = [(low, 600_000), (full, 1_000_000), (empty, 0), (over, 3_000_000)];
+
+        // Plenty of funds: every miner below target gets exactly its deficit, emptiest first
+        assert_eq!(plan_top_ups(&balances, 1_000_000, 10_000_000), vec![(empty, 1_000_000), (low, 400_000)]);
+
+        // Short on funds: the emptiest is filled first and the next gets the remainder
+        assert_eq!(plan_top_ups(&balances, 1_000_000, 1_100_000), vec![(empty, 1_000_000), (low, 100_000)]);
+        assert_eq!(plan_top_ups(&balances, 1_000_000, 700_000), vec![(empty, 700_000)]);
+
+        assert!(plan_top_ups(&balances, 1_000_000, 0).is_empty());
+        assert_eq!(plan_top_ups(&balances, 500_000, 10_000_000), vec![(empty, 500_000)]);
+    }
 }
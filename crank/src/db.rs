@@ -105,6 +105,12 @@ pub async fn init_db(db_path: &Path) -> Result<Pool<Sqlite>, sqlx::Error> {
         .connect(&db_url)
         .await?;
     
+    // Let pruned pages be released with incremental_vacuum. Only takes effect on new
+    // databases directly; existing ones switch over on their next full VACUUM
+    sqlx::query("PRAGMA auto_vacuum = INCREMENTAL")
+        .execute(&pool)
+        .await?;
+    
     // Create the autodeploy_txs table
     sqlx::query(r#"
         CREATE TABLE IF NOT EXISTS autodeploy_txs (
@@ -153,6 +159,32 @@ pub async fn init_db(db_path: &Path) -> Result<Pool<Sqlite>, sqlx::Error> {
         .execute(&pool)
         .await?;
     
+    // Serialized message (instructions + account keys) for each sent transaction,
+    // kept so failed transactions can be replayed with a fresh blockhash
+    sqlx::query(r#"
+        CREATE TABLE IF NOT EXISTS tx_messages (
+            signature TEXT PRIMARY KEY,
+            message BLOB NOT NULL,
+            created_at INTEGER NOT NULL DEFAULT (strftime('%s', 'now'))
+        )
+    "#)
+    .execute(&pool)
+    .await?;
+    
+    // Per-square outcome of each resolved round, for win rate analysis (`square-stats`)
+    sqlx::query(r#"
+        CREATE TABLE IF NOT EXISTS square_history (
+            round_id INTEGER NOT NULL,
+            square INTEGER NOT NULL,
+            deployed INTEGER NOT NULL,
+            miner_count INTEGER NOT NULL,
+            won INTEGER NOT NULL,
+            PRIMARY KEY (round_id, square)
+        )
+    "#)
+    .execute(&pool)
+    .await?;
+    
     Ok(pool)
 }
 
@@ -204,6 +236,154 @@ pub async fn insert_tx(
     Ok(result.last_insert_rowid())
 }
 
+/// Store the serialized message for a sent transaction
+/// Batched transactions share one signature, so repeat inserts are ignored
+pub async fn insert_tx_message(
+    pool: &Pool<Sqlite>,
+    signature: &str,
+    message: &[u8],
+) -> Result<(), sqlx::Error> {
+    sqlx::query("INSERT OR IGNORE INTO tx_messages (signature, message) VALUES (?, ?)")
+        .bind(signature)
+        .bind(message)
+        .execute(pool)
+        .await?;
+    
+    Ok(())
+}
+
+/// Get the serialized message stored for a transaction, if any
+pub async fn get_tx_message(
+    pool: &Pool<Sqlite>,
+    signature: &str,
+) -> Result<Option<Vec<u8>>, sqlx::Error> {
+    let row = sqlx::query("SELECT message FROM tx_messages WHERE signature = ?")
+        .bind(signature)
+        .fetch_optional(pool)
+        .await?;
+    
+    use sqlx::Row;
+    Ok(row.map(|r| r.get("message")))
+}
+
+/// Record one square's outcome for a resolved round
+/// Re-recording a round (e.g. after a restart) overwrites the earlier row
+pub async fn record_square_outcome(
+    pool: &Pool<Sqlite>,
+    round_id: u64,
+    square: u8,
+    deployed: u64,
+    miner_count: u64,
+    won: bool,
+) -> Result<(), sqlx::Error> {
+    sqlx::query(r#"
+        INSERT OR REPLACE INTO square_history (round_id, square, deployed, miner_count, won)
+        VALUES (?, ?, ?, ?, ?)
+        "#)
+        .bind(round_id as i64)
+        .bind(square as i64)
+        .bind(deployed as i64)
+        .bind(miner_count as i64)
+        .bind(won as i64)
+        .execute(pool)
+        .await?;
+    
+    Ok(())
+}
+
+/// Win rate and average stake per square over every recorded round
+pub async fn get_square_stats(pool: &Pool<Sqlite>) -> Result<Vec<SquareStats>, sqlx::Error> {
+    let rows = sqlx::query(r#"
+        SELECT
+            square,
+            COUNT(*) as rounds,
+            SUM(won) as wins,
+            AVG(deployed) as avg_deployed,
+            AVG(miner_count) as avg_miners
+        FROM square_history
+        GROUP BY square
+        ORDER BY square
+        "#)
+        .fetch_all(pool)
+        .await?;
+    
+    use sqlx::Row;
+    Ok(rows
+        .iter()
+        .map(|row| SquareStats {
+            square: row.get::<i64, _>("square") as u8,
+            rounds: row.get::<i64, _>("rounds") as u64,
+            wins: row.get::<Option<i64>, _>("wins").unwrap_or(0) as u64,
+            avg_deployed: row.get::<Option<f64>, _>("avg_deployed").unwrap_or(0.0),
+            avg_miners: row.get::<Option<f64>, _>("avg_miners").unwrap_or(0.0),
+        })
+        .collect())
+}
+
+/// Delete transactions from rounds more than `retention_rounds` behind the newest
+/// recorded round, along with their stored messages. Pending transactions are kept
+/// so confirmation tracking is never cut short. Returns the number of rows removed.
+pub async fn prune_old_rounds(
+    pool: &Pool<Sqlite>,
+    retention_rounds: u64,
+) -> Result<u64, sqlx::Error> {
+    let result = sqlx::query(r#"
+        DELETE FROM autodeploy_txs
+        WHERE status != 0
+          AND round_id < (SELECT MAX(round_id) FROM autodeploy_txs) - ?
+    "#)
+    .bind(retention_rounds as i64)
+    .execute(pool)
+    .await?;
+    
+    sqlx::query("DELETE FROM tx_messages WHERE signature NOT IN (SELECT signature FROM autodeploy_txs)")
+        .execute(pool)
+        .await?;
+    
+    Ok(result.rows_affected())
+}
+
+/// Reclaim free pages: incremental when auto_vacuum is INCREMENTAL, full VACUUM otherwise
+/// (the full VACUUM also converts an older database to incremental mode)
+pub async fn compact(pool: &Pool<Sqlite>) -> Result<(), sqlx::Error> {
+    use sqlx::Row;
+    let mode: i64 = sqlx::query("PRAGMA auto_vacuum")
+        .fetch_one(pool)
+        .await?
+        .get(0);
+    
+    // 2 = INCREMENTAL
+    if mode == 2 {
+        sqlx::query("PRAGMA incremental_vacuum").execute(pool).await?;
+    } else {
+        sqlx::query("VACUUM").execute(pool).await?;
+    }
+    
+    Ok(())
+}
+
+/// Background task applying the retention policy and compacting the database on an interval
+pub async fn run_maintenance(pool: Pool<Sqlite>, retention_rounds: u64, interval_secs: u64) {
+    let mut interval = tokio::time::interval(std::time::Duration::from_secs(interval_secs));
+    loop {
+        interval.tick().await;
+        
+        if retention_rounds > 0 {
+            match prune_old_rounds(&pool, retention_rounds).await {
+                Ok(removed) if removed > 0 => {
+                    tracing::info!("[DB] Pruned {} transactions older than {} rounds", removed, retention_rounds);
+                }
+                Ok(_) => {}
+                Err(e) => tracing::warn!("[DB] Failed to prune old transactions: {}", e),
+            }
+        }
+        
+        if let Err(e) = compact(&pool).await {
+            tracing::warn!("[DB] Failed to compact database: {}", e);
+        }
+    }
+}
+
 /// Update transaction status to confirmed
 pub async fn update_tx_confirmed(
     pool: &Pool<Sqlite>,
@@ -282,6 +462,26 @@ pub async fn update_tx_expired(
     Ok(())
 }
 
+/// Delete a still-pending transaction that was dropped by the cluster, along with its
+/// stored message. Rows that resolved in the meantime are left alone.
+/// Returns true if the row was removed.
+pub async fn delete_dropped_tx(
+    pool: &Pool<Sqlite>,
+    signature: &str,
+) -> Result<bool, sqlx::Error> {
+    let result = sqlx::query("DELETE FROM autodeploy_txs WHERE signature = ? AND status = 0")
+        .bind(signature)
+        .execute(pool)
+        .await?;
+    
+    sqlx::query("DELETE FROM tx_messages WHERE signature = ?")
+        .bind(signature)
+        .execute(pool)
+        .await?;
+    
+    Ok(result.rows_affected() > 0)
+}
+
 /// Get all pending transactions
 pub async fn get_pending_txs(pool: &Pool<Sqlite>) -> Result<Vec<AutodeployTx>, sqlx::Error> {
     let rows = sqlx::query(r#"
@@ -377,6 +577,53 @@ pub async fn get_recent_txs(pool: &Pool<Sqlite>, limit: i32) -> Result<Vec<Autod
     Ok(txs)
 }
 
+/// Get the transaction record for a signature
+pub async fn get_tx_by_signature(pool: &Pool<Sqlite>, signature: &str) -> Result<Option<AutodeployTx>, sqlx::Error> {
+    let row = sqlx::query(r#"
+        SELECT 
+            id, signature, manager_key, deployer_key, auth_id, round_id,
+            amount_per_square, squares_mask, num_squares, total_deployed,
+            deployer_fee, protocol_fee, priority_fee, jito_tip,
+            last_valid_blockheight, sent_at, confirmed_at, finalized_at,
+            status, error_message, compute_units_consumed, slot
+        FROM autodeploy_txs 
+        WHERE signature = ?
+        "#)
+    .bind(signature)
+    .fetch_optional(pool)
+    .await?;
+    
+    let tx = row.map(|row| {
+        use sqlx::Row;
+        AutodeployTx {
+            id: row.get("id"),
+            signature: row.get("signature"),
+            manager_key: row.get("manager_key"),
+            deployer_key: row.get("deployer_key"),
+            auth_id: row.get("auth_id"),
+            round_id: row.get("round_id"),
+            amount_per_square: row.get("amount_per_square"),
+            squares_mask: row.get("squares_mask"),
+            num_squares: row.get("num_squares"),
+            total_deployed: row.get("total_deployed"),
+            deployer_fee: row.get("deployer_fee"),
+            protocol_fee: row.get("protocol_fee"),
+            priority_fee: row.get("priority_fee"),
+            jito_tip: row.get("jito_tip"),
+            last_valid_blockheight: row.get("last_valid_blockheight"),
+            sent_at: row.get("sent_at"),
+            confirmed_at: row.get("confirmed_at"),
+            finalized_at: row.get("finalized_at"),
+            status: row.get("status"),
+            error_message: row.get("error_message"),
+            compute_units_consumed: row.get("compute_units_consumed"),
+            slot: row.get("slot"),
+        }
+    });
+    
+    Ok(tx)
+}
+
 /// Get transaction stats for a time range
 pub async fn get_tx_stats(
     pool: &Pool<Sqlite>,
@@ -421,3 +668,93 @@ pub struct TxStats {
     pub total_deployer_fee: u64,
     pub total_protocol_fee: u64,
 }
+
+/// Aggregated outcomes for one square
+#[derive(Debug, Clone, Default)]
+pub struct SquareStats {
+    pub square: u8,
+    pub rounds: u64,
+    pub wins: u64,
+    pub avg_deployed: f64,
+    pub avg_miners: f64,
+}
+
+impl SquareStats {
+    /// Fraction of recorded rounds this square won (0.0 when none recorded)
+    pub fn win_rate(&self) -> f64 {
+        if self.rounds == 0 {
+            return 0.0;
+        }
+        self.wins as f64 / self.rounds as f64
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    async fn insert_round_tx(pool: &Pool<Sqlite>, signature: &str, round_id: u64) {
+        insert_tx(pool, signature, "manager", "deployer", 0, round_id, 2_800, 1, 1, 2_800, 0, 0, 0, 0, 100, 0)
+            .await
+            .unwrap();
+        insert_tx_message(pool, signature, &[1, 2, 3]).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_retention_keeps_only_recent_rounds() {
+        let db_path = std::env::temp_dir().join(format!("evore-crank-retention-{}.db", std::process::id()));
+        let pool = init_db(&db_path).await.unwrap();
+
+        for (signature, round_id) in [("old-1", 1), ("old-2", 2), ("recent-9", 9), ("recent-10", 10)] {
+            insert_round_tx(&pool, signature, round_id).await;
+            update_tx_confirmed(&pool, signature, 0, 0, None).await.unwrap();
+        }
+        // Still awaiting confirmation, kept however old it is
+        insert_round_tx(&pool, "old-pending", 1).await;
+
+        assert_eq!(prune_old_rounds(&pool, 1).await.unwrap(), 2);
+        for signature in ["old-1", "old-2"] {
+            assert!(get_tx_by_signature(&pool, signature).await.unwrap().is_none());
+            assert!(get_tx_message(&pool, signature).await.unwrap().is_none(), "stored message pruned too");
+        }
+        for signature in ["recent-9", "recent-10", "old-pending"] {
+            assert!(get_tx_by_signature(&pool, signature).await.unwrap().is_some());
+            assert!(get_tx_message(&pool, signature).await.unwrap().is_some());
+        }
+
+        compact(&pool).await.unwrap();
+        assert_eq!(prune_old_rounds(&pool, 1).await.unwrap(), 0);
+
+        pool.close().await;
+        std::fs::remove_file(&db_path).ok();
+    }
+
+    #[tokio::test]
+    async fn test_square_outcomes_aggregate_per_square() {
+        let db_path = std::env::temp_dir().join(format!("evore-crank-squares-{}.db", std::process::id()));
+        let pool = init_db(&db_path).await.unwrap();
+
+        // Square 3 wins rounds 1 and 2, square 7 wins round 3
+        for round_id in 1..=3 {
+            record_square_outcome(&pool, round_id, 3, 1_000 * round_id, 2, round_id != 3).await.unwrap();
+            record_square_outcome(&pool, round_id, 7, 4_000, 4, round_id == 3).await.unwrap();
+        }
+        // Re-recording a round replaces it rather than counting it twice
+        record_square_outcome(&pool, 3, 7, 4_000, 6, true).await.unwrap();
+
+        let stats = get_square_stats(&pool).await.unwrap();
+        assert_eq!(stats.len(), 2);
+
+        assert_eq!((stats[0].square, stats[0].rounds, stats[0].wins), (3, 3, 2));
+        assert_eq!(stats[0].avg_deployed, 2_000.0);
+        assert!((stats[0].win_rate() - 2.0 / 3.0).abs() < 1e-9);
+
+        assert_eq!((stats[1].square, stats[1].rounds, stats[1].wins), (7, 3, 1));
+        assert_eq!(stats[1].avg_miners, (4.0 + 4.0 + 6.0) / 3.0);
+
+        assert_eq!(SquareStats::default().win_rate(), 0.0);
+
+        pool.close().await;
+        std::fs::remove_file(&db_path).ok();
+    }
+}
@@ -0,0 +1,110 @@
+//! What to deploy for one miner
+//!
+//! `run_strategy`, the legacy batched deploy paths and the pipeline's deployer
+//! batcher all describe a deploy with a `DeployPlan`, so the amount, squares,
+//! checkpoint and recycle decisions travel together and the instructions,
+//! fees and compute budget are derived from one place.
+
+use evore::consts::DEPLOY_FEE;
+use evore::instruction::{mm_autocheckpoint, mm_autodeploy, mm_full_autodeploy, recycle_sol};
+use solana_sdk::{instruction::Instruction, pubkey::Pubkey};
+
+use crate::config::DeployerInfo;
+use crate::lut::get_miner_auth_pda;
+
+/// One miner's deploy for a round
+#[derive(Debug, Clone)]
+pub struct DeployPlan {
+    pub deployer: DeployerInfo,
+    pub auth_id: u64,
+    pub round_id: u64,
+    /// Lamports deployed on each square in `squares_mask`
+    pub amount: u64,
+    pub squares_mask: u32,
+    /// Earlier round to checkpoint ahead of the deploy (None = already checkpointed)
+    pub checkpoint_round: Option<u64>,
+    /// Recycle SOL rewards ahead of the deploy
+    pub needs_recycle: bool,
+}
+
+impl DeployPlan {
+    pub fn new(deployer: DeployerInfo, auth_id: u64, round_id: u64, amount: u64, squares_mask: u32) -> Self {
+        Self {
+            deployer,
+            auth_id,
+            round_id,
+            amount,
+            squares_mask,
+            checkpoint_round: None,
+            needs_recycle: false,
+        }
+    }
+
+    /// Checkpoint `round` first and recycle whatever it settles
+    pub fn with_checkpoint(mut self, round: Option<u64>) -> Self {
+        self.checkpoint_round = round;
+        self.needs_recycle = round.is_some();
+        self
+    }
+
+    pub fn needs_checkpoint(&self) -> bool {
+        self.checkpoint_round.is_some()
+    }
+
+    pub fn manager(&self) -> Pubkey {
+        self.deployer.manager_address
+    }
+
+    pub fn miner_auth(&self) -> Pubkey {
+        get_miner_auth_pda(self.deployer.manager_address, self.auth_id)
+    }
+
+    pub fn num_squares(&self) -> u32 {
+        self.squares_mask.count_ones()
+    }
+
+    /// Lamports landing on the board
+    pub fn total_deployed(&self) -> u64 {
+        self.amount * u64::from(self.num_squares())
+    }
+
+    /// bps + flat fee the deployer charges for this deploy
+    pub fn deployer_fee(&self) -> u64 {
+        self.total_deployed() * self.deployer.bps_fee / 10_000 + self.deployer.flat_fee
+    }
+
+    /// Deployer fee plus the protocol's deploy fee
+    pub fn total_fees(&self) -> u64 {
+        self.deployer_fee() + DEPLOY_FEE
+    }
+
+    /// Separate checkpoint / recycle instructions to run ahead of `autodeploy_ix`
+    pub fn settle_ixs(&self, signer: Pubkey) -> Vec<Instruction> {
+        let mut ixs = Vec::new();
+        if let Some(round) = self.checkpoint_round {
+            ixs.push(mm_autocheckpoint(signer, self.manager(), round, self.auth_id));
+        }
+        if self.needs_recycle {
+            ixs.push(recycle_sol(signer, self.manager(), self.auth_id));
+        }
+        ixs
+    }
+
+    /// mm_autodeploy alone (pair with `settle_ixs`)
+    pub fn autodeploy_ix(&self, signer: Pubkey) -> Instruction {
+        mm_autodeploy(signer, self.manager(), self.auth_id, self.round_id, self.amount, self.squares_mask)
+    }
+
+    /// mm_full_autodeploy: checkpoint (current round when none is pending), recycle and deploy
+    pub fn full_autodeploy_ix(&self, signer: Pubkey) -> Instruction {
+        mm_full_autodeploy(
+            signer,
+            self.manager(),
+            self.auth_id,
+            self.round_id,
+            self.checkpoint_round.unwrap_or(self.round_id),
+            self.amount,
+            self.squares_mask,
+        )
+    }
+}
@@ -0,0 +1,85 @@
+//! Slots-before-end deploy window
+//!
+//! Deploying at exactly DEPLOY_SLOTS_BEFORE_END every round makes the crank's
+//! timing trivially predictable. With jitter configured, each round's trigger
+//! is drawn from `slots_before_end ± jitter_slots` (never below the minimum
+//! deploy slots). The draw is a pure function of the seed and round id, so a
+//! round keeps the same trigger across polls and tests can reproduce it.
+
+/// When the slots-before-end trigger fires in each round
+#[derive(Debug, Clone, Copy)]
+pub struct DeployWindow {
+    slots_before_end: u64,
+    jitter_slots: u64,
+    min_slots: u64,
+    seed: u64,
+}
+
+impl DeployWindow {
+    pub fn new(slots_before_end: u64, jitter_slots: u64, min_slots: u64, seed: u64) -> Self {
+        Self { slots_before_end, jitter_slots, min_slots, seed }
+    }
+
+    /// Lowest and highest trigger (slots before end) a round can draw
+    pub fn band(&self) -> (u64, u64) {
+        let low = self.slots_before_end.saturating_sub(self.jitter_slots).max(self.min_slots);
+        let high = self.slots_before_end.saturating_add(self.jitter_slots).max(low);
+        (low, high)
+    }
+
+    /// Slots before end at which `round_id` deploys
+    pub fn trigger_slots(&self, round_id: u64) -> u64 {
+        if self.jitter_slots == 0 {
+            return self.slots_before_end;
+        }
+        let (low, high) = self.band();
+        let span = high - low + 1;
+        low + splitmix64(self.seed ^ round_id) % span
+    }
+
+    /// Whether `round_id` has reached its deploy trigger
+    pub fn is_open(&self, round_id: u64, slots_remaining: u64) -> bool {
+        slots_remaining <= self.trigger_slots(round_id)
+    }
+}
+
+/// SplitMix64 finalizer, spreads consecutive round ids across the band
+pub(crate) fn splitmix64(x: u64) -> u64 {
+    let mut z = x.wrapping_add(0x9E37_79B9_7F4A_7C15);
+    z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+    z ^ (z >> 31)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashSet;
+
+    #[test]
+    fn test_trigger_slot_varies_within_band() {
+        let window = DeployWindow::new(150, 40, 10, 42);
+        let triggers: Vec<u64> = (1000..1200).map(|round| window.trigger_slots(round)).collect();
+
+        assert!(triggers.iter().all(|t| (110..=190).contains(t)), "trigger outside 150 ± 40");
+        let distinct: HashSet<_> = triggers.iter().collect();
+        assert!(distinct.len() > 20, "trigger should vary across rounds, got {} values", distinct.len());
+
+        // Same seed and round, same trigger
+        let again = DeployWindow::new(150, 40, 10, 42);
+        assert_eq!(again.trigger_slots(1234), window.trigger_slots(1234));
+    }
+
+    #[test]
+    fn test_no_jitter_and_min_slots_floor() {
+        let fixed = DeployWindow::new(150, 0, 10, 7);
+        assert!((0..50).all(|round| fixed.trigger_slots(round) == 150));
+        assert!(fixed.is_open(3, 150));
+        assert!(!fixed.is_open(3, 151));
+
+        // The band never reaches below the minimum deploy slots
+        let wide = DeployWindow::new(30, 100, 10, 7);
+        assert_eq!(wide.band(), (10, 130));
+        assert!((0..200).all(|round| wide.trigger_slots(round) >= 10));
+    }
+}
@@ -0,0 +1,110 @@
+//! Entropy readiness trigger
+//!
+//! Watches the board's entropy `Var` account and reports when it has been
+//! committed for the current round, so the run loop can deploy on that
+//! transition instead of a fixed number of slots before the round ends.
+
+use evore::entropy_api::{self, Var};
+use evore::ore_api::{board_pda, Board};
+use solana_sdk::pubkey::Pubkey;
+use steel::AccountDeserialize;
+use tracing::info;
+
+use crate::crank::CrankError;
+use crate::rpc::RpcProvider;
+
+/// Entropy var used by the ORE board (id 0)
+pub fn entropy_var_address() -> Pubkey {
+    entropy_api::var_pda(board_pda().0, 0).0
+}
+
+/// The var has a provider commit and samples at the end of this board's round
+pub fn var_ready_for_round(var: &Var, board_end_slot: u64) -> bool {
+    var.end_at == board_end_slot && var.commit != [0u8; 32]
+}
+
+/// Polls the entropy var and remembers which round it last saw become ready
+pub struct EntropyMonitor {
+    var_address: Pubkey,
+    ready_round: Option<u64>,
+}
+
+impl EntropyMonitor {
+    pub fn new() -> Self {
+        Self {
+            var_address: entropy_var_address(),
+            ready_round: None,
+        }
+    }
+
+    /// Fetch the var and report whether the current round's entropy is ready
+    pub fn poll(&mut self, rpc_client: &dyn RpcProvider, board: &Board) -> Result<bool, CrankError> {
+        let account = rpc_client.get_account(&self.var_address)?;
+        let var = Var::try_from_bytes(&account.data)
+            .map_err(|e| CrankError::Deserialize(format!("{:?}", e)))?;
+        let ready = var_ready_for_round(var, board.end_slot);
+        if self.observe(board.round_id, ready) {
+            info!(
+                "Entropy ready for round {} (var end_at {}), triggering deploy",
+                board.round_id, var.end_at
+            );
+        }
+        Ok(ready)
+    }
+
+    /// Record a readiness reading, true only on the first ready reading of a round
+    pub fn observe(&mut self, round_id: u64, ready: bool) -> bool {
+        if !ready || self.ready_round == Some(round_id) {
+            return false;
+        }
+        self.ready_round = Some(round_id);
+        true
+    }
+}
+
+impl Default for EntropyMonitor {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rpc::mock::MockRpc;
+    use solana_sdk::account::Account;
+    use steel::Zeroable;
+
+    fn var_account(var: &Var) -> Account {
+        let mut data = (entropy_api::EntropyAccount::Var as u64).to_le_bytes().to_vec();
+        data.extend_from_slice(var.to_bytes());
+        Account { lamports: 1_000_000, data, owner: entropy_api::PROGRAM_ID, executable: false, rent_epoch: 0 }
+    }
+
+    #[test]
+    fn test_var_turning_ready_triggers_deploy_once() {
+        let board = Board { round_id: 7, start_slot: 1_000, end_slot: 1_150, epoch_id: 0 };
+        let mut monitor = EntropyMonitor::new();
+
+        // Opened for this round, provider hasn't committed yet
+        let mut var = Var::zeroed();
+        var.end_at = board.end_slot;
+        let rpc = MockRpc::default().with_account(entropy_var_address(), var_account(&var));
+        assert!(!monitor.poll(&rpc, &board).unwrap());
+        assert!(!monitor.observe(board.round_id, false));
+
+        // Commit lands: the first ready reading triggers, later ones don't
+        var.commit = [9; 32];
+        let rpc = MockRpc::default().with_account(entropy_var_address(), var_account(&var));
+        assert!(monitor.poll(&rpc, &board).unwrap());
+        assert_eq!(monitor.ready_round, Some(7));
+        assert!(!monitor.observe(board.round_id, true), "already triggered this round");
+
+        // A commit for another round's end slot isn't ready
+        var.end_at = board.end_slot + 150;
+        let rpc = MockRpc::default().with_account(entropy_var_address(), var_account(&var));
+        assert!(!monitor.poll(&rpc, &board).unwrap());
+
+        assert!(monitor.observe(8, true), "next round triggers again");
+    }
+}
@@ -0,0 +1,81 @@
+//! Human-readable amounts for crank output
+//!
+//! Everything on-chain is in integer base units (lamports, ORE grains). These helpers
+//! render them as decimal SOL/ORE strings without going through floats, so large
+//! balances print exactly and small ones don't round to zero.
+
+use solana_client::rpc_client::RpcClient;
+use solana_sdk::{program_pack::Pack, pubkey::Pubkey};
+use spl_token::state::Mint;
+use tracing::warn;
+
+/// Decimals of native SOL (lamports per SOL = 10^9)
+pub const SOL_DECIMALS: u8 = 9;
+
+/// ORE mint decimals, used when the mint can't be read
+pub const ORE_DECIMALS_FALLBACK: u8 = 11;
+
+/// Render `amount` base units with `decimals` places, trimming trailing zeros ("1.5", "0", "0.0000028")
+pub fn format_units(amount: u64, decimals: u8) -> String {
+    if decimals == 0 {
+        return amount.to_string();
+    }
+
+    let scale = 10u128.pow(decimals as u32);
+    let amount = amount as u128;
+    let whole = amount / scale;
+    let frac = amount % scale;
+    if frac == 0 {
+        return whole.to_string();
+    }
+
+    let frac = format!("{:0width$}", frac, width = decimals as usize);
+    format!("{}.{}", whole, frac.trim_end_matches('0'))
+}
+
+/// Lamports as SOL, e.g. "1.5 SOL"
+pub fn format_sol(lamports: u64) -> String {
+    format!("{} SOL", format_units(lamports, SOL_DECIMALS))
+}
+
+/// ORE base units as ORE, e.g. "0.25 ORE"
+pub fn format_ore(amount: u64, decimals: u8) -> String {
+    format!("{} ORE", format_units(amount, decimals))
+}
+
+/// Read the ORE mint's decimals, falling back to ORE_DECIMALS_FALLBACK if the mint can't be fetched
+pub fn fetch_ore_decimals(rpc_client: &RpcClient) -> u8 {
+    fetch_mint_decimals(rpc_client, &evore::ore_api::MINT_ADDRESS).unwrap_or_else(|e| {
+        warn!("Failed to read ORE mint decimals ({}), assuming {}", e, ORE_DECIMALS_FALLBACK);
+        ORE_DECIMALS_FALLBACK
+    })
+}
+
+/// Read an SPL mint's decimals
+pub fn fetch_mint_decimals(rpc_client: &RpcClient, mint: &Pubkey) -> Result<u8, String> {
+    let account = rpc_client.get_account(mint).map_err(|e| e.to_string())?;
+    let mint = Mint::unpack(&account.data).map_err(|e| e.to_string())?;
+    Ok(mint.decimals)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format_sol_and_ore_amounts() {
+        assert_eq!(format_sol(0), "0 SOL");
+        assert_eq!(format_sol(1), "0.000000001 SOL");
+        assert_eq!(format_sol(2_800), "0.0000028 SOL");
+        assert_eq!(format_sol(1_500_000_000), "1.5 SOL");
+        assert_eq!(format_sol(1_000_000_000), "1 SOL");
+        // Exact at the top of the range, no float rounding
+        assert_eq!(format_sol(u64::MAX), "18446744073.709551615 SOL");
+
+        assert_eq!(format_ore(0, ORE_DECIMALS_FALLBACK), "0 ORE");
+        assert_eq!(format_ore(25_000_000_000, ORE_DECIMALS_FALLBACK), "0.25 ORE");
+        assert_eq!(format_ore(100_000_000_000, ORE_DECIMALS_FALLBACK), "1 ORE");
+        assert_eq!(format_ore(u64::MAX, ORE_DECIMALS_FALLBACK), "184467440.73709551615 ORE");
+        assert_eq!(format_ore(123, 0), "123 ORE");
+    }
+}
@@ -0,0 +1,156 @@
+//! First-run setup (`init`)
+//!
+//! Walks a new operator from a funded keypair to a crankable setup: a manager
+//! owned by the deploy authority with a deployer at the chosen fees, the shared
+//! LUT, and a `.env` the other commands pick up. Each on-chain step is read back
+//! before moving on, and nothing is sent if the `.env` would be clobbered.
+
+use std::path::{Path, PathBuf};
+
+use evore::state::deployer_pda;
+use solana_sdk::{pubkey::Pubkey, signature::Keypair, signer::Signer};
+use tracing::info;
+
+use crate::config::Config;
+use crate::crank::{Crank, CrankError};
+use crate::lut::LutRegistry;
+
+/// Balance the deploy authority needs to pay rent for the manager, deployer and LUT
+const MIN_INIT_BALANCE_LAMPORTS: u64 = 20_000_000;
+
+/// What `init` creates
+#[derive(Debug, Clone)]
+pub struct InitOptions {
+    pub bps_fee: u64,
+    pub flat_fee: u64,
+    pub max_per_round: u64,
+    /// Where the generated `.env` is written
+    pub env_path: PathBuf,
+    /// Overwrite `env_path` if it already exists
+    pub force: bool,
+}
+
+/// Accounts created by `init`
+#[derive(Debug, Clone, Copy)]
+pub struct InitReport {
+    pub manager: Pubkey,
+    pub deployer: Pubkey,
+    pub shared_lut: Pubkey,
+}
+
+/// Create the manager, deployer and shared LUT, then write the `.env`
+pub async fn run(crank: &Crank, config: &Config, opts: &InitOptions) -> Result<InitReport, CrankError> {
+    if opts.env_path.exists() && !opts.force {
+        return Err(CrankError::Parse(format!(
+            "{} already exists (pass --force to overwrite)",
+            opts.env_path.display()
+        )));
+    }
+
+    let authority = crank.deploy_authority_pubkey();
+    let balance = crank.rpc_client().get_balance(&authority)
+        .map_err(|e| CrankError::Rpc(e.to_string()))?;
+    if balance < MIN_INIT_BALANCE_LAMPORTS {
+        return Err(CrankError::Send(format!(
+            "deploy authority {} holds {} lamports, init needs at least {}",
+            authority, balance, MIN_INIT_BALANCE_LAMPORTS
+        )));
+    }
+    info!("[1/4] Deploy authority {} funded ({} lamports)", authority, balance);
+
+    let manager = Keypair::new();
+    let sig = crank
+        .create_manager_and_deployer(&manager, opts.bps_fee, opts.flat_fee, opts.max_per_round)
+        .await?;
+    let deployer = deployer_pda(manager.pubkey()).0;
+    let created = crank.refresh_deployers().await?;
+    if !created.iter().any(|d| d.deployer_address == deployer) {
+        return Err(CrankError::Deserialize(format!("deployer {} not found after {}", deployer, sig)));
+    }
+    info!("[2/4] Created manager {} and deployer {} ({})", manager.pubkey(), deployer, sig);
+
+    let mut registry = LutRegistry::new(config.rpc_client(), authority);
+    registry.load_all_luts().map_err(|e| CrankError::Lut(e.to_string()))?;
+    let shared_lut = crank.ensure_shared_lut(&mut registry).await?;
+    if !registry.get_missing_shared_addresses().is_empty() {
+        return Err(CrankError::Lut(format!("shared LUT {} is missing static accounts", shared_lut)));
+    }
+    info!("[3/4] Shared LUT {} ready", shared_lut);
+
+    let report = InitReport { manager: manager.pubkey(), deployer, shared_lut };
+    write_env(&opts.env_path, config, &report)?;
+    info!("[4/4] Wrote {}", opts.env_path.display());
+
+    Ok(report)
+}
+
+/// `.env` contents for the current settings and the accounts `init` created
+pub fn render_env(config: &Config, report: &InitReport) -> String {
+    format!(
+        "# Generated by `evore-crank init`\n\
+         # Manager: {}\n\
+         # Deployer: {}\n\
+         RPC_URL={}\n\
+         DEPLOY_AUTHORITY_KEYPAIR={}\n\
+         DATABASE_PATH={}\n\
+         PRIORITY_FEE={}\n\
+         \n\
+         # Shared LUT (auto-discovered by `run`, used by the legacy LUT commands)\n\
+         LUT_ADDRESS={}\n",
+        report.manager,
+        report.deployer,
+        config.rpc_url,
+        config.keypair_path.display(),
+        config.db_path.display(),
+        config.priority_fee,
+        report.shared_lut,
+    )
+}
+
+fn write_env(path: &Path, config: &Config, report: &InitReport) -> Result<(), CrankError> {
+    std::fs::write(path, render_env(config, report))
+        .map_err(|e| CrankError::Parse(format!("failed to write {}: {}", path.display(), e)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::crank::create_manager_and_deployer_ixs;
+    use clap::Parser;
+
+    #[test]
+    fn test_init_creates_crankable_manager_and_writes_env() {
+        let (authority, manager) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let deployer = deployer_pda(manager).0;
+
+        // The manager is ours and its deployer names us as deploy authority
+        let ixs = create_manager_and_deployer_ixs(authority, manager, 250, 715, 0);
+        assert_eq!(ixs.len(), 2);
+        assert!(ixs.iter().all(|ix| ix.program_id == evore::id()));
+        assert_eq!(ixs[0].accounts[0].pubkey, authority);
+        assert_eq!(ixs[0].accounts[1].pubkey, manager);
+        assert!(ixs[0].accounts[1].is_signer, "the new manager keypair signs its creation");
+        assert_eq!(ixs[1].accounts[1].pubkey, manager);
+        assert_eq!(ixs[1].accounts[2].pubkey, deployer);
+        assert_eq!(ixs[1].accounts[3].pubkey, authority);
+
+        let config = Config::try_parse_from([
+            "evore-crank",
+            "--keypair-path", "operator.json",
+            "--rpc-url", "http://127.0.0.1:8899",
+        ])
+        .unwrap();
+        let report = InitReport { manager, deployer, shared_lut: Pubkey::new_unique() };
+        let path = std::env::temp_dir().join(format!("evore-crank-init-{}.env", manager));
+
+        write_env(&path, &config, &report).unwrap();
+        let env = std::fs::read_to_string(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        assert!(env.contains(&format!("# Manager: {}\n", manager)));
+        assert!(env.contains(&format!("# Deployer: {}\n", deployer)));
+        assert!(env.contains("RPC_URL=http://127.0.0.1:8899\n"));
+        assert!(env.contains("DEPLOY_AUTHORITY_KEYPAIR=operator.json\n"));
+        assert!(env.contains(&format!("LUT_ADDRESS={}\n", report.shared_lut)));
+    }
+}
@@ -12,11 +12,11 @@
 //! - miner_luts: HashMap<miner_auth_pda, lut_address> for quick lookup
 
 use evore::{
-    ore_api::{board_pda, config_pda, miner_pda, automation_pda, PROGRAM_ID as ORE_PROGRAM_ID, TREASURY_ADDRESS},
+    ore_api::{board_pda, config_pda, automation_pda, PROGRAM_ID as ORE_PROGRAM_ID, TREASURY_ADDRESS},
     entropy_api::{self, PROGRAM_ID as ENTROPY_PROGRAM_ID},
-    state::{deployer_pda, managed_miner_auth_pda},
     consts::FEE_COLLECTOR,
 };
+
 use solana_sdk::address_lookup_table::{
     instruction::{create_lookup_table, extend_lookup_table, deactivate_lookup_table, close_lookup_table},
     state::AddressLookupTable,
@@ -24,7 +24,6 @@ use solana_sdk::address_lookup_table::{
 use solana_client::rpc_client::RpcClient;
 use solana_sdk::{
     address_lookup_table::AddressLookupTableAccount,
-    commitment_config::CommitmentConfig,
     instruction::Instruction,
     message::{v0::Message as V0Message, VersionedMessage},
     pubkey::Pubkey,
@@ -33,8 +32,26 @@ use solana_sdk::{
     transaction::VersionedTransaction,
 };
 use std::collections::{HashMap, HashSet};
+use std::path::Path;
 use tracing::{info, debug, warn};
 
+use crate::lut_cache::{CachedLut, LutCacheFile, LutStamp};
+use crate::pda_cache::{self, MinerPdas};
+
+/// getMultipleAccounts accepts at most this many addresses
+const MAX_MULTIPLE_ACCOUNTS: usize = 100;
+
+/// Size of the AddressLookupTable header, everything a stamp needs
+const LUT_META_SIZE: usize = 56;
+
+/// Header fields that change whenever a LUT's addresses could have
+fn stamp_of(lookup_table: &AddressLookupTable) -> LutStamp {
+    LutStamp {
+        last_extended_slot: lookup_table.meta.last_extended_slot,
+        deactivation_slot: lookup_table.meta.deactivation_slot,
+    }
+}
+
 /// Get the static shared accounts (accounts that don't change between rounds)
 /// These are shared by mm_autodeploy, mm_autocheckpoint, and recycle_sol instructions.
 ///
@@ -70,9 +87,8 @@ pub fn get_static_shared_accounts(deploy_authority: Pubkey) -> Vec<Pubkey> {
 /// Get the 5 accounts specific to a miner (for per-miner LUT)
 /// These are derived from manager + auth_id
 pub fn get_miner_accounts(manager: Pubkey, auth_id: u64) -> Vec<Pubkey> {
-    let (deployer_addr, _) = deployer_pda(manager);
-    let (managed_miner_auth, _) = managed_miner_auth_pda(manager, auth_id);
-    let (ore_miner, _) = miner_pda(managed_miner_auth);
+    let deployer_addr = pda_cache::deployer_address(manager);
+    let MinerPdas { managed_miner_auth, ore_miner } = pda_cache::miner_pdas(manager, auth_id);
     let (automation, _) = automation_pda(managed_miner_auth);
 
     vec![
@@ -86,8 +102,67 @@ pub fn get_miner_accounts(manager: Pubkey, auth_id: u64) -> Vec<Pubkey> {
 
 /// Get the miner_auth PDA for a manager/auth_id (used as key in miner_luts map)
 pub fn get_miner_auth_pda(manager: Pubkey, auth_id: u64) -> Pubkey {
-    let (managed_miner_auth, _) = managed_miner_auth_pda(manager, auth_id);
-    managed_miner_auth
+    pda_cache::miner_pdas(manager, auth_id).managed_miner_auth
+}
+
+/// Difference between the addresses a LUT should hold and what it holds on chain
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct LutDiff {
+    /// Expected but not in the LUT (can be added with an extend)
+    pub missing: Vec<Pubkey>,
+    /// In the LUT but not expected (LUT entries can't be removed, only rebuilt)
+    pub extra: Vec<Pubkey>,
+}
+
+impl LutDiff {
+    /// Compare a LUT's addresses against the expected set, keeping the expected order
+    pub fn between(expected: &[Pubkey], actual: &[Pubkey]) -> Self {
+        Self {
+            missing: expected.iter().filter(|a| !actual.contains(a)).copied().collect(),
+            extra: actual.iter().filter(|a| !expected.contains(a)).copied().collect(),
+        }
+    }
+
+    pub fn is_clean(&self) -> bool {
+        self.missing.is_empty() && self.extra.is_empty()
+    }
+}
+
+/// Switches the run loop to no-LUT batching once LUT creation keeps failing
+/// (e.g. the deploy authority can't pay LUT rent), instead of losing the round
+#[derive(Debug, Clone, Default)]
+pub struct LutFallback {
+    /// Consecutive LUT-creation failures before falling back (0 = never)
+    threshold: usize,
+    failures: usize,
+    /// Round the fallback is engaged for
+    engaged_round: Option<u64>,
+}
+
+impl LutFallback {
+    pub fn new(threshold: usize) -> Self {
+        Self { threshold, ..Self::default() }
+    }
+
+    /// Count a failed LUT creation in `round_id`; true once the fallback engages for that round
+    pub fn record_failure(&mut self, round_id: u64) -> bool {
+        self.failures += 1;
+        if self.threshold > 0 && self.failures >= self.threshold {
+            self.engaged_round = Some(round_id);
+        }
+        self.is_engaged(round_id)
+    }
+
+    /// LUTs are being created again, start counting from zero
+    pub fn record_success(&mut self) {
+        self.failures = 0;
+        self.engaged_round = None;
+    }
+
+    /// Whether `round_id` should skip LUTs and batch at the no-LUT size
+    pub fn is_engaged(&self, round_id: u64) -> bool {
+        self.engaged_round == Some(round_id)
+    }
 }
 
 /// LUT status information for validation and cleanup
@@ -116,15 +191,12 @@ pub struct LutRegistry {
     miner_luts: HashMap<Pubkey, Pubkey>,
     /// Cached LUT accounts for quick access
     lut_cache: HashMap<Pubkey, AddressLookupTableAccount>,
+    /// On-chain stamp each cached LUT was read at (missing for LUTs registered after creation)
+    lut_stamps: HashMap<Pubkey, LutStamp>,
 }
 
 impl LutRegistry {
-    pub fn new(rpc_url: &str, authority: Pubkey) -> Self {
-        let rpc_client = RpcClient::new_with_commitment(
-            rpc_url.to_string(),
-            CommitmentConfig::confirmed(),
-        );
-        
+    pub fn new(rpc_client: RpcClient, authority: Pubkey) -> Self {
         Self {
             rpc_client,
             authority,
@@ -132,6 +204,7 @@ impl LutRegistry {
             shared_lut_accounts: HashSet::new(),
             miner_luts: HashMap::new(),
             lut_cache: HashMap::new(),
+            lut_stamps: HashMap::new(),
         }
     }
     
@@ -174,6 +247,7 @@ impl LutRegistry {
         };
         
         self.lut_cache.insert(lut_address, lut_account.clone());
+        self.lut_stamps.insert(lut_address, stamp_of(&lookup_table));
         
         Ok(lut_account)
     }
@@ -233,40 +307,8 @@ impl LutRegistry {
                 }
             };
             
-            let addresses: Vec<Pubkey> = lookup_table.addresses.to_vec();
-            
-            // Cache the LUT
-            self.lut_cache.insert(lut_address, AddressLookupTableAccount {
-                key: lut_address,
-                addresses: addresses.clone(),
-            });
-            
-            // Determine if this is the shared LUT or a miner LUT
-            // Shared LUT should contain the static shared accounts
-            let static_accounts = get_static_shared_accounts(self.authority);
-            let has_all_static = static_accounts.iter().all(|acc| addresses.contains(acc));
-
-            if has_all_static && self.shared_lut.is_none() {
-                // This looks like the shared LUT
-                self.shared_lut = Some(lut_address);
-                for addr in &addresses {
-                    self.shared_lut_accounts.insert(*addr);
-                }
-                info!("  Identified shared LUT: {} ({} addresses)", lut_address, addresses.len());
-            } else if addresses.len() == 5 {
-                // This looks like a miner LUT (5 accounts per miner)
-                // miner_auth is at index 2 (after manager, deployer)
-                let miner_auth = addresses[2];
-                self.miner_luts.insert(miner_auth, lut_address);
-                debug!("  Identified miner LUT: {} for miner_auth {}", lut_address, miner_auth);
-            } else if addresses.len() == 6 || addresses.len() == 7 {
-                // Legacy LUT formats - will be marked invalid
-                let miner_auth = if addresses.len() == 6 { addresses[3] } else { addresses[4] };
-                debug!("  Legacy miner LUT ({} accounts): {} for miner_auth {} - will be marked invalid",
-                    addresses.len(), lut_address, miner_auth);
-            } else {
-                debug!("  Unknown LUT: {} ({} addresses)", lut_address, addresses.len());
-            }
+            self.lut_stamps.insert(lut_address, stamp_of(&lookup_table));
+            self.index_lut(lut_address, lookup_table.addresses.to_vec());
         }
         
         info!("Loaded {} miner LUTs", self.miner_luts.len());
@@ -274,6 +316,133 @@ impl LutRegistry {
         Ok(num_luts)
     }
     
+    /// Cache a LUT's addresses and file it as the shared LUT or a miner LUT
+    fn index_lut(&mut self, lut_address: Pubkey, addresses: Vec<Pubkey>) {
+        // Determine if this is the shared LUT or a miner LUT
+        // Shared LUT should contain the static shared accounts
+        let static_accounts = get_static_shared_accounts(self.authority);
+        let has_all_static = static_accounts.iter().all(|acc| addresses.contains(acc));
+
+        if has_all_static && self.shared_lut.is_none() {
+            // This looks like the shared LUT
+            self.shared_lut = Some(lut_address);
+            for addr in &addresses {
+                self.shared_lut_accounts.insert(*addr);
+            }
+            info!("  Identified shared LUT: {} ({} addresses)", lut_address, addresses.len());
+        } else if addresses.len() == 5 {
+            // This looks like a miner LUT (5 accounts per miner)
+            // miner_auth is at index 2 (after manager, deployer)
+            let miner_auth = addresses[2];
+            self.miner_luts.insert(miner_auth, lut_address);
+            debug!("  Identified miner LUT: {} for miner_auth {}", lut_address, miner_auth);
+        } else if addresses.len() == 6 || addresses.len() == 7 {
+            // Legacy LUT formats - will be marked invalid
+            let miner_auth = if addresses.len() == 6 { addresses[3] } else { addresses[4] };
+            debug!("  Legacy miner LUT ({} accounts): {} for miner_auth {} - will be marked invalid",
+                addresses.len(), lut_address, miner_auth);
+        } else {
+            debug!("  Unknown LUT: {} ({} addresses)", lut_address, addresses.len());
+        }
+
+        self.lut_cache.insert(lut_address, AddressLookupTableAccount {
+            key: lut_address,
+            addresses,
+        });
+    }
+
+    /// Load LUTs through the cache file at `cache_path`, or with a full scan when there is none
+    /// Returns the count of LUTs found
+    pub fn load_luts(&mut self, cache_path: Option<&Path>) -> Result<usize, LutError> {
+        let Some(path) = cache_path else {
+            return self.load_all_luts();
+        };
+
+        let count = match LutCacheFile::read(path, self.authority) {
+            Some(file) => self.load_from_cache(&file)?,
+            None => {
+                info!("No usable LUT cache at {}, scanning on-chain", path.display());
+                self.load_all_luts()?
+            }
+        };
+
+        let slot = self.rpc_client.get_slot().map_err(|e| LutError::Rpc(e.to_string()))?;
+        if let Err(e) = self.save_cache(path, slot) {
+            warn!("Failed to write LUT cache {}: {}", path.display(), e);
+        }
+        Ok(count)
+    }
+
+    /// Check each cached LUT's header against chain and re-read only the ones that changed
+    fn load_from_cache(&mut self, file: &LutCacheFile) -> Result<usize, LutError> {
+        let keys: Vec<Pubkey> = file.luts.iter().filter_map(|l| l.key()).collect();
+        let plan = file.plan(&self.fetch_stamps(&keys)?);
+        info!(
+            "LUT cache from slot {}: {} fresh, {} stale, {} gone",
+            file.slot, plan.fresh.len(), plan.stale.len(), plan.gone.len()
+        );
+
+        for lut in &file.luts {
+            let (Some(key), Some(addresses)) = (lut.key(), lut.pubkeys()) else { continue };
+            if plan.fresh.contains(&key) {
+                self.lut_stamps.insert(key, lut.stamp);
+                self.index_lut(key, addresses);
+            }
+        }
+
+        for chunk in plan.stale.chunks(MAX_MULTIPLE_ACCOUNTS) {
+            let accounts = self.rpc_client.get_multiple_accounts(chunk)
+                .map_err(|e| LutError::Rpc(e.to_string()))?;
+            for (lut_address, account) in chunk.iter().zip(accounts) {
+                let Some(account) = account else { continue };
+                match AddressLookupTable::deserialize(&account.data) {
+                    Ok(lookup_table) => {
+                        self.lut_stamps.insert(*lut_address, stamp_of(&lookup_table));
+                        self.index_lut(*lut_address, lookup_table.addresses.to_vec());
+                    }
+                    Err(e) => warn!("Failed to deserialize LUT {}: {:?}", lut_address, e),
+                }
+            }
+        }
+
+        info!("Loaded {} miner LUTs", self.miner_luts.len());
+        Ok(plan.fresh.len() + plan.stale.len())
+    }
+
+    /// Current header stamp of each LUT (None if the account is gone), fetching only the headers
+    fn fetch_stamps(&self, keys: &[Pubkey]) -> Result<HashMap<Pubkey, Option<LutStamp>>, LutError> {
+        let config = solana_client::rpc_config::RpcAccountInfoConfig {
+            encoding: Some(solana_account_decoder::UiAccountEncoding::Base64),
+            data_slice: Some(solana_account_decoder::UiDataSliceConfig { offset: 0, length: LUT_META_SIZE }),
+            ..Default::default()
+        };
+
+        let mut stamps = HashMap::with_capacity(keys.len());
+        for chunk in keys.chunks(MAX_MULTIPLE_ACCOUNTS) {
+            let accounts = self.rpc_client.get_multiple_accounts_with_config(chunk, config.clone())
+                .map_err(|e| LutError::Rpc(e.to_string()))?
+                .value;
+            for (key, account) in chunk.iter().zip(accounts) {
+                let stamp = account
+                    .and_then(|a| AddressLookupTable::deserialize(&a.data).ok().map(|lt| stamp_of(&lt)));
+                stamps.insert(*key, stamp);
+            }
+        }
+        Ok(stamps)
+    }
+
+    /// Write every known LUT to the cache file, as checked against chain at `slot`
+    pub fn save_cache(&self, path: &Path, slot: u64) -> Result<(), LutError> {
+        // LUTs registered after creation have no stamp yet; a zero stamp never matches, so
+        // they are re-read on the next load
+        let unknown = LutStamp { last_extended_slot: 0, deactivation_slot: 0 };
+        let mut luts: Vec<CachedLut> = self.lut_cache.values()
+            .map(|lut| CachedLut::new(lut.key, *self.lut_stamps.get(&lut.key).unwrap_or(&unknown), &lut.addresses))
+            .collect();
+        luts.sort_by(|a, b| a.address.cmp(&b.address));
+        LutCacheFile::new(self.authority, slot, luts).write(path)
+    }
+    
     /// Register a miner LUT (after creating it)
     pub fn register_miner_lut(&mut self, miner_auth: Pubkey, lut_address: Pubkey, addresses: Vec<Pubkey>) {
         self.miner_luts.insert(miner_auth, lut_address);
@@ -291,6 +460,31 @@ impl LutRegistry {
             .collect()
     }
     
+    /// Diff the shared LUT against the static shared accounts (None if no shared LUT is loaded)
+    pub fn verify_shared_lut(&self) -> Option<(Pubkey, LutDiff)> {
+        let shared = self.shared_lut?;
+        let lut = self.lut_cache.get(&shared)?;
+        Some((shared, LutDiff::between(&get_static_shared_accounts(self.authority), &lut.addresses)))
+    }
+    
+    /// Diff a miner's LUT against `get_miner_accounts` (None if the miner has no LUT)
+    ///
+    /// A LUT that lost or gained entries is no longer indexed as a miner LUT, so
+    /// fall back to any loaded non-shared LUT holding the miner_auth or manager
+    pub fn verify_miner_lut(&self, manager: Pubkey, auth_id: u64) -> Option<(Pubkey, LutDiff)> {
+        let expected = get_miner_accounts(manager, auth_id);
+        let miner_auth = get_miner_auth_pda(manager, auth_id);
+        let lut = self.miner_luts.get(&miner_auth)
+            .and_then(|addr| self.lut_cache.get(addr))
+            .or_else(|| {
+                self.lut_cache.values().find(|lut| {
+                    Some(lut.key) != self.shared_lut
+                        && (lut.addresses.contains(&miner_auth) || lut.addresses.contains(&manager))
+                })
+            })?;
+        Some((lut.key, LutDiff::between(&expected, &lut.addresses)))
+    }
+    
     /// Check if a miner has a LUT
     pub fn has_miner_lut(&self, miner_auth: &Pubkey) -> bool {
         self.miner_luts.contains_key(miner_auth)
@@ -551,12 +745,7 @@ pub struct LutManager {
 }
 
 impl LutManager {
-    pub fn new(rpc_url: &str, authority: Pubkey) -> Self {
-        let rpc_client = RpcClient::new_with_commitment(
-            rpc_url.to_string(),
-            CommitmentConfig::confirmed(),
-        );
-        
+    pub fn new(rpc_client: RpcClient, authority: Pubkey) -> Self {
         Self {
             rpc_client,
             authority,
@@ -728,8 +917,73 @@ pub enum LutError {
     Compile(String),
     #[error("Sign error: {0}")]
     Sign(String),
+    #[error("LUT cache error: {0}")]
+    Cache(String),
     #[error("LUT not deactivated yet")]
     NotDeactivated,
     #[error("LUT still in cooldown (deactivated at slot {0}, need to wait ~512 slots)")]
     StillInCooldown(u64),
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_lut_fallback_engages_after_repeated_failures() {
+        let mut fallback = LutFallback::new(3);
+        assert!(!fallback.record_failure(10));
+        assert!(!fallback.record_failure(11));
+        assert!(!fallback.is_engaged(12));
+        assert!(fallback.record_failure(12), "third failure should engage the fallback");
+        assert!(fallback.is_engaged(12));
+
+        // Only for the round it engaged in; the next round fails once more and stays on no-LUT
+        assert!(!fallback.is_engaged(13));
+        assert!(fallback.record_failure(13));
+
+        fallback.record_success();
+        assert!(!fallback.is_engaged(13));
+        assert!(!fallback.record_failure(14));
+    }
+
+    #[test]
+    fn test_verify_miner_lut_detects_missing_account() {
+        let authority = Pubkey::new_unique();
+        let manager = Pubkey::new_unique();
+        let expected = get_miner_accounts(manager, 0);
+        let mut registry = LutRegistry::new(RpcClient::new("http://localhost:8899".to_string()), authority);
+
+        let shared = Pubkey::new_unique();
+        let mut shared_addresses = get_static_shared_accounts(authority);
+        let stray = Pubkey::new_unique();
+        shared_addresses.push(stray);
+        registry.index_lut(shared, shared_addresses);
+
+        // Miner LUT that lost its automation account
+        let miner_lut = Pubkey::new_unique();
+        registry.index_lut(miner_lut, expected[..4].to_vec());
+        assert!(!registry.has_miner_lut(&expected[2]), "a 4-account LUT is not indexed as a miner LUT");
+
+        let (lut, diff) = registry.verify_miner_lut(manager, 0).expect("found through its miner_auth");
+        assert_eq!(lut, miner_lut);
+        assert_eq!(diff, LutDiff { missing: vec![expected[4]], extra: vec![] });
+
+        let (lut, diff) = registry.verify_shared_lut().unwrap();
+        assert_eq!(lut, shared);
+        assert_eq!(diff, LutDiff { missing: vec![], extra: vec![stray] });
+
+        // A complete LUT is clean, a miner without any LUT has nothing to verify
+        registry.register_miner_lut(expected[2], miner_lut, expected.clone());
+        assert!(registry.verify_miner_lut(manager, 0).unwrap().1.is_clean());
+        assert!(registry.verify_miner_lut(Pubkey::new_unique(), 0).is_none());
+    }
+
+    #[test]
+    fn test_lut_fallback_disabled_at_zero() {
+        let mut fallback = LutFallback::new(0);
+        for round in 0..10 {
+            assert!(!fallback.record_failure(round));
+        }
+    }
+}
@@ -0,0 +1,160 @@
+//! On-disk LUT registry cache
+//!
+//! `LutRegistry::load_all_luts` runs a getProgramAccounts scan over every
+//! lookup table, which gets slow once the authority owns many miner LUTs.
+//! The cache file keeps the last known contents of each LUT together with its
+//! on-chain stamp (last extended slot and deactivation slot). On startup only
+//! the 56-byte LUT headers are fetched; LUTs whose stamp still matches are
+//! taken from the file, the rest are re-read. Pubkeys are stored base58.
+
+use serde::{Deserialize, Serialize};
+use solana_sdk::pubkey::Pubkey;
+use std::collections::HashMap;
+use std::path::Path;
+use std::str::FromStr;
+
+use crate::lut::LutError;
+
+/// Bumped whenever the file layout changes; other versions are ignored
+const CACHE_VERSION: u32 = 1;
+
+/// Fields of a LUT header that change whenever its addresses could have changed
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LutStamp {
+    pub last_extended_slot: u64,
+    /// u64::MAX while the LUT is active
+    pub deactivation_slot: u64,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CachedLut {
+    pub address: String,
+    pub stamp: LutStamp,
+    pub addresses: Vec<String>,
+}
+
+impl CachedLut {
+    pub fn new(address: Pubkey, stamp: LutStamp, addresses: &[Pubkey]) -> Self {
+        Self {
+            address: address.to_string(),
+            stamp,
+            addresses: addresses.iter().map(|a| a.to_string()).collect(),
+        }
+    }
+
+    pub fn key(&self) -> Option<Pubkey> {
+        Pubkey::from_str(&self.address).ok()
+    }
+
+    /// None if any stored address is not valid base58
+    pub fn pubkeys(&self) -> Option<Vec<Pubkey>> {
+        self.addresses.iter().map(|a| Pubkey::from_str(a).ok()).collect()
+    }
+}
+
+/// Contents of the cache file
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LutCacheFile {
+    pub version: u32,
+    /// LUT authority the entries were scanned for
+    pub authority: String,
+    /// Slot the entries were last checked against chain
+    pub slot: u64,
+    pub luts: Vec<CachedLut>,
+}
+
+/// What to do with each cached LUT after comparing it against chain
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct CachePlan {
+    /// Stamp unchanged, the cached addresses can be used as is
+    pub fresh: Vec<Pubkey>,
+    /// Stamp changed, the LUT must be re-read
+    pub stale: Vec<Pubkey>,
+    /// The LUT account no longer exists
+    pub gone: Vec<Pubkey>,
+}
+
+impl LutCacheFile {
+    pub fn new(authority: Pubkey, slot: u64, luts: Vec<CachedLut>) -> Self {
+        Self { version: CACHE_VERSION, authority: authority.to_string(), slot, luts }
+    }
+
+    /// Reads the cache for `authority`; None if missing, unreadable, another version or another authority
+    pub fn read(path: &Path, authority: Pubkey) -> Option<Self> {
+        let raw = std::fs::read(path).ok()?;
+        let file: Self = serde_json::from_slice(&raw).ok()?;
+        (file.version == CACHE_VERSION && file.authority == authority.to_string()).then_some(file)
+    }
+
+    /// Writes the cache through a temp file so a crash never leaves it half written
+    pub fn write(&self, path: &Path) -> Result<(), LutError> {
+        let json = serde_json::to_vec_pretty(self).map_err(|e| LutError::Cache(e.to_string()))?;
+        let tmp = path.with_extension("tmp");
+        std::fs::write(&tmp, json).map_err(|e| LutError::Cache(e.to_string()))?;
+        std::fs::rename(&tmp, path).map_err(|e| LutError::Cache(e.to_string()))
+    }
+
+    /// Compares each cached LUT against its current on-chain stamp (None = account missing)
+    pub fn plan(&self, current: &HashMap<Pubkey, Option<LutStamp>>) -> CachePlan {
+        let mut plan = CachePlan::default();
+        for lut in &self.luts {
+            let Some(key) = lut.key() else { continue };
+            match current.get(&key) {
+                Some(None) => plan.gone.push(key),
+                Some(Some(stamp)) if *stamp == lut.stamp && lut.pubkeys().is_some() => plan.fresh.push(key),
+                _ => plan.stale.push(key),
+            }
+        }
+        plan
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn stamp(last_extended_slot: u64) -> LutStamp {
+        LutStamp { last_extended_slot, deactivation_slot: u64::MAX }
+    }
+
+    #[test]
+    fn test_warm_cache_skips_rescan_and_flags_changed_lut() {
+        let authority = Pubkey::new_unique();
+        let (shared, miner_a, miner_b) = (Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique());
+        let addrs: Vec<Pubkey> = (0..5).map(|_| Pubkey::new_unique()).collect();
+        let file = LutCacheFile::new(authority, 1_000, vec![
+            CachedLut::new(shared, stamp(10), &addrs),
+            CachedLut::new(miner_a, stamp(20), &addrs),
+            CachedLut::new(miner_b, stamp(30), &addrs),
+        ]);
+
+        let path = std::env::temp_dir().join(format!("evore-lut-cache-{}.json", Pubkey::new_unique()));
+        file.write(&path).unwrap();
+        let warm = LutCacheFile::read(&path, authority).expect("cache written for this authority");
+        assert_eq!(warm, file);
+        assert!(LutCacheFile::read(&path, Pubkey::new_unique()).is_none(), "other authority must rescan");
+        std::fs::remove_file(&path).unwrap();
+        assert!(LutCacheFile::read(&path, authority).is_none(), "missing cache must rescan");
+
+        // miner_a was extended since the cache was written, miner_b was closed
+        let current = HashMap::from([
+            (shared, Some(stamp(10))),
+            (miner_a, Some(stamp(25))),
+            (miner_b, None),
+        ]);
+        let plan = warm.plan(&current);
+        assert_eq!(plan.fresh, vec![shared]);
+        assert_eq!(plan.stale, vec![miner_a]);
+        assert_eq!(plan.gone, vec![miner_b]);
+
+        // A freshly deactivated LUT is stale too
+        let deactivated = HashMap::from([
+            (shared, Some(LutStamp { last_extended_slot: 10, deactivation_slot: 1_200 })),
+            (miner_a, Some(stamp(20))),
+            (miner_b, Some(stamp(30))),
+        ]);
+        let plan = warm.plan(&deactivated);
+        assert_eq!(plan.fresh, vec![miner_a, miner_b]);
+        assert_eq!(plan.stale, vec![shared]);
+    }
+}
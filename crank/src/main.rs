@@ -12,22 +12,34 @@
 //! Transaction batching is limited by Solana's 64 instruction trace limit,
 //! not transaction size. With checkpoint+recycle+deploy per miner, max ~5 deploys/tx.
 
+mod benchmark;
 mod config;
 mod crank;
 mod db;
+mod deploy_plan;
+mod deploy_window;
+mod entropy;
+mod format;
+mod init;
 mod lut;
+mod lut_cache;
 mod miner_cache;
+mod pda_cache;
 mod pipeline;
+mod poll_schedule;
+mod pot_scaling;
+mod rpc;
 mod sender;
+mod snapshot;
+mod strategy_chain;
 
 use clap::Parser;
 use config::Config;
 use lut::{LutManager, LutRegistry, get_miner_auth_pda};
 use solana_sdk::signature::Signer;
 use std::sync::Arc;
-use std::time::Duration;
 use tokio::sync::RwLock;
-use tracing::{error, info, warn, Level};
+use tracing::{debug, error, info, warn, Level};
 use tracing_subscriber::FmtSubscriber;
 
 // =============================================================================
@@ -40,15 +52,18 @@ const DEPLOY_AMOUNT_LAMPORTS: u64 = 2_800;
 /// Which auth_id to deploy for (each manager can have multiple managed miners)
 const AUTH_ID: u64 = 0;
 
-/// Squares mask - which squares to deploy to (0x1FFFFFF = all 25 squares)
-const SQUARES_MASK: u32 = 0x1FFFFFF;
-
 /// How many slots before round end to trigger deployment
 const DEPLOY_SLOTS_BEFORE_END: u64 = 150;
 
 /// Minimum slots remaining to attempt deployment (don't deploy too close to end)
 const MIN_SLOTS_TO_DEPLOY: u64 = 10;
 
+/// Skip the cycle if the board read trails the cluster by more than this many slots (lagging RPC)
+const MAX_BOARD_LAG_SLOTS: u64 = 25;
+
+/// Lamports the top-up command leaves on the deploy authority for its own transaction fees
+const TOP_UP_FEE_ALLOWANCE: u64 = 10_000_000;
+
 /// Maximum deployers to batch in one transaction without LUT
 const MAX_BATCH_SIZE_NO_LUT: usize = 2;
 
@@ -85,11 +100,34 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     let db_pool = db::init_db(&config.db_path).await?;
     
     // Create crank instance
-    let crank = crank::Crank::new(config.clone(), db_pool).await?;
+    let crank = crank::Crank::new(config.clone(), db_pool.clone()).await?;
     info!("Deploy authority: {}", crank.deploy_authority_pubkey());
     
     // Handle subcommand
     match config.command {
+        Some(config::Command::Init { bps_fee, flat_fee, max_per_round, ref out, force }) => {
+            info!("Initializing crank setup...");
+            let opts = init::InitOptions {
+                bps_fee,
+                flat_fee,
+                max_per_round,
+                env_path: out.clone(),
+                force,
+            };
+            match init::run(&crank, &config, &opts).await {
+                Ok(report) => {
+                    info!("✓ Manager: {}", report.manager);
+                    info!("✓ Deployer: {}", report.deployer);
+                    info!("✓ Shared LUT: {}", report.shared_lut);
+                    info!("Fund the managed miner, then start with: evore-crank run");
+                }
+                Err(e) => {
+                    error!("✗ Init failed: {}", e);
+                    return Err(e.into());
+                }
+            }
+            return Ok(());
+        }
         Some(config::Command::Test) => {
             info!("Running test transaction...");
             match crank.send_test_transaction().await {
@@ -103,18 +141,28 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
             }
             return Ok(());
         }
+        Some(config::Command::Benchmark { count }) => {
+            info!("Benchmarking {} test transactions...", count);
+            let report = benchmark::run(&crank, count).await;
+            report.log();
+            if report.samples.is_empty() {
+                return Err("no benchmark transaction confirmed".into());
+            }
+            return Ok(());
+        }
         Some(config::Command::List) => {
             info!("Finding deployers...");
             let deployers = crank.find_deployers().await?;
             
             // Also load LUT registry to show LUT status
-            let mut registry = LutRegistry::new(&config.rpc_url, crank.deploy_authority_pubkey());
+            let mut registry = LutRegistry::new(config.rpc_client(), crank.deploy_authority_pubkey());
             let _ = registry.load_all_luts();
             
             if deployers.is_empty() {
                 warn!("No deployers found where we are the deploy_authority");
                 warn!("Create a deployer with deploy_authority set to: {}", crank.deploy_authority_pubkey());
             } else {
+                let ore_decimals = format::fetch_ore_decimals(crank.rpc_client());
                 info!("Managing {} deployers:", deployers.len());
                 for d in &deployers {
                     let balance = crank.get_miner_balance(d, AUTH_ID).unwrap_or(0);
@@ -129,7 +177,14 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
                     info!("  Manager: {}", d.manager_address);
                     info!("    Deployer: {}", d.deployer_address);
                     info!("    Fee: {}", fee_str);
-                    info!("    Balance: {} lamports ({:.6} SOL)", balance, balance as f64 / 1_000_000_000.0);
+                    info!("    Balance: {} ({} lamports)", format::format_sol(balance), balance);
+                    if let Ok(Some((rewards_sol, rewards_ore))) = crank.get_miner_rewards(d.manager_address, AUTH_ID) {
+                        info!(
+                            "    Unclaimed: {}, {}",
+                            format::format_sol(rewards_sol),
+                            format::format_ore(rewards_ore, ore_decimals)
+                        );
+                    }
                     info!("    Miner LUT: {}", if has_lut { "✓" } else { "✗ (will create on run)" });
                 }
             }
@@ -144,6 +199,36 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
             
             return Ok(());
         }
+        Some(config::Command::VerifyFees) => {
+            info!("Verifying deployer fees against expected fees...");
+            let deployers = crank.find_deployers().await?;
+            if deployers.is_empty() {
+                warn!("No deployers found where we are the deploy_authority");
+                return Ok(());
+            }
+            
+            let mut mismatched = 0;
+            for d in &deployers {
+                let Some(mismatch) = d.fee_mismatch() else {
+                    continue;
+                };
+                mismatched += 1;
+                warn!("  ✗ Manager: {} (deployer {})", d.manager_address, d.deployer_address);
+                if mismatch.bps_exceeded {
+                    warn!("      bps_fee: {} > expected {}", d.bps_fee, d.expected_bps_fee);
+                }
+                if mismatch.flat_exceeded {
+                    warn!("      flat_fee: {} lamports > expected {} lamports", d.flat_fee, d.expected_flat_fee);
+                }
+            }
+            
+            if mismatched == 0 {
+                info!("✓ All {} deployers are within their expected fees", deployers.len());
+            } else {
+                warn!("{} of {} deployers charge more than expected", mismatched, deployers.len());
+            }
+            return Ok(());
+        }
         Some(config::Command::SetExpectedFees { expected_bps_fee, expected_flat_fee }) => {
             info!("Setting expected fees for all deployers...");
             info!("Expected BPS fee: {} (0 = accept any)", expected_bps_fee);
@@ -180,7 +265,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         Some(config::Command::CreateLut) => {
             info!("[LEGACY] Creating new Address Lookup Table...");
             info!("Note: 'run' command auto-creates LUTs. This is for manual management.");
-            let mut lut_manager = LutManager::new(&config.rpc_url, crank.deploy_authority_pubkey());
+            let mut lut_manager = LutManager::new(config.rpc_client(), crank.deploy_authority_pubkey());
             match crank.create_lut(&mut lut_manager).await {
                 Ok(lut_address) => {
                     info!("✓ LUT created: {}", lut_address);
@@ -195,7 +280,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         }
         Some(config::Command::ExtendLut) => {
             let lut_address = config.lut_address.ok_or("LUT_ADDRESS not set in .env")?;
-            let mut lut_manager = LutManager::new(&config.rpc_url, crank.deploy_authority_pubkey());
+            let mut lut_manager = LutManager::new(config.rpc_client(), crank.deploy_authority_pubkey());
             lut_manager.load_lut(lut_address)?;
             
             info!("Adding static shared accounts to LUT...");
@@ -226,7 +311,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         }
         Some(config::Command::ShowLut) => {
             let lut_address = config.lut_address.ok_or("LUT_ADDRESS not set in .env")?;
-            let mut lut_manager = LutManager::new(&config.rpc_url, crank.deploy_authority_pubkey());
+            let mut lut_manager = LutManager::new(config.rpc_client(), crank.deploy_authority_pubkey());
             let lut_account = lut_manager.load_lut(lut_address)?;
             
             info!("LUT Address: {}", lut_address);
@@ -245,7 +330,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         }
         Some(config::Command::DeactivateLut) => {
             let lut_address = config.lut_address.ok_or("LUT_ADDRESS not set in .env")?;
-            let mut lut_manager = LutManager::new(&config.rpc_url, crank.deploy_authority_pubkey());
+            let mut lut_manager = LutManager::new(config.rpc_client(), crank.deploy_authority_pubkey());
             lut_manager.load_lut(lut_address)?;
             
             // Check if already deactivated
@@ -271,7 +356,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         }
         Some(config::Command::CloseLut) => {
             let lut_address = config.lut_address.ok_or("LUT_ADDRESS not set in .env")?;
-            let mut lut_manager = LutManager::new(&config.rpc_url, crank.deploy_authority_pubkey());
+            let mut lut_manager = LutManager::new(config.rpc_client(), crank.deploy_authority_pubkey());
             lut_manager.load_lut(lut_address)?;
             
             // Check deactivation status
@@ -300,7 +385,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
             match crank.close_lut(&lut_manager).await {
                 Ok(lamports) => {
                     info!("✓ LUT closed successfully");
-                    info!("Reclaimed {} lamports ({:.6} SOL)", lamports, lamports as f64 / 1_000_000_000.0);
+                    info!("Reclaimed {} ({} lamports)", format::format_sol(lamports), lamports);
                     info!("Remove LUT_ADDRESS from .env and run 'create-lut' for a new LUT");
                 }
                 Err(e) => {
@@ -310,10 +395,57 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
             }
             return Ok(());
         }
+        Some(config::Command::VerifyLut) => {
+            info!("Verifying LUT contents...");
+            let mut registry = LutRegistry::new(config.rpc_client(), crank.deploy_authority_pubkey());
+            registry.load_all_luts()?;
+            let mut drifted = 0;
+            
+            match registry.verify_shared_lut() {
+                Some((lut, diff)) if diff.is_clean() => info!("  ✓ Shared LUT {}", lut),
+                Some((lut, diff)) => {
+                    drifted += 1;
+                    warn!("  ✗ Shared LUT {}", lut);
+                    for addr in &diff.missing {
+                        warn!("      missing: {}", addr);
+                    }
+                    for addr in &diff.extra {
+                        warn!("      extra: {}", addr);
+                    }
+                    warn!("      LUT entries can't be removed: deactivate it and let 'run' create a new shared LUT");
+                }
+                None => warn!("  ✗ Shared LUT: not found (will create on run)"),
+            }
+            
+            let deployers = crank.find_deployers().await?;
+            for d in &deployers {
+                match registry.verify_miner_lut(d.manager_address, AUTH_ID) {
+                    Some((lut, diff)) if diff.is_clean() => info!("  ✓ Miner LUT {} (manager {})", lut, d.manager_address),
+                    Some((lut, diff)) => {
+                        drifted += 1;
+                        warn!("  ✗ Miner LUT {} (manager {})", lut, d.manager_address);
+                        for addr in &diff.missing {
+                            warn!("      missing: {}", addr);
+                        }
+                        for addr in &diff.extra {
+                            warn!("      extra: {}", addr);
+                        }
+                    }
+                    None => info!("  - Manager {}: no miner LUT (will create on run)", d.manager_address),
+                }
+            }
+            
+            if drifted == 0 {
+                info!("✓ All LUTs hold the expected accounts");
+            } else {
+                warn!("{} LUTs drifted; run 'deactivate-unused' to retire miner LUTs the crank can't use", drifted);
+            }
+            return Ok(());
+        }
         Some(config::Command::DeactivateUnused) => {
             info!("Scanning for unused/invalid LUTs...");
             
-            let registry = LutRegistry::new(&config.rpc_url, crank.deploy_authority_pubkey());
+            let registry = LutRegistry::new(config.rpc_client(), crank.deploy_authority_pubkey());
             
             let unused_luts = registry.get_unused_luts()?;
             
@@ -334,7 +466,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
             
             let mut deactivated = 0;
             for lut in &unused_luts {
-                let mut lut_manager = LutManager::new(&config.rpc_url, crank.deploy_authority_pubkey());
+                let mut lut_manager = LutManager::new(config.rpc_client(), crank.deploy_authority_pubkey());
                 lut_manager.load_lut(lut.address)?;
                 
                 match crank.deactivate_lut(&lut_manager).await {
@@ -355,7 +487,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         Some(config::Command::CleanupDeactivated) => {
             info!("Scanning for deactivating LUTs...");
             
-            let registry = LutRegistry::new(&config.rpc_url, crank.deploy_authority_pubkey());
+            let registry = LutRegistry::new(config.rpc_client(), crank.deploy_authority_pubkey());
             
             let deactivating_luts = registry.get_deactivating_luts()?;
             
@@ -387,12 +519,12 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
                 let mut total_reclaimed = 0u64;
                 
                 for (lut, _) in &ready_to_close {
-                    let mut lut_manager = LutManager::new(&config.rpc_url, crank.deploy_authority_pubkey());
+                    let mut lut_manager = LutManager::new(config.rpc_client(), crank.deploy_authority_pubkey());
                     lut_manager.load_lut(lut.address)?;
                     
                     match crank.close_lut(&lut_manager).await {
                         Ok(lamports) => {
-                            info!("  ✓ Closed {} - reclaimed {} lamports", lut.address, lamports);
+                            info!("  ✓ Closed {} - reclaimed {}", lut.address, format::format_sol(lamports));
                             closed += 1;
                             total_reclaimed += lamports;
                         }
@@ -403,19 +535,188 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
                 }
                 
                 info!("\nClosed {}/{} LUTs", closed, ready_to_close.len());
-                info!("Total reclaimed: {} lamports ({:.6} SOL)", 
-                    total_reclaimed, total_reclaimed as f64 / 1_000_000_000.0);
+                info!("Total reclaimed: {} ({} lamports)", format::format_sol(total_reclaimed), total_reclaimed);
             } else {
                 info!("\nNo LUTs ready to close yet.");
             }
             
             return Ok(());
         }
+        Some(config::Command::SquareStats) => {
+            let stats = db::get_square_stats(&db_pool).await?;
+            if stats.is_empty() {
+                warn!("No square history recorded yet (the run loop records each round as the next begins)");
+                return Ok(());
+            }
+            
+            let rounds = stats.iter().map(|s| s.rounds).max().unwrap_or(0);
+            info!("Square outcomes over {} rounds:", rounds);
+            for s in &stats {
+                info!(
+                    "  Square {:>2}: {:>5} wins / {:>5} rounds ({:>5.2}%) | avg deployed {} | avg miners {:.1}",
+                    s.square,
+                    s.wins,
+                    s.rounds,
+                    s.win_rate() * 100.0,
+                    format::format_sol(s.avg_deployed as u64),
+                    s.avg_miners
+                );
+            }
+            return Ok(());
+        }
         Some(config::Command::CheckAccounts) => {
             info!("Checking all Evore program accounts...\n");
             crank.check_all_accounts()?;
             return Ok(());
         }
+        Some(config::Command::Replay { signature }) => {
+            info!("Replaying transaction {}...", signature);
+            match crank.replay_tx(&signature).await {
+                Ok(sig) => {
+                    info!("✓ Replay confirmed: {}", sig);
+                }
+                Err(e) => {
+                    error!("✗ Replay failed: {}", e);
+                    return Err(e.into());
+                }
+            }
+            return Ok(());
+        }
+        Some(config::Command::TraceMiner { manager, auth_id }) => {
+            let deployers = crank.find_deployers().await?;
+            let Some(deployer) = deployers.iter().find(|d| d.manager_address == manager) else {
+                warn!("No deployer for manager {} where we are the deploy_authority", manager);
+                return Ok(());
+            };
+            
+            let mut registry = LutRegistry::new(config.rpc_client(), crank.deploy_authority_pubkey());
+            registry.load_all_luts()?;
+            let has_lut = registry.has_miner_lut(&get_miner_auth_pda(manager, auth_id));
+            
+            let (board, current_slot) = crank.get_board()?;
+            let mut board_state = pipeline::shared_state::BoardState {
+                round_id: board.round_id,
+                round_address: evore::ore_api::round_pda(board.round_id).0,
+                start_slot: board.start_slot,
+                end_slot: board.end_slot,
+                current_slot,
+                ..Default::default()
+            };
+            board_state.update_phase();
+            
+            let mut cache = miner_cache::MinerCache::new();
+            cache.refresh(crank.rpc_client(), std::slice::from_ref(deployer), auth_id, board.round_id)?;
+            let miner_address = pda_cache::miner_pdas(manager, auth_id).ore_miner;
+            
+            let trace = pipeline::trace::trace_miner(deployer, has_lut, cache.get(&miner_address), &board_state);
+            info!("Tracing manager {} (auth_id {}, miner {})", manager, auth_id, miner_address);
+            for gate in &trace.gates {
+                info!("  {} {:<18} {}", gate.status, gate.gate, gate.detail);
+            }
+            info!("Outcome: {}", trace.outcome);
+            return Ok(());
+        }
+        Some(config::Command::SimulateDeploy { amount, squares_mask }) => {
+            let (board, _) = crank.get_board()?;
+            let deployers = crank.find_deployers().await?;
+            if deployers.is_empty() {
+                warn!("No deployers found where we are the deploy_authority");
+                return Ok(());
+            }
+            
+            info!("Simulating deploys for round {} ({} lamports/square, mask {:#x})", board.round_id, amount, squares_mask);
+            for d in &deployers {
+                match crank.simulate_autodeploy_landed(d, AUTH_ID, board.round_id, amount, squares_mask) {
+                    Ok(landed) => {
+                        info!("  Manager: {}", d.manager_address);
+                        info!("    Total landed: {} lamports", landed.iter().sum::<u64>());
+                        info!("    Per square: {:?}", landed);
+                    }
+                    Err(e) => {
+                        error!("  ✗ Simulation failed for {}: {}", d.manager_address, e);
+                    }
+                }
+            }
+            return Ok(());
+        }
+        Some(config::Command::TopUp { target_lamports }) => {
+            let deployers = crank.find_deployers().await?;
+            if deployers.is_empty() {
+                warn!("No deployers found where we are the deploy_authority");
+                return Ok(());
+            }
+            
+            // Deposits must be signed by the manager authority
+            let owned: std::collections::HashSet<_> = crank.owned_managers(&deployers)?.into_iter().collect();
+            let owned_deployers: Vec<_> = deployers.iter().filter(|d| owned.contains(&d.manager_address)).cloned().collect();
+            if owned_deployers.len() < deployers.len() {
+                info!("Skipping {} managers owned by other authorities", deployers.len() - owned_deployers.len());
+            }
+            
+            let (board, _) = crank.get_board()?;
+            let mut miner_cache = miner_cache::MinerCache::new();
+            miner_cache.refresh(crank.rpc_client(), &owned_deployers, AUTH_ID, board.round_id)?;
+            let balances: Vec<_> = owned_deployers
+                .iter()
+                .filter_map(|d| {
+                    let miner_address = miner_cache.get_miner_address_for_deployer(&d.deployer_address)?;
+                    Some((d.manager_address, miner_cache.get_balance(&miner_address)?))
+                })
+                .collect();
+            
+            // Keep the reserve plus room for the deposits' own transaction fees
+            let authority_balance = crank.rpc_client()
+                .get_balance(&crank.deploy_authority_pubkey())
+                .map_err(|e| crank::CrankError::Rpc(e.to_string()))?;
+            let available = authority_balance
+                .saturating_sub(config.min_authority_reserve_lamports)
+                .saturating_sub(TOP_UP_FEE_ALLOWANCE);
+            
+            let top_ups = crank::plan_top_ups(&balances, target_lamports, available);
+            let total: u64 = top_ups.iter().map(|(_, lamports)| lamports).sum();
+            let short = balances.iter().filter(|(_, balance)| *balance < target_lamports).count();
+            if top_ups.is_empty() {
+                if short == 0 {
+                    info!("All {} managed miners already hold at least {}", balances.len(), format::format_sol(target_lamports));
+                } else {
+                    warn!("{} managed miners are below target but only {} is available", short, format::format_sol(available));
+                }
+                return Ok(());
+            }
+            
+            info!("Topping up {} of {} managed miners to {} ({} total)",
+                top_ups.len(), balances.len(), format::format_sol(target_lamports), format::format_sol(total));
+            for (manager, lamports) in &top_ups {
+                info!("  {}: +{}", manager, format::format_sol(*lamports));
+            }
+            if top_ups.len() < short {
+                warn!("Authority balance covers {} of {} miners below target", top_ups.len(), short);
+            }
+            
+            for sig in crank.execute_top_ups(&top_ups, AUTH_ID).await? {
+                info!("  ✓ {}", sig);
+            }
+            return Ok(());
+        }
+        Some(config::Command::Snapshot { manager, ref out, auth_ids }) => {
+            // Miner LUTs are looked up among the LUTs our deploy authority owns
+            let mut registry = LutRegistry::new(config.rpc_client(), crank.deploy_authority_pubkey());
+            let _ = registry.load_all_luts();
+            
+            let snapshot = snapshot::fetch_snapshot(crank.rpc_client(), manager, auth_ids, &registry)?;
+            let json = serde_json::to_string_pretty(&snapshot)?;
+            match out {
+                Some(path) => {
+                    std::fs::write(path, json)?;
+                    info!(
+                        "Wrote snapshot of {} at slot {} ({} miners) to {}",
+                        manager, snapshot.slot, snapshot.miners.len(), path.display()
+                    );
+                }
+                None => println!("{}", json),
+            }
+            return Ok(());
+        }
         Some(config::Command::Pipeline) => {
             info!("Starting new pipeline architecture...");
             
@@ -424,13 +725,10 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
             info!("Deploy authority: {}", deploy_authority.pubkey());
             
             // Create RPC client
-            let rpc_client = Arc::new(solana_client::rpc_client::RpcClient::new_with_commitment(
-                config.rpc_url.clone(),
-                solana_sdk::commitment_config::CommitmentConfig::confirmed(),
-            ));
+            let rpc_client = Arc::new(config.rpc_client());
             
             // Run pipeline
-            if let Err(e) = pipeline::run_pipeline(config, rpc_client, deploy_authority).await {
+            if let Err(e) = pipeline::run_pipeline(config, rpc_client, deploy_authority, crank.deployer_cache()).await {
                 error!("Pipeline error: {}", e);
                 return Err(e.into());
             }
@@ -445,22 +743,39 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     info!("Database: {}", config.db_path.display());
     info!("Priority fee: {} microlamports/CU", config.priority_fee);
     
+    // Database maintenance (retention + vacuum) in the background
+    if config.db_vacuum_interval_secs > 0 {
+        info!(
+            "DB maintenance every {}s (retention: {} rounds, 0 = keep all)",
+            config.db_vacuum_interval_secs, config.db_retention_rounds
+        );
+        tokio::spawn(db::run_maintenance(
+            db_pool.clone(),
+            config.db_retention_rounds,
+            config.db_vacuum_interval_secs,
+        ));
+    }
+    
     // Initialize LUT Registry (multi-LUT support)
-    let mut registry = LutRegistry::new(&config.rpc_url, crank.deploy_authority_pubkey());
+    let mut registry = LutRegistry::new(config.rpc_client(), crank.deploy_authority_pubkey());
     
     // Load all existing LUTs owned by our authority
     info!("Loading existing LUTs...");
-    match registry.load_all_luts() {
+    match registry.load_luts(config.lut_cache_path.as_deref()) {
         Ok(count) => info!("Found {} LUTs owned by deploy authority", count),
         Err(e) => warn!("Error loading LUTs: {}. Will create as needed.", e),
     }
     
     // Find deployers we manage
     let deployers = crank.find_deployers().await?;
+    pda_cache::prime(&deployers, AUTH_ID);
     
     if deployers.is_empty() {
         warn!("No deployers found where we are the deploy_authority");
         warn!("Create a deployer with deploy_authority set to: {}", crank.deploy_authority_pubkey());
+        if config.once {
+            std::process::exit(StrategyPass::default().exit_code());
+        }
         return Ok(());
     }
     
@@ -484,20 +799,25 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         }
     }
     
-    // Ensure all miners have LUTs
-    info!("Ensuring all miners have LUTs...");
-    match crank.ensure_all_miner_luts(&mut registry, &deployers, AUTH_ID).await {
-        Ok(created) => {
-            if created > 0 {
-                info!("Created {} new miner LUTs", created);
-            } else {
-                info!("All miners already have LUTs");
+    // Ensure all miners have LUTs (with a cap, the run loop creates them round by round instead)
+    if config.max_lut_creations_per_round == 0 {
+        info!("Ensuring all miners have LUTs...");
+        let all_deployers: Vec<_> = deployers.iter().collect();
+        match crank.ensure_all_miner_luts(&mut registry, &all_deployers, AUTH_ID, 0).await {
+            Ok(created) => {
+                if created > 0 {
+                    info!("Created {} new miner LUTs", created);
+                } else {
+                    info!("All miners already have LUTs");
+                }
+            }
+            Err(e) => {
+                error!("Failed to setup miner LUTs: {}", e);
+                return Err(e.into());
             }
         }
-        Err(e) => {
-            error!("Failed to setup miner LUTs: {}", e);
-            return Err(e.into());
-        }
+    } else {
+        info!("Creating up to {} missing miner LUTs per round", config.max_lut_creations_per_round);
     }
 
     // Wrap registry in Arc<RwLock> for sharing across async tasks
@@ -506,14 +826,83 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     // Initialize miner cache for reduced RPC usage
     let mut miner_cache = miner_cache::MinerCache::new();
     
+    let squares_mask = config.deploy_squares_mask();
+    if squares_mask == 0 {
+        return Err("BLOCKED_SQUARES blocks every square in SQUARES".into());
+    }
+    let strategy_chain = config.strategy_chain();
+    if strategy_chain.strategies.is_empty() {
+        return Err("STRATEGY_CHAIN lists no strategies".into());
+    }
+    let pot_scaling = config.pot_scaling();
+    
     // Main loop
-    let poll_interval = Duration::from_millis(config.poll_interval_ms);
     info!("Starting main loop (poll interval: {}ms)", config.poll_interval_ms);
-    info!("Strategy: deploy {} lamports/square, {} squares, {} slots before end",
-        DEPLOY_AMOUNT_LAMPORTS, SQUARES_MASK.count_ones(), DEPLOY_SLOTS_BEFORE_END);
+    if config.poll_interval_idle_ms > 0 || config.poll_jitter_ms > 0 {
+        info!("Poll interval before the deploy window: {}ms, jitter: up to {}ms", config.poll_interval_idle_ms, config.poll_jitter_ms);
+    }
+    match config.deploy_trigger {
+        config::DeployTrigger::SlotsBeforeEnd => info!("Strategy: deploy {} lamports/square, {} squares, {} ± {} slots before end",
+            DEPLOY_AMOUNT_LAMPORTS, squares_mask.count_ones(), DEPLOY_SLOTS_BEFORE_END, config.deploy_window_jitter_slots),
+        config::DeployTrigger::EntropyReady => info!("Strategy: deploy {} lamports/square, {} squares, when entropy is ready",
+            DEPLOY_AMOUNT_LAMPORTS, squares_mask.count_ones()),
+    }
+    if strategy_chain.strategies != [strategy_chain::DeployStrategy::Fixed] {
+        info!("Strategy chain: {:?}", strategy_chain.strategies);
+    }
     info!("Max batch size: {} (limited by 64 account limit)", MAX_BATCH_SIZE);
+    if config.min_board_total_lamports > 0 {
+        info!("Min board total to deploy: {}", format::format_sol(config.min_board_total_lamports));
+    }
+    if pot_scaling.is_enabled() {
+        info!("Pot scaling: +{} bps of the deploy amount per SOL in the round, up to {} bps", pot_scaling.bps_per_sol, pot_scaling.max_bps);
+    }
+    if config.max_deploys_per_manager > 0 {
+        info!("Max deploys per manager per batch: {}", config.max_deploys_per_manager);
+    }
     
     let mut last_round_id: Option<u64> = None;
+    let mut lut_fallback = lut::LutFallback::new(config.lut_failure_threshold);
+    
+    // Last-chance deploy inside the final slots of each round
+    let mut final_sweep = config.final_sweep.then(|| FinalSweep::new(config.final_sweep_priority_fee));
+    if config.final_sweep {
+        info!("Final sweep: one deploy tx under {} slots left ({} microlamports/CU)", MIN_SLOTS_TO_DEPLOY, config.final_sweep_priority_fee);
+    }
+    
+    // Per-round trigger within the slots-before-end band, seeded at startup so it isn't guessable
+    let seed = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_nanos() as u64)
+        .unwrap_or(0);
+    let deploy_window = deploy_window::DeployWindow::new(
+        DEPLOY_SLOTS_BEFORE_END, config.deploy_window_jitter_slots, MIN_SLOTS_TO_DEPLOY, seed,
+    );
+    
+    // Entropy-ready trigger replaces the slots-before-end window
+    let mut entropy_monitor = match config.deploy_trigger {
+        config::DeployTrigger::SlotsBeforeEnd => None,
+        config::DeployTrigger::EntropyReady => Some(entropy::EntropyMonitor::new()),
+    };
+    
+    // Idle polling only where the slots-before-end window can't be open yet; entropy can be
+    // committed at any point of the round
+    let idle_above_slots = match config.deploy_trigger {
+        config::DeployTrigger::SlotsBeforeEnd => Some(deploy_window.band().1),
+        config::DeployTrigger::EntropyReady => None,
+    };
+    let mut poll_schedule = poll_schedule::PollSchedule::new(
+        config.poll_interval_ms, config.poll_interval_idle_ms, config.poll_jitter_ms, idle_above_slots, seed,
+    );
+    let mut last_slots_remaining: Option<u64> = None;
+    
+    let round_limit = pipeline::RoundLimit::new(config.max_rounds);
+    if config.max_rounds > 0 {
+        info!("Stopping after deploying across {} rounds", config.max_rounds);
+    }
+    let mut run_loop_rounds = pipeline::RunLoopRounds::default();
+    let mut balance_round_id: Option<u64> = None;
+    crank.refresh_authority_balance().await;
     
     loop {
         // Check pending transactions first
@@ -521,38 +910,222 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
             error!("Error checking pending txs: {}", e);
         }
         
+        // Reserve guard balance: once per round, outside the deploy sends
+        if last_round_id != balance_round_id {
+            crank.refresh_authority_balance().await;
+            balance_round_id = last_round_id;
+        }
+        
         // Run the deployment strategy with cached miner data
-        if let Err(e) = run_strategy(&crank, &deployers, &mut last_round_id, &mut miner_cache, &registry).await {
-            error!("Strategy error: {}", e);
+        let pass = match run_strategy(&crank, &deployers, &mut last_round_id, &mut last_slots_remaining, &mut miner_cache, &registry, squares_mask, &deploy_window, entropy_monitor.as_mut(), final_sweep.as_mut(), config.defer_optional_checkpoints, &mut lut_fallback, config.max_lut_creations_per_round, config.max_deploys_per_manager, config.min_board_total_lamports, &strategy_chain, &pot_scaling).await {
+            Ok(pass) => pass,
+            Err(e) if config.once => {
+                error!("Strategy error: {}", e);
+                return Err(e.into());
+            }
+            Err(e) => {
+                error!("Strategy error: {}", e);
+                StrategyPass::default()
+            }
+        };
+        
+        // Single pass for cron-driven setups: exit code reports what happened
+        if let Some(code) = pass.once_exit_code(config.once) {
+            info!(
+                "Single pass complete: {} deploys attempted, {} confirmed",
+                pass.attempted, pass.confirmed
+            );
+            std::process::exit(code);
         }
         
-        tokio::time::sleep(poll_interval).await;
+        if run_loop_rounds.record_pass(&round_limit, last_round_id, pass.confirmed) {
+            info!("Deployed across {} rounds (MAX_ROUNDS), exiting", round_limit.completed());
+            return Ok(());
+        }
+        
+        tokio::time::sleep(poll_schedule.next_interval(last_slots_remaining)).await;
+    }
+}
+
+/// Outcome of one run_strategy pass (deployers, not transactions)
+#[derive(Debug, Default, Clone, Copy)]
+struct StrategyPass {
+    /// Deployers included in a deploy transaction this pass
+    attempted: usize,
+    /// Deployers whose deploy transaction confirmed
+    confirmed: usize,
+}
+
+impl StrategyPass {
+    /// Exit code for `--once`: 0 = deploys confirmed, 2 = nothing to deploy, 3 = no deploy confirmed
+    fn exit_code(&self) -> i32 {
+        match (self.attempted, self.confirmed) {
+            (0, _) => 2,
+            (_, 0) => 3,
+            _ => 0,
+        }
+    }
+    
+    /// With `--once` every pass is the last: the exit code to stop with, None to keep running
+    fn once_exit_code(&self, once: bool) -> Option<i32> {
+        once.then(|| self.exit_code())
+    }
+}
+
+/// Round-end last-chance deploy: at most one attempt per round
+#[derive(Debug)]
+struct FinalSweep {
+    /// Priority fee (microlamports/CU) for the sweep transaction
+    priority_fee: u64,
+    /// Last round a sweep was attempted in
+    swept_round: Option<u64>,
+}
+
+impl FinalSweep {
+    fn new(priority_fee: u64) -> Self {
+        Self { priority_fee, swept_round: None }
+    }
+    
+    /// Inside the final slots, after the regular deploy window has closed but before the round ends
+    fn in_window(slots_remaining: u64) -> bool {
+        slots_remaining > 0 && slots_remaining < MIN_SLOTS_TO_DEPLOY
+    }
+    
+    /// Claim this round's sweep, true only the first time it is asked inside the window
+    fn begin(&mut self, round_id: u64, slots_remaining: u64) -> bool {
+        if !Self::in_window(slots_remaining) || self.swept_round == Some(round_id) {
+            return false;
+        }
+        self.swept_round = Some(round_id);
+        true
     }
 }
 
+/// Checkpoint (and recycle, when there is SOL to claim) miners that aren't deploying
+/// (deployer, checkpoint_round, miner_address, has_sol_to_recycle)
+async fn execute_checkpoint_only(
+    crank: &crank::Crank,
+    miner_cache: &mut miner_cache::MinerCache,
+    mut checkpoint_only: Vec<(&config::DeployerInfo, u64, solana_sdk::pubkey::Pubkey, bool)>,
+) {
+    if checkpoint_only.is_empty() {
+        return;
+    }
+    // Largest claims first, in case the window closes before the rest are sent
+    miner_cache.sort_by_claimable_sol(&mut checkpoint_only, |(_, _, miner_addr, _)| *miner_addr);
+    let with_recycle = checkpoint_only.iter().filter(|(_, _, _, has_sol)| *has_sol).count();
+    let without_recycle = checkpoint_only.len() - with_recycle;
+    info!("Executing {} checkpoint operations ({} with recycle, {} without)", 
+        checkpoint_only.len(), with_recycle, without_recycle);
+    for (deployer, round, _miner_addr, has_sol_to_recycle) in checkpoint_only {
+        let op_name = if has_sol_to_recycle { "Checkpoint+recycle" } else { "Checkpoint" };
+        match crank.execute_checkpoint_recycle(deployer, AUTH_ID, round, has_sol_to_recycle).await {
+            Ok(sig) => {
+                info!("✓ {} for {}: {}", op_name, deployer.manager_address, sig);
+                // Invalidate cache after checkpoint
+                miner_cache.invalidate_balances();
+            }
+            Err(e) => error!("✗ {} failed for {}: {}", op_name, deployer.manager_address, e),
+        }
+    }
+}
+
+/// Create ORE miners for deployers whose managed miner doesn't have one yet
+async fn create_missing_miners(
+    crank: &crank::Crank,
+    deployers: &[config::DeployerInfo],
+    miner_cache: &mut miner_cache::MinerCache,
+) {
+    let missing = miner_cache.deployers_without_miner(deployers);
+    
+    for deployer in missing {
+        match crank.create_managed_miner(deployer, AUTH_ID).await {
+            Ok(Some(sig)) => {
+                info!("✓ Created ORE miner for {}: {}", deployer.manager_address, sig);
+                miner_cache.invalidate_balances();
+            }
+            Ok(None) => warn!(
+                "Manager {} has no ORE miner and its authority isn't the deploy authority, it must run MMCreateMiner itself",
+                deployer.manager_address
+            ),
+            Err(e) => error!("✗ Creating ORE miner failed for {}: {}", deployer.manager_address, e),
+        }
+    }
+}
+
+/// Order items by descending priority (stable, so ties keep their discovery order)
+fn sort_by_priority<T>(items: Vec<T>, priorities: Vec<miner_cache::DeployPriority>) -> Vec<T> {
+    let mut scored: Vec<_> = priorities.into_iter().zip(items).collect();
+    scored.sort_by(|(a, _), (b, _)| b.cmp(a));
+    scored.into_iter().map(|(_, item)| item).collect()
+}
+
+/// Slots a board read trails the cluster by, if too far behind to act on
+fn stale_board_lag(context_slot: u64, current_slot: u64) -> Option<u64> {
+    let lag = crank::Crank::board_lag_slots(context_slot, current_slot);
+    (lag > MAX_BOARD_LAG_SLOTS).then_some(lag)
+}
+
+/// Whether a round's pot is too small to be worth deploying into (0 = no minimum)
+fn pot_below_minimum(total_deployed: u64, min_board_total_lamports: u64) -> bool {
+    min_board_total_lamports > 0 && total_deployed < min_board_total_lamports
+}
+
 /// Deployment strategy - customize this for your use case
 /// Uses miner cache to minimize RPC calls
 async fn run_strategy(
     crank: &crank::Crank,
     deployers: &[config::DeployerInfo],
     last_round_id: &mut Option<u64>,
+    last_slots_remaining: &mut Option<u64>,
     miner_cache: &mut miner_cache::MinerCache,
     registry: &Arc<RwLock<LutRegistry>>,
-) -> Result<(), crank::CrankError> {
+    squares_mask: u32,
+    deploy_window: &deploy_window::DeployWindow,
+    entropy_monitor: Option<&mut entropy::EntropyMonitor>,
+    final_sweep: Option<&mut FinalSweep>,
+    defer_optional_checkpoints: bool,
+    lut_fallback: &mut lut::LutFallback,
+    max_lut_creations_per_round: usize,
+    max_deploys_per_manager: usize,
+    min_board_total_lamports: u64,
+    strategy_chain: &strategy_chain::StrategyChain,
+    pot_scaling: &pot_scaling::PotScaling,
+) -> Result<StrategyPass, crank::CrankError> {
     // Get current board state (single RPC call)
-    let (board, current_slot) = crank.get_board()?;
+    *last_slots_remaining = None;
+    let (board, context_slot, current_slot) = crank.get_board_with_context()?;
+    
+    // Don't make decisions on a board the RPC served from far behind the cluster
+    if let Some(lag) = stale_board_lag(context_slot, current_slot) {
+        warn!(
+            "Board read is stale (context slot {}, current slot {}, {} slots behind), skipping cycle",
+            context_slot, current_slot, lag
+        );
+        return Ok(StrategyPass::default());
+    }
     
     // Don't deploy if round hasn't fully started (end_slot is u64::MAX during reset)
     if board.end_slot == u64::MAX {
-        return Ok(());
+        return Ok(StrategyPass::default());
     }
     
     let slots_remaining = board.end_slot.saturating_sub(current_slot);
+    *last_slots_remaining = Some(slots_remaining);
     
     // Check if this is a new round
     let is_new_round = last_round_id.map_or(true, |id| id != board.round_id);
     if is_new_round {
         info!("New round detected: {} (ends in {} slots)", board.round_id, slots_remaining);
+        // The previous round was resolved by the reset that started this one
+        if let Some(finished_round_id) = *last_round_id {
+            info!("Round {} miner cache: {}", finished_round_id, miner_cache.take_stats());
+            match crank.record_round_squares(finished_round_id).await {
+                Ok(true) => debug!("Recorded square outcomes for round {}", finished_round_id),
+                Ok(false) => debug!("Round {} not resolved yet, square outcomes skipped", finished_round_id),
+                Err(e) => warn!("Failed to record square outcomes for round {}: {}", finished_round_id, e),
+            }
+        }
         *last_round_id = Some(board.round_id);
     }
     
@@ -563,26 +1136,107 @@ async fn run_strategy(
         return Err(e);
     }
     
+    // Managers without an ORE miner can't deploy; create them once per round, ahead of the window
+    if is_new_round {
+        create_missing_miners(crank, deployers, miner_cache).await;
+        
+        // LUTs the startup cap left out: richest miners first, they're the ones about to deploy
+        let mut by_balance: Vec<_> = deployers.iter().collect();
+        by_balance.sort_by_key(|d| {
+            let miner_address = miner_cache.get_miner_address_for_deployer(&d.deployer_address);
+            std::cmp::Reverse(miner_address.and_then(|m| miner_cache.get_balance(&m)).unwrap_or(0))
+        });
+        let mut reg = registry.write().await;
+        match crank.ensure_all_miner_luts(&mut reg, &by_balance, AUTH_ID, max_lut_creations_per_round).await {
+            Ok(created) => {
+                if created > 0 {
+                    info!("Created {} new miner LUTs", created);
+                }
+                lut_fallback.record_success();
+            }
+            Err(e) => {
+                error!("Failed to create miner LUTs: {}", e);
+                if lut_fallback.record_failure(board.round_id) {
+                    warn!(
+                        "LUT creation keeps failing, deploying round {} without LUTs in batches of {}",
+                        board.round_id, MAX_BATCH_SIZE_NO_LUT
+                    );
+                }
+            }
+        }
+    }
+    
+    // Past the deploy window only the final sweep (if enabled) still tries, once per round
+    let sweep_fee = final_sweep
+        .filter(|_| slots_remaining < MIN_SLOTS_TO_DEPLOY)
+        .and_then(|sweep| sweep.begin(board.round_id, slots_remaining).then_some(sweep.priority_fee));
+    
     // Don't deploy if too close to round end (transaction won't land in time)
-    if slots_remaining < MIN_SLOTS_TO_DEPLOY {
-        return Ok(());
+    if slots_remaining < MIN_SLOTS_TO_DEPLOY && sweep_fee.is_none() {
+        // Nothing deploys from here on, so deferred checkpoints no longer compete for the window
+        if defer_optional_checkpoints {
+            let deferred: Vec<_> = deployers
+                .iter()
+                .filter_map(|d| {
+                    let miner_address = miner_cache.get_miner_address_for_deployer(&d.deployer_address)?;
+                    let need = miner_cache.checkpoint_need(&miner_address, false)?;
+                    Some((d, need.round(), miner_address, miner_cache.has_sol_to_recycle(&miner_address, crank.recycle_policy())))
+                })
+                .collect();
+            execute_checkpoint_only(crank, miner_cache, deferred).await;
+        }
+        return Ok(StrategyPass::default());
     }
     
-    // Only deploy when close to round end
-    if slots_remaining > DEPLOY_SLOTS_BEFORE_END {
-        return Ok(());
+    if sweep_fee.is_some() {
+        // Balances and deploy state as of this slot, the sweep gets no second chance
+        info!("Final sweep for round {} ({} slots left)", board.round_id, slots_remaining);
+        miner_cache.invalidate_balances();
+        miner_cache.refresh(crank.rpc_client(), deployers, AUTH_ID, board.round_id)?;
+    } else {
+        // Only deploy when close to round end, or once entropy is ready when triggering on it
+        match entropy_monitor {
+            None => {
+                if !deploy_window.is_open(board.round_id, slots_remaining) {
+                    return Ok(StrategyPass::default());
+                }
+            }
+            Some(monitor) => {
+                if !monitor.poll(crank.rpc_client(), &board)? {
+                    return Ok(StrategyPass::default());
+                }
+            }
+        }
+    }
+    
+    // Round totals for the pot gate, pot scaling and the strategies that pick squares from the round so far
+    let round = if min_board_total_lamports > 0 || strategy_chain.needs_round() || pot_scaling.is_enabled() {
+        Some(crank.get_round(board.round_id)?)
+    } else {
+        None
+    };
+    
+    // Small pots aren't worth the fees, wait for the round to fill up
+    if let Some(round) = round.as_ref().filter(|r| pot_below_minimum(r.total_deployed, min_board_total_lamports)) {
+        debug!(
+            "Round {} total deployed {} is below the minimum {}, skipping",
+            board.round_id, format::format_sol(round.total_deployed), format::format_sol(min_board_total_lamports)
+        );
+        return Ok(StrategyPass::default());
     }
     
-    // Calculate required balance once (no RPC needed, just math)
-    let required = crank::Crank::calculate_required_balance_simple(
-        DEPLOY_AMOUNT_LAMPORTS,
-        SQUARES_MASK,
-        deployers.first().map(|d| d.flat_fee).unwrap_or(0),
+    // Required balance for a per-square amount (no RPC needed, just math)
+    let flat_fee = deployers.first().map(|d| d.flat_fee).unwrap_or(0);
+    let required_for = |amount: u64, squares_mask: u32| crank::Crank::calculate_required_balance_simple(
+        amount,
+        squares_mask,
+        flat_fee,
         1, // flat fee type
     );
     
     // Collect deployers for deployment using cached data
-    let mut to_deploy: Vec<(&config::DeployerInfo, u64, u64, u64, u32, Option<u64>)> = Vec::new();
+    let mut to_deploy: Vec<deploy_plan::DeployPlan> = Vec::new();
+    let mut priorities: Vec<miner_cache::DeployPriority> = Vec::new();
     // (deployer, checkpoint_round, miner_address, has_sol_to_recycle)
     let mut checkpoint_only: Vec<(&config::DeployerInfo, u64, solana_sdk::pubkey::Pubkey, bool)> = Vec::new();
     
@@ -593,73 +1247,153 @@ async fn run_strategy(
             None => continue, // Not in cache yet
         };
         
+        // No ORE miner yet: a deploy would fail the whole batch
+        if !miner_cache.get(&miner_address).map_or(false, |m| m.exists) {
+            continue;
+        }
+        
         // Check if already deployed this round using cache
         if miner_cache.has_deployed_in_round(&miner_address, board.round_id) {
             continue; // Already deployed, skip silently
         }
         
-        // Check if checkpoint is needed using cache
-        let checkpoint_round = miner_cache.needs_checkpoint(&miner_address);
+        // Managers with a deploy schedule vary the amount per round (0 = sit this round out),
+        // everyone else deploys the base amount scaled to the pot
+        let scheduled = miner_cache.scheduled_amount(&deployer.manager_address, board.round_id);
+        let total_deployed = round.as_ref().map_or(0, |r| r.total_deployed);
+        let mut amount = scheduled.unwrap_or_else(|| pot_scaling.amount(DEPLOY_AMOUNT_LAMPORTS, total_deployed));
+        
+        // First strategy in the chain with something to deploy picks the squares (0 = none did)
+        let deployed = round.as_ref().map_or([0; 25], |r| r.deployed);
+        let select_squares = |amount: u64| match strategy_chain.select(squares_mask, amount, &deployed) {
+            Ok((strategy, mask)) => {
+                if strategy != strategy_chain.strategies[0] {
+                    debug!("{} falls back to the {:?} strategy ({} squares)", deployer.manager_address, strategy, mask.count_ones());
+                }
+                mask
+            }
+            Err(e) => {
+                debug!("No strategy deploys for {} this round: {}", deployer.manager_address, e);
+                0
+            }
+        };
+        let mut squares_mask = select_squares(amount);
+        let mut required = required_for(amount, squares_mask);
         
         // Get cached balance
         let balance = miner_cache.get_balance(&miner_address).unwrap_or(0);
         
+        // A scaled amount the miner can't afford drops back to the base amount
+        if scheduled.is_none() && amount != DEPLOY_AMOUNT_LAMPORTS && balance < required {
+            debug!("{} can't afford the pot-scaled {} lamports/square, deploying the base amount", deployer.manager_address, amount);
+            amount = DEPLOY_AMOUNT_LAMPORTS;
+            squares_mask = select_squares(amount);
+            required = required_for(amount, squares_mask);
+        }
+        let will_deploy = amount > 0 && squares_mask != 0 && balance >= required;
+        
+        // Check if checkpoint is needed using cache
+        let checkpoint = miner_cache.checkpoint_need(&miner_address, will_deploy);
+        let checkpoint_round = checkpoint.map(|c| c.round());
+        
         // Check if miner has SOL rewards to recycle
-        let has_sol_to_recycle = miner_cache.has_sol_to_recycle(&miner_address);
+        let has_sol_to_recycle = miner_cache.has_sol_to_recycle(&miner_address, crank.recycle_policy());
         
-        if balance >= required {
+        if will_deploy {
             info!(
-                "Adding {} to deploy batch: balance {} >= required {} lamports{}",
-                deployer.manager_address, balance, required,
+                "Adding {} to deploy batch: balance {} >= required {}{}",
+                deployer.manager_address, format::format_sol(balance), format::format_sol(required),
                 if checkpoint_round.is_some() { format!(" (will checkpoint round {})", checkpoint_round.unwrap()) } else { "".to_string() }
             );
-            to_deploy.push((deployer, AUTH_ID, board.round_id, DEPLOY_AMOUNT_LAMPORTS, SQUARES_MASK, checkpoint_round));
-        } else if checkpoint_round.is_some() {
-            // Not enough to deploy but needs checkpoint
-            checkpoint_only.push((deployer, checkpoint_round.unwrap(), miner_address, has_sol_to_recycle));
+            to_deploy.push(
+                deploy_plan::DeployPlan::new(deployer.clone(), AUTH_ID, board.round_id, amount, squares_mask)
+                    .with_checkpoint(checkpoint_round),
+            );
+            let stake = amount.saturating_mul(u64::from(squares_mask.count_ones()));
+            priorities.push(miner_cache.deploy_priority(&miner_address, board.round_id, stake));
+        } else if let Some(need) = checkpoint {
+            // Not enough to deploy, the checkpoint only settles rewards
+            if need.is_deferred(defer_optional_checkpoints) {
+                debug!("Deferring checkpoint of round {} for {} until after the deploy window", need.round(), deployer.manager_address);
+            } else {
+                checkpoint_only.push((deployer, need.round(), miner_address, has_sol_to_recycle));
+            }
         }
         // Don't log insufficient balance every poll - too noisy
     }
     
-    // Execute checkpoint-only for miners that need it
-    if !checkpoint_only.is_empty() {
-        let with_recycle = checkpoint_only.iter().filter(|(_, _, _, has_sol)| *has_sol).count();
-        let without_recycle = checkpoint_only.len() - with_recycle;
-        info!("Executing {} checkpoint operations ({} with recycle, {} without)", 
-            checkpoint_only.len(), with_recycle, without_recycle);
-        for (deployer, round, _miner_addr, has_sol_to_recycle) in checkpoint_only {
-            let op_name = if has_sol_to_recycle { "Checkpoint+recycle" } else { "Checkpoint" };
-            match crank.execute_checkpoint_recycle(deployer, AUTH_ID, round, has_sol_to_recycle).await {
-                Ok(sig) => {
-                    info!("✓ {} for {}: {}", op_name, deployer.manager_address, sig);
-                    // Invalidate cache after checkpoint
-                    miner_cache.invalidate_balances();
-                }
-                Err(e) => error!("✗ {} failed for {}: {}", op_name, deployer.manager_address, e),
-            }
-        }
+    // Execute checkpoint-only for miners that need it (not during the sweep, every slot goes to the deploy)
+    if sweep_fee.is_none() {
+        execute_checkpoint_only(crank, miner_cache, checkpoint_only).await;
     }
     
+    let mut pass = StrategyPass::default();
+    
     // Execute deploys in batches using multi-LUT
     if !to_deploy.is_empty() {
         info!("Deploying for {} managers (round {})", to_deploy.len(), board.round_id);
         
+        // Most valuable deploys go in the first batches in case later ones don't land in time
+        let to_deploy = sort_by_priority(to_deploy, priorities);
+        
+        // No single manager fills a batch while others wait for a later one
+        let no_lut = lut_fallback.is_engaged(board.round_id);
+        let batch_size = if no_lut { MAX_BATCH_SIZE_NO_LUT } else { MAX_BATCH_SIZE };
+        let batches = crank::batch_by_manager(to_deploy, batch_size, max_deploys_per_manager, |p| p.manager());
+        
         let reg = registry.read().await;
         
-        for batch in to_deploy.chunks(MAX_BATCH_SIZE) {
+        // The final sweep has time for a single transaction: the highest-priority batch
+        let max_batches = if sweep_fee.is_some() { 1 } else { usize::MAX };
+        for batch in batches.iter().take(max_batches) {
             let miner_addresses: Vec<_> = batch.iter()
-                .filter_map(|(d, _, _, _, _, _)| miner_cache.get_miner_address_for_deployer(&d.deployer_address))
+                .filter_map(|p| miner_cache.get_miner_address_for_deployer(&p.deployer.deployer_address))
                 .collect();
             let batch_vec: Vec<_> = batch.to_vec();
-            let checkpoints_in_batch = batch.iter().filter(|(_, _, _, _, _, cp)| cp.is_some()).count();
+            let checkpoints_in_batch = batch.iter().filter(|p| p.needs_checkpoint()).count();
+            pass.attempted += batch.len();
             
-            // Use multi-LUT transaction
-            match crank.execute_batched_autodeploys_multi_lut(&reg, batch_vec).await {
+            // Use multi-LUT transaction, unless LUT creation has given up for this round
+            let result = if no_lut {
+                crank.execute_batched_autodeploys(batch_vec).await
+            } else {
+                crank.execute_batched_autodeploys_multi_lut(&reg, batch_vec, sweep_fee).await
+            };
+            match result {
                 Ok(sig) => {
                     info!("✓ Autodeploy ({} deployers, {} checkpoints): {}", 
                         batch.len(), checkpoints_in_batch, sig);
                     // Mark miners as deployed in cache
                     miner_cache.mark_deployed(&miner_addresses, board.round_id);
+                    pass.confirmed += batch.len();
+                }
+                Err(crank::CrankError::Lut(e)) if sweep_fee.is_some() => {
+                    // Too late for the slower no-LUT fallback
+                    error!("✗ Final sweep failed, no LUT path: {}", e);
+                    miner_cache.invalidate_balances();
+                }
+                Err(crank::CrankError::Lut(e)) => {
+                    // LUT missing or deactivated/closed mid-round: resend as smaller legacy txs
+                    warn!(
+                        "Multi-LUT autodeploy unavailable ({}), falling back to no-LUT batches of {}",
+                        e, MAX_BATCH_SIZE_NO_LUT
+                    );
+                    for fallback in batch.chunks(MAX_BATCH_SIZE_NO_LUT) {
+                        let fallback_miners: Vec<_> = fallback.iter()
+                            .filter_map(|p| miner_cache.get_miner_address_for_deployer(&p.deployer.deployer_address))
+                            .collect();
+                        match crank.execute_batched_autodeploys(fallback.to_vec()).await {
+                            Ok(sig) => {
+                                info!("✓ No-LUT autodeploy ({} deployers): {}", fallback.len(), sig);
+                                miner_cache.mark_deployed(&fallback_miners, board.round_id);
+                                pass.confirmed += fallback.len();
+                            }
+                            Err(e) => {
+                                error!("✗ No-LUT autodeploy failed: {}", e);
+                                miner_cache.invalidate_balances();
+                            }
+                        }
+                    }
                 }
                 Err(e) => {
                     error!("✗ Autodeploy failed: {}", e);
@@ -670,5 +1404,86 @@ async fn run_strategy(
         }
     }
     
-    Ok(())
+    Ok(pass)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_once_exits_after_first_pass() {
+        let pass = |attempted, confirmed| StrategyPass { attempted, confirmed };
+
+        // The first pass decides the exit code, whatever it did
+        assert_eq!(pass(3, 3).once_exit_code(true), Some(0));
+        assert_eq!(pass(0, 0).once_exit_code(true), Some(2), "nothing to deploy");
+        assert_eq!(pass(3, 0).once_exit_code(true), Some(3), "deploys failed");
+        assert_eq!(pass(3, 1).once_exit_code(true), Some(0));
+
+        // The daemon keeps going
+        assert_eq!(pass(3, 3).once_exit_code(false), None);
+        assert_eq!(pass(0, 0).once_exit_code(false), None);
+    }
+
+    #[test]
+    fn test_highest_scored_miners_fill_the_first_batch() {
+        let priority = |expected_stake, rounds_idle, balance| miner_cache::DeployPriority { expected_stake, rounds_idle, balance };
+        let to_deploy = vec!["small", "big-idle", "big-rich", "never-deployed", "big-busy", "tiny"];
+        let priorities = vec![
+            priority(2_800, 1, 1_000_000),
+            priority(70_000, 5, 1_000_000),
+            priority(70_000, 1, 9_000_000),
+            priority(2_800, u64::MAX, 1_000_000),
+            priority(70_000, 1, 1_000_000),
+            priority(700, 1, 1_000_000),
+        ];
+
+        let sorted = sort_by_priority(to_deploy, priorities);
+        let batches = crank::batch_by_manager(sorted, 3, 0, |_| solana_sdk::pubkey::Pubkey::default());
+
+        // Stake first, then rounds idle, then balance
+        assert_eq!(batches[0], vec!["big-idle", "big-rich", "big-busy"]);
+        assert_eq!(batches[1], vec!["never-deployed", "small", "tiny"]);
+    }
+
+    #[test]
+    fn test_stale_board_read_skips_cycle() {
+        // RPC served the board 100 slots behind the cluster
+        assert_eq!(stale_board_lag(1_000, 1_100), Some(100));
+        assert_eq!(stale_board_lag(1_000, 1_000 + MAX_BOARD_LAG_SLOTS + 1), Some(MAX_BOARD_LAG_SLOTS + 1));
+
+        assert_eq!(stale_board_lag(1_000, 1_000 + MAX_BOARD_LAG_SLOTS), None, "within the threshold");
+        assert_eq!(stale_board_lag(1_100, 1_100), None);
+        assert_eq!(stale_board_lag(1_105, 1_100), None, "a later context slot isn't stale");
+    }
+
+    #[test]
+    fn test_final_sweep_triggers_only_in_final_slots() {
+        let mut sweep = FinalSweep::new(1_000_000);
+
+        // Regular deploy window and round end are both outside the sweep
+        assert!(!sweep.begin(7, DEPLOY_SLOTS_BEFORE_END));
+        assert!(!sweep.begin(7, MIN_SLOTS_TO_DEPLOY));
+        assert!(!sweep.begin(7, 0));
+        assert_eq!(sweep.swept_round, None);
+
+        // One attempt per round inside the window
+        assert!(sweep.begin(7, MIN_SLOTS_TO_DEPLOY - 1));
+        assert!(!sweep.begin(7, 2), "already swept this round");
+        assert!(sweep.begin(8, 1));
+    }
+
+    #[test]
+    fn test_small_pot_round_is_skipped() {
+        let min = 5 * solana_sdk::native_token::LAMPORTS_PER_SOL;
+
+        assert!(pot_below_minimum(min / 10, min), "small pot skips the deploy");
+        assert!(pot_below_minimum(0, min));
+        assert!(!pot_below_minimum(min, min));
+        assert!(!pot_below_minimum(min * 40, min), "large pot proceeds");
+
+        // No minimum deploys into any round
+        assert!(!pot_below_minimum(0, 0));
+    }
 }
@@ -3,16 +3,17 @@
 //! Caches ORE miner account data in RAM, refreshing only after deployments
 //! or when a new round is detected.
 
-use evore::ore_api::{miner_pda, Miner};
-use evore::state::managed_miner_auth_pda;
+use evore::ore_api::Miner;
+use evore::state::{deploy_schedule_pda, DeploySchedule};
 use solana_client::rpc_client::RpcClient;
 use solana_sdk::pubkey::Pubkey;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use steel::AccountDeserialize;
 use tracing::{debug, info, warn};
 
-use crate::config::DeployerInfo;
+use crate::config::{DeployerInfo, RecyclePolicy};
 use crate::crank::CrankError;
+use crate::pda_cache::{self, MinerPdas};
 
 /// Cached miner data
 #[derive(Debug, Clone)]
@@ -39,6 +40,71 @@ pub struct CachedMiner {
     pub exists: bool,
 }
 
+/// A miner's pending checkpoint, split by whether the next deploy depends on it
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CheckpointNeed {
+    /// The miner deploys this pass, and ORE rejects a deploy into a new round
+    /// until the previous one is checkpointed
+    Required(u64),
+    /// The miner isn't deploying, the checkpoint only settles rewards and can wait
+    Optional(u64),
+}
+
+impl CheckpointNeed {
+    /// Round to checkpoint
+    pub fn round(&self) -> u64 {
+        match self {
+            CheckpointNeed::Required(round) | CheckpointNeed::Optional(round) => *round,
+        }
+    }
+
+    /// Whether to hold this checkpoint back until after the deploy window
+    pub fn is_deferred(&self, defer_optional: bool) -> bool {
+        defer_optional && matches!(self, CheckpointNeed::Optional(_))
+    }
+}
+
+/// Ordering key for miners competing for limited batch space (higher deploys first)
+///
+/// Compared field by field: expected stake (EV on a given board scales with what is
+/// deployed), then rounds since the miner last deployed, then auth balance.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct DeployPriority {
+    /// Lamports this deploy puts on the board
+    pub expected_stake: u64,
+    /// Rounds since the miner last deployed (u64::MAX if it never has)
+    pub rounds_idle: u64,
+    /// managed_miner_auth balance
+    pub balance: u64,
+}
+
+/// How often the cache answered a refresh itself vs went to the RPC, counted per miner
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct CacheStats {
+    /// Miners a refresh served from cache without an RPC call
+    pub hits: u64,
+    /// Miners (or, for a balance-only refresh, balances) refetched over RPC
+    pub misses: u64,
+}
+
+impl CacheStats {
+    /// Share of lookups served from cache, in percent (None before any lookup)
+    pub fn hit_rate(&self) -> Option<f64> {
+        let total = self.hits + self.misses;
+        (total > 0).then(|| self.hits as f64 * 100.0 / total as f64)
+    }
+}
+
+impl std::fmt::Display for CacheStats {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} hits, {} misses", self.hits, self.misses)?;
+        if let Some(rate) = self.hit_rate() {
+            write!(f, " ({:.1}% hit rate)", rate)?;
+        }
+        Ok(())
+    }
+}
+
 /// Miner cache for reducing RPC calls
 pub struct MinerCache {
     /// Cached miner data keyed by miner PDA address
@@ -47,6 +113,10 @@ pub struct MinerCache {
     last_refresh_round: Option<u64>,
     /// Whether we need to refresh all balances
     needs_balance_refresh: bool,
+    /// Deploy schedules keyed by manager address (managers without one are absent)
+    schedules: HashMap<Pubkey, DeploySchedule>,
+    /// Hits and misses since the last `take_stats`
+    stats: CacheStats,
 }
 
 impl MinerCache {
@@ -55,9 +125,21 @@ impl MinerCache {
             miners: HashMap::new(),
             last_refresh_round: None,
             needs_balance_refresh: true,
+            schedules: HashMap::new(),
+            stats: CacheStats::default(),
         }
     }
 
+    /// Hits and misses so far
+    pub fn stats(&self) -> CacheStats {
+        self.stats
+    }
+
+    /// Hits and misses so far, then start counting from zero (e.g. once per round)
+    pub fn take_stats(&mut self) -> CacheStats {
+        std::mem::take(&mut self.stats)
+    }
+
     /// Get cached miner data
     pub fn get(&self, miner_address: &Pubkey) -> Option<&CachedMiner> {
         self.miners.get(miner_address)
@@ -86,18 +168,66 @@ impl MinerCache {
         })
     }
 
+    /// Classify a pending checkpoint given whether the miner deploys this pass
+    pub fn checkpoint_need(&self, miner_address: &Pubkey, will_deploy: bool) -> Option<CheckpointNeed> {
+        self.needs_checkpoint(miner_address).map(|round| {
+            if will_deploy {
+                CheckpointNeed::Required(round)
+            } else {
+                CheckpointNeed::Optional(round)
+            }
+        })
+    }
+
+    /// Priority of a miner deploying `expected_stake` lamports in `round_id`
+    pub fn deploy_priority(&self, miner_address: &Pubkey, round_id: u64, expected_stake: u64) -> DeployPriority {
+        let miner = self.miners.get(miner_address);
+        let rounds_idle = match miner {
+            Some(m) if m.exists && m.has_deployed => round_id.saturating_sub(m.round_id),
+            _ => u64::MAX,
+        };
+        DeployPriority {
+            expected_stake,
+            rounds_idle,
+            balance: miner.map(|m| m.auth_balance).unwrap_or(0),
+        }
+    }
+
+    /// Order `items` so the miners with the most claimable SOL (`rewards_sol`) settle first
+    ///
+    /// ORE's `Miner.cumulative` is the board's per-square total ahead of the miner's own
+    /// deploy (top-miner sampling), not accrued rewards, so it plays no part here. Ties and
+    /// uncached miners (0) keep their order.
+    pub fn sort_by_claimable_sol<T>(&self, items: &mut [T], miner_of: impl Fn(&T) -> Pubkey) {
+        items.sort_by_key(|item| std::cmp::Reverse(self.miners.get(&miner_of(item)).map_or(0, |m| m.rewards_sol)));
+    }
+
+    /// Deployers whose cached ORE miner account hasn't been created yet, in `deployers` order
+    pub fn deployers_without_miner<'a>(&self, deployers: &'a [DeployerInfo]) -> Vec<&'a DeployerInfo> {
+        let missing: HashSet<Pubkey> = self.miners.values()
+            .filter(|m| !m.exists)
+            .map(|m| m.deployer_address)
+            .collect();
+        deployers.iter().filter(|d| missing.contains(&d.deployer_address)).collect()
+    }
+
     /// Get cached balance for a miner's auth PDA
     pub fn get_balance(&self, miner_address: &Pubkey) -> Option<u64> {
         self.miners.get(miner_address).map(|m| m.auth_balance)
     }
 
-    /// Check if miner has SOL rewards to recycle
-    pub fn has_sol_to_recycle(&self, miner_address: &Pubkey) -> bool {
+    /// Check if miner has SOL rewards the recycle policy wants claimed
+    pub fn has_sol_to_recycle(&self, miner_address: &Pubkey, policy: RecyclePolicy) -> bool {
         self.miners.get(miner_address)
-            .map(|m| m.exists && m.rewards_sol > 0)
+            .map(|m| m.exists && policy.should_recycle(m.rewards_sol))
             .unwrap_or(false)
     }
 
+    /// Scheduled lamports per square for a manager in `round_id`, None if it has no schedule
+    pub fn scheduled_amount(&self, manager_address: &Pubkey, round_id: u64) -> Option<u64> {
+        self.schedules.get(manager_address).and_then(|s| s.amount_for_round(round_id))
+    }
+
     /// Mark that balances need refreshing (call after deployment)
     pub fn invalidate_balances(&mut self) {
         self.needs_balance_refresh = true;
@@ -132,8 +262,8 @@ impl MinerCache {
         let mut deployer_map: HashMap<Pubkey, &DeployerInfo> = HashMap::new();
         
         for deployer in deployers {
-            let (auth_pda, _) = managed_miner_auth_pda(deployer.manager_address, auth_id);
-            let (miner_addr, _) = miner_pda(auth_pda);
+            let MinerPdas { managed_miner_auth: auth_pda, ore_miner: miner_addr } =
+                pda_cache::miner_pdas(deployer.manager_address, auth_id);
             
             miner_addresses.push(miner_addr);
             auth_addresses.push(auth_pda);
@@ -143,6 +273,7 @@ impl MinerCache {
         // Only refresh if new round or balances invalidated
         if !is_new_round && !self.needs_balance_refresh && !self.miners.is_empty() {
             debug!("Cache still valid, skipping refresh");
+            self.stats.hits += self.miners.len() as u64;
             return Ok(self.miners.len());
         }
 
@@ -237,19 +368,56 @@ impl MinerCache {
             }
         }
 
+        // Schedules only change when a manager updates them, re-read once per round
+        if is_new_round {
+            self.refresh_schedules(rpc_client, deployers)?;
+        }
+
         self.last_refresh_round = Some(current_round_id);
         self.needs_balance_refresh = false;
+        self.stats.misses += fetched_count as u64;
 
         info!("Miner cache refreshed: {} miners", fetched_count);
         Ok(fetched_count)
     }
 
+    /// Fetch every deployer's manager deploy schedule in batches
+    fn refresh_schedules(
+        &mut self,
+        rpc_client: &RpcClient,
+        deployers: &[DeployerInfo],
+    ) -> Result<(), CrankError> {
+        let managers: Vec<Pubkey> = deployers.iter().map(|d| d.manager_address).collect();
+        let schedule_addresses: Vec<Pubkey> = managers.iter()
+            .map(|m| deploy_schedule_pda(*m).0)
+            .collect();
+
+        self.schedules.clear();
+        for (manager_chunk, schedule_chunk) in managers.chunks(100).zip(schedule_addresses.chunks(100)) {
+            let accounts = rpc_client
+                .get_multiple_accounts(schedule_chunk)
+                .map_err(|e| CrankError::Rpc(format!("Failed to fetch deploy schedules: {}", e)))?;
+
+            for (manager, account) in manager_chunk.iter().zip(accounts.iter()) {
+                if let Some(schedule) = account.as_ref().and_then(|a| DeploySchedule::try_from_bytes(&a.data).ok()) {
+                    self.schedules.insert(*manager, *schedule);
+                }
+            }
+        }
+
+        if !self.schedules.is_empty() {
+            debug!("Loaded {} deploy schedules", self.schedules.len());
+        }
+        Ok(())
+    }
+
     /// Refresh only balances (lighter weight than full refresh)
     pub fn refresh_balances(
         &mut self,
         rpc_client: &RpcClient,
     ) -> Result<(), CrankError> {
         if !self.needs_balance_refresh {
+            self.stats.hits += self.miners.len() as u64;
             return Ok(());
         }
 
@@ -282,6 +450,7 @@ impl MinerCache {
         }
 
         self.needs_balance_refresh = false;
+        self.stats.misses += auth_addresses.len() as u64;
         Ok(())
     }
 
@@ -320,6 +489,8 @@ impl MinerCache {
             .get_multiple_accounts(&[*miner_address, cached.authority])
             .map_err(|e| CrankError::Rpc(format!("Failed to fetch miner accounts: {}", e)))?;
 
+        self.stats.misses += 1;
+
         let miner_account = accounts.get(0).and_then(|a| a.as_ref());
         let auth_account = accounts.get(1).and_then(|a| a.as_ref());
         let auth_balance = auth_account.map(|a| a.lamports).unwrap_or(0);
@@ -368,3 +539,146 @@ impl MinerCache {
         Ok(Some(updated))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn cached(rewards_sol: u64) -> CachedMiner {
+        CachedMiner {
+            miner_address: Pubkey::new_unique(),
+            authority: Pubkey::new_unique(),
+            deployer_address: Pubkey::new_unique(),
+            manager_address: Pubkey::new_unique(),
+            checkpoint_id: 9,
+            round_id: 10,
+            has_deployed: true,
+            auth_balance: 1_000_000,
+            rewards_sol,
+            exists: true,
+        }
+    }
+
+    #[test]
+    fn test_checkpoint_claims_ordered_by_claimable_sol() {
+        let mut cache = MinerCache::new();
+        let miners: Vec<CachedMiner> = [5_000, 0, 2_000_000, 5_000, 70_000].into_iter().map(cached).collect();
+        for miner in &miners {
+            cache.miners.insert(miner.miner_address, miner.clone());
+        }
+        let uncached = Pubkey::new_unique();
+
+        let mut order: Vec<(usize, Pubkey)> = miners.iter().map(|m| m.miner_address).enumerate().collect();
+        order.insert(2, (5, uncached));
+        cache.sort_by_claimable_sol(&mut order, |(_, miner)| *miner);
+
+        // Largest rewards first; equal rewards and nothing-to-claim keep their batch order
+        let indices: Vec<usize> = order.iter().map(|(i, _)| *i).collect();
+        assert_eq!(indices, vec![2, 4, 0, 3, 1, 5]);
+    }
+
+    #[test]
+    fn test_consecutive_rounds_pull_scheduled_amounts() {
+        let mut cache = MinerCache::new();
+        let (scheduled, unscheduled) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let mut amounts = [0u64; evore::consts::MAX_SCHEDULE_ENTRIES];
+        amounts[..3].copy_from_slice(&[2_800, 10_000, 0]);
+        cache.schedules.insert(scheduled, DeploySchedule { manager_key: scheduled, len: 3, amounts });
+
+        let pulled: Vec<Option<u64>> = (30..34).map(|round| cache.scheduled_amount(&scheduled, round)).collect();
+        assert_eq!(pulled, vec![Some(2_800), Some(10_000), Some(0), Some(2_800)]);
+
+        // Managers without a schedule keep the static amount
+        assert_eq!(cache.scheduled_amount(&unscheduled, 30), None);
+    }
+
+    #[test]
+    fn test_optional_checkpoint_is_deferred() {
+        let mut cache = MinerCache::new();
+        let miner = cached(0);
+        let address = miner.miner_address;
+        cache.miners.insert(address, miner);
+
+        // Deploying this pass: the checkpoint gates the deploy and always runs
+        let required = cache.checkpoint_need(&address, true).unwrap();
+        assert_eq!(required, CheckpointNeed::Required(10));
+        assert!(!required.is_deferred(true));
+
+        // Not deploying: only reward accounting, held back when configured
+        let optional = cache.checkpoint_need(&address, false).unwrap();
+        assert_eq!(optional, CheckpointNeed::Optional(10));
+        assert!(optional.is_deferred(true));
+        assert!(!optional.is_deferred(false));
+
+        // Already checkpointed
+        cache.miners.get_mut(&address).unwrap().checkpoint_id = 10;
+        assert_eq!(cache.checkpoint_need(&address, false), None);
+    }
+
+    #[test]
+    fn test_deployer_without_miner_is_routed_to_creation() {
+        // The mock RPC reports every miner account as missing
+        let rpc_client = RpcClient::new_mock("succeeds".to_string());
+        let deployer = |deployer_address| DeployerInfo {
+            deployer_address,
+            manager_address: Pubkey::new_unique(),
+            bps_fee: 0,
+            flat_fee: 0,
+            expected_bps_fee: 0,
+            expected_flat_fee: 0,
+            max_per_round: 0,
+        };
+        let (fresh, existing) = (deployer(Pubkey::new_unique()), deployer(Pubkey::new_unique()));
+        let deployers = vec![fresh.clone(), existing.clone()];
+
+        let mut cache = MinerCache::new();
+        cache.refresh(&rpc_client, &deployers, 0, 5).unwrap();
+        assert_eq!(cache.deployers_without_miner(&deployers).len(), 2);
+
+        // Once a miner exists it's no longer queued for MMCreateMiner
+        let miner_address = cache.get_miner_address_for_deployer(&existing.deployer_address).unwrap();
+        cache.miners.get_mut(&miner_address).unwrap().exists = true;
+        let to_create = cache.deployers_without_miner(&deployers);
+        assert_eq!(to_create.len(), 1);
+        assert_eq!(to_create[0].deployer_address, fresh.deployer_address);
+    }
+
+    #[test]
+    fn test_stats_count_cache_hits_and_refetches() {
+        // The mock RPC answers every getMultipleAccounts with two missing accounts
+        let rpc_client = RpcClient::new_mock("succeeds".to_string());
+        let deployers: Vec<DeployerInfo> = (0..2)
+            .map(|_| DeployerInfo {
+                deployer_address: Pubkey::new_unique(),
+                manager_address: Pubkey::new_unique(),
+                bps_fee: 0,
+                flat_fee: 0,
+                expected_bps_fee: 0,
+                expected_flat_fee: 0,
+                max_per_round: 0,
+            })
+            .collect();
+        let mut cache = MinerCache::new();
+        assert_eq!(cache.stats().hit_rate(), None);
+
+        // First refresh of a round fetches every miner, repeats in the round are served from cache
+        assert_eq!(cache.refresh(&rpc_client, &deployers, 0, 5).unwrap(), 2);
+        assert_eq!(cache.stats(), CacheStats { hits: 0, misses: 2 });
+        cache.refresh(&rpc_client, &deployers, 0, 5).unwrap();
+        cache.refresh(&rpc_client, &deployers, 0, 5).unwrap();
+        assert_eq!(cache.stats(), CacheStats { hits: 4, misses: 2 });
+
+        // A deploy invalidates balances: the next balance read refetches, the one after doesn't
+        cache.invalidate_balances();
+        cache.refresh_balances(&rpc_client).unwrap();
+        cache.refresh_balances(&rpc_client).unwrap();
+        assert_eq!(cache.stats(), CacheStats { hits: 6, misses: 4 });
+        assert_eq!(cache.stats().hit_rate(), Some(60.0));
+
+        // A new round refetches again; taking the stats starts the count over
+        cache.refresh(&rpc_client, &deployers, 0, 6).unwrap();
+        assert_eq!(cache.take_stats(), CacheStats { hits: 6, misses: 6 });
+        assert_eq!(cache.stats(), CacheStats::default());
+        assert_eq!(CacheStats { hits: 3, misses: 1 }.to_string(), "3 hits, 1 misses (75.0% hit rate)");
+    }
+}
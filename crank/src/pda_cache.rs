@@ -0,0 +1,105 @@
+//! Memoized PDA derivations
+//!
+//! `find_program_address` hashes up to 255 bump candidates, and the crank
+//! derives the same managed_miner_auth / ORE miner / deployer addresses for
+//! every deployer on every pass. Addresses are deterministic, so they are
+//! derived once per (manager, auth_id) and reused for the life of the process.
+
+use std::collections::HashMap;
+use std::sync::{Mutex, OnceLock};
+
+use evore::ore_api::miner_pda;
+use evore::state::{deployer_pda, managed_miner_auth_pda};
+use solana_sdk::pubkey::Pubkey;
+use tracing::debug;
+
+use crate::config::DeployerInfo;
+
+/// Addresses derived from one manager + auth_id
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MinerPdas {
+    pub managed_miner_auth: Pubkey,
+    pub ore_miner: Pubkey,
+}
+
+#[derive(Default)]
+struct PdaCache {
+    miners: HashMap<(Pubkey, u64), MinerPdas>,
+    deployers: HashMap<Pubkey, Pubkey>,
+    /// `find_program_address` calls made filling the cache
+    derivations: u64,
+}
+
+impl PdaCache {
+    fn miner_pdas(&mut self, manager: Pubkey, auth_id: u64) -> MinerPdas {
+        *self.miners.entry((manager, auth_id)).or_insert_with(|| {
+            self.derivations += 2;
+            let (managed_miner_auth, _) = managed_miner_auth_pda(manager, auth_id);
+            let (ore_miner, _) = miner_pda(managed_miner_auth);
+            MinerPdas { managed_miner_auth, ore_miner }
+        })
+    }
+
+    fn deployer_address(&mut self, manager: Pubkey) -> Pubkey {
+        *self.deployers.entry(manager).or_insert_with(|| {
+            self.derivations += 1;
+            deployer_pda(manager).0
+        })
+    }
+}
+
+fn cache() -> &'static Mutex<PdaCache> {
+    static CACHE: OnceLock<Mutex<PdaCache>> = OnceLock::new();
+    CACHE.get_or_init(|| Mutex::new(PdaCache::default()))
+}
+
+/// managed_miner_auth and ORE miner addresses for a manager/auth_id
+pub fn miner_pdas(manager: Pubkey, auth_id: u64) -> MinerPdas {
+    cache().lock().unwrap().miner_pdas(manager, auth_id)
+}
+
+/// Deployer PDA for a manager
+pub fn deployer_address(manager: Pubkey) -> Pubkey {
+    cache().lock().unwrap().deployer_address(manager)
+}
+
+/// Derive every discovered deployer's addresses up front so the hot loops only do lookups
+pub fn prime(deployers: &[DeployerInfo], auth_id: u64) {
+    for deployer in deployers {
+        miner_pdas(deployer.manager_address, auth_id);
+        deployer_address(deployer.manager_address);
+    }
+    let cache = cache().lock().unwrap();
+    debug!(
+        "PDA cache: {} miners, {} deployers ({} derivations)",
+        cache.miners.len(), cache.deployers.len(), cache.derivations
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_repeated_derivations_hit_the_cache() {
+        let mut cache = PdaCache::default();
+        let manager = Pubkey::new_unique();
+
+        let first = cache.miner_pdas(manager, 0);
+        let deployer = cache.deployer_address(manager);
+        assert_eq!(cache.derivations, 3);
+        assert_eq!(first.managed_miner_auth, managed_miner_auth_pda(manager, 0).0);
+        assert_eq!(first.ore_miner, miner_pda(first.managed_miner_auth).0);
+        assert_eq!(deployer, deployer_pda(manager).0);
+
+        for _ in 0..10 {
+            assert_eq!(cache.miner_pdas(manager, 0), first);
+            assert_eq!(cache.deployer_address(manager), deployer);
+        }
+        assert_eq!(cache.derivations, 3, "repeats are lookups");
+
+        // Another auth_id is a new key
+        assert_ne!(cache.miner_pdas(manager, 1), first);
+        assert_eq!(cache.derivations, 5);
+    }
+}
@@ -1,123 +1,308 @@
 //! Board State Monitor System
 //!
-//! Runs continuously in background, polling the board account and current slot.
+//! Runs continuously in background, tracking the board account and current slot.
 //! Updates shared BoardState and signals round changes.
+//! Re-fetches epoch-scoped accounts (config, treasury ATA) when `Board.epoch_id` changes.
+//!
+//! With WS_URL set the board PDA and slot are pushed over a websocket subscription, so a
+//! round reset is seen as soon as it lands instead of on the next poll. A dropped
+//! subscription reconnects with backoff, re-reading the board over RPC each time so
+//! nothing missed while disconnected goes unseen. Without WS_URL the board is polled.
 
 use std::sync::Arc;
 
-use evore::ore_api::{board_pda, round_pda, Board};
+use evore::ore_api::{board_pda, config_pda, round_pda, treasury_tokens_address, Board, Config};
+use futures::StreamExt;
+use solana_account_decoder::UiAccountEncoding;
+use solana_client::nonblocking::pubsub_client::PubsubClient;
 use solana_client::rpc_client::RpcClient;
-use solana_sdk::pubkey::Pubkey;
+use solana_client::rpc_config::RpcAccountInfoConfig;
+use solana_sdk::account::Account;
+use solana_sdk::program_pack::Pack;
 use steel::AccountDeserialize;
-use tokio::time::{interval, Duration};
+use tokio::time::{interval, sleep, Duration};
 use tracing::{debug, error, info, warn};
 
+use crate::rpc::RpcProvider;
+
 use super::channels::ChannelSenders;
 use super::shared_state::{RoundPhase, SharedState};
 
-/// Run the board state monitor
+/// First wait before reconnecting a dropped subscription, doubled per failed attempt
+const RECONNECT_BACKOFF_MIN: Duration = Duration::from_millis(500);
+
+/// Longest wait between reconnect attempts
+const RECONNECT_BACKOFF_MAX: Duration = Duration::from_secs(30);
+
+/// Run the board state monitor: subscribed over `ws_url` when set, polled otherwise
 pub async fn run(
     shared: Arc<SharedState>,
     senders: ChannelSenders,
     rpc_client: Arc<RpcClient>,
     poll_interval_ms: u64,
+    ws_url: Option<String>,
 ) {
-    info!("[BoardStateMonitor] Starting...");
+    match ws_url {
+        Some(ws_url) => {
+            info!("[BoardStateMonitor] Starting (websocket {})...", ws_url);
+            run_subscribed(&shared, &senders, rpc_client.as_ref(), &ws_url).await
+        }
+        None => {
+            info!("[BoardStateMonitor] Starting (polling every {}ms)...", poll_interval_ms);
+            run_polling(&shared, &senders, rpc_client.as_ref(), poll_interval_ms).await
+        }
+    }
+}
+
+/// Round and phase the monitor last reported, to detect changes
+#[derive(Default)]
+struct MonitorState {
+    last_round_id: Option<u64>,
+    last_phase: Option<RoundPhase>,
+}
 
+async fn run_polling(shared: &SharedState, senders: &ChannelSenders, rpc_client: &RpcClient, poll_interval_ms: u64) {
     let mut interval = interval(Duration::from_millis(poll_interval_ms));
-    let mut last_round_id: Option<u64> = None;
-    let mut last_phase: Option<RoundPhase> = None;
+    let mut monitor = MonitorState::default();
 
     loop {
         interval.tick().await;
 
         // Fetch board state
-        match fetch_board_state(&rpc_client).await {
+        match fetch_board_state(rpc_client).await {
             Ok((board, current_slot)) => {
-                let round_id = board.round_id;
-                let (round_address, _) = round_pda(round_id);
-
-                // Update shared state
-                {
-                    let mut state = shared.board_state.write().await;
-                    state.round_id = round_id;
-                    state.round_address = round_address;
-                    state.start_slot = board.start_slot;
-                    state.end_slot = board.end_slot;
-                    state.current_slot = current_slot;
-                    state.update_phase();
-
-                    let new_phase = state.phase;
-
-                    // Log phase transitions
-                    if let Some(old_phase) = last_phase {
-                        if std::mem::discriminant(&old_phase) != std::mem::discriminant(&new_phase)
-                        {
-                            info!(
-                                "[BoardStateMonitor] Phase transition: {} -> {}",
-                                old_phase, new_phase
-                            );
-
-                            // Log round stats when entering intermission (round ended)
-                            let was_in_round = matches!(
-                                old_phase,
-                                RoundPhase::DeploymentWindow { .. } | RoundPhase::LateDeploymentWindow { .. }
-                            );
-                            let now_in_intermission = matches!(new_phase, RoundPhase::Intermission { .. });
-
-                            if was_in_round && now_in_intermission {
-                                info!("[BoardStateMonitor] ========== ROUND {} ENDED ==========", round_id);
-                                shared.stats.log_summary(round_id, &new_phase);
-                                info!("[BoardStateMonitor] =====================================");
-                            }
-                        }
-                    }
+                apply_board_state(shared, senders, rpc_client, &mut monitor, &board, current_slot).await;
+            }
+            Err(e) => {
+                error!("[BoardStateMonitor] Failed to fetch board state: {}", e);
+            }
+        }
+    }
+}
+
+async fn run_subscribed(shared: &SharedState, senders: &ChannelSenders, rpc_client: &RpcClient, ws_url: &str) {
+    let mut monitor = MonitorState::default();
+    let mut backoff = ReconnectBackoff::new();
+
+    loop {
+        match subscribe_board(shared, senders, rpc_client, ws_url, &mut monitor, &mut backoff).await {
+            Ok(()) => warn!("[BoardStateMonitor] Board subscription dropped"),
+            Err(e) => error!("[BoardStateMonitor] Board subscription failed: {}", e),
+        }
+        let wait = backoff.next_wait();
+        info!("[BoardStateMonitor] Reconnecting in {}ms", wait.as_millis());
+        sleep(wait).await;
+    }
+}
+
+/// One websocket session: board account and slot updates until either stream ends
+async fn subscribe_board(
+    shared: &SharedState,
+    senders: &ChannelSenders,
+    rpc_client: &RpcClient,
+    ws_url: &str,
+    monitor: &mut MonitorState,
+    backoff: &mut ReconnectBackoff,
+) -> Result<(), String> {
+    let client = PubsubClient::new(ws_url)
+        .await
+        .map_err(|e| format!("Failed to connect: {}", e))?;
 
-                    last_phase = Some(new_phase);
-
-                    // Log periodic status
-                    match new_phase {
-                        RoundPhase::DeploymentWindow { slots_remaining } => {
-                            debug!(
-                                "[BoardStateMonitor] Round {} phase: DeploymentWindow ({} slots remaining)",
-                                round_id, slots_remaining
-                            );
-                        }
-                        RoundPhase::WaitingForFirstDeploy => {
-                            debug!(
-                                "[BoardStateMonitor] Round {} phase: WaitingForFirstDeploy (ready to deploy)",
-                                round_id
-                            );
-                        }
-                        _ => {}
+    let (board_address, _) = board_pda();
+    let account_config = RpcAccountInfoConfig {
+        encoding: Some(UiAccountEncoding::Base64),
+        commitment: Some(rpc_client.commitment()),
+        ..RpcAccountInfoConfig::default()
+    };
+    let (mut board_updates, _board_unsubscribe) = client
+        .account_subscribe(&board_address, Some(account_config))
+        .await
+        .map_err(|e| format!("Failed to subscribe to board: {}", e))?;
+    let (mut slot_updates, _slot_unsubscribe) = client
+        .slot_subscribe()
+        .await
+        .map_err(|e| format!("Failed to subscribe to slots: {}", e))?;
+
+    // Anything that changed while we weren't subscribed
+    let (mut board, mut current_slot) = fetch_board_state(rpc_client).await?;
+    apply_board_state(shared, senders, rpc_client, monitor, &board, current_slot).await;
+    backoff.reset();
+    info!("[BoardStateMonitor] Subscribed to board {}", board_address);
+
+    loop {
+        tokio::select! {
+            update = board_updates.next() => {
+                let Some(update) = update else { return Ok(()) };
+                let parsed = update.value.decode::<Account>().and_then(|account| {
+                    Board::try_from_bytes(&account.data).ok().copied()
+                });
+                match parsed {
+                    Some(updated) => board = updated,
+                    None => {
+                        warn!("[BoardStateMonitor] Failed to parse board update at slot {}", update.context.slot);
+                        continue;
                     }
                 }
+                current_slot = current_slot.max(update.context.slot);
+            }
+            slot = slot_updates.next() => {
+                let Some(slot) = slot else { return Ok(()) };
+                current_slot = current_slot.max(slot.slot);
+            }
+        }
+        apply_board_state(shared, senders, rpc_client, monitor, &board, current_slot).await;
+    }
+}
 
-                // Signal round change when round_id changes (reset occurred)
-                // At this point end_slot is u64::MAX, but we start updates immediately
-                // so our miners can be the first deployers
-                if last_round_id != Some(round_id) {
-                    info!(
-                        "[BoardStateMonitor] New round detected: {} (triggering updates + deployments)",
-                        round_id
-                    );
-                    last_round_id = Some(round_id);
-
-                    // Broadcast round change - this triggers deployer discovery and miner cache update
-                    if let Err(e) = senders.round_changed.send(round_id) {
-                        warn!("[BoardStateMonitor] Failed to broadcast round change: {}", e);
-                    }
+/// Record a board read in shared state, logging phase changes and broadcasting new rounds
+async fn apply_board_state(
+    shared: &SharedState,
+    senders: &ChannelSenders,
+    rpc_client: &RpcClient,
+    monitor: &mut MonitorState,
+    board: &Board,
+    current_slot: u64,
+) {
+    let round_id = board.round_id;
+    let (round_address, _) = round_pda(round_id);
+
+    // Config / treasury ATA only change across epochs, not rounds
+    let needs_static_refresh = shared.static_accounts.read().await.needs_refresh(board.epoch_id);
+    if needs_static_refresh {
+        refresh_static_accounts(shared, rpc_client, board.epoch_id).await;
+    }
+
+    // Update shared state
+    {
+        let mut state = shared.board_state.write().await;
+        state.round_id = round_id;
+        state.round_address = round_address;
+        state.start_slot = board.start_slot;
+        state.end_slot = board.end_slot;
+        state.current_slot = current_slot;
+        state.update_phase();
+
+        let new_phase = state.phase;
+
+        // Log phase transitions
+        if let Some(old_phase) = monitor.last_phase {
+            if std::mem::discriminant(&old_phase) != std::mem::discriminant(&new_phase)
+            {
+                info!(
+                    "[BoardStateMonitor] Phase transition: {} -> {}",
+                    old_phase, new_phase
+                );
+
+                // Log round stats when entering intermission (round ended)
+                let was_in_round = matches!(
+                    old_phase,
+                    RoundPhase::DeploymentWindow { .. } | RoundPhase::LateDeploymentWindow { .. }
+                );
+                let now_in_intermission = matches!(new_phase, RoundPhase::Intermission { .. });
+
+                if was_in_round && now_in_intermission {
+                    info!("[BoardStateMonitor] ========== ROUND {} ENDED ==========", round_id);
+                    shared.stats.log_summary(round_id, &new_phase);
+                    info!("[BoardStateMonitor] =====================================");
                 }
             }
-            Err(e) => {
-                error!("[BoardStateMonitor] Failed to fetch board state: {}", e);
+        }
+
+        monitor.last_phase = Some(new_phase);
+
+        // Log periodic status
+        match new_phase {
+            RoundPhase::DeploymentWindow { slots_remaining } => {
+                debug!(
+                    "[BoardStateMonitor] Round {} phase: DeploymentWindow ({} slots remaining)",
+                    round_id, slots_remaining
+                );
             }
+            RoundPhase::WaitingForFirstDeploy => {
+                debug!(
+                    "[BoardStateMonitor] Round {} phase: WaitingForFirstDeploy (ready to deploy)",
+                    round_id
+                );
+            }
+            _ => {}
+        }
+    }
+
+    // Signal round change when round_id changes (reset occurred)
+    // At this point end_slot is u64::MAX, but we start updates immediately
+    // so our miners can be the first deployers
+    if monitor.last_round_id != Some(round_id) {
+        info!(
+            "[BoardStateMonitor] New round detected: {} (triggering updates + deployments)",
+            round_id
+        );
+        monitor.last_round_id = Some(round_id);
+
+        // Broadcast round change - this triggers deployer discovery and miner cache update
+        if let Err(e) = senders.round_changed.send(round_id) {
+            warn!("[BoardStateMonitor] Failed to broadcast round change: {}", e);
         }
     }
 }
 
+/// Exponential wait between reconnect attempts, reset once a subscription is up
+struct ReconnectBackoff {
+    next: Duration,
+}
+
+impl ReconnectBackoff {
+    fn new() -> Self {
+        Self { next: RECONNECT_BACKOFF_MIN }
+    }
+
+    fn next_wait(&mut self) -> Duration {
+        let wait = self.next;
+        self.next = (self.next * 2).min(RECONNECT_BACKOFF_MAX);
+        wait
+    }
+
+    fn reset(&mut self) {
+        self.next = RECONNECT_BACKOFF_MIN;
+    }
+}
+
+/// Fetch the epoch-scoped ORE accounts and store them in shared state
+async fn refresh_static_accounts(shared: &SharedState, rpc_client: &dyn RpcProvider, epoch_id: u64) {
+    let (config_address, _) = config_pda();
+    let treasury_ata = treasury_tokens_address();
+
+    let accounts = match rpc_client.get_multiple_accounts(&[config_address, treasury_ata]) {
+        Ok(accounts) => accounts,
+        Err(e) => {
+            // Leave the cache stale so the next tick retries
+            warn!("[BoardStateMonitor] Failed to fetch static accounts: {}", e);
+            return;
+        }
+    };
+
+    let config = accounts[0]
+        .as_ref()
+        .and_then(|a| Config::try_from_bytes(&a.data).ok().copied());
+    let treasury_ata_amount = accounts[1]
+        .as_ref()
+        .and_then(|a| spl_token::state::Account::unpack(&a.data).ok())
+        .map(|ata| ata.amount);
+
+    let mut cache = shared.static_accounts.write().await;
+    let previous_epoch = cache.epoch_id;
+    cache.update(epoch_id, config, treasury_ata_amount);
+
+    info!(
+        "[BoardStateMonitor] Refreshed static accounts for epoch {} (previous: {:?}) | config: {} | treasury_ata: {:?}",
+        epoch_id,
+        previous_epoch,
+        if config.is_some() { "ok" } else { "missing" },
+        treasury_ata_amount
+    );
+}
+
 /// Fetch current board state and slot from the chain
-async fn fetch_board_state(rpc_client: &RpcClient) -> Result<(Board, u64), String> {
+async fn fetch_board_state(rpc_client: &dyn RpcProvider) -> Result<(Board, u64), String> {
     // Get board account
     let (board_address, _) = board_pda();
     let board_account = rpc_client
@@ -135,3 +320,57 @@ async fn fetch_board_state(rpc_client: &RpcClient) -> Result<(Board, u64), Strin
     Ok((*board, current_slot))
 }
 
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pipeline::channels::PipelineChannels;
+    use crate::pipeline::failure_alerts::{FailureAlerts, FailureThresholds};
+    use crate::pipeline::shared_state::DeployerCache;
+    use solana_sdk::pubkey::Pubkey;
+
+    fn board(round_id: u64, epoch_id: u64) -> Board {
+        Board { round_id, start_slot: 0, end_slot: u64::MAX, epoch_id }
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_static_accounts_refetched_only_on_epoch_change() {
+        let shared = SharedState::new(
+            RpcClient::new_mock("succeeds".to_string()),
+            Pubkey::new_unique(),
+            Arc::new(tokio::sync::RwLock::new(DeployerCache::new(Duration::ZERO))),
+            FailureAlerts::new(FailureThresholds::default(), None),
+            Duration::ZERO,
+            0,
+        );
+        let channels = PipelineChannels::new();
+        let senders = ChannelSenders::from_channels(&channels);
+        // The mock RPC answers getMultipleAccounts with missing accounts, so a refetch clears the amount
+        let rpc_client = RpcClient::new_mock("succeeds".to_string());
+        let mut monitor = MonitorState::default();
+
+        apply_board_state(&shared, &senders, &rpc_client, &mut monitor, &board(10, 1), 0).await;
+        assert_eq!(shared.static_accounts.read().await.epoch_id, Some(1));
+        shared.static_accounts.write().await.treasury_ata_amount = Some(42);
+
+        // Same-epoch round change reuses the cache
+        apply_board_state(&shared, &senders, &rpc_client, &mut monitor, &board(11, 1), 0).await;
+        assert_eq!(shared.board_state.read().await.round_id, 11);
+        assert_eq!(shared.static_accounts.read().await.treasury_ata_amount, Some(42));
+
+        // A new epoch invalidates it
+        apply_board_state(&shared, &senders, &rpc_client, &mut monitor, &board(12, 2), 0).await;
+        let cache = shared.static_accounts.read().await;
+        assert_eq!(cache.epoch_id, Some(2));
+        assert_eq!(cache.treasury_ata_amount, None, "re-fetched for the new epoch");
+    }
+
+    #[test]
+    fn test_reconnect_backoff_doubles_to_cap_and_resets() {
+        let mut backoff = ReconnectBackoff::new();
+        let waits: Vec<u64> = (0..9).map(|_| backoff.next_wait().as_millis() as u64).collect();
+        assert_eq!(waits, vec![500, 1_000, 2_000, 4_000, 8_000, 16_000, 30_000, 30_000, 30_000]);
+
+        backoff.reset();
+        assert_eq!(backoff.next_wait(), RECONNECT_BACKOFF_MIN);
+    }
+}
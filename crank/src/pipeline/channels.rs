@@ -43,6 +43,14 @@ pub struct PipelineChannels {
     pub to_deployer_batcher: mpsc::Sender<MinerTask>,
     pub from_deployer_batcher: mpsc::Receiver<MinerTask>,
     
+    /// Miners whose rewards were just claimed, checked for profit above target
+    pub to_profit_sweeper: mpsc::Sender<MinerTask>,
+    pub from_profit_sweeper: mpsc::Receiver<MinerTask>,
+    
+    /// Miners whose checkpoint just settled rewards, compounded for opted-in managers
+    pub to_compounder: mpsc::Sender<MinerTask>,
+    pub from_compounder: mpsc::Receiver<MinerTask>,
+    
     // === Transaction flow channels ===
     
     /// Batched transactions ready for signing
@@ -82,6 +90,8 @@ impl PipelineChannels {
         let (to_deployment_check, from_deployment_check) = mpsc::channel(MINER_CHANNEL_SIZE);
         let (to_checkpoint_batcher, from_checkpoint_batcher) = mpsc::channel(MINER_CHANNEL_SIZE);
         let (to_deployer_batcher, from_deployer_batcher) = mpsc::channel(MINER_CHANNEL_SIZE);
+        let (to_profit_sweeper, from_profit_sweeper) = mpsc::channel(MINER_CHANNEL_SIZE);
+        let (to_compounder, from_compounder) = mpsc::channel(MINER_CHANNEL_SIZE);
         
         let (to_tx_processor, from_tx_processor) = mpsc::channel(TX_CHANNEL_SIZE);
         let (to_tx_sender, from_tx_sender) = mpsc::channel(TX_CHANNEL_SIZE);
@@ -106,6 +116,10 @@ impl PipelineChannels {
             from_checkpoint_batcher,
             to_deployer_batcher,
             from_deployer_batcher,
+            to_profit_sweeper,
+            from_profit_sweeper,
+            to_compounder,
+            from_compounder,
             to_tx_processor,
             from_tx_processor,
             to_tx_sender,
@@ -149,6 +163,8 @@ pub struct ChannelSenders {
     pub to_deployment_check: mpsc::Sender<MinerTask>,
     pub to_checkpoint_batcher: mpsc::Sender<MinerTask>,
     pub to_deployer_batcher: mpsc::Sender<MinerTask>,
+    pub to_profit_sweeper: mpsc::Sender<MinerTask>,
+    pub to_compounder: mpsc::Sender<MinerTask>,
     pub to_tx_processor: mpsc::Sender<BatchedTx>,
     pub to_tx_sender: mpsc::Sender<SignedTx>,
     pub to_confirmation: mpsc::Sender<PendingConfirmation>,
@@ -168,6 +184,8 @@ impl ChannelSenders {
             to_deployment_check: channels.to_deployment_check.clone(),
             to_checkpoint_batcher: channels.to_checkpoint_batcher.clone(),
             to_deployer_batcher: channels.to_deployer_batcher.clone(),
+            to_profit_sweeper: channels.to_profit_sweeper.clone(),
+            to_compounder: channels.to_compounder.clone(),
             to_tx_processor: channels.to_tx_processor.clone(),
             to_tx_sender: channels.to_tx_sender.clone(),
             to_confirmation: channels.to_confirmation.clone(),
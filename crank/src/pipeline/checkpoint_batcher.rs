@@ -1,7 +1,7 @@
 //! Checkpoint Batcher System
 //!
 //! Batches checkpoint transactions (up to 5 miners per transaction or 5 second timeout).
-//! Includes recycle_sol for miners that have SOL to recycle.
+//! Includes recycle_sol for miners whose SOL rewards the recycle policy wants claimed.
 
 use std::sync::Arc;
 use std::time::Duration;
@@ -17,6 +17,9 @@ use tokio::sync::mpsc;
 use tokio::time::timeout;
 use tracing::{debug, error, info, warn};
 
+use crate::config::RecyclePolicy;
+use crate::rpc::RpcProvider;
+
 use super::channels::ChannelSenders;
 use super::shared_state::SharedState;
 use super::types::{BatchedTx, MinerTask, TxType};
@@ -36,6 +39,7 @@ pub async fn run(
     rpc_client: Arc<RpcClient>,
     deploy_authority: Arc<Keypair>,
     priority_fee: u64,
+    recycle_policy: RecyclePolicy,
 ) {
     info!("[CheckpointBatcher] Starting...");
 
@@ -58,9 +62,10 @@ pub async fn run(
                         process_batch(
                             &shared,
                             &senders,
-                            &rpc_client,
+                            rpc_client.as_ref(),
                             &deploy_authority,
                             priority_fee,
+                            recycle_policy,
                             std::mem::take(&mut batch),
                         )
                         .await;
@@ -80,9 +85,10 @@ pub async fn run(
                     process_batch(
                         &shared,
                         &senders,
-                        &rpc_client,
+                        rpc_client.as_ref(),
                         &deploy_authority,
                         priority_fee,
+                        recycle_policy,
                         std::mem::take(&mut batch),
                     )
                     .await;
@@ -95,9 +101,10 @@ pub async fn run(
                     process_batch(
                         &shared,
                         &senders,
-                        &rpc_client,
+                        rpc_client.as_ref(),
                         &deploy_authority,
                         priority_fee,
+                        recycle_policy,
                         std::mem::take(&mut batch),
                     )
                     .await;
@@ -118,9 +125,10 @@ pub async fn run(
 async fn process_batch(
     shared: &Arc<SharedState>,
     senders: &ChannelSenders,
-    rpc_client: &RpcClient,
+    rpc_client: &dyn RpcProvider,
     deploy_authority: &Keypair,
     priority_fee: u64,
+    recycle_policy: RecyclePolicy,
     batch: Vec<MinerTask>,
 ) {
     if batch.is_empty() {
@@ -141,7 +149,7 @@ async fn process_batch(
             .map(|task| {
                 let miner = cache.get(&task.miner_address);
                 let checkpoint_round = miner.map(|m| m.round_id).unwrap_or(0);
-                let has_sol_to_recycle = miner.map(|m| m.rewards_sol > 0).unwrap_or(false);
+                let has_sol_to_recycle = miner.map(|m| recycle_policy.should_recycle(m.rewards_sol)).unwrap_or(false);
                 (checkpoint_round, has_sol_to_recycle)
             })
             .collect()
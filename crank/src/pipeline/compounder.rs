@@ -0,0 +1,207 @@
+//! Compounder System
+//!
+//! After checkpoints land, claims the SOL rewards they settled for opted-in
+//! managers straight back into the managed_miner_auth autodeploy balance, so
+//! the next deploys run on the larger bankroll.
+//! Batches up to 5 miners per transaction (or 5 second timeout).
+//!
+//! Uses RecycleSol, which the deploy authority may sign for any manager, as a
+//! step of its own: it runs whatever AUTO_RECYCLE decided for the checkpoint.
+
+use std::collections::HashSet;
+use std::sync::Arc;
+use std::time::Duration;
+
+use evore::instruction::recycle_sol;
+use evore::ore_api::Miner;
+use solana_client::rpc_client::RpcClient;
+use solana_sdk::{
+    compute_budget::ComputeBudgetInstruction,
+    pubkey::Pubkey,
+    signature::{Keypair, Signer},
+    transaction::Transaction,
+};
+use steel::AccountDeserialize;
+use tokio::sync::mpsc;
+use tokio::time::timeout;
+use tracing::{error, info, warn};
+
+use super::channels::ChannelSenders;
+use super::shared_state::SharedState;
+use super::types::{BatchedTx, MinerTask, TxType};
+use super::AUTH_ID;
+use crate::format::format_sol;
+
+/// Maximum miners per compound transaction
+const MAX_BATCH_SIZE: usize = 5;
+
+/// Timeout for batching (wait for more miners before sending)
+const BATCH_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Run the compounder system
+///
+/// With no managers opted in the compounder is disabled and drains its channel.
+pub async fn run(
+    shared: Arc<SharedState>,
+    senders: ChannelSenders,
+    mut rx: mpsc::Receiver<MinerTask>,
+    rpc_client: Arc<RpcClient>,
+    deploy_authority: Arc<Keypair>,
+    priority_fee: u64,
+    managers: HashSet<Pubkey>,
+) {
+    if managers.is_empty() {
+        info!("[Compounder] No managers opted in, compounder disabled");
+        while rx.recv().await.is_some() {}
+        return;
+    }
+
+    info!("[Compounder] Starting... ({} managers)", managers.len());
+
+    let mut batch: Vec<MinerTask> = Vec::with_capacity(MAX_BATCH_SIZE);
+    let mut total_batched = 0u64;
+
+    loop {
+        // Try to receive with timeout
+        let recv_result = if batch.is_empty() {
+            // No batch started, wait indefinitely for first item
+            rx.recv().await.ok_or(())
+        } else {
+            // Batch started, wait with timeout
+            match timeout(BATCH_TIMEOUT, rx.recv()).await {
+                Ok(Some(task)) => Ok(task),
+                Ok(None) => Err(()), // Channel closed
+                Err(_) => {
+                    // Timeout - process current batch
+                    if process_batch(&shared, &senders, &rpc_client, &deploy_authority, priority_fee, std::mem::take(&mut batch)).await {
+                        total_batched += 1;
+                    }
+                    continue;
+                }
+            }
+        };
+
+        match recv_result {
+            Ok(task) => {
+                if !managers.contains(&task.manager()) {
+                    continue;
+                }
+                // Checkpoint batches can repeat a miner, only compound it once per batch
+                if batch.iter().any(|t| t.miner_auth == task.miner_auth) {
+                    continue;
+                }
+                batch.push(task);
+
+                // Process batch if full
+                if batch.len() >= MAX_BATCH_SIZE
+                    && process_batch(&shared, &senders, &rpc_client, &deploy_authority, priority_fee, std::mem::take(&mut batch)).await
+                {
+                    total_batched += 1;
+                }
+            }
+            Err(_) => {
+                // Channel closed, process remaining batch
+                if !batch.is_empty()
+                    && process_batch(&shared, &senders, &rpc_client, &deploy_authority, priority_fee, std::mem::take(&mut batch)).await
+                {
+                    total_batched += 1;
+                }
+                break;
+            }
+        }
+    }
+
+    info!("[Compounder] Shutting down. Total batches: {}", total_batched);
+}
+
+/// Process a batch of miners, returns true if a compound transaction was sent
+async fn process_batch(
+    shared: &Arc<SharedState>,
+    senders: &ChannelSenders,
+    rpc_client: &RpcClient,
+    deploy_authority: &Keypair,
+    priority_fee: u64,
+    batch: Vec<MinerTask>,
+) -> bool {
+    if batch.is_empty() {
+        return false;
+    }
+
+    // Current claimable SOL on each ORE miner
+    let addresses: Vec<Pubkey> = batch.iter().map(|t| t.miner_address).collect();
+    let accounts = match rpc_client.get_multiple_accounts(&addresses) {
+        Ok(accounts) => accounts,
+        Err(e) => {
+            error!("[Compounder] Failed to fetch miners: {}. Dropping batch.", e);
+            return false;
+        }
+    };
+
+    // ~30k CU per recycle (ORE claim_sol CPI)
+    let cu_per_compound = 30_000u32;
+    let mut instructions = vec![
+        ComputeBudgetInstruction::set_compute_unit_limit(cu_per_compound * batch.len() as u32),
+        ComputeBudgetInstruction::set_compute_unit_price(priority_fee),
+    ];
+    let mut compounded: Vec<MinerTask> = Vec::with_capacity(batch.len());
+    let mut compounded_total = 0u64;
+
+    for (task, account) in batch.into_iter().zip(accounts.iter()) {
+        let rewards_sol = account
+            .as_ref()
+            .and_then(|a| Miner::try_from_bytes(&a.data).ok())
+            .map(|m| m.rewards_sol)
+            .unwrap_or(0);
+        if rewards_sol == 0 {
+            continue;
+        }
+
+        instructions.push(recycle_sol(deploy_authority.pubkey(), task.manager(), AUTH_ID));
+        compounded_total += rewards_sol;
+        compounded.push(task);
+    }
+
+    if compounded.is_empty() {
+        return false;
+    }
+
+    // Get recent blockhash
+    let recent_blockhash = match rpc_client.get_latest_blockhash() {
+        Ok(bh) => bh,
+        Err(e) => {
+            error!("[Compounder] Failed to get blockhash: {}. Dropping batch.", e);
+            return false;
+        }
+    };
+
+    // Build transaction
+    let mut tx = Transaction::new_with_payer(&instructions, Some(&deploy_authority.pubkey()));
+    tx.sign(&[deploy_authority], recent_blockhash);
+
+    // Convert to versioned transaction for the pipeline
+    let versioned_tx = match solana_sdk::transaction::VersionedTransaction::try_from(tx) {
+        Ok(vtx) => vtx,
+        Err(e) => {
+            error!("[Compounder] Failed to convert transaction: {}", e);
+            return false;
+        }
+    };
+
+    let compounded_count = compounded.len();
+    let round_id = compounded.first().map(|t| t.round_id).unwrap_or(0);
+    let batched_tx = BatchedTx::new(versioned_tx, compounded, TxType::Compound, round_id);
+
+    // Send to transaction processor
+    if let Err(e) = senders.to_tx_processor.send(batched_tx).await {
+        warn!("[Compounder] Failed to send to tx processor: {}", e);
+        return false;
+    }
+
+    shared.stats.increment(&shared.stats.compounds_sent);
+    shared.stats.add(&shared.stats.compounded_lamports, compounded_total);
+    info!(
+        "[Compounder] Sent compound of {} from {} miners",
+        format_sol(compounded_total), compounded_count
+    );
+    true
+}
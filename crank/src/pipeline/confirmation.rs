@@ -4,21 +4,26 @@
 //! - Polls get_signature_statuses() with batches of up to 200 signatures
 //! - Routes confirmed/failed transactions appropriately
 //! - Handles timeouts and retries
+//! - Reads deploy outcomes from the `DeployEvent`s in confirmed deploy logs
 
 use std::collections::HashMap;
 use std::sync::Arc;
 use std::time::{Duration, Instant};
 
+use base64::Engine;
+use evore::events::DeployEvent;
 use solana_sdk::signature::Signature;
-use tokio::sync::mpsc;
+use tokio::sync::{broadcast, mpsc};
 use tokio::time::interval;
 use tracing::{debug, error, info, warn};
 
 use crate::sender::TxSender;
 
 use super::channels::ChannelSenders;
+use super::deployer_batcher::deploy_spend;
 use super::shared_state::SharedState;
 use super::types::{FailedBatch, MinerTask, PendingConfirmation, TxType};
+use super::AUTH_ID;
 
 /// Maximum signatures per batch check
 const MAX_BATCH_SIZE: usize = 200;
@@ -29,11 +34,25 @@ const CHECK_INTERVAL: Duration = Duration::from_millis(400);
 /// Timeout for confirmations (in seconds)
 const CONFIRMATION_TIMEOUT_SECS: u64 = 60;
 
+/// Prefix the runtime puts before each `sol_log_data` entry
+const PROGRAM_DATA_PREFIX: &str = "Program data: ";
+
+/// Decode every DeployEvent in a transaction's log messages
+fn parse_deploy_events(logs: &[String]) -> Vec<DeployEvent> {
+    logs.iter()
+        .filter_map(|log| log.strip_prefix(PROGRAM_DATA_PREFIX))
+        .flat_map(|fields| fields.split(' '))
+        .filter_map(|field| base64::engine::general_purpose::STANDARD.decode(field).ok())
+        .filter_map(|bytes| DeployEvent::from_log_bytes(&bytes))
+        .collect()
+}
+
 /// Run the confirmation system
 pub async fn run(
     shared: Arc<SharedState>,
     senders: ChannelSenders,
     mut rx: mpsc::Receiver<PendingConfirmation>,
+    mut shutdown_rx: broadcast::Receiver<()>,
     rpc_url: String,
 ) {
     info!("[Confirmation] Starting...");
@@ -49,8 +68,20 @@ pub async fn run(
     let mut failed_count = 0u64;
     let mut timeout_count = 0u64;
 
+    // Set on shutdown: nothing new is being sent, exit once what's tracked has settled
+    let mut draining = false;
+
     loop {
         tokio::select! {
+            // Shutdown: keep checking until every tracked txn has confirmed, failed or timed out
+            _ = shutdown_rx.recv(), if !draining => {
+                info!("[Confirmation] Shutting down, {} txns still pending", pending.len());
+                draining = true;
+                if pending.is_empty() {
+                    return;
+                }
+            }
+
             // Receive new pending confirmations
             Some(confirmation) = rx.recv() => {
                 debug!(
@@ -63,6 +94,10 @@ pub async fn run(
             // Periodic batch check
             _ = check_interval.tick() => {
                 if pending.is_empty() {
+                    if draining {
+                        info!("[Confirmation] All pending txns settled");
+                        return;
+                    }
                     continue;
                 }
 
@@ -98,6 +133,12 @@ pub async fn run(
                             TxType::FeeUpdate => {
                                 shared.stats.increment(&shared.stats.fee_updates_failed);
                             }
+                            TxType::ProfitSweep => {
+                                shared.stats.increment(&shared.stats.profit_sweeps_failed);
+                            }
+                            TxType::Compound => {
+                                shared.stats.increment(&shared.stats.compounds_failed);
+                            }
                         }
 
                         // Send to failure handler for intelligent retry
@@ -127,6 +168,7 @@ pub async fn run(
                                     Some(true) => {
                                         // Confirmed!
                                         if let Some(confirmation) = pending.remove(sig) {
+                                            shared.failure_alerts.record_success();
                                             let elapsed = confirmation.sent_at.elapsed().as_millis() as u64;
 
                                             info!(
@@ -142,17 +184,41 @@ pub async fn run(
                                                     shared.stats.add(&shared.stats.deploy_total_time_ms, elapsed);
                                                     shared.stats.increment(&shared.stats.deploy_count_for_avg);
                                                     shared.stats.add(&shared.stats.miners_deployed, miner_count);
+                                                    // The program logs what each miner actually deployed; without
+                                                    // logs fall back to what the batcher expected to spend
+                                                    let events = match sender.get_transaction_logs(sig).await {
+                                                        Ok(Some(logs)) => Some(parse_deploy_events(&logs)),
+                                                        Ok(None) => None,
+                                                        Err(e) => {
+                                                            warn!("[Confirmation] Failed to fetch deploy logs for {}: {}", sig, e);
+                                                            None
+                                                        }
+                                                    };
+                                                    let mut miner_addresses = Vec::with_capacity(confirmation.miners.len());
+                                                    for task in &confirmation.miners {
+                                                        let (deployed, fees) = match &events {
+                                                            Some(events) => match events
+                                                                .iter()
+                                                                .find(|e| e.manager == task.manager() && e.auth_id == AUTH_ID)
+                                                            {
+                                                                Some(event) => (event.total_deployed, event.fee),
+                                                                None => {
+                                                                    debug!("[Confirmation] No deploy event for miner {}", task.miner_address);
+                                                                    continue;
+                                                                }
+                                                            },
+                                                            None => deploy_spend(&task.deployer),
+                                                        };
+                                                        shared.stats.add(&shared.stats.deploy_lamports_confirmed, deployed);
+                                                        shared.stats.add(&shared.stats.deploy_fees_confirmed, fees);
+                                                        miner_addresses.push(task.miner_address);
+                                                    }
                                                     confirmed_deploy += 1;
 
                                                     // Record deploy confirmed time for round total timing
                                                     shared.stats.record_deploy_confirmed();
 
                                                     // Mark miners as deployed in cache
-                                                    let miner_addresses: Vec<_> = confirmation
-                                                        .miners
-                                                        .iter()
-                                                        .map(|t| t.miner_address)
-                                                        .collect();
                                                     let mut cache = shared.miner_cache.write().await;
                                                     cache.mark_deployed(&miner_addresses, confirmation.round_id);
                                                 }
@@ -162,8 +228,20 @@ pub async fn run(
                                                     shared.stats.increment(&shared.stats.checkpoint_count_for_avg);
                                                     shared.stats.add(&shared.stats.miners_checkpointed, miner_count);
                                                     confirmed_checkpoint += 1;
+
+                                                    // Rewards were just settled: compound what wasn't recycled, check for profit to sweep
+                                                    for miner in confirmation.miners {
+                                                        if let Err(e) = senders.to_compounder.send(miner.clone()).await {
+                                                            warn!("[Confirmation] Failed to send miner to compounder: {}", e);
+                                                        }
+                                                        if let Err(e) = senders.to_profit_sweeper.send(miner).await {
+                                                            warn!("[Confirmation] Failed to send miner to profit sweeper: {}", e);
+                                                        }
+                                                    }
                                                 }
                                                 TxType::FeeUpdate => {
+                                                    // Cached deployers still carry the old expected fees
+                                                    shared.deployers.write().await.invalidate();
                                                     shared.stats.increment(&shared.stats.fee_updates_confirmed);
                                                     shared.stats.add(&shared.stats.fee_update_total_time_ms, elapsed);
                                                     shared.stats.increment(&shared.stats.fee_update_count_for_avg);
@@ -176,6 +254,12 @@ pub async fn run(
                                                         }
                                                     }
                                                 }
+                                                TxType::ProfitSweep => {
+                                                    shared.stats.increment(&shared.stats.profit_sweeps_confirmed);
+                                                }
+                                                TxType::Compound => {
+                                                    shared.stats.increment(&shared.stats.compounds_confirmed);
+                                                }
                                             }
                                         }
                                     }
@@ -202,6 +286,12 @@ pub async fn run(
                                                 TxType::FeeUpdate => {
                                                     shared.stats.increment(&shared.stats.fee_updates_failed);
                                                 }
+                                                TxType::ProfitSweep => {
+                                                    shared.stats.increment(&shared.stats.profit_sweeps_failed);
+                                                }
+                                                TxType::Compound => {
+                                                    shared.stats.increment(&shared.stats.compounds_failed);
+                                                }
                                             }
 
                                             // Send to failure handler for intelligent retry
@@ -253,3 +343,30 @@ pub async fn run(
     }
 }
 
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use solana_sdk::pubkey::Pubkey;
+
+    #[test]
+    fn test_parse_deploy_events_from_logs() {
+        let event = DeployEvent {
+            manager: Pubkey::new_unique(),
+            auth_id: AUTH_ID,
+            round_id: 70_149,
+            total_deployed: 500_000,
+            fee: 25_015,
+            squares_mask: 0b1_1111,
+            _padding: [0; 4],
+        };
+        let encode = |bytes: &[u8]| base64::engine::general_purpose::STANDARD.encode(bytes);
+        let logs = vec![
+            "Program EvoRe invoke [1]".to_string(),
+            format!("{}{}", PROGRAM_DATA_PREFIX, encode(&[1, 2, 3])),
+            format!("{}{}", PROGRAM_DATA_PREFIX, encode(&event.to_log_bytes())),
+            "Program EvoRe success".to_string(),
+        ];
+        assert_eq!(parse_deploy_events(&logs), vec![event]);
+        assert!(parse_deploy_events(&logs[..2]).is_empty());
+    }
+}
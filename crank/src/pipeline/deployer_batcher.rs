@@ -6,7 +6,6 @@
 use std::sync::Arc;
 use std::time::Duration;
 
-use evore::instruction::mm_full_autodeploy;
 use solana_client::rpc_client::RpcClient;
 use solana_sdk::{
     commitment_config::CommitmentConfig,
@@ -17,7 +16,9 @@ use tokio::sync::mpsc;
 use tokio::time::timeout;
 use tracing::{debug, error, info, warn};
 
-use crate::lut::get_miner_auth_pda;
+use crate::config::DeployerInfo;
+use crate::crank::{dedup_by_miner_auth, next_batch_indices, take_batch};
+use crate::deploy_plan::DeployPlan;
 
 use super::channels::ChannelSenders;
 use super::shared_state::SharedState;
@@ -36,7 +37,22 @@ const DEPLOY_AMOUNT: u64 = 2_800;
 /// Deploy to all squares (bitmask with all 25 bits set)
 const SQUARES_MASK: u32 = 0x1FFFFFF;
 
+/// The pipeline's deploy for `task`: DEPLOY_AMOUNT on every square, checkpointing first if needed
+pub fn plan_deploy(task: &MinerTask, checkpoint_round: Option<u64>) -> DeployPlan {
+    DeployPlan::new(task.deployer.clone(), AUTH_ID, task.round_id, DEPLOY_AMOUNT, SQUARES_MASK)
+        .with_checkpoint(checkpoint_round)
+}
+
+/// Lamports one miner's deploy lands on the board and the fees it pays (deployer + protocol)
+pub fn deploy_spend(deployer: &DeployerInfo) -> (u64, u64) {
+    let plan = DeployPlan::new(deployer.clone(), AUTH_ID, 0, DEPLOY_AMOUNT, SQUARES_MASK);
+    (plan.total_deployed(), plan.total_fees())
+}
+
 /// Run the deployer batcher system
+///
+/// A batch holds at most `max_per_manager` miners of one manager (0 = no limit); the
+/// manager's other miners wait in `pending` for the batches after it.
 pub async fn run(
     shared: Arc<SharedState>,
     senders: ChannelSenders,
@@ -44,16 +60,17 @@ pub async fn run(
     rpc_client: Arc<RpcClient>,
     deploy_authority: Arc<Keypair>,
     priority_fee: u64,
+    max_per_manager: usize,
 ) {
     info!("[DeployerBatcher] Starting...");
 
-    let mut batch: Vec<MinerTask> = Vec::with_capacity(MAX_BATCH_SIZE);
+    let mut pending: Vec<MinerTask> = Vec::with_capacity(MAX_BATCH_SIZE);
     let mut total_batched = 0u64;
     let mut total_miners = 0u64;
 
     loop {
         // Try to receive with timeout
-        let recv_result = if batch.is_empty() {
+        let recv_result = if pending.is_empty() {
             // No batch started, wait indefinitely for first item
             rx.recv().await.ok_or(())
         } else {
@@ -63,20 +80,19 @@ pub async fn run(
                 Ok(None) => Err(()), // Channel closed
                 Err(_) => {
                     // Timeout - process current batch
-                    if !batch.is_empty() {
-                        let batch_size = batch.len();
-                        process_batch(
-                            &shared,
-                            &senders,
-                            &rpc_client,
-                            &deploy_authority,
-                            priority_fee,
-                            std::mem::take(&mut batch),
-                        )
-                        .await;
-                        total_batched += 1;
-                        total_miners += batch_size as u64;
-                    }
+                    let batch = take_batch(&mut pending, MAX_BATCH_SIZE, max_per_manager, MinerTask::manager);
+                    let batch_size = batch.len();
+                    process_batch(
+                        &shared,
+                        &senders,
+                        &rpc_client,
+                        &deploy_authority,
+                        priority_fee,
+                        batch,
+                    )
+                    .await;
+                    total_batched += 1;
+                    total_miners += batch_size as u64;
                     continue;
                 }
             }
@@ -84,10 +100,11 @@ pub async fn run(
 
         match recv_result {
             Ok(task) => {
-                batch.push(task);
+                pending.push(task);
 
                 // Process batch if full
-                if batch.len() >= MAX_BATCH_SIZE {
+                if next_batch_indices(&pending, MAX_BATCH_SIZE, max_per_manager, MinerTask::manager).len() >= MAX_BATCH_SIZE {
+                    let batch = take_batch(&mut pending, MAX_BATCH_SIZE, max_per_manager, MinerTask::manager);
                     let batch_size = batch.len();
                     process_batch(
                         &shared,
@@ -95,7 +112,7 @@ pub async fn run(
                         &rpc_client,
                         &deploy_authority,
                         priority_fee,
-                        std::mem::take(&mut batch),
+                        batch,
                     )
                     .await;
                     total_batched += 1;
@@ -103,8 +120,9 @@ pub async fn run(
                 }
             }
             Err(_) => {
-                // Channel closed, process remaining batch
-                if !batch.is_empty() {
+                // Channel closed, process remaining batches
+                while !pending.is_empty() {
+                    let batch = take_batch(&mut pending, MAX_BATCH_SIZE, max_per_manager, MinerTask::manager);
                     let batch_size = batch.len();
                     process_batch(
                         &shared,
@@ -112,7 +130,7 @@ pub async fn run(
                         &rpc_client,
                         &deploy_authority,
                         priority_fee,
-                        std::mem::take(&mut batch),
+                        batch,
                     )
                     .await;
                     total_batched += 1;
@@ -142,6 +160,12 @@ async fn process_batch(
         return;
     }
 
+    // Two deploys for the same miner in one tx would fail the whole batch
+    let (batch, duplicates) = dedup_by_miner_auth(batch, |t| t.miner_auth);
+    if duplicates > 0 {
+        warn!("[DeployerBatcher] Dropped {} duplicate miner(s) from batch", duplicates);
+    }
+
     let batch_size = batch.len();
     let round_id = batch.first().map(|t| t.round_id).unwrap_or(0);
 
@@ -150,29 +174,27 @@ async fn process_batch(
         batch_size, round_id
     );
 
-    // Get checkpoint rounds for miners that need it
-    let checkpoint_rounds: Vec<Option<u64>> = {
+    // Plan each deploy, checkpointing miners that need it
+    let plans: Vec<DeployPlan> = {
         let cache = shared.miner_cache.read().await;
         batch
             .iter()
             .map(|task| {
                 let miner = cache.get(&task.miner_address);
-                miner.and_then(|m| {
+                let checkpoint_round = miner.and_then(|m| {
                     if m.checkpoint_id < m.round_id {
                         Some(m.round_id)
                     } else {
                         None
                     }
-                })
+                });
+                plan_deploy(task, checkpoint_round)
             })
             .collect()
     };
 
     // Collect miner_auths for LUT lookup
-    let miner_auths: Vec<_> = batch
-        .iter()
-        .map(|t| get_miner_auth_pda(t.manager(), AUTH_ID))
-        .collect();
+    let miner_auths: Vec<_> = plans.iter().map(DeployPlan::miner_auth).collect();
 
     // Get LUTs
     let lut_accounts = {
@@ -214,19 +236,8 @@ async fn process_batch(
     ];
 
     // Add mm_full_autodeploy instruction for each miner
-    for (task, checkpoint_round) in batch.iter().zip(checkpoint_rounds.iter()) {
-        // checkpoint_round_id: if checkpoint needed, use that round; otherwise use current round
-        let checkpoint_round_id = checkpoint_round.unwrap_or(round_id);
-
-        instructions.push(mm_full_autodeploy(
-            deploy_authority.pubkey(),
-            task.manager(),
-            AUTH_ID,
-            round_id,
-            checkpoint_round_id,
-            DEPLOY_AMOUNT,
-            SQUARES_MASK,
-        ));
+    for plan in &plans {
+        instructions.push(plan.full_autodeploy_ix(deploy_authority.pubkey()));
     }
 
     // Build versioned transaction with LUTs
@@ -274,3 +285,56 @@ async fn process_batch(
     );
 }
 
+
+#[cfg(test)]
+mod tests {
+    use evore::consts::DEPLOY_FEE;
+    use evore::instruction::mm_full_autodeploy;
+    use solana_sdk::pubkey::Pubkey;
+
+    use super::*;
+    use crate::crank::full_autodeploy_cu_limit;
+    use crate::lut::get_miner_auth_pda;
+
+    fn task(bps_fee: u64, round_id: u64) -> MinerTask {
+        let deployer = DeployerInfo {
+            deployer_address: Pubkey::new_unique(),
+            manager_address: Pubkey::new_unique(),
+            bps_fee,
+            flat_fee: 715,
+            expected_bps_fee: 0,
+            expected_flat_fee: 0,
+            max_per_round: 0,
+        };
+        let miner_auth = get_miner_auth_pda(deployer.manager_address, AUTH_ID);
+        MinerTask::new(deployer, Pubkey::new_unique(), miner_auth, round_id)
+    }
+
+    #[test]
+    fn test_plan_deploy_builds_full_autodeploy() {
+        let signer = Pubkey::new_unique();
+        let fresh = task(100, 42);
+        let behind = task(0, 42);
+
+        let plans = [plan_deploy(&fresh, None), plan_deploy(&behind, Some(40))];
+
+        assert_eq!(plans[0].miner_auth(), fresh.miner_auth);
+        assert!(!plans[0].needs_checkpoint() && !plans[0].needs_recycle);
+        assert!(plans[1].needs_checkpoint() && plans[1].needs_recycle);
+
+        // 2,800 on all 25 squares, 1% + 715 flat on top
+        assert_eq!(plans[0].total_deployed(), 70_000);
+        assert_eq!(plans[0].deployer_fee(), 700 + 715);
+        assert_eq!(deploy_spend(&fresh.deployer), (70_000, 700 + 715 + DEPLOY_FEE));
+
+        // No pending checkpoint means the current round is passed through
+        let ix = plans[0].full_autodeploy_ix(signer);
+        let expected = mm_full_autodeploy(signer, fresh.manager(), AUTH_ID, 42, 42, DEPLOY_AMOUNT, SQUARES_MASK);
+        assert_eq!(ix, expected);
+        let ix = plans[1].full_autodeploy_ix(signer);
+        let expected = mm_full_autodeploy(signer, behind.manager(), AUTH_ID, 42, 40, DEPLOY_AMOUNT, SQUARES_MASK);
+        assert_eq!(ix, expected);
+
+        assert!(full_autodeploy_cu_limit(&plans[1..]) > full_autodeploy_cu_limit(&plans[..1]));
+    }
+}
@@ -4,6 +4,7 @@
 //! - Sufficient SOL balance
 //! - Enough slots remaining (>= 20)
 //! - Not already deployed this round
+//! - Not cooling down after a failed deploy (re-queued once the cooldown passes)
 //!
 //! Routes miners to:
 //! - DeployerBatcher (pass all checks) - checkpoint is bundled with deploy via mm_full_autodeploy
@@ -16,13 +17,44 @@ use std::time::Instant;
 use tokio::sync::mpsc;
 use tracing::{debug, info, warn};
 
+use crate::miner_cache::CachedMiner;
+
 use super::channels::ChannelSenders;
 use super::shared_state::{RoundPhase, SharedState};
 use super::types::MinerTask;
 
 /// Minimum balance required for deployment (in lamports)
 /// This should cover rent + fees for the deploy transaction
-const MIN_DEPLOY_BALANCE: u64 = 10_000_000; // 0.01 SOL
+pub const MIN_DEPLOY_BALANCE: u64 = 10_000_000; // 0.01 SOL
+
+/// Where a miner that passed the round and retry checks goes next
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DeployRoute {
+    /// Enough balance to deploy; a pending checkpoint is bundled via mm_full_autodeploy
+    Deploy { needs_checkpoint: bool },
+    /// Can't deploy but has unchecked rounds, so checkpoint only to collect rewards
+    CheckpointOnly,
+    /// Can't deploy and nothing to checkpoint
+    LowBalance,
+}
+
+/// Check 2: whether the cached miner already deployed in `round_id`
+pub fn already_deployed(miner: &CachedMiner, round_id: u64) -> bool {
+    miner.round_id == round_id && miner.has_deployed
+}
+
+/// Checks 5 and 6: sufficient balance, and a checkpoint left over from previous rounds
+/// (checkpoint_id tracks last checkpointed round, round_id is last deployed round)
+pub fn route(miner: &CachedMiner) -> DeployRoute {
+    let needs_checkpoint = miner.checkpoint_id < miner.round_id;
+    if miner.auth_balance >= MIN_DEPLOY_BALANCE {
+        DeployRoute::Deploy { needs_checkpoint }
+    } else if needs_checkpoint {
+        DeployRoute::CheckpointOnly
+    } else {
+        DeployRoute::LowBalance
+    }
+}
 
 /// Run the deployment check system with multiple workers
 pub async fn run(
@@ -124,7 +156,7 @@ async fn run_worker(
         }
 
         // Check 2: Has miner already deployed this round?
-        if miner.round_id == current_round_id && miner.has_deployed {
+        if already_deployed(&miner, current_round_id) {
             debug!(
                 "{} {} - already deployed this round",
                 prefix, task.manager()
@@ -136,7 +168,21 @@ async fn run_worker(
             continue;
         }
 
-        // Check 3: Does miner have retry limit exceeded?
+        // Check 3: Did this miner's deploy just fail? Come back once the cooldown passes
+        if let Some(wait) = shared.deploy_cooldowns.remaining(&task.miner_address) {
+            debug!(
+                "{} {} - cooling down after failed deploy, retrying miner {} in {}ms",
+                prefix, task.manager(), task.miner_address, wait.as_millis()
+            );
+            let to_deployment_check = senders.to_deployment_check.clone();
+            tokio::spawn(async move {
+                tokio::time::sleep(wait).await;
+                let _ = to_deployment_check.send(task).await;
+            });
+            continue;
+        }
+
+        // Check 4: Does miner have retry limit exceeded?
         if !task.can_retry() && task.retry_count > 0 {
             warn!(
                 "{} SKIPPED max_retries | manager: {} | miner: {} | auth: {} | retries: {}",
@@ -146,46 +192,45 @@ async fn run_worker(
             continue;
         }
 
-        // Check 4: Sufficient balance?
+        // Check 5 + 6: Sufficient balance? Needs checkpoint from previous rounds?
         let balance = miner.auth_balance;
-        let has_sufficient_balance = balance >= MIN_DEPLOY_BALANCE;
-
-        // Check 5: Needs checkpoint from previous rounds?
-        // (checkpoint_id tracks last checkpointed round, round_id is last deployed round)
-        let needs_checkpoint = miner.checkpoint_id < miner.round_id;
 
         // Route based on checks
-        if has_sufficient_balance {
-            // Can deploy - checkpoint (if needed) will be bundled with deploy via mm_full_autodeploy
-            debug!(
-                "{} {} - ready to deploy (balance: {} lamports, needs_checkpoint: {})",
-                prefix, task.manager(), balance, needs_checkpoint
-            );
-            if let Err(e) = senders.to_deployer_batcher.send(task).await {
-                warn!("{} Failed to send to deployer batcher: {}", prefix, e);
+        match route(&miner) {
+            DeployRoute::Deploy { needs_checkpoint } => {
+                // Can deploy - checkpoint (if needed) will be bundled with deploy via mm_full_autodeploy
+                debug!(
+                    "{} {} - ready to deploy (balance: {} lamports, needs_checkpoint: {})",
+                    prefix, task.manager(), balance, needs_checkpoint
+                );
+                if let Err(e) = senders.to_deployer_batcher.send(task).await {
+                    warn!("{} Failed to send to deployer batcher: {}", prefix, e);
+                }
+                deploy_count += 1;
             }
-            deploy_count += 1;
-        } else if needs_checkpoint {
-            // Can't deploy this round (insufficient balance) but has unchecked rounds
-            // Do checkpoint-only to collect any pending rewards from previous deploys
-            info!(
-                "{} CHECKPOINT_ONLY | manager: {} | miner: {} | auth: {} | balance: {} < {} | checkpoint_id: {} < round_id: {}",
-                prefix, task.manager(), task.miner_address, task.miner_auth, balance, MIN_DEPLOY_BALANCE, miner.checkpoint_id, miner.round_id
-            );
-            if let Err(e) = senders.to_checkpoint_batcher.send(task).await {
-                warn!("{} Failed to send to checkpoint batcher: {}", prefix, e);
+            DeployRoute::CheckpointOnly => {
+                // Can't deploy this round (insufficient balance) but has unchecked rounds
+                // Do checkpoint-only to collect any pending rewards from previous deploys
+                info!(
+                    "{} CHECKPOINT_ONLY | manager: {} | miner: {} | auth: {} | balance: {} < {} | checkpoint_id: {} < round_id: {}",
+                    prefix, task.manager(), task.miner_address, task.miner_auth, balance, MIN_DEPLOY_BALANCE, miner.checkpoint_id, miner.round_id
+                );
+                if let Err(e) = senders.to_checkpoint_batcher.send(task).await {
+                    warn!("{} Failed to send to checkpoint batcher: {}", prefix, e);
+                }
+                checkpoint_count += 1;
+            }
+            DeployRoute::LowBalance => {
+                // Can't deploy and no checkpoint needed - nothing to do
+                warn!(
+                    "{} SKIPPED low_balance | manager: {} | miner: {} | auth: {} | balance: {} < {}",
+                    prefix, task.manager(), task.miner_address, task.miner_auth, balance, MIN_DEPLOY_BALANCE
+                );
+                shared
+                    .stats
+                    .increment(&shared.stats.miners_skipped_low_balance);
+                skipped_count += 1;
             }
-            checkpoint_count += 1;
-        } else {
-            // Can't deploy and no checkpoint needed - nothing to do
-            warn!(
-                "{} SKIPPED low_balance | manager: {} | miner: {} | auth: {} | balance: {} < {}",
-                prefix, task.manager(), task.miner_address, task.miner_auth, balance, MIN_DEPLOY_BALANCE
-            );
-            shared
-                .stats
-                .increment(&shared.stats.miners_skipped_low_balance);
-            skipped_count += 1;
         }
 
         // Update stats
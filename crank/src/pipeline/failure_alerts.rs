@@ -0,0 +1,172 @@
+//! Failure alert thresholds
+//!
+//! Individual failed transactions are routine and only logged by the failure
+//! handler. This tracks failures per round and consecutive failures (reset by
+//! any confirmation) and raises one alert when either crosses its threshold.
+//! A threshold re-arms on the next round / after the next confirmation.
+
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::time::Duration;
+
+use tracing::{debug, warn};
+
+/// Thresholds that trigger an alert (0 = disabled)
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct FailureThresholds {
+    pub per_round: u64,
+    pub consecutive: u64,
+}
+
+/// Which threshold a failure pushed over
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FailureAlert {
+    /// `failures` transactions have failed in the current round
+    PerRound { failures: u64 },
+    /// The last `failures` transactions all failed
+    Consecutive { failures: u64 },
+}
+
+impl FailureAlert {
+    fn name(&self) -> &'static str {
+        match self {
+            FailureAlert::PerRound { .. } => "failures_per_round_exceeded",
+            FailureAlert::Consecutive { .. } => "consecutive_failures_exceeded",
+        }
+    }
+
+    fn failures(&self) -> u64 {
+        match self {
+            FailureAlert::PerRound { failures } | FailureAlert::Consecutive { failures } => *failures,
+        }
+    }
+}
+
+/// Failure counters shared by the confirmation and failure handler systems
+pub struct FailureAlerts {
+    thresholds: FailureThresholds,
+    round_failures: AtomicU64,
+    consecutive_failures: AtomicU64,
+    round_alerted: AtomicBool,
+    consecutive_alerted: AtomicBool,
+    /// Webhook that receives alerts as JSON (None = log only)
+    webhook_url: Option<String>,
+    client: reqwest::Client,
+}
+
+impl FailureAlerts {
+    pub fn new(thresholds: FailureThresholds, webhook_url: Option<String>) -> Self {
+        let client = reqwest::Client::builder()
+            .timeout(Duration::from_secs(10))
+            .build()
+            .unwrap();
+
+        Self {
+            thresholds,
+            round_failures: AtomicU64::new(0),
+            consecutive_failures: AtomicU64::new(0),
+            round_alerted: AtomicBool::new(false),
+            consecutive_alerted: AtomicBool::new(false),
+            webhook_url,
+            client,
+        }
+    }
+
+    /// Count a failed transaction; returns the alerts this failure triggered
+    pub fn record_failure(&self) -> Vec<FailureAlert> {
+        let round = self.round_failures.fetch_add(1, Ordering::Relaxed) + 1;
+        let consecutive = self.consecutive_failures.fetch_add(1, Ordering::Relaxed) + 1;
+
+        let mut alerts = Vec::new();
+        if crossed(round, self.thresholds.per_round, &self.round_alerted) {
+            alerts.push(FailureAlert::PerRound { failures: round });
+        }
+        if crossed(consecutive, self.thresholds.consecutive, &self.consecutive_alerted) {
+            alerts.push(FailureAlert::Consecutive { failures: consecutive });
+        }
+        alerts
+    }
+
+    /// A confirmation ends the failure streak
+    pub fn record_success(&self) {
+        self.consecutive_failures.store(0, Ordering::Relaxed);
+        self.consecutive_alerted.store(false, Ordering::Relaxed);
+    }
+
+    /// Start counting a new round
+    pub fn reset_round(&self) {
+        self.round_failures.store(0, Ordering::Relaxed);
+        self.round_alerted.store(false, Ordering::Relaxed);
+    }
+
+    /// Log the alert and POST it to the webhook, if one is configured
+    pub fn notify(&self, alert: FailureAlert, round_id: u64) {
+        let threshold = match alert {
+            FailureAlert::PerRound { .. } => self.thresholds.per_round,
+            FailureAlert::Consecutive { .. } => self.thresholds.consecutive,
+        };
+        warn!(
+            "[FailureAlert] {} in round {}: {} failures (threshold {})",
+            alert.name(), round_id, alert.failures(), threshold
+        );
+
+        let Some(url) = self.webhook_url.clone() else {
+            return;
+        };
+        let body = serde_json::json!({
+            "event": alert.name(),
+            "round_id": round_id,
+            "failures": alert.failures(),
+            "threshold": threshold,
+        });
+        let client = self.client.clone();
+        // Fire and forget so a slow webhook never holds up failure handling
+        tokio::spawn(async move {
+            match client.post(&url).json(&body).send().await {
+                Ok(resp) if resp.status().is_success() => debug!("Sent {} webhook", body["event"]),
+                Ok(resp) => warn!("Alert webhook returned {}", resp.status()),
+                Err(e) => warn!("Alert webhook failed: {}", e),
+            }
+        });
+    }
+}
+
+/// True exactly once when `count` first reaches a nonzero `threshold`
+fn crossed(count: u64, threshold: u64, alerted: &AtomicBool) -> bool {
+    threshold > 0 && count >= threshold && !alerted.swap(true, Ordering::Relaxed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_failures_past_threshold_alert_once() {
+        let alerts = FailureAlerts::new(FailureThresholds { per_round: 5, consecutive: 3 }, None);
+
+        assert!(alerts.record_failure().is_empty());
+        assert!(alerts.record_failure().is_empty());
+        assert_eq!(alerts.record_failure(), vec![FailureAlert::Consecutive { failures: 3 }]);
+        assert!(alerts.record_failure().is_empty(), "the streak already alerted");
+
+        // The streak re-arms after a confirmation, the round count keeps going
+        alerts.record_success();
+        assert_eq!(alerts.record_failure(), vec![FailureAlert::PerRound { failures: 5 }]);
+        assert!(alerts.record_failure().is_empty());
+        assert_eq!(alerts.record_failure(), vec![FailureAlert::Consecutive { failures: 3 }]);
+        for _ in 0..10 {
+            assert!(alerts.record_failure().is_empty());
+        }
+
+        // A new round re-arms the per-round threshold
+        alerts.reset_round();
+        alerts.record_success();
+        for _ in 0..4 {
+            alerts.record_failure();
+        }
+        assert_eq!(alerts.record_failure(), vec![FailureAlert::PerRound { failures: 5 }]);
+
+        // Zero thresholds never alert
+        let disabled = FailureAlerts::new(FailureThresholds::default(), None);
+        assert!((0..20).all(|_| disabled.record_failure().is_empty()));
+    }
+}
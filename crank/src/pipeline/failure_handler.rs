@@ -5,6 +5,9 @@
 //! 2. Refreshing the problematic miner's cache data
 //! 3. Sending the problematic miner back to fee_check (fresh start)
 //! 4. Sending other miners in the batch directly to deployment_check (fast retry)
+//!
+//! Miners blamed for a failed deploy are put on the deploy cooldown, so deployment
+//! check holds their retry back instead of hammering them straight away.
 
 use std::sync::Arc;
 
@@ -49,6 +52,29 @@ pub async fn run(
             failed_batch.tx_type, failed_batch.signature, batch_size, failed_batch.error
         );
 
+        for alert in shared.failure_alerts.record_failure() {
+            shared.stats.increment(&shared.stats.failure_alerts_fired);
+            shared.failure_alerts.notify(alert, failed_batch.round_id);
+        }
+
+        // Profit sweeps are not retried here, the next claim re-evaluates the balance
+        if failed_batch.tx_type == TxType::ProfitSweep {
+            warn!(
+                "[FailureHandler] Dropping failed profit sweep {} ({} miners)",
+                failed_batch.signature, batch_size
+            );
+            continue;
+        }
+
+        // Nor are compounds, the next checkpoint re-evaluates the rewards
+        if failed_batch.tx_type == TxType::Compound {
+            warn!(
+                "[FailureHandler] Dropping failed compound {} ({} miners)",
+                failed_batch.signature, batch_size
+            );
+            continue;
+        }
+
         // Try to identify which miner caused the failure
         let problematic_index = identify_problematic_miner(&failed_batch);
         
@@ -60,6 +86,9 @@ pub async fn run(
                     "[FailureHandler] Identified problematic miner at index {}: {} (manager: {})",
                     idx, problematic_miner.miner_address, problematic_miner.manager()
                 );
+                if failed_batch.tx_type == TxType::Deploy {
+                    shared.deploy_cooldowns.record_failure(problematic_miner.miner_address);
+                }
 
                 // Refresh the problematic miner's cache
                 {
@@ -122,6 +151,10 @@ pub async fn run(
                 );
 
                 for miner in failed_batch.miners {
+                    if failed_batch.tx_type == TxType::Deploy {
+                        shared.deploy_cooldowns.record_failure(miner.miner_address);
+                    }
+
                     // Refresh each miner's cache
                     {
                         let mut cache = shared.miner_cache.write().await;
@@ -205,6 +238,9 @@ fn identify_problematic_miner(failed_batch: &FailedBatch) -> Option<usize> {
                     }
                 }
             }
+            TxType::ProfitSweep | TxType::Compound => {
+                // Profit sweeps and compounds are dropped in run() before reaching here
+            }
         }
     }
 
@@ -10,11 +10,36 @@ use std::sync::Arc;
 use tokio::sync::mpsc;
 use tracing::{debug, info, warn};
 
+use crate::config::DeployerInfo;
+
 use super::channels::ChannelSenders;
 use super::shared_state::SharedState;
 use super::types::MinerTask;
 use super::REQUIRED_FLAT_FEE;
 
+/// Where the fee check sends a deployer
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FeeGate {
+    /// Both fees match, continue to the LUT check
+    Ok,
+    /// Our flat_fee isn't REQUIRED_FLAT_FEE yet, the fee updater sets it
+    NeedsFeeUpdate,
+    /// The user's expected_flat_fee doesn't cover REQUIRED_FLAT_FEE, never cranked
+    UserFeeTooLow,
+}
+
+/// Check 1: expected_flat_fee (set by user) must be >= REQUIRED_FLAT_FEE
+/// Check 2: flat_fee (set by us as deploy_authority) must be REQUIRED_FLAT_FEE
+pub fn check_fees(deployer: &DeployerInfo) -> FeeGate {
+    if deployer.expected_flat_fee < REQUIRED_FLAT_FEE {
+        FeeGate::UserFeeTooLow
+    } else if deployer.flat_fee != REQUIRED_FLAT_FEE {
+        FeeGate::NeedsFeeUpdate
+    } else {
+        FeeGate::Ok
+    }
+}
+
 /// Run the fee check system
 pub async fn run(
     shared: Arc<SharedState>,
@@ -30,9 +55,10 @@ pub async fn run(
     while let Some(task) = rx.recv().await {
         let deployer = &task.deployer;
 
-        // Check 1: expected_flat_fee (set by user) must be >= REQUIRED_FLAT_FEE
-        // This means the user accepts at least our required fee
-        if deployer.expected_flat_fee < REQUIRED_FLAT_FEE {
+        let gate = check_fees(deployer);
+
+        // Check 1: the user must accept at least our required fee
+        if gate == FeeGate::UserFeeTooLow {
             warn!(
                 "[FeeCheck] SKIPPED - user expected_fee too low | manager: {} | miner: {} | auth: {} | expected_flat_fee: {} < required: {}",
                 deployer.manager_address,
@@ -46,9 +72,8 @@ pub async fn run(
             continue;
         }
 
-        // Check 2: flat_fee (set by us as deploy_authority) must be REQUIRED_FLAT_FEE
-        // If not, we need to update it
-        if deployer.flat_fee != REQUIRED_FLAT_FEE {
+        // Check 2: our actual fee must be the required fee, if not we need to update it
+        if gate == FeeGate::NeedsFeeUpdate {
             debug!(
                 "[FeeCheck] {} needs actual fee update: {} -> {}",
                 deployer.manager_address, deployer.flat_fee, REQUIRED_FLAT_FEE
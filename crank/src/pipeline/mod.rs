@@ -15,36 +15,49 @@
 //!                 → [pass] → DeployerBatcher
 //!                 → [needs checkpoint] → CheckpointBatcher
 //!                 → [fail] → Log & Skip
+//!
+//! Confirmation
+//!     → [checkpoint confirmed] → ProfitSweeper → TxProcessor → ...
+//!                              → Compounder → TxProcessor → ...
 //! ```
 
 pub mod board_state_monitor;
 pub mod channels;
 pub mod checkpoint_batcher;
+pub mod compounder;
 pub mod confirmation;
 pub mod deployer_batcher;
 pub mod deployment_check;
 pub mod expected_fee_updater;
+pub mod failure_alerts;
 pub mod failure_handler;
 pub mod fee_check;
 pub mod lut_check;
 pub mod lut_creation;
+pub mod profit_sweeper;
 pub mod shared_state;
+pub mod trace;
 pub mod tx_processor;
 pub mod tx_sender;
 pub mod types;
 
 use std::sync::Arc;
+use std::time::Duration;
 
 use solana_client::rpc_client::RpcClient;
 use solana_sdk::signature::{Keypair, Signer};
 use tokio::sync::mpsc;
-use tracing::{error, info};
+use tokio::task::JoinHandle;
+use tracing::{error, info, warn};
+
+use crate::config::Config;
+use crate::crank::{discover_deployers, CrankError};
+use crate::pda_cache;
 
-use crate::config::{Config, DeployerInfo};
-use crate::crank::CrankError;
+use failure_alerts::{FailureAlerts, FailureThresholds};
 
 pub use channels::{ChannelSenders, PipelineChannels};
-pub use shared_state::{BoardState, PipelineStats, RoundPhase, SharedState};
+pub use shared_state::{BoardState, PipelineStats, RoundLimit, RoundPhase, RoundSummary, RunLoopRounds, SharedDeployerCache, SharedState};
 pub use types::{BatchedTx, MinerTask, PendingConfirmation, SignedTx, TxType};
 
 /// Required flat fee in lamports that users must agree to
@@ -53,18 +66,35 @@ pub const REQUIRED_FLAT_FEE: u64 = 715;
 /// Auth ID used for managed miners (always 0 for now)
 pub const AUTH_ID: u64 = 0;
 
+/// Most time shutdown waits for in-flight transactions, a little past confirmation's own timeout
+const SHUTDOWN_GRACE: Duration = Duration::from_secs(70);
+
 /// Run the pipeline
 pub async fn run_pipeline(
     config: Config,
     rpc_client: Arc<RpcClient>,
     deploy_authority: Arc<Keypair>,
+    deployer_cache: SharedDeployerCache,
 ) -> Result<(), CrankError> {
     info!("Starting pipeline architecture...");
+    if config.max_rounds > 0 {
+        info!("Stopping after deploying across {} rounds", config.max_rounds);
+    }
 
     // Initialize shared state
     let shared = Arc::new(SharedState::new(
-        &config.rpc_url,
+        config.rpc_client(),
         deploy_authority.pubkey(),
+        deployer_cache,
+        FailureAlerts::new(
+            FailureThresholds {
+                per_round: config.alert_failures_per_round,
+                consecutive: config.alert_consecutive_failures,
+            },
+            config.alert_webhook_url.clone(),
+        ),
+        Duration::from_millis(config.deploy_failure_cooldown_ms),
+        config.max_rounds,
     ));
 
     // Create channels
@@ -103,6 +133,14 @@ pub async fn run_pipeline(
         &mut channels.from_deployer_batcher,
         mpsc::channel(1).1,
     );
+    let profit_sweeper_rx = std::mem::replace(
+        &mut channels.from_profit_sweeper,
+        mpsc::channel(1).1,
+    );
+    let compounder_rx = std::mem::replace(
+        &mut channels.from_compounder,
+        mpsc::channel(1).1,
+    );
     let tx_processor_rx = std::mem::replace(
         &mut channels.from_tx_processor,
         mpsc::channel(1).1,
@@ -128,6 +166,7 @@ pub async fn run_pipeline(
             senders.clone(),
             rpc_client.clone(),
             config.poll_interval_ms,
+            config.ws_url.clone(),
         )),
         // Fee Check (pipeline entry point, single worker)
         tokio::spawn(fee_check::run(
@@ -173,6 +212,7 @@ pub async fn run_pipeline(
             rpc_client.clone(),
             deploy_authority.clone(),
             config.priority_fee,
+            config.recycle_policy(),
         )),
         // Deployer Batcher
         tokio::spawn(deployer_batcher::run(
@@ -182,6 +222,30 @@ pub async fn run_pipeline(
             rpc_client.clone(),
             deploy_authority.clone(),
             config.priority_fee,
+            config.max_deploys_per_manager,
+        )),
+        // Profit Sweeper (withdraws balance above target after claims)
+        tokio::spawn(profit_sweeper::run(
+            shared.clone(),
+            senders.clone(),
+            profit_sweeper_rx,
+            rpc_client.clone(),
+            deploy_authority.clone(),
+            config.priority_fee,
+            config.profit_destination.map(|destination| profit_sweeper::SweepTarget {
+                destination,
+                target_lamports: config.profit_target_lamports,
+            }),
+        )),
+        // Compounder (recycles settled SOL rewards for opted-in managers)
+        tokio::spawn(compounder::run(
+            shared.clone(),
+            senders.clone(),
+            compounder_rx,
+            rpc_client.clone(),
+            deploy_authority.clone(),
+            config.priority_fee,
+            config.compound_managers.iter().copied().collect(),
         )),
         // Transaction Processor
         tokio::spawn(tx_processor::run(
@@ -196,13 +260,9 @@ pub async fn run_pipeline(
             senders.clone(),
             tx_sender_rx,
             config.rpc_url.clone(),
-        )),
-        // Confirmation System
-        tokio::spawn(confirmation::run(
-            shared.clone(),
-            senders.clone(),
-            confirmation_rx,
-            config.rpc_url.clone(),
+            config.min_authority_reserve_lamports,
+            config.alert_webhook_url.clone(),
+            config.send_fanout.clone(),
         )),
         // Failure Handler (processes failed batches)
         tokio::spawn(failure_handler::run(
@@ -212,6 +272,14 @@ pub async fn run_pipeline(
             rpc_client.clone(),
         )),
     ];
+    // Confirmation System, kept apart so shutdown can let it settle what's in flight
+    let confirmation_handle = tokio::spawn(confirmation::run(
+        shared.clone(),
+        senders.clone(),
+        confirmation_rx,
+        senders.shutdown.subscribe(),
+        config.rpc_url.clone(),
+    ));
 
     info!("All pipeline systems spawned");
 
@@ -229,13 +297,25 @@ pub async fn run_pipeline(
                 }
 
                 info!("New round detected: {}", new_round_id);
+                if let Some(finished_round_id) = last_round_id {
+                    if close_round(&shared, finished_round_id, config.round_summary).await {
+                        info!(
+                            "Deployed across {} rounds (MAX_ROUNDS), shutting down",
+                            shared.round_limit.completed()
+                        );
+                        shutdown_systems(&senders, handles, confirmation_handle, SHUTDOWN_GRACE).await;
+                        return Ok(());
+                    }
+                }
                 last_round_id = Some(new_round_id);
 
                 // Reset stats for new round
                 shared.stats.reset();
+                shared.failure_alerts.reset_round();
+                shared.deploy_cooldowns.reset();
 
                 // Discover deployers
-                let deployers = match discover_deployers(&rpc_client, &deploy_authority).await {
+                let deployers = match discover_deployers(&shared.deployers, rpc_client.as_ref(), deploy_authority.pubkey()).await {
                     Ok(d) => d,
                     Err(e) => {
                         error!("Failed to discover deployers: {}", e);
@@ -249,6 +329,7 @@ pub async fn run_pipeline(
                 }
 
                 info!("Found {} deployers", deployers.len());
+                pda_cache::prime(&deployers, AUTH_ID);
 
                 // Update miner cache
                 {
@@ -262,7 +343,7 @@ pub async fn run_pipeline(
                 // Load LUTs
                 {
                     let mut lut_cache = shared.lut_cache.write().await;
-                    if let Err(e) = lut_cache.load_all_luts() {
+                    if let Err(e) = lut_cache.load_luts(config.lut_cache_path.as_deref()) {
                         error!("Failed to load LUTs: {}", e);
                         // Continue anyway - we can create LUTs as needed
                     }
@@ -310,78 +391,110 @@ pub async fn run_pipeline(
     }
 }
 
-/// Discover all deployers we have authority over
-async fn discover_deployers(
-    rpc_client: &RpcClient,
-    deploy_authority: &Keypair,
-) -> Result<Vec<DeployerInfo>, CrankError> {
-    use evore::state::Deployer;
-    use solana_client::rpc_config::{RpcAccountInfoConfig, RpcProgramAccountsConfig};
-    use solana_client::rpc_filter::{Memcmp, RpcFilterType};
-    use solana_account_decoder::UiAccountEncoding;
-    use steel::AccountDeserialize;
-
-    let deploy_authority_pubkey = deploy_authority.pubkey();
-
-    // Deployer size: 8 discriminator + 32 manager_key + 32 deploy_authority + 8 bps_fee + 8 flat_fee + 8 expected_bps_fee + 8 expected_flat_fee + 8 max_per_round = 112
-    const DEPLOYER_SIZE: u64 = 112;
-
-    info!(
-        "Scanning for deployers with deploy_authority: {} (data_size={})",
-        deploy_authority_pubkey, DEPLOYER_SIZE
-    );
+/// Log the round that just ended and count it against MAX_ROUNDS; true once the limit is reached
+async fn close_round(shared: &SharedState, finished_round_id: u64, round_summary: bool) -> bool {
+    if round_summary {
+        info!("{}", RoundSummary::from_stats(finished_round_id, &shared.stats));
+    }
+    let cache_stats = shared.miner_cache.write().await.take_stats();
+    info!("Round {} miner cache: {}", finished_round_id, cache_stats);
 
-    // Use getProgramAccounts with optimized filters
-    let accounts = rpc_client
-        .get_program_accounts_with_config(
-            &evore::id(),
-            RpcProgramAccountsConfig {
-                filters: Some(vec![
-                    // Filter by data size first (most efficient filter)
-                    RpcFilterType::DataSize(DEPLOYER_SIZE),
-                    // Filter by account discriminator (Deployer = 101)
-                    RpcFilterType::Memcmp(Memcmp::new_base58_encoded(
-                        0,
-                        &[101, 0, 0, 0, 0, 0, 0, 0], // EvoreAccount::Deployer discriminator
-                    )),
-                    // Filter by deploy_authority (offset: 8 discriminator + 32 manager_key = 40)
-                    RpcFilterType::Memcmp(Memcmp::new_base58_encoded(
-                        40,
-                        deploy_authority_pubkey.as_ref(),
-                    )),
-                ]),
-                account_config: RpcAccountInfoConfig {
-                    encoding: Some(UiAccountEncoding::Base64),
-                    ..Default::default()
-                },
-                ..Default::default()
-            },
+    let deployed = shared.stats.get(&shared.stats.miners_deployed) > 0;
+    shared.round_limit.finish_round(deployed)
+}
+
+/// Stop every system for a clean exit
+///
+/// The other systems are stopped straight away so nothing new gets sent, then
+/// confirmation is told to shut down and given `grace` to settle the transactions
+/// still in flight. False if it had to be stopped with transactions pending.
+async fn shutdown_systems(
+    senders: &ChannelSenders,
+    systems: Vec<JoinHandle<()>>,
+    confirmation: JoinHandle<()>,
+    grace: Duration,
+) -> bool {
+    for handle in &systems {
+        handle.abort();
+    }
+    let _ = senders.shutdown.send(());
+
+    let confirmation_abort = confirmation.abort_handle();
+    let all = systems.into_iter().chain(std::iter::once(confirmation));
+    if tokio::time::timeout(grace, futures::future::join_all(all)).await.is_err() {
+        warn!("Transactions still pending after {}s, stopping anyway", grace.as_secs());
+        confirmation_abort.abort();
+        return false;
+    }
+    info!("Pipeline stopped");
+    true
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use shared_state::DeployerCache;
+    use solana_sdk::pubkey::Pubkey;
+    use std::sync::atomic::{AtomicBool, Ordering};
+
+    fn shared_state(max_rounds: u64) -> SharedState {
+        SharedState::new(
+            RpcClient::new_mock("succeeds".to_string()),
+            Pubkey::new_unique(),
+            Arc::new(tokio::sync::RwLock::new(DeployerCache::new(Duration::ZERO))),
+            FailureAlerts::new(FailureThresholds::default(), None),
+            Duration::ZERO,
+            max_rounds,
         )
-        .map_err(|e| CrankError::Rpc(e.to_string()))?;
-
-    info!("GPA returned {} deployer accounts", accounts.len());
-
-    let mut deployers = Vec::new();
-
-    for (deployer_address, account) in accounts {
-        match Deployer::try_from_bytes(&account.data) {
-            Ok(deployer) => {
-                deployers.push(DeployerInfo {
-                    deployer_address,
-                    manager_address: deployer.manager_key,
-                    bps_fee: deployer.bps_fee,
-                    flat_fee: deployer.flat_fee,
-                    expected_bps_fee: deployer.expected_bps_fee,
-                    expected_flat_fee: deployer.expected_flat_fee,
-                    max_per_round: deployer.max_per_round,
-                });
-            }
-            Err(e) => {
-                error!("Failed to parse deployer {}: {:?}", deployer_address, e);
-            }
-        }
     }
 
-    Ok(deployers)
-}
+    #[tokio::test]
+    async fn test_close_round_stops_after_max_rounds() {
+        let shared = shared_state(2);
+
+        shared.stats.add(&shared.stats.miners_deployed, 3);
+        assert!(!close_round(&shared, 1, true).await);
 
+        shared.stats.reset();
+        assert!(!close_round(&shared, 2, false).await, "nothing of ours landed in round 2");
+
+        shared.stats.add(&shared.stats.miners_deployed, 1);
+        assert!(close_round(&shared, 3, false).await, "second deployed round ends the run");
+        assert_eq!(shared.round_limit.completed(), 2);
+
+        // MAX_ROUNDS = 0 keeps going
+        let unlimited = shared_state(0);
+        unlimited.stats.add(&unlimited.stats.miners_deployed, 1);
+        assert!(!close_round(&unlimited, 1, false).await);
+    }
+
+    #[tokio::test]
+    async fn test_shutdown_lets_confirmation_settle_and_stops_the_rest() {
+        let channels = PipelineChannels::new();
+        let senders = ChannelSenders::from_channels(&channels);
+
+        // Stands in for confirmation with a txn still in flight when shutdown arrives
+        let settled = Arc::new(AtomicBool::new(false));
+        let mut shutdown_rx = senders.shutdown.subscribe();
+        let flag = settled.clone();
+        let confirmation = tokio::spawn(async move {
+            let _ = shutdown_rx.recv().await;
+            tokio::time::sleep(Duration::from_millis(50)).await;
+            flag.store(true, Ordering::SeqCst);
+        });
+        let system_state = Arc::new(());
+        let held = system_state.clone();
+        let system = tokio::spawn(async move {
+            let _held = held;
+            std::future::pending::<()>().await
+        });
+
+        assert!(shutdown_systems(&senders, vec![system], confirmation, Duration::from_secs(5)).await);
+        assert!(settled.load(Ordering::SeqCst), "in-flight txn settled before exit");
+        assert_eq!(Arc::strong_count(&system_state), 1, "other systems were stopped");
+
+        // A confirmation that never settles is cut off after the grace period
+        let stuck = tokio::spawn(std::future::pending::<()>());
+        assert!(!shutdown_systems(&senders, vec![], stuck, Duration::from_millis(50)).await);
+    }
+}
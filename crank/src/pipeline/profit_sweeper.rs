@@ -0,0 +1,354 @@
+//! Profit Sweeper System
+//!
+//! After claims land, withdraws any managed_miner_auth balance above the
+//! configured profit target and forwards it to a cold wallet.
+//! Batches up to 5 miners per transaction (or 5 second timeout).
+//!
+//! WithdrawAutodeployBalance must be signed by the manager authority and always
+//! pays out to the signer, so only managers whose authority is the crank's
+//! deploy authority can be swept. Each swept miner gets a withdraw + transfer pair.
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use evore::instruction::withdraw_autodeploy_balance;
+use evore::state::Manager;
+use solana_client::rpc_client::RpcClient;
+use solana_sdk::{
+    account::Account,
+    compute_budget::ComputeBudgetInstruction,
+    pubkey::Pubkey,
+    signature::{Keypair, Signer},
+    system_instruction,
+    transaction::Transaction,
+};
+use steel::AccountDeserialize;
+use tokio::sync::mpsc;
+use tokio::time::timeout;
+use tracing::{debug, error, info, warn};
+
+use super::channels::ChannelSenders;
+use super::shared_state::SharedState;
+use super::types::{BatchedTx, MinerTask, TxType};
+use super::AUTH_ID;
+use crate::format::format_sol;
+
+/// Maximum miners per sweep transaction
+const MAX_BATCH_SIZE: usize = 5;
+
+/// Timeout for batching (wait for more miners before sending)
+const BATCH_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Rent-exempt minimum the program keeps in the managed_miner_auth PDA
+const AUTH_PDA_RENT: u64 = 890_880;
+
+/// Where and above what balance profits are swept
+#[derive(Debug, Clone, Copy)]
+pub struct SweepTarget {
+    /// Wallet that receives the swept lamports
+    pub destination: Pubkey,
+    /// Balance (on top of rent) left in each managed_miner_auth PDA
+    pub target_lamports: u64,
+}
+
+/// Lamports above `rent + target` that can be swept from a balance
+pub fn excess_above_target(balance: u64, target: u64) -> u64 {
+    balance
+        .saturating_sub(AUTH_PDA_RENT)
+        .saturating_sub(target)
+}
+
+/// Lamports to sweep from a miner, or None if its manager isn't signed for by `authority`
+fn sweepable(
+    authority: &Pubkey,
+    target: u64,
+    manager_account: Option<&Account>,
+    auth_account: Option<&Account>,
+) -> Option<u64> {
+    let is_ours = manager_account
+        .and_then(|a| Manager::try_from_bytes(&a.data).ok())
+        .map(|m| m.authority == *authority)
+        .unwrap_or(false);
+    if !is_ours {
+        return None;
+    }
+
+    let balance = auth_account.map(|a| a.lamports).unwrap_or(0);
+    Some(excess_above_target(balance, target))
+}
+
+/// Run the profit sweeper system
+///
+/// With no target configured the sweeper is disabled and drains its channel.
+pub async fn run(
+    shared: Arc<SharedState>,
+    senders: ChannelSenders,
+    mut rx: mpsc::Receiver<MinerTask>,
+    rpc_client: Arc<RpcClient>,
+    deploy_authority: Arc<Keypair>,
+    priority_fee: u64,
+    target: Option<SweepTarget>,
+) {
+    let target = match target {
+        Some(t) => t,
+        None => {
+            info!("[ProfitSweeper] No profit destination configured, sweeper disabled");
+            while rx.recv().await.is_some() {}
+            return;
+        }
+    };
+
+    info!(
+        "[ProfitSweeper] Starting... (target: {}, destination: {})",
+        format_sol(target.target_lamports), target.destination
+    );
+
+    let mut batch: Vec<MinerTask> = Vec::with_capacity(MAX_BATCH_SIZE);
+    let mut total_batched = 0u64;
+
+    loop {
+        // Try to receive with timeout
+        let recv_result = if batch.is_empty() {
+            // No batch started, wait indefinitely for first item
+            rx.recv().await.ok_or(())
+        } else {
+            // Batch started, wait with timeout
+            match timeout(BATCH_TIMEOUT, rx.recv()).await {
+                Ok(Some(task)) => Ok(task),
+                Ok(None) => Err(()), // Channel closed
+                Err(_) => {
+                    // Timeout - process current batch
+                    if process_batch(
+                        &shared,
+                        &senders,
+                        &rpc_client,
+                        &deploy_authority,
+                        priority_fee,
+                        target,
+                        std::mem::take(&mut batch),
+                    )
+                    .await
+                    {
+                        total_batched += 1;
+                    }
+                    continue;
+                }
+            }
+        };
+
+        match recv_result {
+            Ok(task) => {
+                // Checkpoint batches can repeat a miner, only sweep it once per batch
+                if batch.iter().any(|t| t.miner_auth == task.miner_auth) {
+                    continue;
+                }
+                batch.push(task);
+
+                // Process batch if full
+                if batch.len() >= MAX_BATCH_SIZE
+                    && process_batch(
+                        &shared,
+                        &senders,
+                        &rpc_client,
+                        &deploy_authority,
+                        priority_fee,
+                        target,
+                        std::mem::take(&mut batch),
+                    )
+                    .await
+                {
+                    total_batched += 1;
+                }
+            }
+            Err(_) => {
+                // Channel closed, process remaining batch
+                if !batch.is_empty()
+                    && process_batch(
+                        &shared,
+                        &senders,
+                        &rpc_client,
+                        &deploy_authority,
+                        priority_fee,
+                        target,
+                        std::mem::take(&mut batch),
+                    )
+                    .await
+                {
+                    total_batched += 1;
+                }
+                break;
+            }
+        }
+    }
+
+    info!(
+        "[ProfitSweeper] Shutting down. Total batches: {}",
+        total_batched
+    );
+}
+
+/// Process a batch of miners, returns true if a sweep transaction was sent
+async fn process_batch(
+    shared: &Arc<SharedState>,
+    senders: &ChannelSenders,
+    rpc_client: &RpcClient,
+    deploy_authority: &Keypair,
+    priority_fee: u64,
+    target: SweepTarget,
+    batch: Vec<MinerTask>,
+) -> bool {
+    if batch.is_empty() {
+        return false;
+    }
+
+    // Fetch managers (to verify authority) and auth PDAs (for current balance) in one call
+    let mut addresses: Vec<Pubkey> = batch.iter().map(|t| t.manager()).collect();
+    addresses.extend(batch.iter().map(|t| t.miner_auth));
+    let accounts = match rpc_client.get_multiple_accounts(&addresses) {
+        Ok(accounts) => accounts,
+        Err(e) => {
+            error!(
+                "[ProfitSweeper] Failed to fetch balances: {}. Dropping batch.",
+                e
+            );
+            return false;
+        }
+    };
+    let (manager_accounts, auth_accounts) = accounts.split_at(batch.len());
+
+    // ~20k CU per withdraw + transfer pair
+    let cu_per_sweep = 20_000u32;
+    let mut instructions = vec![
+        ComputeBudgetInstruction::set_compute_unit_limit(cu_per_sweep * batch.len() as u32),
+        ComputeBudgetInstruction::set_compute_unit_price(priority_fee),
+    ];
+    let mut swept: Vec<MinerTask> = Vec::with_capacity(batch.len());
+    let mut swept_total = 0u64;
+
+    for ((task, manager_account), auth_account) in batch
+        .into_iter()
+        .zip(manager_accounts.iter())
+        .zip(auth_accounts.iter())
+    {
+        let excess = match sweepable(
+            &deploy_authority.pubkey(),
+            target.target_lamports,
+            manager_account.as_ref(),
+            auth_account.as_ref(),
+        ) {
+            Some(excess) => excess,
+            None => {
+                debug!(
+                    "[ProfitSweeper] Skipping {}: manager authority is not the deploy authority",
+                    task.manager()
+                );
+                continue;
+            }
+        };
+        if excess == 0 {
+            continue;
+        }
+
+        instructions.push(withdraw_autodeploy_balance(
+            deploy_authority.pubkey(),
+            task.manager(),
+            AUTH_ID,
+            excess,
+        ));
+        instructions.push(system_instruction::transfer(
+            &deploy_authority.pubkey(),
+            &target.destination,
+            excess,
+        ));
+        swept_total += excess;
+        swept.push(task);
+    }
+
+    if swept.is_empty() {
+        return false;
+    }
+
+    // Get recent blockhash
+    let recent_blockhash = match rpc_client.get_latest_blockhash() {
+        Ok(bh) => bh,
+        Err(e) => {
+            error!(
+                "[ProfitSweeper] Failed to get blockhash: {}. Dropping batch.",
+                e
+            );
+            return false;
+        }
+    };
+
+    // Build transaction
+    let mut tx = Transaction::new_with_payer(&instructions, Some(&deploy_authority.pubkey()));
+    tx.sign(&[deploy_authority], recent_blockhash);
+
+    // Convert to versioned transaction for the pipeline
+    let versioned_tx = match solana_sdk::transaction::VersionedTransaction::try_from(tx) {
+        Ok(vtx) => vtx,
+        Err(e) => {
+            error!("[ProfitSweeper] Failed to convert transaction: {}", e);
+            return false;
+        }
+    };
+
+    let swept_count = swept.len();
+    let round_id = swept.first().map(|t| t.round_id).unwrap_or(0);
+    let batched_tx = BatchedTx::new(versioned_tx, swept, TxType::ProfitSweep, round_id);
+
+    // Send to transaction processor
+    if let Err(e) = senders.to_tx_processor.send(batched_tx).await {
+        warn!("[ProfitSweeper] Failed to send to tx processor: {}", e);
+        return false;
+    }
+
+    shared.stats.increment(&shared.stats.profit_sweeps_sent);
+    shared.stats.add(&shared.stats.profit_swept_lamports, swept_total);
+    info!(
+        "[ProfitSweeper] Sent sweep of {} from {} miners to {}",
+        format_sol(swept_total), swept_count, target.destination
+    );
+    true
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use evore::state::EvoreAccount;
+    use steel::Zeroable;
+
+    fn account(lamports: u64, data: Vec<u8>) -> Account {
+        Account { lamports, data, owner: evore::id(), executable: false, rent_epoch: 0 }
+    }
+
+    fn manager_account(authority: Pubkey) -> Account {
+        let mut manager = Manager::zeroed();
+        manager.authority = authority;
+        let mut data = (EvoreAccount::Manager as u64).to_le_bytes().to_vec();
+        data.extend_from_slice(manager.to_bytes());
+        account(1_000_000, data)
+    }
+
+    #[test]
+    fn test_only_excess_above_target_is_swept() {
+        let authority = Pubkey::new_unique();
+        let ours = manager_account(authority);
+        let target = 5_000_000;
+        let auth = |balance| account(balance, vec![]);
+
+        // Rent and the target stay behind
+        assert_eq!(
+            sweepable(&authority, target, Some(&ours), Some(&auth(AUTH_PDA_RENT + target + 1_234_567))),
+            Some(1_234_567)
+        );
+        assert_eq!(sweepable(&authority, target, Some(&ours), Some(&auth(AUTH_PDA_RENT + target))), Some(0));
+        assert_eq!(sweepable(&authority, target, Some(&ours), Some(&auth(AUTH_PDA_RENT))), Some(0));
+        assert_eq!(sweepable(&authority, target, Some(&ours), None), Some(0));
+
+        // Managers we can't sign for are never swept
+        let theirs = manager_account(Pubkey::new_unique());
+        assert_eq!(sweepable(&authority, target, Some(&theirs), Some(&auth(100_000_000))), None);
+        assert_eq!(sweepable(&authority, target, None, Some(&auth(100_000_000))), None);
+    }
+}
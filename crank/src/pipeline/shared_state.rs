@@ -2,14 +2,22 @@
 //!
 //! Contains thread-safe state that is shared between pipeline systems.
 
+use evore::ore_api::Config as OreConfig;
+use solana_client::rpc_client::RpcClient;
 use solana_sdk::pubkey::Pubkey;
+use std::collections::HashMap;
 use std::sync::atomic::{AtomicU64, Ordering};
-use std::time::{Instant, SystemTime, UNIX_EPOCH};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
 use tokio::sync::RwLock;
 
+use crate::config::DeployerInfo;
+use crate::format::format_sol;
 use crate::lut::LutRegistry;
 use crate::miner_cache::MinerCache;
 
+use super::failure_alerts::FailureAlerts;
+
 /// Current phase of the round
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub enum RoundPhase {
@@ -139,6 +147,135 @@ impl BoardState {
     }
 }
 
+/// ORE accounts that only change across epochs (config, treasury ATA)
+///
+/// Keyed on `Board.epoch_id` so these are re-fetched once per epoch
+/// instead of on every round change.
+#[derive(Debug, Default)]
+pub struct StaticAccountCache {
+    /// Epoch the cached accounts were fetched in (None = never fetched)
+    pub epoch_id: Option<u64>,
+    /// ORE config account
+    pub config: Option<OreConfig>,
+    /// ORE balance held by the treasury token account
+    pub treasury_ata_amount: Option<u64>,
+}
+
+impl StaticAccountCache {
+    /// Whether the cached accounts are stale for the given epoch
+    pub fn needs_refresh(&self, epoch_id: u64) -> bool {
+        self.epoch_id != Some(epoch_id)
+    }
+
+    /// Store freshly fetched accounts for an epoch
+    pub fn update(&mut self, epoch_id: u64, config: Option<OreConfig>, treasury_ata_amount: Option<u64>) {
+        self.epoch_id = Some(epoch_id);
+        self.config = config;
+        self.treasury_ata_amount = treasury_ata_amount;
+    }
+}
+
+/// Result of the last deployer scan (getProgramAccounts), reused until the TTL expires
+///
+/// One instance is shared by the `run` loop and the pipeline so both paths see
+/// the same scan. A TTL of zero disables caching.
+#[derive(Debug)]
+pub struct DeployerCache {
+    ttl: Duration,
+    scanned: Option<(Instant, Vec<DeployerInfo>)>,
+}
+
+impl DeployerCache {
+    pub fn new(ttl: Duration) -> Self {
+        Self { ttl, scanned: None }
+    }
+
+    /// Cached deployers if the last scan is younger than the TTL at `now`
+    pub fn get_at(&self, now: Instant) -> Option<Vec<DeployerInfo>> {
+        match &self.scanned {
+            Some((at, deployers)) if now.saturating_duration_since(*at) < self.ttl => {
+                Some(deployers.clone())
+            }
+            _ => None,
+        }
+    }
+
+    /// Cached deployers if still fresh
+    pub fn get(&self) -> Option<Vec<DeployerInfo>> {
+        self.get_at(Instant::now())
+    }
+
+    /// Record a fresh scan taken at `now`
+    pub fn store_at(&mut self, deployers: Vec<DeployerInfo>, now: Instant) {
+        self.scanned = Some((now, deployers));
+    }
+
+    /// Record a fresh scan
+    pub fn store(&mut self, deployers: Vec<DeployerInfo>) {
+        self.store_at(deployers, Instant::now());
+    }
+
+    /// Drop the cached scan so the next discovery hits RPC
+    pub fn invalidate(&mut self) {
+        self.scanned = None;
+    }
+}
+
+/// Handle to the deployer cache shared between the `run` loop and the pipeline
+pub type SharedDeployerCache = Arc<RwLock<DeployerCache>>;
+
+/// Miners whose deploy failed recently, held back from retrying until the cooldown passes
+///
+/// Recorded by the failure handler, enforced by deployment check. Cleared on round
+/// change, so a failure never delays a miner's first deploy of the next round.
+/// A cooldown of zero disables it.
+#[derive(Debug)]
+pub struct DeployCooldowns {
+    cooldown: Duration,
+    failed_at: Mutex<HashMap<Pubkey, Instant>>,
+}
+
+impl DeployCooldowns {
+    pub fn new(cooldown: Duration) -> Self {
+        Self { cooldown, failed_at: Mutex::new(HashMap::new()) }
+    }
+
+    /// Record a failed deploy of `miner` at `now`
+    pub fn record_failure_at(&self, miner: Pubkey, now: Instant) {
+        if self.cooldown.is_zero() {
+            return;
+        }
+        self.failed_at.lock().unwrap().insert(miner, now);
+    }
+
+    /// Record a failed deploy of `miner`
+    pub fn record_failure(&self, miner: Pubkey) {
+        self.record_failure_at(miner, Instant::now());
+    }
+
+    /// Time `miner` must still wait at `now` before its next deploy (None = may deploy)
+    pub fn remaining_at(&self, miner: &Pubkey, now: Instant) -> Option<Duration> {
+        let mut failed_at = self.failed_at.lock().unwrap();
+        let at = *failed_at.get(miner)?;
+        let waited = now.saturating_duration_since(at);
+        if waited >= self.cooldown {
+            failed_at.remove(miner);
+            return None;
+        }
+        Some(self.cooldown - waited)
+    }
+
+    /// Time `miner` must still wait before its next deploy
+    pub fn remaining(&self, miner: &Pubkey) -> Option<Duration> {
+        self.remaining_at(miner, Instant::now())
+    }
+
+    /// Forget every failure (new round)
+    pub fn reset(&self) {
+        self.failed_at.lock().unwrap().clear();
+    }
+}
+
 /// Pipeline statistics for monitoring and logging
 #[derive(Debug, Default)]
 pub struct PipelineStats {
@@ -166,6 +303,8 @@ pub struct PipelineStats {
     pub deploys_failed: AtomicU64,
     pub deploy_total_time_ms: AtomicU64,
     pub deploy_count_for_avg: AtomicU64,
+    pub deploy_lamports_confirmed: AtomicU64, // Lamports landed on the board by confirmed deploys
+    pub deploy_fees_confirmed: AtomicU64,     // Deployer + protocol fees paid by confirmed deploys
 
     // Checkpoint transaction stats
     pub checkpoints_sent: AtomicU64,
@@ -181,6 +320,21 @@ pub struct PipelineStats {
     pub fee_update_total_time_ms: AtomicU64,
     pub fee_update_count_for_avg: AtomicU64,
 
+    // Profit sweep transaction stats
+    pub profit_sweeps_sent: AtomicU64,
+    pub profit_sweeps_confirmed: AtomicU64,
+    pub profit_sweeps_failed: AtomicU64,
+    pub profit_swept_lamports: AtomicU64,
+
+    // Compound transaction stats
+    pub compounds_sent: AtomicU64,
+    pub compounds_confirmed: AtomicU64,
+    pub compounds_failed: AtomicU64,
+    pub compounded_lamports: AtomicU64,
+
+    // Failure threshold alerts raised
+    pub failure_alerts_fired: AtomicU64,
+
     // System timing
     pub lut_check_total_time_ms: AtomicU64,
     pub lut_check_count: AtomicU64,
@@ -287,6 +441,8 @@ impl PipelineStats {
         self.deploys_failed.store(0, Ordering::Relaxed);
         self.deploy_total_time_ms.store(0, Ordering::Relaxed);
         self.deploy_count_for_avg.store(0, Ordering::Relaxed);
+        self.deploy_lamports_confirmed.store(0, Ordering::Relaxed);
+        self.deploy_fees_confirmed.store(0, Ordering::Relaxed);
         self.checkpoints_sent.store(0, Ordering::Relaxed);
         self.checkpoints_confirmed.store(0, Ordering::Relaxed);
         self.checkpoints_failed.store(0, Ordering::Relaxed);
@@ -297,6 +453,15 @@ impl PipelineStats {
         self.fee_updates_failed.store(0, Ordering::Relaxed);
         self.fee_update_total_time_ms.store(0, Ordering::Relaxed);
         self.fee_update_count_for_avg.store(0, Ordering::Relaxed);
+        self.profit_sweeps_sent.store(0, Ordering::Relaxed);
+        self.profit_sweeps_confirmed.store(0, Ordering::Relaxed);
+        self.profit_sweeps_failed.store(0, Ordering::Relaxed);
+        self.profit_swept_lamports.store(0, Ordering::Relaxed);
+        self.compounds_sent.store(0, Ordering::Relaxed);
+        self.compounds_confirmed.store(0, Ordering::Relaxed);
+        self.compounds_failed.store(0, Ordering::Relaxed);
+        self.compounded_lamports.store(0, Ordering::Relaxed);
+        self.failure_alerts_fired.store(0, Ordering::Relaxed);
         self.lut_check_total_time_ms.store(0, Ordering::Relaxed);
         self.lut_check_count.store(0, Ordering::Relaxed);
         self.deployment_check_total_time_ms.store(0, Ordering::Relaxed);
@@ -372,6 +537,125 @@ impl PipelineStats {
             self.get(&self.fee_updates_failed),
             self.fee_update_avg_time_ms()
         );
+        tracing::info!(
+            "        Txns ProfitSweep: {} sent, {} confirmed, {} failed ({} queued)",
+            self.get(&self.profit_sweeps_sent),
+            self.get(&self.profit_sweeps_confirmed),
+            self.get(&self.profit_sweeps_failed),
+            format_sol(self.get(&self.profit_swept_lamports))
+        );
+        tracing::info!(
+            "        Txns Compound:   {} sent, {} confirmed, {} failed ({} queued)",
+            self.get(&self.compounds_sent),
+            self.get(&self.compounds_confirmed),
+            self.get(&self.compounds_failed),
+            format_sol(self.get(&self.compounded_lamports))
+        );
+        let alerts = self.get(&self.failure_alerts_fired);
+        if alerts > 0 {
+            tracing::warn!("        Failure alerts:  {} raised this round", alerts);
+        }
+    }
+}
+
+/// Spend and outcome of one round's deploys, logged on round change
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct RoundSummary {
+    pub round_id: u64,
+    pub miners_deployed: u64,
+    pub lamports_deployed: u64,
+    pub fees_paid: u64,
+    pub confirmations: u64,
+    pub failures: u64,
+    pub avg_confirm_ms: f64,
+}
+
+impl RoundSummary {
+    /// Snapshot the deploy counters (call before `PipelineStats::reset`)
+    pub fn from_stats(round_id: u64, stats: &PipelineStats) -> Self {
+        Self {
+            round_id,
+            miners_deployed: stats.get(&stats.miners_deployed),
+            lamports_deployed: stats.get(&stats.deploy_lamports_confirmed),
+            fees_paid: stats.get(&stats.deploy_fees_confirmed),
+            confirmations: stats.get(&stats.deploys_confirmed),
+            failures: stats.get(&stats.deploys_failed),
+            avg_confirm_ms: stats.deploy_avg_time_ms(),
+        }
+    }
+}
+
+impl std::fmt::Display for RoundSummary {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "Round {} summary: {} miners deployed, {} deployed, {} fees, {} confirmed, {} failed, avg confirm {:.1}ms",
+            self.round_id,
+            self.miners_deployed,
+            format_sol(self.lamports_deployed),
+            format_sol(self.fees_paid),
+            self.confirmations,
+            self.failures,
+            self.avg_confirm_ms
+        )
+    }
+}
+
+/// MAX_ROUNDS: rounds we've deployed in, and whether the crank should stop
+///
+/// A round counts once it's over, if any of our deploys confirmed in it; rounds
+/// where nothing landed (no deployers, nothing affordable) don't use up the limit.
+#[derive(Debug)]
+pub struct RoundLimit {
+    /// Rounds to deploy across before stopping (0 = unlimited)
+    max_rounds: u64,
+    completed: AtomicU64,
+}
+
+impl RoundLimit {
+    pub fn new(max_rounds: u64) -> Self {
+        Self { max_rounds, completed: AtomicU64::new(0) }
+    }
+
+    /// Record a finished round; true once the limit has been reached
+    pub fn finish_round(&self, deployed: bool) -> bool {
+        if deployed {
+            self.completed.fetch_add(1, Ordering::Relaxed);
+        }
+        self.is_reached()
+    }
+
+    pub fn is_reached(&self) -> bool {
+        self.max_rounds > 0 && self.completed() >= self.max_rounds
+    }
+
+    /// Rounds with a confirmed deploy so far
+    pub fn completed(&self) -> u64 {
+        self.completed.load(Ordering::Relaxed)
+    }
+}
+
+/// The run loop's view of MAX_ROUNDS: a round is finished once a pass sees the next one
+#[derive(Debug, Default)]
+pub struct RunLoopRounds {
+    round_id: Option<u64>,
+    /// Whether a deploy of ours confirmed in `round_id`
+    deployed: bool,
+}
+
+impl RunLoopRounds {
+    /// Record a pass that saw `round_id` and confirmed `confirmed` deploys into it;
+    /// true once `limit` is reached and the loop should exit
+    pub fn record_pass(&mut self, limit: &RoundLimit, round_id: Option<u64>, confirmed: usize) -> bool {
+        if self.round_id.is_some() && round_id != self.round_id {
+            if limit.finish_round(self.deployed) {
+                return true;
+            }
+            self.deployed = false;
+        }
+        self.round_id = round_id;
+        self.deployed |= confirmed > 0;
+        false
     }
 }
 
@@ -383,19 +667,153 @@ pub struct SharedState {
     pub lut_cache: RwLock<LutRegistry>,
     /// Current board/round state
     pub board_state: RwLock<BoardState>,
+    /// Epoch-scoped ORE accounts (config, treasury ATA)
+    pub static_accounts: RwLock<StaticAccountCache>,
+    /// Deployer discovery results (shared with the `run` loop)
+    pub deployers: SharedDeployerCache,
     /// Pipeline statistics
     pub stats: PipelineStats,
+    /// Failure threshold tracking (alerts on spikes, not single failures)
+    pub failure_alerts: FailureAlerts,
+    /// Per-miner retry cooldown after a failed deploy
+    pub deploy_cooldowns: DeployCooldowns,
+    /// Rounds deployed across, against MAX_ROUNDS
+    pub round_limit: RoundLimit,
 }
 
 impl SharedState {
     /// Create new shared state
-    pub fn new(rpc_url: &str, authority: Pubkey) -> Self {
+    pub fn new(
+        rpc_client: RpcClient,
+        authority: Pubkey,
+        deployers: SharedDeployerCache,
+        failure_alerts: FailureAlerts,
+        deploy_cooldown: Duration,
+        max_rounds: u64,
+    ) -> Self {
         Self {
             miner_cache: RwLock::new(MinerCache::new()),
-            lut_cache: RwLock::new(LutRegistry::new(rpc_url, authority)),
+            lut_cache: RwLock::new(LutRegistry::new(rpc_client, authority)),
             board_state: RwLock::new(BoardState::default()),
+            static_accounts: RwLock::new(StaticAccountCache::default()),
+            deployers,
             stats: PipelineStats::new(),
+            failure_alerts,
+            deploy_cooldowns: DeployCooldowns::new(deploy_cooldown),
+            round_limit: RoundLimit::new(max_rounds),
         }
     }
 }
 
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_failed_miner_waits_out_cooldown() {
+        let cooldowns = DeployCooldowns::new(Duration::from_millis(2_000));
+        let (failed, other) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let t0 = Instant::now();
+
+        cooldowns.record_failure_at(failed, t0);
+        assert_eq!(cooldowns.remaining_at(&failed, t0), Some(Duration::from_millis(2_000)));
+        assert_eq!(
+            cooldowns.remaining_at(&failed, t0 + Duration::from_millis(1_500)),
+            Some(Duration::from_millis(500)),
+            "not retried before the cooldown elapses"
+        );
+        assert_eq!(cooldowns.remaining_at(&other, t0), None, "other miners are unaffected");
+
+        assert_eq!(cooldowns.remaining_at(&failed, t0 + Duration::from_millis(2_000)), None);
+
+        // A second failure starts a fresh cooldown
+        let t1 = t0 + Duration::from_millis(3_000);
+        cooldowns.record_failure_at(failed, t1);
+        assert!(cooldowns.remaining_at(&failed, t1 + Duration::from_millis(1_999)).is_some());
+
+        // A new round clears it
+        cooldowns.reset();
+        assert_eq!(cooldowns.remaining_at(&failed, t1), None);
+
+        // Zero cooldown never holds a miner back
+        let disabled = DeployCooldowns::new(Duration::ZERO);
+        disabled.record_failure_at(failed, t0);
+        assert_eq!(disabled.remaining_at(&failed, t0), None);
+    }
+
+    #[test]
+    fn test_round_limit_stops_after_max_deployed_rounds() {
+        let limit = RoundLimit::new(2);
+        assert!(!limit.is_reached());
+
+        assert!(!limit.finish_round(true), "one of two rounds");
+        assert!(!limit.finish_round(false), "a round without a confirmed deploy doesn't count");
+        assert_eq!(limit.completed(), 1);
+        assert!(limit.finish_round(true), "second deployed round stops the crank");
+        assert!(limit.is_reached());
+
+        // 0 never stops
+        let unlimited = RoundLimit::new(0);
+        assert!((0..100).all(|_| !unlimited.finish_round(true)));
+        assert_eq!(unlimited.completed(), 100);
+    }
+
+    #[test]
+    fn test_run_loop_exits_once_max_rounds_deployed() {
+        let limit = RoundLimit::new(2);
+        let mut rounds = RunLoopRounds::default();
+
+        // Round 10: several passes, one deploy confirms
+        assert!(!rounds.record_pass(&limit, Some(10), 0));
+        assert!(!rounds.record_pass(&limit, Some(10), 2));
+        assert!(!rounds.record_pass(&limit, Some(10), 0));
+        // Round 11 starts, closing 10; the deploy in this pass belongs to 11
+        assert!(!rounds.record_pass(&limit, Some(11), 1));
+        assert_eq!(limit.completed(), 1);
+        // Round 12 closes 11 and reaches the limit before anything else is deployed
+        assert!(rounds.record_pass(&limit, Some(12), 0));
+        assert_eq!(limit.completed(), 2);
+
+        // A round without a confirmed deploy doesn't count
+        let limit = RoundLimit::new(1);
+        let mut rounds = RunLoopRounds::default();
+        assert!(!rounds.record_pass(&limit, Some(10), 0));
+        assert!(!rounds.record_pass(&limit, Some(11), 0));
+        assert!(!rounds.record_pass(&limit, Some(12), 3));
+        assert!(rounds.record_pass(&limit, Some(13), 0));
+    }
+
+    #[test]
+    fn test_round_summary_from_stats() {
+        let stats = PipelineStats::new();
+        stats.add(&stats.miners_deployed, 3);
+        stats.add(&stats.deploy_lamports_confirmed, 3 * 70_000);
+        stats.add(&stats.deploy_fees_confirmed, 3 * 10_715);
+        stats.add(&stats.deploys_confirmed, 2);
+        stats.increment(&stats.deploys_failed);
+        stats.add(&stats.deploy_total_time_ms, 900 + 1_500);
+        stats.add(&stats.deploy_count_for_avg, 2);
+
+        let summary = RoundSummary::from_stats(41, &stats);
+        assert_eq!(summary, RoundSummary {
+            round_id: 41,
+            miners_deployed: 3,
+            lamports_deployed: 210_000,
+            fees_paid: 32_145,
+            confirmations: 2,
+            failures: 1,
+            avg_confirm_ms: 1_200.0,
+        });
+        assert_eq!(
+            summary.to_string(),
+            format!(
+                "Round 41 summary: 3 miners deployed, {} deployed, {} fees, 2 confirmed, 1 failed, avg confirm 1200.0ms",
+                format_sol(210_000), format_sol(32_145)
+            )
+        );
+
+        // Taken before the reset, the next round starts from zero
+        stats.reset();
+        assert_eq!(RoundSummary::from_stats(42, &stats), RoundSummary { round_id: 42, ..Default::default() });
+    }
+}
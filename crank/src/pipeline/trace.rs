@@ -0,0 +1,253 @@
+//! Deploy decision trace for a single miner
+//!
+//! Runs one miner through the same gates the pipeline applies (fee check,
+//! LUT check, deployment check) and records every gate's result instead of
+//! stopping at the first one, so `trace-miner` can show exactly where a
+//! miner is filtered. Runtime-only state (failure cooldowns, retry counts)
+//! lives in the running pipeline and is not part of the trace.
+
+use std::fmt;
+
+use crate::config::DeployerInfo;
+use crate::miner_cache::CachedMiner;
+
+use super::deployment_check::{already_deployed, route, DeployRoute, MIN_DEPLOY_BALANCE};
+use super::fee_check::{check_fees, FeeGate};
+use super::shared_state::BoardState;
+use super::REQUIRED_FLAT_FEE;
+
+/// Result of one gate
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GateStatus {
+    Pass,
+    /// Failed, but the pipeline fixes it itself (fee update, LUT creation) and retries
+    Fixable,
+    /// Filters the miner out
+    Blocked,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GateResult {
+    pub gate: &'static str,
+    pub status: GateStatus,
+    pub detail: String,
+}
+
+/// What the pipeline ends up doing with the miner
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TraceOutcome {
+    Deploy,
+    CheckpointOnly,
+    /// Filtered at this gate
+    Skipped(&'static str),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MinerTrace {
+    pub gates: Vec<GateResult>,
+    pub outcome: TraceOutcome,
+}
+
+impl fmt::Display for GateStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            GateStatus::Pass => write!(f, "✓"),
+            GateStatus::Fixable => write!(f, "~"),
+            GateStatus::Blocked => write!(f, "✗"),
+        }
+    }
+}
+
+impl fmt::Display for TraceOutcome {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TraceOutcome::Deploy => write!(f, "deploy"),
+            TraceOutcome::CheckpointOnly => write!(f, "checkpoint only"),
+            TraceOutcome::Skipped(gate) => write!(f, "skipped at '{}'", gate),
+        }
+    }
+}
+
+impl MinerTrace {
+    fn push(&mut self, gate: &'static str, status: GateStatus, detail: String) {
+        self.gates.push(GateResult { gate, status, detail });
+    }
+}
+
+/// Trace `deployer`'s miner against the current board
+///
+/// `miner` is the miner cache entry (None if the cache has nothing for it)
+pub fn trace_miner(
+    deployer: &DeployerInfo,
+    has_lut: bool,
+    miner: Option<&CachedMiner>,
+    board: &BoardState,
+) -> MinerTrace {
+    let mut trace = MinerTrace { gates: Vec::new(), outcome: TraceOutcome::Deploy };
+
+    // FeeCheck
+    let fee_gate = check_fees(deployer);
+    trace.push(
+        "expected fee",
+        if fee_gate == FeeGate::UserFeeTooLow { GateStatus::Blocked } else { GateStatus::Pass },
+        format!("expected_flat_fee {} vs required {}", deployer.expected_flat_fee, REQUIRED_FLAT_FEE),
+    );
+    trace.push(
+        "flat fee",
+        match fee_gate {
+            FeeGate::NeedsFeeUpdate => GateStatus::Fixable,
+            _ => GateStatus::Pass,
+        },
+        if fee_gate == FeeGate::NeedsFeeUpdate {
+            format!("flat_fee {} != {}, fee updater will set it", deployer.flat_fee, REQUIRED_FLAT_FEE)
+        } else {
+            format!("flat_fee {}", deployer.flat_fee)
+        },
+    );
+
+    // LUTCheck
+    trace.push(
+        "miner LUT",
+        if has_lut { GateStatus::Pass } else { GateStatus::Fixable },
+        if has_lut { "present".to_string() } else { "missing, LUT creation will build it".to_string() },
+    );
+
+    // DeploymentCheck
+    let Some(miner) = miner else {
+        trace.push("miner cache", GateStatus::Blocked, "no cached miner data".to_string());
+        trace.outcome = first_blocked(&trace);
+        return trace;
+    };
+    trace.push(
+        "miner cache",
+        GateStatus::Pass,
+        if miner.exists { "cached".to_string() } else { "cached, ORE miner not created yet".to_string() },
+    );
+
+    trace.push(
+        "round open",
+        if board.can_deploy() { GateStatus::Pass } else { GateStatus::Blocked },
+        format!("round {} phase {}", board.round_id, board.phase),
+    );
+
+    let deployed = already_deployed(miner, board.round_id);
+    trace.push(
+        "already deployed",
+        if deployed { GateStatus::Blocked } else { GateStatus::Pass },
+        format!("last deployed round {} (current {})", miner.round_id, board.round_id),
+    );
+
+    let deploy_route = route(miner);
+    trace.push(
+        "balance",
+        match deploy_route {
+            DeployRoute::Deploy { .. } => GateStatus::Pass,
+            _ => GateStatus::Blocked,
+        },
+        format!("{} lamports vs required {}", miner.auth_balance, MIN_DEPLOY_BALANCE),
+    );
+
+    trace.push(
+        "checkpoint needed",
+        GateStatus::Pass,
+        match deploy_route {
+            DeployRoute::Deploy { needs_checkpoint: true } => {
+                format!("round {} unchecked, bundled with the deploy", miner.round_id)
+            }
+            DeployRoute::CheckpointOnly => {
+                format!("round {} unchecked, checkpoint-only instead of a deploy", miner.round_id)
+            }
+            _ => "no".to_string(),
+        },
+    );
+
+    trace.outcome = match first_blocked(&trace) {
+        TraceOutcome::Skipped("balance") if deploy_route == DeployRoute::CheckpointOnly => TraceOutcome::CheckpointOnly,
+        outcome => outcome,
+    };
+    trace
+}
+
+fn first_blocked(trace: &MinerTrace) -> TraceOutcome {
+    trace
+        .gates
+        .iter()
+        .find(|g| g.status == GateStatus::Blocked)
+        .map_or(TraceOutcome::Deploy, |g| TraceOutcome::Skipped(g.gate))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use solana_sdk::pubkey::Pubkey;
+
+    use crate::pipeline::shared_state::RoundPhase;
+
+    impl MinerTrace {
+        /// Status of a gate by name (None if it wasn't evaluated)
+        fn status(&self, gate: &str) -> Option<GateStatus> {
+            self.gates.iter().find(|g| g.gate == gate).map(|g| g.status)
+        }
+    }
+
+    #[test]
+    fn test_trace_shows_where_miner_is_filtered() {
+        let deployer = DeployerInfo {
+            deployer_address: Pubkey::new_unique(),
+            manager_address: Pubkey::new_unique(),
+            bps_fee: 0,
+            flat_fee: 500,
+            expected_bps_fee: 0,
+            expected_flat_fee: REQUIRED_FLAT_FEE,
+            max_per_round: 0,
+        };
+        let mut miner = CachedMiner {
+            miner_address: Pubkey::new_unique(),
+            authority: Pubkey::new_unique(),
+            deployer_address: deployer.deployer_address,
+            manager_address: deployer.manager_address,
+            checkpoint_id: 41,
+            round_id: 42,
+            has_deployed: true,
+            auth_balance: MIN_DEPLOY_BALANCE - 1,
+            rewards_sol: 0,
+            exists: true,
+        };
+        let mut board = BoardState { round_id: 43, end_slot: 1_000, current_slot: 500, ..Default::default() };
+        board.update_phase();
+        assert!(matches!(board.phase, RoundPhase::DeploymentWindow { .. }));
+
+        // Fee update and LUT creation are fixed by the pipeline; low balance with an
+        // unchecked round routes to a checkpoint
+        let trace = trace_miner(&deployer, false, Some(&miner), &board);
+        assert_eq!(trace.status("expected fee"), Some(GateStatus::Pass));
+        assert_eq!(trace.status("flat fee"), Some(GateStatus::Fixable));
+        assert_eq!(trace.status("miner LUT"), Some(GateStatus::Fixable));
+        assert_eq!(trace.status("already deployed"), Some(GateStatus::Pass));
+        assert_eq!(trace.status("balance"), Some(GateStatus::Blocked));
+        assert_eq!(trace.outcome, TraceOutcome::CheckpointOnly);
+
+        // Already deployed this round wins over the balance gate
+        miner.round_id = 43;
+        let trace = trace_miner(&deployer, true, Some(&miner), &board);
+        assert_eq!(trace.outcome, TraceOutcome::Skipped("already deployed"));
+        assert_eq!(trace.gates.len(), 8, "every gate is reported, not just the first failure");
+
+        // Funded miner in an open round deploys
+        miner.round_id = 42;
+        miner.auth_balance = MIN_DEPLOY_BALANCE;
+        assert_eq!(trace_miner(&deployer, true, Some(&miner), &board).outcome, TraceOutcome::Deploy);
+
+        // A user who never accepted our fee is filtered first, whatever else holds
+        let stingy = DeployerInfo { expected_flat_fee: REQUIRED_FLAT_FEE - 1, ..deployer.clone() };
+        assert_eq!(trace_miner(&stingy, true, Some(&miner), &board).outcome, TraceOutcome::Skipped("expected fee"));
+
+        // Past round end nothing deploys, and an uncached miner can't be traced further
+        board.current_slot = 1_000;
+        board.update_phase();
+        assert_eq!(trace_miner(&deployer, true, Some(&miner), &board).outcome, TraceOutcome::Skipped("round open"));
+        let trace = trace_miner(&deployer, true, None, &board);
+        assert_eq!(trace.outcome, TraceOutcome::Skipped("miner cache"));
+        assert_eq!(trace.status("round open"), None);
+    }
+}
@@ -25,10 +25,16 @@ pub async fn run(
     _senders: ChannelSenders,
     mut rx: mpsc::Receiver<SignedTx>,
     rpc_url: String,
+    min_reserve_lamports: u64,
+    alert_webhook_url: Option<String>,
+    send_fanout: Vec<String>,
 ) {
     info!("[TxSender] Starting...");
 
-    let sender = TxSender::new(rpc_url);
+    let sender = TxSender::new(rpc_url)
+        .with_min_reserve(min_reserve_lamports)
+        .with_alert_webhook(alert_webhook_url)
+        .with_fanout(send_fanout);
     let mut sent_count = 0u64;
     let mut failed_count = 0u64;
 
@@ -20,6 +20,10 @@ pub enum TxType {
     Checkpoint,
     /// Fee update transaction (batch of up to 10 miners)
     FeeUpdate,
+    /// Profit sweep transaction (withdraw + transfer, batch of up to 5 miners)
+    ProfitSweep,
+    /// Compound transaction (recycle SOL rewards into the autodeploy balance, batch of up to 5 miners)
+    Compound,
 }
 
 impl std::fmt::Display for TxType {
@@ -28,6 +32,8 @@ impl std::fmt::Display for TxType {
             TxType::Deploy => write!(f, "Deploy"),
             TxType::Checkpoint => write!(f, "Checkpoint"),
             TxType::FeeUpdate => write!(f, "FeeUpdate"),
+            TxType::ProfitSweep => write!(f, "ProfitSweep"),
+            TxType::Compound => write!(f, "Compound"),
         }
     }
 }
@@ -0,0 +1,84 @@
+//! Run loop sleep between passes
+//!
+//! Polling every POLL_INTERVAL_MS all round long mostly re-reads a board that
+//! is nowhere near its deploy window, and every crank started at the same time
+//! hits the RPC in lockstep. Outside the window the loop sleeps
+//! POLL_INTERVAL_IDLE_MS instead (cut short so it wakes as the window opens),
+//! and every sleep gets up to POLL_JITTER_MS added, drawn afresh each pass.
+
+use std::time::Duration;
+
+use crate::deploy_window::splitmix64;
+
+/// Approximate slot time, used to wake before the deploy window opens
+const SLOT_MS: u64 = 400;
+
+#[derive(Debug, Clone)]
+pub struct PollSchedule {
+    interval_ms: u64,
+    idle_interval_ms: u64,
+    jitter_ms: u64,
+    /// Slots remaining above which the round can't be in its deploy window (None = it always can)
+    idle_above_slots: Option<u64>,
+    state: u64,
+}
+
+impl PollSchedule {
+    /// `idle_interval_ms` 0 polls at `interval_ms` all round
+    pub fn new(interval_ms: u64, idle_interval_ms: u64, jitter_ms: u64, idle_above_slots: Option<u64>, seed: u64) -> Self {
+        Self { interval_ms, idle_interval_ms, jitter_ms, idle_above_slots, state: seed }
+    }
+
+    /// Sleep before the next pass, given the slots left in the round the last pass saw
+    /// (None when it couldn't tell, e.g. a stale board or a round reset)
+    pub fn next_interval(&mut self, slots_remaining: Option<u64>) -> Duration {
+        let base = match (slots_remaining, self.idle_above_slots) {
+            (Some(remaining), Some(window)) if self.idle_interval_ms > 0 && remaining > window => {
+                let until_window_ms = (remaining - window).saturating_mul(SLOT_MS);
+                self.idle_interval_ms.min(until_window_ms).max(self.interval_ms)
+            }
+            _ => self.interval_ms,
+        };
+        Duration::from_millis(base + self.jitter())
+    }
+
+    fn jitter(&mut self) -> u64 {
+        if self.jitter_ms == 0 {
+            return 0;
+        }
+        self.state = self.state.wrapping_add(1);
+        splitmix64(self.state) % (self.jitter_ms + 1)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_idle_outside_window_fast_inside_with_jitter() {
+        let ms = |d: Duration| d.as_millis() as u64;
+
+        // No idle interval or jitter: the fixed POLL_INTERVAL_MS as before
+        let mut fixed = PollSchedule::new(400, 0, 0, Some(150), 1);
+        assert_eq!(ms(fixed.next_interval(Some(5_000))), 400);
+        assert_eq!(ms(fixed.next_interval(None)), 400);
+
+        let mut schedule = PollSchedule::new(400, 5_000, 0, Some(150), 1);
+        assert_eq!(ms(schedule.next_interval(Some(1_000))), 5_000, "far from the window");
+        assert_eq!(ms(schedule.next_interval(Some(150))), 400, "inside the window");
+        assert_eq!(ms(schedule.next_interval(Some(160))), 4_000, "wakes as the window opens");
+        assert_eq!(ms(schedule.next_interval(Some(151))), 400, "never below the fast interval");
+        assert_eq!(ms(schedule.next_interval(None)), 400, "unknown round state polls fast");
+
+        // Entropy-ready trigger can open any time
+        let mut entropy = PollSchedule::new(400, 5_000, 0, None, 1);
+        assert_eq!(ms(entropy.next_interval(Some(1_000))), 400);
+
+        // Jitter stays within bounds and varies pass to pass
+        let mut jittered = PollSchedule::new(400, 5_000, 100, Some(150), 7);
+        let sleeps: Vec<u64> = (0..50).map(|_| ms(jittered.next_interval(Some(100)))).collect();
+        assert!(sleeps.iter().all(|s| (400..=500).contains(s)));
+        assert!(sleeps.iter().any(|&s| s != sleeps[0]), "jitter should vary");
+    }
+}
@@ -0,0 +1,61 @@
+//! Deploy amount that grows with the round's pot
+//!
+//! Bigger pots pay more per winning square, so the run loop can raise its
+//! per-square amount as a round fills up: `POT_SCALE_BPS_PER_SOL` adds that
+//! share of `DEPLOY_AMOUNT_LAMPORTS` for every SOL already deployed into the
+//! round, up to `POT_SCALE_MAX_BPS` of it. Scheduled amounts are the manager's
+//! own choice and are never scaled.
+
+const LAMPORTS_PER_SOL: u128 = 1_000_000_000;
+
+/// POT_SCALE_BPS_PER_SOL / POT_SCALE_MAX_BPS
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PotScaling {
+    /// Basis points of the base amount added per SOL in the round (0 = off)
+    pub bps_per_sol: u64,
+    /// Largest scaled amount, in basis points of the base amount
+    pub max_bps: u64,
+}
+
+impl PotScaling {
+    pub fn is_enabled(&self) -> bool {
+        self.bps_per_sol > 0
+    }
+
+    /// `base` scaled for a round with `total_deployed` lamports in it; never below `base`
+    pub fn amount(&self, base: u64, total_deployed: u64) -> u64 {
+        if !self.is_enabled() {
+            return base;
+        }
+        let bps = 10_000
+            + u128::from(self.bps_per_sol) * u128::from(total_deployed) / LAMPORTS_PER_SOL;
+        let bps = bps.min(u128::from(self.max_bps)).max(10_000);
+        let scaled = u128::from(base) * bps / 10_000;
+        scaled.min(u128::from(u64::MAX)) as u64
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_amount_grows_with_pot_up_to_cap() {
+        // +10% per SOL, at most 3x
+        let scaling = PotScaling { bps_per_sol: 1_000, max_bps: 30_000 };
+        let sol = LAMPORTS_PER_SOL as u64;
+
+        assert_eq!(scaling.amount(2_800, 0), 2_800, "empty pot deploys the base amount");
+        assert_eq!(scaling.amount(2_800, 5 * sol), 4_200);
+        assert!(scaling.amount(2_800, 10 * sol) > scaling.amount(2_800, 5 * sol));
+        assert_eq!(scaling.amount(2_800, 20 * sol), 8_400, "20 SOL reaches the 3x cap");
+        assert_eq!(scaling.amount(2_800, 1_000 * sol), 8_400, "bigger pots stay at the cap");
+
+        // A cap below 1x never shrinks the amount, and scaling off leaves it alone
+        let low_cap = PotScaling { max_bps: 5_000, ..scaling };
+        assert_eq!(low_cap.amount(2_800, 100 * sol), 2_800);
+        let off = PotScaling { bps_per_sol: 0, ..scaling };
+        assert!(!off.is_enabled());
+        assert_eq!(off.amount(2_800, 100 * sol), 2_800);
+    }
+}
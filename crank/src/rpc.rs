@@ -0,0 +1,168 @@
+//! RPC access behind a trait
+//!
+//! `RpcProvider` covers the reads the crank's discovery and board tracking
+//! need, so that logic can run against `RpcClient` in production and against
+//! an in-memory mock in tests. Transactions go out through `TxSender`, which
+//! talks HTTP directly and is not covered here.
+
+use solana_client::rpc_client::RpcClient;
+use solana_client::rpc_config::RpcProgramAccountsConfig;
+use solana_sdk::{account::Account, hash::Hash, pubkey::Pubkey};
+
+use crate::crank::CrankError;
+
+/// The RPC methods the crank relies on
+pub trait RpcProvider: Send + Sync {
+    fn get_account(&self, pubkey: &Pubkey) -> Result<Account, CrankError>;
+
+    fn get_multiple_accounts(&self, pubkeys: &[Pubkey]) -> Result<Vec<Option<Account>>, CrankError>;
+
+    /// Accounts owned by `program_id` that pass `config.filters`
+    fn get_program_accounts(
+        &self,
+        program_id: &Pubkey,
+        config: RpcProgramAccountsConfig,
+    ) -> Result<Vec<(Pubkey, Account)>, CrankError>;
+
+    fn get_slot(&self) -> Result<u64, CrankError>;
+
+    fn get_latest_blockhash(&self) -> Result<Hash, CrankError>;
+}
+
+fn rpc_err(e: impl std::fmt::Display) -> CrankError {
+    CrankError::Rpc(e.to_string())
+}
+
+impl RpcProvider for RpcClient {
+    fn get_account(&self, pubkey: &Pubkey) -> Result<Account, CrankError> {
+        RpcClient::get_account(self, pubkey).map_err(rpc_err)
+    }
+
+    fn get_multiple_accounts(&self, pubkeys: &[Pubkey]) -> Result<Vec<Option<Account>>, CrankError> {
+        RpcClient::get_multiple_accounts(self, pubkeys).map_err(rpc_err)
+    }
+
+    fn get_program_accounts(
+        &self,
+        program_id: &Pubkey,
+        config: RpcProgramAccountsConfig,
+    ) -> Result<Vec<(Pubkey, Account)>, CrankError> {
+        self.get_program_accounts_with_config(program_id, config).map_err(rpc_err)
+    }
+
+    fn get_slot(&self) -> Result<u64, CrankError> {
+        RpcClient::get_slot(self).map_err(rpc_err)
+    }
+
+    fn get_latest_blockhash(&self) -> Result<Hash, CrankError> {
+        RpcClient::get_latest_blockhash(self).map_err(rpc_err)
+    }
+}
+
+/// In-memory `RpcProvider` for tests
+#[cfg(test)]
+pub mod mock {
+    use std::collections::HashMap;
+
+    use solana_sdk::account::AccountSharedData;
+
+    use super::*;
+
+    #[derive(Default)]
+    pub struct MockRpc {
+        pub accounts: HashMap<Pubkey, Account>,
+        pub slot: u64,
+        pub blockhash: Hash,
+    }
+
+    impl MockRpc {
+        pub fn with_account(mut self, address: Pubkey, account: Account) -> Self {
+            self.accounts.insert(address, account);
+            self
+        }
+    }
+
+    impl RpcProvider for MockRpc {
+        fn get_account(&self, pubkey: &Pubkey) -> Result<Account, CrankError> {
+            self.accounts
+                .get(pubkey)
+                .cloned()
+                .ok_or_else(|| CrankError::Rpc(format!("AccountNotFound: pubkey={}", pubkey)))
+        }
+
+        fn get_multiple_accounts(&self, pubkeys: &[Pubkey]) -> Result<Vec<Option<Account>>, CrankError> {
+            Ok(pubkeys.iter().map(|p| self.accounts.get(p).cloned()).collect())
+        }
+
+        fn get_program_accounts(
+            &self,
+            program_id: &Pubkey,
+            config: RpcProgramAccountsConfig,
+        ) -> Result<Vec<(Pubkey, Account)>, CrankError> {
+            let filters = config.filters.unwrap_or_default();
+            Ok(self
+                .accounts
+                .iter()
+                .filter(|(_, account)| account.owner == *program_id)
+                .filter(|(_, account)| {
+                    let shared = AccountSharedData::from((*account).clone());
+                    filters.iter().all(|f| f.allows(&shared))
+                })
+                .map(|(address, account)| (*address, account.clone()))
+                .collect())
+        }
+
+        fn get_slot(&self) -> Result<u64, CrankError> {
+            Ok(self.slot)
+        }
+
+        fn get_latest_blockhash(&self) -> Result<Hash, CrankError> {
+            Ok(self.blockhash)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use solana_client::rpc_filter::{Memcmp, RpcFilterType};
+
+    use super::mock::MockRpc;
+    use super::*;
+
+    fn account(owner: Pubkey, data: Vec<u8>) -> Account {
+        Account { lamports: 1, data, owner, executable: false, rent_epoch: 0 }
+    }
+
+    #[test]
+    fn test_mock_program_accounts_apply_filters() {
+        let program = Pubkey::new_unique();
+        let (matching, wrong_bytes, wrong_size, other_program) =
+            (Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique());
+        let rpc = MockRpc::default()
+            .with_account(matching, account(program, vec![101, 0, 7, 7]))
+            .with_account(wrong_bytes, account(program, vec![100, 0, 7, 7]))
+            .with_account(wrong_size, account(program, vec![101, 0, 7]))
+            .with_account(other_program, account(Pubkey::new_unique(), vec![101, 0, 7, 7]));
+
+        let config = |filters| RpcProgramAccountsConfig { filters: Some(filters), ..Default::default() };
+        let found = rpc
+            .get_program_accounts(&program, config(vec![
+                RpcFilterType::DataSize(4),
+                RpcFilterType::Memcmp(Memcmp::new_raw_bytes(0, vec![101])),
+            ]))
+            .unwrap();
+        assert_eq!(found.iter().map(|(a, _)| *a).collect::<Vec<_>>(), vec![matching]);
+
+        // No filters: everything the program owns
+        let mut all: Vec<Pubkey> =
+            rpc.get_program_accounts(&program, config(vec![])).unwrap().into_iter().map(|(a, _)| a).collect();
+        all.sort();
+        let mut expected = vec![matching, wrong_bytes, wrong_size];
+        expected.sort();
+        assert_eq!(all, expected);
+
+        assert!(rpc.get_account(&Pubkey::new_unique()).is_err());
+        let multiple = rpc.get_multiple_accounts(&[matching, Pubkey::new_unique()]).unwrap();
+        assert!(multiple[0].is_some() && multiple[1].is_none());
+    }
+}
@@ -3,17 +3,71 @@
 //! Handles sending transactions via standard RPC
 
 use solana_sdk::{
+    instruction::CompiledInstruction,
+    pubkey,
+    pubkey::Pubkey,
     signature::Signature,
     transaction::{Transaction, VersionedTransaction},
 };
 use std::str::FromStr;
-use std::time::Duration;
-use tracing::info;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+use tracing::{debug, info, warn};
+
+/// Base fee charged per transaction signature (lamports)
+const LAMPORTS_PER_SIGNATURE: u64 = 5_000;
+
+/// ComputeBudget program, whose instructions set the priority fee
+const COMPUTE_BUDGET_PROGRAM_ID: Pubkey = pubkey!("ComputeBudget111111111111111111111111111111");
+
+/// Compute units a transaction without SetComputeUnitLimit gets per non-budget instruction
+const DEFAULT_UNITS_PER_INSTRUCTION: u64 = 200_000;
+
+/// Largest compute unit limit a transaction can request
+const MAX_COMPUTE_UNIT_LIMIT: u64 = 1_400_000;
+
+/// How long sends stay paused after the reserve guard trips
+const RESERVE_PAUSE: Duration = Duration::from_secs(30);
 
 /// Transaction sender
 pub struct TxSender {
     client: reqwest::Client,
     rpc_url: String,
+    /// Minimum lamports the fee payer must keep after paying for a send (0 = disabled)
+    min_reserve_lamports: u64,
+    /// Last fee payer balance read for the reserve guard: (payer, lamports), refreshed off the send path
+    payer_balance: Mutex<Option<(Pubkey, u64)>>,
+    /// Set when the reserve guard trips: (resume_at, last observed balance)
+    reserve_paused_until: Mutex<Option<(Instant, u64)>>,
+    /// Whether the payer is currently below the reserve (cleared once a balance check passes)
+    reserve_breached: AtomicBool,
+    /// Low-balance notifications sent
+    reserve_low_alerts: AtomicU64,
+    /// Replenished notifications sent
+    reserve_replenished_alerts: AtomicU64,
+    /// Webhook that receives reserve low/replenished events as JSON (None = log only)
+    alert_webhook_url: Option<String>,
+    /// Extra RPC endpoints every sendTransaction is also broadcast to (empty = primary only)
+    fanout_urls: Vec<String>,
+}
+
+/// Reserve guard state changes worth telling the operator about
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReserveEvent {
+    /// Balance dropped below the reserve, sends are paused
+    Low,
+    /// Balance recovered above the reserve, sends resume
+    Replenished,
+}
+
+impl ReserveEvent {
+    fn name(&self) -> &'static str {
+        match self {
+            ReserveEvent::Low => "authority_balance_low",
+            ReserveEvent::Replenished => "authority_balance_replenished",
+        }
+    }
 }
 
 impl TxSender {
@@ -26,17 +80,211 @@ impl TxSender {
         Self {
             client,
             rpc_url,
+            min_reserve_lamports: 0,
+            payer_balance: Mutex::new(None),
+            reserve_paused_until: Mutex::new(None),
+            reserve_breached: AtomicBool::new(false),
+            reserve_low_alerts: AtomicU64::new(0),
+            reserve_replenished_alerts: AtomicU64::new(0),
+            alert_webhook_url: None,
+            fanout_urls: Vec::new(),
+        }
+    }
+    
+    /// Refuse to send when the fee payer would end up below `lamports`
+    pub fn with_min_reserve(mut self, lamports: u64) -> Self {
+        self.min_reserve_lamports = lamports;
+        self
+    }
+    
+    /// POST reserve low/replenished events to `url`
+    pub fn with_alert_webhook(mut self, url: Option<String>) -> Self {
+        self.alert_webhook_url = url;
+        self
+    }
+    
+    /// Also broadcast every send to these RPC endpoints
+    pub fn with_fanout(mut self, urls: Vec<String>) -> Self {
+        self.fanout_urls = urls;
+        self
+    }
+    
+    /// Record a reserve check result and notify once per low -> replenished transition
+    fn track_reserve(&self, payer: &Pubkey, balance: u64, allowed: bool) {
+        let was_breached = self.reserve_breached.swap(!allowed, Ordering::Relaxed);
+        let Some(event) = reserve_transition(was_breached, allowed) else {
+            return;
+        };
+        
+        let count = match event {
+            ReserveEvent::Low => self.reserve_low_alerts.fetch_add(1, Ordering::Relaxed) + 1,
+            ReserveEvent::Replenished => self.reserve_replenished_alerts.fetch_add(1, Ordering::Relaxed) + 1,
+        };
+        match event {
+            ReserveEvent::Low => warn!(
+                "Deploy authority {} balance low: {} lamports (reserve {}) [alert #{}]",
+                payer, balance, self.min_reserve_lamports, count
+            ),
+            ReserveEvent::Replenished => info!(
+                "Deploy authority {} balance replenished: {} lamports (reserve {}), sends resumed [alert #{}]",
+                payer, balance, self.min_reserve_lamports, count
+            ),
+        }
+        
+        let Some(url) = self.alert_webhook_url.clone() else {
+            return;
+        };
+        let body = serde_json::json!({
+            "event": event.name(),
+            "authority": payer.to_string(),
+            "balance_lamports": balance,
+            "reserve_lamports": self.min_reserve_lamports,
+        });
+        let client = self.client.clone();
+        // Fire and forget so a slow webhook never holds up a send
+        tokio::spawn(async move {
+            match client.post(&url).json(&body).send().await {
+                Ok(resp) if resp.status().is_success() => debug!("Sent {} webhook", body["event"]),
+                Ok(resp) => warn!("Alert webhook returned {}", resp.status()),
+                Err(e) => warn!("Alert webhook failed: {}", e),
+            }
+        });
+    }
+    
+    /// Get the SOL balance of an account via standard RPC (getBalance)
+    pub async fn get_balance(&self, address: &Pubkey) -> Result<u64, SendError> {
+        let body = serde_json::json!({
+            "jsonrpc": "2.0",
+            "id": 1,
+            "method": "getBalance",
+            "params": [
+                address.to_string(),
+                { "commitment": "confirmed" }
+            ]
+        });
+        
+        let response = self.client
+            .post(&self.rpc_url)
+            .json(&body)
+            .send()
+            .await
+            .map_err(|e| SendError::Network(e.to_string()))?;
+        
+        let json: serde_json::Value = response.json().await
+            .map_err(|e| SendError::Parse(e.to_string()))?;
+        
+        if let Some(error) = json.get("error") {
+            return Err(SendError::RpcError(error.to_string()));
         }
+        
+        json["result"]["value"].as_u64()
+            .ok_or(SendError::Parse("No balance in response".to_string()))
+    }
+    
+    /// Re-read the fee payer balance the reserve guard checks sends against
+    ///
+    /// Call this off the send path (the run loop does it once per round). A failed
+    /// read clears the cached balance, so sends go through unchecked until the next
+    /// refresh instead of being dropped.
+    pub async fn refresh_payer_balance(&self, payer: &Pubkey) {
+        if self.min_reserve_lamports == 0 {
+            return;
+        }
+        
+        match self.get_balance(payer).await {
+            Ok(balance) => self.set_payer_balance(payer, Some(balance)),
+            Err(e) => {
+                warn!("Failed to read deploy authority {} balance for the reserve guard: {}", payer, e);
+                self.set_payer_balance(payer, None);
+            }
+        }
+    }
+    
+    fn set_payer_balance(&self, payer: &Pubkey, balance: Option<u64>) {
+        *self.payer_balance.lock().unwrap() = balance.map(|balance| (*payer, balance));
+    }
+    
+    /// Check that paying `fee` for this send keeps the fee payer at or above the reserve
+    ///
+    /// Judged against the cached balance, so no RPC round trip sits in front of the send;
+    /// without a reading for `payer` the send goes through. Each allowed send's fee is
+    /// taken off the cached balance until the next refresh. Once the guard trips, sends
+    /// are skipped for RESERVE_PAUSE.
+    fn check_reserve(&self, payer: &Pubkey, fee: u64) -> Result<(), SendError> {
+        if self.min_reserve_lamports == 0 {
+            return Ok(());
+        }
+        
+        self.reserve_paused_at(Instant::now())?;
+        let mut cached = self.payer_balance.lock().unwrap();
+        let Some((cached_payer, balance)) = cached.as_mut() else {
+            return Ok(());
+        };
+        if cached_payer != payer {
+            return Ok(());
+        }
+        
+        self.apply_reserve_check_at(payer, *balance, fee, Instant::now())?;
+        *balance = balance.saturating_sub(fee);
+        Ok(())
+    }
+    
+    /// Err while a tripped reserve guard is still pausing sends at `now`
+    fn reserve_paused_at(&self, now: Instant) -> Result<(), SendError> {
+        if let Some((resume_at, balance)) = *self.reserve_paused_until.lock().unwrap() {
+            if now < resume_at {
+                return Err(SendError::BelowReserve {
+                    balance,
+                    reserve: self.min_reserve_lamports,
+                });
+            }
+        }
+        Ok(())
+    }
+    
+    /// Judge a fresh `balance` reading against the reserve, pausing sends from `now` if it's breached
+    fn apply_reserve_check_at(&self, payer: &Pubkey, balance: u64, fee: u64, now: Instant) -> Result<(), SendError> {
+        let allowed = reserve_allows_send(balance, fee, self.min_reserve_lamports);
+        self.track_reserve(payer, balance, allowed);
+        
+        if !allowed {
+            warn!(
+                "Deploy authority {} balance {} lamports would drop below reserve {} after fee {} - pausing sends for {}s",
+                payer, balance, self.min_reserve_lamports, fee, RESERVE_PAUSE.as_secs()
+            );
+            *self.reserve_paused_until.lock().unwrap() = Some((now + RESERVE_PAUSE, balance));
+            return Err(SendError::BelowReserve {
+                balance,
+                reserve: self.min_reserve_lamports,
+            });
+        }
+        
+        *self.reserve_paused_until.lock().unwrap() = None;
+        Ok(())
     }
     
     /// Send a transaction via standard RPC (sendTransaction)
     pub async fn send_rpc(&self, tx: &Transaction) -> Result<Signature, SendError> {
+        if let Some(payer) = tx.message.account_keys.first() {
+            let fee = transaction_fee(tx.signatures.len(), &tx.message.account_keys, &tx.message.instructions);
+            self.check_reserve(payer, fee)?;
+        }
+        
         let tx_bytes = bincode::serialize(tx)
             .map_err(|e| SendError::Serialize(e.to_string()))?;
         let tx_base64 = base64::encode(&tx_bytes);
         
         info!("Sending tx: {} bytes (limit 1232)", tx_bytes.len());
         
+        self.broadcast_transaction(tx_base64).await
+    }
+    
+    /// Submit a base64 transaction to the primary RPC and every fan-out endpoint at once
+    ///
+    /// Every endpoint gets the same signed bytes, so whichever accepts first yields the one
+    /// signature confirmation is tracked under. The remaining submissions keep running in
+    /// the background; the send only fails if every endpoint rejects it.
+    async fn broadcast_transaction(&self, tx_base64: String) -> Result<Signature, SendError> {
         let body = serde_json::json!({
             "jsonrpc": "2.0",
             "id": 1,
@@ -51,6 +299,55 @@ impl TxSender {
             ]
         });
         
+        if self.fanout_urls.is_empty() {
+            return post_send_transaction(&self.client, &self.rpc_url, &body).await;
+        }
+        
+        let endpoints: Vec<String> = std::iter::once(self.rpc_url.clone())
+            .chain(self.fanout_urls.iter().cloned())
+            .collect();
+        let (result_tx, mut result_rx) = tokio::sync::mpsc::unbounded_channel();
+        for url in &endpoints {
+            let client = self.client.clone();
+            let url = url.clone();
+            let body = body.clone();
+            let result_tx = result_tx.clone();
+            tokio::spawn(async move {
+                let result = post_send_transaction(&client, &url, &body).await;
+                let _ = result_tx.send((url, result));
+            });
+        }
+        drop(result_tx);
+        
+        let mut last_err = None;
+        while let Some((url, result)) = result_rx.recv().await {
+            match result {
+                Ok(signature) => {
+                    debug!("Fan-out send {} accepted first by {}", signature, url);
+                    return Ok(signature);
+                }
+                Err(e) => {
+                    debug!("Fan-out send to {} failed: {}", url, e);
+                    last_err = Some(e);
+                }
+            }
+        }
+        Err(last_err.unwrap_or_else(|| SendError::Network("no endpoints to send to".to_string())))
+    }
+    
+    /// Fetch the log messages of a confirmed transaction (getTransaction)
+    /// Returns None while the transaction is not yet visible or its meta has no logs
+    pub async fn get_transaction_logs(&self, signature: &Signature) -> Result<Option<Vec<String>>, SendError> {
+        let body = serde_json::json!({
+            "jsonrpc": "2.0",
+            "id": 1,
+            "method": "getTransaction",
+            "params": [
+                signature.to_string(),
+                { "encoding": "json", "commitment": "confirmed", "maxSupportedTransactionVersion": 0 }
+            ]
+        });
+        
         let response = self.client
             .post(&self.rpc_url)
             .json(&body)
@@ -65,13 +362,9 @@ impl TxSender {
             return Err(SendError::RpcError(error.to_string()));
         }
         
-        let sig_str = json["result"].as_str()
-            .ok_or(SendError::Parse("No result in response".to_string()))?;
-        
-        let signature = Signature::from_str(sig_str)
-            .map_err(|e| SendError::Parse(e.to_string()))?;
-        
-        Ok(signature)
+        Ok(json["result"]["meta"]["logMessages"].as_array().map(|logs| {
+            logs.iter().filter_map(|l| l.as_str().map(str::to_string)).collect()
+        }))
     }
     
     /// Check transaction signature status for a single signature
@@ -155,7 +448,11 @@ impl TxSender {
     /// Send and confirm a transaction via standard RPC
     pub async fn send_and_confirm_rpc(&self, tx: &Transaction, max_retries: u32) -> Result<Signature, SendError> {
         let signature = self.send_rpc(tx).await?;
-        
+        self.confirm_rpc(tx, signature, max_retries).await
+    }
+    
+    /// Poll until an already sent transaction confirms, re-sending it every 5 seconds
+    pub async fn confirm_rpc(&self, tx: &Transaction, signature: Signature, max_retries: u32) -> Result<Signature, SendError> {
         // Poll for confirmation
         for i in 0..max_retries {
             tokio::time::sleep(Duration::from_millis(500)).await;
@@ -188,47 +485,18 @@ impl TxSender {
     
     /// Send a versioned transaction via standard RPC
     pub async fn send_versioned_rpc(&self, tx: &VersionedTransaction) -> Result<Signature, SendError> {
+        if let Some(payer) = tx.message.static_account_keys().first() {
+            let fee = transaction_fee(tx.signatures.len(), tx.message.static_account_keys(), tx.message.instructions());
+            self.check_reserve(payer, fee)?;
+        }
+        
         let tx_bytes = bincode::serialize(tx)
             .map_err(|e| SendError::Serialize(e.to_string()))?;
         let tx_base64 = base64::encode(&tx_bytes);
         
         info!("Sending versioned tx: {} bytes (limit 1232)", tx_bytes.len());
         
-        let body = serde_json::json!({
-            "jsonrpc": "2.0",
-            "id": 1,
-            "method": "sendTransaction",
-            "params": [
-                tx_base64,
-                {
-                    "encoding": "base64",
-                    "skipPreflight": true,
-                    "maxRetries": 0
-                }
-            ]
-        });
-        
-        let response = self.client
-            .post(&self.rpc_url)
-            .json(&body)
-            .send()
-            .await
-            .map_err(|e| SendError::Network(e.to_string()))?;
-        
-        let json: serde_json::Value = response.json().await
-            .map_err(|e| SendError::Parse(e.to_string()))?;
-        
-        if let Some(error) = json.get("error") {
-            return Err(SendError::RpcError(error.to_string()));
-        }
-        
-        let sig_str = json["result"].as_str()
-            .ok_or(SendError::Parse("No result in response".to_string()))?;
-        
-        let signature = Signature::from_str(sig_str)
-            .map_err(|e| SendError::Parse(e.to_string()))?;
-        
-        Ok(signature)
+        self.broadcast_transaction(tx_base64).await
     }
     
     /// Send and confirm a versioned transaction via standard RPC
@@ -380,6 +648,78 @@ pub enum SendError {
     TransactionFailed(String),
     #[error("Timeout waiting for confirmation: {0}")]
     Timeout(String),
+    #[error("Fee payer balance {balance} lamports would drop below reserve of {reserve} lamports")]
+    BelowReserve { balance: u64, reserve: u64 },
+}
+
+/// POST a sendTransaction request to one endpoint and parse the returned signature
+async fn post_send_transaction(
+    client: &reqwest::Client,
+    url: &str,
+    body: &serde_json::Value,
+) -> Result<Signature, SendError> {
+    let response = client
+        .post(url)
+        .json(body)
+        .send()
+        .await
+        .map_err(|e| SendError::Network(e.to_string()))?;
+    
+    let json: serde_json::Value = response.json().await
+        .map_err(|e| SendError::Parse(e.to_string()))?;
+    
+    if let Some(error) = json.get("error") {
+        return Err(SendError::RpcError(error.to_string()));
+    }
+    
+    let sig_str = json["result"].as_str()
+        .ok_or(SendError::Parse("No result in response".to_string()))?;
+    
+    Signature::from_str(sig_str).map_err(|e| SendError::Parse(e.to_string()))
+}
+
+/// Signature fee plus the priority fee (unit price x unit limit) set by the
+/// transaction's ComputeBudget instructions
+fn transaction_fee(num_signatures: usize, account_keys: &[Pubkey], instructions: &[CompiledInstruction]) -> u64 {
+    let mut unit_limit = None;
+    let mut unit_price = 0u64;
+    let mut other_instructions = 0u64;
+    for ix in instructions {
+        if account_keys.get(ix.program_id_index as usize) != Some(&COMPUTE_BUDGET_PROGRAM_ID) {
+            other_instructions += 1;
+            continue;
+        }
+        match ix.data.split_first() {
+            // SetComputeUnitLimit(u32)
+            Some((2, rest)) if rest.len() >= 4 => {
+                unit_limit = Some(u32::from_le_bytes(rest[..4].try_into().unwrap()) as u64);
+            }
+            // SetComputeUnitPrice(u64 microlamports)
+            Some((3, rest)) if rest.len() >= 8 => {
+                unit_price = u64::from_le_bytes(rest[..8].try_into().unwrap());
+            }
+            _ => {}
+        }
+    }
+    let unit_limit = unit_limit
+        .unwrap_or(other_instructions * DEFAULT_UNITS_PER_INSTRUCTION)
+        .min(MAX_COMPUTE_UNIT_LIMIT);
+    let priority_fee = (unit_price as u128 * unit_limit as u128).div_ceil(1_000_000);
+    LAMPORTS_PER_SIGNATURE * num_signatures as u64 + priority_fee as u64
+}
+
+/// Whether a payer holding `balance` can pay `fee` and still keep `reserve`
+fn reserve_allows_send(balance: u64, fee: u64, reserve: u64) -> bool {
+    balance.checked_sub(fee).is_some_and(|left| left >= reserve)
+}
+
+/// The event (if any) for a reserve check, given whether the payer was already below the reserve
+fn reserve_transition(was_breached: bool, allowed: bool) -> Option<ReserveEvent> {
+    match (was_breached, allowed) {
+        (false, false) => Some(ReserveEvent::Low),
+        (true, true) => Some(ReserveEvent::Replenished),
+        _ => None,
+    }
 }
 
 /// Confirmation result for batch operations
@@ -392,3 +732,200 @@ pub enum ConfirmationResult {
     /// Transaction timed out waiting for confirmation
     Timeout(Signature),
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use solana_sdk::{
+        compute_budget::ComputeBudgetInstruction, message::Message, signature::Keypair, signer::Signer,
+        system_instruction,
+    };
+    use std::sync::Arc;
+
+    /// Minimal JSON-RPC endpoint: accepts sendTransaction with `signature`, reports it confirmed
+    /// Returns its URL and how many sendTransaction / getSignatureStatuses calls it served
+    async fn mock_endpoint(signature: Signature) -> (String, Arc<AtomicU64>, Arc<AtomicU64>) {
+        use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        let (sends, polls) = (Arc::new(AtomicU64::new(0)), Arc::new(AtomicU64::new(0)));
+        let (sent, polled) = (sends.clone(), polls.clone());
+        tokio::spawn(async move {
+            loop {
+                let (mut stream, _) = listener.accept().await.unwrap();
+                let mut request = Vec::new();
+                let mut buf = [0u8; 4096];
+                // Headers, then as much body as Content-Length says
+                let body = loop {
+                    let n = stream.read(&mut buf).await.unwrap();
+                    request.extend_from_slice(&buf[..n]);
+                    let text = String::from_utf8_lossy(&request).to_string();
+                    if let Some((head, body)) = text.split_once("\r\n\r\n") {
+                        let len: usize = head
+                            .lines()
+                            .find_map(|l| l.to_lowercase().strip_prefix("content-length:").map(|v| v.trim().parse().unwrap()))
+                            .unwrap_or(0);
+                        if body.len() >= len {
+                            break body.to_string();
+                        }
+                    }
+                };
+                let request: serde_json::Value = serde_json::from_str(&body).unwrap();
+                let result = match request["method"].as_str() {
+                    Some("sendTransaction") => {
+                        sent.fetch_add(1, Ordering::SeqCst);
+                        serde_json::json!(signature.to_string())
+                    }
+                    _ => {
+                        polled.fetch_add(1, Ordering::SeqCst);
+                        serde_json::json!({ "context": { "slot": 1 }, "value": [{ "err": null, "confirmationStatus": "confirmed" }] })
+                    }
+                };
+                let response = serde_json::json!({ "jsonrpc": "2.0", "id": 1, "result": result }).to_string();
+                let http = format!(
+                    "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                    response.len(),
+                    response
+                );
+                stream.write_all(http.as_bytes()).await.unwrap();
+            }
+        });
+        (url, sends, polls)
+    }
+
+    #[tokio::test]
+    async fn test_fanout_sends_to_every_endpoint_and_confirms_once() {
+        let payer = Keypair::new();
+        let ix = system_instruction::transfer(&payer.pubkey(), &Pubkey::new_unique(), 1);
+        let tx = Transaction::new_signed_with_payer(&[ix], Some(&payer.pubkey()), &[&payer], solana_sdk::hash::Hash::new_unique());
+        let signature = tx.signatures[0];
+
+        let (primary, primary_sends, primary_polls) = mock_endpoint(signature).await;
+        let (extra_a, extra_a_sends, extra_a_polls) = mock_endpoint(signature).await;
+        let (extra_b, extra_b_sends, extra_b_polls) = mock_endpoint(signature).await;
+        let sender = TxSender::new(primary).with_fanout(vec![extra_a, extra_b]);
+
+        assert_eq!(sender.send_and_confirm_rpc(&tx, 5).await.unwrap(), signature);
+
+        // Background submissions may still be in flight when the first accept returns
+        for _ in 0..50 {
+            if extra_a_sends.load(Ordering::SeqCst) == 1 && extra_b_sends.load(Ordering::SeqCst) == 1 {
+                break;
+            }
+            tokio::time::sleep(Duration::from_millis(20)).await;
+        }
+        for sends in [&primary_sends, &extra_a_sends, &extra_b_sends] {
+            assert_eq!(sends.load(Ordering::SeqCst), 1, "submitted to every endpoint");
+        }
+        // Confirmation is tracked once, under the single signature, on the primary
+        assert_eq!(primary_polls.load(Ordering::SeqCst), 1);
+        assert_eq!(extra_a_polls.load(Ordering::SeqCst) + extra_b_polls.load(Ordering::SeqCst), 0);
+    }
+
+    fn legacy_fee(tx: &Transaction) -> u64 {
+        transaction_fee(tx.signatures.len(), &tx.message.account_keys, &tx.message.instructions)
+    }
+
+    #[test]
+    fn test_priority_fee_breach_skips_send_and_pauses() {
+        let payer = Pubkey::new_unique();
+        let transfer = system_instruction::transfer(&payer, &Pubkey::new_unique(), 1);
+
+        // 1_400_000 CU at 100_000 microlamports/CU = 140_000 lamports on top of the signature fee
+        let message = Message::new(
+            &[
+                ComputeBudgetInstruction::set_compute_unit_limit(1_400_000),
+                ComputeBudgetInstruction::set_compute_unit_price(100_000),
+                transfer.clone(),
+            ],
+            Some(&payer),
+        );
+        let tx = Transaction::new_unsigned(message);
+        assert_eq!(legacy_fee(&tx), 5_000 + 140_000);
+
+        // Without a limit the runtime default applies to the non-budget instructions
+        let message = Message::new(&[ComputeBudgetInstruction::set_compute_unit_price(100_000), transfer.clone()], Some(&payer));
+        assert_eq!(legacy_fee(&Transaction::new_unsigned(message)), 5_000 + 20_000);
+        let message = Message::new(&[transfer], Some(&payer));
+        assert_eq!(legacy_fee(&Transaction::new_unsigned(message)), 5_000);
+
+        // Enough for the signature fee alone, but not once the priority fee is counted
+        let sender = TxSender::new("http://localhost:8899".to_string()).with_min_reserve(1_000_000);
+        let balance = 1_100_000;
+        assert!(reserve_allows_send(balance, LAMPORTS_PER_SIGNATURE, 1_000_000));
+        let t0 = Instant::now();
+        assert!(matches!(
+            sender.apply_reserve_check_at(&payer, balance, legacy_fee(&tx), t0),
+            Err(SendError::BelowReserve { balance: 1_100_000, reserve: 1_000_000 })
+        ));
+
+        // Sends stay skipped for RESERVE_PAUSE, then the balance is checked again
+        assert!(matches!(
+            sender.reserve_paused_at(t0 + Duration::from_secs(29)),
+            Err(SendError::BelowReserve { .. })
+        ));
+        assert!(sender.reserve_paused_at(t0 + RESERVE_PAUSE).is_ok());
+        assert!(sender.apply_reserve_check_at(&payer, 2_000_000, legacy_fee(&tx), t0 + RESERVE_PAUSE).is_ok());
+        assert!(sender.reserve_paused_at(t0 + RESERVE_PAUSE).is_ok(), "a passing check lifts the pause");
+        assert_eq!(sender.reserve_low_alerts.load(Ordering::Relaxed), 1);
+        assert_eq!(sender.reserve_replenished_alerts.load(Ordering::Relaxed), 1);
+    }
+
+    #[test]
+    fn test_reserve_checks_the_cached_balance() {
+        let sender = TxSender::new("http://localhost:8899".to_string()).with_min_reserve(1_000_000);
+        let payer = Pubkey::new_unique();
+        let fee = LAMPORTS_PER_SIGNATURE;
+
+        // No reading yet (or the last one failed): the send goes through
+        assert!(sender.check_reserve(&payer, fee).is_ok());
+
+        // Each allowed send is charged against the cached balance
+        sender.set_payer_balance(&payer, Some(1_000_000 + fee));
+        assert!(sender.check_reserve(&payer, fee).is_ok());
+        assert_eq!(*sender.payer_balance.lock().unwrap(), Some((payer, 1_000_000)));
+        assert!(matches!(
+            sender.check_reserve(&payer, fee),
+            Err(SendError::BelowReserve { balance: 1_000_000, reserve: 1_000_000 })
+        ));
+
+        // A reading for some other account says nothing about this payer
+        let sender = TxSender::new("http://localhost:8899".to_string()).with_min_reserve(1_000_000);
+        sender.set_payer_balance(&Pubkey::new_unique(), Some(0));
+        assert!(sender.check_reserve(&payer, fee).is_ok());
+    }
+
+    #[test]
+    fn test_reserve_low_and_replenished_notify_once_each() {
+        let sender = TxSender::new("http://localhost:8899".to_string()).with_min_reserve(1_000_000);
+        let payer = Pubkey::new_unique();
+        let alerts = || {
+            (
+                sender.reserve_low_alerts.load(Ordering::Relaxed),
+                sender.reserve_replenished_alerts.load(Ordering::Relaxed),
+            )
+        };
+
+        // Healthy from the start: nothing to report
+        sender.track_reserve(&payer, 5_000_000, true);
+        assert_eq!(alerts(), (0, 0));
+
+        // Several checks below the reserve, one low alert
+        for balance in [900_000, 800_000, 700_000] {
+            sender.track_reserve(&payer, balance, false);
+        }
+        assert_eq!(alerts(), (1, 0));
+
+        // Topped up: one replenished alert however many checks pass
+        for balance in [3_000_000, 3_000_000] {
+            sender.track_reserve(&payer, balance, true);
+        }
+        assert_eq!(alerts(), (1, 1));
+
+        assert_eq!(reserve_transition(false, false), Some(ReserveEvent::Low));
+        assert_eq!(reserve_transition(true, true), Some(ReserveEvent::Replenished));
+        assert_eq!(reserve_transition(true, false), None);
+        assert_eq!(reserve_transition(false, true), None);
+    }
+}
@@ -0,0 +1,333 @@
+//! Manager state snapshot
+//!
+//! Collects everything the program and ORE hold for one manager (manager
+//! account, deployers, allowlist, schedule, managed miner balances, ORE miner
+//! states and their LUTs) into one JSON document for support and migration.
+//! Pubkeys are written base58 so the dump can be read without decoding.
+
+use evore::ore_api::{miner_pda, Miner};
+use evore::state::{
+    deploy_authority_allowlist_pda, deploy_schedule_pda, deployer_pda, managed_miner_auth_pda,
+    strategy_deployer_pda, DeployAuthorityAllowlist, DeploySchedule, Deployer, Manager,
+    StrategyDeployer, LEGACY_MANAGER_SIZE,
+};
+use serde::Serialize;
+use solana_client::rpc_client::RpcClient;
+use solana_sdk::{account::Account, pubkey::Pubkey};
+use steel::AccountDeserialize;
+
+use crate::crank::CrankError;
+use crate::lut::LutRegistry;
+
+/// Manager-level accounts, fetched in this order ahead of the per-miner ones
+const MANAGER_ACCOUNTS: usize = 5;
+
+/// getMultipleAccounts accepts at most this many addresses
+const MAX_MULTIPLE_ACCOUNTS: usize = 100;
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ManagerSnapshot {
+    /// Slot the accounts were read at
+    pub slot: u64,
+    pub manager: String,
+    /// None when the manager account does not exist
+    pub manager_account: Option<ManagerState>,
+    pub deployer: Option<DeployerState>,
+    pub strategy_deployer: Option<StrategyDeployerState>,
+    pub allowlist: Option<Vec<String>>,
+    pub schedule: Option<Vec<u64>>,
+    /// Shared LUT of the snapshotting deploy authority, if it has one
+    pub shared_lut: Option<String>,
+    /// Managed miners that hold lamports or have an ORE miner
+    pub miners: Vec<MinerState>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ManagerState {
+    pub authority: String,
+    /// Created before `reserved` existed and still needs MigrateManager
+    pub legacy: bool,
+    pub lamports: u64,
+    /// Per-instruction deploy ceiling (0 = none, always 0 for legacy managers)
+    pub max_deploy_per_tx: u64,
+    /// Nominee of an InitiateTransferManager that hasn't accepted yet
+    pub pending_authority: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct DeployerState {
+    pub address: String,
+    pub deploy_authority: String,
+    pub bps_fee: u64,
+    pub flat_fee: u64,
+    pub expected_bps_fee: u64,
+    pub expected_flat_fee: u64,
+    pub max_per_round: u64,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct StrategyDeployerState {
+    #[serde(flatten)]
+    pub deployer: DeployerState,
+    pub strategy_type: u8,
+    pub strategy_data: Vec<u8>,
+    pub max_squares_per_deploy: u8,
+    pub blocked_squares_mask: u32,
+    pub max_loss_per_round: u64,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct MinerState {
+    pub auth_id: u64,
+    pub managed_miner_auth: String,
+    pub balance: u64,
+    pub ore_miner: Option<OreMinerState>,
+    pub lut: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct OreMinerState {
+    pub address: String,
+    pub round_id: u64,
+    pub checkpoint_id: u64,
+    pub checkpoint_fee: u64,
+    pub deployed: Vec<u64>,
+    pub rewards_sol: u64,
+    pub rewards_ore: u64,
+    pub refined_ore: u64,
+    pub lifetime_rewards_sol: u64,
+}
+
+impl DeployerState {
+    fn new(address: Pubkey, deploy_authority: Pubkey, fees: [u64; 5]) -> Self {
+        let [bps_fee, flat_fee, expected_bps_fee, expected_flat_fee, max_per_round] = fees;
+        Self {
+            address: address.to_string(),
+            deploy_authority: deploy_authority.to_string(),
+            bps_fee,
+            flat_fee,
+            expected_bps_fee,
+            expected_flat_fee,
+            max_per_round,
+        }
+    }
+}
+
+/// Addresses read for a snapshot: the manager-level accounts, then
+/// (managed_miner_auth, ORE miner) for each auth_id below `auth_ids`
+pub fn snapshot_addresses(manager: Pubkey, auth_ids: u64) -> Vec<Pubkey> {
+    let mut addresses = vec![
+        manager,
+        deployer_pda(manager).0,
+        strategy_deployer_pda(manager).0,
+        deploy_authority_allowlist_pda(manager).0,
+        deploy_schedule_pda(manager).0,
+    ];
+    for auth_id in 0..auth_ids {
+        let (miner_auth, _) = managed_miner_auth_pda(manager, auth_id);
+        addresses.push(miner_auth);
+        addresses.push(miner_pda(miner_auth).0);
+    }
+    addresses
+}
+
+/// Assemble a snapshot from accounts laid out as `snapshot_addresses` returns them
+pub fn build_snapshot(
+    manager: Pubkey,
+    slot: u64,
+    accounts: &[Option<Account>],
+    luts: &LutRegistry,
+) -> ManagerSnapshot {
+    let (manager_level, miner_level) = accounts.split_at(MANAGER_ACCOUNTS.min(accounts.len()));
+    let at = |i: usize| manager_level.get(i).and_then(|a| a.as_ref());
+
+    let manager_account = at(0).and_then(|a| {
+        let legacy = a.data.len() == LEGACY_MANAGER_SIZE;
+        let (authority, max_deploy_per_tx, pending_authority) = if legacy {
+            (Pubkey::try_from(&a.data[8..40]).ok()?, 0, Pubkey::default())
+        } else {
+            let manager = Manager::try_from_bytes(&a.data).ok()?;
+            (manager.authority, manager.max_deploy_per_tx, manager.pending_authority)
+        };
+        Some(ManagerState {
+            authority: authority.to_string(),
+            legacy,
+            lamports: a.lamports,
+            max_deploy_per_tx,
+            pending_authority: (pending_authority != Pubkey::default()).then(|| pending_authority.to_string()),
+        })
+    });
+
+    let deployer = at(1)
+        .and_then(|a| Deployer::try_from_bytes(&a.data).ok())
+        .map(|d| DeployerState::new(
+            deployer_pda(manager).0,
+            d.deploy_authority,
+            [d.bps_fee, d.flat_fee, d.expected_bps_fee, d.expected_flat_fee, d.max_per_round],
+        ));
+
+    let strategy_deployer = at(2)
+        .and_then(|a| StrategyDeployer::from_account_data(&a.data).ok())
+        .map(|d| StrategyDeployerState {
+            deployer: DeployerState::new(
+                strategy_deployer_pda(manager).0,
+                d.deploy_authority,
+                [d.bps_fee, d.flat_fee, d.expected_bps_fee, d.expected_flat_fee, d.max_per_round],
+            ),
+            strategy_type: d.strategy_type,
+            strategy_data: d.strategy_data.to_vec(),
+            max_squares_per_deploy: d.max_squares_per_deploy,
+            blocked_squares_mask: d.blocked_squares(),
+            max_loss_per_round: d.max_loss_per_round,
+        });
+
+    let allowlist = at(3)
+        .and_then(|a| DeployAuthorityAllowlist::try_from_bytes(&a.data).ok())
+        .map(|l| l.entries().iter().map(|k| k.to_string()).collect());
+
+    let schedule = at(4)
+        .and_then(|a| DeploySchedule::try_from_bytes(&a.data).ok())
+        .map(|s| s.amounts[..(s.len as usize).min(s.amounts.len())].to_vec());
+
+    let miners = miner_level
+        .chunks(2)
+        .enumerate()
+        .filter_map(|(auth_id, pair)| {
+            let auth_id = auth_id as u64;
+            let (miner_auth, _) = managed_miner_auth_pda(manager, auth_id);
+            let balance = pair.first().and_then(|a| a.as_ref()).map(|a| a.lamports).unwrap_or(0);
+            let ore_miner = pair
+                .get(1)
+                .and_then(|a| a.as_ref())
+                .and_then(|a| Miner::try_from_bytes(&a.data).ok())
+                .map(|m| OreMinerState {
+                    address: miner_pda(miner_auth).0.to_string(),
+                    round_id: m.round_id,
+                    checkpoint_id: m.checkpoint_id,
+                    checkpoint_fee: m.checkpoint_fee,
+                    deployed: m.deployed.to_vec(),
+                    rewards_sol: m.rewards_sol,
+                    rewards_ore: m.rewards_ore,
+                    refined_ore: m.refined_ore,
+                    lifetime_rewards_sol: m.lifetime_rewards_sol,
+                });
+            if balance == 0 && ore_miner.is_none() {
+                return None;
+            }
+            Some(MinerState {
+                auth_id,
+                managed_miner_auth: miner_auth.to_string(),
+                balance,
+                ore_miner,
+                lut: luts.get_miner_lut(&miner_auth).map(|l| l.to_string()),
+            })
+        })
+        .collect();
+
+    ManagerSnapshot {
+        slot,
+        manager: manager.to_string(),
+        manager_account,
+        deployer,
+        strategy_deployer,
+        allowlist,
+        schedule,
+        shared_lut: luts.shared_lut().map(|l| l.to_string()),
+        miners,
+    }
+}
+
+/// Read a manager's accounts (getMultipleAccounts, 100 per call) and assemble its snapshot
+pub fn fetch_snapshot(
+    rpc_client: &RpcClient,
+    manager: Pubkey,
+    auth_ids: u64,
+    luts: &LutRegistry,
+) -> Result<ManagerSnapshot, CrankError> {
+    let addresses = snapshot_addresses(manager, auth_ids);
+    let mut accounts = Vec::with_capacity(addresses.len());
+    let mut slot = 0;
+    for chunk in addresses.chunks(MAX_MULTIPLE_ACCOUNTS) {
+        let response = rpc_client
+            .get_multiple_accounts_with_commitment(chunk, rpc_client.commitment())
+            .map_err(|e| CrankError::Rpc(e.to_string()))?;
+        slot = slot.max(response.context.slot);
+        accounts.extend(response.value);
+    }
+    Ok(build_snapshot(manager, slot, &accounts, luts))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use evore::ore_api::OreAccount;
+    use evore::state::EvoreAccount;
+    use steel::Zeroable;
+
+    fn account(discriminator: u64, body: &[u8], lamports: u64) -> Option<Account> {
+        let mut data = discriminator.to_le_bytes().to_vec();
+        data.extend_from_slice(body);
+        Some(Account { lamports, data, owner: evore::id(), executable: false, rent_epoch: 0 })
+    }
+
+    #[test]
+    fn test_snapshot_from_mocked_accounts() {
+        let manager = Pubkey::new_unique();
+        let authority = Pubkey::new_unique();
+        let deploy_authority = Pubkey::new_unique();
+
+        let mut manager_state = Manager::zeroed();
+        manager_state.authority = authority;
+        let mut deployer = Deployer::zeroed();
+        deployer.manager_key = manager;
+        deployer.deploy_authority = deploy_authority;
+        deployer.bps_fee = 250;
+        deployer.expected_bps_fee = 250;
+        let mut miner = Miner::zeroed();
+        miner.round_id = 12;
+        miner.deployed[4] = 1_000;
+
+        // auth_id 0 is funded with an ORE miner, auth_id 1 was never used
+        let accounts = vec![
+            account(EvoreAccount::Manager as u64, manager_state.to_bytes(), 5_000),
+            account(EvoreAccount::Deployer as u64, deployer.to_bytes(), 1_000),
+            None,
+            None,
+            None,
+            account(0, &[], 40_000),
+            account(OreAccount::Miner as u64, miner.to_bytes(), 1_000),
+            None,
+            None,
+        ];
+        assert_eq!(accounts.len(), snapshot_addresses(manager, 2).len());
+
+        let (miner_auth, _) = managed_miner_auth_pda(manager, 0);
+        let lut = Pubkey::new_unique();
+        let mut luts = LutRegistry::new(RpcClient::new_mock("succeeds".to_string()), deploy_authority);
+        luts.register_miner_lut(miner_auth, lut, vec![miner_auth]);
+
+        let snapshot = build_snapshot(manager, 77, &accounts, &luts);
+        let json = serde_json::to_value(&snapshot).unwrap();
+
+        assert_eq!(json["slot"], 77);
+        assert_eq!(json["manager"], manager.to_string());
+        assert_eq!(json["manager_account"]["authority"], authority.to_string());
+        assert_eq!(json["manager_account"]["legacy"], false);
+        assert_eq!(json["deployer"]["address"], deployer_pda(manager).0.to_string());
+        assert_eq!(json["deployer"]["deploy_authority"], deploy_authority.to_string());
+        assert_eq!(json["deployer"]["bps_fee"], 250);
+        assert!(json["strategy_deployer"].is_null());
+        assert!(json["allowlist"].is_null());
+        assert!(json["schedule"].is_null());
+
+        let miners = json["miners"].as_array().unwrap();
+        assert_eq!(miners.len(), 1, "the unused auth_id is left out");
+        assert_eq!(miners[0]["auth_id"], 0);
+        assert_eq!(miners[0]["managed_miner_auth"], miner_auth.to_string());
+        assert_eq!(miners[0]["balance"], 40_000);
+        assert_eq!(miners[0]["lut"], lut.to_string());
+        assert_eq!(miners[0]["ore_miner"]["address"], miner_pda(miner_auth).0.to_string());
+        assert_eq!(miners[0]["ore_miner"]["round_id"], 12);
+        assert_eq!(miners[0]["ore_miner"]["deployed"][4], 1_000);
+    }
+}
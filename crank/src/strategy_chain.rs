@@ -0,0 +1,139 @@
+//! Ordered deploy strategies for the run loop
+//!
+//! Each strategy picks which of the configured squares a miner deploys on this
+//! round. The chain tries them in order and falls through to the next one when a
+//! strategy comes up with nothing to deploy - the `NoDeployments` the program
+//! returns for an empty squares mask - so "EV, else percentage" is
+//! `STRATEGY_CHAIN=ev,percentage`. Any other error stops the chain.
+
+use clap::ValueEnum;
+use evore::error::EvoreError;
+
+/// Number of squares on the board besides the winning one
+const LOSING_SQUARES: u128 = 24;
+
+/// How one strategy picks squares
+#[derive(ValueEnum, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DeployStrategy {
+    /// Every configured square
+    Fixed,
+    /// Configured squares where our amount has positive expected SOL back on the round so far
+    /// (the losing squares' pot against a 1-in-25 win, ORE rewards not counted)
+    Ev,
+    /// Configured squares where our amount stays within PERCENTAGE_MAX_SHARE_BPS of the
+    /// square's total (empty squares are skipped, we'd own all of them)
+    Percentage,
+}
+
+/// STRATEGY_CHAIN and the parameters its strategies need
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StrategyChain {
+    pub strategies: Vec<DeployStrategy>,
+    pub percentage_max_share_bps: u64,
+}
+
+/// Whether `error` means "nothing to deploy" rather than a deploy that can't work
+pub fn is_no_deployments(error: EvoreError) -> bool {
+    matches!(error, EvoreError::NoDeployments)
+}
+
+impl DeployStrategy {
+    /// Squares of `allowed` this strategy deploys `amount` on, given the round's per-square totals
+    pub fn squares(
+        self,
+        allowed: u32,
+        amount: u64,
+        deployed: &[u64; 25],
+        percentage_max_share_bps: u64,
+    ) -> Result<u32, EvoreError> {
+        let total: u128 = deployed.iter().map(|&d| d as u128).sum();
+        let amount = amount as u128;
+
+        let mask = (0..25)
+            .filter(|&i| allowed & (1 << i) != 0)
+            .filter(|&i| {
+                let on_square = deployed[i] as u128;
+                match self {
+                    DeployStrategy::Fixed => true,
+                    // Win 1/25 of the time: a/(d+a) of the other squares' T-d, vs losing a the other 24/25
+                    DeployStrategy::Ev => total - on_square > LOSING_SQUARES * (on_square + amount),
+                    DeployStrategy::Percentage => {
+                        amount * 10_000 <= percentage_max_share_bps as u128 * (on_square + amount)
+                    }
+                }
+            })
+            .fold(0u32, |mask, i| mask | (1 << i));
+
+        if mask == 0 {
+            return Err(EvoreError::NoDeployments);
+        }
+        Ok(mask)
+    }
+}
+
+impl StrategyChain {
+    /// Whether any strategy looks at the round's per-square totals
+    pub fn needs_round(&self) -> bool {
+        self.strategies.iter().any(|&s| s != DeployStrategy::Fixed)
+    }
+
+    /// The first strategy with something to deploy, and the squares it picked
+    pub fn select(
+        &self,
+        allowed: u32,
+        amount: u64,
+        deployed: &[u64; 25],
+    ) -> Result<(DeployStrategy, u32), EvoreError> {
+        for &strategy in &self.strategies {
+            match strategy.squares(allowed, amount, deployed, self.percentage_max_share_bps) {
+                Ok(mask) => return Ok((strategy, mask)),
+                Err(e) if is_no_deployments(e) => continue,
+                Err(e) => return Err(e),
+            }
+        }
+        Err(EvoreError::NoDeployments)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ALL: u32 = evore::squares::ALL_SQUARES_MASK as u32;
+
+    #[test]
+    fn test_ev_without_deployments_falls_back_to_percentage() {
+        let chain = StrategyChain {
+            strategies: vec![DeployStrategy::Ev, DeployStrategy::Percentage],
+            percentage_max_share_bps: 1_000,
+        };
+
+        // An evenly filled board is never +EV, so EV has nothing and percentage deploys
+        // wherever 2_800 lamports stays under 10% of the square
+        let mut deployed = [1_000_000u64; 25];
+        assert_eq!(DeployStrategy::Ev.squares(ALL, 2_800, &deployed, 1_000), Err(EvoreError::NoDeployments));
+        assert_eq!(chain.select(ALL, 2_800, &deployed), Ok((DeployStrategy::Percentage, ALL)));
+
+        // Thin squares on a full board are +EV, so EV wins and percentage is never tried
+        deployed[4] = 10_000;
+        deployed[9] = 0;
+        let (strategy, mask) = chain.select(ALL, 2_800, &deployed).unwrap();
+        assert_eq!(strategy, DeployStrategy::Ev);
+        assert_eq!(mask, (1 << 4) | (1 << 9));
+        // Those are exactly the squares percentage leaves out
+        assert_eq!(
+            DeployStrategy::Percentage.squares(ALL, 2_800, &deployed, 1_000),
+            Ok(ALL & !((1 << 4) | (1 << 9)))
+        );
+
+        // Only configured squares are considered, and nothing anywhere ends the chain
+        assert_eq!(chain.select(1 << 4, 2_800, &deployed), Ok((DeployStrategy::Ev, 1 << 4)));
+        assert_eq!(chain.select(ALL, 2_800, &[0; 25]), Err(EvoreError::NoDeployments));
+
+        // Fixed takes the configured squares as they are and never looks at the round
+        let fixed = StrategyChain { strategies: vec![DeployStrategy::Fixed], percentage_max_share_bps: 0 };
+        assert!(!fixed.needs_round());
+        assert_eq!(fixed.select(0b101, 2_800, &[0; 25]), Ok((DeployStrategy::Fixed, 0b101)));
+        assert!(chain.needs_round());
+    }
+}
@@ -0,0 +1,36 @@
+use crate::{squares::BOARD_SQUARES, validation::StrategyType};
+
+/// Highest compute unit limit a transaction can request
+pub const MAX_COMPUTE_UNIT_LIMIT: u32 = 1_400_000;
+
+/// (base, per square) compute units for one strat autodeploy
+///
+/// Sized with headroom over program-test measurements: Manual and Split land in a
+/// single ORE deploy (~30-35k whatever the square count), the per-square strategies
+/// pay one ORE deploy CPI plus their own math per square (a full board needs the
+/// whole 1.4M), and the EV waterfill grows with the squares it considers.
+const fn strategy_cu_preset(strategy_type: StrategyType) -> (u32, u32) {
+    match strategy_type {
+        StrategyType::Manual | StrategyType::Split => (50_000, 1_000),
+        StrategyType::Percentage
+        | StrategyType::DynamicSplitPercentage
+        | StrategyType::MirrorTopMiner
+        | StrategyType::LoserPool
+        | StrategyType::Momentum
+        | StrategyType::Weighted => (50_000, 56_000),
+        StrategyType::Ev | StrategyType::DynamicEv => (200_000, 48_000),
+    }
+}
+
+/// Compute unit limit for a strat autodeploy touching `squares_count` squares.
+///
+/// A count of 0 (unknown, e.g. EV picking its own squares) or above the board is
+/// treated as all 25 squares. Never exceeds `MAX_COMPUTE_UNIT_LIMIT`.
+pub fn strategy_cu_limit(strategy_type: StrategyType, squares_count: u64) -> u32 {
+    let squares = match squares_count {
+        0 => BOARD_SQUARES as u32,
+        n => n.min(BOARD_SQUARES as u64) as u32,
+    };
+    let (base, per_square) = strategy_cu_preset(strategy_type);
+    (base + per_square * squares).min(MAX_COMPUTE_UNIT_LIMIT)
+}
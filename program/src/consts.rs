@@ -4,7 +4,30 @@ use steel::Pubkey;
 pub const MANAGED_MINER_AUTH: &[u8] = b"managed-miner-auth";
 pub const DEPLOYER: &[u8] = b"deployer";
 pub const STRATEGY_DEPLOYER: &[u8] = b"strategy-deployer";
+pub const DEPLOY_AUTHORITY_ALLOWLIST: &[u8] = b"deploy-authority-allowlist";
+pub const DEPLOY_SCHEDULE: &[u8] = b"deploy-schedule";
 pub const FEE_COLLECTOR: Pubkey = pubkey!("56qSi79jWdM1zie17NKFvdsh213wPb15HHUqGUjmJ2Lr");
 
 pub const DEPLOY_FEE: u64 = 0_000_001_000;
 
+/// Maximum deploy authorities a single manager allowlist can hold
+pub const MAX_ALLOWLISTED_AUTHORITIES: usize = 8;
+
+/// Maximum entries in a manager's per-round deploy schedule
+pub const MAX_SCHEDULE_ENTRIES: usize = 16;
+
+/// Interface version reported by GetProgramInfo; bump on any instruction or account layout change
+pub const PROGRAM_VERSION: u32 = 9;
+
+// UpdateDeployer field mask bits (which fields an update should write)
+pub const UPDATE_BPS_FEE: u64 = 1 << 0;
+pub const UPDATE_FLAT_FEE: u64 = 1 << 1;
+pub const UPDATE_EXPECTED_BPS_FEE: u64 = 1 << 2;
+pub const UPDATE_EXPECTED_FLAT_FEE: u64 = 1 << 3;
+pub const UPDATE_MAX_PER_ROUND: u64 = 1 << 4;
+pub const UPDATE_ALL_FIELDS: u64 = UPDATE_BPS_FEE
+    | UPDATE_FLAT_FEE
+    | UPDATE_EXPECTED_BPS_FEE
+    | UPDATE_EXPECTED_FLAT_FEE
+    | UPDATE_MAX_PER_ROUND;
+
@@ -19,7 +19,7 @@ pub enum EvoreError {
     #[error("Too many slots left: wait until closer to round end")]
     TooManySlotsLeft = 2,
     
-    /// The round has already ended
+    /// The round has already ended (zero slots remaining, `slot >= end_slot`)
     #[error("End slot reached: round has already ended")]
     EndSlotReached = 3,
     
@@ -114,6 +114,58 @@ pub enum EvoreError {
     /// The strategy deployer account has not been initialized
     #[error("Strategy deployer not initialized: create strategy deployer first")]
     StratDeployerNotInitialized = 22,
+
+    /// A squares count or squares mask references squares beyond the 25-square board
+    #[error("Invalid square count: squares_count must be at most 25 and masks may only use bits 0-24")]
+    InvalidSquareCount = 23,
+
+    /// The manager has a deploy authority allowlist and the deploy_authority is not on it
+    #[error("Authority not allowlisted: deploy_authority is not on the manager's allowlist")]
+    AuthorityNotAllowlisted = 24,
+
+    /// The deploy authority allowlist has no free entries left
+    #[error("Allowlist full: remove an entry before adding another deploy authority")]
+    AllowlistFull = 25,
+
+    /// The deploy schedule has more entries than the account can hold
+    #[error("Invalid deploy schedule: at most 16 entries are allowed")]
+    InvalidDeploySchedule = 26,
+
+    /// The ORE miner's round_id is ahead of the board's (stale board or reorg)
+    #[error("Miner ahead of board: miner round_id is greater than board round_id")]
+    MinerAheadOfBoard = 27,
+
+    /// The manager account already has the current layout
+    #[error("Manager already migrated: account is already in new format")]
+    ManagerAlreadyMigrated = 28,
+
+    /// The top miner account doesn't belong to the round's top_miner (or the round has none)
+    #[error("Invalid top miner: account does not match round top_miner")]
+    InvalidTopMiner = 29,
+
+    /// The treasury or treasury token account passed to a claim isn't ORE's
+    #[error("Invalid treasury: treasury or treasury token account does not match ORE's")]
+    InvalidTreasury = 30,
+
+    /// A managed_miner_auth PDA passed to CloseManager still holds lamports
+    #[error("Manager not empty: withdraw every managed_miner_auth balance before closing")]
+    ManagerNotEmpty = 31,
+
+    /// The deploy would leave more at risk this round than the strategy deployer's max_loss_per_round
+    #[error("Exceeds max loss per round: worst-case loss would exceed max_loss_per_round limit")]
+    ExceedsMaxLoss = 32,
+
+    /// One deploy instruction would exceed the manager's max_deploy_per_tx ceiling
+    #[error("Deploy ceiling exceeded: deploy total exceeds the manager's max_deploy_per_tx")]
+    DeployCeilingExceeded = 33,
+
+    /// An MMAutodeploy squares_mask has bits set above square 24
+    #[error("Invalid squares mask: only bits 0-24 map to board squares")]
+    InvalidSquaresMask = 34,
+
+    /// The ORE program was redeployed since the strategy deployer pinned its deploy slot
+    #[error("ORE program changed: ORE program data slot does not match the pinned slot")]
+    OreProgramChanged = 35,
 }
 
 error!(EvoreError);
@@ -0,0 +1,49 @@
+//! Structured program logs
+//!
+//! Every managed-miner deploy logs a `DeployEvent` with `sol_log_data`, which shows up in
+//! the transaction's log messages as `Program data: <base64>`. Off-chain readers (the crank)
+//! decode it with `DeployEvent::from_log_bytes` to learn exactly what landed without
+//! re-fetching the ORE miner.
+
+use solana_program::pubkey::Pubkey;
+use steel::*;
+
+/// Leads every DeployEvent's bytes so other `Program data:` lines are never mistaken for one
+pub const DEPLOY_EVENT_TAG: [u8; 8] = *b"evdeploy";
+
+/// One managed miner's deploy, logged once per miner by every autodeploy instruction
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Pod, Zeroable)]
+pub struct DeployEvent {
+    pub manager: Pubkey,
+    pub auth_id: u64,
+    pub round_id: u64,
+    /// Lamports put on the board, summed over every square
+    pub total_deployed: u64,
+    /// Deployer plus protocol fee taken by this deploy (0 on a repeat deploy in the round)
+    pub fee: u64,
+    /// Squares that received a deploy (bit i = square i)
+    pub squares_mask: u32,
+    pub _padding: [u8; 4],
+}
+
+impl DeployEvent {
+    /// Tag followed by the little-endian event
+    pub fn to_log_bytes(&self) -> Vec<u8> {
+        let mut bytes = Vec::with_capacity(DEPLOY_EVENT_TAG.len() + std::mem::size_of::<Self>());
+        bytes.extend_from_slice(&DEPLOY_EVENT_TAG);
+        bytes.extend_from_slice(bytemuck::bytes_of(self));
+        bytes
+    }
+
+    /// Decodes `to_log_bytes` output; None for any other data
+    pub fn from_log_bytes(data: &[u8]) -> Option<Self> {
+        let body = data.strip_prefix(&DEPLOY_EVENT_TAG)?;
+        bytemuck::try_pod_read_unaligned(body).ok()
+    }
+
+    /// Writes the event to the program log
+    pub fn log(&self) {
+        solana_program::log::sol_log_data(&[&self.to_log_bytes()]);
+    }
+}
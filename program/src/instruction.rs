@@ -1,7 +1,7 @@
-use spl_associated_token_account::get_associated_token_address;
+use spl_associated_token_account::get_associated_token_address_with_program_id;
 use steel::*;
 
-use crate::{consts::FEE_COLLECTOR, entropy_api, ore_api::{self, automation_pda, board_pda, config_pda, miner_pda, round_pda, treasury_pda}, state::{managed_miner_auth_pda, deployer_pda, strategy_deployer_pda}};
+use crate::{consts::{FEE_COLLECTOR, MAX_SCHEDULE_ENTRIES, UPDATE_ALL_FIELDS}, entropy_api, ore_api::{self, automation_pda, board_pda, config_pda, miner_pda, round_pda, treasury_pda}, state::{managed_miner_auth_pda, deployer_pda, deploy_authority_allowlist_pda, deploy_schedule_pda, strategy_deployer_pda}};
 
 #[repr(u8)]
 #[derive(Clone, Copy, Debug, Eq, PartialEq, TryFromPrimitive)]
@@ -28,6 +28,19 @@ pub enum Instructions {
     MMStratFullAutodeploy = 19,
     MMStratAutocheckpoint = 20,
     RecycleStratSol = 21,
+    AddDeployAuthority = 22,
+    RemoveDeployAuthority = 23,
+    SetDeploySchedule = 24,
+    MigrateAuthId = 25,
+    MigrateManager = 26,
+    GetProgramInfo = 27,
+    CloseManager = 28,
+    SetMaxDeployPerTx = 29,
+    InitiateTransferManager = 30,
+    AcceptTransferManager = 31,
+    MMPreviewRecycle = 32,
+    MMStatus = 33,
+    BulkUpdateDeployerFees = 34,
 }
 
 /// Deployment strategy enum with associated data
@@ -52,7 +65,7 @@ pub enum DeployStrategy {
     Manual {
         amounts: [u64; 25],   // Amount to deploy on each square (0 = skip)
     },
-    /// Split: deploy total amount equally across all 25 squares in one CPI call
+    /// Split: deploy total amount equally across all 25 squares (remainder to the highest-EV square)
     Split {
         amount: u64,          // Total amount to split across 25 squares
     },
@@ -94,8 +107,10 @@ pub struct TransferManager {}
 
 instruction!(Instructions, TransferManager);
 
-/// Transfer manager authority to a new pubkey.
+/// Transfer manager authority to a new pubkey, effective immediately.
 /// Note: This transfers all associated mining accounts (deployer, miner, etc.)
+/// Prefer initiate_transfer_manager + accept_transfer_manager, which can't hand the
+/// manager to an address nobody controls.
 pub fn transfer_manager(signer: Pubkey, manager: Pubkey, new_authority: Pubkey) -> Instruction {
     Instruction {
         program_id: crate::id(),
@@ -114,7 +129,8 @@ pub fn transfer_manager(signer: Pubkey, manager: Pubkey, new_authority: Pubkey)
 /// - auth_id: [u8; 8] - Manager auth ID
 /// - bump: u8 - PDA bump
 /// - allow_multi_deploy: u8 - If 0, fail if already deployed this round (applies to all strategies)
-/// - _pad: [u8; 6] - Padding for alignment
+/// - simulate: u8 - If 1, only plan the deploy and return the per-square amounts (no transfers or CPIs)
+/// - _pad: [u8; 5] - Padding for alignment
 /// - data: [u8; 256] - Strategy data where:
 ///   - data[0]: strategy discriminant (0 = EV, 1 = Percentage, 2 = Manual, 3 = Split)
 ///   
@@ -142,7 +158,8 @@ pub struct MMDeploy {
     pub auth_id: [u8; 8],
     pub bump: u8,
     pub allow_multi_deploy: u8,
-    pub _pad: [u8; 6],
+    pub simulate: u8,
+    pub _pad: [u8; 5],
     pub data: [u8; 256],
 }
 
@@ -187,11 +204,18 @@ impl MMDeploy {
             auth_id: auth_id.to_le_bytes(),
             bump,
             allow_multi_deploy: if allow_multi_deploy { 1 } else { 0 },
-            _pad: [0; 6],
+            simulate: 0,
+            _pad: [0; 5],
             data,
         }
     }
 
+    /// Same deploy, but the program only returns the planned `[u64; 25]` via return data
+    pub fn preview(mut self) -> Self {
+        self.simulate = 1;
+        self
+    }
+
     /// Parse the strategy from the instruction data
     pub fn get_strategy(&self) -> Result<DeployStrategy, ()> {
         let strategy = self.data[0];
@@ -233,16 +257,18 @@ impl MMDeploy {
     pub fn get_allow_multi_deploy(&self) -> bool {
         self.allow_multi_deploy != 0
     }
+
+    /// Check if this is a preview (simulate) deploy
+    pub fn is_simulate(&self) -> bool {
+        self.simulate != 0
+    }
 }
 
-/// Build deploy accounts (shared by all strategies)
-fn build_deploy_accounts(
-    signer: Pubkey,
-    manager: Pubkey,
-    auth_id: u64,
-    round_id: u64,
-) -> (Vec<AccountMeta>, u8) {
-    let (managed_miner_auth_address, bump) = managed_miner_auth_pda(manager, auth_id);
+/// Accounts used by every MMDeploy variant (ev/percentage/manual/split), in order,
+/// without the leading signer. Lets clients pre-fetch the accounts or put them in a LUT
+/// without deriving each PDA themselves.
+pub fn deploy_accounts(manager: Pubkey, auth_id: u64, round_id: u64) -> Vec<AccountMeta> {
+    let (managed_miner_auth_address, _) = managed_miner_auth_pda(manager, auth_id);
     let ore_miner_address = miner_pda(managed_miner_auth_address);
 
     let authority = managed_miner_auth_address;
@@ -252,8 +278,7 @@ fn build_deploy_accounts(
     let round_address = round_pda(round_id).0;
     let entropy_var_address = entropy_api::var_pda(board_address, 0).0;
 
-    let accounts = vec![
-        AccountMeta::new(signer, true),
+    vec![
         AccountMeta::new(manager, false),
         AccountMeta::new(managed_miner_auth_address, false),
         AccountMeta::new(ore_miner_address.0, false),
@@ -266,7 +291,20 @@ fn build_deploy_accounts(
         AccountMeta::new_readonly(ore_api::id(), false),
         AccountMeta::new_readonly(entropy_api::id(), false),
         AccountMeta::new_readonly(system_program::id(), false),
-    ];
+    ]
+}
+
+/// Build deploy accounts (shared by all strategies)
+fn build_deploy_accounts(
+    signer: Pubkey,
+    manager: Pubkey,
+    auth_id: u64,
+    round_id: u64,
+) -> (Vec<AccountMeta>, u8) {
+    let (_, bump) = managed_miner_auth_pda(manager, auth_id);
+
+    let mut accounts = vec![AccountMeta::new(signer, true)];
+    accounts.extend(deploy_accounts(manager, auth_id, round_id));
 
     (accounts, bump)
 }
@@ -369,6 +407,75 @@ pub fn split_deploy(
     }
 }
 
+/// Preview an EV deploy: same accounts and checks as `ev_deploy`, but the program
+/// sets the planned per-square amounts (`[u64; 25]`, little endian) as return data
+/// instead of moving funds or calling ORE. Meant for `simulateTransaction`.
+pub fn ev_deploy_preview(
+    signer: Pubkey,
+    manager: Pubkey,
+    auth_id: u64,
+    round_id: u64,
+    bankroll: u64,
+    max_per_square: u64,
+    min_bet: u64,
+    ore_value: u64,
+    slots_left: u64,
+    attempts: u64,
+    allow_multi_deploy: bool,
+) -> Instruction {
+    as_preview(ev_deploy(
+        signer, manager, auth_id, round_id, bankroll, max_per_square, min_bet, ore_value,
+        slots_left, attempts, allow_multi_deploy,
+    ))
+}
+
+/// Preview a percentage deploy (see `ev_deploy_preview`)
+pub fn percentage_deploy_preview(
+    signer: Pubkey,
+    manager: Pubkey,
+    auth_id: u64,
+    round_id: u64,
+    bankroll: u64,
+    percentage: u64,
+    squares_count: u64,
+    allow_multi_deploy: bool,
+) -> Instruction {
+    as_preview(percentage_deploy(
+        signer, manager, auth_id, round_id, bankroll, percentage, squares_count, allow_multi_deploy,
+    ))
+}
+
+/// Preview a manual deploy (see `ev_deploy_preview`)
+pub fn manual_deploy_preview(
+    signer: Pubkey,
+    manager: Pubkey,
+    auth_id: u64,
+    round_id: u64,
+    amounts: [u64; 25],
+    allow_multi_deploy: bool,
+) -> Instruction {
+    as_preview(manual_deploy(signer, manager, auth_id, round_id, amounts, allow_multi_deploy))
+}
+
+/// Preview a split deploy (see `ev_deploy_preview`)
+pub fn split_deploy_preview(
+    signer: Pubkey,
+    manager: Pubkey,
+    auth_id: u64,
+    round_id: u64,
+    amount: u64,
+    allow_multi_deploy: bool,
+) -> Instruction {
+    as_preview(split_deploy(signer, manager, auth_id, round_id, amount, allow_multi_deploy))
+}
+
+/// Set the simulate flag on a built MMDeploy instruction
+fn as_preview(mut ix: Instruction) -> Instruction {
+    let args = *MMDeploy::try_from_bytes(&ix.data[1..]).expect("MMDeploy data");
+    ix.data = args.preview().to_bytes();
+    ix
+}
+
 #[repr(C)]
 #[derive(Clone, Copy, Debug, Pod, Zeroable)]
 pub struct MMCheckpoint {
@@ -448,13 +555,45 @@ pub struct MMClaimORE {
 instruction!(Instructions, MMClaimORE);
 
 pub fn mm_claim_ore(signer: Pubkey, manager: Pubkey, auth_id: u64) -> Instruction {
+    build_claim_ore(signer, manager, auth_id, signer, spl_token::id())
+}
+
+/// Claim ORE into `destination`'s ATA (e.g. a treasury or cold wallet) instead of the signer's.
+/// The signer must still be the manager authority; the ATA is created if missing.
+pub fn mm_claim_ore_to(signer: Pubkey, manager: Pubkey, auth_id: u64, destination: Pubkey) -> Instruction {
+    let mut ix = build_claim_ore(signer, manager, auth_id, destination, spl_token::id());
+    ix.accounts.push(AccountMeta::new_readonly(destination, false));
+    ix
+}
+
+/// `mm_claim_ore_to` for an ORE mint owned by `token_program` (e.g. Token-2022).
+/// ATAs are derived under that program and it is passed through to the ORE claim.
+pub fn mm_claim_ore_with_token_program(
+    signer: Pubkey,
+    manager: Pubkey,
+    auth_id: u64,
+    destination: Pubkey,
+    token_program: Pubkey,
+) -> Instruction {
+    let mut ix = build_claim_ore(signer, manager, auth_id, destination, token_program);
+    ix.accounts.push(AccountMeta::new_readonly(destination, false));
+    ix
+}
+
+fn build_claim_ore(
+    signer: Pubkey,
+    manager: Pubkey,
+    auth_id: u64,
+    destination: Pubkey,
+    token_program: Pubkey,
+) -> Instruction {
     let (managed_miner_auth_address, bump) = managed_miner_auth_pda(manager, auth_id);
     let ore_miner_address = miner_pda(managed_miner_auth_address);
     let board_address = board_pda().0;
     let treasury_address = treasury_pda().0;
-    let treasury_tokens_address = get_associated_token_address(&treasury_address, &ore_api::MINT_ADDRESS);
-    let recipient_address = get_associated_token_address(&managed_miner_auth_address, &ore_api::MINT_ADDRESS);
-    let signer_recipient_address = get_associated_token_address(&signer, &ore_api::MINT_ADDRESS);
+    let treasury_tokens_address = ore_api::treasury_tokens_address();
+    let recipient_address = get_associated_token_address_with_program_id(&managed_miner_auth_address, &ore_api::MINT_ADDRESS, &token_program);
+    let signer_recipient_address = get_associated_token_address_with_program_id(&destination, &ore_api::MINT_ADDRESS, &token_program);
 
     Instruction {
         program_id: crate::id(),
@@ -470,7 +609,7 @@ pub fn mm_claim_ore(signer: Pubkey, manager: Pubkey, auth_id: u64) -> Instructio
             AccountMeta::new(treasury_address, false),
             AccountMeta::new(treasury_tokens_address, false),
             AccountMeta::new_readonly(system_program::id(), false),
-            AccountMeta::new_readonly(spl_token::id(), false),
+            AccountMeta::new_readonly(token_program, false),
             AccountMeta::new_readonly(spl_associated_token_account::id(), false),
             AccountMeta::new_readonly(ore_api::id(), false),
         ],
@@ -524,6 +663,7 @@ pub fn create_deployer(
             AccountMeta::new(deployer_address, false),
             AccountMeta::new_readonly(deploy_authority, false),
             AccountMeta::new_readonly(system_program::id(), false),
+            AccountMeta::new_readonly(deploy_authority_allowlist_pda(manager).0, false),
         ],
         data: CreateDeployer {
             bps_fee: bps_fee.to_le_bytes(),
@@ -537,7 +677,7 @@ pub fn create_deployer(
 /// Updates deployer configuration
 /// - Manager authority: can update deploy_authority, expected_bps_fee, expected_flat_fee, max_per_round
 /// - Deploy authority: can update deploy_authority, bps_fee, flat_fee
-/// Pass current values for fields you don't want to change
+/// Only fields whose bit is set in update_mask (see consts::UPDATE_*) are written
 #[repr(C)]
 #[derive(Clone, Copy, Debug, Pod, Zeroable)]
 pub struct UpdateDeployer {
@@ -551,6 +691,8 @@ pub struct UpdateDeployer {
     pub expected_flat_fee: [u8; 8],
     /// Maximum lamports to deploy per round (0 = unlimited) - manager only
     pub max_per_round: [u8; 8],
+    /// Bitmask of fields to write (consts::UPDATE_*); unset fields are left untouched
+    pub update_mask: [u8; 8],
 }
 
 instruction!(Instructions, UpdateDeployer);
@@ -568,6 +710,34 @@ pub fn update_deployer(
     new_expected_bps_fee: u64,
     new_expected_flat_fee: u64,
     new_max_per_round: u64,
+) -> Instruction {
+    update_deployer_fields(
+        signer,
+        manager,
+        new_deploy_authority,
+        UPDATE_ALL_FIELDS,
+        new_bps_fee,
+        new_flat_fee,
+        new_expected_bps_fee,
+        new_expected_flat_fee,
+        new_max_per_round,
+    )
+}
+
+/// Update only the deployer fields selected by `update_mask` (consts::UPDATE_*)
+/// Values for unselected fields are ignored, so concurrent updates to different
+/// fields don't clobber each other. deploy_authority is always set to `new_deploy_authority`,
+/// which must be on the manager's allowlist (if it has one) when it differs from the current one.
+pub fn update_deployer_fields(
+    signer: Pubkey,
+    manager: Pubkey,
+    new_deploy_authority: Pubkey,
+    update_mask: u64,
+    new_bps_fee: u64,
+    new_flat_fee: u64,
+    new_expected_bps_fee: u64,
+    new_expected_flat_fee: u64,
+    new_max_per_round: u64,
 ) -> Instruction {
     let (deployer_address, _bump) = deployer_pda(manager);
 
@@ -579,6 +749,7 @@ pub fn update_deployer(
             AccountMeta::new(deployer_address, false),
             AccountMeta::new_readonly(new_deploy_authority, false),
             AccountMeta::new_readonly(system_program::id(), false),
+            AccountMeta::new_readonly(deploy_authority_allowlist_pda(manager).0, false),
         ],
         data: UpdateDeployer {
             bps_fee: new_bps_fee.to_le_bytes(),
@@ -586,6 +757,7 @@ pub fn update_deployer(
             expected_bps_fee: new_expected_bps_fee.to_le_bytes(),
             expected_flat_fee: new_expected_flat_fee.to_le_bytes(),
             max_per_round: new_max_per_round.to_le_bytes(),
+            update_mask: update_mask.to_le_bytes(),
         }.to_bytes(),
     }
 }
@@ -939,6 +1111,38 @@ pub fn mm_full_autodeploy(
     }
 }
 
+/// `mm_full_autodeploy` for several managed miners of one manager, atomically
+///
+/// The first auth_id is carried in the args as usual; the rest follow as trailing
+/// auth_ids, each adding its managed_miner_auth, ore_miner, automation and
+/// checkpoint_round accounts after the 16 base accounts. All miners deploy the same
+/// `amount` on `squares_mask` and checkpoint `checkpoint_round_id` if they need it.
+pub fn mm_full_autodeploy_multi(
+    signer: Pubkey,
+    manager: Pubkey,
+    auth_ids: &[u64],
+    round_id: u64,
+    checkpoint_round_id: u64,
+    amount: u64,
+    squares_mask: u32,
+) -> Instruction {
+    let (&first, rest) = auth_ids.split_first().expect("at least one auth_id");
+    let mut ix = mm_full_autodeploy(signer, manager, first, round_id, checkpoint_round_id, amount, squares_mask);
+    let checkpoint_round_address = round_pda(checkpoint_round_id).0;
+
+    for &auth_id in rest {
+        let (managed_miner_auth_address, _) = managed_miner_auth_pda(manager, auth_id);
+        ix.accounts.extend([
+            AccountMeta::new(managed_miner_auth_address, false),                     // managed_miner_auth PDA
+            AccountMeta::new(miner_pda(managed_miner_auth_address).0, false),        // ore_miner
+            AccountMeta::new(automation_pda(managed_miner_auth_address).0, false),   // automation
+            AccountMeta::new(checkpoint_round_address, false),                       // checkpoint_round
+        ]);
+        ix.data.extend_from_slice(&auth_id.to_le_bytes());
+    }
+    ix
+}
+
 // ============================================================================
 // MMCreateMiner Instruction
 // ============================================================================
@@ -958,6 +1162,9 @@ instruction!(Instructions, MMCreateMiner);
 /// This CPIs to ORE's automate instruction twice:
 /// 1. First with executor = signer (opens automation, creates miner)
 /// 2. Second with executor = Pubkey::default() (closes automation)
+///
+/// If the miner already exists nothing is created: any open automation is closed
+/// and the instruction succeeds, so it is safe to send without knowing the state.
 /// 
 /// Note: executor_2 = Pubkey::default() = system_program::id()
 /// We use readonly for executor_2 to avoid privilege conflicts with system_program.
@@ -993,7 +1200,8 @@ pub fn mm_create_miner(signer: Pubkey, manager: Pubkey, auth_id: u64) -> Instruc
 
 /// WithdrawTokens instruction data
 /// Withdraws the full balance of any SPL token from a managed_miner_auth's ATA
-/// to the manager authority's ATA. Mint-agnostic: pass the mint as an account.
+/// to the manager authority's ATA, or to the ATA of an optional trailing destination
+/// owner account. Mint-agnostic: pass the mint as an account.
 #[repr(C)]
 #[derive(Clone, Copy, Debug, Pod, Zeroable)]
 pub struct WithdrawTokens {
@@ -1006,9 +1214,49 @@ instruction!(Instructions, WithdrawTokens);
 /// Withdraw full token balance from a managed_miner_auth's ATA to the signer's ATA.
 /// The mint is passed as an account, making this instruction mint-agnostic.
 pub fn withdraw_tokens(signer: Pubkey, manager: Pubkey, auth_id: u64, mint: Pubkey) -> Instruction {
+    build_withdraw_tokens(signer, manager, auth_id, mint, signer, spl_token::id())
+}
+
+/// Withdraw full token balance into `destination`'s ATA instead of the signer's.
+/// The signer must still be the manager authority; the ATA is created if missing.
+pub fn withdraw_tokens_to(
+    signer: Pubkey,
+    manager: Pubkey,
+    auth_id: u64,
+    mint: Pubkey,
+    destination: Pubkey,
+) -> Instruction {
+    let mut ix = build_withdraw_tokens(signer, manager, auth_id, mint, destination, spl_token::id());
+    ix.accounts.push(AccountMeta::new_readonly(destination, false));
+    ix
+}
+
+/// `withdraw_tokens_to` for a mint owned by `token_program` (e.g. Token-2022).
+/// ATAs are derived under that program; the processor checks it owns the mint.
+pub fn withdraw_tokens_with_token_program(
+    signer: Pubkey,
+    manager: Pubkey,
+    auth_id: u64,
+    mint: Pubkey,
+    destination: Pubkey,
+    token_program: Pubkey,
+) -> Instruction {
+    let mut ix = build_withdraw_tokens(signer, manager, auth_id, mint, destination, token_program);
+    ix.accounts.push(AccountMeta::new_readonly(destination, false));
+    ix
+}
+
+fn build_withdraw_tokens(
+    signer: Pubkey,
+    manager: Pubkey,
+    auth_id: u64,
+    mint: Pubkey,
+    destination: Pubkey,
+    token_program: Pubkey,
+) -> Instruction {
     let (managed_miner_auth_address, bump) = managed_miner_auth_pda(manager, auth_id);
-    let source_ata = get_associated_token_address(&managed_miner_auth_address, &mint);
-    let destination_ata = get_associated_token_address(&signer, &mint);
+    let source_ata = get_associated_token_address_with_program_id(&managed_miner_auth_address, &mint, &token_program);
+    let destination_ata = get_associated_token_address_with_program_id(&destination, &mint, &token_program);
 
     Instruction {
         program_id: crate::id(),
@@ -1020,7 +1268,7 @@ pub fn withdraw_tokens(signer: Pubkey, manager: Pubkey, auth_id: u64, mint: Pubk
             AccountMeta::new(destination_ata, false),
             AccountMeta::new_readonly(mint, false),
             AccountMeta::new_readonly(system_program::id(), false),
-            AccountMeta::new_readonly(spl_token::id(), false),
+            AccountMeta::new_readonly(token_program, false),
             AccountMeta::new_readonly(spl_associated_token_account::id(), false),
         ],
         data: WithdrawTokens {
@@ -1042,7 +1290,10 @@ pub struct CreateStratDeployer {
     pub max_per_round: [u8; 8],
     pub strategy_type: u8,
     pub strategy_data: [u8; 64],
-    pub _pad: [u8; 7],
+    pub max_squares_per_deploy: u8,
+    pub blocked_squares_mask: [u8; 4],
+    pub _pad: [u8; 2],
+    pub max_loss_per_round: [u8; 8],
 }
 
 instruction!(Instructions, CreateStratDeployer);
@@ -1056,6 +1307,9 @@ pub fn create_strat_deployer(
     max_per_round: u64,
     strategy_type: u8,
     strategy_data: [u8; 64],
+    max_squares_per_deploy: u8,
+    blocked_squares_mask: u32,
+    max_loss_per_round: u64,
 ) -> Instruction {
     let (strat_deployer_address, _) = crate::state::strategy_deployer_pda(manager);
 
@@ -1067,6 +1321,7 @@ pub fn create_strat_deployer(
             AccountMeta::new(strat_deployer_address, false),
             AccountMeta::new_readonly(deploy_authority, false),
             AccountMeta::new_readonly(system_program::id(), false),
+            AccountMeta::new_readonly(deploy_authority_allowlist_pda(manager).0, false),
         ],
         data: CreateStratDeployer {
             bps_fee: bps_fee.to_le_bytes(),
@@ -1074,7 +1329,10 @@ pub fn create_strat_deployer(
             max_per_round: max_per_round.to_le_bytes(),
             strategy_type,
             strategy_data,
-            _pad: [0; 7],
+            max_squares_per_deploy,
+            blocked_squares_mask: blocked_squares_mask.to_le_bytes(),
+            _pad: [0; 2],
+            max_loss_per_round: max_loss_per_round.to_le_bytes(),
         }.to_bytes(),
     }
 }
@@ -1093,7 +1351,10 @@ pub struct UpdateStratDeployer {
     pub max_per_round: [u8; 8],
     pub strategy_type: u8,
     pub strategy_data: [u8; 64],
-    pub _pad: [u8; 7],
+    pub max_squares_per_deploy: u8,
+    pub blocked_squares_mask: [u8; 4],
+    pub _pad: [u8; 2],
+    pub max_loss_per_round: [u8; 8],
 }
 
 instruction!(Instructions, UpdateStratDeployer);
@@ -1109,6 +1370,9 @@ pub fn update_strat_deployer(
     max_per_round: u64,
     strategy_type: u8,
     strategy_data: [u8; 64],
+    max_squares_per_deploy: u8,
+    blocked_squares_mask: u32,
+    max_loss_per_round: u64,
 ) -> Instruction {
     let (strat_deployer_address, _) = crate::state::strategy_deployer_pda(manager);
 
@@ -1120,6 +1384,7 @@ pub fn update_strat_deployer(
             AccountMeta::new(strat_deployer_address, false),
             AccountMeta::new_readonly(new_deploy_authority, false),
             AccountMeta::new_readonly(system_program::id(), false),
+            AccountMeta::new_readonly(deploy_authority_allowlist_pda(manager).0, false),
         ],
         data: UpdateStratDeployer {
             bps_fee: bps_fee.to_le_bytes(),
@@ -1129,7 +1394,10 @@ pub fn update_strat_deployer(
             max_per_round: max_per_round.to_le_bytes(),
             strategy_type,
             strategy_data,
-            _pad: [0; 7],
+            max_squares_per_deploy,
+            blocked_squares_mask: blocked_squares_mask.to_le_bytes(),
+            _pad: [0; 2],
+            max_loss_per_round: max_loss_per_round.to_le_bytes(),
         }.to_bytes(),
     }
 }
@@ -1273,6 +1541,27 @@ pub fn mm_strat_autodeploy(
     }
 }
 
+/// MMStratAutodeploy for a MirrorTopMiner deployer: appends the ORE miner of
+/// `top_miner` (the current round's `top_miner` authority) for the program to copy
+pub fn mm_strat_autodeploy_mirror(
+    deploy_authority: Pubkey,
+    manager: Pubkey,
+    auth_id: u64,
+    amount: u64,
+    top_miner: Pubkey,
+) -> Instruction {
+    let mut ix = mm_strat_autodeploy(deploy_authority, manager, auth_id, amount, 0, 0);
+    ix.accounts.push(AccountMeta::new_readonly(miner_pda(top_miner).0, false)); // 14: top miner
+    ix
+}
+
+/// Appends ORE's program data account to an MMStratAutodeploy or MMStratFullAutodeploy,
+/// required when the deployer pins an ORE program slot (`strategy_data` word 7)
+pub fn with_ore_program_data(mut ix: Instruction) -> Instruction {
+    ix.accounts.push(AccountMeta::new_readonly(ore_api::program_data_pda().0, false));
+    ix
+}
+
 // ============================================================================
 // MMStratFullAutodeploy - Strategy-based full autodeploy (checkpoint + recycle + deploy)
 // ============================================================================
@@ -1335,3 +1624,459 @@ pub fn mm_strat_full_autodeploy(
         }.to_bytes(),
     }
 }
+
+/// MMStratFullAutodeploy for a MirrorTopMiner deployer (see `mm_strat_autodeploy_mirror`)
+pub fn mm_strat_full_autodeploy_mirror(
+    deploy_authority: Pubkey,
+    manager: Pubkey,
+    auth_id: u64,
+    amount: u64,
+    top_miner: Pubkey,
+) -> Instruction {
+    let mut ix = mm_strat_full_autodeploy(deploy_authority, manager, auth_id, amount, 0, 0);
+    ix.accounts.push(AccountMeta::new_readonly(miner_pda(top_miner).0, false)); // 16: top miner
+    ix
+}
+
+// ============================================================================
+// Deploy Authority Allowlist Instructions
+// ============================================================================
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Pod, Zeroable)]
+pub struct AddDeployAuthority {}
+
+instruction!(Instructions, AddDeployAuthority);
+
+/// Allowlist a deploy authority for the manager's deployers (manager authority only).
+/// Creates the allowlist PDA on first use; once it has entries, deployers can only be
+/// created for listed authorities.
+pub fn add_deploy_authority(signer: Pubkey, manager: Pubkey, deploy_authority: Pubkey) -> Instruction {
+    let (allowlist_address, _) = deploy_authority_allowlist_pda(manager);
+
+    Instruction {
+        program_id: crate::id(),
+        accounts: vec![
+            AccountMeta::new(signer, true),
+            AccountMeta::new_readonly(manager, false),
+            AccountMeta::new(allowlist_address, false),
+            AccountMeta::new_readonly(deploy_authority, false),
+            AccountMeta::new_readonly(system_program::id(), false),
+        ],
+        data: AddDeployAuthority {}.to_bytes(),
+    }
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Pod, Zeroable)]
+pub struct RemoveDeployAuthority {}
+
+instruction!(Instructions, RemoveDeployAuthority);
+
+/// Remove a deploy authority from the manager's allowlist (manager authority only).
+/// Existing deployers are not affected; removing the last entry lifts the restriction.
+pub fn remove_deploy_authority(signer: Pubkey, manager: Pubkey, deploy_authority: Pubkey) -> Instruction {
+    let (allowlist_address, _) = deploy_authority_allowlist_pda(manager);
+
+    Instruction {
+        program_id: crate::id(),
+        accounts: vec![
+            AccountMeta::new(signer, true),
+            AccountMeta::new_readonly(manager, false),
+            AccountMeta::new(allowlist_address, false),
+            AccountMeta::new_readonly(deploy_authority, false),
+        ],
+        data: RemoveDeployAuthority {}.to_bytes(),
+    }
+}
+
+// ============================================================================
+// SetDeploySchedule Instruction
+// ============================================================================
+
+/// SetDeploySchedule instruction data
+/// Overwrites the manager's per-round deploy schedule (only the first `len` amounts are used)
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Pod, Zeroable)]
+pub struct SetDeploySchedule {
+    /// Number of schedule entries (0 = no schedule, max MAX_SCHEDULE_ENTRIES)
+    pub len: [u8; 8],
+    /// Lamports per square for each entry
+    pub amounts: [[u8; 8]; MAX_SCHEDULE_ENTRIES],
+}
+
+instruction!(Instructions, SetDeploySchedule);
+
+/// Set the manager's deploy schedule (manager authority only), creating the PDA on first use.
+/// Round r is deployed with amounts[r % amounts.len()]; an empty slice clears the schedule.
+pub fn set_deploy_schedule(signer: Pubkey, manager: Pubkey, amounts: &[u64]) -> Instruction {
+    let (schedule_address, _) = deploy_schedule_pda(manager);
+
+    let mut encoded = [[0u8; 8]; MAX_SCHEDULE_ENTRIES];
+    for (slot, amount) in encoded.iter_mut().zip(amounts) {
+        *slot = amount.to_le_bytes();
+    }
+
+    Instruction {
+        program_id: crate::id(),
+        accounts: vec![
+            AccountMeta::new(signer, true),
+            AccountMeta::new_readonly(manager, false),
+            AccountMeta::new(schedule_address, false),
+            AccountMeta::new_readonly(system_program::id(), false),
+        ],
+        data: SetDeploySchedule {
+            len: (amounts.len() as u64).to_le_bytes(),
+            amounts: encoded,
+        }.to_bytes(),
+    }
+}
+
+// ============================================================================
+// MigrateAuthId Instruction
+// ============================================================================
+
+/// MigrateAuthId instruction data
+/// Moves the whole managed_miner_auth balance from one auth_id to another of the same manager
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Pod, Zeroable)]
+pub struct MigrateAuthId {
+    pub old_auth_id: [u8; 8],
+    pub new_auth_id: [u8; 8],
+}
+
+instruction!(Instructions, MigrateAuthId);
+
+/// Move all SOL from the `old_auth_id` managed_miner_auth PDA to the `new_auth_id` one (manager authority only)
+pub fn migrate_auth_id(
+    signer: Pubkey,
+    manager: Pubkey,
+    old_auth_id: u64,
+    new_auth_id: u64,
+) -> Instruction {
+    let (old_auth_address, _) = managed_miner_auth_pda(manager, old_auth_id);
+    let (new_auth_address, _) = managed_miner_auth_pda(manager, new_auth_id);
+
+    Instruction {
+        program_id: crate::id(),
+        accounts: vec![
+            AccountMeta::new(signer, true),                         // 0: signer (manager authority)
+            AccountMeta::new_readonly(manager, false),              // 1: manager
+            AccountMeta::new(old_auth_address, false),              // 2: old managed_miner_auth PDA
+            AccountMeta::new(new_auth_address, false),              // 3: new managed_miner_auth PDA
+            AccountMeta::new_readonly(system_program::id(), false), // 4: system_program
+        ],
+        data: MigrateAuthId {
+            old_auth_id: old_auth_id.to_le_bytes(),
+            new_auth_id: new_auth_id.to_le_bytes(),
+        }.to_bytes(),
+    }
+}
+
+// ============================================================================
+// MigrateManager Instruction
+// ============================================================================
+
+/// MigrateManager instruction data (no args)
+/// Grows a legacy 40-byte manager to the current Manager size
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Pod, Zeroable)]
+pub struct MigrateManager {}
+
+instruction!(Instructions, MigrateManager);
+
+/// Grow a legacy manager account to the current layout (manager authority pays the extra rent)
+pub fn migrate_manager(signer: Pubkey, manager: Pubkey) -> Instruction {
+    Instruction {
+        program_id: crate::id(),
+        accounts: vec![
+            AccountMeta::new(signer, true),                         // 0: signer (manager authority, pays rent)
+            AccountMeta::new(manager, false),                       // 1: manager
+            AccountMeta::new_readonly(system_program::id(), false), // 2: system_program
+        ],
+        data: MigrateManager {}.to_bytes(),
+    }
+}
+
+// ============================================================================
+// GetProgramInfo Instruction
+// ============================================================================
+
+/// GetProgramInfo instruction data (no args, no accounts)
+/// Sets `ProgramInfo` as return data so clients can check what this deployment supports
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Pod, Zeroable)]
+pub struct GetProgramInfo {}
+
+instruction!(Instructions, GetProgramInfo);
+
+/// GetProgramInfo return data
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Pod, Zeroable)]
+pub struct ProgramInfo {
+    /// `consts::PROGRAM_VERSION` of the deployed program
+    pub version: u32,
+    /// Bit i set when `StrategyType` i is accepted
+    pub strategies: u32,
+    /// Bit i set when the instruction with discriminator i is handled
+    pub instructions: u64,
+}
+
+impl ProgramInfo {
+    /// Version and features of this build
+    pub fn current() -> Self {
+        let instructions = crate::processor::HANDLERS
+            .iter()
+            .fold(0u64, |mask, (ix, _)| mask | 1 << *ix as u8);
+        let strategies = (0..32u8)
+            .filter(|&t| crate::validation::StrategyType::try_from(t).is_ok())
+            .fold(0u32, |mask, t| mask | 1 << t);
+
+        Self { version: crate::consts::PROGRAM_VERSION, strategies, instructions }
+    }
+
+    /// Parses the return data of a GetProgramInfo call
+    pub fn from_return_data(data: &[u8]) -> Option<Self> {
+        bytemuck::try_pod_read_unaligned(data).ok()
+    }
+
+    pub fn supports_instruction(&self, ix: Instructions) -> bool {
+        self.instructions & (1 << ix as u8) != 0
+    }
+
+    pub fn supports_strategy(&self, strategy_type: crate::validation::StrategyType) -> bool {
+        self.strategies & (1 << strategy_type as u8) != 0
+    }
+}
+
+/// Query the program's version and feature bitmask (read it back from the transaction's return data)
+pub fn get_program_info() -> Instruction {
+    Instruction {
+        program_id: crate::id(),
+        accounts: vec![],
+        data: GetProgramInfo {}.to_bytes(),
+    }
+}
+
+// ============================================================================
+// CloseManager Instruction
+// ============================================================================
+
+/// CloseManager instruction data (no args)
+/// Closes a manager whose managed_miner_auth PDAs are all empty, along with its deployer,
+/// strategy deployer, allowlist and schedule PDAs, returning their rent to the authority
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Pod, Zeroable)]
+pub struct CloseManager {}
+
+instruction!(Instructions, CloseManager);
+
+/// Close `manager` and send its rent to the signer (manager authority only).
+/// `auth_id_count` managed_miner_auth PDAs (auth_ids 0..auth_id_count) are passed along
+/// and must all be empty; withdraw or migrate their balances first. The program requires
+/// at least auth_id 0. Any deployer, strategy deployer, allowlist or schedule PDA the
+/// manager created is closed too.
+pub fn close_manager(signer: Pubkey, manager: Pubkey, auth_id_count: u64) -> Instruction {
+    let mut accounts = vec![
+        AccountMeta::new(signer, true),                                     // 0: signer (manager authority, receives rent)
+        AccountMeta::new(manager, false),                                   // 1: manager
+        AccountMeta::new(deployer_pda(manager).0, false),                   // 2: deployer
+        AccountMeta::new(strategy_deployer_pda(manager).0, false),          // 3: strategy deployer
+        AccountMeta::new(deploy_authority_allowlist_pda(manager).0, false), // 4: allowlist
+        AccountMeta::new(deploy_schedule_pda(manager).0, false),            // 5: schedule
+    ];
+    // 6..: managed_miner_auth PDAs for auth_ids 0, 1, 2, ...
+    accounts.extend(
+        (0..auth_id_count).map(|auth_id| AccountMeta::new_readonly(managed_miner_auth_pda(manager, auth_id).0, false)),
+    );
+
+    Instruction {
+        program_id: crate::id(),
+        accounts,
+        data: CloseManager {}.to_bytes(),
+    }
+}
+
+// ============================================================================
+// SetMaxDeployPerTx Instruction
+// ============================================================================
+
+/// SetMaxDeployPerTx instruction data
+/// Sets the manager's per-instruction deploy ceiling
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Pod, Zeroable)]
+pub struct SetMaxDeployPerTx {
+    /// Most lamports one deploy instruction may deploy (0 = no ceiling)
+    pub max_deploy_per_tx: [u8; 8],
+}
+
+instruction!(Instructions, SetMaxDeployPerTx);
+
+/// Set the most lamports any single autodeploy for `manager` may deploy (manager authority only).
+/// 0 removes the ceiling. The ceiling holds whatever deployer or strategy deployer runs the deploy.
+pub fn set_max_deploy_per_tx(signer: Pubkey, manager: Pubkey, max_deploy_per_tx: u64) -> Instruction {
+    Instruction {
+        program_id: crate::id(),
+        accounts: vec![
+            AccountMeta::new(signer, true),   // 0: signer (manager authority)
+            AccountMeta::new(manager, false), // 1: manager
+        ],
+        data: SetMaxDeployPerTx {
+            max_deploy_per_tx: max_deploy_per_tx.to_le_bytes(),
+        }.to_bytes(),
+    }
+}
+
+// ============================================================================
+// Two-step TransferManager Instructions
+// ============================================================================
+
+/// InitiateTransferManager instruction data (no args)
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Pod, Zeroable)]
+pub struct InitiateTransferManager {}
+
+instruction!(Instructions, InitiateTransferManager);
+
+/// Nominate `new_authority` as the manager's next authority (manager authority only).
+/// Nothing changes hands until `new_authority` signs AcceptTransferManager; initiating
+/// again replaces the pending nominee, and the default pubkey cancels the transfer.
+pub fn initiate_transfer_manager(signer: Pubkey, manager: Pubkey, new_authority: Pubkey) -> Instruction {
+    Instruction {
+        program_id: crate::id(),
+        accounts: vec![
+            AccountMeta::new(signer, true),                 // 0: signer (manager authority)
+            AccountMeta::new(manager, false),               // 1: manager
+            AccountMeta::new_readonly(new_authority, false), // 2: pending authority
+        ],
+        data: InitiateTransferManager {}.to_bytes(),
+    }
+}
+
+/// AcceptTransferManager instruction data (no args)
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Pod, Zeroable)]
+pub struct AcceptTransferManager {}
+
+instruction!(Instructions, AcceptTransferManager);
+
+/// Accept a pending manager transfer; must be signed by the pending authority
+pub fn accept_transfer_manager(signer: Pubkey, manager: Pubkey) -> Instruction {
+    Instruction {
+        program_id: crate::id(),
+        accounts: vec![
+            AccountMeta::new(signer, true),   // 0: signer (pending authority)
+            AccountMeta::new(manager, false), // 1: manager
+        ],
+        data: AcceptTransferManager {}.to_bytes(),
+    }
+}
+
+// ============================================================================
+// MMPreviewRecycle Instruction
+// ============================================================================
+
+/// MMPreviewRecycle instruction data
+/// Read-only: sets the SOL a RecycleSol would claim (u64 LE) as return data
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Pod, Zeroable)]
+pub struct MMPreviewRecycle {
+    /// Auth ID of the managed miner
+    pub auth_id: [u8; 8],
+}
+
+instruction!(Instructions, MMPreviewRecycle);
+
+/// Preview how much SOL recycling the miner at `auth_id` would claim, without moving lamports.
+/// Meant for simulateTransaction; read the amount with `preview_recycle_amount`.
+pub fn mm_preview_recycle(authority: Pubkey, manager: Pubkey, auth_id: u64) -> Instruction {
+    let (managed_miner_auth_address, _) = managed_miner_auth_pda(manager, auth_id);
+    let ore_miner_address = miner_pda(managed_miner_auth_address).0;
+
+    Instruction {
+        program_id: crate::id(),
+        accounts: vec![
+            AccountMeta::new_readonly(authority, true),                   // 0: signer (fee payer)
+            AccountMeta::new_readonly(manager, false),                    // 1: manager
+            AccountMeta::new_readonly(managed_miner_auth_address, false), // 2: managed_miner_auth PDA
+            AccountMeta::new_readonly(ore_miner_address, false),          // 3: ore_miner
+        ],
+        data: MMPreviewRecycle {
+            auth_id: auth_id.to_le_bytes(),
+        }.to_bytes(),
+    }
+}
+
+/// Parses the return data of an MMPreviewRecycle call into recyclable lamports
+pub fn preview_recycle_amount(data: &[u8]) -> Option<u64> {
+    Some(u64::from_le_bytes(data.try_into().ok()?))
+}
+
+// ============================================================================
+// MMStatus Instruction
+// ============================================================================
+
+/// MMStatus instruction data
+/// Read-only: sets the managed miner's `view::MMStatus` as return data
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Pod, Zeroable)]
+pub struct MMStatus {
+    /// Auth ID of the managed miner
+    pub auth_id: [u8; 8],
+}
+
+instruction!(Instructions, MMStatus);
+
+/// Read the balance and checkpoint status of the miner at `auth_id` in one call.
+/// Meant for simulateTransaction; decode the result with `view::MMStatus::from_return_data`.
+pub fn mm_status(authority: Pubkey, manager: Pubkey, auth_id: u64) -> Instruction {
+    let (managed_miner_auth_address, _) = managed_miner_auth_pda(manager, auth_id);
+    let ore_miner_address = miner_pda(managed_miner_auth_address).0;
+
+    Instruction {
+        program_id: crate::id(),
+        accounts: vec![
+            AccountMeta::new_readonly(authority, true),                   // 0: signer (fee payer)
+            AccountMeta::new_readonly(manager, false),                    // 1: manager
+            AccountMeta::new_readonly(managed_miner_auth_address, false), // 2: managed_miner_auth PDA
+            AccountMeta::new_readonly(ore_miner_address, false),          // 3: ore_miner
+        ],
+        data: MMStatus {
+            auth_id: auth_id.to_le_bytes(),
+        }.to_bytes(),
+    }
+}
+
+// ============================================================================
+// BulkUpdateDeployerFees Instruction
+// ============================================================================
+
+/// BulkUpdateDeployerFees instruction data
+/// Sets the actual fees on many deployers at once (deploy authority only)
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Pod, Zeroable)]
+pub struct BulkUpdateDeployerFees {
+    /// Actual bps fee to charge
+    pub bps_fee: [u8; 8],
+    /// Actual flat fee to charge
+    pub flat_fee: [u8; 8],
+}
+
+instruction!(Instructions, BulkUpdateDeployerFees);
+
+/// Set `bps_fee` / `flat_fee` on the deployers of every manager in `managers` in one instruction,
+/// e.g. to bring fees that drifted from the managers' expected fees back in line. Only deployers
+/// whose deploy_authority is `signer` change; the expected fees stay the managers' to set.
+pub fn bulk_update_deployer_fees(signer: Pubkey, managers: &[Pubkey], bps_fee: u64, flat_fee: u64) -> Instruction {
+    let mut accounts = vec![AccountMeta::new_readonly(signer, true)]; // 0: signer (deploy authority)
+    accounts.extend(managers.iter().map(|&manager| AccountMeta::new(deployer_pda(manager).0, false))); // 1..: deployers
+
+    Instruction {
+        program_id: crate::id(),
+        accounts,
+        data: BulkUpdateDeployerFees {
+            bps_fee: bps_fee.to_le_bytes(),
+            flat_fee: flat_fee.to_le_bytes(),
+        }.to_bytes(),
+    }
+}
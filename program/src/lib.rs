@@ -14,6 +14,10 @@ pub mod consts;
 pub mod ore_api;
 pub mod entropy_api;
 pub mod validation;
+pub mod squares;
+pub mod compute;
+pub mod events;
+pub mod view;
 
 declare_id!("8jaLKWLJAj5jVCZbxpe3zRUvLB3LD48MRtaQ2AjfCfxa");
 
@@ -36,74 +40,8 @@ pub fn process_instruction(
     let instruction =
         Instructions::try_from(*instruction).or(Err(ProgramError::InvalidInstructionData))?;
 
-    match instruction {
-        Instructions::CreateManager => {
-            process_create_manager::process_create_manager(accounts, data)?;
-        }
-        Instructions::MMDeploy => {
-            process_mm_deploy::process_mm_deploy(accounts, data)?;
-        }
-        Instructions::MMCheckpoint => {
-            process_checkpoint::process_checkpoint(accounts, data)?;
-        }
-        Instructions::MMClaimSOL => {
-            process_claim_sol::process_claim_sol(accounts, data)?;
-        }
-        Instructions::MMClaimORE => {
-            process_claim_ore::process_claim_ore(accounts, data)?;
-        }
-        Instructions::CreateDeployer => {
-            process_create_deployer::process_create_deployer(accounts, data)?;
-        }
-        Instructions::UpdateDeployer => {
-            process_update_deployer::process_update_deployer(accounts, data)?;
-        }
-        Instructions::MMAutodeploy => {
-            process_mm_autodeploy::process_mm_autodeploy(accounts, data)?;
-        }
-        Instructions::DepositAutodeployBalance => {
-            process_deposit_autodeploy_balance::process_deposit_autodeploy_balance(accounts, data)?;
-        }
-        Instructions::RecycleSol => {
-            process_recycle_sol::process_recycle_sol(accounts, data)?;
-        }
-        Instructions::WithdrawAutodeployBalance => {
-            process_withdraw_autodeploy_balance::process_withdraw_autodeploy_balance(accounts, data)?;
-        }
-        Instructions::MMAutocheckpoint => {
-            process_mm_autocheckpoint::process_mm_autocheckpoint(accounts, data)?;
-        }
-        Instructions::MMFullAutodeploy => {
-            process_mm_full_autodeploy::process_mm_full_autodeploy(accounts, data)?;
-        }
-        Instructions::TransferManager => {
-            process_transfer_manager::process_transfer_manager(accounts, data)?;
-        }
-        Instructions::MMCreateMiner => {
-            process_mm_create_miner::process_mm_create_miner(accounts, data)?;
-        }
-        Instructions::WithdrawTokens => {
-            process_withdraw_tokens::process_withdraw_tokens(accounts, data)?;
-        }
-        Instructions::CreateStratDeployer => {
-            process_create_strat_deployer::process_create_strat_deployer(accounts, data)?;
-        }
-        Instructions::UpdateStratDeployer => {
-            process_update_strat_deployer::process_update_strat_deployer(accounts, data)?;
-        }
-        Instructions::MMStratAutodeploy => {
-            process_mm_strat_autodeploy::process_mm_strat_autodeploy(accounts, data)?;
-        }
-        Instructions::MMStratFullAutodeploy => {
-            process_mm_strat_full_autodeploy::process_mm_strat_full_autodeploy(accounts, data)?;
-        }
-        Instructions::MMStratAutocheckpoint => {
-            process_mm_strat_autocheckpoint::process_mm_strat_autocheckpoint(accounts, data)?;
-        }
-        Instructions::RecycleStratSol => {
-            process_recycle_strat_sol::process_recycle_strat_sol(accounts, data)?;
-        }
-    }
+    let handler = handler(instruction).ok_or(ProgramError::InvalidInstructionData)?;
+    handler(accounts, data)?;
 
     Ok(())
 }
@@ -1,6 +1,6 @@
 use solana_program::pubkey;
 use serde::{Serialize, Deserialize};
-use spl_associated_token_account::get_associated_token_address;
+use spl_associated_token_account::{get_associated_token_address, get_associated_token_address_with_program_id};
 use steel::*;
 
 use crate::entropy_api;
@@ -31,9 +31,15 @@ pub const STAKE: &[u8] = b"stake";
 /// The address of the treasury account.
 pub const TREASURY_ADDRESS: Pubkey = pubkey!("45db2FSR4mcXdSVVZbKbwojU6uYDpMyhpEi7cC8nHaWG");
 
+/// The address of the treasury's ORE token account (ATA of the treasury for the mint).
+pub const TREASURY_TOKENS_ADDRESS: Pubkey = pubkey!("GwZS8yBuPPkPgY4uh7eEhHN5EEdpkf7EBZ1za6nuP3wF");
+
 /// The address to indicate automation is permissionless.
 pub const EXECUTOR_ADDRESS: Pubkey = pubkey!("executor11111111111111111111111111111111112");
 
+/// The upgradeable loader that owns the ORE program and its program data account.
+pub const BPF_LOADER_UPGRADEABLE: Pubkey = pubkey!("BPFLoaderUpgradeab1e11111111111111111111111");
+
 pub const INTERMISSION_SLOTS: u64 = 35;
 
 /// The checkpoint fee that miners must hold (in lamports)
@@ -78,8 +84,25 @@ pub fn stake_pda(authority: Pubkey) -> (Pubkey, u8) {
     Pubkey::find_program_address(&[STAKE, &authority.to_bytes()], &PROGRAM_ID)
 }
 
+/// The ORE program's program data account, which records the slot ORE was last deployed at
+pub fn program_data_pda() -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[PROGRAM_ID.as_ref()], &BPF_LOADER_UPGRADEABLE)
+}
+
+/// Last-deployed slot from loader program data (u32 variant 3, u64 slot, then the authority)
+/// None if `data` isn't a program data account
+pub fn program_data_slot(data: &[u8]) -> Option<u64> {
+    if data.get(0..4)? != 3u32.to_le_bytes() {
+        return None;
+    }
+    Some(u64::from_le_bytes(data.get(4..12)?.try_into().ok()?))
+}
+
+/// The treasury's ORE token account, derived as the treasury ATA for the mint.
+/// Off-chain builders use this; on-chain checks compare against `TREASURY_TOKENS_ADDRESS`
+/// to skip the PDA search, and the tests keep the two equal.
 pub fn treasury_tokens_address() -> Pubkey {
-    spl_associated_token_account::get_associated_token_address(&TREASURY_ADDRESS, &MINT_ADDRESS)
+    get_associated_token_address(&TREASURY_ADDRESS, &MINT_ADDRESS)
 }
 
 #[repr(u8)]
@@ -216,6 +239,8 @@ pub struct Round {
     pub deployed: [u64; 25],
 
     /// The hash of the end slot, provided by solana, used for random number generation.
+    /// Stays zeroed for the whole live round (it's written at reset), so it can't gate deploys;
+    /// entropy readiness for a live round is the entropy `Var` commit.
     pub slot_hash: [u8; 32],
 
     /// The count of miners on each square.
@@ -408,6 +433,8 @@ impl Miner {
 
 account!(OreAccount, Miner);
 
+/// ORE's global config. Carries no per-square deploy minimum, so
+/// evore has nothing on-chain to clamp deploys to; EV strategies rely on the caller's `min_bet`.
 #[repr(C)]
 #[derive(Clone, Copy, Debug, PartialEq, Pod, Zeroable, Serialize, Deserialize)]
 pub struct Config {
@@ -721,11 +748,17 @@ pub fn claim_sol(signer: Pubkey) -> Instruction {
 pub struct ClaimORE {}
 instruction!(OreInstruction, ClaimORE);
 pub fn claim_ore(signer: Pubkey) -> Instruction {
+    claim_ore_with_token_program(signer, spl_token::ID)
+}
+
+/// `claim_ore` with the recipient ATA and token program account taken from `token_program`,
+/// for when the mint is owned by Token-2022 rather than classic SPL Token.
+pub fn claim_ore_with_token_program(signer: Pubkey, token_program: Pubkey) -> Instruction {
     let board_address = board_pda().0;
     let miner_address = miner_pda(signer).0;
     let treasury_address = treasury_pda().0;
-    let treasury_tokens_address = get_associated_token_address(&treasury_address, &MINT_ADDRESS);
-    let recipient_address = get_associated_token_address(&signer, &MINT_ADDRESS);
+    let treasury_tokens_address = treasury_tokens_address();
+    let recipient_address = get_associated_token_address_with_program_id(&signer, &MINT_ADDRESS, &token_program);
 
     Instruction {
         program_id: PROGRAM_ID,
@@ -738,7 +771,7 @@ pub fn claim_ore(signer: Pubkey) -> Instruction {
             AccountMeta::new(treasury_address, false),
             AccountMeta::new(treasury_tokens_address, false),
             AccountMeta::new_readonly(system_program::ID, false),
-            AccountMeta::new_readonly(spl_token::ID, false),
+            AccountMeta::new_readonly(token_program, false),
             AccountMeta::new_readonly(spl_associated_token_account::ID, false),
             AccountMeta::new_readonly(PROGRAM_ID, false),
         ],
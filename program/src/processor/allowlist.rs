@@ -0,0 +1,38 @@
+use solana_program::{account_info::AccountInfo, program_error::ProgramError};
+use steel::*;
+
+use crate::{
+    consts::DEPLOY_AUTHORITY_ALLOWLIST,
+    error::EvoreError,
+    state::DeployAuthorityAllowlist,
+};
+
+/// Rejects `deploy_authority` when the manager's allowlist exists and doesn't list it.
+/// The allowlist account must be the manager's PDA even when it hasn't been created.
+pub(crate) fn check_deploy_authority_allowed(
+    manager_key: &Pubkey,
+    allowlist_info: &AccountInfo,
+    deploy_authority: &Pubkey,
+) -> Result<(), ProgramError> {
+    let (allowlist_pda, _) = Pubkey::find_program_address(
+        &[DEPLOY_AUTHORITY_ALLOWLIST, manager_key.as_ref()],
+        &crate::id(),
+    );
+
+    if allowlist_pda != *allowlist_info.key {
+        return Err(EvoreError::InvalidPDA.into());
+    }
+
+    // No allowlist: any deploy authority may be used
+    if allowlist_info.data_is_empty() {
+        return Ok(());
+    }
+
+    let allowlist = allowlist_info.as_account::<DeployAuthorityAllowlist>(&crate::id())?;
+
+    if !allowlist.allows(deploy_authority) {
+        return Err(EvoreError::AuthorityNotAllowlisted.into());
+    }
+
+    Ok(())
+}
@@ -1,3 +1,7 @@
+use solana_program::{account_info::AccountInfo, entrypoint::ProgramResult};
+
+use crate::instruction::Instructions;
+
 pub mod process_create_manager;
 pub mod process_mm_deploy;
 pub mod process_checkpoint;
@@ -20,4 +24,68 @@ pub mod process_mm_strat_autodeploy;
 pub mod process_mm_strat_autocheckpoint;
 pub mod process_mm_strat_full_autodeploy;
 pub mod process_recycle_strat_sol;
+pub mod process_add_deploy_authority;
+pub mod process_remove_deploy_authority;
+pub mod process_set_deploy_schedule;
+pub mod process_migrate_auth_id;
+pub mod process_migrate_manager;
+pub mod process_get_program_info;
+pub mod process_close_manager;
+pub mod process_set_max_deploy_per_tx;
+pub mod process_initiate_transfer_manager;
+pub mod process_accept_transfer_manager;
+pub mod process_mm_preview_recycle;
+pub mod process_mm_status;
+pub mod process_bulk_update_deployer_fees;
+pub(crate) mod allowlist;
 pub(crate) mod strategy_dispatch;
+
+/// Instruction processor: `(accounts, data)` with the discriminator byte already stripped
+pub type Handler = fn(&[AccountInfo], &[u8]) -> ProgramResult;
+
+/// Routing table for `process_instruction`, one registration per `Instructions` variant
+pub const HANDLERS: &[(Instructions, Handler)] = &[
+    (Instructions::CreateManager, process_create_manager::process_create_manager),
+    (Instructions::MMDeploy, process_mm_deploy::process_mm_deploy),
+    (Instructions::MMCheckpoint, process_checkpoint::process_checkpoint),
+    (Instructions::MMClaimSOL, process_claim_sol::process_claim_sol),
+    (Instructions::MMClaimORE, process_claim_ore::process_claim_ore),
+    (Instructions::CreateDeployer, process_create_deployer::process_create_deployer),
+    (Instructions::UpdateDeployer, process_update_deployer::process_update_deployer),
+    (Instructions::MMAutodeploy, process_mm_autodeploy::process_mm_autodeploy),
+    (Instructions::DepositAutodeployBalance, process_deposit_autodeploy_balance::process_deposit_autodeploy_balance),
+    (Instructions::RecycleSol, process_recycle_sol::process_recycle_sol),
+    (Instructions::WithdrawAutodeployBalance, process_withdraw_autodeploy_balance::process_withdraw_autodeploy_balance),
+    (Instructions::MMAutocheckpoint, process_mm_autocheckpoint::process_mm_autocheckpoint),
+    (Instructions::MMFullAutodeploy, process_mm_full_autodeploy::process_mm_full_autodeploy),
+    (Instructions::TransferManager, process_transfer_manager::process_transfer_manager),
+    (Instructions::MMCreateMiner, process_mm_create_miner::process_mm_create_miner),
+    (Instructions::WithdrawTokens, process_withdraw_tokens::process_withdraw_tokens),
+    (Instructions::CreateStratDeployer, process_create_strat_deployer::process_create_strat_deployer),
+    (Instructions::UpdateStratDeployer, process_update_strat_deployer::process_update_strat_deployer),
+    (Instructions::MMStratAutodeploy, process_mm_strat_autodeploy::process_mm_strat_autodeploy),
+    (Instructions::MMStratFullAutodeploy, process_mm_strat_full_autodeploy::process_mm_strat_full_autodeploy),
+    (Instructions::MMStratAutocheckpoint, process_mm_strat_autocheckpoint::process_mm_strat_autocheckpoint),
+    (Instructions::RecycleStratSol, process_recycle_strat_sol::process_recycle_strat_sol),
+    (Instructions::AddDeployAuthority, process_add_deploy_authority::process_add_deploy_authority),
+    (Instructions::RemoveDeployAuthority, process_remove_deploy_authority::process_remove_deploy_authority),
+    (Instructions::SetDeploySchedule, process_set_deploy_schedule::process_set_deploy_schedule),
+    (Instructions::MigrateAuthId, process_migrate_auth_id::process_migrate_auth_id),
+    (Instructions::MigrateManager, process_migrate_manager::process_migrate_manager),
+    (Instructions::GetProgramInfo, process_get_program_info::process_get_program_info),
+    (Instructions::CloseManager, process_close_manager::process_close_manager),
+    (Instructions::SetMaxDeployPerTx, process_set_max_deploy_per_tx::process_set_max_deploy_per_tx),
+    (Instructions::InitiateTransferManager, process_initiate_transfer_manager::process_initiate_transfer_manager),
+    (Instructions::AcceptTransferManager, process_accept_transfer_manager::process_accept_transfer_manager),
+    (Instructions::MMPreviewRecycle, process_mm_preview_recycle::process_mm_preview_recycle),
+    (Instructions::MMStatus, process_mm_status::process_mm_status),
+    (Instructions::BulkUpdateDeployerFees, process_bulk_update_deployer_fees::process_bulk_update_deployer_fees),
+];
+
+/// Processor registered for an instruction, None if the variant was never registered
+pub fn handler(instruction: Instructions) -> Option<Handler> {
+    HANDLERS
+        .iter()
+        .find(|(registered, _)| *registered == instruction)
+        .map(|(_, handler)| *handler)
+}
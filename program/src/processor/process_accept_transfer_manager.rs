@@ -0,0 +1,42 @@
+use solana_program::{account_info::AccountInfo, program_error::ProgramError, pubkey::Pubkey};
+use steel::*;
+
+use crate::{error::EvoreError, state::Manager};
+
+/// Process AcceptTransferManager instruction
+/// Promotes the pending authority once it signs, completing the two-step transfer
+pub fn process_accept_transfer_manager(
+    accounts: &[AccountInfo],
+    _instruction_data: &[u8],
+) -> Result<(), ProgramError> {
+    let [
+        signer,               // 0: signer (pending authority)
+        manager_account_info, // 1: manager
+    ] = accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+
+    if !signer.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    if !manager_account_info.is_writable {
+        return Err(ProgramError::InvalidAccountData);
+    }
+
+    if manager_account_info.data_is_empty() {
+        return Err(EvoreError::ManagerNotInitialized.into());
+    }
+
+    let manager = manager_account_info
+        .as_account_mut::<Manager>(&crate::id())?;
+
+    if manager.pending_authority == Pubkey::default() || manager.pending_authority != *signer.key {
+        return Err(EvoreError::NotAuthorized.into());
+    }
+
+    manager.authority = manager.pending_authority;
+    manager.pending_authority = Pubkey::default();
+
+    Ok(())
+}
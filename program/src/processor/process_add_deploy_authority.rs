@@ -0,0 +1,103 @@
+use solana_program::{
+    account_info::AccountInfo, program_error::ProgramError, system_program,
+};
+use steel::*;
+
+use crate::{
+    consts::{DEPLOY_AUTHORITY_ALLOWLIST, MAX_ALLOWLISTED_AUTHORITIES},
+    error::EvoreError,
+    state::{DeployAuthorityAllowlist, EvoreAccount, Manager},
+};
+
+pub fn process_add_deploy_authority(
+    accounts: &[AccountInfo],
+    _instruction_data: &[u8],
+) -> Result<(), ProgramError> {
+    let [
+        signer,
+        manager_account_info,
+        allowlist_account_info,
+        deploy_authority_info,
+        system_program_info,
+    ] = accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+
+    // Verify signer
+    if !signer.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    // Verify system program
+    if *system_program_info.key != system_program::id() {
+        return Err(ProgramError::IncorrectProgramId);
+    }
+
+    // Verify manager is initialized and signer is the authority
+    if manager_account_info.data_is_empty() {
+        return Err(EvoreError::ManagerNotInitialized.into());
+    }
+
+    let manager = Manager::load(manager_account_info)?;
+
+    if manager.authority != *signer.key {
+        return Err(EvoreError::NotAuthorized.into());
+    }
+
+    // Derive and verify allowlist PDA
+    let (allowlist_pda, allowlist_bump) = Pubkey::find_program_address(
+        &[DEPLOY_AUTHORITY_ALLOWLIST, manager_account_info.key.as_ref()],
+        &crate::id(),
+    );
+
+    if allowlist_pda != *allowlist_account_info.key {
+        return Err(EvoreError::InvalidPDA.into());
+    }
+
+    // Create the allowlist on first use
+    if allowlist_account_info.data_is_empty() {
+        let allowlist_size = 8 + std::mem::size_of::<DeployAuthorityAllowlist>();
+        let rent = solana_program::rent::Rent::get()?;
+        let lamports = rent.minimum_balance(allowlist_size);
+
+        solana_program::program::invoke_signed(
+            &solana_program::system_instruction::create_account(
+                signer.key,
+                allowlist_account_info.key,
+                lamports,
+                allowlist_size as u64,
+                &crate::id(),
+            ),
+            &[signer.clone(), allowlist_account_info.clone(), system_program_info.clone()],
+            &[&[DEPLOY_AUTHORITY_ALLOWLIST, manager_account_info.key.as_ref(), &[allowlist_bump]]],
+        )?;
+
+        let allowlist = DeployAuthorityAllowlist {
+            manager_key: *manager_account_info.key,
+            count: 0,
+            authorities: [Pubkey::default(); MAX_ALLOWLISTED_AUTHORITIES],
+        };
+
+        let mut data = allowlist_account_info.try_borrow_mut_data()?;
+        let discr = (EvoreAccount::DeployAuthorityAllowlist as u64).to_le_bytes();
+        data[..8].copy_from_slice(&discr);
+        data[8..8 + std::mem::size_of::<DeployAuthorityAllowlist>()].copy_from_slice(allowlist.to_bytes());
+    }
+
+    let allowlist = allowlist_account_info.as_account_mut::<DeployAuthorityAllowlist>(&crate::id())?;
+
+    // Adding an authority that's already listed is a no-op
+    if allowlist.entries().contains(deploy_authority_info.key) {
+        return Ok(());
+    }
+
+    let count = allowlist.count as usize;
+    if count >= MAX_ALLOWLISTED_AUTHORITIES {
+        return Err(EvoreError::AllowlistFull.into());
+    }
+
+    allowlist.authorities[count] = *deploy_authority_info.key;
+    allowlist.count += 1;
+
+    Ok(())
+}
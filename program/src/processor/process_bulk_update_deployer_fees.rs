@@ -0,0 +1,59 @@
+use solana_program::{
+    account_info::AccountInfo, msg, program_error::ProgramError,
+};
+use steel::*;
+
+use crate::{
+    error::EvoreError,
+    instruction::BulkUpdateDeployerFees,
+    state::{deployer_pda, Deployer},
+};
+
+/// Process BulkUpdateDeployerFees instruction
+/// Sets the actual bps_fee / flat_fee on every deployer passed after the signer. Deployers the
+/// signer isn't the deploy_authority of are skipped, so a stale list can't fail the batch.
+pub fn process_bulk_update_deployer_fees(
+    accounts: &[AccountInfo],
+    instruction_data: &[u8],
+) -> Result<(), ProgramError> {
+    let args = BulkUpdateDeployerFees::try_from_bytes(instruction_data)?;
+    let bps_fee = u64::from_le_bytes(args.bps_fee);
+    let flat_fee = u64::from_le_bytes(args.flat_fee);
+
+    let [
+        signer, // 0: signer (deploy authority)
+        deployer_account_infos @ .., // 1..: deployers
+    ] = accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+
+    if !signer.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    if deployer_account_infos.is_empty() {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    }
+
+    for deployer_account_info in deployer_account_infos {
+        if deployer_account_info.data_is_empty() {
+            return Err(EvoreError::DeployerNotInitialized.into());
+        }
+
+        let deployer = deployer_account_info.as_account_mut::<Deployer>(&crate::id())?;
+
+        if deployer_pda(deployer.manager_key).0 != *deployer_account_info.key {
+            return Err(EvoreError::InvalidPDA.into());
+        }
+
+        if deployer.deploy_authority != *signer.key {
+            msg!("Skipping deployer {}: not its deploy authority", deployer_account_info.key);
+            continue;
+        }
+
+        deployer.bps_fee = bps_fee;
+        deployer.flat_fee = flat_fee;
+    }
+
+    Ok(())
+}
@@ -52,8 +52,7 @@ pub fn process_checkpoint(
         return Err(ProgramError::IncorrectProgramId);
     }
 
-    let manager = manager_account_info
-        .as_account::<Manager>(&crate::id())?;
+    let manager = Manager::load(manager_account_info)?;
 
     if manager.authority != *signer.key {
         return Err(EvoreError::NotAuthorized.into());
@@ -4,7 +4,8 @@ use solana_program::{
 use steel::*;
 
 use crate::{
-    error::EvoreError, instruction::MMClaimORE, ore_api::{self}, state::Manager
+    error::EvoreError, instruction::MMClaimORE, ore_api::{self}, state::Manager,
+    validation::validate_token_program,
 };
 
 pub fn process_claim_ore(
@@ -14,6 +15,12 @@ pub fn process_claim_ore(
     let args = MMClaimORE::try_from_bytes(instruction_data)?;
     let auth_id = u64::from_le_bytes(args.auth_id);
 
+    // Optional trailing account: owner of the destination ATA (defaults to the signer)
+    let (accounts, destination_owner) = match accounts.len() {
+        15 => (&accounts[..14], Some(&accounts[14])),
+        _ => (accounts, None),
+    };
+
     let [
             signer,
             manager_account_info,
@@ -65,25 +72,31 @@ pub fn process_claim_ore(
         return Err(EvoreError::InvalidPDA.into());
     }
 
-    if *system_program.key != solana_program::system_program::id() {
-        return Err(ProgramError::IncorrectProgramId);
+    if *treasury_account_info.key != ore_api::TREASURY_ADDRESS
+        || *treasury_tokens_account_info.key != ore_api::TREASURY_TOKENS_ADDRESS
+    {
+        return Err(EvoreError::InvalidTreasury.into());
     }
 
-    if *spl_program.key != spl_token::id() {
+    if *system_program.key != solana_program::system_program::id() {
         return Err(ProgramError::IncorrectProgramId);
     }
 
+    // Classic SPL Token or Token-2022, whichever owns the mint
+    validate_token_program(mint_account_info, spl_program)?;
+
     if *spl_ata_program.key != spl_associated_token_account::id() {
         return Err(ProgramError::IncorrectProgramId);
     }
 
-    let manager = manager_account_info
-        .as_account::<Manager>(&crate::id())?;
+    let manager = Manager::load(manager_account_info)?;
 
     if manager.authority != *signer.key {
         return Err(EvoreError::NotAuthorized.into());
     }
 
+    let destination_owner = destination_owner.unwrap_or(signer);
+
     // Use create_program_address with bump from instruction data for deterministic CU usage
     let managed_miner_auth_pda = Pubkey::create_program_address(
         &[
@@ -129,8 +142,9 @@ pub fn process_claim_ore(
     }
 
     solana_program::program::invoke_signed(
-        &ore_api::claim_ore(
+        &ore_api::claim_ore_with_token_program(
             managed_miner_auth_key,
+            *spl_program.key,
         ),
         &claim_ore_accounts,
         &[&[
@@ -145,7 +159,7 @@ pub fn process_claim_ore(
     if signer_recipient_account_info.data_is_empty() {
         create_associated_token_account(
             signer,
-            signer,
+            destination_owner,
             signer_recipient_account_info,
             mint_account_info,
             system_program,
@@ -153,17 +167,21 @@ pub fn process_claim_ore(
             spl_ata_program,
         )?;
     } else {
-        signer_recipient_account_info.as_associated_token_account(signer.key, mint_account_info.key)?;
+        signer_recipient_account_info.as_associated_token_account(destination_owner.key, mint_account_info.key)?;
     }
 
     let managed_auth_tokens = recipient_account_info.as_associated_token_account(&managed_miner_auth_key, &mint_account_info.key)?;
 
-    transfer_signed_with_bump(
+    let decimals = mint_account_info.as_mint()?.decimals();
+
+    transfer_checked_signed_with_bump(
         managed_miner_auth_account_info,
         recipient_account_info,
+        mint_account_info,
         signer_recipient_account_info,
         spl_program,
         managed_auth_tokens.amount(),
+        decimals,
         &[
             crate::consts::MANAGED_MINER_AUTH,
             manager_account_info.key.as_ref(),
@@ -1,7 +1,6 @@
 use solana_program::{
     account_info::AccountInfo, program_error::ProgramError, pubkey::Pubkey,
 };
-use steel::*;
 
 use crate::{
     error::EvoreError, instruction::MMClaimSOL, ore_api::{self}, state::Manager
@@ -54,8 +53,7 @@ pub fn process_claim_sol(
         return Err(ProgramError::IncorrectProgramId);
     }
 
-    let manager = manager_account_info
-        .as_account::<Manager>(&crate::id())?;
+    let manager = Manager::load(manager_account_info)?;
 
     if manager.authority != *signer.key {
         return Err(EvoreError::NotAuthorized.into());
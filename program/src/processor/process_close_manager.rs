@@ -0,0 +1,112 @@
+use solana_program::{
+    account_info::AccountInfo, program_error::ProgramError, pubkey::Pubkey,
+};
+use steel::*;
+
+use crate::{
+    error::EvoreError,
+    state::{deploy_authority_allowlist_pda, deploy_schedule_pda, deployer_pda, strategy_deployer_pda, Manager},
+};
+
+/// Process CloseManager instruction
+/// Closes the manager and returns its rent to the authority. Every managed_miner_auth
+/// PDA passed along (auth_ids 0, 1, 2, ... in order, at least auth_id 0) must be empty,
+/// so no balance is stranded behind a manager that no longer exists. The manager's
+/// Deployer, StrategyDeployer, allowlist and schedule PDAs are closed along with it.
+pub fn process_close_manager(
+    accounts: &[AccountInfo],
+    _instruction_data: &[u8],
+) -> Result<(), ProgramError> {
+    let [
+        signer,                           // 0: signer (manager authority, receives rent)
+        manager_account_info,             // 1: manager
+        deployer_account_info,            // 2: deployer PDA
+        strat_deployer_account_info,      // 3: strategy deployer PDA
+        allowlist_account_info,           // 4: deploy authority allowlist PDA
+        schedule_account_info,            // 5: deploy schedule PDA
+        managed_miner_auth_accounts @ .., // 6..: managed_miner_auth PDAs for auth_ids 0..
+    ] = accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+
+    // auth_id 0 always exists, so it can never be left out
+    if managed_miner_auth_accounts.is_empty() {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    }
+
+    if !signer.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    if !signer.is_writable {
+        return Err(ProgramError::InvalidAccountData);
+    }
+
+    if !manager_account_info.is_writable {
+        return Err(ProgramError::InvalidAccountData);
+    }
+
+    if manager_account_info.data_is_empty() {
+        return Err(EvoreError::ManagerNotInitialized.into());
+    }
+
+    let manager = Manager::load(manager_account_info)?;
+
+    if manager.authority != *signer.key {
+        return Err(EvoreError::NotAuthorized.into());
+    }
+
+    for (auth_id, managed_miner_auth_account_info) in managed_miner_auth_accounts.iter().enumerate() {
+        let (managed_miner_auth_pda, _) = Pubkey::find_program_address(
+            &[
+                crate::consts::MANAGED_MINER_AUTH,
+                manager_account_info.key.as_ref(),
+                &(auth_id as u64).to_le_bytes(),
+            ],
+            &crate::id(),
+        );
+
+        if managed_miner_auth_pda != *managed_miner_auth_account_info.key {
+            return Err(EvoreError::InvalidPDA.into());
+        }
+
+        if managed_miner_auth_account_info.lamports() > 0 {
+            return Err(EvoreError::ManagerNotEmpty.into());
+        }
+    }
+
+    let manager_key = *manager_account_info.key;
+    close_if_open(deployer_account_info, deployer_pda(manager_key).0, signer)?;
+    close_if_open(strat_deployer_account_info, strategy_deployer_pda(manager_key).0, signer)?;
+    close_if_open(allowlist_account_info, deploy_authority_allowlist_pda(manager_key).0, signer)?;
+    close_if_open(schedule_account_info, deploy_schedule_pda(manager_key).0, signer)?;
+
+    // Zero the data before handing the account back to the system program
+    manager_account_info.try_borrow_mut_data()?.fill(0);
+    manager_account_info.close(signer)?;
+
+    Ok(())
+}
+
+/// Closes a per-manager PDA into `signer` if it was ever created; a PDA that was never
+/// initialized is left alone.
+fn close_if_open<'a>(
+    account_info: &AccountInfo<'a>,
+    expected_address: Pubkey,
+    signer: &AccountInfo<'a>,
+) -> Result<(), ProgramError> {
+    if *account_info.key != expected_address {
+        return Err(EvoreError::InvalidPDA.into());
+    }
+
+    if account_info.owner != &crate::id() {
+        return Ok(());
+    }
+
+    if !account_info.is_writable {
+        return Err(ProgramError::InvalidAccountData);
+    }
+
+    account_info.try_borrow_mut_data()?.fill(0);
+    account_info.close(signer)
+}
@@ -6,6 +6,7 @@ use steel::*;
 use crate::{
     consts::DEPLOYER,
     error::EvoreError,
+    processor::allowlist::check_deploy_authority_allowed,
     instruction::CreateDeployer,
     state::{Deployer, EvoreAccount, Manager},
 };
@@ -26,6 +27,7 @@ pub fn process_create_deployer(
         deployer_account_info,
         deploy_authority_info,
         system_program_info,
+        allowlist_account_info,
     ] = accounts else {
         return Err(ProgramError::NotEnoughAccountKeys);
     };
@@ -45,7 +47,7 @@ pub fn process_create_deployer(
         return Err(EvoreError::ManagerNotInitialized.into());
     }
 
-    let manager = manager_account_info.as_account::<Manager>(&crate::id())?;
+    let manager = Manager::load(manager_account_info)?;
 
     if manager.authority != *signer.key {
         return Err(EvoreError::NotAuthorized.into());
@@ -56,6 +58,13 @@ pub fn process_create_deployer(
         return Err(EvoreError::DeployerAlreadyInitialized.into());
     }
 
+    // Verify deploy_authority is allowed by the manager's allowlist (if it has one)
+    check_deploy_authority_allowed(
+        manager_account_info.key,
+        allowlist_account_info,
+        deploy_authority_info.key,
+    )?;
+
     // Derive and verify deployer PDA
     let (deployer_pda, deployer_bump) = Pubkey::find_program_address(
         &[DEPLOYER, manager_account_info.key.as_ref()],
@@ -6,7 +6,9 @@ use steel::*;
 use crate::{
     consts::STRATEGY_DEPLOYER,
     error::EvoreError,
+    processor::allowlist::check_deploy_authority_allowed,
     instruction::CreateStratDeployer,
+    squares::{validate_squares_count, validate_squares_mask},
     state::{EvoreAccount, Manager, StrategyDeployer},
     validation::{StrategyType, validate_strategy_data},
 };
@@ -21,6 +23,9 @@ pub fn process_create_strat_deployer(
     let max_per_round = u64::from_le_bytes(args.max_per_round);
     let strategy_type_raw = args.strategy_type;
     let strategy_data = args.strategy_data;
+    let max_squares_per_deploy = args.max_squares_per_deploy;
+    let blocked_squares_mask = args.blocked_squares_mask;
+    let max_loss_per_round = u64::from_le_bytes(args.max_loss_per_round);
 
     let [
         signer,
@@ -28,6 +33,7 @@ pub fn process_create_strat_deployer(
         strat_deployer_account_info,
         deploy_authority_info,
         system_program_info,
+        allowlist_account_info,
     ] = accounts else {
         return Err(ProgramError::NotEnoughAccountKeys);
     };
@@ -44,7 +50,7 @@ pub fn process_create_strat_deployer(
         return Err(EvoreError::ManagerNotInitialized.into());
     }
 
-    let manager = manager_account_info.as_account::<Manager>(&crate::id())?;
+    let manager = Manager::load(manager_account_info)?;
 
     if manager.authority != *signer.key {
         return Err(EvoreError::NotAuthorized.into());
@@ -54,8 +60,16 @@ pub fn process_create_strat_deployer(
         return Err(EvoreError::AlreadyInitialized.into());
     }
 
+    check_deploy_authority_allowed(
+        manager_account_info.key,
+        allowlist_account_info,
+        deploy_authority_info.key,
+    )?;
+
     let strategy_type = StrategyType::try_from(strategy_type_raw)?;
     validate_strategy_data(strategy_type, &strategy_data)?;
+    validate_squares_count(max_squares_per_deploy as u64)?;
+    validate_squares_mask(u32::from_le_bytes(blocked_squares_mask) as u64)?;
 
     let (strat_deployer_pda, strat_deployer_bump) = Pubkey::find_program_address(
         &[STRATEGY_DEPLOYER, manager_account_info.key.as_ref()],
@@ -92,7 +106,10 @@ pub fn process_create_strat_deployer(
         max_per_round,
         strategy_type: strategy_type_raw,
         strategy_data,
-        _padding: [0u8; 7],
+        max_squares_per_deploy,
+        blocked_squares_mask,
+        _padding: [0u8; 2],
+        max_loss_per_round,
     };
 
     let mut data = strat_deployer_account_info.try_borrow_mut_data()?;
@@ -1,7 +1,6 @@
 use solana_program::{
     account_info::AccountInfo, program_error::ProgramError, pubkey::Pubkey, system_program,
 };
-use steel::*;
 
 use crate::{
     consts::MANAGED_MINER_AUTH,
@@ -42,7 +41,7 @@ pub fn process_deposit_autodeploy_balance(
         return Err(EvoreError::ManagerNotInitialized.into());
     }
 
-    let manager = manager_account_info.as_account::<Manager>(&crate::id())?;
+    let manager = Manager::load(manager_account_info)?;
 
     if manager.authority != *signer.key {
         return Err(EvoreError::NotAuthorized.into());
@@ -0,0 +1,14 @@
+use solana_program::{account_info::AccountInfo, program_error::ProgramError};
+
+use crate::instruction::ProgramInfo;
+
+/// Process GetProgramInfo instruction
+/// Read-only: sets the program's version and feature bitmask as return data.
+pub fn process_get_program_info(
+    _accounts: &[AccountInfo],
+    _instruction_data: &[u8],
+) -> Result<(), ProgramError> {
+    solana_program::program::set_return_data(bytemuck::bytes_of(&ProgramInfo::current()));
+
+    Ok(())
+}
@@ -0,0 +1,44 @@
+use solana_program::{account_info::AccountInfo, program_error::ProgramError};
+use steel::*;
+
+use crate::{error::EvoreError, state::Manager};
+
+/// Process InitiateTransferManager instruction
+/// Records the nominated authority as pending; the current authority stays in control
+/// until the nominee signs AcceptTransferManager
+pub fn process_initiate_transfer_manager(
+    accounts: &[AccountInfo],
+    _instruction_data: &[u8],
+) -> Result<(), ProgramError> {
+    let [
+        signer,                 // 0: signer (manager authority)
+        manager_account_info,   // 1: manager
+        pending_authority_info, // 2: pending authority
+    ] = accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+
+    if !signer.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    if !manager_account_info.is_writable {
+        return Err(ProgramError::InvalidAccountData);
+    }
+
+    if manager_account_info.data_is_empty() {
+        return Err(EvoreError::ManagerNotInitialized.into());
+    }
+
+    let manager = manager_account_info
+        .as_account_mut::<Manager>(&crate::id())?;
+
+    if manager.authority != *signer.key {
+        return Err(EvoreError::NotAuthorized.into());
+    }
+
+    // Overwrites any earlier nominee that hasn't accepted yet
+    manager.pending_authority = *pending_authority_info.key;
+
+    Ok(())
+}
@@ -0,0 +1,100 @@
+use solana_program::{
+    account_info::AccountInfo, program_error::ProgramError, pubkey::Pubkey, system_program,
+};
+
+use crate::{
+    consts::MANAGED_MINER_AUTH,
+    error::EvoreError,
+    instruction::MigrateAuthId,
+    state::Manager,
+};
+
+/// Process MigrateAuthId instruction
+/// Moves the entire managed_miner_auth balance from old_auth_id to new_auth_id
+/// Only SOL moves; the old PDA's ORE miner and its rewards stay with old_auth_id
+pub fn process_migrate_auth_id(
+    accounts: &[AccountInfo],
+    instruction_data: &[u8],
+) -> Result<(), ProgramError> {
+    let args = MigrateAuthId::try_from_bytes(instruction_data)?;
+    let old_auth_id = u64::from_le_bytes(args.old_auth_id);
+    let new_auth_id = u64::from_le_bytes(args.new_auth_id);
+
+    let [
+        signer,                   // 0: signer (manager authority)
+        manager_account_info,     // 1: manager
+        old_auth_account_info,    // 2: old managed_miner_auth PDA
+        new_auth_account_info,    // 3: new managed_miner_auth PDA
+        system_program_info,      // 4: system_program
+    ] = accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+
+    // Basic validations
+    if !signer.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    if *system_program_info.key != system_program::id() {
+        return Err(ProgramError::IncorrectProgramId);
+    }
+
+    if old_auth_id == new_auth_id {
+        return Err(ProgramError::InvalidArgument);
+    }
+
+    // Verify manager is initialized and signer is the authority
+    if manager_account_info.data_is_empty() {
+        return Err(EvoreError::ManagerNotInitialized.into());
+    }
+
+    let manager = Manager::load(manager_account_info)?;
+
+    if manager.authority != *signer.key {
+        return Err(EvoreError::NotAuthorized.into());
+    }
+
+    // Both PDAs must belong to this manager
+    let (old_auth_pda, old_auth_bump) = Pubkey::find_program_address(
+        &[MANAGED_MINER_AUTH, manager_account_info.key.as_ref(), &old_auth_id.to_le_bytes()],
+        &crate::id(),
+    );
+    if old_auth_pda != *old_auth_account_info.key {
+        return Err(EvoreError::InvalidPDA.into());
+    }
+
+    let (new_auth_pda, _) = Pubkey::find_program_address(
+        &[MANAGED_MINER_AUTH, manager_account_info.key.as_ref(), &new_auth_id.to_le_bytes()],
+        &crate::id(),
+    );
+    if new_auth_pda != *new_auth_account_info.key {
+        return Err(EvoreError::InvalidPDA.into());
+    }
+
+    // Move everything, rent included: the old PDA holds no data and can drop to zero
+    let amount = old_auth_account_info.lamports();
+    if amount == 0 {
+        return Err(EvoreError::InsufficientAutodeployBalance.into());
+    }
+
+    solana_program::program::invoke_signed(
+        &solana_program::system_instruction::transfer(
+            old_auth_account_info.key,
+            new_auth_account_info.key,
+            amount,
+        ),
+        &[
+            old_auth_account_info.clone(),
+            new_auth_account_info.clone(),
+            system_program_info.clone(),
+        ],
+        &[&[
+            MANAGED_MINER_AUTH,
+            manager_account_info.key.as_ref(),
+            &old_auth_id.to_le_bytes(),
+            &[old_auth_bump],
+        ]],
+    )?;
+
+    Ok(())
+}
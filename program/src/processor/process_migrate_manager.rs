@@ -0,0 +1,90 @@
+use solana_program::{
+    account_info::AccountInfo, program_error::ProgramError, pubkey::Pubkey, rent::Rent,
+    system_program, sysvar::Sysvar,
+};
+use steel::*;
+
+use crate::{
+    error::EvoreError,
+    state::{Manager, LEGACY_MANAGER_SIZE},
+};
+
+/// Process MigrateManager instruction
+/// Reallocs a legacy 40-byte manager to the current size, keeping the authority and
+/// zeroing the new reserved bytes. The manager authority tops up the rent.
+pub fn process_migrate_manager(
+    accounts: &[AccountInfo],
+    _instruction_data: &[u8],
+) -> Result<(), ProgramError> {
+    let [
+        signer,                  // 0: signer (manager authority, pays rent)
+        manager_account_info,    // 1: manager
+        system_program_info,     // 2: system_program
+    ] = accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+
+    if !signer.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    if *system_program_info.key != system_program::id() {
+        return Err(ProgramError::IncorrectProgramId);
+    }
+
+    if !manager_account_info.is_writable {
+        return Err(ProgramError::InvalidAccountData);
+    }
+
+    if manager_account_info.data_is_empty() {
+        return Err(EvoreError::ManagerNotInitialized.into());
+    }
+
+    if *manager_account_info.owner != crate::id() {
+        return Err(ProgramError::InvalidAccountOwner);
+    }
+
+    let new_size = 8 + std::mem::size_of::<Manager>();
+
+    // Legacy layout can't be loaded as Manager, read the authority straight from the bytes
+    let authority = {
+        let data = manager_account_info.try_borrow_data()?;
+        if data[0] != Manager::discriminator() {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        if data.len() == new_size {
+            return Err(EvoreError::ManagerAlreadyMigrated.into());
+        }
+        if data.len() != LEGACY_MANAGER_SIZE {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        Pubkey::try_from(&data[8..40]).map_err(|_| ProgramError::InvalidAccountData)?
+    };
+
+    if authority != *signer.key {
+        return Err(EvoreError::NotAuthorized.into());
+    }
+
+    // Top up rent for the larger account
+    let required = Rent::get()?.minimum_balance(new_size);
+    let shortfall = required.saturating_sub(manager_account_info.lamports());
+    if shortfall > 0 {
+        solana_program::program::invoke(
+            &solana_program::system_instruction::transfer(
+                signer.key,
+                manager_account_info.key,
+                shortfall,
+            ),
+            &[
+                signer.clone(),
+                manager_account_info.clone(),
+                system_program_info.clone(),
+            ],
+        )?;
+    }
+
+    // New bytes are zeroed, which is the reserved field's initial value
+    manager_account_info.resize(new_size)?;
+
+    Ok(())
+}
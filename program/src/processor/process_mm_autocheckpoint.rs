@@ -59,7 +59,7 @@ pub fn process_mm_autocheckpoint(
         return Err(EvoreError::ManagerNotInitialized.into());
     }
 
-    let _manager = manager_account_info.as_account::<Manager>(&crate::id())?;
+    let _manager = Manager::load(manager_account_info)?;
 
     // Verify deployer is initialized
     if deployer_account_info.data_is_empty() {
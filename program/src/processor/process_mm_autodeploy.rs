@@ -7,8 +7,10 @@ use crate::{
     consts::{DEPLOY_FEE, DEPLOYER, FEE_COLLECTOR, MANAGED_MINER_AUTH},
     entropy_api,
     error::EvoreError,
+    events::DeployEvent,
     instruction::MMAutodeploy,
     ore_api::{self, Board},
+    squares::{mask_to_squares, ALL_SQUARES_MASK},
     state::{Deployer, Manager},
 };
 
@@ -66,7 +68,7 @@ pub fn process_mm_autodeploy(
         return Err(EvoreError::ManagerNotInitialized.into());
     }
 
-    let _manager = manager_account_info.as_account::<Manager>(&crate::id())?;
+    let manager = Manager::load(manager_account_info)?;
 
     // Verify deployer is initialized
     if deployer_account_info.data_is_empty() {
@@ -125,14 +127,25 @@ pub fn process_mm_autodeploy(
         return Err(EvoreError::EndSlotReached.into());
     }
 
-    // Convert squares_mask to [bool; 25]
-    let mut squares = [false; 25];
-    for i in 0..25 {
-        if (squares_mask >> i) & 1 == 1 {
-            squares[i] = true;
+    // A miner already past the board's round means the board is stale (or reorged), don't deploy on it
+    if !ore_miner_account_info.data_is_empty() {
+        let miner = ore_miner_account_info.as_account::<ore_api::Miner>(&ore_api::id())?;
+        if miner.round_id > board.round_id {
+            return Err(EvoreError::MinerAheadOfBoard.into());
         }
     }
 
+    // Only bits 0-24 map to board squares, and at least one must be set
+    if squares_mask as u64 & !ALL_SQUARES_MASK != 0 {
+        return Err(EvoreError::InvalidSquaresMask.into());
+    }
+    if squares_mask == 0 {
+        return Err(EvoreError::NoDeployments.into());
+    }
+
+    // Convert squares_mask to [bool; 25]
+    let squares = mask_to_squares(squares_mask as u64);
+
     // Count how many squares are being deployed to
     let num_squares = squares.iter().filter(|&&s| s).count() as u64;
     if num_squares == 0 {
@@ -140,12 +153,15 @@ pub fn process_mm_autodeploy(
     }
 
     // Calculate total amount to deploy in this transaction
-    let total_to_deploy = amount.saturating_mul(num_squares);
+    let total_to_deploy = amount.checked_mul(num_squares).ok_or(EvoreError::ArithmeticOverflow)?;
 
     if total_to_deploy == 0 {
         return Err(EvoreError::NoDeployments.into());
     }
 
+    // Absolute ceiling on one deploy, whatever the per-round caps allow
+    manager.check_deploy_ceiling(total_to_deploy)?;
+
     // Check max_per_round limit (includes already deployed amount for this round)
     if max_per_round > 0 {
         // Get already deployed amount for this round (if miner exists and is in current round)
@@ -153,7 +169,9 @@ pub fn process_mm_autodeploy(
             let miner = ore_miner_account_info.as_account::<ore_api::Miner>(&ore_api::id())?;
             if miner.round_id == board.round_id {
                 // Sum all deployed amounts for current round
-                miner.deployed.iter().sum::<u64>()
+                miner.deployed.iter()
+                    .try_fold(0u64, |acc, &d| acc.checked_add(d))
+                    .ok_or(EvoreError::ArithmeticOverflow)?
             } else {
                 0
             }
@@ -161,23 +179,30 @@ pub fn process_mm_autodeploy(
             0
         };
 
-        let total_for_round = already_deployed.saturating_add(total_to_deploy);
+        let total_for_round = already_deployed.checked_add(total_to_deploy).ok_or(EvoreError::ArithmeticOverflow)?;
         if total_for_round > max_per_round {
             return Err(EvoreError::ExceedsMaxPerRound.into());
         }
     }
 
-    // Calculate deployer fee
-    let bps_fee_amount = if bps_fee > 0 {
-        total_to_deploy.saturating_mul(bps_fee).saturating_div(10_000)
+    // Check if already deployed this round (only if miner exists)
+    let is_already_deployed = if !ore_miner_account_info.data_is_empty() {
+        let miner = ore_miner_account_info.as_account::<ore_api::Miner>(&ore_api::id())?;
+        miner.round_id == board.round_id
     } else {
-        0
+        false // First ever deploy, miner doesn't exist yet
     };
-    
-    let deployer_fee = bps_fee_amount.saturating_add(flat_fee);
+
     let protocol_fee = DEPLOY_FEE;
 
-    // Calculate funds needed
+    // Fees are only charged on the first deploy of a round, so only budget for them then
+    let (reserved_fees, clamp_bps_fee) = if is_already_deployed {
+        (0, 0)
+    } else {
+        (flat_fee.checked_add(protocol_fee).ok_or(EvoreError::ArithmeticOverflow)?, bps_fee)
+    };
+
+    // Calculate funds needed on top of the deploy itself
     const AUTH_PDA_RENT: u64 = 890_880;
     let miner_rent = if ore_miner_account_info.data_is_empty() {
         let size = 8 + std::mem::size_of::<ore_api::Miner>();
@@ -185,19 +210,29 @@ pub fn process_mm_autodeploy(
     } else {
         0
     };
-    
-    let required_balance = AUTH_PDA_RENT
-        .saturating_add(ore_api::CHECKPOINT_FEE)
-        .saturating_add(total_to_deploy)
-        .saturating_add(miner_rent)
-        .saturating_add(deployer_fee)
-        .saturating_add(protocol_fee);
-
-    // Check managed_miner_auth has enough funds
-    let current_balance = managed_miner_auth_account_info.lamports();
-    if current_balance < required_balance {
+
+    let reserved = AUTH_PDA_RENT
+        .checked_add(ore_api::CHECKPOINT_FEE).ok_or(EvoreError::ArithmeticOverflow)?
+        .checked_add(miner_rent).ok_or(EvoreError::ArithmeticOverflow)?
+        .checked_add(reserved_fees).ok_or(EvoreError::ArithmeticOverflow)?;
+
+    // The PDA may have been drained (withdrawal) since the crank planned this deploy;
+    // shrink the per-square amount to what it can still pay for instead of failing
+    let available = managed_miner_auth_account_info.lamports().saturating_sub(reserved);
+    let amount = clamp_amount_to_balance(amount, num_squares, clamp_bps_fee, available)?;
+    if amount == 0 {
         return Err(EvoreError::InsufficientAutodeployBalance.into());
     }
+    let total_to_deploy = amount.checked_mul(num_squares).ok_or(EvoreError::ArithmeticOverflow)?;
+
+    // Calculate deployer fee
+    let bps_fee_amount = if bps_fee > 0 {
+        total_to_deploy.checked_mul(bps_fee).ok_or(EvoreError::ArithmeticOverflow)? / 10_000
+    } else {
+        0
+    };
+    
+    let deployer_fee = bps_fee_amount.checked_add(flat_fee).ok_or(EvoreError::ArithmeticOverflow)?;
 
     // Managed miner auth PDA seeds for signed transfers
     let managed_miner_auth_seeds: &[&[u8]] = &[
@@ -207,14 +242,6 @@ pub fn process_mm_autodeploy(
         &[managed_miner_auth_bump],
     ];
 
-    // Check if already deployed this round (only if miner exists)
-    let is_already_deployed = if !ore_miner_account_info.data_is_empty() {
-        let miner = ore_miner_account_info.as_account::<ore_api::Miner>(&ore_api::id())?;
-        miner.round_id == board.round_id
-    } else {
-        false // First ever deploy, miner doesn't exist yet
-    };
-
     // Transfer protocol fee from managed_miner_auth to FEE_COLLECTOR (only on first deploy of round)
     if protocol_fee > 0 && !is_already_deployed {
         solana_program::program::invoke_signed(
@@ -281,5 +308,40 @@ pub fn process_mm_autodeploy(
         &[managed_miner_auth_seeds],
     )?;
 
+    // Report what was actually deployed, which is less than requested when clamped
+    solana_program::program::set_return_data(&total_to_deploy.to_le_bytes());
+
+    DeployEvent {
+        manager: *manager_account_info.key,
+        auth_id,
+        round_id: round.id,
+        total_deployed: total_to_deploy,
+        fee: if is_already_deployed { 0 } else { deployer_fee.saturating_add(protocol_fee) },
+        squares_mask,
+        _padding: [0; 4],
+    }
+    .log();
+
     Ok(())
 }
+
+/// Largest per-square amount (at most `amount`) whose deploy plus bps fee fits in `available`
+///
+/// Everything else the deploy pays for (rent, flat and protocol fees) must already be
+/// taken out of `available`.
+pub(crate) fn clamp_amount_to_balance(
+    amount: u64,
+    num_squares: u64,
+    bps_fee: u64,
+    available: u64,
+) -> Result<u64, ProgramError> {
+    let total = amount.checked_mul(num_squares).ok_or(EvoreError::ArithmeticOverflow)?;
+    let bps_fee_amount = total.checked_mul(bps_fee).ok_or(EvoreError::ArithmeticOverflow)? / 10_000;
+    if total.checked_add(bps_fee_amount).ok_or(EvoreError::ArithmeticOverflow)? <= available {
+        return Ok(amount);
+    }
+
+    // total * (10_000 + bps) / 10_000 <= available, solved for the per-square amount
+    let per_square = (available as u128 * 10_000) / ((10_000 + bps_fee as u128) * num_squares as u128);
+    Ok(per_square.min(amount as u128) as u64)
+}
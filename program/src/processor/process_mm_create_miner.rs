@@ -1,5 +1,9 @@
 use solana_program::{
-    account_info::AccountInfo, program_error::ProgramError, pubkey::Pubkey, system_program
+    account_info::AccountInfo,
+    instruction::{AccountMeta, Instruction},
+    program_error::ProgramError,
+    pubkey::Pubkey,
+    system_program,
 };
 use steel::*;
 
@@ -62,7 +66,7 @@ pub fn process_mm_create_miner(
         return Err(EvoreError::ManagerNotInitialized.into());
     }
 
-    let manager = manager_account_info.as_account::<Manager>(&crate::id())?;
+    let manager = Manager::load(manager_account_info)?;
 
     if manager.authority != *signer.key {
         return Err(EvoreError::NotAuthorized.into());
@@ -83,6 +87,45 @@ pub fn process_mm_create_miner(
         return Err(EvoreError::InvalidPDA.into());
     }
 
+    // Seeds for signing CPIs
+    let managed_miner_auth_seeds: &[&[u8]] = &[
+        crate::consts::MANAGED_MINER_AUTH,
+        manager_account_info.key.as_ref(),
+        &auth_id.to_le_bytes(),
+        &[args.bump],
+    ];
+
+    // Miner already exists: only close a leftover automation, nothing is created or funded
+    if !miner_account_info.data_is_empty() {
+        if *miner_account_info.key != ore_api::miner_pda(managed_miner_auth_pda).0 {
+            return Err(EvoreError::InvalidPDA.into());
+        }
+        miner_account_info.as_account::<ore_api::Miner>(&ore_api::id())?;
+
+        if !automation_account_info.data_is_empty() {
+            if *automation_account_info.key != ore_api::automation_pda(managed_miner_auth_pda).0 {
+                return Err(EvoreError::InvalidPDA.into());
+            }
+            solana_program::program::invoke_signed(
+                &close_automation_ix(
+                    managed_miner_auth_account_info,
+                    automation_account_info,
+                    miner_account_info,
+                    system_program_info,
+                ),
+                &[
+                    managed_miner_auth_account_info.clone(),
+                    automation_account_info.clone(),
+                    executor_2_account_info.clone(),
+                    miner_account_info.clone(),
+                    system_program_info.clone(),
+                ],
+                &[managed_miner_auth_seeds],
+            )?;
+        }
+        return Ok(());
+    }
+
     // Calculate rent needed for miner and automation account creation
     // During the first automate call, ORE creates both automation and miner accounts
     // The automation account will be closed in the second call, returning its rent
@@ -96,7 +139,9 @@ pub fn process_mm_create_miner(
     let automation_rent = solana_program::rent::Rent::default().minimum_balance(automation_size);
     
     // ORE requires checkpoint_fee to be held in the miner account
-    let total_required = miner_rent + automation_rent + ore_api::CHECKPOINT_FEE ;
+    let total_required = miner_rent
+        .checked_add(automation_rent).ok_or(EvoreError::ArithmeticOverflow)?
+        .checked_add(ore_api::CHECKPOINT_FEE).ok_or(EvoreError::ArithmeticOverflow)?;
     
     // Transfer SOL from signer to managed_miner_auth for account creation
     solana_program::program::invoke(
@@ -112,14 +157,6 @@ pub fn process_mm_create_miner(
         ],
     )?;
 
-    // Seeds for signing CPIs
-    let managed_miner_auth_seeds: &[&[u8]] = &[
-        crate::consts::MANAGED_MINER_AUTH,
-        manager_account_info.key.as_ref(),
-        &auth_id.to_le_bytes(),
-        &[args.bump],
-    ];
-
     // Build accounts for first automate CPI (open automation)
     // executor_1 = signer (opens automation and creates miner)
     let automate_accounts_open = vec![
@@ -155,33 +192,14 @@ pub fn process_mm_create_miner(
         system_program_info.clone(),
     ];
 
-    // Build close instruction manually with executor as readonly
-    // ORE doesn't actually check executor is writable, so using readonly
-    // avoids privilege conflicts with system_program (same pubkey)
-    use solana_program::instruction::{AccountMeta, Instruction};
-    let close_ix = Instruction {
-        program_id: ore_api::id(),
-        accounts: vec![
-            AccountMeta::new(*managed_miner_auth_account_info.key, true),
-            AccountMeta::new(*automation_account_info.key, false),
-            AccountMeta::new_readonly(Pubkey::default(), false), // executor readonly!
-            AccountMeta::new(*miner_account_info.key, false),
-            AccountMeta::new_readonly(*system_program_info.key, false),
-        ],
-        data: ore_api::Automate {
-            amount: 0u64.to_le_bytes(),
-            deposit: 0u64.to_le_bytes(),
-            fee: 0u64.to_le_bytes(),
-            mask: 0u64.to_le_bytes(),
-            strategy: 0,
-            reload: 0u64.to_le_bytes(),
-        }
-        .to_bytes(),
-    };
-
     // Second CPI: Close automation
     solana_program::program::invoke_signed(
-        &close_ix,
+        &close_automation_ix(
+            managed_miner_auth_account_info,
+            automation_account_info,
+            miner_account_info,
+            system_program_info,
+        ),
         &automate_accounts_close,
         &[managed_miner_auth_seeds],
     )?;
@@ -207,3 +225,34 @@ pub fn process_mm_create_miner(
 
     Ok(())
 }
+
+/// ORE automate instruction that closes the automation account (executor = Pubkey::default())
+///
+/// Built manually with the executor readonly: ORE doesn't check it is writable, and
+/// readonly avoids privilege conflicts with system_program (same pubkey)
+fn close_automation_ix(
+    managed_miner_auth_account_info: &AccountInfo,
+    automation_account_info: &AccountInfo,
+    miner_account_info: &AccountInfo,
+    system_program_info: &AccountInfo,
+) -> Instruction {
+    Instruction {
+        program_id: ore_api::id(),
+        accounts: vec![
+            AccountMeta::new(*managed_miner_auth_account_info.key, true),
+            AccountMeta::new(*automation_account_info.key, false),
+            AccountMeta::new_readonly(Pubkey::default(), false), // executor readonly!
+            AccountMeta::new(*miner_account_info.key, false),
+            AccountMeta::new_readonly(*system_program_info.key, false),
+        ],
+        data: ore_api::Automate {
+            amount: 0u64.to_le_bytes(),
+            deposit: 0u64.to_le_bytes(),
+            fee: 0u64.to_le_bytes(),
+            mask: 0u64.to_le_bytes(),
+            strategy: 0,
+            reload: 0u64.to_le_bytes(),
+        }
+        .to_bytes(),
+    }
+}
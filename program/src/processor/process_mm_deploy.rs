@@ -5,7 +5,7 @@ use solana_program::{
 use steel::*;
 
 use crate::{
-    consts::{DEPLOY_FEE, FEE_COLLECTOR}, entropy_api, error::EvoreError, instruction::{DeployStrategy, MMDeploy}, ore_api::{self, Board, Round}, state::Manager
+    consts::{DEPLOY_FEE, FEE_COLLECTOR}, entropy_api, error::EvoreError, instruction::{DeployStrategy, MMDeploy}, ore_api::{self, Board, Round}, squares::validate_squares_count, state::Manager
 };
 
 /// A batch of deployments to execute in a single CPI call
@@ -47,6 +47,10 @@ pub fn process_mm_deploy(
     let strategy = args.get_strategy()
         .map_err(|_| ProgramError::InvalidInstructionData)?;
 
+    if let DeployStrategy::Percentage { squares_count, .. } = strategy {
+        validate_squares_count(squares_count)?;
+    }
+
     let [
             signer,
             manager_account_info,
@@ -65,6 +69,7 @@ pub fn process_mm_deploy(
         return Err(ProgramError::NotEnoughAccountKeys);
     };
 
+    // Slot comes from the runtime sysvar, never from a caller-supplied account
     let clock = Clock::get()?;
     let board = board_account_info
         .as_account::<Board>(&ore_api::id())?;
@@ -75,7 +80,7 @@ pub fn process_mm_deploy(
 
     // EV strategy has slots_left check
     if let DeployStrategy::EV { slots_left, .. } = strategy {
-        let current_slots_left = board.end_slot - clock.slot;
+        let current_slots_left = ev_slots_remaining(board, &clock)?;
         if current_slots_left > slots_left {
             return Err(EvoreError::TooManySlotsLeft.into());
         }
@@ -109,8 +114,7 @@ pub fn process_mm_deploy(
         return Err(EvoreError::InvalidFeeCollector.into());
     }
 
-    let manager = manager_account_info
-        .as_account::<Manager>(&crate::id())?;
+    let manager = Manager::load(manager_account_info)?;
 
     if manager.authority != *signer.key {
         return Err(EvoreError::NotAuthorized.into());
@@ -134,6 +138,13 @@ pub fn process_mm_deploy(
         return Err(EvoreError::InvalidPDA.into());
     }
 
+    // The already-deployed check below reads this account, so it must be the real ORE miner;
+    // an empty stand-in would read as "not deployed" and let a stale tx through the guard
+    let expected_ore_miner = ore_api::miner_pda(managed_miner_auth_pda).0;
+    if expected_ore_miner != *ore_miner_account_info.key {
+        return Err(EvoreError::InvalidPDA.into());
+    }
+
     // Check if already deployed this round (only if miner exists)
     let is_already_deployed = if !ore_miner_account_info.data_is_empty() {
         let miner = ore_miner_account_info.as_account::<ore_api::Miner>(&ore_api::id())?;
@@ -157,7 +168,7 @@ pub fn process_mm_deploy(
             calculate_percentage_deployments(round, bankroll, percentage, squares_count)
         },
         DeployStrategy::Manual { amounts } => {
-            calculate_manual_deployments(amounts)
+            calculate_manual_deployments(amounts)?
         },
         DeployStrategy::Split { amount } => {
             calculate_split_deployments(round, amount)
@@ -168,6 +179,13 @@ pub fn process_mm_deploy(
         return Err(EvoreError::NoDeployments.into());
     }
 
+    // Preview: report the plan and stop before any transfer or ORE CPI
+    if args.is_simulate() {
+        let planned = planned_amounts(&batches);
+        solana_program::program::set_return_data(bytemuck::cast_slice(&planned));
+        return Ok(());
+    }
+
     let deploy_accounts = 
         vec![
             managed_miner_auth_account_info.clone(),
@@ -236,10 +254,10 @@ pub fn process_mm_deploy(
     // - miner_rent: if miner account needs creation
     // - automation_rent: temporary automation account creation (returned on close)
     let required_balance = AUTH_PDA_RENT
-        .saturating_add(ore_api::CHECKPOINT_FEE)
-        .saturating_add(total_deployed)
-        .saturating_add(miner_rent)
-        .saturating_add(automation_rent);
+        .checked_add(ore_api::CHECKPOINT_FEE).ok_or(EvoreError::ArithmeticOverflow)?
+        .checked_add(total_deployed).ok_or(EvoreError::ArithmeticOverflow)?
+        .checked_add(miner_rent).ok_or(EvoreError::ArithmeticOverflow)?
+        .checked_add(automation_rent).ok_or(EvoreError::ArithmeticOverflow)?;
     
     let current_balance = managed_miner_auth_account_info.lamports();
     let transfer_amount = required_balance.saturating_sub(current_balance);
@@ -264,14 +282,74 @@ pub fn process_mm_deploy(
         &[args.bump],
     ];
 
-    let managed_miner_auth_key = *managed_miner_auth_account_info.key;
-
     // Deposit all available SOL from auth_pda except its rent-exempt minimum.
     // automation_rent is deducted separately by ORE's create_account before deposit.
     let deposit = managed_miner_auth_account_info.lamports()
         .saturating_sub(AUTH_PDA_RENT)
         .saturating_sub(automation_rent);
 
+    deploy_batches_with_automation(
+        &mut batches,
+        round.id,
+        deposit,
+        managed_miner_auth_account_info,
+        automation_account_info,
+        ore_miner_account_info,
+        system_program,
+        &deploy_accounts,
+        managed_miner_auth_seeds,
+    )?;
+
+    Ok(())
+}
+
+/// Per-square lamports the batches would deploy
+pub(crate) fn planned_amounts(batches: &[DeploymentBatch]) -> [u64; 25] {
+    let mut planned = [0u64; 25];
+    for batch in batches {
+        for (i, &selected) in batch.squares.iter().enumerate() {
+            if selected {
+                planned[i] += batch.amount;
+            }
+        }
+    }
+    planned
+}
+
+/// Slots left in the round for the EV `slots_left` window
+///
+/// Zero remaining (`clock.slot >= end_slot`) means the round is over, so it is
+/// rejected with `EndSlotReached` here instead of being treated as "inside the window".
+pub(crate) fn ev_slots_remaining(board: &Board, clock: &Clock) -> Result<u64, ProgramError> {
+    match board.end_slot.checked_sub(clock.slot) {
+        Some(remaining) if remaining > 0 => Ok(remaining),
+        _ => Err(EvoreError::EndSlotReached.into()),
+    }
+}
+
+/// Deploy all batches for one miner inside a single ephemeral automation session
+///
+/// Automation is opened once, every batch is deployed through it, then it is closed once.
+/// The automation PDA is keyed by the miner authority, so a session can't be shared
+/// across managed miners - each miner in a multi-miner transaction pays for exactly one
+/// open/close pair regardless of how many batches it deploys.
+///
+/// Batches are separate ORE CPIs, but a failing one (out of compute, round ended) fails
+/// the whole transaction and the runtime discards every account write, including squares
+/// deployed by earlier batches. A retry always starts from the pre-transaction miner.
+pub(crate) fn deploy_batches_with_automation<'a>(
+    batches: &mut [DeploymentBatch],
+    round_id: u64,
+    deposit: u64,
+    managed_miner_auth_account_info: &AccountInfo<'a>,
+    automation_account_info: &AccountInfo<'a>,
+    ore_miner_account_info: &AccountInfo<'a>,
+    system_program: &AccountInfo<'a>,
+    deploy_accounts: &[AccountInfo<'a>],
+    managed_miner_auth_seeds: &[&[u8]],
+) -> Result<(), ProgramError> {
+    let managed_miner_auth_key = *managed_miner_auth_account_info.key;
+
     // Sort batches ascending by amount so the largest deploy is last.
     // automation.amount = max_batch_amount is both:
     //   1. The Discretionary cap on per-deploy amount
@@ -309,7 +387,7 @@ pub fn process_mm_deploy(
     // Execute batched deployments (ascending order).
     // With automation active, ORE deducts from automation.balance directly
     // instead of doing a system_program::transfer CPI, saving 1 trace per deploy.
-    for batch in batches {
+    for batch in batches.iter() {
         if batch.amount == 0 {
             continue;
         }
@@ -319,10 +397,10 @@ pub fn process_mm_deploy(
                 managed_miner_auth_key,
                 managed_miner_auth_key,
                 batch.amount,
-                round.id,
+                round_id,
                 batch.squares,
             ),
-            &deploy_accounts,
+            deploy_accounts,
             &[managed_miner_auth_seeds],
         )?;
     }
@@ -444,6 +522,7 @@ pub(crate) fn calculate_percentage_deployments(
 
 /// Calculate deployments using split strategy
 /// Splits the total amount equally across all 25 squares in a single CPI call
+/// Any remainder from the division goes to the highest-EV square (see split_with_remainder)
 fn calculate_split_deployments(
     round: &Round,
     total_amount: u64,
@@ -466,20 +545,41 @@ fn calculate_split_deployments(
         return (Vec::new(), 0);
     }
     
-    // Total actually deployed (may be slightly less due to integer division)
-    let actual_total = per_square * 25;
-    
-    // Single batch with all 25 squares
-    let batch = DeploymentBatch::all_squares(per_square);
+    (split_with_remainder(round, total_amount, per_square), total_amount)
+}
+
+/// Batches for an even split across all 25 squares that deploys the full `total_amount`
+/// The division remainder is added to the highest-EV square. ORE ignores a second deploy
+/// to a square the miner already holds this round, so that square gets its own batch.
+pub(crate) fn split_with_remainder(round: &Round, total_amount: u64, per_square: u64) -> Vec<DeploymentBatch> {
+    let remainder = total_amount - per_square * 25;
+    if remainder == 0 {
+        return vec![DeploymentBatch::all_squares(per_square)];
+    }
     
-    (vec![batch], actual_total)
+    let best = highest_ev_square(round, &[true; 25]);
+    let mut rest = [true; 25];
+    rest[best] = false;
+    vec![
+        DeploymentBatch::new(per_square, rest),
+        DeploymentBatch::single(per_square + remainder, best),
+    ]
+}
+
+/// Highest-EV square among `squares` for an additional lamport: the one with the least
+/// deployed by others this round. Ties go to the lowest index so the choice is deterministic.
+pub(crate) fn highest_ev_square(round: &Round, squares: &[bool; 25]) -> usize {
+    (0..25)
+        .filter(|&i| squares[i])
+        .min_by_key(|&i| (round.deployed[i], i))
+        .unwrap_or(0)
 }
 
 /// Calculate deployments using manual strategy
-/// Simply uses the provided amounts directly, one batch per square
+/// Simply uses the provided amounts directly, one batch per square; fails if they sum past u64::MAX
 fn calculate_manual_deployments(
     amounts: [u64; 25],
-) -> (Vec<DeploymentBatch>, u64) {
+) -> Result<(Vec<DeploymentBatch>, u64), ProgramError> {
     let mut batches = Vec::new();
     let mut total: u64 = 0;
     
@@ -487,11 +587,11 @@ fn calculate_manual_deployments(
         let amount = amounts[i];
         if amount > 0 {
             batches.push(DeploymentBatch::single(amount, i));
-            total = total.saturating_add(amount);
+            total = total.checked_add(amount).ok_or(EvoreError::ArithmeticOverflow)?;
         }
     }
     
-    (batches, total)
+    Ok((batches, total))
 }
 
 /// Calculate deployments using EV waterfill strategy
@@ -1062,3 +1162,92 @@ pub fn plan_max_profit_waterfill(
     best_alloc
 }
 
+/// Drops funded squares whose expected profit is less than `min_ev_bps` of their
+/// stake (0 = keep every square). Dropped stake is not redistributed.
+pub fn keep_min_ev_squares(
+    alloc: Allocation,
+    t: [u64; 25],
+    ore_value_lamports: u64,
+    min_ev_bps: u64,
+) -> Allocation {
+    if min_ev_bps == 0 {
+        return alloc;
+    }
+
+    let total_sum = u128::from(sum25_u64(&t));
+    let ore_u128 = u128::from(ore_value_lamports);
+
+    let mut per_square = [0u64; 25];
+    let mut spent = 0u64;
+    let mut ev_sum = 0i64;
+    for (i, &x) in alloc.per_square.iter().enumerate() {
+        if x == 0 {
+            continue;
+        }
+        let (n, d) = profit_fraction_fixed_s(total_sum, u128::from(t[i]), u128::from(x), ore_u128);
+        // n/d >= min_ev_bps/10_000 * x
+        if n < 0 {
+            continue;
+        }
+        let margin = (n as u128).saturating_mul(10_000);
+        let required = d.saturating_mul(u128::from(x)).saturating_mul(u128::from(min_ev_bps));
+        if margin < required {
+            continue;
+        }
+        let ev = (n / d.min(i128::MAX as u128) as i128).clamp(i64::MIN as i128, i64::MAX as i128) as i64;
+        per_square[i] = x;
+        spent = spent.saturating_add(x);
+        ev_sum = ev_sum.saturating_add(ev);
+    }
+
+    Allocation {
+        per_square,
+        spent,
+        exp_profit_est_lamports: ev_sum,
+    }
+}
+
+/// Keeps only the `max_squares` funded squares with the highest expected profit
+/// (0 = no cap). Stake dropped from the other squares is not redistributed.
+pub fn keep_top_ev_squares(
+    alloc: Allocation,
+    t: [u64; 25],
+    ore_value_lamports: u64,
+    max_squares: u64,
+) -> Allocation {
+    let funded = alloc.per_square.iter().filter(|&&x| x > 0).count() as u64;
+    if max_squares == 0 || funded <= max_squares {
+        return alloc;
+    }
+
+    let total_sum = u128::from(sum25_u64(&t));
+    let ore_u128 = u128::from(ore_value_lamports);
+
+    let mut ranked: Vec<(i64, usize)> = Vec::with_capacity(funded as usize);
+    for (i, &x) in alloc.per_square.iter().enumerate() {
+        if x == 0 {
+            continue;
+        }
+        let (n, d) = profit_fraction_fixed_s(total_sum, u128::from(t[i]), u128::from(x), ore_u128);
+        let ev = (n / d.min(i128::MAX as u128) as i128).clamp(i64::MIN as i128, i64::MAX as i128) as i64;
+        ranked.push((ev, i));
+    }
+    // Highest EV first; ties go to the lower square index
+    ranked.sort_by(|a, b| b.0.cmp(&a.0).then(a.1.cmp(&b.1)));
+
+    let mut per_square = [0u64; 25];
+    let mut spent = 0u64;
+    let mut ev_sum = 0i64;
+    for &(ev, i) in ranked.iter().take(max_squares as usize) {
+        per_square[i] = alloc.per_square[i];
+        spent = spent.saturating_add(per_square[i]);
+        ev_sum = ev_sum.saturating_add(ev);
+    }
+
+    Allocation {
+        per_square,
+        spent,
+        exp_profit_est_lamports: ev_sum,
+    }
+}
+
@@ -7,24 +7,41 @@ use crate::{
     consts::{DEPLOY_FEE, DEPLOYER, FEE_COLLECTOR, MANAGED_MINER_AUTH},
     entropy_api,
     error::EvoreError,
+    events::DeployEvent,
     instruction::MMFullAutodeploy,
     ore_api::{self, Board, Miner, Round},
+    squares::{mask_to_squares, squares_to_mask, validate_squares_mask},
     state::{Deployer, Manager},
 };
 
 /// Process MMFullAutodeploy instruction
-/// 
+///
 /// Combined checkpoint (if needed) + recycle (if needed) + deploy in one instruction.
 /// Funds come from managed_miner_auth directly.
+///
+/// Instruction data may carry extra auth_ids (8 bytes each) after the args; each is
+/// deployed the same way, with its 4 miner accounts appended after the usual 16.
 pub fn process_mm_full_autodeploy(
     accounts: &[AccountInfo],
     instruction_data: &[u8],
 ) -> Result<(), ProgramError> {
-    let args = MMFullAutodeploy::try_from_bytes(instruction_data)?;
+    let args_len = std::mem::size_of::<MMFullAutodeploy>();
+    if instruction_data.len() < args_len {
+        return Err(ProgramError::InvalidInstructionData);
+    }
+    let (args_data, extra_auth_ids) = instruction_data.split_at(args_len);
+    let extra_auth_ids = extra_auth_ids.chunks_exact(8);
+    if !extra_auth_ids.remainder().is_empty() {
+        return Err(ProgramError::InvalidInstructionData);
+    }
+    let args = MMFullAutodeploy::try_from_bytes(args_data)?;
     let auth_id = u64::from_le_bytes(args.auth_id);
     let amount = u64::from_le_bytes(args.amount);
     let squares_mask = u32::from_le_bytes(args.squares_mask);
+    let extra_auth_ids = extra_auth_ids
+        .map(|b| u64::from_le_bytes(b.try_into().unwrap()));
 
+    let (accounts, extra_miner_accounts) = accounts.split_at(accounts.len().min(16));
     let [
         signer,                            // 0: deploy_authority (signer)
         manager_account_info,              // 1: manager
@@ -46,6 +63,11 @@ pub fn process_mm_full_autodeploy(
         return Err(ProgramError::NotEnoughAccountKeys);
     };
 
+    // 16..: managed_miner_auth, ore_miner, automation, checkpoint_round per extra auth_id
+    if extra_miner_accounts.len() != extra_auth_ids.len() * 4 {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    }
+
     // Basic validations
     if !signer.is_signer {
         return Err(ProgramError::MissingRequiredSignature);
@@ -72,7 +94,7 @@ pub fn process_mm_full_autodeploy(
         return Err(EvoreError::ManagerNotInitialized.into());
     }
 
-    let _manager = manager_account_info.as_account::<Manager>(&crate::id())?;
+    let manager = Manager::load(manager_account_info)?;
 
     // Verify deployer is initialized
     if deployer_account_info.data_is_empty() {
@@ -112,6 +134,149 @@ pub fn process_mm_full_autodeploy(
         return Err(EvoreError::UnexpectedFee.into());
     }
 
+    // Get round and board for operations
+    let round = round_account_info.as_account::<Round>(&ore_api::id())?;
+    let board = board_account_info.as_account::<Board>(&ore_api::id())?;
+    let clock = Clock::get()?;
+
+    // Check if round hasn't ended
+    if clock.slot >= board.end_slot {
+        return Err(EvoreError::EndSlotReached.into());
+    }
+
+    // Convert squares_mask to [bool; 25]
+    validate_squares_mask(squares_mask as u64)?;
+    let squares = mask_to_squares(squares_mask as u64);
+
+    let num_squares = squares.iter().filter(|&&s| s).count() as u64;
+    if num_squares == 0 {
+        return Err(EvoreError::NoDeployments.into());
+    }
+
+    let total_to_deploy = amount.checked_mul(num_squares).ok_or(EvoreError::ArithmeticOverflow)?;
+    if total_to_deploy == 0 {
+        return Err(EvoreError::NoDeployments.into());
+    }
+
+    // The ceiling covers every miner this instruction deploys for
+    let miner_count = 1 + extra_auth_ids.len() as u64;
+    manager.check_deploy_ceiling(total_to_deploy.checked_mul(miner_count).ok_or(EvoreError::ArithmeticOverflow)?)?;
+
+    let ctx = FullAutodeployContext {
+        signer,
+        manager_account_info,
+        fee_collector_account_info,
+        config_account_info,
+        board_account_info,
+        round_account_info,
+        treasury_account_info,
+        entropy_var_account_info,
+        ore_program,
+        entropy_program,
+        system_program_info,
+        board_round_id: board.round_id,
+        round_id: round.id,
+        bps_fee,
+        flat_fee,
+        max_per_round,
+        amount,
+        squares,
+        total_to_deploy,
+    };
+
+    let first_miner = MinerAccounts {
+        managed_miner_auth_account_info,
+        ore_miner_account_info,
+        automation_account_info,
+        checkpoint_round_account_info,
+    };
+    let extra_miners = extra_miner_accounts.chunks_exact(4).map(|miner| MinerAccounts {
+        managed_miner_auth_account_info: &miner[0],
+        ore_miner_account_info: &miner[1],
+        automation_account_info: &miner[2],
+        checkpoint_round_account_info: &miner[3],
+    });
+
+    let miners = std::iter::once((auth_id, first_miner)).chain(extra_auth_ids.zip(extra_miners));
+    let mut deployed_auth_ids = Vec::new();
+    for (auth_id, miner) in miners {
+        // A repeated auth_id would deploy (and pay fees for) the same miner twice
+        if deployed_auth_ids.contains(&auth_id) {
+            return Err(ProgramError::InvalidInstructionData);
+        }
+        deployed_auth_ids.push(auth_id);
+
+        full_autodeploy_miner(&ctx, auth_id, &miner)?;
+    }
+
+    Ok(())
+}
+
+/// Accounts and deploy parameters shared by every miner in one MMFullAutodeploy
+struct FullAutodeployContext<'a, 'info> {
+    signer: &'a AccountInfo<'info>,
+    manager_account_info: &'a AccountInfo<'info>,
+    fee_collector_account_info: &'a AccountInfo<'info>,
+    config_account_info: &'a AccountInfo<'info>,
+    board_account_info: &'a AccountInfo<'info>,
+    round_account_info: &'a AccountInfo<'info>,
+    treasury_account_info: &'a AccountInfo<'info>,
+    entropy_var_account_info: &'a AccountInfo<'info>,
+    ore_program: &'a AccountInfo<'info>,
+    entropy_program: &'a AccountInfo<'info>,
+    system_program_info: &'a AccountInfo<'info>,
+    board_round_id: u64,
+    round_id: u64,
+    bps_fee: u64,
+    flat_fee: u64,
+    max_per_round: u64,
+    amount: u64,
+    squares: [bool; 25],
+    total_to_deploy: u64,
+}
+
+/// One managed miner's accounts
+struct MinerAccounts<'a, 'info> {
+    managed_miner_auth_account_info: &'a AccountInfo<'info>,
+    ore_miner_account_info: &'a AccountInfo<'info>,
+    automation_account_info: &'a AccountInfo<'info>,
+    checkpoint_round_account_info: &'a AccountInfo<'info>,
+}
+
+/// Checkpoint (if needed), recycle (if needed) and deploy for one managed miner
+fn full_autodeploy_miner<'info>(
+    ctx: &FullAutodeployContext<'_, 'info>,
+    auth_id: u64,
+    miner: &MinerAccounts<'_, 'info>,
+) -> Result<(), ProgramError> {
+    let FullAutodeployContext {
+        signer,
+        manager_account_info,
+        fee_collector_account_info,
+        config_account_info,
+        board_account_info,
+        round_account_info,
+        treasury_account_info,
+        entropy_var_account_info,
+        ore_program,
+        entropy_program,
+        system_program_info,
+        board_round_id,
+        round_id,
+        bps_fee,
+        flat_fee,
+        max_per_round,
+        amount,
+        squares,
+        total_to_deploy,
+    } = *ctx;
+    let MinerAccounts {
+        managed_miner_auth_account_info,
+        ore_miner_account_info,
+        automation_account_info,
+        checkpoint_round_account_info,
+    } = *miner;
+
     // Verify managed_miner_auth PDA
     let (expected_managed_miner_auth, managed_miner_auth_bump) = Pubkey::find_program_address(
         &[
@@ -126,16 +291,6 @@ pub fn process_mm_full_autodeploy(
         return Err(EvoreError::InvalidPDA.into());
     }
 
-    // Get round and board for operations
-    let round = round_account_info.as_account::<Round>(&ore_api::id())?;
-    let board = board_account_info.as_account::<Board>(&ore_api::id())?;
-    let clock = Clock::get()?;
-
-    // Check if round hasn't ended
-    if clock.slot >= board.end_slot {
-        return Err(EvoreError::EndSlotReached.into());
-    }
-
     // Seeds for managed_miner_auth PDA
     let managed_miner_auth_seeds: &[&[u8]] = &[
         MANAGED_MINER_AUTH,
@@ -153,11 +308,11 @@ pub fn process_mm_full_autodeploy(
       let ore_miner = ore_miner_account_info.as_account::<Miner>(&ore_api::id())?;
       ore_miner.round_id
     };
-    
+
     // Check miner state only if it exists
     let (needs_checkpoint, is_already_deployed) = if !ore_miner_account_info.data_is_empty() {
         let miner = ore_miner_account_info.as_account::<Miner>(&ore_api::id())?;
-        (miner.checkpoint_id < checkpoint_round_id, miner.round_id == board.round_id)
+        (miner.checkpoint_id < checkpoint_round_id, miner.round_id == board_round_id)
     } else {
         (false, false) // First ever deploy, miner doesn't exist yet
     };
@@ -213,32 +368,16 @@ pub fn process_mm_full_autodeploy(
     // ==========================================================================
     // STEP 3: Deploy
     // ==========================================================================
-    // Convert squares_mask to [bool; 25]
-    let mut squares = [false; 25];
-    for i in 0..25 {
-        if (squares_mask >> i) & 1 == 1 {
-            squares[i] = true;
-        }
-    }
-
-    let num_squares = squares.iter().filter(|&&s| s).count() as u64;
-    if num_squares == 0 {
-        return Err(EvoreError::NoDeployments.into());
-    }
-
-    let total_to_deploy = amount.saturating_mul(num_squares);
-    if total_to_deploy == 0 {
-        return Err(EvoreError::NoDeployments.into());
-    }
-
     // Check max_per_round limit (includes already deployed amount for this round)
     if max_per_round > 0 {
         // Get already deployed amount for this round (if miner exists and is in current round)
         let already_deployed = if !ore_miner_account_info.data_is_empty() {
             let miner = ore_miner_account_info.as_account::<Miner>(&ore_api::id())?;
-            if miner.round_id == board.round_id {
+            if miner.round_id == board_round_id {
                 // Sum all deployed amounts for current round
-                miner.deployed.iter().sum::<u64>()
+                miner.deployed.iter()
+                    .try_fold(0u64, |acc, &d| acc.checked_add(d))
+                    .ok_or(EvoreError::ArithmeticOverflow)?
             } else {
                 0
             }
@@ -246,7 +385,7 @@ pub fn process_mm_full_autodeploy(
             0
         };
 
-        let total_for_round = already_deployed.saturating_add(total_to_deploy);
+        let total_for_round = already_deployed.checked_add(total_to_deploy).ok_or(EvoreError::ArithmeticOverflow)?;
         if total_for_round > max_per_round {
             return Err(EvoreError::ExceedsMaxPerRound.into());
         }
@@ -254,11 +393,11 @@ pub fn process_mm_full_autodeploy(
 
     // Calculate fees
     let bps_fee_amount = if bps_fee > 0 {
-        total_to_deploy.saturating_mul(bps_fee).saturating_div(10_000)
+        total_to_deploy.checked_mul(bps_fee).ok_or(EvoreError::ArithmeticOverflow)? / 10_000
     } else {
         0
     };
-    let deployer_fee = bps_fee_amount.saturating_add(flat_fee);
+    let deployer_fee = bps_fee_amount.checked_add(flat_fee).ok_or(EvoreError::ArithmeticOverflow)?;
     let protocol_fee = DEPLOY_FEE;
 
     // Calculate required balance
@@ -271,11 +410,11 @@ pub fn process_mm_full_autodeploy(
     };
 
     let required_balance = AUTH_PDA_RENT
-        .saturating_add(ore_api::CHECKPOINT_FEE)
-        .saturating_add(total_to_deploy)
-        .saturating_add(miner_rent)
-        .saturating_add(deployer_fee)
-        .saturating_add(protocol_fee);
+        .checked_add(ore_api::CHECKPOINT_FEE).ok_or(EvoreError::ArithmeticOverflow)?
+        .checked_add(total_to_deploy).ok_or(EvoreError::ArithmeticOverflow)?
+        .checked_add(miner_rent).ok_or(EvoreError::ArithmeticOverflow)?
+        .checked_add(deployer_fee).ok_or(EvoreError::ArithmeticOverflow)?
+        .checked_add(protocol_fee).ok_or(EvoreError::ArithmeticOverflow)?;
 
     // Check managed_miner_auth has enough funds
     let current_balance = managed_miner_auth_account_info.lamports();
@@ -341,12 +480,23 @@ pub fn process_mm_full_autodeploy(
             *managed_miner_auth_account_info.key,
             *managed_miner_auth_account_info.key,
             amount,
-            round.id,
+            round_id,
             squares,
         ),
         &deploy_accounts,
         &[managed_miner_auth_seeds],
     )?;
 
+    DeployEvent {
+        manager: *manager_account_info.key,
+        auth_id,
+        round_id,
+        total_deployed: total_to_deploy,
+        fee: if is_already_deployed { 0 } else { deployer_fee.saturating_add(protocol_fee) },
+        squares_mask: squares_to_mask(&squares),
+        _padding: [0; 4],
+    }
+    .log();
+
     Ok(())
 }
@@ -0,0 +1,58 @@
+use solana_program::{account_info::AccountInfo, program_error::ProgramError};
+use steel::*;
+
+use crate::{
+    error::EvoreError,
+    instruction::MMPreviewRecycle,
+    ore_api::{self, Miner},
+    state::{managed_miner_auth_pda, Manager},
+};
+
+/// Process MMPreviewRecycle instruction
+/// Read-only dry run of RecycleSol: sets the miner's claimable rewards_sol (u64 LE) as
+/// return data, 0 when the ORE miner doesn't exist yet. No lamports move.
+pub fn process_mm_preview_recycle(
+    accounts: &[AccountInfo],
+    instruction_data: &[u8],
+) -> Result<(), ProgramError> {
+    let args = MMPreviewRecycle::try_from_bytes(instruction_data)?;
+    let auth_id = u64::from_le_bytes(args.auth_id);
+
+    let [
+        signer,                          // 0: signer (fee payer)
+        manager_account_info,            // 1: manager
+        managed_miner_auth_account_info, // 2: managed_miner_auth PDA
+        ore_miner_account_info,          // 3: ore_miner
+    ] = accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+
+    if !signer.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    if manager_account_info.data_is_empty() {
+        return Err(EvoreError::ManagerNotInitialized.into());
+    }
+
+    let _manager = Manager::load(manager_account_info)?;
+
+    if managed_miner_auth_pda(*manager_account_info.key, auth_id).0 != *managed_miner_auth_account_info.key {
+        return Err(EvoreError::InvalidPDA.into());
+    }
+
+    if ore_api::miner_pda(*managed_miner_auth_account_info.key).0 != *ore_miner_account_info.key {
+        return Err(EvoreError::InvalidPDA.into());
+    }
+
+    // Same amount RecycleSol would claim
+    let recyclable = if ore_miner_account_info.data_is_empty() {
+        0
+    } else {
+        ore_miner_account_info.as_account::<Miner>(&ore_api::id())?.rewards_sol
+    };
+
+    solana_program::program::set_return_data(&recyclable.to_le_bytes());
+
+    Ok(())
+}
@@ -0,0 +1,59 @@
+use solana_program::{account_info::AccountInfo, program_error::ProgramError};
+use steel::*;
+
+use crate::{
+    error::EvoreError,
+    instruction::MMStatus,
+    ore_api::{self, Miner},
+    state::{managed_miner_auth_pda, Manager},
+    view,
+};
+
+/// Process MMStatus instruction
+/// Read-only: sets the managed miner's `view::MMStatus` as return data. The ORE fields are 0
+/// when the ORE miner doesn't exist yet.
+pub fn process_mm_status(
+    accounts: &[AccountInfo],
+    instruction_data: &[u8],
+) -> Result<(), ProgramError> {
+    let args = MMStatus::try_from_bytes(instruction_data)?;
+    let auth_id = u64::from_le_bytes(args.auth_id);
+
+    let [
+        signer,                          // 0: signer (fee payer)
+        manager_account_info,            // 1: manager
+        managed_miner_auth_account_info, // 2: managed_miner_auth PDA
+        ore_miner_account_info,          // 3: ore_miner
+    ] = accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+
+    if !signer.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    if manager_account_info.data_is_empty() {
+        return Err(EvoreError::ManagerNotInitialized.into());
+    }
+
+    let _manager = Manager::load(manager_account_info)?;
+
+    if managed_miner_auth_pda(*manager_account_info.key, auth_id).0 != *managed_miner_auth_account_info.key {
+        return Err(EvoreError::InvalidPDA.into());
+    }
+
+    if ore_api::miner_pda(*managed_miner_auth_account_info.key).0 != *ore_miner_account_info.key {
+        return Err(EvoreError::InvalidPDA.into());
+    }
+
+    let miner = if ore_miner_account_info.data_is_empty() {
+        None
+    } else {
+        Some(ore_miner_account_info.as_account::<Miner>(&ore_api::id())?)
+    };
+    let status = view::MMStatus::new(managed_miner_auth_account_info.lamports(), miner);
+
+    solana_program::program::set_return_data(bytemuck::bytes_of(&status));
+
+    Ok(())
+}
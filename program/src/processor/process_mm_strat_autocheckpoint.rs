@@ -41,7 +41,7 @@ pub fn process_mm_strat_autocheckpoint(
         return Err(EvoreError::ManagerNotInitialized.into());
     }
 
-    let _manager = manager_account_info.as_account::<Manager>(&crate::id())?;
+    let _manager = Manager::load(manager_account_info)?;
 
     if strat_deployer_account_info.data_is_empty() {
         return Err(EvoreError::StratDeployerNotInitialized.into());
@@ -56,7 +56,7 @@ pub fn process_mm_strat_autocheckpoint(
         return Err(EvoreError::InvalidPDA.into());
     }
 
-    let strat_deployer = strat_deployer_account_info.as_account::<StrategyDeployer>(&crate::id())?;
+    let strat_deployer = StrategyDeployer::load(strat_deployer_account_info)?;
 
     if strat_deployer.deploy_authority != *signer.key {
         return Err(EvoreError::InvalidDeployAuthority.into());
@@ -1,6 +1,5 @@
 use solana_program::{
     account_info::AccountInfo,
-    instruction::{AccountMeta, Instruction},
     program_error::ProgramError,
     pubkey::Pubkey,
     system_program,
@@ -11,9 +10,14 @@ use crate::{
     consts::{DEPLOY_FEE, FEE_COLLECTOR, MANAGED_MINER_AUTH, STRATEGY_DEPLOYER},
     entropy_api,
     error::EvoreError,
+    events::DeployEvent,
     instruction::MMStratFullAutodeploy,
     ore_api::{self, Board, Miner, Round},
-    processor::strategy_dispatch::{dispatch_strategy, StrategyResult},
+    processor::{
+        process_mm_deploy::deploy_batches_with_automation,
+        strategy_dispatch::{dispatch_strategy, StrategyResult},
+    },
+    squares::squares_to_mask,
     state::{Manager, StrategyDeployer},
 };
 
@@ -27,6 +31,12 @@ pub fn process_mm_strat_full_autodeploy(
     let squares_mask = u32::from_le_bytes(args.squares_mask);
     let extra = u32::from_le_bytes(args.extra);
 
+    // Optional trailing accounts: the round's top miner (MirrorTopMiner strategy) and ORE's
+    // program data (pinned ORE deploy slot), told apart by owner
+    let (accounts, trailing) = accounts.split_at(accounts.len().min(16));
+    let ore_program_data_info = trailing.iter().find(|a| *a.owner == ore_api::BPF_LOADER_UPGRADEABLE);
+    let top_miner_info = trailing.iter().find(|a| *a.owner != ore_api::BPF_LOADER_UPGRADEABLE);
+
     let [
         signer,
         manager_account_info,
@@ -72,7 +82,7 @@ pub fn process_mm_strat_full_autodeploy(
         return Err(EvoreError::ManagerNotInitialized.into());
     }
 
-    let _manager = manager_account_info.as_account::<Manager>(&crate::id())?;
+    let manager = Manager::load(manager_account_info)?;
 
     if strat_deployer_account_info.data_is_empty() {
         return Err(EvoreError::StratDeployerNotInitialized.into());
@@ -87,15 +97,20 @@ pub fn process_mm_strat_full_autodeploy(
         return Err(EvoreError::InvalidPDA.into());
     }
 
-    let strat_deployer = strat_deployer_account_info.as_account::<StrategyDeployer>(&crate::id())?;
+    let strat_deployer = StrategyDeployer::load(strat_deployer_account_info)?;
     let deploy_authority = strat_deployer.deploy_authority;
     let bps_fee = strat_deployer.bps_fee;
     let flat_fee = strat_deployer.flat_fee;
     let expected_bps_fee = strat_deployer.expected_bps_fee;
     let expected_flat_fee = strat_deployer.expected_flat_fee;
     let max_per_round = strat_deployer.max_per_round;
-    let strategy_type = strat_deployer.strategy_type;
-    let strategy_data = strat_deployer.strategy_data;
+    let max_loss_per_round = strat_deployer.max_loss_per_round;
+    let strategy = strat_deployer.decode_strategy()?;
+    let max_squares_per_deploy = strat_deployer.max_squares_per_deploy;
+    let blocked_squares = strat_deployer.blocked_squares();
+
+    // A pinned deployer only deploys into the ORE build it was set up against
+    strat_deployer.check_ore_program_slot(ore_program_data_info)?;
 
     if deploy_authority != *signer.key {
         return Err(EvoreError::InvalidDeployAuthority.into());
@@ -198,16 +213,35 @@ pub fn process_mm_strat_full_autodeploy(
     // ======================================================================
     // STEP 3: Strategy dispatch
     // ======================================================================
-    let StrategyResult { mut batches, total_to_deploy, needs_automation } = dispatch_strategy(
-        strategy_type,
-        &strategy_data,
+    let strategy_result = dispatch_strategy(
+        strategy,
         amount,
         squares_mask,
         extra,
         &board,
         &round,
         &clock,
-    )?;
+        top_miner_info,
+    )?
+    .block_squares(blocked_squares)?
+    .cap_squares(max_squares_per_deploy, round);
+
+    if max_loss_per_round > 0 {
+        let already_deployed = if !ore_miner_account_info.data_is_empty() {
+            let miner = ore_miner_account_info.as_account::<Miner>(&ore_api::id())?;
+            if miner.round_id == board.round_id { miner.deployed } else { [0; 25] }
+        } else {
+            [0; 25]
+        };
+
+        if strategy_result.worst_case_loss(&already_deployed) > max_loss_per_round {
+            return Err(EvoreError::ExceedsMaxLoss.into());
+        }
+    }
+
+    let StrategyResult { mut batches, total_to_deploy, needs_automation } = strategy_result;
+
+    manager.check_deploy_ceiling(total_to_deploy)?;
 
     // ======================================================================
     // STEP 4: Max per round check
@@ -216,7 +250,9 @@ pub fn process_mm_strat_full_autodeploy(
         let already_deployed = if !ore_miner_account_info.data_is_empty() {
             let miner = ore_miner_account_info.as_account::<Miner>(&ore_api::id())?;
             if miner.round_id == board.round_id {
-                miner.deployed.iter().sum::<u64>()
+                miner.deployed.iter()
+                    .try_fold(0u64, |acc, &d| acc.checked_add(d))
+                    .ok_or(EvoreError::ArithmeticOverflow)?
             } else {
                 0
             }
@@ -224,7 +260,7 @@ pub fn process_mm_strat_full_autodeploy(
             0
         };
 
-        let total_for_round = already_deployed.saturating_add(total_to_deploy);
+        let total_for_round = already_deployed.checked_add(total_to_deploy).ok_or(EvoreError::ArithmeticOverflow)?;
         if total_for_round > max_per_round {
             return Err(EvoreError::ExceedsMaxPerRound.into());
         }
@@ -234,12 +270,12 @@ pub fn process_mm_strat_full_autodeploy(
     // STEP 5: Fee calculation + balance check
     // ======================================================================
     let bps_fee_amount = if bps_fee > 0 {
-        total_to_deploy.saturating_mul(bps_fee).saturating_div(10_000)
+        total_to_deploy.checked_mul(bps_fee).ok_or(EvoreError::ArithmeticOverflow)? / 10_000
     } else {
         0
     };
 
-    let deployer_fee = bps_fee_amount.saturating_add(flat_fee);
+    let deployer_fee = bps_fee_amount.checked_add(flat_fee).ok_or(EvoreError::ArithmeticOverflow)?;
     let protocol_fee = DEPLOY_FEE;
 
     const AUTH_PDA_RENT: u64 = 890_880;
@@ -258,12 +294,12 @@ pub fn process_mm_strat_full_autodeploy(
     };
 
     let required_balance = AUTH_PDA_RENT
-        .saturating_add(ore_api::CHECKPOINT_FEE)
-        .saturating_add(total_to_deploy)
-        .saturating_add(miner_rent)
-        .saturating_add(deployer_fee)
-        .saturating_add(protocol_fee)
-        .saturating_add(automation_rent);
+        .checked_add(ore_api::CHECKPOINT_FEE).ok_or(EvoreError::ArithmeticOverflow)?
+        .checked_add(total_to_deploy).ok_or(EvoreError::ArithmeticOverflow)?
+        .checked_add(miner_rent).ok_or(EvoreError::ArithmeticOverflow)?
+        .checked_add(deployer_fee).ok_or(EvoreError::ArithmeticOverflow)?
+        .checked_add(protocol_fee).ok_or(EvoreError::ArithmeticOverflow)?
+        .checked_add(automation_rent).ok_or(EvoreError::ArithmeticOverflow)?;
 
     let current_balance = managed_miner_auth_account_info.lamports();
     if current_balance < required_balance {
@@ -324,36 +360,25 @@ pub fn process_mm_strat_full_autodeploy(
     ];
 
     if needs_automation {
-        batches.sort_by_key(|b| b.amount);
-        let max_batch_amount = batches.last().map(|b| b.amount).unwrap_or(0);
-
         let deposit = managed_miner_auth_account_info.lamports()
             .saturating_sub(AUTH_PDA_RENT)
             .saturating_sub(automation_rent)
             .saturating_sub(miner_rent);
 
-        solana_program::program::invoke_signed(
-            &ore_api::automate(
-                *managed_miner_auth_account_info.key,
-                max_batch_amount,
-                deposit,
-                *managed_miner_auth_account_info.key,
-                0, 0, 2, false,
-            ),
-            &[
-                managed_miner_auth_account_info.clone(),
-                automation_account_info.clone(),
-                managed_miner_auth_account_info.clone(),
-                ore_miner_account_info.clone(),
-                system_program_info.clone(),
-            ],
-            &[managed_miner_auth_seeds],
+        deploy_batches_with_automation(
+            &mut batches,
+            round.id,
+            deposit,
+            managed_miner_auth_account_info,
+            automation_account_info,
+            ore_miner_account_info,
+            system_program_info,
+            &deploy_accounts,
+            managed_miner_auth_seeds,
         )?;
-
-        for batch in &batches {
-            if batch.amount == 0 {
-                continue;
-            }
+    } else {
+        // Non-automation strategies may return more than one batch (e.g. split remainder square)
+        for batch in batches.iter().filter(|b| b.amount > 0) {
             solana_program::program::invoke_signed(
                 &ore_api::deploy(
                     *managed_miner_auth_account_info.key,
@@ -366,53 +391,21 @@ pub fn process_mm_strat_full_autodeploy(
                 &[managed_miner_auth_seeds],
             )?;
         }
+    }
 
-        if !automation_account_info.data_is_empty() {
-            let close_ix = Instruction {
-                program_id: ore_api::id(),
-                accounts: vec![
-                    AccountMeta::new(*managed_miner_auth_account_info.key, true),
-                    AccountMeta::new(*automation_account_info.key, false),
-                    AccountMeta::new_readonly(Pubkey::default(), false),
-                    AccountMeta::new(*ore_miner_account_info.key, false),
-                    AccountMeta::new_readonly(*system_program_info.key, false),
-                ],
-                data: ore_api::Automate {
-                    amount: 0u64.to_le_bytes(),
-                    deposit: 0u64.to_le_bytes(),
-                    fee: 0u64.to_le_bytes(),
-                    mask: 0u64.to_le_bytes(),
-                    strategy: 0,
-                    reload: 0u64.to_le_bytes(),
-                }
-                .to_bytes(),
-            };
-            solana_program::program::invoke_signed(
-                &close_ix,
-                &[
-                    managed_miner_auth_account_info.clone(),
-                    automation_account_info.clone(),
-                    system_program_info.clone(),
-                    ore_miner_account_info.clone(),
-                    system_program_info.clone(),
-                ],
-                &[managed_miner_auth_seeds],
-            )?;
-        }
-    } else {
-        let batch = &batches[0];
-        solana_program::program::invoke_signed(
-            &ore_api::deploy(
-                *managed_miner_auth_account_info.key,
-                *managed_miner_auth_account_info.key,
-                batch.amount,
-                round.id,
-                batch.squares,
-            ),
-            &deploy_accounts,
-            &[managed_miner_auth_seeds],
-        )?;
+    DeployEvent {
+        manager: *manager_account_info.key,
+        auth_id,
+        round_id: round.id,
+        total_deployed: total_to_deploy,
+        fee: if is_already_deployed { 0 } else { deployer_fee.saturating_add(protocol_fee) },
+        squares_mask: batches
+            .iter()
+            .filter(|b| b.amount > 0)
+            .fold(0u32, |mask, b| mask | squares_to_mask(&b.squares)),
+        _padding: [0; 4],
     }
+    .log();
 
     Ok(())
 }
@@ -56,7 +56,7 @@ pub fn process_recycle_sol(
         return Err(EvoreError::ManagerNotInitialized.into());
     }
 
-    let _manager = manager_account_info.as_account::<Manager>(&crate::id())?;
+    let _manager = Manager::load(manager_account_info)?;
 
     // Verify deployer is initialized
     if deployer_account_info.data_is_empty() {
@@ -74,10 +74,8 @@ pub fn process_recycle_sol(
     }
 
     // Load deployer and verify signer is deploy_authority
-    let data = deployer_account_info.try_borrow_data()?;
-    let deployer = Deployer::try_from_bytes(&data[8..])?;
+    let deployer = deployer_account_info.as_account::<Deployer>(&crate::id())?;
     let deploy_authority = deployer.deploy_authority;
-    drop(data);
 
     if deploy_authority != *signer.key {
         return Err(EvoreError::InvalidDeployAuthority.into());
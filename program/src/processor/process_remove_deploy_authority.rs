@@ -0,0 +1,63 @@
+use solana_program::{account_info::AccountInfo, program_error::ProgramError};
+use steel::*;
+
+use crate::{
+    consts::DEPLOY_AUTHORITY_ALLOWLIST,
+    error::EvoreError,
+    state::{DeployAuthorityAllowlist, Manager},
+};
+
+pub fn process_remove_deploy_authority(
+    accounts: &[AccountInfo],
+    _instruction_data: &[u8],
+) -> Result<(), ProgramError> {
+    let [signer, manager_account_info, allowlist_account_info, deploy_authority_info] = accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+
+    // Verify signer
+    if !signer.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    // Verify manager is initialized and signer is the authority
+    if manager_account_info.data_is_empty() {
+        return Err(EvoreError::ManagerNotInitialized.into());
+    }
+
+    let manager = Manager::load(manager_account_info)?;
+
+    if manager.authority != *signer.key {
+        return Err(EvoreError::NotAuthorized.into());
+    }
+
+    // Derive and verify allowlist PDA
+    let (allowlist_pda, _) = Pubkey::find_program_address(
+        &[DEPLOY_AUTHORITY_ALLOWLIST, manager_account_info.key.as_ref()],
+        &crate::id(),
+    );
+
+    if allowlist_pda != *allowlist_account_info.key {
+        return Err(EvoreError::InvalidPDA.into());
+    }
+
+    if allowlist_account_info.data_is_empty() {
+        return Err(EvoreError::AuthorityNotAllowlisted.into());
+    }
+
+    let allowlist = allowlist_account_info.as_account_mut::<DeployAuthorityAllowlist>(&crate::id())?;
+
+    let index = allowlist
+        .entries()
+        .iter()
+        .position(|authority| authority == deploy_authority_info.key)
+        .ok_or(EvoreError::AuthorityNotAllowlisted)?;
+
+    // Swap the last entry into the freed slot so used entries stay contiguous
+    let last = allowlist.entries().len() - 1;
+    allowlist.authorities[index] = allowlist.authorities[last];
+    allowlist.authorities[last] = Pubkey::default();
+    allowlist.count -= 1;
+
+    Ok(())
+}
@@ -0,0 +1,99 @@
+use solana_program::{
+    account_info::AccountInfo, program_error::ProgramError, system_program,
+};
+use steel::*;
+
+use crate::{
+    consts::{DEPLOY_SCHEDULE, MAX_SCHEDULE_ENTRIES},
+    error::EvoreError,
+    instruction::SetDeploySchedule,
+    state::{DeploySchedule, EvoreAccount, Manager},
+};
+
+pub fn process_set_deploy_schedule(
+    accounts: &[AccountInfo],
+    instruction_data: &[u8],
+) -> Result<(), ProgramError> {
+    let args = SetDeploySchedule::try_from_bytes(instruction_data)?;
+    let len = u64::from_le_bytes(args.len);
+
+    if len > MAX_SCHEDULE_ENTRIES as u64 {
+        return Err(EvoreError::InvalidDeploySchedule.into());
+    }
+
+    let [
+        signer,
+        manager_account_info,
+        schedule_account_info,
+        system_program_info,
+    ] = accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+
+    // Verify signer
+    if !signer.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    // Verify system program
+    if *system_program_info.key != system_program::id() {
+        return Err(ProgramError::IncorrectProgramId);
+    }
+
+    // Verify manager is initialized and signer is the authority
+    if manager_account_info.data_is_empty() {
+        return Err(EvoreError::ManagerNotInitialized.into());
+    }
+
+    let manager = Manager::load(manager_account_info)?;
+
+    if manager.authority != *signer.key {
+        return Err(EvoreError::NotAuthorized.into());
+    }
+
+    // Derive and verify schedule PDA
+    let (schedule_pda, schedule_bump) = Pubkey::find_program_address(
+        &[DEPLOY_SCHEDULE, manager_account_info.key.as_ref()],
+        &crate::id(),
+    );
+
+    if schedule_pda != *schedule_account_info.key {
+        return Err(EvoreError::InvalidPDA.into());
+    }
+
+    // Create the schedule on first use
+    if schedule_account_info.data_is_empty() {
+        let schedule_size = 8 + std::mem::size_of::<DeploySchedule>();
+        let rent = solana_program::rent::Rent::get()?;
+        let lamports = rent.minimum_balance(schedule_size);
+
+        solana_program::program::invoke_signed(
+            &solana_program::system_instruction::create_account(
+                signer.key,
+                schedule_account_info.key,
+                lamports,
+                schedule_size as u64,
+                &crate::id(),
+            ),
+            &[signer.clone(), schedule_account_info.clone(), system_program_info.clone()],
+            &[&[DEPLOY_SCHEDULE, manager_account_info.key.as_ref(), &[schedule_bump]]],
+        )?;
+
+        let mut data = schedule_account_info.try_borrow_mut_data()?;
+        let discr = (EvoreAccount::DeploySchedule as u64).to_le_bytes();
+        data[..8].copy_from_slice(&discr);
+    }
+
+    // Overwrite the whole schedule, unused entries are zeroed
+    let mut amounts = [0u64; MAX_SCHEDULE_ENTRIES];
+    for (amount, encoded) in amounts.iter_mut().zip(args.amounts.iter()).take(len as usize) {
+        *amount = u64::from_le_bytes(*encoded);
+    }
+
+    let schedule = schedule_account_info.as_account_mut::<DeploySchedule>(&crate::id())?;
+    schedule.manager_key = *manager_account_info.key;
+    schedule.len = len;
+    schedule.amounts = amounts;
+
+    Ok(())
+}
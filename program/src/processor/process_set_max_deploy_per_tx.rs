@@ -0,0 +1,46 @@
+use solana_program::{
+    account_info::AccountInfo, program_error::ProgramError,
+};
+use steel::*;
+
+use crate::{error::EvoreError, instruction::SetMaxDeployPerTx, state::Manager};
+
+/// Process SetMaxDeployPerTx instruction
+/// Writes the manager's per-instruction deploy ceiling; only the manager authority may set it
+pub fn process_set_max_deploy_per_tx(
+    accounts: &[AccountInfo],
+    instruction_data: &[u8],
+) -> Result<(), ProgramError> {
+    let args = SetMaxDeployPerTx::try_from_bytes(instruction_data)?;
+    let max_deploy_per_tx = u64::from_le_bytes(args.max_deploy_per_tx);
+
+    let [
+        signer,               // 0: signer (manager authority)
+        manager_account_info, // 1: manager
+    ] = accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+
+    if !signer.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    if !manager_account_info.is_writable {
+        return Err(ProgramError::InvalidAccountData);
+    }
+
+    if manager_account_info.data_is_empty() {
+        return Err(EvoreError::ManagerNotInitialized.into());
+    }
+
+    let manager = manager_account_info
+        .as_account_mut::<Manager>(&crate::id())?;
+
+    if manager.authority != *signer.key {
+        return Err(EvoreError::NotAuthorized.into());
+    }
+
+    manager.max_deploy_per_tx = max_deploy_per_tx;
+
+    Ok(())
+}
@@ -1,4 +1,4 @@
-use solana_program::{account_info::AccountInfo, program_error::ProgramError};
+use solana_program::{account_info::AccountInfo, program_error::ProgramError, pubkey::Pubkey};
 use steel::*;
 
 use crate::{error::EvoreError, state::Manager};
@@ -28,8 +28,9 @@ pub fn process_transfer_manager(
         return Err(EvoreError::NotAuthorized.into());
     }
 
-    // 4. Update authority to new pubkey
+    // 4. Update authority to new pubkey, dropping any two-step transfer in flight
     manager.authority = *new_authority_info.key;
+    manager.pending_authority = Pubkey::default();
 
     Ok(())
 }
@@ -4,8 +4,12 @@ use solana_program::{
 use steel::*;
 
 use crate::{
-    consts::DEPLOYER,
+    consts::{
+        DEPLOYER, UPDATE_ALL_FIELDS, UPDATE_BPS_FEE, UPDATE_EXPECTED_BPS_FEE,
+        UPDATE_EXPECTED_FLAT_FEE, UPDATE_FLAT_FEE, UPDATE_MAX_PER_ROUND,
+    },
     error::EvoreError,
+    processor::allowlist::check_deploy_authority_allowed,
     instruction::UpdateDeployer,
     state::{Deployer, Manager},
 };
@@ -14,7 +18,16 @@ pub fn process_update_deployer(
     accounts: &[AccountInfo],
     instruction_data: &[u8],
 ) -> Result<(), ProgramError> {
-    let args = UpdateDeployer::try_from_bytes(instruction_data)?;
+    // Legacy clients send the payload without update_mask; treat that as "update everything"
+    let legacy_len = std::mem::size_of::<UpdateDeployer>() - 8;
+    let (args, update_mask) = if instruction_data.len() == legacy_len {
+        let mut padded = [0u8; std::mem::size_of::<UpdateDeployer>()];
+        padded[..legacy_len].copy_from_slice(instruction_data);
+        (*UpdateDeployer::try_from_bytes(&padded)?, UPDATE_ALL_FIELDS)
+    } else {
+        let args = *UpdateDeployer::try_from_bytes(instruction_data)?;
+        (args, u64::from_le_bytes(args.update_mask))
+    };
     let new_bps_fee = u64::from_le_bytes(args.bps_fee);
     let new_flat_fee = u64::from_le_bytes(args.flat_fee);
     let new_expected_bps_fee = u64::from_le_bytes(args.expected_bps_fee);
@@ -27,6 +40,7 @@ pub fn process_update_deployer(
         deployer_account_info,
         new_deploy_authority_info,
         _system_program_info,
+        optional_accounts @ ..,
     ] = accounts else {
         return Err(ProgramError::NotEnoughAccountKeys);
     };
@@ -41,7 +55,7 @@ pub fn process_update_deployer(
         return Err(EvoreError::ManagerNotInitialized.into());
     }
 
-    let manager = manager_account_info.as_account::<Manager>(&crate::id())?;
+    let manager = Manager::load(manager_account_info)?;
 
     // Verify deployer is initialized
     if deployer_account_info.data_is_empty() {
@@ -70,6 +84,18 @@ pub fn process_update_deployer(
         return Err(EvoreError::NotAuthorized.into());
     }
 
+    // A new deploy authority must pass the manager's allowlist, same as on creation.
+    // The allowlist PDA is only required when the authority actually changes, so
+    // fee-only updates from older clients keep working.
+    if *new_deploy_authority_info.key != current_deploy_authority {
+        let allowlist_account_info = optional_accounts.first().ok_or(ProgramError::NotEnoughAccountKeys)?;
+        check_deploy_authority_allowed(
+            manager_account_info.key,
+            allowlist_account_info,
+            new_deploy_authority_info.key,
+        )?;
+    }
+
     // Update deployer data
     let mut data = deployer_account_info.try_borrow_mut_data()?;
     
@@ -79,11 +105,17 @@ pub fn process_update_deployer(
         // deploy_authority at offset 40
         data[40..72].copy_from_slice(new_deploy_authority_info.key.as_ref());
         // expected_bps_fee at offset 88
-        data[88..96].copy_from_slice(&new_expected_bps_fee.to_le_bytes());
+        if update_mask & UPDATE_EXPECTED_BPS_FEE != 0 {
+            data[88..96].copy_from_slice(&new_expected_bps_fee.to_le_bytes());
+        }
         // expected_flat_fee at offset 96
-        data[96..104].copy_from_slice(&new_expected_flat_fee.to_le_bytes());
+        if update_mask & UPDATE_EXPECTED_FLAT_FEE != 0 {
+            data[96..104].copy_from_slice(&new_expected_flat_fee.to_le_bytes());
+        }
         // max_per_round at offset 104
-        data[104..112].copy_from_slice(&new_max_per_round.to_le_bytes());
+        if update_mask & UPDATE_MAX_PER_ROUND != 0 {
+            data[104..112].copy_from_slice(&new_max_per_round.to_le_bytes());
+        }
     }
     
     if is_deploy_authority {
@@ -92,9 +124,13 @@ pub fn process_update_deployer(
         // deploy_authority at offset 40
         data[40..72].copy_from_slice(new_deploy_authority_info.key.as_ref());
         // bps_fee at offset 72
-        data[72..80].copy_from_slice(&new_bps_fee.to_le_bytes());
+        if update_mask & UPDATE_BPS_FEE != 0 {
+            data[72..80].copy_from_slice(&new_bps_fee.to_le_bytes());
+        }
         // flat_fee at offset 80
-        data[80..88].copy_from_slice(&new_flat_fee.to_le_bytes());
+        if update_mask & UPDATE_FLAT_FEE != 0 {
+            data[80..88].copy_from_slice(&new_flat_fee.to_le_bytes());
+        }
     }
 
     Ok(())
@@ -1,13 +1,15 @@
 use solana_program::{
-    account_info::AccountInfo, program_error::ProgramError, pubkey::Pubkey,
+    account_info::AccountInfo, program_error::ProgramError, pubkey::Pubkey, rent::Rent,
+    sysvar::Sysvar,
 };
-use steel::*;
 
 use crate::{
     consts::STRATEGY_DEPLOYER,
     error::EvoreError,
+    processor::allowlist::check_deploy_authority_allowed,
     instruction::UpdateStratDeployer,
-    state::{Manager, StrategyDeployer},
+    squares::{validate_squares_count, validate_squares_mask},
+    state::{Manager, StrategyDeployer, LEGACY_STRATEGY_DEPLOYER_SIZE},
     validation::{StrategyType, validate_strategy_data},
 };
 
@@ -23,13 +25,17 @@ pub fn process_update_strat_deployer(
     let new_max_per_round = u64::from_le_bytes(args.max_per_round);
     let new_strategy_type = args.strategy_type;
     let new_strategy_data = args.strategy_data;
+    let new_max_squares_per_deploy = args.max_squares_per_deploy;
+    let new_blocked_squares_mask = args.blocked_squares_mask;
+    let new_max_loss_per_round = u64::from_le_bytes(args.max_loss_per_round);
 
     let [
         signer,
         manager_account_info,
         strat_deployer_account_info,
         new_deploy_authority_info,
-        _system_program_info,
+        system_program_info,
+        optional_accounts @ ..,
     ] = accounts else {
         return Err(ProgramError::NotEnoughAccountKeys);
     };
@@ -42,7 +48,7 @@ pub fn process_update_strat_deployer(
         return Err(EvoreError::ManagerNotInitialized.into());
     }
 
-    let manager = manager_account_info.as_account::<Manager>(&crate::id())?;
+    let manager = Manager::load(manager_account_info)?;
 
     if strat_deployer_account_info.data_is_empty() {
         return Err(EvoreError::StratDeployerNotInitialized.into());
@@ -57,7 +63,7 @@ pub fn process_update_strat_deployer(
         return Err(EvoreError::InvalidPDA.into());
     }
 
-    let strat_deployer = strat_deployer_account_info.as_account::<StrategyDeployer>(&crate::id())?;
+    let strat_deployer = StrategyDeployer::load(strat_deployer_account_info)?;
     let current_deploy_authority = strat_deployer.deploy_authority;
 
     let is_manager_authority = manager.authority == *signer.key;
@@ -67,11 +73,50 @@ pub fn process_update_strat_deployer(
         return Err(EvoreError::NotAuthorized.into());
     }
 
-    let mut data = strat_deployer_account_info.try_borrow_mut_data()?;
+    // A new deploy authority must pass the manager's allowlist, same as on creation.
+    // The allowlist PDA is only required when the authority actually changes, so
+    // fee-only updates from older clients keep working.
+    if *new_deploy_authority_info.key != current_deploy_authority {
+        let allowlist_account_info = optional_accounts.first().ok_or(ProgramError::NotEnoughAccountKeys)?;
+        check_deploy_authority_allowed(
+            manager_account_info.key,
+            allowlist_account_info,
+            new_deploy_authority_info.key,
+        )?;
+    }
 
     if is_manager_authority {
         let strategy_type = StrategyType::try_from(new_strategy_type)?;
         validate_strategy_data(strategy_type, &new_strategy_data)?;
+        validate_squares_count(new_max_squares_per_deploy as u64)?;
+        validate_squares_mask(u32::from_le_bytes(new_blocked_squares_mask) as u64)?;
+
+        // Legacy accounts have no room for max_loss_per_round, grow them first
+        let new_size = 8 + std::mem::size_of::<StrategyDeployer>();
+        if strat_deployer_account_info.data_len() == LEGACY_STRATEGY_DEPLOYER_SIZE {
+            let required = Rent::get()?.minimum_balance(new_size);
+            let shortfall = required.saturating_sub(strat_deployer_account_info.lamports());
+            if shortfall > 0 {
+                solana_program::program::invoke(
+                    &solana_program::system_instruction::transfer(
+                        signer.key,
+                        strat_deployer_account_info.key,
+                        shortfall,
+                    ),
+                    &[
+                        signer.clone(),
+                        strat_deployer_account_info.clone(),
+                        system_program_info.clone(),
+                    ],
+                )?;
+            }
+            strat_deployer_account_info.resize(new_size)?;
+        }
+    }
+
+    let mut data = strat_deployer_account_info.try_borrow_mut_data()?;
+
+    if is_manager_authority {
 
         data[40..72].copy_from_slice(new_deploy_authority_info.key.as_ref());
         data[88..96].copy_from_slice(&new_expected_bps_fee.to_le_bytes());
@@ -79,6 +124,9 @@ pub fn process_update_strat_deployer(
         data[104..112].copy_from_slice(&new_max_per_round.to_le_bytes());
         data[112..113].copy_from_slice(&[new_strategy_type]);
         data[113..177].copy_from_slice(&new_strategy_data);
+        data[177] = new_max_squares_per_deploy;
+        data[178..182].copy_from_slice(&new_blocked_squares_mask);
+        data[184..192].copy_from_slice(&new_max_loss_per_round.to_le_bytes());
     }
 
     if is_deploy_authority {
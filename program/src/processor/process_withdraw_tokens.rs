@@ -4,7 +4,8 @@ use solana_program::{
 use steel::*;
 
 use crate::{
-    error::EvoreError, instruction::WithdrawTokens, state::Manager
+    error::EvoreError, instruction::WithdrawTokens, state::Manager,
+    validation::validate_token_program,
 };
 
 pub fn process_withdraw_tokens(
@@ -14,6 +15,12 @@ pub fn process_withdraw_tokens(
     let args = WithdrawTokens::try_from_bytes(instruction_data)?;
     let auth_id = u64::from_le_bytes(args.auth_id);
 
+    // Optional trailing account: owner of the destination ATA (defaults to the signer)
+    let (accounts, destination_owner) = match accounts.len() {
+        10 => (&accounts[..9], Some(&accounts[9])),
+        _ => (accounts, None),
+    };
+
     let [
         signer,
         manager_account_info,
@@ -56,21 +63,21 @@ pub fn process_withdraw_tokens(
         return Err(ProgramError::IncorrectProgramId);
     }
 
-    if *spl_program.key != spl_token::id() {
-        return Err(ProgramError::IncorrectProgramId);
-    }
+    // Classic SPL Token or Token-2022, whichever owns the mint
+    validate_token_program(mint_account_info, spl_program)?;
 
     if *spl_ata_program.key != spl_associated_token_account::id() {
         return Err(ProgramError::IncorrectProgramId);
     }
 
-    let manager = manager_account_info
-        .as_account::<Manager>(&crate::id())?;
+    let manager = Manager::load(manager_account_info)?;
 
     if manager.authority != *signer.key {
         return Err(EvoreError::NotAuthorized.into());
     }
 
+    let destination_owner = destination_owner.unwrap_or(signer);
+
     let managed_miner_auth_pda = Pubkey::create_program_address(
         &[
             crate::consts::MANAGED_MINER_AUTH,
@@ -91,7 +98,7 @@ pub fn process_withdraw_tokens(
     if destination_ata_account_info.data_is_empty() {
         create_associated_token_account(
             signer,
-            signer,
+            destination_owner,
             destination_ata_account_info,
             mint_account_info,
             system_program,
@@ -100,15 +107,20 @@ pub fn process_withdraw_tokens(
         )?;
     } else {
         destination_ata_account_info
-            .as_associated_token_account(signer.key, mint_account_info.key)?;
+            .as_associated_token_account(destination_owner.key, mint_account_info.key)?;
     }
 
-    transfer_signed_with_bump(
+    // transfer_checked: Token-2022 rejects plain transfers for mints with some extensions
+    let decimals = mint_account_info.as_mint()?.decimals();
+
+    transfer_checked_signed_with_bump(
         managed_miner_auth_account_info,
         source_ata_account_info,
+        mint_account_info,
         destination_ata_account_info,
         spl_program,
         source_tokens.amount(),
+        decimals,
         &[
             crate::consts::MANAGED_MINER_AUTH,
             manager_account_info.key.as_ref(),
@@ -3,11 +3,13 @@ use steel::*;
 
 use crate::{
     error::EvoreError,
-    ore_api::{Board, Round},
+    ore_api::{self, Board, Miner, Round},
     processor::process_mm_deploy::{
-        calculate_percentage_deployments, plan_max_profit_waterfill, DeploymentBatch,
+        calculate_percentage_deployments, ev_slots_remaining, keep_min_ev_squares, keep_top_ev_squares,
+        plan_max_profit_waterfill, split_with_remainder, DeploymentBatch,
     },
-    validation::{validate_strategy_data, StrategyType},
+    squares::{mask_to_squares, validate_squares_mask},
+    state::{DspParams, DynevParams, EvParams, LoserPoolParams, MomentumParams, PercentageParams, StrategyParams, WeightedParams},
 };
 
 pub(crate) struct StrategyResult {
@@ -16,31 +18,111 @@ pub(crate) struct StrategyResult {
     pub needs_automation: bool,
 }
 
+impl StrategyResult {
+    /// Drops the squares set in `blocked_mask` from every batch. Errors if nothing is left.
+    pub fn block_squares(self, blocked_mask: u32) -> Result<Self, ProgramError> {
+        let StrategyResult { batches, needs_automation, .. } = self;
+
+        let mut total: u64 = 0;
+        let batches: Vec<DeploymentBatch> = batches
+            .into_iter()
+            .filter_map(|mut batch| {
+                for (i, square) in batch.squares.iter_mut().enumerate() {
+                    *square &= blocked_mask & (1 << i) == 0;
+                }
+                let count = batch.squares.iter().filter(|&&s| s).count() as u64;
+                total = total.saturating_add(batch.amount.saturating_mul(count));
+                (count > 0).then_some(batch)
+            })
+            .collect();
+
+        if total == 0 {
+            return Err(EvoreError::NoDeployments.into());
+        }
+
+        Ok(StrategyResult { batches, total_to_deploy: total, needs_automation })
+    }
+
+    /// Keeps only the `max_squares` squares the strategy funded most heavily; ties go to the
+    /// square with less already deployed this round, then the lower index. 0 means no cap.
+    pub fn cap_squares(self, max_squares: u8, round: &Round) -> Self {
+        let per_square = self.per_square();
+        let StrategyResult { batches, total_to_deploy, needs_automation } = self;
+
+        let mut ranked: Vec<usize> = (0..25).filter(|&i| per_square[i] > 0).collect();
+        if max_squares == 0 || ranked.len() <= max_squares as usize {
+            return StrategyResult { batches, total_to_deploy, needs_automation };
+        }
+        ranked.sort_by(|&a, &b| {
+            per_square[b]
+                .cmp(&per_square[a])
+                .then(round.deployed[a].cmp(&round.deployed[b]))
+                .then(a.cmp(&b))
+        });
+
+        let mut keep = [false; 25];
+        for &i in ranked.iter().take(max_squares as usize) {
+            keep[i] = true;
+        }
+
+        let mut total: u64 = 0;
+        let batches = batches
+            .into_iter()
+            .filter_map(|mut batch| {
+                for (square, &kept) in batch.squares.iter_mut().zip(keep.iter()) {
+                    *square &= kept;
+                }
+                let count = batch.squares.iter().filter(|&&s| s).count() as u64;
+                total = total.saturating_add(batch.amount.saturating_mul(count));
+                (count > 0).then_some(batch)
+            })
+            .collect();
+
+        StrategyResult { batches, total_to_deploy: total, needs_automation }
+    }
+
+    /// Lamports the batches put on each square
+    pub fn per_square(&self) -> [u64; 25] {
+        let mut per_square = [0u64; 25];
+        for batch in &self.batches {
+            for (i, _) in batch.squares.iter().enumerate().filter(|(_, &on)| on) {
+                per_square[i] = per_square[i].saturating_add(batch.amount);
+            }
+        }
+        per_square
+    }
+
+    /// Most the miner can lose this round once the batches land on top of `already_deployed`:
+    /// everything it has out, less the smallest stake if every square is covered (the winning
+    /// square returns at least that much)
+    pub fn worst_case_loss(&self, already_deployed: &[u64; 25]) -> u64 {
+        let mut stakes = self.per_square();
+        for (stake, &already) in stakes.iter_mut().zip(already_deployed.iter()) {
+            *stake = stake.saturating_add(already);
+        }
+        let total = stakes.iter().fold(0u64, |acc, &s| acc.saturating_add(s));
+        let floor = stakes.iter().copied().min().unwrap_or(0);
+        total - floor
+    }
+}
+
 /// Dispatch strategy to compute deployment batches.
 ///
-/// Validates strategy data before computing deployments.
-/// Returns error on invalid strategy type, invalid data, or if no deployments can be made.
+/// Takes parameters from `StrategyDeployer::decode_strategy`, which has already validated them.
+/// Returns error if no deployments can be made.
 pub(crate) fn dispatch_strategy(
-    strategy_type_raw: u8,
-    strategy_data: &[u8; 64],
+    strategy: StrategyParams,
     amount: u64,
     squares_mask: u32,
     extra: u32,
     board: &Board,
     round: &Round,
     clock: &Clock,
+    top_miner_info: Option<&AccountInfo>,
 ) -> Result<StrategyResult, ProgramError> {
-    let strategy_type = StrategyType::try_from(strategy_type_raw)?;
-    validate_strategy_data(strategy_type, strategy_data)?;
-
-    match strategy_type {
-        StrategyType::Ev => {
-            let max_per_square = u64::from_le_bytes(strategy_data[0..8].try_into().unwrap());
-            let min_bet = u64::from_le_bytes(strategy_data[8..16].try_into().unwrap());
-            let slots_left = u64::from_le_bytes(strategy_data[16..24].try_into().unwrap());
-            let ore_value = u64::from_le_bytes(strategy_data[24..32].try_into().unwrap());
-
-            let current_slots_left = board.end_slot - clock.slot;
+    match strategy {
+        StrategyParams::Ev(EvParams { max_per_square, min_bet, slots_left, ore_value, max_squares, min_ev_bps }) => {
+            let current_slots_left = ev_slots_remaining(board, clock)?;
             if current_slots_left > slots_left {
                 return Err(EvoreError::TooManySlotsLeft.into());
             }
@@ -48,6 +130,8 @@ pub(crate) fn dispatch_strategy(
             let alloc = plan_max_profit_waterfill(
                 round.deployed, amount, min_bet, 100, 10, ore_value, max_per_square,
             );
+            let alloc = keep_min_ev_squares(alloc, round.deployed, ore_value, min_ev_bps);
+            let alloc = keep_top_ev_squares(alloc, round.deployed, ore_value, max_squares);
 
             let mut ev_batches: Vec<DeploymentBatch> = Vec::new();
             for i in 0..25 {
@@ -61,34 +145,30 @@ pub(crate) fn dispatch_strategy(
             }
             Ok(StrategyResult { batches: ev_batches, total_to_deploy: total, needs_automation: true })
         }
-        StrategyType::Manual => {
-            let mut squares = [false; 25];
-            for i in 0..25 {
-                if (squares_mask >> i) & 1 == 1 {
-                    squares[i] = true;
-                }
-            }
+        StrategyParams::Manual => {
+            validate_squares_mask(squares_mask as u64)?;
+            let squares = mask_to_squares(squares_mask as u64);
             let num_squares = squares.iter().filter(|&&s| s).count() as u64;
             if num_squares == 0 {
                 return Err(EvoreError::NoDeployments.into());
             }
-            let total = amount.saturating_mul(num_squares);
+            let total = amount.checked_mul(num_squares).ok_or(EvoreError::ArithmeticOverflow)?;
             if total == 0 {
                 return Err(EvoreError::NoDeployments.into());
             }
             Ok(StrategyResult { batches: vec![DeploymentBatch::new(amount, squares)], total_to_deploy: total, needs_automation: false })
         }
-        StrategyType::Split => {
+        StrategyParams::Split(_) => {
             let per_square = amount / 25;
             if per_square == 0 {
                 return Err(EvoreError::NoDeployments.into());
             }
-            let total = per_square * 25;
-            Ok(StrategyResult { batches: vec![DeploymentBatch::all_squares(per_square)], total_to_deploy: total, needs_automation: false })
+            // Integer division leaves up to 24 lamports; put them on the highest-EV square so
+            // the full bankroll is deployed
+            let batches = split_with_remainder(round, amount, per_square);
+            Ok(StrategyResult { batches, total_to_deploy: amount, needs_automation: false })
         }
-        StrategyType::Percentage => {
-            let percentage = u64::from_le_bytes(strategy_data[0..8].try_into().unwrap());
-            let squares_count = u64::from_le_bytes(strategy_data[8..16].try_into().unwrap());
+        StrategyParams::Percentage(PercentageParams { percentage, squares_count, .. }) => {
             let bankroll = amount;
 
             let (batches, total) = calculate_percentage_deployments(round, bankroll, percentage, squares_count);
@@ -97,10 +177,7 @@ pub(crate) fn dispatch_strategy(
             }
             Ok(StrategyResult { batches, total_to_deploy: total, needs_automation: true })
         }
-        StrategyType::DynamicSplitPercentage => {
-            let percentage = u64::from_le_bytes(strategy_data[0..8].try_into().unwrap());
-            let squares_mask_val = u64::from_le_bytes(strategy_data[8..16].try_into().unwrap());
-
+        StrategyParams::DynamicSplitPercentage(DspParams { percentage, squares_mask: squares_mask_val, .. }) => {
             let p = percentage as u128;
             if p == 0 || p >= 10000 {
                 return Err(EvoreError::NoDeployments.into());
@@ -133,19 +210,19 @@ pub(crate) fn dispatch_strategy(
             }
             Ok(StrategyResult { batches: dsp_batches, total_to_deploy: total, needs_automation: true })
         }
-        StrategyType::DynamicEv => {
-            let max_ps = u64::from_le_bytes(strategy_data[0..8].try_into().unwrap());
-            let min_b = u64::from_le_bytes(strategy_data[8..16].try_into().unwrap());
-            let sl = u64::from_le_bytes(strategy_data[16..24].try_into().unwrap());
-            let max_ore = u64::from_le_bytes(strategy_data[24..32].try_into().unwrap());
-
+        StrategyParams::DynamicEv(DynevParams {
+            max_per_square: max_ps,
+            min_bet: min_b,
+            slots_left: sl,
+            max_ore_value: max_ore,
+        }) => {
             let ore_value = (u64::from(extra) << 32) | u64::from(squares_mask);
 
             if max_ore > 0 && ore_value > max_ore {
                 return Err(EvoreError::InvalidStrategyData.into());
             }
 
-            let current_slots_left = board.end_slot.saturating_sub(clock.slot);
+            let current_slots_left = ev_slots_remaining(board, clock)?;
             if current_slots_left > sl {
                 return Err(EvoreError::TooManySlotsLeft.into());
             }
@@ -166,5 +243,193 @@ pub(crate) fn dispatch_strategy(
             }
             Ok(StrategyResult { batches: dynev_batches, total_to_deploy: total, needs_automation: true })
         }
+        StrategyParams::MirrorTopMiner => {
+            let top_miner_info = top_miner_info.ok_or(ProgramError::NotEnoughAccountKeys)?;
+            let top_miner = load_top_miner(top_miner_info, round)?;
+
+            let (batches, total) = mirror_distribution(&top_miner.deployed, amount);
+            if total == 0 {
+                return Err(EvoreError::NoDeployments.into());
+            }
+            Ok(StrategyResult { batches, total_to_deploy: total, needs_automation: true })
+        }
+        StrategyParams::LoserPool(LoserPoolParams { fraction_bps, squares_mask: squares_mask_val }) => {
+            let (batches, total) = loser_pool_distribution(round, fraction_bps, squares_mask_val, amount);
+            if total == 0 {
+                return Err(EvoreError::NoDeployments.into());
+            }
+            Ok(StrategyResult { batches, total_to_deploy: total, needs_automation: true })
+        }
+        StrategyParams::Momentum(MomentumParams { squares_count }) => {
+            let (batches, total) = momentum_distribution(&round.deployed, squares_count, amount);
+            if total == 0 {
+                return Err(EvoreError::NoDeployments.into());
+            }
+            Ok(StrategyResult { batches, total_to_deploy: total, needs_automation: true })
+        }
+        StrategyParams::Weighted(WeightedParams { bankroll, min_per_square, exponent }) => {
+            let (batches, total) = weighted_distribution(round, amount.min(bankroll), min_per_square, exponent);
+            if total == 0 {
+                return Err(EvoreError::NoDeployments.into());
+            }
+            Ok(StrategyResult { batches, total_to_deploy: total, needs_automation: true })
+        }
+    }
+}
+
+/// Loads the ORE miner of `round.top_miner`, rejecting any other account
+fn load_top_miner<'a>(top_miner_info: &'a AccountInfo, round: &Round) -> Result<&'a Miner, ProgramError> {
+    if round.top_miner == Pubkey::default() {
+        return Err(EvoreError::InvalidTopMiner.into());
+    }
+    if *top_miner_info.key != ore_api::miner_pda(round.top_miner).0 {
+        return Err(EvoreError::InvalidTopMiner.into());
+    }
+    let miner = top_miner_info.as_account::<Miner>(&ore_api::id())?;
+    if miner.authority != round.top_miner {
+        return Err(EvoreError::InvalidTopMiner.into());
+    }
+    // `deployed` only describes this round once the miner has played in it
+    if miner.round_id != round.id {
+        return Err(EvoreError::NoDeployments.into());
+    }
+    Ok(miner)
+}
+
+/// Splits `bankroll` across squares in proportion to `deployed` (floor per square).
+/// Returns the batches and the total actually allocated.
+pub(crate) fn mirror_distribution(deployed: &[u64; 25], bankroll: u64) -> (Vec<DeploymentBatch>, u64) {
+    let top_total: u128 = deployed.iter().map(|&d| u128::from(d)).sum();
+    if top_total == 0 {
+        return (Vec::new(), 0);
+    }
+
+    let mut batches = Vec::new();
+    let mut total: u64 = 0;
+    for (i, &d) in deployed.iter().enumerate() {
+        let amount_i = (u128::from(bankroll) * u128::from(d) / top_total) as u64;
+        if amount_i == 0 {
+            continue;
+        }
+        batches.push(DeploymentBatch::single(amount_i, i));
+        total = total.saturating_add(amount_i);
+    }
+    (batches, total)
+}
+
+/// Bets `fraction_bps` of each masked square's loser pool (everything deployed on the
+/// other squares), in square order until `bankroll` runs out; the last bet takes what is left.
+/// Returns the batches and the total allocated.
+pub(crate) fn loser_pool_distribution(
+    round: &Round,
+    fraction_bps: u64,
+    squares_mask: u64,
+    bankroll: u64,
+) -> (Vec<DeploymentBatch>, u64) {
+    let mut batches = Vec::new();
+    let mut total: u64 = 0;
+    for i in 0..25 {
+        if (squares_mask >> i) & 1 == 0 {
+            continue;
+        }
+        let loser_pool = round.total_deployed.saturating_sub(round.deployed[i]);
+        let amount_i = (u128::from(loser_pool) * u128::from(fraction_bps) / 10_000) as u64;
+        if amount_i == 0 {
+            continue;
+        }
+        let remaining = bankroll - total;
+        if amount_i >= remaining {
+            if remaining > 0 {
+                batches.push(DeploymentBatch::single(remaining, i));
+                total = bankroll;
+            }
+            break;
+        }
+        batches.push(DeploymentBatch::single(amount_i, i));
+        total += amount_i;
+    }
+    (batches, total)
+}
+
+/// Backs the `squares_count` most-deployed squares (ties go to the lower index), splitting
+/// `bankroll` in proportion to what each already holds - the opposite of the EV strategy,
+/// which favours the emptiest squares. Squares with nothing deployed are never picked.
+/// Returns the batches and the total allocated.
+pub(crate) fn momentum_distribution(
+    deployed: &[u64; 25],
+    squares_count: u64,
+    bankroll: u64,
+) -> (Vec<DeploymentBatch>, u64) {
+    let mut ranked: Vec<usize> = (0..25).filter(|&i| deployed[i] > 0).collect();
+    ranked.sort_by(|&a, &b| deployed[b].cmp(&deployed[a]).then(a.cmp(&b)));
+
+    let mut top = [0u64; 25];
+    for &i in ranked.iter().take(squares_count as usize) {
+        top[i] = deployed[i];
+    }
+    mirror_distribution(&top, bankroll)
+}
+
+/// Fixed-point scale the odds are normalised to before `weighted_distribution` raises them to
+/// the exponent: (2^15)^4 stays far enough below u128::MAX to multiply by a u64 bankroll
+const WEIGHT_SCALE: u128 = 1 << 15;
+
+/// Splits `bankroll` in proportion to `(total_deployed - deployed[i])^exponent`, so squares
+/// with less on them (better implied odds) get more. Squares whose share falls below
+/// `min_per_square` are dropped and the bankroll re-split among the rest; the rounding
+/// remainder goes to the largest share, so whatever is allocated sums to `bankroll`.
+/// Returns the batches and the total allocated.
+pub(crate) fn weighted_distribution(
+    round: &Round,
+    bankroll: u64,
+    min_per_square: u64,
+    exponent: u64,
+) -> (Vec<DeploymentBatch>, u64) {
+    let odds = round.deployed.map(|d| round.total_deployed.saturating_sub(d));
+    let max_odds = odds.iter().copied().max().unwrap_or(0);
+
+    // An empty board gives every square the same odds
+    let mut weights = [0u128; 25];
+    for (weight, &o) in weights.iter_mut().zip(odds.iter()) {
+        let base = if max_odds == 0 { 1 } else { u128::from(o) * WEIGHT_SCALE / u128::from(max_odds) };
+        *weight = base.pow(exponent as u32);
+    }
+
+    // Dropping a square only grows the others' shares, so kept squares never fall below the floor
+    let mut shares = loop {
+        let weight_total: u128 = weights.iter().sum();
+        if weight_total == 0 {
+            return (Vec::new(), 0);
+        }
+        let mut shares = [0u64; 25];
+        for (share, &weight) in shares.iter_mut().zip(weights.iter()) {
+            *share = (u128::from(bankroll) * weight / weight_total) as u64;
+        }
+
+        let mut dropped = false;
+        for (weight, &share) in weights.iter_mut().zip(shares.iter()) {
+            if *weight > 0 && (share == 0 || share < min_per_square) {
+                *weight = 0;
+                dropped = true;
+            }
+        }
+        if !dropped {
+            break shares;
+        }
+    };
+
+    let allocated: u64 = shares.iter().sum();
+    if let Some(top) = (0..25).max_by(|&a, &b| shares[a].cmp(&shares[b]).then(b.cmp(&a))) {
+        shares[top] += bankroll - allocated;
+    }
+
+    // Squares with equal odds get equal shares; one batch (one ORE deploy) per distinct share
+    let mut batches: Vec<DeploymentBatch> = Vec::new();
+    for i in (0..25).filter(|&i| shares[i] > 0) {
+        match batches.iter_mut().find(|b| b.amount == shares[i]) {
+            Some(batch) => batch.squares[i] = true,
+            None => batches.push(DeploymentBatch::single(shares[i], i)),
+        }
     }
+    (batches, bankroll)
 }
@@ -0,0 +1,55 @@
+use solana_program::program_error::ProgramError;
+
+use crate::error::EvoreError;
+
+/// Number of squares on the ORE board
+pub const BOARD_SQUARES: usize = 25;
+
+/// Mask with one bit set for every board square
+pub const ALL_SQUARES_MASK: u64 = (1 << BOARD_SQUARES) - 1;
+
+/// Rejects a square count larger than the board
+pub fn validate_squares_count(squares_count: u64) -> Result<(), ProgramError> {
+    if squares_count > BOARD_SQUARES as u64 {
+        return Err(EvoreError::InvalidSquareCount.into());
+    }
+    Ok(())
+}
+
+/// Rejects a squares mask with bits set above the last board square
+pub fn validate_squares_mask(squares_mask: u64) -> Result<(), ProgramError> {
+    if squares_mask & !ALL_SQUARES_MASK != 0 {
+        return Err(EvoreError::InvalidSquareCount.into());
+    }
+    Ok(())
+}
+
+/// Builds a squares mask from explicit square indices (0-24), rejecting anything off the board
+pub fn indices_to_mask(indices: &[usize]) -> Result<u32, ProgramError> {
+    let mut mask = 0u32;
+    for &i in indices {
+        if i >= BOARD_SQUARES {
+            return Err(EvoreError::InvalidSquareCount.into());
+        }
+        mask |= 1 << i;
+    }
+    Ok(mask)
+}
+
+/// Expands a squares mask into one flag per square (bit i = square i)
+pub fn mask_to_squares(squares_mask: u64) -> [bool; BOARD_SQUARES] {
+    let mut squares = [false; BOARD_SQUARES];
+    for (i, square) in squares.iter_mut().enumerate() {
+        *square = (squares_mask >> i) & 1 == 1;
+    }
+    squares
+}
+
+/// Packs one flag per square back into a squares mask (bit i = square i)
+pub fn squares_to_mask(squares: &[bool; BOARD_SQUARES]) -> u32 {
+    squares
+        .iter()
+        .enumerate()
+        .filter(|(_, &on)| on)
+        .fold(0u32, |mask, (i, _)| mask | 1 << i)
+}
@@ -2,7 +2,11 @@ use steel::*;
 use serde::{Serialize, Deserialize};
 use serde_big_array::BigArray;
 
-use crate::consts::{MANAGED_MINER_AUTH, DEPLOYER, STRATEGY_DEPLOYER};
+use crate::validation::{validate_strategy_data, StrategyType};
+use crate::consts::{
+    DEPLOYER, DEPLOY_AUTHORITY_ALLOWLIST, DEPLOY_SCHEDULE, MANAGED_MINER_AUTH, MAX_ALLOWLISTED_AUTHORITIES,
+    MAX_SCHEDULE_ENTRIES, STRATEGY_DEPLOYER,
+};
 
 #[repr(u8)]
 #[derive(Clone, Copy, Debug, Eq, PartialEq, IntoPrimitive, TryFromPrimitive)]
@@ -10,6 +14,8 @@ pub enum EvoreAccount {
     Manager = 100,
     Deployer = 101,
     StrategyDeployer = 102,
+    DeployAuthorityAllowlist = 103,
+    DeploySchedule = 104,
 }
 
 #[repr(C)]
@@ -18,10 +24,58 @@ pub struct Manager {
     /// The authority of this managed miner account. Which is authority of all 
     /// associated auth_id's miners
     pub authority: Pubkey,
+
+    /// Most lamports a single deploy instruction may put on the board, across every
+    /// miner it deploys for (0 = no ceiling) - set by the authority with SetMaxDeployPerTx
+    pub max_deploy_per_tx: u64,
+
+    /// Authority nominated with InitiateTransferManager, promoted once it signs
+    /// AcceptTransferManager (default pubkey = no transfer pending)
+    pub pending_authority: Pubkey,
+
+    /// Zeroed space for future fields; managers created before it existed
+    /// are grown with MigrateManager
+    pub reserved: [u64; 3],
 }
 
+/// Size of a Manager account created before `reserved` was added (discriminator + authority)
+pub const LEGACY_MANAGER_SIZE: usize = 8 + 32;
+
 account!(EvoreAccount, Manager);
 
+impl Manager {
+    /// Reads a Manager of either the current or the legacy size
+    /// Legacy accounts read with the fields past `authority` zeroed; instructions that write
+    /// the manager still need it grown with MigrateManager first
+    pub fn load(info: &AccountInfo) -> Result<Manager, ProgramError> {
+        if *info.owner != crate::id() {
+            return Err(ProgramError::InvalidAccountOwner);
+        }
+        Self::from_account_data(&info.try_borrow_data()?)
+    }
+
+    /// Decodes raw account data (discriminator included) of either the current or the legacy size
+    pub fn from_account_data(data: &[u8]) -> Result<Manager, ProgramError> {
+        if data.first() != Some(&Manager::discriminator()) {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        if data.len() != 8 + std::mem::size_of::<Manager>() && data.len() != LEGACY_MANAGER_SIZE {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        let mut manager = Manager::zeroed();
+        bytemuck::bytes_of_mut(&mut manager)[..data.len() - 8].copy_from_slice(&data[8..]);
+        Ok(manager)
+    }
+
+    /// Errors if deploying `total` lamports in one instruction would break `max_deploy_per_tx`
+    pub fn check_deploy_ceiling(&self, total: u64) -> Result<(), ProgramError> {
+        if self.max_deploy_per_tx > 0 && total > self.max_deploy_per_tx {
+            return Err(crate::error::EvoreError::DeployCeilingExceeded.into());
+        }
+        Ok(())
+    }
+}
+
 /// Deployer account - allows a deploy_authority to execute deploys on behalf of a manager
 /// PDA seeds: ["deployer", manager_key]
 /// Stores manager_key for easy lookup when scanning by deploy_authority
@@ -66,15 +120,303 @@ pub struct StrategyDeployer {
     pub strategy_type: u8,
     #[serde(with = "BigArray")]
     pub strategy_data: [u8; 64],
-    pub _padding: [u8; 7],
+    /// Most squares one deploy may fund; the strategy's heaviest squares are kept (0 = no cap)
+    pub max_squares_per_deploy: u8,
+    /// Squares no deploy may fund, whatever the strategy picks (u32 LE bitmask, bit i = square i)
+    pub blocked_squares_mask: [u8; 4],
+    pub _padding: [u8; 2],
+    /// Most lamports a deploy may leave at risk this round, counting what the miner already
+    /// has on the board; the best winning square is assumed to pay back its stake (0 = no cap)
+    pub max_loss_per_round: u64,
 }
 
+/// Size of a StrategyDeployer account created before `max_loss_per_round` was added
+pub const LEGACY_STRATEGY_DEPLOYER_SIZE: usize = 8 + 176;
+
 account!(EvoreAccount, StrategyDeployer);
 
+impl StrategyDeployer {
+    /// Reads a StrategyDeployer of either the current or the legacy size
+    /// Legacy accounts read with `max_loss_per_round` = 0 until UpdateStratDeployer grows them
+    pub fn load(info: &AccountInfo) -> Result<StrategyDeployer, ProgramError> {
+        if *info.owner != crate::id() {
+            return Err(ProgramError::InvalidAccountOwner);
+        }
+        Self::from_account_data(&info.try_borrow_data()?)
+    }
+
+    /// Decodes raw account data (discriminator included) of either the current or the legacy size
+    pub fn from_account_data(data: &[u8]) -> Result<StrategyDeployer, ProgramError> {
+        if data.first() != Some(&StrategyDeployer::discriminator()) {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        if data.len() != 8 + std::mem::size_of::<StrategyDeployer>() && data.len() != LEGACY_STRATEGY_DEPLOYER_SIZE {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        let mut strat_deployer = StrategyDeployer::zeroed();
+        bytemuck::bytes_of_mut(&mut strat_deployer)[..data.len() - 8].copy_from_slice(&data[8..]);
+        Ok(strat_deployer)
+    }
+
+    /// `blocked_squares_mask` as a u32 (bit i = square i)
+    pub fn blocked_squares(&self) -> u32 {
+        u32::from_le_bytes(self.blocked_squares_mask)
+    }
+
+    /// ORE program deploy slot the deployer is pinned to (`strategy_data` word 7, unused by
+    /// every strategy); deploys fail once ORE is redeployed at another slot (0 = not pinned)
+    pub fn expected_ore_program_slot(&self) -> u64 {
+        u64::from_le_bytes(self.strategy_data[56..64].try_into().unwrap())
+    }
+
+    /// Errors unless ORE's `program_data` account shows ORE still deployed at the pinned slot;
+    /// unpinned deployers pass without the account
+    pub fn check_ore_program_slot(&self, program_data: Option<&AccountInfo>) -> Result<(), ProgramError> {
+        let expected_ore_program_slot = self.expected_ore_program_slot();
+        if expected_ore_program_slot == 0 {
+            return Ok(());
+        }
+        let program_data = program_data.ok_or(ProgramError::NotEnoughAccountKeys)?;
+        if *program_data.key != crate::ore_api::program_data_pda().0 {
+            return Err(crate::error::EvoreError::InvalidPDA.into());
+        }
+        let slot = crate::ore_api::program_data_slot(&program_data.try_borrow_data()?)
+            .ok_or(ProgramError::InvalidAccountData)?;
+        if slot != expected_ore_program_slot {
+            return Err(crate::error::EvoreError::OreProgramChanged.into());
+        }
+        Ok(())
+    }
+
+    /// Typed view of `strategy_data` for `strategy_type`
+    /// Fails like CreateStratDeployer would on an unknown type or out-of-range parameters
+    pub fn decode_strategy(&self) -> Result<StrategyParams, ProgramError> {
+        let strategy_type = StrategyType::try_from(self.strategy_type)?;
+        validate_strategy_data(strategy_type, &self.strategy_data)?;
+        Ok(StrategyParams::decode(strategy_type, &self.strategy_data))
+    }
+}
+
+/// Parameters of a strategy, decoded from the little-endian u64 words of `strategy_data`
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum StrategyParams {
+    Ev(EvParams),
+    Percentage(PercentageParams),
+    Manual,
+    Split(SplitParams),
+    DynamicSplitPercentage(DspParams),
+    DynamicEv(DynevParams),
+    MirrorTopMiner,
+    LoserPool(LoserPoolParams),
+    Momentum(MomentumParams),
+    Weighted(WeightedParams),
+}
+
+/// EV waterfill: words 0-5
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct EvParams {
+    pub max_per_square: u64,
+    pub min_bet: u64,
+    /// Only deploy once at most this many slots are left in the round
+    pub slots_left: u64,
+    pub ore_value: u64,
+    /// Keep only the best this many squares (0 = no limit)
+    pub max_squares: u64,
+    /// Skip squares expected to return less than this over their stake, in basis points
+    pub min_ev_bps: u64,
+}
+
+/// Percentage of the cheapest squares: words 0-3
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct PercentageParams {
+    /// Share of each square to own, in basis points
+    pub percentage: u64,
+    pub squares_count: u64,
+    pub motherlode_min: u64,
+    pub motherlode_max: u64,
+}
+
+/// Even split across the board: words 0-1
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct SplitParams {
+    pub motherlode_min: u64,
+    pub motherlode_max: u64,
+}
+
+/// Dynamic split percentage over a square mask: words 0-3
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct DspParams {
+    /// Share of each square to own, in basis points
+    pub percentage: u64,
+    pub squares_mask: u64,
+    pub motherlode_min: u64,
+    pub motherlode_max: u64,
+}
+
+/// EV waterfill with the ORE value supplied per deploy: words 0-3
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct DynevParams {
+    pub max_per_square: u64,
+    pub min_bet: u64,
+    /// Only deploy once at most this many slots are left in the round
+    pub slots_left: u64,
+    /// Highest ORE value a deploy may pass (0 = no cap)
+    pub max_ore_value: u64,
+}
+
+/// Fraction of each masked square's loser pool: words 0-1
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct LoserPoolParams {
+    pub fraction_bps: u64,
+    pub squares_mask: u64,
+}
+
+/// Proportional bets on the most crowded squares: word 0
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct MomentumParams {
+    /// How many of the highest-deployed squares to back (1-25)
+    pub squares_count: u64,
+}
+
+/// Bankroll split by each square's implied odds: words 0-2
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct WeightedParams {
+    /// Most one deploy spends; a smaller deploy amount spends only that
+    pub bankroll: u64,
+    /// Squares whose share falls below this are skipped and their share re-split
+    pub min_per_square: u64,
+    /// Power the odds are raised to (1-4); higher concentrates on the emptiest squares
+    pub exponent: u64,
+}
+
+impl StrategyParams {
+    /// Reads `strategy_data` as `strategy_type`'s parameters, without range checks
+    pub fn decode(strategy_type: StrategyType, strategy_data: &[u8; 64]) -> Self {
+        let word = |i: usize| u64::from_le_bytes(strategy_data[i * 8..i * 8 + 8].try_into().unwrap());
+        match strategy_type {
+            StrategyType::Ev => StrategyParams::Ev(EvParams {
+                max_per_square: word(0),
+                min_bet: word(1),
+                slots_left: word(2),
+                ore_value: word(3),
+                max_squares: word(4),
+                min_ev_bps: word(5),
+            }),
+            StrategyType::Percentage => StrategyParams::Percentage(PercentageParams {
+                percentage: word(0),
+                squares_count: word(1),
+                motherlode_min: word(2),
+                motherlode_max: word(3),
+            }),
+            StrategyType::Manual => StrategyParams::Manual,
+            StrategyType::Split => StrategyParams::Split(SplitParams {
+                motherlode_min: word(0),
+                motherlode_max: word(1),
+            }),
+            StrategyType::DynamicSplitPercentage => StrategyParams::DynamicSplitPercentage(DspParams {
+                percentage: word(0),
+                squares_mask: word(1),
+                motherlode_min: word(2),
+                motherlode_max: word(3),
+            }),
+            StrategyType::DynamicEv => StrategyParams::DynamicEv(DynevParams {
+                max_per_square: word(0),
+                min_bet: word(1),
+                slots_left: word(2),
+                max_ore_value: word(3),
+            }),
+            StrategyType::MirrorTopMiner => StrategyParams::MirrorTopMiner,
+            StrategyType::LoserPool => StrategyParams::LoserPool(LoserPoolParams {
+                fraction_bps: word(0),
+                squares_mask: word(1),
+            }),
+            StrategyType::Momentum => StrategyParams::Momentum(MomentumParams {
+                squares_count: word(0),
+            }),
+            StrategyType::Weighted => StrategyParams::Weighted(WeightedParams {
+                bankroll: word(0),
+                min_per_square: word(1),
+                exponent: word(2),
+            }),
+        }
+    }
+}
+
+/// Deploy authorities a manager allows on its deployers
+/// PDA seeds: ["deploy-authority-allowlist", manager_key]
+/// When this account exists with at least one entry, CreateDeployer and CreateStratDeployer
+/// reject any deploy_authority that isn't listed. Missing or empty = no restriction.
+/// Size: 32 + 8 + 32 * 8 = 296 bytes (+ 8 discriminator = 304)
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Pod, Zeroable, Serialize, Deserialize)]
+pub struct DeployAuthorityAllowlist {
+    /// The manager this allowlist belongs to
+    pub manager_key: Pubkey,
+    /// Number of used entries at the start of `authorities`
+    pub count: u64,
+    /// Allowlisted deploy authorities (only the first `count` are meaningful)
+    pub authorities: [Pubkey; MAX_ALLOWLISTED_AUTHORITIES],
+}
+
+account!(EvoreAccount, DeployAuthorityAllowlist);
+
+impl DeployAuthorityAllowlist {
+    /// The used entries of the allowlist
+    pub fn entries(&self) -> &[Pubkey] {
+        &self.authorities[..(self.count as usize).min(MAX_ALLOWLISTED_AUTHORITIES)]
+    }
+
+    /// Whether `deploy_authority` may be used, an empty allowlist allows anyone
+    pub fn allows(&self, deploy_authority: &Pubkey) -> bool {
+        self.count == 0 || self.entries().contains(deploy_authority)
+    }
+}
+
+/// Per-round deploy amounts a manager wants its crank to cycle through
+/// PDA seeds: ["deploy-schedule", manager_key]
+/// Round r uses amounts[r % len] (lamports per square, 0 = skip that round).
+/// Read off-chain only; len = 0 means no schedule (the crank's default amount applies).
+/// Size: 32 + 8 + 8 * 16 = 168 bytes (+ 8 discriminator = 176)
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Pod, Zeroable, Serialize, Deserialize)]
+pub struct DeploySchedule {
+    /// The manager this schedule belongs to
+    pub manager_key: Pubkey,
+    /// Number of used entries at the start of `amounts`
+    pub len: u64,
+    /// Lamports per square for each step of the cycle
+    pub amounts: [u64; MAX_SCHEDULE_ENTRIES],
+}
+
+account!(EvoreAccount, DeploySchedule);
+
+impl DeploySchedule {
+    /// Scheduled amount for `round_id`, None when the schedule is empty
+    pub fn amount_for_round(&self, round_id: u64) -> Option<u64> {
+        let len = self.len.min(MAX_SCHEDULE_ENTRIES as u64);
+        if len == 0 {
+            return None;
+        }
+        Some(self.amounts[(round_id % len) as usize])
+    }
+}
+
 pub fn strategy_deployer_pda(manager_key: Pubkey) -> (Pubkey, u8) {
     Pubkey::find_program_address(&[STRATEGY_DEPLOYER, &manager_key.to_bytes()], &crate::ID)
 }
 
+/// Derives the deploy authority allowlist PDA for a given manager key
+/// Seeds: ["deploy-authority-allowlist", manager_key]
+pub fn deploy_authority_allowlist_pda(manager_key: Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[DEPLOY_AUTHORITY_ALLOWLIST, &manager_key.to_bytes()], &crate::ID)
+}
+
+/// Derives the deploy schedule PDA for a given manager key
+/// Seeds: ["deploy-schedule", manager_key]
+pub fn deploy_schedule_pda(manager_key: Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[DEPLOY_SCHEDULE, &manager_key.to_bytes()], &crate::ID)
+}
+
 pub fn managed_miner_auth_pda(manager: Pubkey, auth_id: u64) -> (Pubkey, u8) {
     Pubkey::find_program_address(&[MANAGED_MINER_AUTH, &manager.to_bytes(), &auth_id.to_le_bytes()], &crate::ID)
 }
@@ -1,6 +1,12 @@
-use solana_program::program_error::ProgramError;
+use solana_program::{account_info::AccountInfo, program_error::ProgramError};
 
-use crate::error::EvoreError;
+use crate::{
+    error::EvoreError,
+    squares::{validate_squares_count, validate_squares_mask},
+};
+
+/// Highest exponent a Weighted strategy may use; keeps the square weights inside a u128
+pub const MAX_WEIGHTED_EXPONENT: u64 = 4;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 #[repr(u8)]
@@ -11,6 +17,10 @@ pub enum StrategyType {
     Split = 3,
     DynamicSplitPercentage = 4,
     DynamicEv = 5,
+    MirrorTopMiner = 6,
+    LoserPool = 7,
+    Momentum = 8,
+    Weighted = 9,
 }
 
 impl TryFrom<u8> for StrategyType {
@@ -24,6 +34,10 @@ impl TryFrom<u8> for StrategyType {
             3 => Ok(StrategyType::Split),
             4 => Ok(StrategyType::DynamicSplitPercentage),
             5 => Ok(StrategyType::DynamicEv),
+            6 => Ok(StrategyType::MirrorTopMiner),
+            7 => Ok(StrategyType::LoserPool),
+            8 => Ok(StrategyType::Momentum),
+            9 => Ok(StrategyType::Weighted),
             _ => Err(EvoreError::InvalidStrategyType.into()),
         }
     }
@@ -34,6 +48,7 @@ pub fn validate_strategy_data(strategy_type: StrategyType, strategy_data: &[u8;
         StrategyType::Ev => {
             let max_per_square = u64::from_le_bytes(strategy_data[0..8].try_into().unwrap());
             let min_bet = u64::from_le_bytes(strategy_data[8..16].try_into().unwrap());
+            let max_squares = u64::from_le_bytes(strategy_data[32..40].try_into().unwrap());
 
             if max_per_square == 0 {
                 return Err(EvoreError::InvalidStrategyData.into());
@@ -41,6 +56,7 @@ pub fn validate_strategy_data(strategy_type: StrategyType, strategy_data: &[u8;
             if min_bet == 0 {
                 return Err(EvoreError::InvalidStrategyData.into());
             }
+            validate_squares_count(max_squares)?;
         }
         StrategyType::Percentage => {
             let percentage = u64::from_le_bytes(strategy_data[0..8].try_into().unwrap());
@@ -51,9 +67,10 @@ pub fn validate_strategy_data(strategy_type: StrategyType, strategy_data: &[u8;
             if percentage == 0 || percentage > 10_000 {
                 return Err(EvoreError::InvalidStrategyData.into());
             }
-            if squares_count == 0 || squares_count > 25 {
+            if squares_count == 0 {
                 return Err(EvoreError::InvalidStrategyData.into());
             }
+            validate_squares_count(squares_count)?;
             if motherlode_min > 0 && motherlode_max > 0 && motherlode_min > motherlode_max {
                 return Err(EvoreError::InvalidStrategyData.into());
             }
@@ -79,9 +96,7 @@ pub fn validate_strategy_data(strategy_type: StrategyType, strategy_data: &[u8;
             if squares_mask == 0 {
                 return Err(EvoreError::InvalidStrategyData.into());
             }
-            if squares_mask & !0x1FF_FFFF != 0 {
-                return Err(EvoreError::InvalidStrategyData.into());
-            }
+            validate_squares_mask(squares_mask)?;
             if motherlode_min > 0 && motherlode_max > 0 && motherlode_min > motherlode_max {
                 return Err(EvoreError::InvalidStrategyData.into());
             }
@@ -97,6 +112,52 @@ pub fn validate_strategy_data(strategy_type: StrategyType, strategy_data: &[u8;
                 return Err(EvoreError::InvalidStrategyData.into());
             }
         }
+        StrategyType::MirrorTopMiner => {}
+        StrategyType::LoserPool => {
+            let fraction_bps = u64::from_le_bytes(strategy_data[0..8].try_into().unwrap());
+            let squares_mask = u64::from_le_bytes(strategy_data[8..16].try_into().unwrap());
+
+            if fraction_bps == 0 || fraction_bps > 10_000 {
+                return Err(EvoreError::InvalidStrategyData.into());
+            }
+            if squares_mask == 0 {
+                return Err(EvoreError::InvalidStrategyData.into());
+            }
+            validate_squares_mask(squares_mask)?;
+        }
+        StrategyType::Momentum => {
+            let squares_count = u64::from_le_bytes(strategy_data[0..8].try_into().unwrap());
+
+            if squares_count == 0 {
+                return Err(EvoreError::InvalidStrategyData.into());
+            }
+            validate_squares_count(squares_count)?;
+        }
+        StrategyType::Weighted => {
+            let bankroll = u64::from_le_bytes(strategy_data[0..8].try_into().unwrap());
+            let exponent = u64::from_le_bytes(strategy_data[16..24].try_into().unwrap());
+
+            if bankroll == 0 {
+                return Err(EvoreError::InvalidStrategyData.into());
+            }
+            if exponent == 0 || exponent > MAX_WEIGHTED_EXPONENT {
+                return Err(EvoreError::InvalidStrategyData.into());
+            }
+        }
     }
     Ok(())
 }
+
+/// Checks that `token_program` owns `mint` and is classic SPL Token or Token-2022,
+/// so token CPIs and ATA derivations use the program the mint actually lives under.
+pub fn validate_token_program(mint: &AccountInfo, token_program: &AccountInfo) -> Result<(), ProgramError> {
+    if *mint.owner != spl_token::ID && *mint.owner != spl_token_2022::ID {
+        return Err(ProgramError::InvalidAccountOwner);
+    }
+
+    if token_program.key != mint.owner {
+        return Err(ProgramError::IncorrectProgramId);
+    }
+
+    Ok(())
+}
@@ -0,0 +1,54 @@
+//! Return data of read-only instructions
+//!
+//! View instructions change no accounts and report what they read with
+//! `set_return_data`, so a front-end can simulate one transaction instead of
+//! fetching accounts and deriving PDAs itself.
+
+use steel::*;
+
+use crate::ore_api::Miner;
+
+/// MMStatus return data: a managed miner's balance and checkpoint state
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Pod, Zeroable)]
+pub struct MMStatus {
+    /// Lamports held by the managed_miner_auth PDA
+    pub balance: u64,
+    /// Round the ORE miner last deployed in
+    pub round_id: u64,
+    /// Round the ORE miner last checkpointed
+    pub checkpoint_id: u64,
+    /// SOL the ORE miner can claim
+    pub rewards_sol: u64,
+    /// ORE the ORE miner can claim
+    pub rewards_ore: u64,
+    /// Squares with a deploy in `round_id`
+    pub deployed_count: u64,
+}
+
+impl MMStatus {
+    /// Status of `miner` (None when the ORE miner doesn't exist yet) under a PDA holding `balance`
+    pub fn new(balance: u64, miner: Option<&Miner>) -> Self {
+        let Some(miner) = miner else {
+            return Self { balance, ..Self::default() };
+        };
+        Self {
+            balance,
+            round_id: miner.round_id,
+            checkpoint_id: miner.checkpoint_id,
+            rewards_sol: miner.rewards_sol,
+            rewards_ore: miner.rewards_ore,
+            deployed_count: miner.deployed.iter().filter(|&&d| d > 0).count() as u64,
+        }
+    }
+
+    /// Parses the return data of an MMStatus call
+    pub fn from_return_data(data: &[u8]) -> Option<Self> {
+        bytemuck::try_pod_read_unaligned(data).ok()
+    }
+
+    /// Whether the last round played still has to be checkpointed before rewards are final
+    pub fn needs_checkpoint(&self) -> bool {
+        self.checkpoint_id < self.round_id
+    }
+}
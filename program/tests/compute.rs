@@ -0,0 +1,60 @@
+use evore::{
+    compute::{strategy_cu_limit, MAX_COMPUTE_UNIT_LIMIT},
+    validation::StrategyType,
+};
+
+#[test]
+fn test_manual_presets_stay_small() {
+    assert_eq!(strategy_cu_limit(StrategyType::Manual, 1), 51_000);
+    assert_eq!(strategy_cu_limit(StrategyType::Manual, 25), 75_000);
+    assert_eq!(strategy_cu_limit(StrategyType::Split, 25), 75_000);
+}
+
+#[test]
+fn test_per_square_presets_scale_with_squares() {
+    assert_eq!(strategy_cu_limit(StrategyType::Percentage, 1), 106_000);
+    assert_eq!(strategy_cu_limit(StrategyType::Percentage, 5), 330_000);
+    assert_eq!(strategy_cu_limit(StrategyType::Percentage, 25), MAX_COMPUTE_UNIT_LIMIT);
+    assert_eq!(strategy_cu_limit(StrategyType::LoserPool, 25), 1_400_000);
+    assert_eq!(strategy_cu_limit(StrategyType::Momentum, 3), strategy_cu_limit(StrategyType::Percentage, 3));
+    assert_eq!(strategy_cu_limit(StrategyType::Weighted, 25), MAX_COMPUTE_UNIT_LIMIT);
+    assert_eq!(
+        strategy_cu_limit(StrategyType::DynamicSplitPercentage, 10),
+        strategy_cu_limit(StrategyType::MirrorTopMiner, 10),
+    );
+}
+
+#[test]
+fn test_ev_full_board_uses_max_limit() {
+    assert_eq!(strategy_cu_limit(StrategyType::Ev, 25), MAX_COMPUTE_UNIT_LIMIT);
+    assert_eq!(strategy_cu_limit(StrategyType::DynamicEv, 25), 1_400_000);
+    assert_eq!(strategy_cu_limit(StrategyType::Ev, 5), 440_000);
+}
+
+#[test]
+fn test_unknown_or_oversized_count_means_full_board() {
+    assert_eq!(strategy_cu_limit(StrategyType::Ev, 0), strategy_cu_limit(StrategyType::Ev, 25));
+    assert_eq!(strategy_cu_limit(StrategyType::Percentage, 0), strategy_cu_limit(StrategyType::Percentage, 25));
+    assert_eq!(strategy_cu_limit(StrategyType::Manual, 1_000), strategy_cu_limit(StrategyType::Manual, 25));
+}
+
+#[test]
+fn test_presets_never_exceed_max() {
+    let all = [
+        StrategyType::Ev,
+        StrategyType::Percentage,
+        StrategyType::Manual,
+        StrategyType::Split,
+        StrategyType::DynamicSplitPercentage,
+        StrategyType::DynamicEv,
+        StrategyType::MirrorTopMiner,
+        StrategyType::LoserPool,
+        StrategyType::Momentum,
+        StrategyType::Weighted,
+    ];
+    for strategy in all {
+        for squares in 0..=30 {
+            assert!(strategy_cu_limit(strategy, squares) <= MAX_COMPUTE_UNIT_LIMIT);
+        }
+    }
+}
@@ -0,0 +1,201 @@
+mod strat_common;
+
+use strat_common::*;
+
+use std::sync::{Mutex, Once};
+
+use evore::consts::DEPLOY_FEE;
+use evore::events::DeployEvent;
+use evore::instruction::{create_strat_deployer, mm_strat_autodeploy};
+use evore::state::managed_miner_auth_pda;
+use solana_program::{
+    account_info::AccountInfo,
+    entrypoint::ProgramResult,
+    instruction::Instruction,
+    program_stubs::{set_syscall_stubs, SyscallStubs},
+};
+use solana_sdk::{pubkey::Pubkey, signature::Keypair, signer::Signer};
+
+// ============================================================================
+// Log capture
+// ============================================================================
+
+/// Data logged with `sol_log_data` by any builtin program in this test binary
+static LOGGED_DATA: Mutex<Vec<Vec<u8>>> = Mutex::new(Vec::new());
+
+/// Program-test records `msg!` but only prints `sol_log_data`, so wrap its stubs to keep the data
+struct CaptureLogData {
+    inner: Box<dyn SyscallStubs>,
+}
+
+impl SyscallStubs for CaptureLogData {
+    fn sol_log_data(&self, fields: &[&[u8]]) {
+        LOGGED_DATA.lock().unwrap().extend(fields.iter().map(|f| f.to_vec()));
+        self.inner.sol_log_data(fields)
+    }
+
+    fn sol_log(&self, message: &str) {
+        self.inner.sol_log(message)
+    }
+    fn sol_log_compute_units(&self) {
+        self.inner.sol_log_compute_units()
+    }
+    fn sol_remaining_compute_units(&self) -> u64 {
+        self.inner.sol_remaining_compute_units()
+    }
+    fn sol_invoke_signed(&self, instruction: &Instruction, account_infos: &[AccountInfo], signers_seeds: &[&[&[u8]]]) -> ProgramResult {
+        self.inner.sol_invoke_signed(instruction, account_infos, signers_seeds)
+    }
+    fn sol_get_sysvar(&self, sysvar_id_addr: *const u8, var_addr: *mut u8, offset: u64, length: u64) -> u64 {
+        self.inner.sol_get_sysvar(sysvar_id_addr, var_addr, offset, length)
+    }
+    fn sol_get_clock_sysvar(&self, var_addr: *mut u8) -> u64 {
+        self.inner.sol_get_clock_sysvar(var_addr)
+    }
+    fn sol_get_epoch_schedule_sysvar(&self, var_addr: *mut u8) -> u64 {
+        self.inner.sol_get_epoch_schedule_sysvar(var_addr)
+    }
+    fn sol_get_fees_sysvar(&self, var_addr: *mut u8) -> u64 {
+        self.inner.sol_get_fees_sysvar(var_addr)
+    }
+    fn sol_get_rent_sysvar(&self, var_addr: *mut u8) -> u64 {
+        self.inner.sol_get_rent_sysvar(var_addr)
+    }
+    fn sol_get_epoch_rewards_sysvar(&self, var_addr: *mut u8) -> u64 {
+        self.inner.sol_get_epoch_rewards_sysvar(var_addr)
+    }
+    fn sol_get_last_restart_slot(&self, var_addr: *mut u8) -> u64 {
+        self.inner.sol_get_last_restart_slot(var_addr)
+    }
+    fn sol_get_epoch_stake(&self, vote_address: *const u8) -> u64 {
+        self.inner.sol_get_epoch_stake(vote_address)
+    }
+    unsafe fn sol_memcpy(&self, dst: *mut u8, src: *const u8, n: usize) {
+        self.inner.sol_memcpy(dst, src, n)
+    }
+    unsafe fn sol_memmove(&self, dst: *mut u8, src: *const u8, n: usize) {
+        self.inner.sol_memmove(dst, src, n)
+    }
+    unsafe fn sol_memcmp(&self, s1: *const u8, s2: *const u8, n: usize, result: *mut i32) {
+        self.inner.sol_memcmp(s1, s2, n, result)
+    }
+    unsafe fn sol_memset(&self, s: *mut u8, c: u8, n: usize) {
+        self.inner.sol_memset(s, c, n)
+    }
+    fn sol_get_return_data(&self) -> Option<(Pubkey, Vec<u8>)> {
+        self.inner.sol_get_return_data()
+    }
+    fn sol_set_return_data(&self, data: &[u8]) {
+        self.inner.sol_set_return_data(data)
+    }
+    fn sol_get_processed_sibling_instruction(&self, index: usize) -> Option<Instruction> {
+        self.inner.sol_get_processed_sibling_instruction(index)
+    }
+    fn sol_get_stack_height(&self) -> u64 {
+        self.inner.sol_get_stack_height()
+    }
+}
+
+/// Wraps program-test's stubs once they are installed (after the first context starts)
+fn capture_log_data() {
+    static WRAP: Once = Once::new();
+    WRAP.call_once(|| {
+        // Swap in a placeholder to take ownership of the current stubs, then wrap them
+        struct Placeholder;
+        impl SyscallStubs for Placeholder {}
+        let inner = set_syscall_stubs(Box::new(Placeholder));
+        set_syscall_stubs(Box::new(CaptureLogData { inner }));
+    });
+}
+
+/// DeployEvents logged for `manager` so far
+fn deploy_events(manager: Pubkey) -> Vec<DeployEvent> {
+    LOGGED_DATA
+        .lock()
+        .unwrap()
+        .iter()
+        .filter_map(|data| DeployEvent::from_log_bytes(data))
+        .filter(|event| event.manager == manager)
+        .collect()
+}
+
+// ============================================================================
+// DeployEvent logs
+// ============================================================================
+
+#[tokio::test]
+async fn test_strat_autodeploy_logs_decodable_deploy_event() {
+    let mut program_test = setup_programs();
+    let manager = Keypair::new();
+    let authority = Keypair::new();
+    let deploy_authority = Keypair::new();
+    let auth_id: u64 = 0;
+
+    add_manager_account(&mut program_test, manager.pubkey(), authority.pubkey());
+    let (mma_pda, _) = managed_miner_auth_pda(manager.pubkey(), auth_id);
+    setup_strat_deploy_test_accounts(&mut program_test, 0, 1, 500);
+    add_autodeploy_balance(&mut program_test, mma_pda, 10_000_000_000);
+
+    let mut context = program_test.start_with_context().await;
+    capture_log_data();
+    let payer = context.payer.insecure_clone();
+
+    let fund_ix = solana_sdk::system_instruction::transfer(&payer.pubkey(), &authority.pubkey(), 2_000_000_000);
+    let fund_ix2 = solana_sdk::system_instruction::transfer(&payer.pubkey(), &deploy_authority.pubkey(), 2_000_000_000);
+    let fund_fc = solana_sdk::system_instruction::transfer(&payer.pubkey(), &evore::consts::FEE_COLLECTOR, 1_000_000);
+    send_transaction(&mut context, &[fund_ix, fund_ix2, fund_fc], &[&payer]).await.unwrap();
+    context.last_blockhash = context.banks_client.get_latest_blockhash().await.unwrap();
+
+    let ix = create_strat_deployer(
+        authority.pubkey(), manager.pubkey(), deploy_authority.pubkey(),
+        1000, 5_000, 0, // 10% bps + 5k flat
+        2, // Manual
+        manual_strategy_data(),
+        0,
+        0,
+        0,
+    );
+    send_transaction(&mut context, &[ix], &[&payer, &authority]).await.unwrap();
+    context.last_blockhash = context.banks_client.get_latest_blockhash().await.unwrap();
+    assert!(deploy_events(manager.pubkey()).is_empty(), "nothing deployed yet");
+
+    let amount: u64 = 100_000_000;
+    let squares_mask: u32 = 0b1001_0011;
+    let ix = mm_strat_autodeploy(deploy_authority.pubkey(), manager.pubkey(), auth_id, amount, squares_mask, 0);
+    send_transaction(&mut context, &[ix], &[&payer, &deploy_authority]).await.unwrap();
+
+    let total_deployed = amount * 4;
+    assert_eq!(
+        deploy_events(manager.pubkey()),
+        vec![DeployEvent {
+            manager: manager.pubkey(),
+            auth_id,
+            round_id: 0,
+            total_deployed,
+            fee: total_deployed * 1000 / 10_000 + 5_000 + DEPLOY_FEE,
+            squares_mask,
+            _padding: [0; 4],
+        }]
+    );
+}
+
+#[test]
+fn test_deploy_event_log_bytes_round_trip() {
+    let event = DeployEvent {
+        manager: Pubkey::new_unique(),
+        auth_id: 3,
+        round_id: 70_149,
+        total_deployed: 600_000,
+        fee: 31_715,
+        squares_mask: 0x1FF_FFFF,
+        _padding: [0; 4],
+    };
+    let bytes = event.to_log_bytes();
+    assert_eq!(bytes.len(), 8 + std::mem::size_of::<DeployEvent>());
+    assert_eq!(DeployEvent::from_log_bytes(&bytes), Some(event));
+
+    // Other program data never decodes as an event
+    assert!(DeployEvent::from_log_bytes(&bytes[1..]).is_none());
+    assert!(DeployEvent::from_log_bytes(&bytes[..bytes.len() - 1]).is_none());
+    assert!(DeployEvent::from_log_bytes(&600_000u64.to_le_bytes()).is_none());
+}
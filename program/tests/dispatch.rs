@@ -0,0 +1,25 @@
+use evore::{
+    instruction::Instructions,
+    processor::{handler, HANDLERS},
+};
+
+#[test]
+fn test_every_instruction_has_a_handler() {
+    let variants: Vec<Instructions> = (0..=u8::MAX)
+        .filter_map(|d| Instructions::try_from(d).ok())
+        .collect();
+    assert!(!variants.is_empty());
+
+    for ix in &variants {
+        assert!(handler(*ix).is_some(), "{:?} has no registered handler", ix);
+    }
+    assert_eq!(HANDLERS.len(), variants.len(), "handler table has stale or extra entries");
+}
+
+#[test]
+fn test_each_instruction_registered_once() {
+    for (i, (ix, _)) in HANDLERS.iter().enumerate() {
+        let count = HANDLERS.iter().filter(|(other, _)| other == ix).count();
+        assert_eq!(count, 1, "{:?} registered {} times (entry {})", ix, count, i);
+    }
+}
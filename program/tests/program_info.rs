@@ -0,0 +1,40 @@
+use evore::{
+    consts::PROGRAM_VERSION,
+    instruction::{get_program_info, Instructions, ProgramInfo},
+    validation::StrategyType,
+};
+use solana_program_test::{processor, ProgramTest};
+use solana_sdk::{signer::Signer, transaction::Transaction};
+
+#[tokio::test]
+async fn test_get_program_info_returns_version_and_features() {
+    let program_test = ProgramTest::new("evore", evore::id(), processor!(evore::process_instruction));
+    let context = program_test.start_with_context().await;
+
+    let tx = Transaction::new_signed_with_payer(
+        &[get_program_info()],
+        Some(&context.payer.pubkey()),
+        &[&context.payer],
+        context.last_blockhash,
+    );
+    let result = context.banks_client.process_transaction_with_metadata(tx).await.unwrap();
+    result.result.expect("GetProgramInfo should succeed");
+
+    let return_data = result.metadata.unwrap().return_data.expect("program info is returned");
+    assert_eq!(return_data.program_id, evore::id());
+    let info = ProgramInfo::from_return_data(&return_data.data).expect("return data is a ProgramInfo");
+
+    assert_eq!(info, ProgramInfo::current());
+    assert_eq!(info.version, PROGRAM_VERSION);
+    assert!(info.supports_instruction(Instructions::MMStratFullAutodeploy));
+    assert!(info.supports_instruction(Instructions::GetProgramInfo));
+    assert!(info.supports_strategy(StrategyType::Ev));
+    assert!(info.supports_strategy(StrategyType::Weighted));
+    assert_eq!(info.strategies >> (StrategyType::Weighted as u8 + 1), 0, "no strategy past the last variant");
+}
+
+#[test]
+fn test_from_return_data_rejects_wrong_length() {
+    assert!(ProgramInfo::from_return_data(&[0u8; 8]).is_none());
+    assert!(ProgramInfo::from_return_data(&[]).is_none());
+}
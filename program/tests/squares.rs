@@ -0,0 +1,36 @@
+use evore::{
+    error::EvoreError,
+    squares::{indices_to_mask, mask_to_squares, squares_to_mask, ALL_SQUARES_MASK},
+};
+use solana_program::program_error::ProgramError;
+
+#[test]
+fn test_indices_to_mask_sets_listed_bits() {
+    // "0,3,7,12"
+    let mask = indices_to_mask(&[0, 3, 7, 12]).unwrap();
+    assert_eq!(mask, (1 << 0) | (1 << 3) | (1 << 7) | (1 << 12));
+}
+
+#[test]
+fn test_indices_to_mask_all_squares() {
+    let all: Vec<usize> = (0..25).collect();
+    assert_eq!(indices_to_mask(&all).unwrap() as u64, ALL_SQUARES_MASK);
+}
+
+#[test]
+fn test_indices_to_mask_duplicates_are_idempotent() {
+    assert_eq!(indices_to_mask(&[4, 4, 4]).unwrap(), 1 << 4);
+}
+
+#[test]
+fn test_indices_to_mask_rejects_out_of_range() {
+    let err = indices_to_mask(&[0, 25]).unwrap_err();
+    assert_eq!(err, ProgramError::from(EvoreError::InvalidSquareCount));
+}
+
+#[test]
+fn test_squares_to_mask_round_trips() {
+    for mask in [0u32, 1, 0b1010_0101, 1 << 24, ALL_SQUARES_MASK as u32] {
+        assert_eq!(squares_to_mask(&mask_to_squares(mask as u64)), mask);
+    }
+}
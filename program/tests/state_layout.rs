@@ -0,0 +1,195 @@
+//! Byte-layout golden tests for program state accounts
+//!
+//! The crank (and any other off-chain client) reads these accounts with raw offsets and
+//! GPA memcmp filters, e.g. discriminator at 0 and deploy_authority at 40. These tests pin
+//! the exact serialized bytes so a field reorder or size change fails here first.
+
+use evore::state::{DeployAuthorityAllowlist, DeploySchedule, Deployer, EvoreAccount, Manager, StrategyDeployer};
+use solana_sdk::pubkey::Pubkey;
+use steel::AccountDeserialize;
+
+// ============================================================================
+// Helpers
+// ============================================================================
+
+/// Account data as stored on-chain: 8-byte LE discriminator followed by the struct
+fn account_data(discriminator: EvoreAccount, body: &[u8]) -> Vec<u8> {
+    let mut data = (discriminator as u64).to_le_bytes().to_vec();
+    data.extend_from_slice(body);
+    data
+}
+
+fn key(fill: u8) -> Pubkey {
+    Pubkey::new_from_array([fill; 32])
+}
+
+// ============================================================================
+// Discriminators
+// ============================================================================
+
+#[test]
+fn test_discriminators_are_stable() {
+    assert_eq!(EvoreAccount::Manager as u8, 100);
+    assert_eq!(EvoreAccount::Deployer as u8, 101);
+    assert_eq!(EvoreAccount::StrategyDeployer as u8, 102);
+    assert_eq!(EvoreAccount::DeployAuthorityAllowlist as u8, 103);
+    assert_eq!(EvoreAccount::DeploySchedule as u8, 104);
+}
+
+// ============================================================================
+// Manager
+// ============================================================================
+
+#[test]
+fn test_manager_layout() {
+    let manager = Manager { authority: key(0xA1), max_deploy_per_tx: 0x7777, pending_authority: key(0xB2), reserved: [0; 3] };
+    let data = account_data(EvoreAccount::Manager, manager.to_bytes());
+
+    let mut expected = vec![100, 0, 0, 0, 0, 0, 0, 0];
+    expected.extend_from_slice(&[0xA1; 32]);              // 8..40 authority
+    expected.extend_from_slice(&0x7777u64.to_le_bytes()); // 40..48 max_deploy_per_tx
+    expected.extend_from_slice(&[0xB2; 32]);              // 48..80 pending_authority
+    expected.extend_from_slice(&[0; 24]);                 // 80..104 reserved
+    assert_eq!(data, expected);
+    assert_eq!(data.len(), 104);
+
+    assert_eq!(*Manager::try_from_bytes(&data).unwrap(), manager);
+}
+
+// ============================================================================
+// Deployer
+// ============================================================================
+
+#[test]
+fn test_deployer_layout() {
+    let deployer = Deployer {
+        manager_key: key(0xB1),
+        deploy_authority: key(0xB2),
+        bps_fee: 0x0101,
+        flat_fee: 0x0202,
+        expected_bps_fee: 0x0303,
+        expected_flat_fee: 0x0404,
+        max_per_round: 0x0505,
+    };
+    let data = account_data(EvoreAccount::Deployer, deployer.to_bytes());
+
+    let mut expected = vec![101, 0, 0, 0, 0, 0, 0, 0];
+    expected.extend_from_slice(&[0xB1; 32]);                 // 8..40 manager_key
+    expected.extend_from_slice(&[0xB2; 32]);                 // 40..72 deploy_authority
+    expected.extend_from_slice(&0x0101u64.to_le_bytes());    // 72..80 bps_fee
+    expected.extend_from_slice(&0x0202u64.to_le_bytes());    // 80..88 flat_fee
+    expected.extend_from_slice(&0x0303u64.to_le_bytes());    // 88..96 expected_bps_fee
+    expected.extend_from_slice(&0x0404u64.to_le_bytes());    // 96..104 expected_flat_fee
+    expected.extend_from_slice(&0x0505u64.to_le_bytes());    // 104..112 max_per_round
+    assert_eq!(data, expected);
+    assert_eq!(data.len(), 112);
+
+    // Offsets the crank's GPA filters and UpdateDeployer processor rely on
+    assert_eq!(&data[40..72], deployer.deploy_authority.as_ref());
+    assert_eq!(&data[104..112], &0x0505u64.to_le_bytes());
+
+    assert_eq!(*Deployer::try_from_bytes(&data).unwrap(), deployer);
+}
+
+// ============================================================================
+// StrategyDeployer
+// ============================================================================
+
+#[test]
+fn test_strategy_deployer_layout() {
+    let mut strategy_data = [0u8; 64];
+    for (i, b) in strategy_data.iter_mut().enumerate() {
+        *b = i as u8;
+    }
+    let strat = StrategyDeployer {
+        manager_key: key(0xC1),
+        deploy_authority: key(0xC2),
+        bps_fee: 0x1111,
+        flat_fee: 0x2222,
+        expected_bps_fee: 0x3333,
+        expected_flat_fee: 0x4444,
+        max_per_round: 0x5555,
+        strategy_type: 5,
+        strategy_data,
+        max_squares_per_deploy: 10,
+        blocked_squares_mask: 0x0100_0081u32.to_le_bytes(),
+        _padding: [0; 2],
+        max_loss_per_round: 0x6666,
+    };
+    let data = account_data(EvoreAccount::StrategyDeployer, strat.to_bytes());
+
+    let mut expected = vec![102, 0, 0, 0, 0, 0, 0, 0];
+    expected.extend_from_slice(&[0xC1; 32]);                 // 8..40 manager_key
+    expected.extend_from_slice(&[0xC2; 32]);                 // 40..72 deploy_authority
+    expected.extend_from_slice(&0x1111u64.to_le_bytes());    // 72..80 bps_fee
+    expected.extend_from_slice(&0x2222u64.to_le_bytes());    // 80..88 flat_fee
+    expected.extend_from_slice(&0x3333u64.to_le_bytes());    // 88..96 expected_bps_fee
+    expected.extend_from_slice(&0x4444u64.to_le_bytes());    // 96..104 expected_flat_fee
+    expected.extend_from_slice(&0x5555u64.to_le_bytes());    // 104..112 max_per_round
+    expected.push(5);                                        // 112 strategy_type
+    expected.extend_from_slice(&strategy_data);              // 113..177 strategy_data
+    expected.push(10);                                       // 177 max_squares_per_deploy
+    expected.extend_from_slice(&[0x81, 0, 0, 0x01]);         // 178..182 blocked_squares_mask
+    expected.extend_from_slice(&[0; 2]);                     // 182..184 padding
+    expected.extend_from_slice(&0x6666u64.to_le_bytes());    // 184..192 max_loss_per_round
+    assert_eq!(data, expected);
+    assert_eq!(data.len(), 192);
+
+    assert_eq!(*StrategyDeployer::try_from_bytes(&data).unwrap(), strat);
+}
+
+// ============================================================================
+// DeployAuthorityAllowlist
+// ============================================================================
+
+#[test]
+fn test_deploy_authority_allowlist_layout() {
+    let mut authorities = [Pubkey::default(); 8];
+    authorities[0] = key(0xD2);
+    authorities[1] = key(0xD3);
+    let allowlist = DeployAuthorityAllowlist {
+        manager_key: key(0xD1),
+        count: 2,
+        authorities,
+    };
+    let data = account_data(EvoreAccount::DeployAuthorityAllowlist, allowlist.to_bytes());
+
+    let mut expected = vec![103, 0, 0, 0, 0, 0, 0, 0];
+    expected.extend_from_slice(&[0xD1; 32]);                 // 8..40 manager_key
+    expected.extend_from_slice(&2u64.to_le_bytes());         // 40..48 count
+    expected.extend_from_slice(&[0xD2; 32]);                 // 48..80 authorities[0]
+    expected.extend_from_slice(&[0xD3; 32]);                 // 80..112 authorities[1]
+    expected.extend_from_slice(&[0; 32 * 6]);                // 112..304 unused entries
+    assert_eq!(data, expected);
+    assert_eq!(data.len(), 304);
+
+    assert_eq!(*DeployAuthorityAllowlist::try_from_bytes(&data).unwrap(), allowlist);
+}
+
+// ============================================================================
+// DeploySchedule
+// ============================================================================
+
+#[test]
+fn test_deploy_schedule_layout() {
+    let mut amounts = [0u64; 16];
+    amounts[0] = 0x1111;
+    amounts[1] = 0x2222;
+    let schedule = DeploySchedule {
+        manager_key: key(0xE1),
+        len: 2,
+        amounts,
+    };
+    let data = account_data(EvoreAccount::DeploySchedule, schedule.to_bytes());
+
+    let mut expected = vec![104, 0, 0, 0, 0, 0, 0, 0];
+    expected.extend_from_slice(&[0xE1; 32]);                 // 8..40 manager_key
+    expected.extend_from_slice(&2u64.to_le_bytes());         // 40..48 len
+    expected.extend_from_slice(&0x1111u64.to_le_bytes());    // 48..56 amounts[0]
+    expected.extend_from_slice(&0x2222u64.to_le_bytes());    // 56..64 amounts[1]
+    expected.extend_from_slice(&[0; 8 * 14]);                // 64..176 unused entries
+    assert_eq!(data, expected);
+    assert_eq!(data.len(), 176);
+
+    assert_eq!(*DeploySchedule::try_from_bytes(&data).unwrap(), schedule);
+}
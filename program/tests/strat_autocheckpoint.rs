@@ -60,6 +60,9 @@ async fn setup_checkpoint_test() -> (
     let ix = create_strat_deployer(
         authority.pubkey(), manager.pubkey(), deploy_authority.pubkey(),
         0, 0, 1_000_000_000, 2, manual_strategy_data(),
+        0,
+        0,
+        0,
     );
     send_transaction(&mut context, &[ix], &[&payer, &authority]).await.unwrap();
     context.last_blockhash = context.banks_client.get_latest_blockhash().await.unwrap();
@@ -3,8 +3,12 @@ mod strat_common;
 use strat_common::*;
 
 use evore::state::{strategy_deployer_pda, managed_miner_auth_pda};
+use evore::error::EvoreError;
 use evore::instruction::{create_strat_deployer, mm_strat_autodeploy};
-use solana_sdk::{signature::Keypair, signer::Signer, pubkey::Pubkey};
+use solana_sdk::{
+    instruction::InstructionError, pubkey::Pubkey, signature::Keypair, signer::Signer,
+    transaction::TransactionError,
+};
 
 async fn setup_dsp_test(
     percentage: u64,
@@ -47,6 +51,9 @@ async fn setup_dsp_test(
         0, 0, 0,
         4, // DynamicSplitPercentage
         strategy_data,
+        0,
+        0,
+        0,
     );
     send_transaction(&mut context, &[ix], &[&payer, &authority]).await.unwrap();
     context.last_blockhash = context.banks_client.get_latest_blockhash().await.unwrap();
@@ -71,3 +78,44 @@ async fn test_dsp_deploys_to_masked_squares() {
     let result = send_transaction(&mut context, &[ix], &[&payer, &deploy_authority]).await;
     assert!(result.is_ok(), "DSP autodeploy should succeed: {:?}", result.err());
 }
+
+/// A mask selecting a 26th square (bit 25) must fail with InvalidSquareCount
+#[tokio::test]
+async fn test_dsp_rejects_mask_beyond_25_squares() {
+    let mut program_test = setup_programs();
+    let manager = Keypair::new();
+    let authority = Keypair::new();
+    let deploy_authority = Keypair::new();
+    let auth_id: u64 = 0;
+
+    add_manager_account(&mut program_test, manager.pubkey(), authority.pubkey());
+    let (mma_pda, _) = managed_miner_auth_pda(manager.pubkey(), auth_id);
+    setup_strat_deploy_test_accounts(&mut program_test, 0, 1, 500);
+    add_autodeploy_balance(&mut program_test, mma_pda, 50_000_000_000);
+    fund_account(&mut program_test, deploy_authority.pubkey(), 2_000_000_000);
+
+    // Written directly: create_strat_deployer would refuse this strategy data
+    let (strat_deployer, _) = strategy_deployer_pda(manager.pubkey());
+    add_strat_deployer_account(
+        &mut program_test, strat_deployer, manager.pubkey(), deploy_authority.pubkey(),
+        0, 0, 0, 0, 0,
+        4, // DynamicSplitPercentage,
+        dsp_strategy_data(2000, (1 << 25) | 0b11111, 0, 0),
+        0,
+    );
+
+    let mut context = program_test.start_with_context().await;
+    let payer = context.payer.insecure_clone();
+
+    let ix = mm_strat_autodeploy(
+        deploy_authority.pubkey(), manager.pubkey(), auth_id,
+        5_000_000_000, 0, 0,
+    );
+    let err = send_transaction(&mut context, &[ix], &[&payer, &deploy_authority])
+        .await
+        .expect_err("bit 25 in the mask should be rejected");
+    assert_eq!(
+        err.unwrap(),
+        TransactionError::InstructionError(1, InstructionError::Custom(EvoreError::InvalidSquareCount as u32)),
+    );
+}
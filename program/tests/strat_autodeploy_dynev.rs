@@ -47,6 +47,9 @@ async fn setup_dynev_test(
         0, 0, 0,
         5, // DynamicEv
         strategy_data,
+        0,
+        0,
+        0,
     );
     send_transaction(&mut context, &[ix], &[&payer, &authority]).await.unwrap();
     context.last_blockhash = context.banks_client.get_latest_blockhash().await.unwrap();
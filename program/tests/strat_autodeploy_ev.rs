@@ -4,8 +4,20 @@ use strat_common::*;
 
 use evore::state::{strategy_deployer_pda, managed_miner_auth_pda};
 use evore::instruction::{create_strat_deployer, mm_strat_autodeploy};
-use evore::ore_api::miner_pda;
-use solana_sdk::{signature::Keypair, signer::Signer, pubkey::Pubkey};
+use evore::ore_api::{miner_pda, Miner};
+use evore::error::EvoreError;
+use solana_sdk::{
+    instruction::InstructionError, pubkey::Pubkey, signature::Keypair, signer::Signer,
+    transaction::TransactionError,
+};
+use steel::AccountDeserialize;
+
+/// EV strategy data that also skips squares returning less than `min_ev_bps` over their stake
+fn ev_strategy_data_min_ev(max_per_square: u64, min_bet: u64, slots_left: u64, ore_value: u64, min_ev_bps: u64) -> [u8; 64] {
+    let mut d = ev_strategy_data(max_per_square, min_bet, slots_left, ore_value);
+    d[40..48].copy_from_slice(&min_ev_bps.to_le_bytes());
+    d
+}
 
 async fn setup_ev_test(
     max_per_square: u64,
@@ -19,6 +31,20 @@ async fn setup_ev_test(
     Pubkey,   // manager pubkey
     Pubkey,   // managed_miner_auth
     u64,      // auth_id
+) {
+    let strategy_data = ev_strategy_data(max_per_square, min_bet, slots_left, ore_value);
+    setup_ev_test_with_data(strategy_data, max_per_round).await
+}
+
+async fn setup_ev_test_with_data(
+    strategy_data: [u8; 64],
+    max_per_round: u64,
+) -> (
+    solana_program_test::ProgramTestContext,
+    Keypair,  // deploy_authority
+    Pubkey,   // manager pubkey
+    Pubkey,   // managed_miner_auth
+    u64,      // auth_id
 ) {
     let mut program_test = setup_programs();
     let manager = Keypair::new();
@@ -33,8 +59,6 @@ async fn setup_ev_test(
     setup_strat_deploy_test_accounts(&mut program_test, 0, 1, 500);
     add_autodeploy_balance(&mut program_test, mma_pda, 50_000_000_000);
 
-    let strategy_data = ev_strategy_data(max_per_square, min_bet, slots_left, ore_value);
-
     let mut context = program_test.start_with_context().await;
     let payer = context.payer.insecure_clone();
 
@@ -56,6 +80,9 @@ async fn setup_ev_test(
         0, 0, max_per_round,
         0, // EV
         strategy_data,
+        0,
+        0,
+        0,
     );
     send_transaction(&mut context, &[ix], &[&payer, &authority]).await.unwrap();
     context.last_blockhash = context.banks_client.get_latest_blockhash().await.unwrap();
@@ -118,3 +145,137 @@ async fn test_ev_small_bankroll_still_deploys() {
     let result = send_transaction(&mut context, &[ix], &[&payer, &deploy_authority]).await;
     assert!(result.is_ok(), "EV with small bankroll should deploy to best squares: {:?}", result.err());
 }
+
+// ============================================================================
+// EV strategy: max_squares cap
+// ============================================================================
+
+/// Runs one EV autodeploy and returns the per-square amounts that landed on the miner
+async fn ev_deploy_landed(strategy_data: [u8; 64], bankroll: u64) -> [u64; 25] {
+    let (mut context, deploy_authority, manager, mma_pda, auth_id) =
+        setup_ev_test_with_data(strategy_data, 0).await;
+    let payer = context.payer.insecure_clone();
+
+    let ix = mm_strat_autodeploy(deploy_authority.pubkey(), manager, auth_id, bankroll, 0, 0);
+    let result = send_transaction(&mut context, &[ix], &[&payer, &deploy_authority]).await;
+    assert!(result.is_ok(), "EV autodeploy should succeed: {:?}", result.err());
+
+    let (ore_miner, _) = miner_pda(mma_pda);
+    let account = context.banks_client.get_account(ore_miner).await.unwrap().unwrap();
+    Miner::try_from_bytes(&account.data).unwrap().deployed
+}
+
+#[tokio::test]
+async fn test_ev_max_squares_funds_only_top_n() {
+    let max_squares: u64 = 2;
+    let bankroll: u64 = 5_000_000_000;
+
+    let uncapped = ev_strategy_data(500_000_000, 1_000_000, 500, 2_000_000_000);
+    let mut capped = uncapped;
+    capped[32..40].copy_from_slice(&max_squares.to_le_bytes());
+
+    let all = ev_deploy_landed(uncapped, bankroll).await;
+    let top = ev_deploy_landed(capped, bankroll).await;
+
+    let funded_all = all.iter().filter(|&&x| x > 0).count() as u64;
+    assert!(funded_all > max_squares, "more than max_squares squares should be +EV, got {}", funded_all);
+
+    let funded_top: Vec<usize> = (0..25).filter(|&i| top[i] > 0).collect();
+    assert_eq!(funded_top.len() as u64, max_squares);
+
+    // The kept squares carry the same stake as uncapped and beat every dropped square on EV
+    assert_kept_top_ev(&all, &top, &funded_top, 2_000_000_000);
+}
+
+/// Checks `kept` are the highest-EV squares of the uncapped plan `all`
+fn assert_kept_top_ev(all: &[u64; 25], top: &[u64; 25], kept: &[usize], ore_value: u64) {
+    let deployed = strat_round_deployed();
+    let total: u128 = deployed.iter().map(|&t| t as u128).sum();
+    let ev = |i: usize| -> f64 {
+        let t = deployed[i] as f64;
+        let x = all[i] as f64;
+        let l = (total - deployed[i] as u128) as f64;
+        // Same SOL + ORE expected profit the program ranks squares by
+        x * (891.0 * l - 24_010.0 * (t + x)) / (25_000.0 * (t + x)) + ore_value as f64 * x / (25.0 * (t + x))
+    };
+
+    for &i in kept {
+        assert_eq!(top[i], all[i], "square {} stake should be unchanged by the cap", i);
+    }
+    let worst_kept = kept.iter().map(|&i| ev(i)).fold(f64::INFINITY, f64::min);
+    for i in (0..25).filter(|i| all[*i] > 0 && !kept.contains(i)) {
+        assert!(ev(i) <= worst_kept, "dropped square {} has higher EV than a kept one", i);
+    }
+}
+
+// ============================================================================
+// EV strategy: min_ev_bps threshold
+// ============================================================================
+
+#[tokio::test]
+async fn test_ev_zero_min_ev_bps_deploys_all_positive_ev_squares() {
+    let bankroll: u64 = 5_000_000_000;
+
+    let plain = ev_strategy_data(500_000_000, 1_000_000, 500, 2_000_000_000);
+    let zero = ev_strategy_data_min_ev(500_000_000, 1_000_000, 500, 2_000_000_000, 0);
+
+    let all = ev_deploy_landed(plain, bankroll).await;
+    assert!(all.iter().filter(|&&x| x > 0).count() > 1, "several squares should be +EV");
+    assert_eq!(ev_deploy_landed(zero, bankroll).await, all, "0 bps keeps every +EV square");
+}
+
+#[tokio::test]
+async fn test_ev_min_ev_bps_skips_thin_margin_squares() {
+    let bankroll: u64 = 5_000_000_000;
+    let ore_value: u64 = 2_000_000_000;
+
+    let all = ev_deploy_landed(ev_strategy_data(500_000_000, 1_000_000, 500, ore_value), bankroll).await;
+    let mut margins: Vec<f64> = (0..25).filter(|&i| all[i] > 0).map(|i| ev_margin_bps(&all, i, ore_value)).collect();
+    margins.sort_by(|a, b| a.partial_cmp(b).unwrap());
+
+    // Threshold halfway between the two thinnest margins drops exactly the thinnest square
+    let min_ev_bps = ((margins[0] + margins[1]) / 2.0) as u64;
+    let landed = ev_deploy_landed(
+        ev_strategy_data_min_ev(500_000_000, 1_000_000, 500, ore_value, min_ev_bps),
+        bankroll,
+    )
+    .await;
+    for i in (0..25).filter(|&i| all[i] > 0) {
+        if ev_margin_bps(&all, i, ore_value) >= min_ev_bps as f64 {
+            assert_eq!(landed[i], all[i], "square {} clears the threshold and keeps its stake", i);
+        } else {
+            assert_eq!(landed[i], 0, "square {} is below the threshold", i);
+        }
+    }
+    assert_eq!(landed.iter().filter(|&&x| x > 0).count(), margins.len() - 1);
+}
+
+#[tokio::test]
+async fn test_ev_min_ev_bps_above_every_square_fails() {
+    let ore_value: u64 = 2_000_000_000;
+    let all = ev_deploy_landed(ev_strategy_data(500_000_000, 1_000_000, 500, ore_value), 5_000_000_000).await;
+    let best = (0..25).filter(|&i| all[i] > 0).map(|i| ev_margin_bps(&all, i, ore_value)).fold(0.0, f64::max);
+
+    let strategy_data = ev_strategy_data_min_ev(500_000_000, 1_000_000, 500, ore_value, best as u64 + 1);
+    let (mut context, deploy_authority, manager, _mma_pda, auth_id) =
+        setup_ev_test_with_data(strategy_data, 0).await;
+    let payer = context.payer.insecure_clone();
+
+    let ix = mm_strat_autodeploy(deploy_authority.pubkey(), manager, auth_id, 5_000_000_000, 0, 0);
+    let result = send_transaction(&mut context, &[ix], &[&payer, &deploy_authority]).await;
+    assert_eq!(
+        result.unwrap_err().unwrap(),
+        TransactionError::InstructionError(1, InstructionError::Custom(EvoreError::NoDeployments as u32)),
+    );
+}
+
+/// Expected profit of square `i` of the plan `all` over its stake, in basis points
+fn ev_margin_bps(all: &[u64; 25], i: usize, ore_value: u64) -> f64 {
+    let deployed = strat_round_deployed();
+    let total: u128 = deployed.iter().map(|&t| t as u128).sum();
+    let t = deployed[i] as f64;
+    let x = all[i] as f64;
+    let l = (total - deployed[i] as u128) as f64;
+    let ev = x * (891.0 * l - 24_010.0 * (t + x)) / (25_000.0 * (t + x)) + ore_value as f64 * x / (25.0 * (t + x));
+    ev * 10_000.0 / x
+}
@@ -0,0 +1,75 @@
+mod strat_common;
+
+use strat_common::*;
+
+use evore::instruction::mm_strat_autodeploy;
+use solana_sdk::{pubkey::Pubkey, signature::Keypair, signer::Signer};
+
+/// Squares 0, 4 and 10: the most, a middling and the least crowded square of the board
+const SKEWED_MASK: u64 = (1 << 0) | (1 << 4) | (1 << 10);
+
+async fn setup_loser_pool_test(
+    fraction_bps: u64,
+    squares_mask: u64,
+) -> (
+    solana_program_test::ProgramTestContext,
+    Keypair,  // deploy_authority
+    Pubkey,   // manager pubkey
+    Pubkey,   // managed_miner_auth
+    u64,      // auth_id
+) {
+    setup_strat_autodeploy_test(
+        7, // LoserPool
+        loser_pool_strategy_data(fraction_bps, squares_mask),
+        0,
+    )
+    .await
+}
+
+// ============================================================================
+// LoserPool strategy: bet a fraction of what the other squares hold
+// ============================================================================
+
+#[tokio::test]
+async fn test_loser_pool_amounts_scale_with_loser_pool() {
+    // 10% of each square's loser pool
+    let (mut context, deploy_authority, manager, mma_pda, auth_id) =
+        setup_loser_pool_test(1_000, SKEWED_MASK).await;
+    let payer = context.payer.insecure_clone();
+
+    let ix = mm_strat_autodeploy(deploy_authority.pubkey(), manager, auth_id, 10_000_000_000, 0, 0);
+    let result = send_transaction(&mut context, &[ix], &[&payer, &deploy_authority]).await;
+    assert!(result.is_ok(), "LoserPool autodeploy should succeed: {:?}", result.err());
+
+    let round = strat_round_deployed();
+    let total: u64 = round.iter().sum();
+    let deployed = miner_deployed(&mut context, mma_pda).await;
+    for (i, (&landed, &on_square)) in deployed.iter().zip(round.iter()).enumerate() {
+        let expected = if (SKEWED_MASK >> i) & 1 == 1 { (total - on_square) / 10 } else { 0 };
+        assert_eq!(landed, expected, "square {} landed amount", i);
+    }
+
+    // The emptier the square, the bigger its loser pool and the bet
+    assert_eq!(deployed[0], 940_000_000);
+    assert_eq!(deployed[4], 1_140_000_000);
+    assert_eq!(deployed[10], 1_230_000_000);
+}
+
+#[tokio::test]
+async fn test_loser_pool_capped_by_bankroll() {
+    let (mut context, deploy_authority, manager, mma_pda, auth_id) =
+        setup_loser_pool_test(1_000, SKEWED_MASK).await;
+    let payer = context.payer.insecure_clone();
+
+    // Covers square 0 in full, only part of square 4 and nothing of square 10
+    let bankroll: u64 = 2_000_000_000;
+    let ix = mm_strat_autodeploy(deploy_authority.pubkey(), manager, auth_id, bankroll, 0, 0);
+    let result = send_transaction(&mut context, &[ix], &[&payer, &deploy_authority]).await;
+    assert!(result.is_ok(), "LoserPool autodeploy should succeed: {:?}", result.err());
+
+    let deployed = miner_deployed(&mut context, mma_pda).await;
+    assert_eq!(deployed[0], 940_000_000);
+    assert_eq!(deployed[4], 1_060_000_000);
+    assert_eq!(deployed[10], 0);
+    assert_eq!(deployed.iter().sum::<u64>(), bankroll);
+}
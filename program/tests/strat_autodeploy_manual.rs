@@ -3,15 +3,23 @@ mod strat_common;
 use strat_common::*;
 
 use evore::consts::FEE_COLLECTOR;
+use evore::error::EvoreError;
 use evore::state::{strategy_deployer_pda, managed_miner_auth_pda};
 use evore::instruction::{create_strat_deployer, mm_strat_autodeploy};
-use evore::ore_api::miner_pda;
-use solana_sdk::{signature::Keypair, signer::Signer, pubkey::Pubkey};
+use evore::ore_api::{miner_pda, Miner};
+use solana_sdk::{
+    instruction::InstructionError, pubkey::Pubkey, signature::Keypair, signer::Signer,
+    transaction::TransactionError,
+};
+use steel::AccountDeserialize;
 
 async fn setup_manual_autodeploy_test(
     bps_fee: u64,
     flat_fee: u64,
     max_per_round: u64,
+    max_squares_per_deploy: u8,
+    blocked_squares_mask: u32,
+    max_loss_per_round: u64,
 ) -> (
     solana_program_test::ProgramTestContext,
     Keypair,  // deploy_authority
@@ -57,6 +65,9 @@ async fn setup_manual_autodeploy_test(
         bps_fee, flat_fee, max_per_round,
         2, // Manual
         manual_strategy_data(),
+        max_squares_per_deploy,
+        blocked_squares_mask,
+        max_loss_per_round,
     );
     send_transaction(&mut context, &[ix], &[&payer, &authority]).await.unwrap();
     context.last_blockhash = context.banks_client.get_latest_blockhash().await.unwrap();
@@ -71,7 +82,7 @@ async fn setup_manual_autodeploy_test(
 #[tokio::test]
 async fn test_manual_single_square() {
     let (mut context, deploy_authority, manager, mma_pda, auth_id) =
-        setup_manual_autodeploy_test(0, 0, 0).await;
+        setup_manual_autodeploy_test(0, 0, 0, 0, 0, 0).await;
     let payer = context.payer.insecure_clone();
 
     // Deploy 0.1 SOL to square 0 only
@@ -94,7 +105,7 @@ async fn test_manual_single_square() {
 #[tokio::test]
 async fn test_manual_multiple_squares() {
     let (mut context, deploy_authority, manager, mma_pda, auth_id) =
-        setup_manual_autodeploy_test(0, 0, 0).await;
+        setup_manual_autodeploy_test(0, 0, 0, 0, 0, 0).await;
     let payer = context.payer.insecure_clone();
 
     // Deploy 0.05 SOL to squares 0-4
@@ -114,6 +125,91 @@ async fn test_manual_multiple_squares() {
     assert!(result.is_ok(), "Manual autodeploy multiple squares should succeed: {:?}", result.err());
 }
 
+/// A 25-square manual deploy lands on only `max_squares_per_deploy` squares
+#[tokio::test]
+async fn test_manual_max_squares_per_deploy_cap() {
+    let (mut context, deploy_authority, manager, mma_pda, auth_id) =
+        setup_manual_autodeploy_test(0, 0, 0, 10, 0, 0).await;
+    let payer = context.payer.insecure_clone();
+
+    let amount: u64 = 10_000_000; // 0.01 SOL per square
+    let ix = mm_strat_autodeploy(
+        deploy_authority.pubkey(),
+        manager,
+        auth_id,
+        amount,
+        0x1FFFFFF, // all 25 squares
+        0,
+    );
+
+    let result = send_transaction(&mut context, &[ix], &[&payer, &deploy_authority]).await;
+    assert!(result.is_ok(), "Capped manual autodeploy should succeed: {:?}", result.err());
+
+    let (ore_miner, _) = miner_pda(mma_pda);
+    let account = context.banks_client.get_account(ore_miner).await.unwrap().unwrap();
+    let deployed = Miner::try_from_bytes(&account.data).unwrap().deployed;
+    assert_eq!(deployed.iter().filter(|&&d| d > 0).count(), 10, "cap should limit the deploy to 10 squares");
+    assert_eq!(deployed.iter().sum::<u64>(), 10 * amount, "dropped squares should not be charged");
+}
+
+/// Blocked squares get nothing even though the manual mask asks for every square
+#[tokio::test]
+async fn test_manual_blocked_squares_never_funded() {
+    let blocked: u32 = (1 << 0) | (1 << 7) | (1 << 24);
+    let (mut context, deploy_authority, manager, mma_pda, auth_id) =
+        setup_manual_autodeploy_test(0, 0, 0, 0, blocked, 0).await;
+    let payer = context.payer.insecure_clone();
+
+    let amount: u64 = 10_000_000; // 0.01 SOL per square
+    let ix = mm_strat_autodeploy(
+        deploy_authority.pubkey(),
+        manager,
+        auth_id,
+        amount,
+        0x1FFFFFF, // all 25 squares
+        0,
+    );
+
+    let result = send_transaction(&mut context, &[ix], &[&payer, &deploy_authority]).await;
+    assert!(result.is_ok(), "Manual autodeploy around blocked squares should succeed: {:?}", result.err());
+
+    let (ore_miner, _) = miner_pda(mma_pda);
+    let account = context.banks_client.get_account(ore_miner).await.unwrap().unwrap();
+    let deployed = Miner::try_from_bytes(&account.data).unwrap().deployed;
+    for (i, &d) in deployed.iter().enumerate() {
+        if blocked & (1 << i) != 0 {
+            assert_eq!(d, 0, "blocked square {} must not be funded", i);
+        } else {
+            assert_eq!(d, amount, "unblocked square {} should be funded", i);
+        }
+    }
+}
+
+/// A deploy that only targets blocked squares is rejected
+#[tokio::test]
+async fn test_manual_only_blocked_squares_fails() {
+    let (mut context, deploy_authority, manager, _mma_pda, auth_id) =
+        setup_manual_autodeploy_test(0, 0, 0, 0, 0b11, 0).await;
+    let payer = context.payer.insecure_clone();
+
+    let ix = mm_strat_autodeploy(
+        deploy_authority.pubkey(),
+        manager,
+        auth_id,
+        10_000_000,
+        0b11,
+        0,
+    );
+
+    let err = send_transaction(&mut context, &[ix], &[&payer, &deploy_authority])
+        .await
+        .expect_err("a fully blocked deploy must be rejected");
+    assert_eq!(
+        err.unwrap(),
+        TransactionError::InstructionError(1, InstructionError::Custom(EvoreError::NoDeployments as u32)),
+    );
+}
+
 // ============================================================================
 // Fee calculation
 // ============================================================================
@@ -121,7 +217,7 @@ async fn test_manual_multiple_squares() {
 #[tokio::test]
 async fn test_manual_fee_calculation_bps_and_flat() {
     let (mut context, deploy_authority, manager, mma_pda, auth_id) =
-        setup_manual_autodeploy_test(1000, 10_000, 0).await; // 10% bps + 10k lamport flat
+        setup_manual_autodeploy_test(1000, 10_000, 0, 0, 0, 0).await; // 10% bps + 10k lamport flat
     let payer = context.payer.insecure_clone();
 
     let balance_before = context.banks_client
@@ -160,7 +256,7 @@ async fn test_manual_fee_calculation_bps_and_flat() {
 #[tokio::test]
 async fn test_manual_zero_amount_fails() {
     let (mut context, deploy_authority, manager, mma_pda, auth_id) =
-        setup_manual_autodeploy_test(0, 0, 0).await;
+        setup_manual_autodeploy_test(0, 0, 0, 0, 0, 0).await;
     let payer = context.payer.insecure_clone();
 
     let ix = mm_strat_autodeploy(
@@ -183,7 +279,7 @@ async fn test_manual_zero_amount_fails() {
 #[tokio::test]
 async fn test_manual_max_per_round_enforced() {
     let (mut context, deploy_authority, manager, mma_pda, auth_id) =
-        setup_manual_autodeploy_test(0, 0, 50_000_000).await; // 0.05 SOL max per round
+        setup_manual_autodeploy_test(0, 0, 50_000_000, 0, 0, 0).await; // 0.05 SOL max per round
     let payer = context.payer.insecure_clone();
 
     // Try to deploy 0.1 SOL (exceeds 0.05 max)
@@ -199,3 +295,146 @@ async fn test_manual_max_per_round_enforced() {
     let result = send_transaction(&mut context, &[ix], &[&payer, &deploy_authority]).await;
     assert!(result.is_err(), "Deploy exceeding max_per_round must fail");
 }
+
+// ============================================================================
+// Max loss per round enforced
+// ============================================================================
+
+/// 0.05 SOL on 5 squares leaves 0.25 SOL at risk, over a 0.2 SOL cap
+#[tokio::test]
+async fn test_manual_max_loss_per_round_enforced() {
+    let (mut context, deploy_authority, manager, _mma_pda, auth_id) =
+        setup_manual_autodeploy_test(0, 0, 0, 0, 0, 200_000_000).await;
+    let payer = context.payer.insecure_clone();
+
+    let ix = mm_strat_autodeploy(
+        deploy_authority.pubkey(),
+        manager,
+        auth_id,
+        50_000_000,
+        0b11111,
+        0,
+    );
+
+    let err = send_transaction(&mut context, &[ix], &[&payer, &deploy_authority])
+        .await
+        .expect_err("deploy exceeding max_loss_per_round must fail");
+    assert_eq!(
+        err.unwrap(),
+        TransactionError::InstructionError(1, InstructionError::Custom(EvoreError::ExceedsMaxLoss as u32)),
+    );
+}
+
+#[tokio::test]
+async fn test_manual_under_max_loss_per_round_succeeds() {
+    let (mut context, deploy_authority, manager, _mma_pda, auth_id) =
+        setup_manual_autodeploy_test(0, 0, 0, 0, 0, 300_000_000).await;
+    let payer = context.payer.insecure_clone();
+
+    let ix = mm_strat_autodeploy(
+        deploy_authority.pubkey(),
+        manager,
+        auth_id,
+        50_000_000,
+        0b11111,
+        0,
+    );
+
+    let result = send_transaction(&mut context, &[ix], &[&payer, &deploy_authority]).await;
+    assert!(result.is_ok(), "deploy under max_loss_per_round should succeed: {:?}", result.err());
+}
+
+/// Covering every square keeps one stake safe: 25 x 0.01 SOL risks only 0.24 SOL
+#[tokio::test]
+async fn test_manual_max_loss_counts_winning_square() {
+    let (mut context, deploy_authority, manager, _mma_pda, auth_id) =
+        setup_manual_autodeploy_test(0, 0, 0, 0, 0, 240_000_000).await;
+    let payer = context.payer.insecure_clone();
+
+    let ix = mm_strat_autodeploy(
+        deploy_authority.pubkey(),
+        manager,
+        auth_id,
+        10_000_000,
+        0x1FFFFFF,
+        0,
+    );
+
+    let result = send_transaction(&mut context, &[ix], &[&payer, &deploy_authority]).await;
+    assert!(result.is_ok(), "all-square deploy within max_loss_per_round should succeed: {:?}", result.err());
+}
+
+/// What the miner already has on the board this round counts towards the cap
+#[tokio::test]
+async fn test_manual_max_loss_includes_prior_deploys() {
+    let (mut context, deploy_authority, manager, _mma_pda, auth_id) =
+        setup_manual_autodeploy_test(0, 0, 0, 0, 0, 150_000_000).await;
+    let payer = context.payer.insecure_clone();
+
+    let first = mm_strat_autodeploy(deploy_authority.pubkey(), manager, auth_id, 100_000_000, 1, 0);
+    send_transaction(&mut context, &[first], &[&payer, &deploy_authority]).await.unwrap();
+    context.last_blockhash = context.banks_client.get_latest_blockhash().await.unwrap();
+
+    let second = mm_strat_autodeploy(deploy_authority.pubkey(), manager, auth_id, 100_000_000, 0b10, 0);
+    let err = send_transaction(&mut context, &[second], &[&payer, &deploy_authority])
+        .await
+        .expect_err("second deploy pushes the round over max_loss_per_round");
+    assert_eq!(
+        err.unwrap(),
+        TransactionError::InstructionError(1, InstructionError::Custom(EvoreError::ExceedsMaxLoss as u32)),
+    );
+}
+
+// ============================================================================
+// Overflow-safe amount math
+// ============================================================================
+
+#[tokio::test]
+async fn test_manual_amount_times_squares_overflow() {
+    let (mut context, deploy_authority, manager, _mma_pda, auth_id) =
+        setup_manual_autodeploy_test(0, 0, 0, 0, 0, 0).await;
+    let payer = context.payer.insecure_clone();
+
+    // u64::MAX / 2 + 1 on two squares wraps past u64::MAX
+    let ix = mm_strat_autodeploy(
+        deploy_authority.pubkey(),
+        manager,
+        auth_id,
+        u64::MAX / 2 + 1,
+        0b11,
+        0,
+    );
+
+    let err = send_transaction(&mut context, &[ix], &[&payer, &deploy_authority])
+        .await
+        .expect_err("amount * squares overflow must be rejected");
+    assert_eq!(
+        err.unwrap(),
+        TransactionError::InstructionError(1, InstructionError::Custom(EvoreError::ArithmeticOverflow as u32)),
+    );
+}
+
+#[tokio::test]
+async fn test_manual_bps_fee_overflow() {
+    let (mut context, deploy_authority, manager, _mma_pda, auth_id) =
+        setup_manual_autodeploy_test(1_000, 0, 0, 0, 0, 0).await; // 10%
+    let payer = context.payer.insecure_clone();
+
+    // The deploy total fits in a u64, but total * bps_fee does not
+    let ix = mm_strat_autodeploy(
+        deploy_authority.pubkey(),
+        manager,
+        auth_id,
+        u64::MAX / 100,
+        1,
+        0,
+    );
+
+    let err = send_transaction(&mut context, &[ix], &[&payer, &deploy_authority])
+        .await
+        .expect_err("bps fee overflow must be rejected");
+    assert_eq!(
+        err.unwrap(),
+        TransactionError::InstructionError(1, InstructionError::Custom(EvoreError::ArithmeticOverflow as u32)),
+    );
+}
@@ -0,0 +1,200 @@
+mod strat_common;
+
+use strat_common::*;
+
+use evore::state::managed_miner_auth_pda;
+use evore::error::EvoreError;
+use evore::instruction::{create_strat_deployer, mm_strat_autodeploy, mm_strat_autodeploy_mirror};
+use evore::ore_api::{self, board_pda, miner_pda, round_pda, Miner, Round};
+use solana_program::rent::Rent;
+use solana_program_test::ProgramTest;
+use solana_sdk::{
+    account::Account, instruction::InstructionError, pubkey::Pubkey, signature::Keypair, signer::Signer,
+    transaction::TransactionError,
+};
+use steel::AccountDeserialize;
+
+/// Top miner's current-round deployment: 30% / 10% / 60% across squares 2, 7 and 20
+fn top_miner_deployed() -> [u64; 25] {
+    let mut deployed = [0u64; 25];
+    deployed[2] = 300_000_000;
+    deployed[7] = 100_000_000;
+    deployed[20] = 600_000_000;
+    deployed
+}
+
+/// `add_round_account` with the round's top miner set
+fn add_round_account_with_top_miner(
+    program_test: &mut ProgramTest,
+    round_id: u64,
+    deployed: [u64; 25],
+    total_deployed: u64,
+    expires_at: u64,
+    top_miner: Pubkey,
+) {
+    let round = Round {
+        id: round_id,
+        deployed,
+        slot_hash: [0u8; 32],
+        count: [0u64; 25],
+        expires_at,
+        motherlode: 0,
+        rent_payer: Pubkey::default(),
+        top_miner,
+        top_miner_reward: 0,
+        total_deployed,
+        total_miners: 0,
+        total_vaulted: 0,
+        total_winnings: 0,
+    };
+
+    let mut data = Vec::new();
+    let discr = (ore_api::OreAccount::Round as u64).to_le_bytes();
+    data.extend_from_slice(&discr);
+    data.extend_from_slice(round.to_bytes());
+
+    program_test.add_account(
+        round_pda(round_id).0,
+        Account {
+            lamports: Rent::default().minimum_balance(data.len()).max(1),
+            data,
+            owner: ore_api::id(),
+            executable: false,
+            rent_epoch: 0,
+        },
+    );
+}
+
+async fn setup_mirror_test(
+    top_miner: Pubkey,
+) -> (
+    solana_program_test::ProgramTestContext,
+    Keypair,  // deploy_authority
+    Pubkey,   // manager pubkey
+    Pubkey,   // managed_miner_auth
+    u64,      // auth_id
+) {
+    let mut program_test = setup_programs();
+    let manager = Keypair::new();
+    let authority = Keypair::new();
+    let deploy_authority = Keypair::new();
+    let auth_id: u64 = 0;
+
+    add_manager_account(&mut program_test, manager.pubkey(), authority.pubkey());
+
+    let (mma_pda, _) = managed_miner_auth_pda(manager.pubkey(), auth_id);
+    add_autodeploy_balance(&mut program_test, mma_pda, 50_000_000_000);
+
+    // Same board/round as setup_strat_deploy_test_accounts, with top_miner set
+    let (round_id, current_slot, end_slot) = (0, 1, 501);
+    add_board_account(&mut program_test, round_id, current_slot, end_slot, 0);
+    let deployed = strat_round_deployed();
+    let total_deployed: u64 = deployed.iter().sum();
+    add_round_account_with_top_miner(
+        &mut program_test, round_id, deployed, total_deployed, end_slot + 1000, top_miner,
+    );
+    add_entropy_var_account(&mut program_test, board_pda().0, end_slot);
+    add_treasury_account(&mut program_test);
+    add_mint_account(&mut program_test);
+    add_treasury_ata_account(&mut program_test);
+    add_config_account(&mut program_test);
+
+    add_ore_miner_account(&mut program_test, top_miner, top_miner_deployed(), 0, 0, round_id, round_id);
+
+    let mut context = program_test.start_with_context().await;
+    let payer = context.payer.insecure_clone();
+
+    let fund_ix = solana_sdk::system_instruction::transfer(
+        &payer.pubkey(), &authority.pubkey(), 2_000_000_000,
+    );
+    let fund_ix2 = solana_sdk::system_instruction::transfer(
+        &payer.pubkey(), &deploy_authority.pubkey(), 2_000_000_000,
+    );
+    let fund_fee_collector = solana_sdk::system_instruction::transfer(
+        &payer.pubkey(), &evore::consts::FEE_COLLECTOR, 1_000_000,
+    );
+    send_transaction(&mut context, &[fund_ix, fund_ix2, fund_fee_collector], &[&payer]).await.unwrap();
+    context.last_blockhash = context.banks_client.get_latest_blockhash().await.unwrap();
+
+    let ix = create_strat_deployer(
+        authority.pubkey(), manager.pubkey(), deploy_authority.pubkey(),
+        0, 0, 0,
+        6, // MirrorTopMiner
+        [0u8; 64],
+        0,
+        0,
+        0,
+    );
+    send_transaction(&mut context, &[ix], &[&payer, &authority]).await.unwrap();
+    context.last_blockhash = context.banks_client.get_latest_blockhash().await.unwrap();
+
+    (context, deploy_authority, manager.pubkey(), mma_pda, auth_id)
+}
+
+// ============================================================================
+// MirrorTopMiner strategy: copy the top miner's distribution
+// ============================================================================
+
+#[tokio::test]
+async fn test_mirror_deploys_proportionally_to_top_miner() {
+    let top_miner = Pubkey::new_unique();
+    let (mut context, deploy_authority, manager, mma_pda, auth_id) =
+        setup_mirror_test(top_miner).await;
+    let payer = context.payer.insecure_clone();
+
+    let bankroll: u64 = 2_000_000_000;
+    let ix = mm_strat_autodeploy_mirror(deploy_authority.pubkey(), manager, auth_id, bankroll, top_miner);
+    let result = send_transaction(&mut context, &[ix], &[&payer, &deploy_authority]).await;
+    assert!(result.is_ok(), "Mirror autodeploy should succeed: {:?}", result.err());
+
+    let (ore_miner, _) = miner_pda(mma_pda);
+    let account = context.banks_client.get_account(ore_miner).await.unwrap().unwrap();
+    let miner = Miner::try_from_bytes(&account.data).unwrap();
+
+    let top = top_miner_deployed();
+    let top_total: u64 = top.iter().sum();
+    for (i, (&landed, &top_square)) in miner.deployed.iter().zip(top.iter()).enumerate() {
+        let expected = (bankroll as u128 * top_square as u128 / top_total as u128) as u64;
+        assert_eq!(landed, expected, "square {} landed amount", i);
+    }
+    assert_eq!(miner.deployed[2], 600_000_000);
+    assert_eq!(miner.deployed[7], 200_000_000);
+    assert_eq!(miner.deployed[20], 1_200_000_000);
+    assert_eq!(miner.deployed.iter().sum::<u64>(), bankroll);
+}
+
+#[tokio::test]
+async fn test_mirror_rejects_miner_that_is_not_top_miner() {
+    let top_miner = Pubkey::new_unique();
+    let (mut context, deploy_authority, manager, _mma_pda, auth_id) =
+        setup_mirror_test(top_miner).await;
+    let payer = context.payer.insecure_clone();
+
+    // A miner PDA for any other authority must be refused
+    let imposter = Pubkey::new_unique();
+    let ix = mm_strat_autodeploy_mirror(deploy_authority.pubkey(), manager, auth_id, 2_000_000_000, imposter);
+    let err = send_transaction(&mut context, &[ix], &[&payer, &deploy_authority])
+        .await
+        .expect_err("miner other than round.top_miner should be rejected");
+    assert_eq!(
+        err.unwrap(),
+        TransactionError::InstructionError(1, InstructionError::Custom(EvoreError::InvalidTopMiner as u32)),
+    );
+}
+
+#[tokio::test]
+async fn test_mirror_requires_top_miner_account() {
+    let top_miner = Pubkey::new_unique();
+    let (mut context, deploy_authority, manager, _mma_pda, auth_id) =
+        setup_mirror_test(top_miner).await;
+    let payer = context.payer.insecure_clone();
+
+    let ix = mm_strat_autodeploy(deploy_authority.pubkey(), manager, auth_id, 2_000_000_000, 0, 0);
+    let err = send_transaction(&mut context, &[ix], &[&payer, &deploy_authority])
+        .await
+        .expect_err("mirror without the top miner account should fail");
+    assert_eq!(
+        err.unwrap(),
+        TransactionError::InstructionError(1, InstructionError::NotEnoughAccountKeys),
+    );
+}
@@ -0,0 +1,66 @@
+mod strat_common;
+
+use strat_common::*;
+
+use evore::instruction::mm_strat_autodeploy;
+use solana_sdk::{pubkey::Pubkey, signature::Keypair, signer::Signer};
+
+async fn setup_momentum_test(
+    squares_count: u64,
+) -> (
+    solana_program_test::ProgramTestContext,
+    Keypair,  // deploy_authority
+    Pubkey,   // manager pubkey
+    Pubkey,   // managed_miner_auth
+    u64,      // auth_id
+) {
+    setup_strat_autodeploy_test(
+        8, // Momentum
+        momentum_strategy_data(squares_count),
+        0,
+    )
+    .await
+}
+
+// ============================================================================
+// Momentum strategy: pile onto the most crowded squares
+// ============================================================================
+
+#[tokio::test]
+async fn test_momentum_backs_highest_deployed_squares() {
+    let (mut context, deploy_authority, manager, mma_pda, auth_id) = setup_momentum_test(3).await;
+    let payer = context.payer.insecure_clone();
+
+    let bankroll: u64 = 1_500_000_000;
+    let ix = mm_strat_autodeploy(deploy_authority.pubkey(), manager, auth_id, bankroll, 0, 0);
+    let result = send_transaction(&mut context, &[ix], &[&payer, &deploy_authority]).await;
+    assert!(result.is_ok(), "Momentum autodeploy should succeed: {:?}", result.err());
+
+    // Squares 0-2 hold 3.0, 2.5 and 2.0 SOL of the skewed board: the bankroll splits 6:5:4
+    let deployed = miner_deployed(&mut context, mma_pda).await;
+    assert_eq!(deployed[0], 600_000_000);
+    assert_eq!(deployed[1], 500_000_000);
+    assert_eq!(deployed[2], 400_000_000);
+    for (i, &landed) in deployed.iter().enumerate().skip(3) {
+        assert_eq!(landed, 0, "square {} is not among the top 3", i);
+    }
+    assert_eq!(deployed.iter().sum::<u64>(), bankroll);
+}
+
+#[tokio::test]
+async fn test_momentum_skips_empty_squares() {
+    // Only 11 squares hold anything, so asking for all 25 backs just those
+    let (mut context, deploy_authority, manager, mma_pda, auth_id) = setup_momentum_test(25).await;
+    let payer = context.payer.insecure_clone();
+
+    let ix = mm_strat_autodeploy(deploy_authority.pubkey(), manager, auth_id, 10_000_000_000, 0, 0);
+    let result = send_transaction(&mut context, &[ix], &[&payer, &deploy_authority]).await;
+    assert!(result.is_ok(), "Momentum autodeploy should succeed: {:?}", result.err());
+
+    let round = strat_round_deployed();
+    let deployed = miner_deployed(&mut context, mma_pda).await;
+    for (i, (&landed, &on_square)) in deployed.iter().zip(round.iter()).enumerate() {
+        assert_eq!(landed > 0, on_square > 0, "square {} funded iff already deployed", i);
+    }
+    assert!(deployed[0] > deployed[4] && deployed[4] > deployed[10]);
+}
@@ -3,8 +3,12 @@ mod strat_common;
 use strat_common::*;
 
 use evore::state::{strategy_deployer_pda, managed_miner_auth_pda};
+use evore::error::EvoreError;
 use evore::instruction::{create_strat_deployer, mm_strat_autodeploy};
-use solana_sdk::{signature::Keypair, signer::Signer, pubkey::Pubkey};
+use solana_sdk::{
+    compute_budget::ComputeBudgetInstruction, instruction::InstructionError, pubkey::Pubkey,
+    signature::Keypair, signer::Signer, transaction::{Transaction, TransactionError},
+};
 
 async fn setup_percentage_test(
     percentage: u64,
@@ -47,6 +51,9 @@ async fn setup_percentage_test(
         0, 0, 0,
         1, // Percentage
         strategy_data,
+        0,
+        0,
+        0,
     );
     send_transaction(&mut context, &[ix], &[&payer, &authority]).await.unwrap();
     context.last_blockhash = context.banks_client.get_latest_blockhash().await.unwrap();
@@ -86,3 +93,76 @@ async fn test_percentage_small_bankroll_reduces_pct() {
     let result = send_transaction(&mut context, &[ix], &[&payer, &deploy_authority]).await;
     assert!(result.is_ok(), "Percentage with small bankroll should still deploy: {:?}", result.err());
 }
+
+#[tokio::test]
+async fn test_percentage_opens_and_closes_automation_once() {
+    let (context, deploy_authority, manager, mma_pda, auth_id) =
+        setup_percentage_test(1000, 5, 0, 0).await; // 10% of top 5 -> 5 deploy CPIs
+    let payer = context.payer.insecure_clone();
+
+    let ix = mm_strat_autodeploy(
+        deploy_authority.pubkey(), manager, auth_id,
+        5_000_000_000, 0, 0,
+    );
+    let tx = Transaction::new_signed_with_payer(
+        &[ComputeBudgetInstruction::set_compute_unit_limit(1_400_000), ix],
+        Some(&payer.pubkey()),
+        &[&payer, &deploy_authority],
+        context.last_blockhash,
+    );
+    let outcome = context.banks_client.process_transaction_with_metadata(tx).await.unwrap();
+    assert!(outcome.result.is_ok(), "Percentage autodeploy should succeed: {:?}", outcome.result);
+    let logs = outcome.metadata.unwrap().log_messages;
+
+    let ore_cpis = logs.iter()
+        .filter(|l| **l == format!("Program {} invoke [2]", evore::ore_api::id()))
+        .count();
+    let deploy_cpis = logs.iter().filter(|l| l.contains("deploying")).count();
+    assert!(deploy_cpis > 1, "expected a multi-square deploy, got {} deploy CPIs", deploy_cpis);
+    // Everything else the program sends to ORE is the automation open + close pair
+    assert_eq!(ore_cpis - deploy_cpis, 2, "automation should be opened and closed exactly once");
+
+    let (automation, _) = evore::ore_api::automation_pda(mma_pda);
+    assert!(context.banks_client.get_account(automation).await.unwrap().is_none(), "automation should be closed");
+}
+
+/// A stored squares_count of 26 must fail with InvalidSquareCount instead of reading past the board
+#[tokio::test]
+async fn test_percentage_rejects_squares_count_over_25() {
+    let mut program_test = setup_programs();
+    let manager = Keypair::new();
+    let authority = Keypair::new();
+    let deploy_authority = Keypair::new();
+    let auth_id: u64 = 0;
+
+    add_manager_account(&mut program_test, manager.pubkey(), authority.pubkey());
+    let (mma_pda, _) = managed_miner_auth_pda(manager.pubkey(), auth_id);
+    setup_strat_deploy_test_accounts(&mut program_test, 0, 1, 500);
+    add_autodeploy_balance(&mut program_test, mma_pda, 50_000_000_000);
+    fund_account(&mut program_test, deploy_authority.pubkey(), 2_000_000_000);
+
+    // Written directly: create_strat_deployer would refuse this strategy data
+    let (strat_deployer, _) = strategy_deployer_pda(manager.pubkey());
+    add_strat_deployer_account(
+        &mut program_test, strat_deployer, manager.pubkey(), deploy_authority.pubkey(),
+        0, 0, 0, 0, 0,
+        1, // Percentage,
+        percentage_strategy_data(1000, 26, 0, 0),
+        0,
+    );
+
+    let mut context = program_test.start_with_context().await;
+    let payer = context.payer.insecure_clone();
+
+    let ix = mm_strat_autodeploy(
+        deploy_authority.pubkey(), manager.pubkey(), auth_id,
+        5_000_000_000, 0, 0,
+    );
+    let err = send_transaction(&mut context, &[ix], &[&payer, &deploy_authority])
+        .await
+        .expect_err("squares_count = 26 should be rejected");
+    assert_eq!(
+        err.unwrap(),
+        TransactionError::InstructionError(1, InstructionError::Custom(EvoreError::InvalidSquareCount as u32)),
+    );
+}
@@ -4,8 +4,9 @@ use strat_common::*;
 
 use evore::state::{strategy_deployer_pda, managed_miner_auth_pda};
 use evore::instruction::{create_strat_deployer, mm_strat_autodeploy};
-use evore::ore_api::miner_pda;
+use evore::ore_api::{miner_pda, Miner};
 use solana_sdk::{signature::Keypair, signer::Signer, pubkey::Pubkey};
+use steel::AccountDeserialize;
 
 async fn setup_split_test(
     max_per_round: u64,
@@ -53,6 +54,9 @@ async fn setup_split_test(
         0, 0, max_per_round,
         3, // Split
         strategy_data,
+        0,
+        0,
+        0,
     );
     send_transaction(&mut context, &[ix], &[&payer, &authority]).await.unwrap();
     context.last_blockhash = context.banks_client.get_latest_blockhash().await.unwrap();
@@ -105,3 +109,36 @@ async fn test_split_small_bankroll_rounds_down() {
     let result = send_transaction(&mut context, &[ix], &[&payer, &deploy_authority]).await;
     assert!(result.is_err(), "Split with <25 lamports bankroll should fail");
 }
+
+#[tokio::test]
+async fn test_split_remainder_goes_to_highest_ev_square() {
+    let (mut context, deploy_authority, manager, mma_pda, auth_id) =
+        setup_split_test(0, 0, 0).await;
+    let payer = context.payer.insecure_clone();
+
+    // 2_500_000_007 / 25 = 100_000_000 per square with 7 lamports left over.
+    // Squares 11..25 are empty in the test round; the lowest-indexed empty square (11)
+    // is the highest-EV square and must receive the remainder.
+    let bankroll: u64 = 2_500_000_007;
+
+    let ix = mm_strat_autodeploy(
+        deploy_authority.pubkey(),
+        manager,
+        auth_id,
+        bankroll,
+        0, 0,
+    );
+
+    let result = send_transaction(&mut context, &[ix], &[&payer, &deploy_authority]).await;
+    assert!(result.is_ok(), "Split autodeploy should succeed: {:?}", result.err());
+
+    let (ore_miner, _) = miner_pda(mma_pda);
+    let account = context.banks_client.get_account(ore_miner).await.unwrap().unwrap();
+    let miner = Miner::try_from_bytes(&account.data).unwrap();
+
+    for i in 0..25 {
+        let expected = if i == 11 { 100_000_007 } else { 100_000_000 };
+        assert_eq!(miner.deployed[i], expected, "square {} landed amount", i);
+    }
+    assert_eq!(miner.deployed.iter().sum::<u64>(), bankroll);
+}
@@ -0,0 +1,112 @@
+mod strat_common;
+
+use strat_common::*;
+
+use evore::instruction::mm_strat_autodeploy;
+use solana_sdk::{pubkey::Pubkey, signature::Keypair, signer::Signer};
+
+async fn setup_weighted_test(
+    strategy_data: [u8; 64],
+    bps_fee: u64,
+) -> (
+    solana_program_test::ProgramTestContext,
+    Keypair,  // deploy_authority
+    Pubkey,   // manager pubkey
+    Pubkey,   // managed_miner_auth
+    u64,      // auth_id
+) {
+    setup_strat_autodeploy_test(
+        9, // Weighted
+        strategy_data,
+        bps_fee,
+    )
+    .await
+}
+
+/// Deploys `amount` with a fresh Weighted deployer and returns what landed per square
+async fn deploy_weighted(strategy_data: [u8; 64], amount: u64) -> [u64; 25] {
+    let (mut context, deploy_authority, manager, mma_pda, auth_id) = setup_weighted_test(strategy_data, 0).await;
+    let payer = context.payer.insecure_clone();
+
+    let ix = mm_strat_autodeploy(deploy_authority.pubkey(), manager, auth_id, amount, 0, 0);
+    let result = send_transaction(&mut context, &[ix], &[&payer, &deploy_authority]).await;
+    assert!(result.is_ok(), "Weighted autodeploy should succeed: {:?}", result.err());
+    miner_deployed(&mut context, mma_pda).await
+}
+
+// ============================================================================
+// Weighted strategy: bankroll split by implied square odds
+// ============================================================================
+
+#[tokio::test]
+async fn test_weighted_allocation_sums_to_bankroll() {
+    let bankroll: u64 = 2_400_000_000;
+    let (mut context, deploy_authority, manager, mma_pda, auth_id) =
+        setup_weighted_test(weighted_strategy_data(bankroll, 0, 1), 500).await; // 5% bps fee
+    let payer = context.payer.insecure_clone();
+
+    let fee_before = context.banks_client.get_balance(deploy_authority.pubkey()).await.unwrap();
+
+    let ix = mm_strat_autodeploy(deploy_authority.pubkey(), manager, auth_id, bankroll, 0, 0);
+    let result = send_transaction(&mut context, &[ix], &[&payer, &deploy_authority]).await;
+    assert!(result.is_ok(), "Weighted autodeploy should succeed: {:?}", result.err());
+
+    // The odds of the 12.4 SOL board add up to 24 x 12.4 SOL, so with exponent 1 every empty
+    // square gets ~1/24 of the bankroll and square 0 (3 SOL on it) ~9.4/12.4 of that. The odds
+    // are fixed point, so shares are only close; the rounding remainder lands on square 11
+    let deployed = miner_deployed(&mut context, mma_pda).await;
+    assert_eq!(deployed.iter().sum::<u64>(), bankroll, "the whole bankroll is deployed");
+    for (i, &landed) in deployed.iter().enumerate().skip(11) {
+        assert!(landed.abs_diff(100_000_000) < 10_000, "empty square {} gets ~1/24: {}", i, landed);
+        if i > 11 {
+            assert_eq!(landed, deployed[24], "equal odds, equal shares");
+        }
+    }
+    assert!(deployed[0].abs_diff(75_806_451) < 10_000, "square 0 gets ~9.4/12.4 of 1/24: {}", deployed[0]);
+    assert!(deployed[..11].windows(2).all(|w| w[0] <= w[1]), "less deployed means better odds");
+
+    // The deployer fee comes on top of the bankroll, not out of it
+    let fee_after = context.banks_client.get_balance(deploy_authority.pubkey()).await.unwrap();
+    assert_eq!(fee_after - fee_before, bankroll * 500 / 10_000);
+}
+
+#[tokio::test]
+async fn test_weighted_higher_exponent_concentrates_on_low_deployed_squares() {
+    let bankroll: u64 = 2_400_000_000;
+    let linear = deploy_weighted(weighted_strategy_data(bankroll, 0, 1), bankroll).await;
+    let steep = deploy_weighted(weighted_strategy_data(bankroll, 0, 4), bankroll).await;
+
+    assert_eq!(steep.iter().sum::<u64>(), bankroll);
+    assert!(steep[24] > linear[24], "empty squares get more with a higher exponent");
+    assert!(steep[0] < linear[0], "the most deployed square gets less with a higher exponent");
+    // Ratio of an empty square to square 0 grows from 12.4/9.4 to (12.4/9.4)^4
+    assert!(steep[24] * linear[0] > linear[24] * steep[0] * 2);
+
+    let empty_share = |deployed: &[u64; 25]| deployed[11..].iter().sum::<u64>();
+    assert!(empty_share(&steep) > empty_share(&linear));
+}
+
+#[tokio::test]
+async fn test_weighted_skips_squares_below_floor() {
+    // Squares 0 and 1 would get ~75.8M and ~79.8M, under the 80M floor; the rest re-split it all
+    let bankroll: u64 = 2_400_000_000;
+    let floor: u64 = 80_000_000;
+    let deployed = deploy_weighted(weighted_strategy_data(bankroll, floor, 1), bankroll).await;
+
+    assert_eq!(deployed[0], 0);
+    assert_eq!(deployed[1], 0);
+    for (i, &landed) in deployed.iter().enumerate().skip(2) {
+        assert!(landed >= floor, "square {} funded at or above the floor: {}", i, landed);
+    }
+    assert_eq!(deployed.iter().sum::<u64>(), bankroll);
+}
+
+#[tokio::test]
+async fn test_weighted_smaller_amount_spends_only_amount() {
+    let deployed = deploy_weighted(weighted_strategy_data(2_400_000_000, 0, 2), 1_000_000_000).await;
+    assert_eq!(deployed.iter().sum::<u64>(), 1_000_000_000);
+
+    // And the bankroll caps a larger deploy amount
+    let deployed = deploy_weighted(weighted_strategy_data(1_000_000_000, 0, 2), 5_000_000_000).await;
+    assert_eq!(deployed.iter().sum::<u64>(), 1_000_000_000);
+}
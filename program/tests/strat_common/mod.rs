@@ -64,7 +64,7 @@ pub fn add_manager_account(
     manager_address: Pubkey,
     authority: Pubkey,
 ) {
-    let manager = Manager { authority };
+    let manager = Manager { authority, max_deploy_per_tx: 0, pending_authority: Pubkey::default(), reserved: [0; 3] };
 
     let mut data = Vec::new();
     let discr = (EvoreAccount::Manager as u64).to_le_bytes();
@@ -95,6 +95,7 @@ pub fn add_strat_deployer_account(
     max_per_round: u64,
     strategy_type: u8,
     strategy_data: [u8; 64],
+    max_loss_per_round: u64,
 ) {
     let strat_deployer = StrategyDeployer {
         manager_key,
@@ -106,7 +107,10 @@ pub fn add_strat_deployer_account(
         max_per_round,
         strategy_type,
         strategy_data,
-        _padding: [0u8; 7],
+        max_squares_per_deploy: 0,
+        blocked_squares_mask: [0u8; 4],
+        _padding: [0u8; 2],
+        max_loss_per_round,
     };
 
     let mut data = Vec::new();
@@ -344,10 +348,7 @@ pub fn add_mint_account(program_test: &mut ProgramTest) {
 pub fn add_treasury_ata_account(program_test: &mut ProgramTest) {
     let data = read_file(&"tests/buffers/treasury_at_account.so");
     program_test.add_account(
-        spl_associated_token_account::get_associated_token_address(
-            &TREASURY_ADDRESS,
-            &MINT_ADDRESS,
-        ),
+        ore_api::treasury_tokens_address(),
         Account {
             lamports: Rent::default().minimum_balance(data.len()).max(1),
             data,
@@ -376,16 +377,8 @@ pub fn add_config_account(program_test: &mut ProgramTest) {
 // Deploy Test Setup
 // ============================================================================
 
-pub fn setup_strat_deploy_test_accounts(
-    program_test: &mut ProgramTest,
-    round_id: u64,
-    current_slot: u64,
-    slots_until_end: u64,
-) -> Board {
-    let end_slot = current_slot + slots_until_end;
-
-    let board = add_board_account(program_test, round_id, current_slot, end_slot, 0);
-
+/// Per-square round deployments used by `setup_strat_deploy_test_accounts`
+pub fn strat_round_deployed() -> [u64; 25] {
     let mut deployed = [0u64; 25];
     deployed[0] = 3_000_000_000;
     deployed[1] = 2_500_000_000;
@@ -398,6 +391,20 @@ pub fn setup_strat_deploy_test_accounts(
     deployed[8] = 200_000_000;
     deployed[9] = 200_000_000;
     deployed[10] = 100_000_000;
+    deployed
+}
+
+pub fn setup_strat_deploy_test_accounts(
+    program_test: &mut ProgramTest,
+    round_id: u64,
+    current_slot: u64,
+    slots_until_end: u64,
+) -> Board {
+    let end_slot = current_slot + slots_until_end;
+
+    let board = add_board_account(program_test, round_id, current_slot, end_slot, 0);
+
+    let deployed = strat_round_deployed();
     let total_deployed: u64 = deployed.iter().sum();
     add_round_account(program_test, round_id, deployed, total_deployed, end_slot + 1000);
 
@@ -410,6 +417,58 @@ pub fn setup_strat_deploy_test_accounts(
     board
 }
 
+/// Manager with a funded auth_id 0 miner and a `strategy_type` strategy deployer, on the
+/// `setup_strat_deploy_test_accounts` board
+/// Returns (context, deploy_authority, manager, managed_miner_auth, auth_id)
+pub async fn setup_strat_autodeploy_test(
+    strategy_type: u8,
+    strategy_data: [u8; 64],
+    bps_fee: u64,
+) -> (solana_program_test::ProgramTestContext, Keypair, Pubkey, Pubkey, u64) {
+    let mut program_test = setup_programs();
+    let manager = Keypair::new();
+    let authority = Keypair::new();
+    let deploy_authority = Keypair::new();
+    let auth_id: u64 = 0;
+
+    add_manager_account(&mut program_test, manager.pubkey(), authority.pubkey());
+
+    let (mma_pda, _) = managed_miner_auth_pda(manager.pubkey(), auth_id);
+
+    setup_strat_deploy_test_accounts(&mut program_test, 0, 1, 500);
+    add_autodeploy_balance(&mut program_test, mma_pda, 50_000_000_000);
+
+    let mut context = program_test.start_with_context().await;
+    let payer = context.payer.insecure_clone();
+
+    let fund_ix = solana_sdk::system_instruction::transfer(&payer.pubkey(), &authority.pubkey(), 2_000_000_000);
+    let fund_ix2 = solana_sdk::system_instruction::transfer(&payer.pubkey(), &deploy_authority.pubkey(), 2_000_000_000);
+    let fund_fc = solana_sdk::system_instruction::transfer(&payer.pubkey(), &FEE_COLLECTOR, 1_000_000);
+    send_transaction(&mut context, &[fund_ix, fund_ix2, fund_fc], &[&payer]).await.unwrap();
+    context.last_blockhash = context.banks_client.get_latest_blockhash().await.unwrap();
+
+    let ix = evore::instruction::create_strat_deployer(
+        authority.pubkey(), manager.pubkey(), deploy_authority.pubkey(),
+        bps_fee, 0, 0,
+        strategy_type,
+        strategy_data,
+        0,
+        0,
+        0,
+    );
+    send_transaction(&mut context, &[ix], &[&payer, &authority]).await.unwrap();
+    context.last_blockhash = context.banks_client.get_latest_blockhash().await.unwrap();
+
+    (context, deploy_authority, manager.pubkey(), mma_pda, auth_id)
+}
+
+/// Per-square amounts the managed miner auth's ORE miner has on the board
+pub async fn miner_deployed(context: &mut solana_program_test::ProgramTestContext, mma_pda: Pubkey) -> [u64; 25] {
+    let (ore_miner, _) = miner_pda(mma_pda);
+    let account = context.banks_client.get_account(ore_miner).await.unwrap().unwrap();
+    Miner::try_from_bytes(&account.data).unwrap().deployed
+}
+
 // ============================================================================
 // Strategy Data Builders
 // ============================================================================
@@ -461,6 +520,27 @@ pub fn dynev_strategy_data(max_per_square: u64, min_bet: u64, slots_left: u64, m
     d
 }
 
+pub fn loser_pool_strategy_data(fraction_bps: u64, squares_mask: u64) -> [u8; 64] {
+    let mut d = [0u8; 64];
+    d[0..8].copy_from_slice(&fraction_bps.to_le_bytes());
+    d[8..16].copy_from_slice(&squares_mask.to_le_bytes());
+    d
+}
+
+pub fn momentum_strategy_data(squares_count: u64) -> [u8; 64] {
+    let mut d = [0u8; 64];
+    d[0..8].copy_from_slice(&squares_count.to_le_bytes());
+    d
+}
+
+pub fn weighted_strategy_data(bankroll: u64, min_per_square: u64, exponent: u64) -> [u8; 64] {
+    let mut d = [0u8; 64];
+    d[0..8].copy_from_slice(&bankroll.to_le_bytes());
+    d[8..16].copy_from_slice(&min_per_square.to_le_bytes());
+    d[16..24].copy_from_slice(&exponent.to_le_bytes());
+    d
+}
+
 // ============================================================================
 // State Helpers
 // ============================================================================
@@ -34,6 +34,9 @@ async fn test_create_manual_strategy_succeeds() {
         1_000_000_000, // max_per_round
         2,    // Manual strategy
         strategy_data,
+        10,   // max_squares_per_deploy
+        0,    // blocked_squares_mask
+        0,    // max_loss_per_round
     );
 
     let payer = context.payer.insecure_clone();
@@ -55,6 +58,7 @@ async fn test_create_manual_strategy_succeeds() {
     assert_eq!(state.max_per_round, 1_000_000_000);
     assert_eq!(state.strategy_type, 2);
     assert_eq!(state.strategy_data, strategy_data);
+    assert_eq!(state.max_squares_per_deploy, 10);
 }
 
 // ============================================================================
@@ -84,6 +88,9 @@ async fn test_create_ev_strategy_succeeds() {
         2_000_000_000,
         0, // EV strategy
         strategy_data,
+        0,
+        0,
+        0,
     );
 
     let payer = context.payer.insecure_clone();
@@ -123,6 +130,9 @@ async fn test_create_duplicate_fails() {
         deploy_authority.pubkey(),
         0, 0, 1_000_000_000,
         2, manual_strategy_data(),
+        0,
+        0,
+        0,
     );
 
     // First create succeeds
@@ -161,6 +171,9 @@ async fn test_create_wrong_authority_fails() {
         deploy_authority.pubkey(),
         0, 0, 1_000_000_000,
         2, manual_strategy_data(),
+        0,
+        0,
+        0,
     );
 
     let payer = context.payer.insecure_clone();
@@ -168,6 +181,62 @@ async fn test_create_wrong_authority_fails() {
     assert!(result.is_err(), "Wrong authority must be rejected");
 }
 
+#[tokio::test]
+async fn test_create_max_squares_above_board_fails() {
+    let mut program_test = setup_programs();
+    let manager = Keypair::new();
+    let authority = Keypair::new();
+    let deploy_authority = Keypair::new();
+
+    add_manager_account(&mut program_test, manager.pubkey(), authority.pubkey());
+    fund_account(&mut program_test, authority.pubkey(), 10_000_000_000);
+
+    let mut context = program_test.start_with_context().await;
+
+    let ix = create_strat_deployer(
+        authority.pubkey(),
+        manager.pubkey(),
+        deploy_authority.pubkey(),
+        0, 0, 1_000_000_000,
+        2, manual_strategy_data(),
+        26,
+        0,
+        0,
+    );
+
+    let payer = context.payer.insecure_clone();
+    let result = send_transaction(&mut context, &[ix], &[&payer, &authority]).await;
+    assert!(result.is_err(), "A cap above 25 squares must be rejected");
+}
+
+#[tokio::test]
+async fn test_create_blocked_squares_off_board_fails() {
+    let mut program_test = setup_programs();
+    let manager = Keypair::new();
+    let authority = Keypair::new();
+    let deploy_authority = Keypair::new();
+
+    add_manager_account(&mut program_test, manager.pubkey(), authority.pubkey());
+    fund_account(&mut program_test, authority.pubkey(), 10_000_000_000);
+
+    let mut context = program_test.start_with_context().await;
+
+    let ix = create_strat_deployer(
+        authority.pubkey(),
+        manager.pubkey(),
+        deploy_authority.pubkey(),
+        0, 0, 1_000_000_000,
+        2, manual_strategy_data(),
+        0,
+        1 << 25,
+        0,
+    );
+
+    let payer = context.payer.insecure_clone();
+    let result = send_transaction(&mut context, &[ix], &[&payer, &authority]).await;
+    assert!(result.is_err(), "A blocked square off the board must be rejected");
+}
+
 // ============================================================================
 // Invalid strategy data rejected
 // ============================================================================
@@ -193,6 +262,9 @@ async fn test_create_invalid_strategy_data_fails() {
         0, 0, 1_000_000_000,
         0, // EV strategy
         bad_ev_data,
+        0,
+        0,
+        0,
     );
 
     let payer = context.payer.insecure_clone();
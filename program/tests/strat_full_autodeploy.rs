@@ -61,6 +61,9 @@ async fn setup_full_test(
         bps_fee, 0, 0,
         strategy_type,
         strategy_data,
+        0,
+        0,
+        0,
     );
     send_transaction(&mut context, &[ix], &[&payer, &authority]).await.unwrap();
     context.last_blockhash = context.banks_client.get_latest_blockhash().await.unwrap();
@@ -0,0 +1,155 @@
+mod strat_common;
+
+use strat_common::*;
+
+use evore::error::EvoreError;
+use evore::instruction::{mm_strat_autodeploy, mm_strat_full_autodeploy, with_ore_program_data};
+use evore::ore_api::{self, miner_pda, Miner};
+use evore::state::{managed_miner_auth_pda, strategy_deployer_pda};
+use solana_program::instruction::Instruction;
+use solana_sdk::{
+    account::Account, instruction::InstructionError, pubkey::Pubkey, signature::Keypair,
+    signer::Signer, transaction::TransactionError,
+};
+use steel::AccountDeserialize;
+
+/// Slot the test ORE program data says ORE was last deployed at
+const ORE_DEPLOY_SLOT: u64 = 1_234;
+
+/// Manual strategy data with the ORE program slot pinned in word 7
+fn pinned_strategy_data(expected_ore_program_slot: u64) -> [u8; 64] {
+    let mut d = manual_strategy_data();
+    d[56..64].copy_from_slice(&expected_ore_program_slot.to_le_bytes());
+    d
+}
+
+async fn setup_pinned_test(
+    expected_ore_program_slot: u64,
+) -> (solana_program_test::ProgramTestContext, Keypair, Pubkey, Pubkey) {
+    let mut program_test = setup_programs();
+    let manager = Keypair::new().pubkey();
+    let authority = Keypair::new();
+    let deploy_authority = Keypair::new();
+    let (mma_pda, _) = managed_miner_auth_pda(manager, 0);
+
+    add_manager_account(&mut program_test, manager, authority.pubkey());
+    add_strat_deployer_account(
+        &mut program_test, strategy_deployer_pda(manager).0, manager, deploy_authority.pubkey(),
+        0, 0, 0, 0, 0,
+        2, // Manual
+        pinned_strategy_data(expected_ore_program_slot),
+        0,
+    );
+    setup_strat_deploy_test_accounts(&mut program_test, 0, 1, 500);
+    // Checkpointed miner, so the full variant has nothing to settle before deploying
+    add_ore_miner_account(&mut program_test, mma_pda, [0; 25], 0, 0, 0, 0);
+    add_autodeploy_balance(&mut program_test, mma_pda, 10_000_000_000);
+    fund_account(&mut program_test, deploy_authority.pubkey(), 1_000_000_000);
+    fund_account(&mut program_test, evore::consts::FEE_COLLECTOR, 1_000_000);
+
+    // Loader v3 program data: ProgramData variant, deploy slot, no upgrade authority
+    let mut data = 3u32.to_le_bytes().to_vec();
+    data.extend_from_slice(&ORE_DEPLOY_SLOT.to_le_bytes());
+    data.push(0);
+    program_test.add_account(
+        ore_api::program_data_pda().0,
+        Account {
+            lamports: 1_000_000_000,
+            data,
+            owner: ore_api::BPF_LOADER_UPGRADEABLE,
+            executable: false,
+            rent_epoch: 0,
+        },
+    );
+
+    let context = program_test.start_with_context().await;
+    (context, deploy_authority, manager, mma_pda)
+}
+
+async fn deploy(
+    context: &mut solana_program_test::ProgramTestContext,
+    deploy_authority: &Keypair,
+    ix: Instruction,
+) -> Result<(), TransactionError> {
+    let payer = context.payer.insecure_clone();
+    send_transaction(context, &[ix], &[&payer, deploy_authority])
+        .await
+        .map_err(|e| e.unwrap())
+}
+
+async fn deployed_total(context: &mut solana_program_test::ProgramTestContext, mma_pda: Pubkey) -> u64 {
+    let account = context.banks_client.get_account(miner_pda(mma_pda).0).await.unwrap().unwrap();
+    Miner::try_from_bytes(&account.data).unwrap().deployed.iter().sum()
+}
+
+// ============================================================================
+// Pinned ORE program slot
+// ============================================================================
+
+#[tokio::test]
+async fn test_pinned_slot_mismatch_rejects_deploy() {
+    let (mut context, deploy_authority, manager, _mma_pda) = setup_pinned_test(ORE_DEPLOY_SLOT - 1).await;
+
+    // ORE was redeployed after the pin
+    let ix = with_ore_program_data(mm_strat_autodeploy(deploy_authority.pubkey(), manager, 0, 10_000_000, 0b111, 0));
+    assert_eq!(
+        deploy(&mut context, &deploy_authority, ix).await,
+        Err(TransactionError::InstructionError(1, InstructionError::Custom(EvoreError::OreProgramChanged as u32))),
+    );
+
+    // A pinned deployer can't skip the check by leaving the program data out
+    let ix = mm_strat_autodeploy(deploy_authority.pubkey(), manager, 0, 10_000_000, 0b111, 0);
+    assert_eq!(
+        deploy(&mut context, &deploy_authority, ix).await,
+        Err(TransactionError::InstructionError(1, InstructionError::NotEnoughAccountKeys)),
+    );
+}
+
+#[tokio::test]
+async fn test_pinned_slot_match_deploys() {
+    let (mut context, deploy_authority, manager, mma_pda) = setup_pinned_test(ORE_DEPLOY_SLOT).await;
+
+    let ix = with_ore_program_data(mm_strat_autodeploy(deploy_authority.pubkey(), manager, 0, 10_000_000, 0b111, 0));
+    deploy(&mut context, &deploy_authority, ix).await.expect("ORE is still at the pinned slot");
+    assert_eq!(deployed_total(&mut context, mma_pda).await, 30_000_000);
+}
+
+#[tokio::test]
+async fn test_unpinned_deploys_with_or_without_program_data() {
+    let (mut context, deploy_authority, manager, mma_pda) = setup_pinned_test(0).await;
+
+    let ix = mm_strat_autodeploy(deploy_authority.pubkey(), manager, 0, 10_000_000, 0b1, 0);
+    deploy(&mut context, &deploy_authority, ix).await.expect("zero pin skips the check");
+
+    // Passing the program data anyway is harmless
+    context.last_blockhash = context.banks_client.get_latest_blockhash().await.unwrap();
+    let ix = with_ore_program_data(mm_strat_autodeploy(deploy_authority.pubkey(), manager, 0, 10_000_000, 0b10, 0));
+    deploy(&mut context, &deploy_authority, ix).await.expect("unpinned deploy ignores the program data");
+    assert_eq!(deployed_total(&mut context, mma_pda).await, 20_000_000);
+}
+
+#[tokio::test]
+async fn test_pinned_slot_mismatch_rejects_full_deploy() {
+    let (mut context, deploy_authority, manager, _mma_pda) = setup_pinned_test(ORE_DEPLOY_SLOT - 1).await;
+
+    let ix = with_ore_program_data(mm_strat_full_autodeploy(deploy_authority.pubkey(), manager, 0, 10_000_000, 0b111, 0));
+    assert_eq!(
+        deploy(&mut context, &deploy_authority, ix).await,
+        Err(TransactionError::InstructionError(1, InstructionError::Custom(EvoreError::OreProgramChanged as u32))),
+    );
+
+    let ix = mm_strat_full_autodeploy(deploy_authority.pubkey(), manager, 0, 10_000_000, 0b111, 0);
+    assert_eq!(
+        deploy(&mut context, &deploy_authority, ix).await,
+        Err(TransactionError::InstructionError(1, InstructionError::NotEnoughAccountKeys)),
+    );
+}
+
+#[tokio::test]
+async fn test_pinned_slot_match_full_deploys() {
+    let (mut context, deploy_authority, manager, mma_pda) = setup_pinned_test(ORE_DEPLOY_SLOT).await;
+
+    let ix = with_ore_program_data(mm_strat_full_autodeploy(deploy_authority.pubkey(), manager, 0, 10_000_000, 0b111, 0));
+    deploy(&mut context, &deploy_authority, ix).await.expect("ORE is still at the pinned slot");
+    assert_eq!(deployed_total(&mut context, mma_pda).await, 30_000_000);
+}
@@ -51,6 +51,9 @@ async fn setup_security_env() -> (
         0, 0, 0,
         2, // Manual
         manual_strategy_data(),
+        0,
+        0,
+        0,
     );
     send_transaction(&mut context, &[ix], &[&payer, &authority]).await.unwrap();
     context.last_blockhash = context.banks_client.get_latest_blockhash().await.unwrap();
@@ -116,6 +119,9 @@ async fn test_update_rejects_random_signer() {
     let ix = update_strat_deployer(
         attacker.pubkey(), manager, deploy_authority.pubkey(),
         0, 0, 0, 0, 0, 2, manual_strategy_data(),
+        0,
+        0,
+        0,
     );
     let result = send_transaction(&mut context, &[ix], &[&payer, &attacker]).await;
     assert!(result.is_err(), "Random signer must be rejected for update");
@@ -194,6 +200,7 @@ async fn test_autodeploy_rejects_fee_exceeding_expected() {
         0,      // max_per_round
         2,      // Manual
         manual_strategy_data(),
+        0,      // max_loss_per_round
     );
 
     let mut context = program_test.start_with_context().await;
@@ -233,6 +240,9 @@ async fn test_strat_deployer_does_not_affect_regular_deployer() {
     let ix = create_strat_deployer(
         authority.pubkey(), manager.pubkey(), deploy_authority.pubkey(),
         0, 0, 0, 2, manual_strategy_data(),
+        0,
+        0,
+        0,
     );
     send_transaction(&mut context, &[ix], &[&payer, &authority]).await.unwrap();
 
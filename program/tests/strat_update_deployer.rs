@@ -2,9 +2,10 @@ mod strat_common;
 
 use strat_common::*;
 
-use evore::state::{strategy_deployer_pda, StrategyDeployer};
+use evore::state::{strategy_deployer_pda, EvoreAccount, StrategyDeployer, LEGACY_STRATEGY_DEPLOYER_SIZE};
 use evore::instruction::{create_strat_deployer, update_strat_deployer};
-use solana_sdk::{signature::Keypair, signer::Signer, pubkey::Pubkey};
+use solana_program::rent::Rent;
+use solana_sdk::{account::Account, signature::Keypair, signer::Signer, pubkey::Pubkey};
 
 // ============================================================================
 // Helper: create a strat deployer then return the context for update tests
@@ -54,6 +55,9 @@ async fn setup_with_strat_deployer(
         max_per_round,
         strategy_type,
         strategy_data,
+        0,
+        0,
+        0,
     );
 
     send_transaction(&mut context, &[ix], &[&payer, &authority])
@@ -85,6 +89,9 @@ async fn test_manager_updates_expected_bps_fee() {
         2_000_000_000,  // new max_per_round
         2,     // strategy_type unchanged
         manual_strategy_data(),
+        12,    // new max_squares_per_deploy
+        0b101, // new blocked_squares_mask
+        0,     // max_loss_per_round
     );
 
     send_transaction(&mut context, &[ix], &[&payer, &authority])
@@ -94,6 +101,8 @@ async fn test_manager_updates_expected_bps_fee() {
     assert_eq!(state.expected_bps_fee, 200);
     assert_eq!(state.expected_flat_fee, 100);
     assert_eq!(state.max_per_round, 2_000_000_000);
+    assert_eq!(state.max_squares_per_deploy, 12);
+    assert_eq!(state.blocked_squares(), 0b101);
 }
 
 // ============================================================================
@@ -117,6 +126,9 @@ async fn test_deploy_authority_updates_bps_fee() {
         1_000_000_000,
         2,
         manual_strategy_data(),
+        0,
+        0,
+        0,
     );
 
     send_transaction(&mut context, &[ix], &[&payer, &deploy_authority])
@@ -150,6 +162,9 @@ async fn test_manager_updates_strategy_to_ev() {
         1_000_000_000,
         0,  // EV strategy
         new_strategy_data,
+        0,
+        0,
+        0,
     );
 
     send_transaction(&mut context, &[ix], &[&payer, &authority])
@@ -180,6 +195,9 @@ async fn test_update_invalid_strategy_data_fails() {
         1_000_000_000,
         0,  // EV
         bad_data,
+        0,
+        0,
+        0,
     );
 
     let result = send_transaction(&mut context, &[ix], &[&payer, &authority]).await;
@@ -213,8 +231,85 @@ async fn test_update_wrong_authority_fails() {
         1_000_000_000,
         2,
         manual_strategy_data(),
+        0,
+        0,
+        0,
     );
 
     let result = send_transaction(&mut context, &[ix], &[&payer, &wrong_signer]).await;
     assert!(result.is_err(), "Wrong authority must be rejected");
 }
+
+// ============================================================================
+// Legacy accounts grow to hold max_loss_per_round
+// ============================================================================
+
+#[tokio::test]
+async fn test_manager_update_grows_legacy_strat_deployer() {
+    let mut program_test = setup_programs();
+    let manager = Keypair::new();
+    let authority = Keypair::new();
+    let deploy_authority = Keypair::new();
+    add_manager_account(&mut program_test, manager.pubkey(), authority.pubkey());
+
+    // A StrategyDeployer created before max_loss_per_round existed
+    let (strat_pda, _) = strategy_deployer_pda(manager.pubkey());
+    let strat = StrategyDeployer {
+        manager_key: manager.pubkey(),
+        deploy_authority: deploy_authority.pubkey(),
+        bps_fee: 0,
+        flat_fee: 0,
+        expected_bps_fee: 0,
+        expected_flat_fee: 0,
+        max_per_round: 1_000_000_000,
+        strategy_type: 2,
+        strategy_data: manual_strategy_data(),
+        max_squares_per_deploy: 0,
+        blocked_squares_mask: [0; 4],
+        _padding: [0; 2],
+        max_loss_per_round: 0,
+    };
+    let mut data = (EvoreAccount::StrategyDeployer as u64).to_le_bytes().to_vec();
+    data.extend_from_slice(&strat.to_bytes()[..LEGACY_STRATEGY_DEPLOYER_SIZE - 8]);
+    program_test.add_account(
+        strat_pda,
+        Account {
+            lamports: Rent::default().minimum_balance(data.len()),
+            data,
+            owner: evore::id(),
+            executable: false,
+            rent_epoch: 0,
+        },
+    );
+
+    let mut context = program_test.start_with_context().await;
+    let payer = context.payer.insecure_clone();
+    let fund_ix = solana_sdk::system_instruction::transfer(
+        &payer.pubkey(), &authority.pubkey(), 1_000_000_000,
+    );
+    send_transaction(&mut context, &[fund_ix], &[&payer]).await.unwrap();
+    context.last_blockhash = context.banks_client.get_latest_blockhash().await.unwrap();
+
+    let ix = update_strat_deployer(
+        authority.pubkey(),
+        manager.pubkey(),
+        deploy_authority.pubkey(),
+        0, 0, 0, 0,
+        1_000_000_000,
+        2,
+        manual_strategy_data(),
+        0,
+        0,
+        750_000_000,
+    );
+    send_transaction(&mut context, &[ix], &[&payer, &authority]).await.unwrap();
+
+    let account = context.banks_client.get_account(strat_pda).await.unwrap().unwrap();
+    assert_eq!(account.data.len(), 8 + std::mem::size_of::<StrategyDeployer>());
+    assert!(account.lamports >= Rent::default().minimum_balance(account.data.len()));
+
+    let state = get_strat_deployer_state(&mut context.banks_client, strat_pda).await;
+    assert_eq!(state.max_loss_per_round, 750_000_000);
+    assert_eq!(state.max_per_round, 1_000_000_000);
+    assert_eq!(state.deploy_authority, deploy_authority.pubkey());
+}
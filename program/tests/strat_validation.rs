@@ -1,20 +1,25 @@
 mod strat_common;
 
-use evore::state::{StrategyDeployer, strategy_deployer_pda};
+use evore::error::EvoreError;
+use evore::state::{
+    DspParams, DynevParams, EvParams, LoserPoolParams, MomentumParams, PercentageParams, SplitParams, StrategyDeployer,
+    StrategyParams, WeightedParams, strategy_deployer_pda, LEGACY_STRATEGY_DEPLOYER_SIZE,
+};
 use evore::validation::{StrategyType, validate_strategy_data};
-use solana_sdk::pubkey::Pubkey;
+use solana_sdk::{program_error::ProgramError, pubkey::Pubkey};
 
 // ============================================================================
 // StrategyDeployer struct layout
 // ============================================================================
 
 #[test]
-fn test_strat_deployer_struct_size_is_176() {
+fn test_strat_deployer_struct_size_is_184() {
     assert_eq!(
         std::mem::size_of::<StrategyDeployer>(),
-        176,
-        "StrategyDeployer struct must be exactly 176 bytes (184 with 8-byte discriminator)"
+        184,
+        "StrategyDeployer struct must be exactly 184 bytes (192 with 8-byte discriminator)"
     );
+    assert_eq!(LEGACY_STRATEGY_DEPLOYER_SIZE, 184, "legacy accounts end before max_loss_per_round");
 }
 
 // ============================================================================
@@ -65,11 +70,15 @@ fn test_strategy_type_from_u8() {
     assert_eq!(StrategyType::try_from(3).unwrap(), StrategyType::Split);
     assert_eq!(StrategyType::try_from(4).unwrap(), StrategyType::DynamicSplitPercentage);
     assert_eq!(StrategyType::try_from(5).unwrap(), StrategyType::DynamicEv);
+    assert_eq!(StrategyType::try_from(6).unwrap(), StrategyType::MirrorTopMiner);
+    assert_eq!(StrategyType::try_from(7).unwrap(), StrategyType::LoserPool);
+    assert_eq!(StrategyType::try_from(8).unwrap(), StrategyType::Momentum);
+    assert_eq!(StrategyType::try_from(9).unwrap(), StrategyType::Weighted);
 }
 
 #[test]
 fn test_invalid_strategy_type_fails() {
-    assert!(StrategyType::try_from(6).is_err());
+    assert!(StrategyType::try_from(10).is_err());
     assert!(StrategyType::try_from(255).is_err());
 }
 
@@ -116,6 +125,15 @@ fn test_ev_zero_ore_value_ok() {
     assert!(validate_strategy_data(StrategyType::Ev, &data).is_ok());
 }
 
+#[test]
+fn test_ev_max_squares_over_board_fails() {
+    let mut data = ev_data(100_000, 1_000, 50, 1_000_000_000);
+    data[32..40].copy_from_slice(&26u64.to_le_bytes());
+    assert!(validate_strategy_data(StrategyType::Ev, &data).is_err());
+    data[32..40].copy_from_slice(&25u64.to_le_bytes());
+    assert!(validate_strategy_data(StrategyType::Ev, &data).is_ok());
+}
+
 // ============================================================================
 // Percentage strategy validation
 // ============================================================================
@@ -295,3 +313,179 @@ fn test_dynev_zero_max_ore_value_ok() {
     let data = dynev_data(100_000, 1_000, 50, 0);
     assert!(validate_strategy_data(StrategyType::DynamicEv, &data).is_ok());
 }
+
+// ============================================================================
+// LoserPool strategy validation
+// ============================================================================
+
+fn loser_pool_data(fraction_bps: u64, mask: u64) -> [u8; 64] {
+    let mut d = [0u8; 64];
+    d[0..8].copy_from_slice(&fraction_bps.to_le_bytes());
+    d[8..16].copy_from_slice(&mask.to_le_bytes());
+    d
+}
+
+#[test]
+fn test_loser_pool_valid_data() {
+    assert!(validate_strategy_data(StrategyType::LoserPool, &loser_pool_data(500, 0x1FF_FFFF)).is_ok());
+    assert!(validate_strategy_data(StrategyType::LoserPool, &loser_pool_data(10_000, 1)).is_ok());
+}
+
+#[test]
+fn test_loser_pool_zero_fraction_fails() {
+    assert!(validate_strategy_data(StrategyType::LoserPool, &loser_pool_data(0, 0x1FF_FFFF)).is_err());
+}
+
+#[test]
+fn test_loser_pool_over_10000_fraction_fails() {
+    assert!(validate_strategy_data(StrategyType::LoserPool, &loser_pool_data(10_001, 0x1FF_FFFF)).is_err());
+}
+
+#[test]
+fn test_loser_pool_zero_mask_fails() {
+    assert!(validate_strategy_data(StrategyType::LoserPool, &loser_pool_data(500, 0)).is_err());
+}
+
+#[test]
+fn test_loser_pool_invalid_bits_fails() {
+    assert!(validate_strategy_data(StrategyType::LoserPool, &loser_pool_data(500, 1 << 25)).is_err());
+}
+
+// ============================================================================
+// Momentum strategy validation
+// ============================================================================
+
+#[test]
+fn test_momentum_valid_data() {
+    assert!(validate_strategy_data(StrategyType::Momentum, &strat_common::momentum_strategy_data(1)).is_ok());
+    assert!(validate_strategy_data(StrategyType::Momentum, &strat_common::momentum_strategy_data(25)).is_ok());
+}
+
+#[test]
+fn test_momentum_zero_squares_fails() {
+    assert!(validate_strategy_data(StrategyType::Momentum, &strat_common::momentum_strategy_data(0)).is_err());
+}
+
+#[test]
+fn test_momentum_over_25_squares_fails() {
+    assert!(validate_strategy_data(StrategyType::Momentum, &strat_common::momentum_strategy_data(26)).is_err());
+}
+
+// ============================================================================
+// Weighted strategy validation
+// ============================================================================
+
+#[test]
+fn test_weighted_valid_data() {
+    assert!(validate_strategy_data(StrategyType::Weighted, &strat_common::weighted_strategy_data(1_000_000_000, 0, 1)).is_ok());
+    assert!(validate_strategy_data(StrategyType::Weighted, &strat_common::weighted_strategy_data(1, 10_000, 4)).is_ok());
+}
+
+#[test]
+fn test_weighted_zero_bankroll_fails() {
+    assert!(validate_strategy_data(StrategyType::Weighted, &strat_common::weighted_strategy_data(0, 0, 2)).is_err());
+}
+
+#[test]
+fn test_weighted_exponent_out_of_range_fails() {
+    assert!(validate_strategy_data(StrategyType::Weighted, &strat_common::weighted_strategy_data(1_000_000, 0, 0)).is_err());
+    assert!(validate_strategy_data(StrategyType::Weighted, &strat_common::weighted_strategy_data(1_000_000, 0, 5)).is_err());
+}
+
+// ============================================================================
+// StrategyDeployer::decode_strategy
+// ============================================================================
+
+fn strat_deployer_with(strategy_type: StrategyType, strategy_data: [u8; 64]) -> StrategyDeployer {
+    StrategyDeployer {
+        manager_key: Pubkey::new_unique(),
+        deploy_authority: Pubkey::new_unique(),
+        bps_fee: 0,
+        flat_fee: 0,
+        expected_bps_fee: 0,
+        expected_flat_fee: 0,
+        max_per_round: 0,
+        strategy_type: strategy_type as u8,
+        strategy_data,
+        max_squares_per_deploy: 0,
+        blocked_squares_mask: [0; 4],
+        _padding: [0; 2],
+        max_loss_per_round: 0,
+    }
+}
+
+#[test]
+fn test_decode_strategy_round_trips_builders() {
+    use strat_common::*;
+
+    let cases = [
+        (
+            strat_deployer_with(StrategyType::Ev, ev_strategy_data(1_000, 10, 5, 800)),
+            StrategyParams::Ev(EvParams { max_per_square: 1_000, min_bet: 10, slots_left: 5, ore_value: 800, max_squares: 0, min_ev_bps: 0 }),
+        ),
+        (
+            strat_deployer_with(StrategyType::Percentage, percentage_strategy_data(500, 7, 1, 9)),
+            StrategyParams::Percentage(PercentageParams { percentage: 500, squares_count: 7, motherlode_min: 1, motherlode_max: 9 }),
+        ),
+        (strat_deployer_with(StrategyType::Manual, manual_strategy_data()), StrategyParams::Manual),
+        (
+            strat_deployer_with(StrategyType::Split, split_strategy_data(3, 4)),
+            StrategyParams::Split(SplitParams { motherlode_min: 3, motherlode_max: 4 }),
+        ),
+        (
+            strat_deployer_with(StrategyType::DynamicSplitPercentage, dsp_strategy_data(2_500, 0b1011, 0, 0)),
+            StrategyParams::DynamicSplitPercentage(DspParams { percentage: 2_500, squares_mask: 0b1011, motherlode_min: 0, motherlode_max: 0 }),
+        ),
+        (
+            strat_deployer_with(StrategyType::DynamicEv, dynev_strategy_data(2_000, 20, 8, 1_500)),
+            StrategyParams::DynamicEv(DynevParams { max_per_square: 2_000, min_bet: 20, slots_left: 8, max_ore_value: 1_500 }),
+        ),
+        (strat_deployer_with(StrategyType::MirrorTopMiner, [0u8; 64]), StrategyParams::MirrorTopMiner),
+        (
+            strat_deployer_with(StrategyType::LoserPool, loser_pool_strategy_data(750, 0x1FF_FFFF)),
+            StrategyParams::LoserPool(LoserPoolParams { fraction_bps: 750, squares_mask: 0x1FF_FFFF }),
+        ),
+        (
+            strat_deployer_with(StrategyType::Momentum, momentum_strategy_data(4)),
+            StrategyParams::Momentum(MomentumParams { squares_count: 4 }),
+        ),
+        (
+            strat_deployer_with(StrategyType::Weighted, weighted_strategy_data(2_000_000_000, 50_000, 3)),
+            StrategyParams::Weighted(WeightedParams { bankroll: 2_000_000_000, min_per_square: 50_000, exponent: 3 }),
+        ),
+    ];
+
+    for (deployer, expected) in cases {
+        assert_eq!(deployer.decode_strategy().unwrap(), expected);
+    }
+}
+
+#[test]
+fn test_decode_strategy_reads_ev_max_squares() {
+    let mut data = strat_common::ev_strategy_data(1_000, 10, 5, 800);
+    data[32..40].copy_from_slice(&3u64.to_le_bytes());
+    let deployer = strat_deployer_with(StrategyType::Ev, data);
+    match deployer.decode_strategy().unwrap() {
+        StrategyParams::Ev(params) => assert_eq!(params.max_squares, 3),
+        other => panic!("expected Ev params, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_decode_strategy_rejects_unknown_type() {
+    let mut deployer = strat_deployer_with(StrategyType::Manual, [0u8; 64]);
+    deployer.strategy_type = 200;
+    assert_eq!(
+        deployer.decode_strategy().unwrap_err(),
+        ProgramError::Custom(EvoreError::InvalidStrategyType as u32)
+    );
+}
+
+#[test]
+fn test_decode_strategy_rejects_invalid_data() {
+    let deployer = strat_deployer_with(StrategyType::Percentage, strat_common::percentage_strategy_data(0, 5, 0, 0));
+    assert_eq!(
+        deployer.decode_strategy().unwrap_err(),
+        ProgramError::Custom(EvoreError::InvalidStrategyData as u32)
+    );
+}
@@ -10,7 +10,7 @@ use evore::{
 use solana_program::{rent::Rent, system_instruction};
 use solana_program_test::{processor, read_file, ProgramTest};
 use solana_sdk::{
-    account::Account, compute_budget::ComputeBudgetInstruction, pubkey,
+    account::Account, compute_budget::ComputeBudgetInstruction,
     pubkey::Pubkey, signature::Keypair, signer::Signer, transaction::Transaction,
 };
 use steel::{AccountDeserialize, Numeric};
@@ -73,7 +73,7 @@ pub fn add_manager_account(
     manager_address: Pubkey,
     authority: Pubkey,
 ) {
-    let manager = Manager { authority };
+    let manager = Manager { authority, max_deploy_per_tx: 0, pending_authority: Pubkey::default(), reserved: [0; 3] };
     
     let mut data = Vec::new();
     let discr = (EvoreAccount::Manager as u64).to_le_bytes();
@@ -291,7 +291,7 @@ pub fn add_mint_account(program_test: &mut ProgramTest) {
 pub fn add_treasury_ata_account(program_test: &mut ProgramTest) {
     let data = read_file(&"tests/buffers/treasury_at_account.so");
     program_test.add_account(
-        pubkey!("GwZS8yBuPPkPgY4uh7eEhHN5EEdpkf7EBZ1za6nuP3wF"),
+        ore_api::treasury_tokens_address(),
         Account {
             lamports: Rent::default().minimum_balance(data.len()).max(1),
             data,
@@ -459,6 +459,95 @@ mod create_manager {
     }
 }
 
+// ============================================================================
+// CreateDeployer Tests
+// ============================================================================
+
+mod create_deployer {
+    use super::*;
+
+    /// Off-chain readers (the crank's GPA filters) assume a 112-byte Deployer account
+    const DEPLOYER_ACCOUNT_SIZE: usize = 112;
+
+    /// The deployer is allocated exactly 112 bytes and funded with exactly the rent-exempt minimum
+    #[tokio::test]
+    async fn test_deployer_is_exactly_rent_exempt() {
+        let mut program_test = setup_programs();
+
+        let manager_authority = Keypair::new();
+        let manager_address = Keypair::new().pubkey();
+        let deploy_authority = Keypair::new().pubkey();
+
+        add_manager_account(&mut program_test, manager_address, manager_authority.pubkey());
+
+        let context = program_test.start_with_context().await;
+
+        let ix = evore::instruction::create_deployer(
+            manager_authority.pubkey(),
+            manager_address,
+            deploy_authority,
+            500,
+            5_000,
+            0,
+        );
+        let tx = Transaction::new_signed_with_payer(
+            &[
+                system_instruction::transfer(&context.payer.pubkey(), &manager_authority.pubkey(), 1_000_000_000),
+                ix,
+            ],
+            Some(&context.payer.pubkey()),
+            &[&context.payer, &manager_authority],
+            context.last_blockhash,
+        );
+        context.banks_client.process_transaction(tx).await.expect("create deployer should succeed");
+
+        let rent = context.banks_client.get_rent().await.unwrap();
+        let deployer_account = context
+            .banks_client
+            .get_account(deployer_pda(manager_address).0)
+            .await
+            .unwrap()
+            .expect("deployer should exist");
+
+        assert_eq!(8 + std::mem::size_of::<Deployer>(), DEPLOYER_ACCOUNT_SIZE);
+        assert_eq!(deployer_account.data.len(), DEPLOYER_ACCOUNT_SIZE);
+        assert_eq!(deployer_account.lamports, rent.minimum_balance(DEPLOYER_ACCOUNT_SIZE));
+        assert!(rent.is_exempt(deployer_account.lamports, deployer_account.data.len()));
+        assert_eq!(deployer_account.owner, evore::id());
+    }
+}
+
+mod deploy_accounts {
+    use super::*;
+    use evore::instruction::deploy_accounts;
+
+    /// deploy_accounts must list exactly what the deploy builders send after the signer
+    #[test]
+    fn test_matches_ev_and_percentage_deploy() {
+        let signer = Keypair::new().pubkey();
+        let manager = Keypair::new().pubkey();
+        let auth_id = 3u64;
+
+        let expected = deploy_accounts(manager, auth_id, TEST_ROUND_ID);
+        assert_eq!(expected.len(), 12);
+        assert_eq!(expected[0].pubkey, manager);
+        assert_eq!(expected[1].pubkey, managed_miner_auth_pda(manager, auth_id).0);
+        assert_eq!(expected[7].pubkey, round_pda(TEST_ROUND_ID).0);
+
+        let ev = evore::instruction::ev_deploy(
+            signer, manager, auth_id, TEST_ROUND_ID, 1_000_000_000, 100_000_000, 10_000, 0, 2, 0, false,
+        );
+        let percentage = evore::instruction::percentage_deploy(
+            signer, manager, auth_id, TEST_ROUND_ID, 1_000_000_000, 500, 5, false,
+        );
+        for ix in [ev, percentage] {
+            assert_eq!(ix.accounts[0].pubkey, signer);
+            assert!(ix.accounts[0].is_signer);
+            assert_eq!(ix.accounts[1..], expected[..]);
+        }
+    }
+}
+
 mod ev_deploy {
     use super::*;
 
@@ -508,6 +597,57 @@ mod ev_deploy {
         let result = context.banks_client.process_transaction(tx).await;
         assert!(result.is_err(), "should fail when round has ended (EndSlotExceeded)");
     }
+    
+    #[tokio::test]
+    async fn test_zero_slots_remaining_is_end_slot_reached() {
+        let mut program_test = setup_programs();
+        
+        let miner = Keypair::new();
+        let manager_keypair = Keypair::new();
+        let manager_address = manager_keypair.pubkey();
+        let auth_id = 1u64;
+        let managed_miner_auth = managed_miner_auth_pda(manager_address, auth_id);
+        
+        // Round ends 5 slots after current_slot; we warp exactly onto end_slot
+        let current_slot = 1000;
+        let board = setup_deploy_test_accounts(&mut program_test, TEST_ROUND_ID, current_slot, 5);
+        add_ore_miner_account(
+            &mut program_test,
+            managed_miner_auth.0,
+            [0u64; 25], 0, 0,
+            TEST_ROUND_ID - 1, TEST_ROUND_ID - 1,
+        );
+        
+        let mut context = program_test.start_with_context().await;
+        let _ = context.warp_to_slot(board.end_slot);
+        
+        // Fund
+        let ix0 = system_instruction::transfer(&context.payer.pubkey(), &miner.pubkey(), 2_000_000_000);
+        let ix1 = system_instruction::transfer(&context.payer.pubkey(), &managed_miner_auth.0, 1_000_000_000);
+        let ix2 = system_instruction::transfer(&context.payer.pubkey(), &FEE_COLLECTOR, 1_000_000);
+        let blockhash = context.banks_client.get_latest_blockhash().await.unwrap();
+        let tx = Transaction::new_signed_with_payer(&[ix0, ix1, ix2], Some(&context.payer.pubkey()), &[&context.payer], blockhash);
+        context.banks_client.process_transaction(tx).await.unwrap();
+        
+        // slots_remaining == 0: must be a typed error, not an underflow or ORE failure
+        let cu_limit_ix = ComputeBudgetInstruction::set_compute_unit_limit(1_400_000);
+        let ix1 = evore::instruction::create_manager(miner.pubkey(), manager_address);
+        let ix2 = evore::instruction::ev_deploy(
+            miner.pubkey(), manager_address, auth_id, TEST_ROUND_ID,
+            300_000_000, 100_000_000, 10_000, 800_000_000, 2, 0, true,
+        );
+        
+        let blockhash = context.banks_client.get_latest_blockhash().await.unwrap();
+        let tx = Transaction::new_signed_with_payer(&[cu_limit_ix, ix1, ix2], Some(&miner.pubkey()), &[&miner, &manager_keypair], blockhash);
+        let err = context.banks_client.process_transaction(tx).await.unwrap_err().unwrap();
+        assert_eq!(
+            err,
+            solana_sdk::transaction::TransactionError::InstructionError(
+                2,
+                solana_sdk::instruction::InstructionError::Custom(evore::error::EvoreError::EndSlotReached as u32)
+            )
+        );
+    }
 
     #[tokio::test]
     async fn test_invalid_fee_collector() {
@@ -798,6 +938,72 @@ mod ev_deploy {
         assert!(result.is_err(), "should fail when too many slots left");
     }
     
+    /// The slots_left window is checked against the runtime Clock: the same board and
+    /// accounts are rejected early in the round and accepted once the bank warps closer
+    #[tokio::test]
+    async fn test_slots_left_uses_runtime_clock() {
+        use solana_sdk::{instruction::InstructionError, transaction::TransactionError};
+        
+        let mut program_test = setup_programs();
+        
+        let miner = Keypair::new();
+        let manager_keypair = Keypair::new();
+        let manager_address = manager_keypair.pubkey();
+        let auth_id = 1u64;
+        let managed_miner_auth = managed_miner_auth_pda(manager_address, auth_id);
+        
+        // Round ending in 100 slots
+        let current_slot = 1000;
+        let board = setup_deploy_test_accounts(&mut program_test, TEST_ROUND_ID, current_slot, 100);
+        
+        add_ore_miner_account(
+            &mut program_test,
+            managed_miner_auth.0,
+            [0u64; 25], 0, 0,
+            TEST_ROUND_ID - 1, TEST_ROUND_ID - 1,
+        );
+        
+        let mut context = program_test.start_with_context().await;
+        let _ = context.warp_to_slot(current_slot + 10); // 90 slots left
+        
+        let ix0 = system_instruction::transfer(&context.payer.pubkey(), &miner.pubkey(), 2_000_000_000);
+        let ix1 = system_instruction::transfer(&context.payer.pubkey(), &FEE_COLLECTOR, 1_000_000);
+        let blockhash = context.banks_client.get_latest_blockhash().await.unwrap();
+        let tx = Transaction::new_signed_with_payer(&[ix0, ix1], Some(&context.payer.pubkey()), &[&context.payer], blockhash);
+        context.banks_client.process_transaction(tx).await.unwrap();
+        
+        let deploy_ixs = || {
+            let deploy = evore::instruction::ev_deploy(
+                miner.pubkey(), manager_address, auth_id, TEST_ROUND_ID,
+                300_000_000, 100_000_000, 10_000, 800_000_000,
+                2,  // slots_left threshold
+                0,  // attempts
+                true,  // allow_multi_deploy
+            );
+            // The slot never comes from a caller-supplied account
+            assert!(deploy.accounts.iter().all(|meta| meta.pubkey != solana_program::sysvar::clock::ID));
+            vec![
+                ComputeBudgetInstruction::set_compute_unit_limit(1_400_000),
+                evore::instruction::create_manager(miner.pubkey(), manager_address),
+                deploy,
+            ]
+        };
+        
+        let blockhash = context.banks_client.get_latest_blockhash().await.unwrap();
+        let tx = Transaction::new_signed_with_payer(&deploy_ixs(), Some(&miner.pubkey()), &[&miner, &manager_keypair], blockhash);
+        let err = context.banks_client.process_transaction(tx).await.unwrap_err().unwrap();
+        assert_eq!(
+            err,
+            TransactionError::InstructionError(2, InstructionError::Custom(evore::error::EvoreError::TooManySlotsLeft as u32)),
+        );
+        
+        // Same accounts, only the bank's clock moves: 2 slots left is inside the window
+        let _ = context.warp_to_slot(board.end_slot - 2);
+        let blockhash = context.get_new_latest_blockhash().await.unwrap();
+        let tx = Transaction::new_signed_with_payer(&deploy_ixs(), Some(&miner.pubkey()), &[&miner, &manager_keypair], blockhash);
+        context.banks_client.process_transaction(tx).await.expect("deploy should succeed once the clock is inside the window");
+    }
+    
     #[tokio::test]
     async fn test_wrong_authority() {
         let mut program_test = setup_programs();
@@ -1883,6 +2089,76 @@ mod percentage_deploy {
         context.banks_client.process_transaction(tx).await.expect("should succeed when multi_deploy is allowed");
     }
 
+    /// A deploy that sat in a queue and lands after the miner already deployed this round
+    /// must be rejected outright, leaving balances and miner state untouched
+    #[tokio::test]
+    async fn test_stale_deploy_after_round_deployed_rejected() {
+        use solana_sdk::{instruction::InstructionError, transaction::TransactionError};
+        use evore::error::EvoreError;
+
+        let mut program_test = setup_programs();
+
+        let miner = Keypair::new();
+        let manager_address = Keypair::new().pubkey();
+        let auth_id = 1u64;
+        let (managed_miner_auth, _) = managed_miner_auth_pda(manager_address, auth_id);
+
+        add_manager_account(&mut program_test, manager_address, miner.pubkey());
+
+        let current_slot = 1000;
+        let _board = setup_deploy_test_accounts(&mut program_test, TEST_ROUND_ID, current_slot, 5);
+        add_ore_miner_account(&mut program_test, managed_miner_auth, [0u64; 25], 0, 0, TEST_ROUND_ID - 1, TEST_ROUND_ID - 1);
+
+        let mut context = program_test.start_with_context().await;
+        let _ = context.warp_to_slot(current_slot + 3);
+
+        let ix0 = system_instruction::transfer(&context.payer.pubkey(), &miner.pubkey(), 2_000_000_000);
+        let ix1 = system_instruction::transfer(&context.payer.pubkey(), &FEE_COLLECTOR, 1_000_000);
+        let blockhash = context.banks_client.get_latest_blockhash().await.unwrap();
+        let tx = Transaction::new_signed_with_payer(&[ix0, ix1], Some(&context.payer.pubkey()), &[&context.payer], blockhash);
+        context.banks_client.process_transaction(tx).await.unwrap();
+
+        let deploy_ix = || evore::instruction::percentage_deploy(
+            miner.pubkey(),
+            manager_address,
+            auth_id,
+            TEST_ROUND_ID,
+            500_000_000,
+            1000,
+            5,
+            false,
+        );
+
+        // The first copy lands and deploys this round
+        let cu_limit_ix = ComputeBudgetInstruction::set_compute_unit_limit(1_400_000);
+        let blockhash = context.banks_client.get_latest_blockhash().await.unwrap();
+        let tx = Transaction::new_signed_with_payer(&[cu_limit_ix.clone(), deploy_ix()], Some(&miner.pubkey()), &[&miner], blockhash);
+        context.banks_client.process_transaction(tx).await.expect("first deploy should succeed");
+
+        let ore_miner_address = miner_pda(managed_miner_auth).0;
+        let miner_before = context.banks_client.get_account(ore_miner_address).await.unwrap().unwrap();
+        assert_eq!(Miner::try_from_bytes(&miner_before.data).unwrap().round_id, TEST_ROUND_ID);
+        let auth_balance_before = context.banks_client.get_balance(managed_miner_auth).await.unwrap();
+
+        // The delayed copy: same deploy, new blockhash so it isn't deduplicated by signature
+        let _ = context.warp_to_slot(current_slot + 4);
+        let blockhash = context.get_new_latest_blockhash().await.unwrap();
+        let tx = Transaction::new_signed_with_payer(&[cu_limit_ix, deploy_ix()], Some(&miner.pubkey()), &[&miner], blockhash);
+        let err = context.banks_client.process_transaction(tx).await.unwrap_err().unwrap();
+        assert_eq!(
+            err,
+            TransactionError::InstructionError(1, InstructionError::Custom(EvoreError::AlreadyDeployedThisRound as u32)),
+        );
+
+        let miner_after = context.banks_client.get_account(ore_miner_address).await.unwrap().unwrap();
+        assert_eq!(miner_after.data, miner_before.data, "ORE miner must not change");
+        assert_eq!(
+            context.banks_client.get_balance(managed_miner_auth).await.unwrap(),
+            auth_balance_before,
+            "no lamports may leave the managed miner auth"
+        );
+    }
+
     #[tokio::test]
     async fn test_invalid_pda() {
         let mut program_test = setup_programs();
@@ -2345,6 +2621,7 @@ mod percentage_deploy {
 
 mod manual_deploy {
     use super::*;
+    use solana_program_test::{BanksTransactionResultWithMetadata, ProgramTestContext};
 
     #[tokio::test]
     async fn test_success_with_balance_verification() {
@@ -2467,7 +2744,7 @@ mod manual_deploy {
     }
 
     #[tokio::test]
-    async fn test_single_square() {
+    async fn test_amounts_sum_overflow() {
         let mut program_test = setup_programs();
         
         let miner = Keypair::new();
@@ -2476,6 +2753,8 @@ mod manual_deploy {
         let auth_id = 1u64;
         let managed_miner_auth = managed_miner_auth_pda(manager_address, auth_id);
         
+        add_manager_account(&mut program_test, manager_address, miner.pubkey());
+        
         let current_slot = 1000;
         let _board = setup_deploy_test_accounts(&mut program_test, TEST_ROUND_ID, current_slot, 5);
         add_ore_miner_account(&mut program_test, managed_miner_auth.0, [0u64; 25], 0, 0, TEST_ROUND_ID - 1, TEST_ROUND_ID - 1);
@@ -2491,13 +2770,12 @@ mod manual_deploy {
         let tx = Transaction::new_signed_with_payer(&[ix0, ix1, ix2], Some(&context.payer.pubkey()), &[&context.payer], blockhash);
         context.banks_client.process_transaction(tx).await.unwrap();
         
-        // Deploy to single square
+        // Two near-u64::MAX amounts: the total must fail typed instead of saturating
         let mut amounts = [0u64; 25];
-        amounts[12] = 100_000_000; // 0.1 SOL on square 12 only
-        
+        amounts[0] = u64::MAX - 1;
+        amounts[1] = u64::MAX - 1;
         let cu_limit_ix = ComputeBudgetInstruction::set_compute_unit_limit(1_400_000);
-        let ix1 = evore::instruction::create_manager(miner.pubkey(), manager_address);
-        let ix2 = evore::instruction::manual_deploy(
+        let ix = evore::instruction::manual_deploy(
             miner.pubkey(),
             manager_address,
             auth_id,
@@ -2507,88 +2785,304 @@ mod manual_deploy {
         );
         
         let blockhash = context.banks_client.get_latest_blockhash().await.unwrap();
-        let tx = Transaction::new_signed_with_payer(
-            &[cu_limit_ix, ix1, ix2],
-            Some(&miner.pubkey()),
-            &[&miner, &manager_keypair],
-            blockhash,
+        let tx = Transaction::new_signed_with_payer(&[cu_limit_ix, ix], Some(&miner.pubkey()), &[&miner], blockhash);
+        let err = context.banks_client.process_transaction(tx).await.unwrap_err().unwrap();
+        assert_eq!(
+            err,
+            solana_sdk::transaction::TransactionError::InstructionError(
+                1,
+                solana_sdk::instruction::InstructionError::Custom(evore::error::EvoreError::ArithmeticOverflow as u32)
+            )
         );
-        context.banks_client.process_transaction(tx).await.expect("single square deploy should succeed");
     }
-}
-
-mod checkpoint {
-    use super::*;
 
     #[tokio::test]
-    async fn test_manager_not_initialized() {
+    async fn test_single_square() {
         let mut program_test = setup_programs();
         
         let miner = Keypair::new();
-        let manager_address = Pubkey::new_unique();
+        let manager_keypair = Keypair::new();
+        let manager_address = manager_keypair.pubkey();
         let auth_id = 1u64;
         let managed_miner_auth = managed_miner_auth_pda(manager_address, auth_id);
         
-        // Setup accounts but DON'T create manager - add empty account
         let current_slot = 1000;
-        add_board_account(&mut program_test, TEST_ROUND_ID, current_slot, current_slot + 100, 0);
-        add_round_account(&mut program_test, TEST_ROUND_ID, [0u64; 25], 0, current_slot + 1000);
-        add_treasury_account(&mut program_test);
+        let _board = setup_deploy_test_accounts(&mut program_test, TEST_ROUND_ID, current_slot, 5);
         add_ore_miner_account(&mut program_test, managed_miner_auth.0, [0u64; 25], 0, 0, TEST_ROUND_ID - 1, TEST_ROUND_ID - 1);
         
-        // Add empty manager account
-        program_test.add_account(
-            manager_address,
-            Account {
-                lamports: 1_000_000,
-                data: vec![],
-                owner: evore::id(),
-                executable: false,
-                rent_epoch: 0,
-            },
-        );
-        
-        let context = program_test.start_with_context().await;
+        let mut context = program_test.start_with_context().await;
+        let _ = context.warp_to_slot(current_slot + 3);
         
-        // Fund miner
-        let ix = system_instruction::transfer(&context.payer.pubkey(), &miner.pubkey(), 1_000_000_000);
+        // Fund
+        let ix0 = system_instruction::transfer(&context.payer.pubkey(), &miner.pubkey(), 2_000_000_000);
+        let ix1 = system_instruction::transfer(&context.payer.pubkey(), &managed_miner_auth.0, 1_000_000_000);
+        let ix2 = system_instruction::transfer(&context.payer.pubkey(), &FEE_COLLECTOR, 1_000_000);
         let blockhash = context.banks_client.get_latest_blockhash().await.unwrap();
-        let tx = Transaction::new_signed_with_payer(&[ix], Some(&context.payer.pubkey()), &[&context.payer], blockhash);
+        let tx = Transaction::new_signed_with_payer(&[ix0, ix1, ix2], Some(&context.payer.pubkey()), &[&context.payer], blockhash);
         context.banks_client.process_transaction(tx).await.unwrap();
         
-        // Try checkpoint with uninitialized manager
-        let ix = evore::instruction::mm_checkpoint(miner.pubkey(), manager_address, TEST_ROUND_ID, auth_id);
+        // Deploy to single square
+        let mut amounts = [0u64; 25];
+        amounts[12] = 100_000_000; // 0.1 SOL on square 12 only
+        
+        let cu_limit_ix = ComputeBudgetInstruction::set_compute_unit_limit(1_400_000);
+        let ix1 = evore::instruction::create_manager(miner.pubkey(), manager_address);
+        let ix2 = evore::instruction::manual_deploy(
+            miner.pubkey(),
+            manager_address,
+            auth_id,
+            TEST_ROUND_ID,
+            amounts,
+            true,  // allow_multi_deploy
+        );
+        
         let blockhash = context.banks_client.get_latest_blockhash().await.unwrap();
-        let tx = Transaction::new_signed_with_payer(&[ix], Some(&miner.pubkey()), &[&miner], blockhash);
-        let result = context.banks_client.process_transaction(tx).await;
-        assert!(result.is_err(), "should fail with uninitialized manager");
+        let tx = Transaction::new_signed_with_payer(
+            &[cu_limit_ix, ix1, ix2],
+            Some(&miner.pubkey()),
+            &[&miner, &manager_keypair],
+            blockhash,
+        );
+        context.banks_client.process_transaction(tx).await.expect("single square deploy should succeed");
     }
 
     #[tokio::test]
-    async fn test_invalid_pda() {
+    async fn test_preview_returns_planned_amounts_without_state_change() {
         let mut program_test = setup_programs();
         
         let miner = Keypair::new();
         let manager_keypair = Keypair::new();
         let manager_address = manager_keypair.pubkey();
         let auth_id = 1u64;
-        let wrong_auth_id = 999u64;
-        let wrong_managed_miner_auth = managed_miner_auth_pda(manager_address, wrong_auth_id);
-        
-        // Pre-create manager
-        add_manager_account(&mut program_test, manager_address, miner.pubkey());
+        let managed_miner_auth = managed_miner_auth_pda(manager_address, auth_id);
+        let ore_miner_address = miner_pda(managed_miner_auth.0).0;
         
-        // Setup with wrong PDA
         let current_slot = 1000;
-        add_board_account(&mut program_test, TEST_ROUND_ID, current_slot, current_slot + 100, 0);
-        add_round_account(&mut program_test, TEST_ROUND_ID, [0u64; 25], 0, current_slot + 1000);
-        add_treasury_account(&mut program_test);
-        add_ore_miner_account(&mut program_test, wrong_managed_miner_auth.0, [0u64; 25], 0, 0, TEST_ROUND_ID - 1, TEST_ROUND_ID - 1);
+        let _board = setup_deploy_test_accounts(&mut program_test, TEST_ROUND_ID, current_slot, 5);
+        add_ore_miner_account(&mut program_test, managed_miner_auth.0, [0u64; 25], 0, 0, TEST_ROUND_ID - 1, TEST_ROUND_ID - 1);
         
-        let context = program_test.start_with_context().await;
+        let mut context = program_test.start_with_context().await;
+        let _ = context.warp_to_slot(current_slot + 3);
         
-        // Fund
-        let ix = system_instruction::transfer(&context.payer.pubkey(), &miner.pubkey(), 1_000_000_000);
+        // Fund and create the manager up front so the preview tx is the only thing left
+        let ix0 = system_instruction::transfer(&context.payer.pubkey(), &miner.pubkey(), 2_000_000_000);
+        let ix1 = system_instruction::transfer(&context.payer.pubkey(), &managed_miner_auth.0, 1_000_000_000);
+        let ix2 = system_instruction::transfer(&context.payer.pubkey(), &FEE_COLLECTOR, 1_000_000);
+        let ix3 = evore::instruction::create_manager(miner.pubkey(), manager_address);
+        let blockhash = context.banks_client.get_latest_blockhash().await.unwrap();
+        let tx = Transaction::new_signed_with_payer(
+            &[ix0, ix1, ix2, ix3],
+            Some(&context.payer.pubkey()),
+            &[&context.payer, &miner, &manager_keypair],
+            blockhash,
+        );
+        context.banks_client.process_transaction(tx).await.unwrap();
+        
+        let miner_balance_before = context.banks_client.get_balance(miner.pubkey()).await.unwrap();
+        let auth_balance_before = context.banks_client.get_balance(managed_miner_auth.0).await.unwrap();
+        let fee_collector_before = context.banks_client.get_balance(FEE_COLLECTOR).await.unwrap();
+        let ore_miner_before = context.banks_client.get_account(ore_miner_address).await.unwrap().unwrap();
+        
+        let mut amounts = [0u64; 25];
+        amounts[3] = 20_000_000;
+        amounts[17] = 45_000_000;
+        
+        // Payer covers the tx fee so the miner's balance only moves if the deploy does
+        let ix = evore::instruction::manual_deploy_preview(
+            miner.pubkey(),
+            manager_address,
+            auth_id,
+            TEST_ROUND_ID,
+            amounts,
+            false,
+        );
+        let blockhash = context.banks_client.get_latest_blockhash().await.unwrap();
+        let tx = Transaction::new_signed_with_payer(
+            &[ix],
+            Some(&context.payer.pubkey()),
+            &[&context.payer, &miner],
+            blockhash,
+        );
+        let result = context.banks_client.process_transaction_with_metadata(tx).await.unwrap();
+        result.result.expect("preview should succeed");
+        
+        let return_data = result.metadata.unwrap().return_data.expect("preview sets return data");
+        assert_eq!(return_data.program_id, evore::id());
+        let planned: Vec<u64> = return_data
+            .data
+            .chunks_exact(8)
+            .map(|c| u64::from_le_bytes(c.try_into().unwrap()))
+            .collect();
+        assert_eq!(planned, amounts.to_vec());
+        
+        // Nothing moved and ORE never saw a deploy
+        assert_eq!(context.banks_client.get_balance(miner.pubkey()).await.unwrap(), miner_balance_before);
+        assert_eq!(context.banks_client.get_balance(managed_miner_auth.0).await.unwrap(), auth_balance_before);
+        assert_eq!(context.banks_client.get_balance(FEE_COLLECTOR).await.unwrap(), fee_collector_before);
+        let ore_miner_after = context.banks_client.get_account(ore_miner_address).await.unwrap().unwrap();
+        assert_eq!(ore_miner_after.data, ore_miner_before.data);
+    }
+
+    /// Manual deploy of a distinct amount on every square (25 deploy CPIs) under `cu_limit`
+    ///
+    /// Returns the outcome and the ORE miner, round and managed_miner_auth state before and after
+    async fn deploy_every_square(cu_limit: u32) -> (
+        BanksTransactionResultWithMetadata,
+        (Account, Account, u64),
+        (Account, Account, u64),
+    ) {
+        let mut program_test = setup_programs();
+
+        let miner = Keypair::new();
+        let manager_address = Keypair::new().pubkey();
+        let auth_id = 1u64;
+        let managed_miner_auth = managed_miner_auth_pda(manager_address, auth_id);
+
+        add_manager_account(&mut program_test, manager_address, miner.pubkey());
+        let current_slot = 1000;
+        let _board = setup_deploy_test_accounts(&mut program_test, TEST_ROUND_ID, current_slot, 5);
+        // Last round's deployment is still on the miner, so any partial write would show up
+        add_ore_miner_account(&mut program_test, managed_miner_auth.0, [1_000_000u64; 25], 0, 0, TEST_ROUND_ID - 1, TEST_ROUND_ID - 1);
+
+        let mut context = program_test.start_with_context().await;
+        let _ = context.warp_to_slot(current_slot + 3);
+
+        let ix0 = system_instruction::transfer(&context.payer.pubkey(), &miner.pubkey(), 5_000_000_000);
+        let ix1 = system_instruction::transfer(&context.payer.pubkey(), &FEE_COLLECTOR, 1_000_000);
+        let blockhash = context.banks_client.get_latest_blockhash().await.unwrap();
+        let tx = Transaction::new_signed_with_payer(&[ix0, ix1], Some(&context.payer.pubkey()), &[&context.payer], blockhash);
+        context.banks_client.process_transaction(tx).await.unwrap();
+
+        let before = snapshot(&mut context, managed_miner_auth.0).await;
+
+        let mut amounts = [0u64; 25];
+        for (i, amount) in amounts.iter_mut().enumerate() {
+            *amount = 10_000_000 + i as u64 * 1_000;
+        }
+        let ix = evore::instruction::manual_deploy(miner.pubkey(), manager_address, auth_id, TEST_ROUND_ID, amounts, true);
+        let blockhash = context.banks_client.get_latest_blockhash().await.unwrap();
+        let tx = Transaction::new_signed_with_payer(
+            &[ComputeBudgetInstruction::set_compute_unit_limit(cu_limit), ix],
+            Some(&miner.pubkey()),
+            &[&miner],
+            blockhash,
+        );
+        let outcome = context.banks_client.process_transaction_with_metadata(tx).await.unwrap();
+
+        let after = snapshot(&mut context, managed_miner_auth.0).await;
+        (outcome, before, after)
+    }
+
+    async fn snapshot(context: &mut ProgramTestContext, managed_miner_auth: Pubkey) -> (Account, Account, u64) {
+        let banks_client = &mut context.banks_client;
+        (
+            banks_client.get_account(miner_pda(managed_miner_auth).0).await.unwrap().unwrap(),
+            banks_client.get_account(round_pda(TEST_ROUND_ID).0).await.unwrap().unwrap(),
+            banks_client.get_balance(managed_miner_auth).await.unwrap(),
+        )
+    }
+
+    fn deploy_cpis(outcome: &BanksTransactionResultWithMetadata) -> usize {
+        outcome.metadata.as_ref().unwrap().log_messages.iter().filter(|l| l.contains("deploying")).count()
+    }
+
+    #[tokio::test]
+    async fn test_failure_mid_loop_rolls_back_earlier_squares() {
+        // Baseline: all 25 squares land, which tells us what the whole loop costs
+        let (outcome, _, (miner_after, _, _)) = deploy_every_square(1_400_000).await;
+        outcome.result.clone().expect("unconstrained deploy should succeed");
+        assert_eq!(deploy_cpis(&outcome), 25);
+        let consumed = outcome.metadata.as_ref().unwrap().compute_units_consumed;
+        assert_eq!(Miner::try_from_bytes(&miner_after.data).unwrap().deployed.iter().filter(|&&d| d > 0).count(), 25);
+
+        // Half the budget runs out partway through the deploy CPIs
+        let (outcome, before, after) = deploy_every_square((consumed / 2) as u32).await;
+        assert!(outcome.result.is_err(), "deploy should run out of compute");
+        let landed = deploy_cpis(&outcome);
+        assert!(landed > 0 && landed < 25, "failure should hit mid-loop, {} deploys ran first", landed);
+
+        // None of the squares deployed before the failure survive
+        let (miner_before, round_before, auth_before) = before;
+        let (miner_after, round_after, auth_after) = after;
+        assert_eq!(Miner::try_from_bytes(&miner_after.data).unwrap().deployed, [1_000_000u64; 25]);
+        assert_eq!(miner_after.data, miner_before.data, "miner is untouched");
+        assert_eq!(round_after.data, round_before.data, "round totals are untouched");
+        assert_eq!(auth_after, auth_before, "no lamports left managed_miner_auth");
+    }
+}
+
+mod checkpoint {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_manager_not_initialized() {
+        let mut program_test = setup_programs();
+        
+        let miner = Keypair::new();
+        let manager_address = Pubkey::new_unique();
+        let auth_id = 1u64;
+        let managed_miner_auth = managed_miner_auth_pda(manager_address, auth_id);
+        
+        // Setup accounts but DON'T create manager - add empty account
+        let current_slot = 1000;
+        add_board_account(&mut program_test, TEST_ROUND_ID, current_slot, current_slot + 100, 0);
+        add_round_account(&mut program_test, TEST_ROUND_ID, [0u64; 25], 0, current_slot + 1000);
+        add_treasury_account(&mut program_test);
+        add_ore_miner_account(&mut program_test, managed_miner_auth.0, [0u64; 25], 0, 0, TEST_ROUND_ID - 1, TEST_ROUND_ID - 1);
+        
+        // Add empty manager account
+        program_test.add_account(
+            manager_address,
+            Account {
+                lamports: 1_000_000,
+                data: vec![],
+                owner: evore::id(),
+                executable: false,
+                rent_epoch: 0,
+            },
+        );
+        
+        let context = program_test.start_with_context().await;
+        
+        // Fund miner
+        let ix = system_instruction::transfer(&context.payer.pubkey(), &miner.pubkey(), 1_000_000_000);
+        let blockhash = context.banks_client.get_latest_blockhash().await.unwrap();
+        let tx = Transaction::new_signed_with_payer(&[ix], Some(&context.payer.pubkey()), &[&context.payer], blockhash);
+        context.banks_client.process_transaction(tx).await.unwrap();
+        
+        // Try checkpoint with uninitialized manager
+        let ix = evore::instruction::mm_checkpoint(miner.pubkey(), manager_address, TEST_ROUND_ID, auth_id);
+        let blockhash = context.banks_client.get_latest_blockhash().await.unwrap();
+        let tx = Transaction::new_signed_with_payer(&[ix], Some(&miner.pubkey()), &[&miner], blockhash);
+        let result = context.banks_client.process_transaction(tx).await;
+        assert!(result.is_err(), "should fail with uninitialized manager");
+    }
+
+    #[tokio::test]
+    async fn test_invalid_pda() {
+        let mut program_test = setup_programs();
+        
+        let miner = Keypair::new();
+        let manager_keypair = Keypair::new();
+        let manager_address = manager_keypair.pubkey();
+        let auth_id = 1u64;
+        let wrong_auth_id = 999u64;
+        let wrong_managed_miner_auth = managed_miner_auth_pda(manager_address, wrong_auth_id);
+        
+        // Pre-create manager
+        add_manager_account(&mut program_test, manager_address, miner.pubkey());
+        
+        // Setup with wrong PDA
+        let current_slot = 1000;
+        add_board_account(&mut program_test, TEST_ROUND_ID, current_slot, current_slot + 100, 0);
+        add_round_account(&mut program_test, TEST_ROUND_ID, [0u64; 25], 0, current_slot + 1000);
+        add_treasury_account(&mut program_test);
+        add_ore_miner_account(&mut program_test, wrong_managed_miner_auth.0, [0u64; 25], 0, 0, TEST_ROUND_ID - 1, TEST_ROUND_ID - 1);
+        
+        let context = program_test.start_with_context().await;
+        
+        // Fund
+        let ix = system_instruction::transfer(&context.payer.pubkey(), &miner.pubkey(), 1_000_000_000);
         let blockhash = context.banks_client.get_latest_blockhash().await.unwrap();
         let tx = Transaction::new_signed_with_payer(&[ix], Some(&context.payer.pubkey()), &[&context.payer], blockhash);
         context.banks_client.process_transaction(tx).await.unwrap();
@@ -2936,6 +3430,63 @@ mod claim_ore {
         assert!(result.is_err(), "should fail with invalid PDA");
     }
 
+    #[test]
+    fn test_treasury_tokens_address_is_derived_ata() {
+        use spl_associated_token_account::get_associated_token_address;
+
+        assert_eq!(ore_api::treasury_pda().0, TREASURY_ADDRESS);
+        assert_eq!(
+            ore_api::TREASURY_TOKENS_ADDRESS,
+            get_associated_token_address(&TREASURY_ADDRESS, &MINT_ADDRESS),
+        );
+        assert_eq!(ore_api::treasury_tokens_address(), ore_api::TREASURY_TOKENS_ADDRESS);
+
+        // Both our claim and the ORE claim it CPIs pass the derived ATA
+        let ix = evore::instruction::mm_claim_ore(Pubkey::new_unique(), Pubkey::new_unique(), 1);
+        assert_eq!(ix.accounts[8].pubkey, TREASURY_ADDRESS);
+        assert_eq!(ix.accounts[9].pubkey, ore_api::TREASURY_TOKENS_ADDRESS);
+        let ore_ix = ore_api::claim_ore(Pubkey::new_unique());
+        assert_eq!(ore_ix.accounts[6].pubkey, ore_api::TREASURY_TOKENS_ADDRESS);
+    }
+
+    #[tokio::test]
+    async fn test_wrong_treasury_tokens_rejected() {
+        let mut program_test = setup_programs();
+        
+        let miner = Keypair::new();
+        let manager_address = Keypair::new().pubkey();
+        let auth_id = 1u64;
+        let managed_miner_auth = managed_miner_auth_pda(manager_address, auth_id);
+        
+        add_manager_account(&mut program_test, manager_address, miner.pubkey());
+        add_ore_miner_account(&mut program_test, managed_miner_auth.0, [0u64; 25], 0, 1_000_000_000, TEST_ROUND_ID - 1, TEST_ROUND_ID - 1);
+        add_treasury_account(&mut program_test);
+        add_mint_account(&mut program_test);
+        add_treasury_ata_account(&mut program_test);
+        
+        let context = program_test.start_with_context().await;
+        
+        let ix = system_instruction::transfer(&context.payer.pubkey(), &miner.pubkey(), 1_000_000_000);
+        let blockhash = context.banks_client.get_latest_blockhash().await.unwrap();
+        let tx = Transaction::new_signed_with_payer(&[ix], Some(&context.payer.pubkey()), &[&context.payer], blockhash);
+        context.banks_client.process_transaction(tx).await.unwrap();
+        
+        // Account index 9 is the treasury token account
+        let mut ix = evore::instruction::mm_claim_ore(miner.pubkey(), manager_address, auth_id);
+        ix.accounts[9].pubkey = Pubkey::new_unique();
+        
+        let blockhash = context.banks_client.get_latest_blockhash().await.unwrap();
+        let tx = Transaction::new_signed_with_payer(&[ix], Some(&miner.pubkey()), &[&miner], blockhash);
+        let err = context.banks_client.process_transaction(tx).await.unwrap_err().unwrap();
+        assert_eq!(
+            err,
+            solana_sdk::transaction::TransactionError::InstructionError(
+                0,
+                solana_sdk::instruction::InstructionError::Custom(evore::error::EvoreError::InvalidTreasury as u32)
+            )
+        );
+    }
+
     #[tokio::test]
     async fn test_wrong_authority() {
         let mut program_test = setup_programs();
@@ -3176,7 +3727,7 @@ mod mm_autodeploy_fee_tests {
         // Verify manager account
         let manager_account = context.banks_client.get_account(manager_address).await.unwrap().unwrap();
         assert_eq!(manager_account.owner, evore::id());
-        assert_eq!(manager_account.data.len(), 40); // 8 discriminator + 32 authority
+        assert_eq!(manager_account.data.len(), 104); // 8 discriminator + 32 authority + 64 reserved
         
         // Verify deployer account
         let deployer_account = context.banks_client.get_account(deployer_pda_addr).await.unwrap().unwrap();
@@ -3410,120 +3961,1842 @@ mod mm_autodeploy_fee_tests {
             "Balance decrease should be roughly deployed amount only, no Evore fees on second deploy"
         );
     }
-}
 
-// ============================================================================
-// MMCreateMiner Tests
-// ============================================================================
-
-mod test_ore_automate_direct {
-    use super::*;
-    use solana_sdk::pubkey::Pubkey;
-
-    /// Test calling ORE automate directly (open then close) to verify the flow works
+    /// A miner whose round_id is ahead of the board (stale board / reorg) must not be deployed for
     #[tokio::test]
-    async fn test_automate_open_close() {
+    async fn test_rejects_miner_ahead_of_board() {
         let mut program_test = setup_programs();
         
-        let authority = Keypair::new();
+        let deploy_authority = Keypair::new();
+        let manager_address = Keypair::new().pubkey();
+        let auth_id = 0u64;
+        let (managed_miner_auth_addr, _) = managed_miner_auth_pda(manager_address, auth_id);
+        let (deployer_pda_addr, _) = deployer_pda(manager_address);
         
-        // Fund authority
-        program_test.add_account(
-            authority.pubkey(),
-            Account {
-                lamports: 10_000_000_000, // 10 SOL
-                data: vec![],
-                owner: solana_sdk::system_program::id(),
-                executable: false,
-                rent_epoch: 0,
-            },
-        );
-
-        let ctx = program_test.start_with_context().await;
-
-        let (miner_address, _) = miner_pda(authority.pubkey());
-        let automation_address = ore_api::automation_pda(authority.pubkey()).0;
-
-        // Step 1: Open automation (creates miner)
-        // executor = authority (opens)
-        let open_ix = ore_api::automate(
-            authority.pubkey(),
-            0,
-            0,
-            authority.pubkey(), // executor = signer opens
-            0,
+        add_manager_account(&mut program_test, manager_address, deploy_authority.pubkey());
+        add_deployer_account(
+            &mut program_test,
+            deployer_pda_addr,
+            manager_address,
+            deploy_authority.pubkey(),
+            500,
+            1000,
             0,
             0,
-            false,
         );
-
-        let open_tx = Transaction::new_signed_with_payer(
-            &[open_ix],
-            Some(&authority.pubkey()),
-            &[&authority],
-            ctx.last_blockhash,
+        
+        let current_slot = 1000;
+        let _board = setup_deploy_test_accounts(&mut program_test, TEST_ROUND_ID, current_slot, 100);
+        
+        // Miner already played a round the board hasn't reached
+        add_ore_miner_account(
+            &mut program_test,
+            managed_miner_auth_addr,
+            [0u64; 25],
+            0, 0,
+            TEST_ROUND_ID + 1, // checkpoint_id
+            TEST_ROUND_ID + 1, // round_id - ahead of the board
         );
-
-        ctx.banks_client.process_transaction(open_tx).await.unwrap();
-
-        // Verify miner and automation exist
-        let miner_account = ctx.banks_client.get_account(miner_address).await.unwrap();
-        assert!(miner_account.is_some(), "Miner account should exist after open");
+        add_autodeploy_balance(&mut program_test, managed_miner_auth_addr, 10_000_000_000);
         
-        let automation_account = ctx.banks_client.get_account(automation_address).await.unwrap();
-        assert!(automation_account.is_some(), "Automation account should exist after open");
-
-        // Step 2: Close automation
-        // executor = Pubkey::default() (closes)
-        let close_ix = ore_api::automate(
-            authority.pubkey(),
-            0,
-            0,
-            Pubkey::default(), // executor = default closes
+        let mut context = program_test.start_with_context().await;
+        let _ = context.warp_to_slot(current_slot + 3);
+        
+        let ix0 = system_instruction::transfer(&context.payer.pubkey(), &deploy_authority.pubkey(), 100_000_000);
+        let blockhash = context.banks_client.get_latest_blockhash().await.unwrap();
+        let tx = Transaction::new_signed_with_payer(&[ix0], Some(&context.payer.pubkey()), &[&context.payer], blockhash);
+        context.banks_client.process_transaction(tx).await.unwrap();
+        
+        let cu_limit_ix = ComputeBudgetInstruction::set_compute_unit_limit(1_400_000);
+        let ix = evore::instruction::mm_autodeploy(
+            deploy_authority.pubkey(),
+            manager_address,
+            auth_id,
+            TEST_ROUND_ID,
+            100_000,
+            0b11111,
+        );
+        let blockhash = context.banks_client.get_latest_blockhash().await.unwrap();
+        let tx = Transaction::new_signed_with_payer(
+            &[cu_limit_ix, ix],
+            Some(&deploy_authority.pubkey()),
+            &[&deploy_authority],
+            blockhash
+        );
+        let err = context.banks_client.process_transaction(tx).await.unwrap_err().unwrap();
+        assert_eq!(
+            err,
+            solana_sdk::transaction::TransactionError::InstructionError(
+                1,
+                solana_sdk::instruction::InstructionError::Custom(evore::error::EvoreError::MinerAheadOfBoard as u32)
+            )
+        );
+        
+        // Nothing left the managed_miner_auth
+        let balance = context.banks_client.get_balance(managed_miner_auth_addr).await.unwrap();
+        assert_eq!(balance, 10_000_000_000);
+    }
+
+    /// A PDA drained below the planned deploy gets a smaller deploy instead of a failure
+    #[tokio::test]
+    async fn test_clamps_deploy_to_available_balance() {
+        let mut program_test = setup_programs();
+        
+        let deploy_authority = Keypair::new();
+        let manager_address = Keypair::new().pubkey();
+        let auth_id = 0u64;
+        let (managed_miner_auth_addr, _) = managed_miner_auth_pda(manager_address, auth_id);
+        let (deployer_pda_addr, _) = deployer_pda(manager_address);
+        
+        let bps_fee = 500u64;
+        let flat_fee = 1000u64;
+        add_manager_account(&mut program_test, manager_address, deploy_authority.pubkey());
+        add_deployer_account(
+            &mut program_test,
+            deployer_pda_addr,
+            manager_address,
+            deploy_authority.pubkey(),
+            bps_fee,
+            flat_fee,
             0,
             0,
+        );
+        
+        let current_slot = 1000;
+        let _board = setup_deploy_test_accounts(&mut program_test, TEST_ROUND_ID, current_slot, 100);
+        add_ore_miner_account(
+            &mut program_test,
+            managed_miner_auth_addr,
+            [0u64; 25],
+            0, 0,
+            TEST_ROUND_ID - 1,
+            TEST_ROUND_ID - 1,
+        );
+        
+        // Rent + checkpoint fee + flat and protocol fees are reserved, leaving 2_100_000
+        // for the deploy and its 5% fee: 400_000 per square instead of the planned 1_000_000
+        let reserved = 890_880 + ore_api::CHECKPOINT_FEE + flat_fee + 1000;
+        add_autodeploy_balance(&mut program_test, managed_miner_auth_addr, reserved + 2_100_000);
+        
+        let mut context = program_test.start_with_context().await;
+        let _ = context.warp_to_slot(current_slot + 3);
+        
+        let ix0 = system_instruction::transfer(&context.payer.pubkey(), &deploy_authority.pubkey(), 100_000_000);
+        let ix1 = system_instruction::transfer(&context.payer.pubkey(), &FEE_COLLECTOR, 1_000_000);
+        let blockhash = context.banks_client.get_latest_blockhash().await.unwrap();
+        let tx = Transaction::new_signed_with_payer(&[ix0, ix1], Some(&context.payer.pubkey()), &[&context.payer], blockhash);
+        context.banks_client.process_transaction(tx).await.unwrap();
+        
+        let cu_limit_ix = ComputeBudgetInstruction::set_compute_unit_limit(1_400_000);
+        let ix = evore::instruction::mm_autodeploy(
+            deploy_authority.pubkey(),
+            manager_address,
+            auth_id,
+            TEST_ROUND_ID,
+            1_000_000,
+            0b11111,
+        );
+        let blockhash = context.banks_client.get_latest_blockhash().await.unwrap();
+        let tx = Transaction::new_signed_with_payer(
+            &[cu_limit_ix, ix],
+            Some(&deploy_authority.pubkey()),
+            &[&deploy_authority],
+            blockhash
+        );
+        let result = context.banks_client.process_transaction_with_metadata(tx).await.unwrap();
+        result.result.expect("clamped deploy should succeed");
+        
+        let return_data = result.metadata.unwrap().return_data.expect("deployed total is returned");
+        assert_eq!(return_data.program_id, evore::id());
+        assert_eq!(u64::from_le_bytes(return_data.data[..8].try_into().unwrap()), 2_000_000);
+        
+        let (ore_miner_address, _) = miner_pda(managed_miner_auth_addr);
+        let miner_account = context.banks_client.get_account(ore_miner_address).await.unwrap().unwrap();
+        let miner = Miner::try_from_bytes(&miner_account.data).unwrap();
+        assert_eq!(miner.round_id, TEST_ROUND_ID);
+        assert_eq!(&miner.deployed[..5], &[400_000u64; 5]);
+        assert!(miner.deployed[5..].iter().all(|&d| d == 0));
+    }
+
+    /// A repeat deploy in the same round pays no fees, so none are held back from the clamp
+    #[tokio::test]
+    async fn test_clamp_skips_fees_when_already_deployed() {
+        let mut program_test = setup_programs();
+        
+        let deploy_authority = Keypair::new();
+        let manager_address = Keypair::new().pubkey();
+        let auth_id = 0u64;
+        let (managed_miner_auth_addr, _) = managed_miner_auth_pda(manager_address, auth_id);
+        let (deployer_pda_addr, _) = deployer_pda(manager_address);
+        
+        add_manager_account(&mut program_test, manager_address, deploy_authority.pubkey());
+        add_deployer_account(
+            &mut program_test,
+            deployer_pda_addr,
+            manager_address,
+            deploy_authority.pubkey(),
+            500,
+            1000,
+            0,
             0,
-            false,
         );
+        
+        let current_slot = 1000;
+        let _board = setup_deploy_test_accounts(&mut program_test, TEST_ROUND_ID, current_slot, 100);
+        // Already deployed on square 10 this round
+        let mut deployed = [0u64; 25];
+        deployed[10] = 100_000;
+        add_ore_miner_account(
+            &mut program_test,
+            managed_miner_auth_addr,
+            deployed,
+            0, 0,
+            TEST_ROUND_ID - 1,
+            TEST_ROUND_ID,
+        );
+        
+        // Only rent and the checkpoint fee are reserved: all 2_000_000 goes to the deploy
+        let reserved = 890_880 + ore_api::CHECKPOINT_FEE;
+        add_autodeploy_balance(&mut program_test, managed_miner_auth_addr, reserved + 2_000_000);
+        
+        let mut context = program_test.start_with_context().await;
+        let _ = context.warp_to_slot(current_slot + 3);
+        
+        let ix0 = system_instruction::transfer(&context.payer.pubkey(), &deploy_authority.pubkey(), 100_000_000);
+        let blockhash = context.banks_client.get_latest_blockhash().await.unwrap();
+        let tx = Transaction::new_signed_with_payer(&[ix0], Some(&context.payer.pubkey()), &[&context.payer], blockhash);
+        context.banks_client.process_transaction(tx).await.unwrap();
+        
+        let cu_limit_ix = ComputeBudgetInstruction::set_compute_unit_limit(1_400_000);
+        let ix = evore::instruction::mm_autodeploy(
+            deploy_authority.pubkey(),
+            manager_address,
+            auth_id,
+            TEST_ROUND_ID,
+            1_000_000,
+            0b11111,
+        );
+        let blockhash = context.banks_client.get_latest_blockhash().await.unwrap();
+        let tx = Transaction::new_signed_with_payer(
+            &[cu_limit_ix, ix],
+            Some(&deploy_authority.pubkey()),
+            &[&deploy_authority],
+            blockhash
+        );
+        let result = context.banks_client.process_transaction_with_metadata(tx).await.unwrap();
+        result.result.expect("clamped deploy should succeed");
+        
+        let return_data = result.metadata.unwrap().return_data.expect("deployed total is returned");
+        assert_eq!(u64::from_le_bytes(return_data.data[..8].try_into().unwrap()), 2_000_000);
+        
+        let (ore_miner_address, _) = miner_pda(managed_miner_auth_addr);
+        let miner_account = context.banks_client.get_account(ore_miner_address).await.unwrap().unwrap();
+        let miner = Miner::try_from_bytes(&miner_account.data).unwrap();
+        assert_eq!(&miner.deployed[..5], &[400_000u64; 5]);
+        assert_eq!(miner.deployed[10], 100_000);
+    }
+}
+
+// ============================================================================
+// MMAutodeploy squares_mask Tests
+// ============================================================================
+
+mod mm_autodeploy_squares_mask {
+    use super::*;
+    use solana_program_test::BanksClientError;
+    use solana_sdk::{instruction::InstructionError, transaction::TransactionError};
+
+    /// Deploys 100_000 lamports per square of `squares_mask` for a fresh, funded miner
+    async fn autodeploy_mask(squares_mask: u32) -> Result<(), BanksClientError> {
+        let mut program_test = setup_programs();
+
+        let deploy_authority = Keypair::new();
+        let manager_address = Keypair::new().pubkey();
+        let (managed_miner_auth_addr, _) = managed_miner_auth_pda(manager_address, 0);
+        let (deployer_pda_addr, _) = deployer_pda(manager_address);
+
+        add_manager_account(&mut program_test, manager_address, deploy_authority.pubkey());
+        add_deployer_account(&mut program_test, deployer_pda_addr, manager_address, deploy_authority.pubkey(), 0, 0, 0, 0);
+
+        let current_slot = 1000;
+        let _board = setup_deploy_test_accounts(&mut program_test, TEST_ROUND_ID, current_slot, 100);
+        add_ore_miner_account(
+            &mut program_test,
+            managed_miner_auth_addr,
+            [0u64; 25],
+            0, 0,
+            TEST_ROUND_ID - 1,
+            TEST_ROUND_ID - 1,
+        );
+        add_autodeploy_balance(&mut program_test, managed_miner_auth_addr, 10_000_000_000);
+
+        let mut context = program_test.start_with_context().await;
+        let _ = context.warp_to_slot(current_slot + 3);
+
+        let payer = context.payer.pubkey();
+        let fund = [
+            system_instruction::transfer(&payer, &FEE_COLLECTOR, 1_000_000),
+            system_instruction::transfer(&payer, &deploy_authority.pubkey(), 100_000_000),
+        ];
+        let blockhash = context.banks_client.get_latest_blockhash().await.unwrap();
+        let tx = Transaction::new_signed_with_payer(&fund, Some(&payer), &[&context.payer], blockhash);
+        context.banks_client.process_transaction(tx).await.unwrap();
+
+        let cu_limit_ix = ComputeBudgetInstruction::set_compute_unit_limit(1_400_000);
+        let ix = evore::instruction::mm_autodeploy(
+            deploy_authority.pubkey(),
+            manager_address,
+            0,
+            TEST_ROUND_ID,
+            100_000,
+            squares_mask,
+        );
+        let blockhash = context.get_new_latest_blockhash().await.unwrap();
+        let tx = Transaction::new_signed_with_payer(
+            &[cu_limit_ix, ix],
+            Some(&deploy_authority.pubkey()),
+            &[&deploy_authority],
+            blockhash,
+        );
+        context.banks_client.process_transaction(tx).await
+    }
+
+    fn custom_error(error: evore::error::EvoreError) -> TransactionError {
+        TransactionError::InstructionError(1, InstructionError::Custom(error as u32))
+    }
+
+    /// Bit 25 would be a 26th square
+    #[tokio::test]
+    async fn test_mask_above_board_fails() {
+        let err = autodeploy_mask(0x2000000).await;
+        assert_eq!(err.unwrap_err().unwrap(), custom_error(evore::error::EvoreError::InvalidSquaresMask));
+    }
+
+    #[tokio::test]
+    async fn test_zero_mask_fails() {
+        let err = autodeploy_mask(0x0).await;
+        assert_eq!(err.unwrap_err().unwrap(), custom_error(evore::error::EvoreError::NoDeployments));
+    }
+
+    #[tokio::test]
+    async fn test_full_board_mask_succeeds() {
+        autodeploy_mask(0x1FFFFFF).await.expect("all 25 squares is a valid mask");
+    }
+}
+
+// ============================================================================
+// MMFullAutodeploy Tests
+// ============================================================================
+
+mod mm_full_autodeploy {
+    use super::*;
+
+    /// One instruction deploys for every listed auth_id of the manager
+    #[tokio::test]
+    async fn test_multi_auth_id_deploys_every_miner() {
+        let mut program_test = setup_programs();
+        
+        let deploy_authority = Keypair::new();
+        let manager_address = Keypair::new().pubkey();
+        let (deployer_pda_addr, _) = deployer_pda(manager_address);
+        let auth_ids = [0u64, 1, 2];
+        
+        add_manager_account(&mut program_test, manager_address, deploy_authority.pubkey());
+        add_deployer_account(
+            &mut program_test,
+            deployer_pda_addr,
+            manager_address,
+            deploy_authority.pubkey(),
+            0,
+            715,
+            0,
+            0,
+        );
+        
+        let current_slot = 1000;
+        let _board = setup_deploy_test_accounts(&mut program_test, TEST_ROUND_ID, current_slot, 100);
+        for &auth_id in &auth_ids {
+            let (managed_miner_auth_addr, _) = managed_miner_auth_pda(manager_address, auth_id);
+            add_ore_miner_account(
+                &mut program_test,
+                managed_miner_auth_addr,
+                [0u64; 25],
+                0, 0,
+                TEST_ROUND_ID - 1,
+                TEST_ROUND_ID - 1,
+            );
+            add_autodeploy_balance(&mut program_test, managed_miner_auth_addr, 1_000_000_000);
+        }
+        
+        let mut context = program_test.start_with_context().await;
+        let _ = context.warp_to_slot(current_slot + 3);
+        
+        let ix0 = system_instruction::transfer(&context.payer.pubkey(), &FEE_COLLECTOR, 1_000_000);
+        let ix1 = system_instruction::transfer(&context.payer.pubkey(), &deploy_authority.pubkey(), 100_000_000);
+        let blockhash = context.banks_client.get_latest_blockhash().await.unwrap();
+        let tx = Transaction::new_signed_with_payer(&[ix0, ix1], Some(&context.payer.pubkey()), &[&context.payer], blockhash);
+        context.banks_client.process_transaction(tx).await.unwrap();
+        
+        let amount_per_square = 100_000u64;
+        let squares_mask = 0b111u32;
+        let cu_limit_ix = ComputeBudgetInstruction::set_compute_unit_limit(1_400_000);
+        let ix = evore::instruction::mm_full_autodeploy_multi(
+            deploy_authority.pubkey(),
+            manager_address,
+            &auth_ids,
+            TEST_ROUND_ID,
+            TEST_ROUND_ID,
+            amount_per_square,
+            squares_mask,
+        );
+        assert_eq!(ix.accounts.len(), 16 + 4 * 2);
+        
+        let blockhash = context.banks_client.get_latest_blockhash().await.unwrap();
+        let tx = Transaction::new_signed_with_payer(&[cu_limit_ix, ix], Some(&deploy_authority.pubkey()), &[&deploy_authority], blockhash);
+        context.banks_client.process_transaction(tx).await.expect("multi-miner full autodeploy should succeed");
+        
+        for &auth_id in &auth_ids {
+            let (managed_miner_auth_addr, _) = managed_miner_auth_pda(manager_address, auth_id);
+            let account = context.banks_client.get_account(miner_pda(managed_miner_auth_addr).0).await.unwrap().unwrap();
+            let miner = Miner::try_from_bytes(&account.data).unwrap();
+            assert_eq!(miner.round_id, TEST_ROUND_ID, "auth_id {} should be in the current round", auth_id);
+            assert_eq!(miner.deployed[..3], [amount_per_square; 3], "auth_id {} deployed amounts", auth_id);
+            assert!(miner.deployed[3..].iter().all(|&d| d == 0));
+        }
+    }
+
+    /// A repeated auth_id is rejected instead of deploying the same miner twice
+    #[tokio::test]
+    async fn test_multi_auth_id_rejects_duplicates() {
+        use solana_sdk::{instruction::InstructionError, transaction::TransactionError};
+
+        let mut program_test = setup_programs();
+        
+        let deploy_authority = Keypair::new();
+        let manager_address = Keypair::new().pubkey();
+        let (deployer_pda_addr, _) = deployer_pda(manager_address);
+        let (managed_miner_auth_addr, _) = managed_miner_auth_pda(manager_address, 0);
+        
+        add_manager_account(&mut program_test, manager_address, deploy_authority.pubkey());
+        add_deployer_account(&mut program_test, deployer_pda_addr, manager_address, deploy_authority.pubkey(), 0, 715, 0, 0);
+        
+        let current_slot = 1000;
+        let _board = setup_deploy_test_accounts(&mut program_test, TEST_ROUND_ID, current_slot, 100);
+        add_ore_miner_account(&mut program_test, managed_miner_auth_addr, [0u64; 25], 0, 0, TEST_ROUND_ID - 1, TEST_ROUND_ID - 1);
+        add_autodeploy_balance(&mut program_test, managed_miner_auth_addr, 1_000_000_000);
+        
+        let mut context = program_test.start_with_context().await;
+        let _ = context.warp_to_slot(current_slot + 3);
+        
+        let ix0 = system_instruction::transfer(&context.payer.pubkey(), &FEE_COLLECTOR, 1_000_000);
+        let ix1 = system_instruction::transfer(&context.payer.pubkey(), &deploy_authority.pubkey(), 100_000_000);
+        let blockhash = context.banks_client.get_latest_blockhash().await.unwrap();
+        let tx = Transaction::new_signed_with_payer(&[ix0, ix1], Some(&context.payer.pubkey()), &[&context.payer], blockhash);
+        context.banks_client.process_transaction(tx).await.unwrap();
+        
+        let cu_limit_ix = ComputeBudgetInstruction::set_compute_unit_limit(1_400_000);
+        let ix = evore::instruction::mm_full_autodeploy_multi(
+            deploy_authority.pubkey(), manager_address, &[0, 0], TEST_ROUND_ID, TEST_ROUND_ID, 100_000, 0b1,
+        );
+        let blockhash = context.banks_client.get_latest_blockhash().await.unwrap();
+        let tx = Transaction::new_signed_with_payer(&[cu_limit_ix, ix], Some(&deploy_authority.pubkey()), &[&deploy_authority], blockhash);
+        let err = context.banks_client.process_transaction(tx).await.unwrap_err().unwrap();
+        assert_eq!(err, TransactionError::InstructionError(1, InstructionError::InvalidInstructionData));
+    }
+}
+
+// ============================================================================
+// UpdateDeployer Tests
+// ============================================================================
+
+mod update_deployer {
+    use super::*;
+    use evore::consts::UPDATE_MAX_PER_ROUND;
+
+    /// Updating only max_per_round must leave every other deployer field untouched
+    #[tokio::test]
+    async fn test_partial_update_max_per_round_only() {
+        let mut program_test = setup_programs();
+        
+        let manager_authority = Keypair::new();
+        let deploy_authority = Keypair::new();
+        let manager_address = Keypair::new().pubkey();
+        let (deployer_pda_addr, _) = deployer_pda(manager_address);
+        
+        add_manager_account(&mut program_test, manager_address, manager_authority.pubkey());
+        add_deployer_account(
+            &mut program_test,
+            deployer_pda_addr,
+            manager_address,
+            deploy_authority.pubkey(),
+            500,
+            1000,
+            600,
+            2000,
+        );
+        
+        let context = program_test.start_with_context().await;
+        
+        // Values for unselected fields are deliberately different so a full write would show up
+        let ix = evore::instruction::update_deployer_fields(
+            manager_authority.pubkey(),
+            manager_address,
+            deploy_authority.pubkey(),
+            UPDATE_MAX_PER_ROUND,
+            9_999,
+            9_999,
+            9_999,
+            9_999,
+            42_000_000,
+        );
+        let blockhash = context.banks_client.get_latest_blockhash().await.unwrap();
+        let tx = Transaction::new_signed_with_payer(
+            &[ix],
+            Some(&context.payer.pubkey()),
+            &[&context.payer, &manager_authority],
+            blockhash,
+        );
+        context.banks_client.process_transaction(tx).await.expect("partial update should succeed");
+        
+        let deployer_account = context.banks_client.get_account(deployer_pda_addr).await.unwrap().unwrap();
+        let deployer = Deployer::try_from_bytes(&deployer_account.data).unwrap();
+        assert_eq!(deployer.max_per_round, 42_000_000);
+        assert_eq!(deployer.manager_key, manager_address);
+        assert_eq!(deployer.deploy_authority, deploy_authority.pubkey());
+        assert_eq!(deployer.bps_fee, 500);
+        assert_eq!(deployer.flat_fee, 1000);
+        assert_eq!(deployer.expected_bps_fee, 600);
+        assert_eq!(deployer.expected_flat_fee, 2000);
+    }
+
+    /// One BulkUpdateDeployerFees fixes the fees of every deployer the signer deploys for
+    #[tokio::test]
+    async fn test_bulk_update_fees_updates_only_own_deployers() {
+        let mut program_test = setup_programs();
+
+        let deploy_authority = Keypair::new();
+        let other_authority = Keypair::new();
+        let managers: Vec<Pubkey> = (0..3).map(|_| Keypair::new().pubkey()).collect();
+        for (i, &manager_address) in managers.iter().enumerate() {
+            // The last deployer belongs to someone else
+            let authority = if i < 2 { deploy_authority.pubkey() } else { other_authority.pubkey() };
+            add_manager_account(&mut program_test, manager_address, Keypair::new().pubkey());
+            add_deployer_account(&mut program_test, deployer_pda(manager_address).0, manager_address, authority, 500, 1000, 600, 2000);
+        }
+
+        let context = program_test.start_with_context().await;
+
+        let ix = evore::instruction::bulk_update_deployer_fees(deploy_authority.pubkey(), &managers, 0, 1_500);
+        let blockhash = context.banks_client.get_latest_blockhash().await.unwrap();
+        let tx = Transaction::new_signed_with_payer(
+            &[ix],
+            Some(&context.payer.pubkey()),
+            &[&context.payer, &deploy_authority],
+            blockhash,
+        );
+        context.banks_client.process_transaction(tx).await.expect("bulk fee update should succeed");
+
+        let mut fees = Vec::new();
+        for &manager_address in &managers {
+            let account = context.banks_client.get_account(deployer_pda(manager_address).0).await.unwrap().unwrap();
+            let deployer = Deployer::try_from_bytes(&account.data).unwrap();
+            assert_eq!((deployer.expected_bps_fee, deployer.expected_flat_fee), (600, 2000), "expected fees are the manager's");
+            fees.push((deployer.bps_fee, deployer.flat_fee));
+        }
+        assert_eq!(fees, vec![(0, 1_500), (0, 1_500), (500, 1000)]);
+    }
+}
+
+// ============================================================================
+// WithdrawAutodeployBalance Tests
+// ============================================================================
+
+mod withdraw_autodeploy_balance {
+    use super::*;
+
+    /// Profit sweep (withdraw + transfer) moves only the excess above the target to the destination
+    #[tokio::test]
+    async fn test_profit_sweep_withdraws_only_excess() {
+        let mut program_test = setup_programs();
+
+        let authority = Keypair::new();
+        let manager_address = Keypair::new().pubkey();
+        let destination = Keypair::new().pubkey();
+        let auth_id = 0u64;
+        let (managed_miner_auth, _) = managed_miner_auth_pda(manager_address, auth_id);
+
+        add_manager_account(&mut program_test, manager_address, authority.pubkey());
+
+        let context = program_test.start_with_context().await;
+
+        const AUTH_PDA_RENT: u64 = 890_880;
+        let target = 1_000_000_000u64;
+        let profit = 250_000_000u64;
+        let ix0 = system_instruction::transfer(&context.payer.pubkey(), &authority.pubkey(), 1_000_000_000);
+        let ix1 = system_instruction::transfer(&context.payer.pubkey(), &managed_miner_auth, AUTH_PDA_RENT + target + profit);
+        let blockhash = context.banks_client.get_latest_blockhash().await.unwrap();
+        let tx = Transaction::new_signed_with_payer(&[ix0, ix1], Some(&context.payer.pubkey()), &[&context.payer], blockhash);
+        context.banks_client.process_transaction(tx).await.unwrap();
+
+        let authority_before = context.banks_client.get_balance(authority.pubkey()).await.unwrap();
+        let balance = context.banks_client.get_balance(managed_miner_auth).await.unwrap();
+        let excess = balance.saturating_sub(AUTH_PDA_RENT).saturating_sub(target);
+        assert_eq!(excess, profit);
+
+        // Same instruction pair the crank's profit sweeper sends
+        let ixs = [
+            evore::instruction::withdraw_autodeploy_balance(authority.pubkey(), manager_address, auth_id, excess),
+            system_instruction::transfer(&authority.pubkey(), &destination, excess),
+        ];
+        let blockhash = context.banks_client.get_latest_blockhash().await.unwrap();
+        let tx = Transaction::new_signed_with_payer(&ixs, Some(&context.payer.pubkey()), &[&context.payer, &authority], blockhash);
+        context.banks_client.process_transaction(tx).await.expect("profit sweep should succeed");
+
+        let auth_after = context.banks_client.get_balance(managed_miner_auth).await.unwrap();
+        let destination_after = context.banks_client.get_balance(destination).await.unwrap();
+        let authority_after = context.banks_client.get_balance(authority.pubkey()).await.unwrap();
+        assert_eq!(auth_after, AUTH_PDA_RENT + target, "target balance must stay in the auth PDA");
+        assert_eq!(destination_after, profit, "destination receives exactly the excess");
+        assert_eq!(authority_after, authority_before, "authority only passes the excess through");
+    }
+}
+
+// ============================================================================
+// Compound Tests
+// ============================================================================
+
+mod compound {
+    use super::*;
+
+    /// The crank's compounder recycle moves the settled SOL rewards into the autodeploy balance
+    #[tokio::test]
+    async fn test_compound_returns_claimed_sol_to_autodeploy_balance() {
+        let mut program_test = setup_programs();
+
+        let authority = Keypair::new();
+        let deploy_authority = Keypair::new();
+        let manager_address = Keypair::new().pubkey();
+        let auth_id = 0u64;
+        let (managed_miner_auth, _) = managed_miner_auth_pda(manager_address, auth_id);
+        let ore_miner_address = miner_pda(managed_miner_auth);
+        let sol_rewards = 300_000_000u64;
+
+        add_manager_account(&mut program_test, manager_address, authority.pubkey());
+        add_deployer_account(
+            &mut program_test,
+            deployer_pda(manager_address).0,
+            manager_address,
+            deploy_authority.pubkey(),
+            0, 0, 0, 0,
+        );
+        add_ore_miner_account(&mut program_test, managed_miner_auth, [0u64; 25], sol_rewards, 0, TEST_ROUND_ID - 1, TEST_ROUND_ID - 1);
+        add_board_account(&mut program_test, TEST_ROUND_ID, 1, TEST_ROUND_ID + 1000, 0);
+        add_autodeploy_balance(&mut program_test, managed_miner_auth, 1_000_000_000);
+
+        let context = program_test.start_with_context().await;
+
+        // The ORE miner pays out the rewards it holds
+        let ix0 = system_instruction::transfer(&context.payer.pubkey(), &ore_miner_address.0, sol_rewards);
+        let ix1 = system_instruction::transfer(&context.payer.pubkey(), &deploy_authority.pubkey(), 1_000_000_000);
+        let blockhash = context.banks_client.get_latest_blockhash().await.unwrap();
+        let tx = Transaction::new_signed_with_payer(&[ix0, ix1], Some(&context.payer.pubkey()), &[&context.payer], blockhash);
+        context.banks_client.process_transaction(tx).await.unwrap();
+
+        let balance_before = context.banks_client.get_balance(managed_miner_auth).await.unwrap();
+
+        let ix = evore::instruction::recycle_sol(deploy_authority.pubkey(), manager_address, auth_id);
+        let blockhash = context.banks_client.get_latest_blockhash().await.unwrap();
+        let tx = Transaction::new_signed_with_payer(&[ix], Some(&context.payer.pubkey()), &[&context.payer, &deploy_authority], blockhash);
+        context.banks_client.process_transaction(tx).await.expect("compound should succeed");
+
+        let balance_after = context.banks_client.get_balance(managed_miner_auth).await.unwrap();
+        assert_eq!(balance_after, balance_before + sol_rewards, "claimed SOL must land in the autodeploy balance");
+
+        let miner_account = context.banks_client.get_account(ore_miner_address.0).await.unwrap().unwrap();
+        let miner = Miner::try_from_bytes(&miner_account.data).unwrap();
+        assert_eq!(miner.rewards_sol, 0, "rewards are fully claimed");
+    }
+}
+
+// ============================================================================
+// MMPreviewRecycle Tests
+// ============================================================================
+
+mod preview_recycle {
+    use super::*;
+    use evore::instruction::{mm_preview_recycle, preview_recycle_amount};
+
+    /// Returns the previewed amount for auth_id 0 and the managed_miner_auth / ore miner balances after
+    async fn preview(rewards_sol: Option<u64>) -> (u64, u64, Option<u64>) {
+        let mut program_test = setup_programs();
+        let authority = Keypair::new();
+        let manager_address = Keypair::new().pubkey();
+        let (managed_miner_auth_addr, _) = managed_miner_auth_pda(manager_address, 0);
+
+        add_manager_account(&mut program_test, manager_address, authority.pubkey());
+        add_autodeploy_balance(&mut program_test, managed_miner_auth_addr, 1_000_000_000);
+        if let Some(rewards_sol) = rewards_sol {
+            add_ore_miner_account(&mut program_test, managed_miner_auth_addr, [0u64; 25], rewards_sol, 0, TEST_ROUND_ID, TEST_ROUND_ID);
+        }
+
+        let context = program_test.start_with_context().await;
+        let ore_miner = miner_pda(managed_miner_auth_addr).0;
+        let miner_lamports_before = context.banks_client.get_balance(ore_miner).await.unwrap();
+        if let Some(rewards_sol) = rewards_sol {
+            let account = context.banks_client.get_account(ore_miner).await.unwrap().unwrap();
+            assert_eq!(Miner::try_from_bytes(&account.data).unwrap().rewards_sol, rewards_sol);
+        }
+
+        // Anyone can pay for the preview; it needs no manager or deploy authority
+        let payer = context.payer.insecure_clone();
+        let ix = mm_preview_recycle(payer.pubkey(), manager_address, 0);
+        let tx = Transaction::new_signed_with_payer(&[ix], Some(&payer.pubkey()), &[&payer], context.last_blockhash);
+        let result = context.banks_client.process_transaction_with_metadata(tx).await.unwrap();
+        result.result.expect("preview should succeed");
+
+        let return_data = result.metadata.unwrap().return_data.expect("preview sets return data");
+        assert_eq!(return_data.program_id, evore::id());
+        let amount = preview_recycle_amount(&return_data.data).expect("return data is a u64");
+
+        assert_eq!(context.banks_client.get_balance(ore_miner).await.unwrap(), miner_lamports_before, "no lamports leave the miner");
+        let auth_balance = context.banks_client.get_balance(managed_miner_auth_addr).await.unwrap();
+        let miner_rewards = context.banks_client.get_account(ore_miner).await.unwrap()
+            .map(|a| Miner::try_from_bytes(&a.data).unwrap().rewards_sol);
+        (amount, auth_balance, miner_rewards)
+    }
+
+    #[tokio::test]
+    async fn test_preview_returns_rewards_sol_without_claiming() {
+        let (amount, auth_balance, miner_rewards) = preview(Some(123_456_789)).await;
+        assert_eq!(amount, 123_456_789);
+        assert_eq!(auth_balance, 1_000_000_000, "nothing is recycled into managed_miner_auth");
+        assert_eq!(miner_rewards, Some(123_456_789), "rewards_sol stays claimable");
+    }
+
+    #[tokio::test]
+    async fn test_preview_without_miner_returns_zero() {
+        let (amount, _, miner_rewards) = preview(None).await;
+        assert_eq!(amount, 0);
+        assert_eq!(miner_rewards, None);
+    }
+
+    #[test]
+    fn test_preview_recycle_amount_rejects_wrong_length() {
+        assert_eq!(preview_recycle_amount(&42u64.to_le_bytes()), Some(42));
+        assert!(preview_recycle_amount(&[0u8; 4]).is_none());
+        assert!(preview_recycle_amount(&[]).is_none());
+    }
+}
+
+// ============================================================================
+// MMStatus Tests
+// ============================================================================
+
+mod mm_status {
+    use super::*;
+    use evore::instruction::mm_status;
+    use evore::view::MMStatus;
+
+    /// Simulates MMStatus for auth_id 0 of a manager whose managed_miner_auth holds 1 SOL
+    async fn simulate_status(miner: Option<([u64; 25], u64, u64, u64, u64)>) -> MMStatus {
+        let mut program_test = setup_programs();
+        let authority = Keypair::new();
+        let manager_address = Keypair::new().pubkey();
+        let (managed_miner_auth_addr, _) = managed_miner_auth_pda(manager_address, 0);
+
+        add_manager_account(&mut program_test, manager_address, authority.pubkey());
+        add_autodeploy_balance(&mut program_test, managed_miner_auth_addr, 1_000_000_000);
+        if let Some((deployed, rewards_sol, rewards_ore, checkpoint_id, round_id)) = miner {
+            add_ore_miner_account(&mut program_test, managed_miner_auth_addr, deployed, rewards_sol, rewards_ore, checkpoint_id, round_id);
+        }
+
+        let context = program_test.start_with_context().await;
+        let payer = context.payer.insecure_clone();
+        let ix = mm_status(payer.pubkey(), manager_address, 0);
+        let tx = Transaction::new_signed_with_payer(&[ix], Some(&payer.pubkey()), &[&payer], context.last_blockhash);
+        let simulation = context.banks_client.simulate_transaction(tx).await.unwrap();
+        simulation.result.expect("simulation should succeed").expect("status should succeed");
+
+        let return_data = simulation.simulation_details.unwrap().return_data.expect("status sets return data");
+        assert_eq!(return_data.program_id, evore::id());
+        let status = MMStatus::from_return_data(&return_data.data).expect("return data is an MMStatus");
+
+        let auth_balance = context.banks_client.get_balance(managed_miner_auth_addr).await.unwrap();
+        assert_eq!(status.balance, auth_balance);
+        status
+    }
+
+    #[tokio::test]
+    async fn test_status_matches_seeded_miner() {
+        let mut deployed = [0u64; 25];
+        deployed[0] = 10_000;
+        deployed[7] = 20_000;
+        deployed[24] = 30_000;
+
+        let status = simulate_status(Some((deployed, 123_456_789, 42_000, TEST_ROUND_ID - 1, TEST_ROUND_ID))).await;
+        assert_eq!(
+            status,
+            MMStatus {
+                balance: 1_000_000_000,
+                round_id: TEST_ROUND_ID,
+                checkpoint_id: TEST_ROUND_ID - 1,
+                rewards_sol: 123_456_789,
+                rewards_ore: 42_000,
+                deployed_count: 3,
+            }
+        );
+        assert!(status.needs_checkpoint());
+    }
+
+    #[tokio::test]
+    async fn test_status_without_miner_reports_only_balance() {
+        let status = simulate_status(None).await;
+        assert_eq!(status, MMStatus { balance: 1_000_000_000, ..MMStatus::default() });
+        assert!(!status.needs_checkpoint());
+    }
+
+    #[test]
+    fn test_status_from_return_data_rejects_wrong_length() {
+        let status = MMStatus { balance: 1, round_id: 2, checkpoint_id: 2, rewards_sol: 3, rewards_ore: 4, deployed_count: 5 };
+        let bytes = bytemuck::bytes_of(&status);
+        assert_eq!(MMStatus::from_return_data(bytes), Some(status));
+        assert!(MMStatus::from_return_data(&bytes[..bytes.len() - 1]).is_none());
+        assert!(MMStatus::from_return_data(&[]).is_none());
+    }
+}
+
+// ============================================================================
+// MigrateManager Tests
+// ============================================================================
+
+mod migrate_manager {
+    use super::*;
+
+    /// Pre-reserved layout: discriminator + authority only
+    fn add_legacy_manager_account(program_test: &mut ProgramTest, manager_address: Pubkey, authority: Pubkey) {
+        let mut data = (EvoreAccount::Manager as u64).to_le_bytes().to_vec();
+        data.extend_from_slice(authority.as_ref());
+        assert_eq!(data.len(), evore::state::LEGACY_MANAGER_SIZE);
+        program_test.add_account(
+            manager_address,
+            Account {
+                lamports: Rent::default().minimum_balance(data.len()),
+                data,
+                owner: evore::id(),
+                executable: false,
+                rent_epoch: 0,
+            },
+        );
+    }
+
+    /// A legacy 40-byte manager grows to the current size and keeps its authority
+    #[tokio::test]
+    async fn test_migrate_legacy_manager_preserves_authority() {
+        let mut program_test = setup_programs();
+
+        let authority = Keypair::new();
+        let manager_address = Keypair::new().pubkey();
+        add_legacy_manager_account(&mut program_test, manager_address, authority.pubkey());
+
+        let mut context = program_test.start_with_context().await;
+
+        let fund = system_instruction::transfer(&context.payer.pubkey(), &authority.pubkey(), 1_000_000_000);
+        let ix = evore::instruction::migrate_manager(authority.pubkey(), manager_address);
+        let blockhash = context.banks_client.get_latest_blockhash().await.unwrap();
+        let tx = Transaction::new_signed_with_payer(&[fund, ix], Some(&context.payer.pubkey()), &[&context.payer, &authority], blockhash);
+        context.banks_client.process_transaction(tx).await.expect("migrate should succeed");
+
+        let account = context.banks_client.get_account(manager_address).await.unwrap().unwrap();
+        let new_size = 8 + std::mem::size_of::<Manager>();
+        assert_eq!(account.data.len(), new_size);
+        assert!(account.lamports >= Rent::default().minimum_balance(new_size), "must stay rent-exempt");
+
+        let manager = Manager::try_from_bytes(&account.data).unwrap();
+        assert_eq!(manager.authority, authority.pubkey());
+        assert_eq!(manager.max_deploy_per_tx, 0, "migrated managers start without a deploy ceiling");
+        assert_eq!(manager.pending_authority, Pubkey::default(), "migrated managers have no pending transfer");
+        assert_eq!(manager.reserved, [0; 3]);
+
+        // Migrating twice is rejected
+        let ix = evore::instruction::migrate_manager(authority.pubkey(), manager_address);
+        let blockhash = context.get_new_latest_blockhash().await.unwrap();
+        let tx = Transaction::new_signed_with_payer(&[ix], Some(&context.payer.pubkey()), &[&context.payer, &authority], blockhash);
+        assert!(context.banks_client.process_transaction(tx).await.is_err());
+    }
+
+    /// An unmigrated manager keeps working for instructions that only read it
+    #[tokio::test]
+    async fn test_unmigrated_manager_still_usable() {
+        let mut program_test = setup_programs();
+
+        let authority = Keypair::new();
+        let attacker = Keypair::new();
+        let manager_address = Keypair::new().pubkey();
+        let (old_auth, _) = managed_miner_auth_pda(manager_address, 0);
+        let (new_auth, _) = managed_miner_auth_pda(manager_address, 1);
+        add_legacy_manager_account(&mut program_test, manager_address, authority.pubkey());
+        add_autodeploy_balance(&mut program_test, old_auth, 3_000_000_000);
+
+        let context = program_test.start_with_context().await;
+        let old_before = context.banks_client.get_balance(old_auth).await.unwrap();
+
+        // The authority is still read from the legacy bytes
+        let ix = evore::instruction::migrate_auth_id(attacker.pubkey(), manager_address, 0, 1);
+        let blockhash = context.banks_client.get_latest_blockhash().await.unwrap();
+        let tx = Transaction::new_signed_with_payer(&[ix], Some(&context.payer.pubkey()), &[&context.payer, &attacker], blockhash);
+        assert!(context.banks_client.process_transaction(tx).await.is_err());
+
+        let ix = evore::instruction::migrate_auth_id(authority.pubkey(), manager_address, 0, 1);
+        let tx = Transaction::new_signed_with_payer(&[ix], Some(&context.payer.pubkey()), &[&context.payer, &authority], blockhash);
+        context.banks_client.process_transaction(tx).await.expect("legacy manager should load");
+
+        assert_eq!(context.banks_client.get_balance(new_auth).await.unwrap(), old_before);
+        let account = context.banks_client.get_account(manager_address).await.unwrap().unwrap();
+        assert_eq!(account.data.len(), evore::state::LEGACY_MANAGER_SIZE, "reading never resizes");
+    }
+}
+
+// ============================================================================
+// MigrateAuthId Tests
+// ============================================================================
+
+mod migrate_auth_id {
+    use super::*;
+
+    /// The full old auth PDA balance lands in the new auth PDA
+    #[tokio::test]
+    async fn test_migrate_auth_id_moves_balance() {
+        let mut program_test = setup_programs();
+
+        let authority = Keypair::new();
+        let manager_address = Keypair::new().pubkey();
+        let (old_auth, _) = managed_miner_auth_pda(manager_address, 0);
+        let (new_auth, _) = managed_miner_auth_pda(manager_address, 1);
+
+        add_manager_account(&mut program_test, manager_address, authority.pubkey());
+        add_autodeploy_balance(&mut program_test, old_auth, 3_000_000_000);
+
+        let context = program_test.start_with_context().await;
+
+        let old_before = context.banks_client.get_balance(old_auth).await.unwrap();
+        assert!(old_before > 0);
+
+        let ix = evore::instruction::migrate_auth_id(authority.pubkey(), manager_address, 0, 1);
+        let blockhash = context.banks_client.get_latest_blockhash().await.unwrap();
+        let tx = Transaction::new_signed_with_payer(&[ix], Some(&context.payer.pubkey()), &[&context.payer, &authority], blockhash);
+        context.banks_client.process_transaction(tx).await.expect("migrate should succeed");
+
+        assert_eq!(context.banks_client.get_balance(old_auth).await.unwrap(), 0);
+        assert_eq!(context.banks_client.get_balance(new_auth).await.unwrap(), old_before);
+    }
+
+    /// Only the manager authority can move the balance
+    #[tokio::test]
+    async fn test_migrate_auth_id_wrong_authority() {
+        let mut program_test = setup_programs();
+
+        let authority = Keypair::new();
+        let attacker = Keypair::new();
+        let manager_address = Keypair::new().pubkey();
+        let (old_auth, _) = managed_miner_auth_pda(manager_address, 0);
+
+        add_manager_account(&mut program_test, manager_address, authority.pubkey());
+        add_autodeploy_balance(&mut program_test, old_auth, 3_000_000_000);
+
+        let context = program_test.start_with_context().await;
+
+        let ix = evore::instruction::migrate_auth_id(attacker.pubkey(), manager_address, 0, 1);
+        let blockhash = context.banks_client.get_latest_blockhash().await.unwrap();
+        let tx = Transaction::new_signed_with_payer(&[ix], Some(&context.payer.pubkey()), &[&context.payer, &attacker], blockhash);
+        assert!(context.banks_client.process_transaction(tx).await.is_err());
+    }
+}
+
+// ============================================================================
+// MMCreateMiner Tests
+// ============================================================================
+
+mod test_ore_automate_direct {
+    use super::*;
+    use solana_sdk::pubkey::Pubkey;
+
+    /// Test calling ORE automate directly (open then close) to verify the flow works
+    #[tokio::test]
+    async fn test_automate_open_close() {
+        let mut program_test = setup_programs();
+        
+        let authority = Keypair::new();
+        
+        // Fund authority
+        program_test.add_account(
+            authority.pubkey(),
+            Account {
+                lamports: 10_000_000_000, // 10 SOL
+                data: vec![],
+                owner: solana_sdk::system_program::id(),
+                executable: false,
+                rent_epoch: 0,
+            },
+        );
+
+        let ctx = program_test.start_with_context().await;
+
+        let (miner_address, _) = miner_pda(authority.pubkey());
+        let automation_address = ore_api::automation_pda(authority.pubkey()).0;
+
+        // Step 1: Open automation (creates miner)
+        // executor = authority (opens)
+        let open_ix = ore_api::automate(
+            authority.pubkey(),
+            0,
+            0,
+            authority.pubkey(), // executor = signer opens
+            0,
+            0,
+            0,
+            false,
+        );
+
+        let open_tx = Transaction::new_signed_with_payer(
+            &[open_ix],
+            Some(&authority.pubkey()),
+            &[&authority],
+            ctx.last_blockhash,
+        );
+
+        ctx.banks_client.process_transaction(open_tx).await.unwrap();
+
+        // Verify miner and automation exist
+        let miner_account = ctx.banks_client.get_account(miner_address).await.unwrap();
+        assert!(miner_account.is_some(), "Miner account should exist after open");
+        
+        let automation_account = ctx.banks_client.get_account(automation_address).await.unwrap();
+        assert!(automation_account.is_some(), "Automation account should exist after open");
+
+        // Step 2: Close automation
+        // executor = Pubkey::default() (closes)
+        let close_ix = ore_api::automate(
+            authority.pubkey(),
+            0,
+            0,
+            Pubkey::default(), // executor = default closes
+            0,
+            0,
+            0,
+            false,
+        );
+
+        let close_tx = Transaction::new_signed_with_payer(
+            &[close_ix],
+            Some(&authority.pubkey()),
+            &[&authority],
+            ctx.last_blockhash,
+        );
+
+        ctx.banks_client.process_transaction(close_tx).await.unwrap();
+
+        // Verify miner still exists and automation is closed
+        let miner_account_final = ctx.banks_client.get_account(miner_address).await.unwrap();
+        assert!(miner_account_final.is_some(), "Miner account should still exist");
+        
+        let automation_account_final = ctx.banks_client.get_account(automation_address).await.unwrap();
+        assert!(automation_account_final.is_none(), "Automation account should be closed");
+    }
+}
+
+mod test_mm_create_miner {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_success() {
+        let mut program_test = setup_programs();
+        
+        // Setup manager
+        let manager = Keypair::new();
+        let authority = Keypair::new();
+        add_manager_account(&mut program_test, manager.pubkey(), authority.pubkey());
+        
+        let auth_id = 0u64;
+        let (managed_miner_auth, _) = managed_miner_auth_pda(manager.pubkey(), auth_id);
+        
+        // Fund authority to pay for transaction and miner rent
+        program_test.add_account(
+            authority.pubkey(),
+            Account {
+                lamports: 10_000_000_000, // 10 SOL
+                data: vec![],
+                owner: solana_sdk::system_program::id(),
+                executable: false,
+                rent_epoch: 0,
+            },
+        );
+
+        let ctx = program_test.start_with_context().await;
+
+        // Build and send MMCreateMiner instruction
+        let ix = evore::instruction::mm_create_miner(
+            authority.pubkey(),
+            manager.pubkey(),
+            auth_id,
+        );
+
+        let tx = Transaction::new_signed_with_payer(
+            &[ix],
+            Some(&authority.pubkey()),
+            &[&authority],
+            ctx.last_blockhash,
+        );
+
+        ctx.banks_client.process_transaction(tx).await.unwrap();
+
+        // Verify miner account was created
+        let (miner_address, _) = miner_pda(managed_miner_auth);
+        let miner_account = ctx.banks_client.get_account(miner_address).await.unwrap();
+        assert!(miner_account.is_some(), "Miner account should exist");
+        
+        // Verify automation account was closed
+        let automation_address = ore_api::automation_pda(managed_miner_auth).0;
+        let automation_account = ctx.banks_client.get_account(automation_address).await.unwrap();
+        assert!(automation_account.is_none(), "Automation account should be closed");
+    }
+
+    #[tokio::test]
+    async fn test_second_call_is_noop_success() {
+        let mut program_test = setup_programs();
+        
+        let manager = Keypair::new();
+        let authority = Keypair::new();
+        add_manager_account(&mut program_test, manager.pubkey(), authority.pubkey());
+        
+        let auth_id = 0u64;
+        let (managed_miner_auth, _) = managed_miner_auth_pda(manager.pubkey(), auth_id);
+        
+        program_test.add_account(
+            authority.pubkey(),
+            Account {
+                lamports: 10_000_000_000, // 10 SOL
+                data: vec![],
+                owner: solana_sdk::system_program::id(),
+                executable: false,
+                rent_epoch: 0,
+            },
+        );
+
+        let mut ctx = program_test.start_with_context().await;
+
+        let ix = evore::instruction::mm_create_miner(authority.pubkey(), manager.pubkey(), auth_id);
+        let tx = Transaction::new_signed_with_payer(&[ix], Some(&authority.pubkey()), &[&authority], ctx.last_blockhash);
+        ctx.banks_client.process_transaction(tx).await.unwrap();
+
+        let (miner_address, _) = miner_pda(managed_miner_auth);
+        let miner_before = ctx.banks_client.get_account(miner_address).await.unwrap().expect("miner created");
+        let authority_before = ctx.banks_client.get_balance(authority.pubkey()).await.unwrap();
+
+        // Same instruction again, now that the miner exists
+        let ix = evore::instruction::mm_create_miner(authority.pubkey(), manager.pubkey(), auth_id);
+        let blockhash = ctx.get_new_latest_blockhash().await.unwrap();
+        let tx = Transaction::new_signed_with_payer(&[ix], Some(&authority.pubkey()), &[&authority], blockhash);
+        ctx.banks_client.process_transaction(tx).await.expect("second mm_create_miner should succeed");
+
+        // Miner untouched and nothing but the tx fee charged
+        let miner_after = ctx.banks_client.get_account(miner_address).await.unwrap().expect("miner still exists");
+        assert_eq!(miner_after.data, miner_before.data);
+        assert_eq!(miner_after.lamports, miner_before.lamports);
+        let authority_after = ctx.banks_client.get_balance(authority.pubkey()).await.unwrap();
+        assert_eq!(authority_before - authority_after, 5_000);
+
+        let automation_address = ore_api::automation_pda(managed_miner_auth).0;
+        assert!(ctx.banks_client.get_account(automation_address).await.unwrap().is_none());
+    }
+}
+
+// ============================================================================
+// WithdrawTokens Tests
+// ============================================================================
+
+mod withdraw_tokens {
+    use super::*;
+    use solana_program::program_pack::Pack;
+    use solana_sdk::{instruction::InstructionError, transaction::TransactionError};
+    use spl_token::state::Mint as SplMint;
+    use spl_token::state::Account as SplTokenAccount;
+
+    /// Helper: add a pre-serialized SPL Mint account to ProgramTest
+    fn add_spl_mint_account(program_test: &mut ProgramTest, mint_address: Pubkey) {
+        add_mint_account_for_program(program_test, mint_address, spl_token::id());
+    }
+
+    /// Helper: add a mint owned by `token_program`; base Token-2022 mints share the SPL layout
+    fn add_mint_account_for_program(program_test: &mut ProgramTest, mint_address: Pubkey, token_program: Pubkey) {
+        let mut mint_data = vec![0u8; SplMint::LEN];
+        let mint_state = SplMint {
+            mint_authority: solana_program::program_option::COption::None,
+            supply: 1_000_000_000,
+            decimals: 9,
+            is_initialized: true,
+            freeze_authority: solana_program::program_option::COption::None,
+        };
+        SplMint::pack(mint_state, &mut mint_data).unwrap();
+
+        program_test.add_account(
+            mint_address,
+            Account {
+                lamports: Rent::default().minimum_balance(SplMint::LEN),
+                data: mint_data,
+                owner: token_program,
+                executable: false,
+                rent_epoch: 0,
+            },
+        );
+    }
+
+    /// Helper: add a pre-serialized SPL Token Account (ATA) with a given balance
+    fn add_spl_token_account(
+        program_test: &mut ProgramTest,
+        ata_address: Pubkey,
+        mint: Pubkey,
+        owner: Pubkey,
+        amount: u64,
+    ) {
+        add_token_account_for_program(program_test, ata_address, mint, owner, amount, spl_token::id());
+    }
+
+    /// Helper: add a token account owned by `token_program` with a given balance
+    fn add_token_account_for_program(
+        program_test: &mut ProgramTest,
+        ata_address: Pubkey,
+        mint: Pubkey,
+        owner: Pubkey,
+        amount: u64,
+        token_program: Pubkey,
+    ) {
+        let mut token_data = vec![0u8; SplTokenAccount::LEN];
+        let token_state = SplTokenAccount {
+            mint,
+            owner,
+            amount,
+            delegate: solana_program::program_option::COption::None,
+            state: spl_token::state::AccountState::Initialized,
+            is_native: solana_program::program_option::COption::None,
+            delegated_amount: 0,
+            close_authority: solana_program::program_option::COption::None,
+        };
+        SplTokenAccount::pack(token_state, &mut token_data).unwrap();
+
+        program_test.add_account(
+            ata_address,
+            Account {
+                lamports: Rent::default().minimum_balance(SplTokenAccount::LEN),
+                data: token_data,
+                owner: token_program,
+                executable: false,
+                rent_epoch: 0,
+            },
+        );
+    }
+
+    #[tokio::test]
+    async fn test_withdraw_tokens_success() {
+        let mut program_test = setup_programs();
+
+        // Setup authority and manager
+        let authority = Keypair::new();
+        let manager = Keypair::new();
+        let manager_address = manager.pubkey();
+        let auth_id = 0u64;
+
+        add_manager_account(&mut program_test, manager_address, authority.pubkey());
+
+        // Create a test SPL mint
+        let mint_keypair = Keypair::new();
+        let mint_address = mint_keypair.pubkey();
+        add_spl_mint_account(&mut program_test, mint_address);
+
+        // Derive managed_miner_auth PDA
+        let (managed_miner_auth_address, _bump) = managed_miner_auth_pda(manager_address, auth_id);
+
+        // Create source ATA (managed_miner_auth's token account) with balance
+        let source_ata = spl_associated_token_account::get_associated_token_address(
+            &managed_miner_auth_address,
+            &mint_address,
+        );
+        let token_amount = 500_000_000u64; // 0.5 tokens
+        add_spl_token_account(
+            &mut program_test,
+            source_ata,
+            mint_address,
+            managed_miner_auth_address,
+            token_amount,
+        );
+
+        // Fund authority
+        program_test.add_account(
+            authority.pubkey(),
+            Account {
+                lamports: 10_000_000_000,
+                data: vec![],
+                owner: solana_sdk::system_program::id(),
+                executable: false,
+                rent_epoch: 0,
+            },
+        );
+
+        let ctx = program_test.start_with_context().await;
+
+        // Build and send WithdrawTokens instruction
+        let ix = evore::instruction::withdraw_tokens(
+            authority.pubkey(),
+            manager_address,
+            auth_id,
+            mint_address,
+        );
+
+        let tx = Transaction::new_signed_with_payer(
+            &[ix],
+            Some(&authority.pubkey()),
+            &[&authority],
+            ctx.last_blockhash,
+        );
+
+        ctx.banks_client.process_transaction(tx).await.unwrap();
+
+        // Verify destination ATA was created and received all tokens
+        let destination_ata = spl_associated_token_account::get_associated_token_address(
+            &authority.pubkey(),
+            &mint_address,
+        );
+        let dest_account = ctx
+            .banks_client
+            .get_account(destination_ata)
+            .await
+            .unwrap()
+            .expect("destination ATA should exist");
+
+        let dest_token = SplTokenAccount::unpack(&dest_account.data).unwrap();
+        assert_eq!(
+            dest_token.amount, token_amount,
+            "destination ATA should have the full token balance"
+        );
+
+        // Verify source ATA is now empty
+        let src_account = ctx
+            .banks_client
+            .get_account(source_ata)
+            .await
+            .unwrap()
+            .expect("source ATA should still exist");
+
+        let src_token = SplTokenAccount::unpack(&src_account.data).unwrap();
+        assert_eq!(src_token.amount, 0, "source ATA should be empty after withdrawal");
+    }
+
+    /// With a destination, tokens land in the destination's (newly created) ATA, not the signer's.
+    /// ORE claims use the same destination handling; the claim CPI itself can't run against the
+    /// fixture accounts here, so the token path is exercised through WithdrawTokens.
+    #[tokio::test]
+    async fn test_withdraw_tokens_to_destination() {
+        let mut program_test = setup_programs();
+
+        let authority = Keypair::new();
+        let manager_address = Keypair::new().pubkey();
+        let destination = Keypair::new().pubkey();
+        let auth_id = 0u64;
+
+        add_manager_account(&mut program_test, manager_address, authority.pubkey());
+
+        let mint_address = Keypair::new().pubkey();
+        add_spl_mint_account(&mut program_test, mint_address);
+
+        let (managed_miner_auth_address, _bump) = managed_miner_auth_pda(manager_address, auth_id);
+        let source_ata = spl_associated_token_account::get_associated_token_address(
+            &managed_miner_auth_address,
+            &mint_address,
+        );
+        let token_amount = 750_000_000u64;
+        add_spl_token_account(
+            &mut program_test,
+            source_ata,
+            mint_address,
+            managed_miner_auth_address,
+            token_amount,
+        );
+
+        program_test.add_account(
+            authority.pubkey(),
+            Account {
+                lamports: 10_000_000_000,
+                data: vec![],
+                owner: solana_sdk::system_program::id(),
+                executable: false,
+                rent_epoch: 0,
+            },
+        );
+
+        let ctx = program_test.start_with_context().await;
+
+        let ix = evore::instruction::withdraw_tokens_to(
+            authority.pubkey(),
+            manager_address,
+            auth_id,
+            mint_address,
+            destination,
+        );
+        let tx = Transaction::new_signed_with_payer(
+            &[ix],
+            Some(&authority.pubkey()),
+            &[&authority],
+            ctx.last_blockhash,
+        );
+        ctx.banks_client.process_transaction(tx).await.unwrap();
+
+        let destination_ata = spl_associated_token_account::get_associated_token_address(
+            &destination,
+            &mint_address,
+        );
+        let dest_account = ctx
+            .banks_client
+            .get_account(destination_ata)
+            .await
+            .unwrap()
+            .expect("destination ATA should be created");
+        let dest_token = SplTokenAccount::unpack(&dest_account.data).unwrap();
+        assert_eq!(dest_token.owner, destination);
+        assert_eq!(dest_token.amount, token_amount, "destination ATA should receive the full balance");
+
+        let signer_ata = spl_associated_token_account::get_associated_token_address(
+            &authority.pubkey(),
+            &mint_address,
+        );
+        assert!(
+            ctx.banks_client.get_account(signer_ata).await.unwrap().is_none(),
+            "signer ATA should not be created"
+        );
+    }
+
+    /// A Token-2022 mint routes the transfer and the destination ATA creation through Token-2022
+    #[tokio::test]
+    async fn test_withdraw_tokens_token_2022() {
+        let mut program_test = setup_programs();
+
+        let authority = Keypair::new();
+        let manager_address = Keypair::new().pubkey();
+        let auth_id = 0u64;
+        let token_program = spl_token_2022::id();
+
+        add_manager_account(&mut program_test, manager_address, authority.pubkey());
+
+        let mint_address = Keypair::new().pubkey();
+        add_mint_account_for_program(&mut program_test, mint_address, token_program);
+
+        let (managed_miner_auth_address, _bump) = managed_miner_auth_pda(manager_address, auth_id);
+        let source_ata = spl_associated_token_account::get_associated_token_address_with_program_id(
+            &managed_miner_auth_address,
+            &mint_address,
+            &token_program,
+        );
+        let token_amount = 250_000_000u64;
+        add_token_account_for_program(
+            &mut program_test,
+            source_ata,
+            mint_address,
+            managed_miner_auth_address,
+            token_amount,
+            token_program,
+        );
+
+        program_test.add_account(
+            authority.pubkey(),
+            Account {
+                lamports: 10_000_000_000,
+                data: vec![],
+                owner: solana_sdk::system_program::id(),
+                executable: false,
+                rent_epoch: 0,
+            },
+        );
+
+        let ctx = program_test.start_with_context().await;
+
+        let ix = evore::instruction::withdraw_tokens_with_token_program(
+            authority.pubkey(),
+            manager_address,
+            auth_id,
+            mint_address,
+            authority.pubkey(),
+            token_program,
+        );
+        let tx = Transaction::new_signed_with_payer(
+            &[ix],
+            Some(&authority.pubkey()),
+            &[&authority],
+            ctx.last_blockhash,
+        );
+        ctx.banks_client.process_transaction(tx).await.unwrap();
+
+        let destination_ata = spl_associated_token_account::get_associated_token_address_with_program_id(
+            &authority.pubkey(),
+            &mint_address,
+            &token_program,
+        );
+        let dest_account = ctx
+            .banks_client
+            .get_account(destination_ata)
+            .await
+            .unwrap()
+            .expect("destination ATA should be created");
+        assert_eq!(dest_account.owner, token_program, "destination ATA should belong to Token-2022");
+        let dest_token = SplTokenAccount::unpack(&dest_account.data[..SplTokenAccount::LEN]).unwrap();
+        assert_eq!(dest_token.amount, token_amount, "destination ATA should receive the full balance");
+
+        let src_account = ctx.banks_client.get_account(source_ata).await.unwrap().unwrap();
+        let src_token = SplTokenAccount::unpack(&src_account.data).unwrap();
+        assert_eq!(src_token.amount, 0, "source ATA should be empty after withdrawal");
+    }
+
+    /// Passing classic SPL Token for a Token-2022 mint is rejected
+    #[tokio::test]
+    async fn test_withdraw_tokens_token_program_mismatch() {
+        let mut program_test = setup_programs();
+
+        let authority = Keypair::new();
+        let manager_address = Keypair::new().pubkey();
+        let auth_id = 0u64;
+
+        add_manager_account(&mut program_test, manager_address, authority.pubkey());
+
+        let mint_address = Keypair::new().pubkey();
+        add_mint_account_for_program(&mut program_test, mint_address, spl_token_2022::id());
+
+        program_test.add_account(
+            authority.pubkey(),
+            Account {
+                lamports: 10_000_000_000,
+                data: vec![],
+                owner: solana_sdk::system_program::id(),
+                executable: false,
+                rent_epoch: 0,
+            },
+        );
+
+        let ctx = program_test.start_with_context().await;
+
+        let ix = evore::instruction::withdraw_tokens(
+            authority.pubkey(),
+            manager_address,
+            auth_id,
+            mint_address,
+        );
+        let tx = Transaction::new_signed_with_payer(
+            &[ix],
+            Some(&authority.pubkey()),
+            &[&authority],
+            ctx.last_blockhash,
+        );
+        let err = ctx.banks_client.process_transaction(tx).await.unwrap_err().unwrap();
+        assert_eq!(
+            err,
+            TransactionError::InstructionError(0, InstructionError::IncorrectProgramId),
+        );
+    }
+
+    #[tokio::test]
+    async fn test_withdraw_tokens_wrong_authority() {
+        let mut program_test = setup_programs();
+
+        // Setup real authority and an imposter
+        let real_authority = Keypair::new();
+        let imposter = Keypair::new();
+        let manager = Keypair::new();
+        let manager_address = manager.pubkey();
+        let auth_id = 0u64;
+
+        add_manager_account(&mut program_test, manager_address, real_authority.pubkey());
+
+        // Create a test SPL mint
+        let mint_keypair = Keypair::new();
+        let mint_address = mint_keypair.pubkey();
+        add_spl_mint_account(&mut program_test, mint_address);
+
+        // Derive managed_miner_auth PDA
+        let (managed_miner_auth_address, _bump) = managed_miner_auth_pda(manager_address, auth_id);
+
+        // Create source ATA with balance
+        let source_ata = spl_associated_token_account::get_associated_token_address(
+            &managed_miner_auth_address,
+            &mint_address,
+        );
+        let token_amount = 500_000_000u64;
+        add_spl_token_account(
+            &mut program_test,
+            source_ata,
+            mint_address,
+            managed_miner_auth_address,
+            token_amount,
+        );
+
+        // Fund imposter (not the real authority)
+        program_test.add_account(
+            imposter.pubkey(),
+            Account {
+                lamports: 10_000_000_000,
+                data: vec![],
+                owner: solana_sdk::system_program::id(),
+                executable: false,
+                rent_epoch: 0,
+            },
+        );
+
+        let ctx = program_test.start_with_context().await;
+
+        // Build instruction with imposter as signer - should fail
+        let ix = evore::instruction::withdraw_tokens(
+            imposter.pubkey(),
+            manager_address,
+            auth_id,
+            mint_address,
+        );
+
+        let tx = Transaction::new_signed_with_payer(
+            &[ix],
+            Some(&imposter.pubkey()),
+            &[&imposter],
+            ctx.last_blockhash,
+        );
+
+        let result = ctx.banks_client.process_transaction(tx).await;
+        assert!(
+            result.is_err(),
+            "transaction should fail when signer is not the manager authority"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_withdraw_tokens_manager_not_initialized() {
+        let mut program_test = setup_programs();
+
+        let authority = Keypair::new();
+        let manager = Keypair::new();
+        let manager_address = manager.pubkey();
+        let auth_id = 0u64;
+
+        // Do NOT add a manager account - leave it uninitialized (empty)
+        program_test.add_account(
+            manager_address,
+            Account {
+                lamports: 1_000_000,
+                data: vec![],
+                owner: solana_sdk::system_program::id(),
+                executable: false,
+                rent_epoch: 0,
+            },
+        );
+
+        // Create a test SPL mint
+        let mint_keypair = Keypair::new();
+        let mint_address = mint_keypair.pubkey();
+        add_spl_mint_account(&mut program_test, mint_address);
+
+        // Derive managed_miner_auth PDA
+        let (managed_miner_auth_address, _bump) = managed_miner_auth_pda(manager_address, auth_id);
+
+        // Create source ATA with balance
+        let source_ata = spl_associated_token_account::get_associated_token_address(
+            &managed_miner_auth_address,
+            &mint_address,
+        );
+        let token_amount = 500_000_000u64;
+        add_spl_token_account(
+            &mut program_test,
+            source_ata,
+            mint_address,
+            managed_miner_auth_address,
+            token_amount,
+        );
+
+        // Fund authority
+        program_test.add_account(
+            authority.pubkey(),
+            Account {
+                lamports: 10_000_000_000,
+                data: vec![],
+                owner: solana_sdk::system_program::id(),
+                executable: false,
+                rent_epoch: 0,
+            },
+        );
+
+        let ctx = program_test.start_with_context().await;
+
+        // Build instruction - should fail because manager is not initialized
+        let ix = evore::instruction::withdraw_tokens(
+            authority.pubkey(),
+            manager_address,
+            auth_id,
+            mint_address,
+        );
+
+        let tx = Transaction::new_signed_with_payer(
+            &[ix],
+            Some(&authority.pubkey()),
+            &[&authority],
+            ctx.last_blockhash,
+        );
+
+        let result = ctx.banks_client.process_transaction(tx).await;
+        assert!(
+            result.is_err(),
+            "transaction should fail when manager is not initialized"
+        );
+    }
+}
+// ============================================================================
+// CloseManager Tests
+// ============================================================================
+
+mod close_manager {
+    use super::*;
+    use solana_sdk::{instruction::InstructionError, transaction::TransactionError};
+
+    /// Rent goes back to the authority and the manager account is gone
+    #[tokio::test]
+    async fn test_close_manager_success() {
+        let mut program_test = setup_programs();
+
+        let authority = Keypair::new();
+        let manager_address = Keypair::new().pubkey();
+        add_manager_account(&mut program_test, manager_address, authority.pubkey());
+        add_autodeploy_balance(&mut program_test, authority.pubkey(), 1_000_000_000);
+
+        let mut context = program_test.start_with_context().await;
+        let manager_rent = context.banks_client.get_account(manager_address).await.unwrap().unwrap().lamports;
+        let authority_before = context.banks_client.get_balance(authority.pubkey()).await.unwrap();
+
+        let ix = evore::instruction::close_manager(authority.pubkey(), manager_address, 2);
+        let blockhash = context.get_new_latest_blockhash().await.unwrap();
+        let tx = Transaction::new_signed_with_payer(&[ix], Some(&context.payer.pubkey()), &[&context.payer, &authority], blockhash);
+        context.banks_client.process_transaction(tx).await.expect("close should succeed");
+
+        assert!(context.banks_client.get_account(manager_address).await.unwrap().is_none(), "manager should be closed");
+        let authority_after = context.banks_client.get_balance(authority.pubkey()).await.unwrap();
+        assert_eq!(authority_after, authority_before + manager_rent);
+    }
+
+    #[tokio::test]
+    async fn test_close_manager_with_balance_fails() {
+        let mut program_test = setup_programs();
+
+        let authority = Keypair::new();
+        let manager_address = Keypair::new().pubkey();
+        add_manager_account(&mut program_test, manager_address, authority.pubkey());
+        add_autodeploy_balance(&mut program_test, authority.pubkey(), 1_000_000_000);
+
+        // auth_id 1 still holds an autodeploy balance
+        let (managed_miner_auth_address, _) = managed_miner_auth_pda(manager_address, 1);
+        add_autodeploy_balance(&mut program_test, managed_miner_auth_address, 1_000_000_000);
+
+        let mut context = program_test.start_with_context().await;
+
+        let ix = evore::instruction::close_manager(authority.pubkey(), manager_address, 2);
+        let blockhash = context.get_new_latest_blockhash().await.unwrap();
+        let tx = Transaction::new_signed_with_payer(&[ix], Some(&context.payer.pubkey()), &[&context.payer, &authority], blockhash);
+        let err = context.banks_client.process_transaction(tx).await.unwrap_err().unwrap();
+        assert_eq!(
+            err,
+            TransactionError::InstructionError(0, InstructionError::Custom(evore::error::EvoreError::ManagerNotEmpty as u32)),
+        );
+        assert!(context.banks_client.get_account(manager_address).await.unwrap().is_some(), "manager must stay open");
+    }
 
-        let close_tx = Transaction::new_signed_with_payer(
-            &[close_ix],
-            Some(&authority.pubkey()),
-            &[&authority],
-            ctx.last_blockhash,
+    /// Leaving out auth_id 0 can't skip the emptiness check on a funded PDA
+    #[tokio::test]
+    async fn test_close_manager_without_auth_ids_fails() {
+        let mut program_test = setup_programs();
+
+        let authority = Keypair::new();
+        let manager_address = Keypair::new().pubkey();
+        add_manager_account(&mut program_test, manager_address, authority.pubkey());
+        add_autodeploy_balance(&mut program_test, authority.pubkey(), 1_000_000_000);
+
+        // auth_id 0 holds a balance but is not passed
+        let (managed_miner_auth_address, _) = managed_miner_auth_pda(manager_address, 0);
+        add_autodeploy_balance(&mut program_test, managed_miner_auth_address, 1_000_000_000);
+
+        let mut context = program_test.start_with_context().await;
+
+        let ix = evore::instruction::close_manager(authority.pubkey(), manager_address, 0);
+        let blockhash = context.get_new_latest_blockhash().await.unwrap();
+        let tx = Transaction::new_signed_with_payer(&[ix], Some(&context.payer.pubkey()), &[&context.payer, &authority], blockhash);
+        let err = context.banks_client.process_transaction(tx).await.unwrap_err().unwrap();
+        assert_eq!(
+            err,
+            TransactionError::InstructionError(0, InstructionError::NotEnoughAccountKeys),
         );
+        assert!(context.banks_client.get_account(manager_address).await.unwrap().is_some(), "manager must stay open");
+    }
 
-        ctx.banks_client.process_transaction(close_tx).await.unwrap();
+    /// The manager's deployer is closed with it and its rent goes to the authority too
+    #[tokio::test]
+    async fn test_close_manager_closes_deployer() {
+        let mut program_test = setup_programs();
 
-        // Verify miner still exists and automation is closed
-        let miner_account_final = ctx.banks_client.get_account(miner_address).await.unwrap();
-        assert!(miner_account_final.is_some(), "Miner account should still exist");
-        
-        let automation_account_final = ctx.banks_client.get_account(automation_address).await.unwrap();
-        assert!(automation_account_final.is_none(), "Automation account should be closed");
+        let authority = Keypair::new();
+        let manager_address = Keypair::new().pubkey();
+        add_manager_account(&mut program_test, manager_address, authority.pubkey());
+        add_autodeploy_balance(&mut program_test, authority.pubkey(), 1_000_000_000);
+
+        let (deployer_address, _) = deployer_pda(manager_address);
+        add_deployer_account(&mut program_test, deployer_address, manager_address, Keypair::new().pubkey(), 0, 0, 0, 0);
+
+        let mut context = program_test.start_with_context().await;
+        let manager_rent = context.banks_client.get_account(manager_address).await.unwrap().unwrap().lamports;
+        let deployer_rent = context.banks_client.get_account(deployer_address).await.unwrap().unwrap().lamports;
+        let authority_before = context.banks_client.get_balance(authority.pubkey()).await.unwrap();
+
+        let ix = evore::instruction::close_manager(authority.pubkey(), manager_address, 1);
+        let blockhash = context.get_new_latest_blockhash().await.unwrap();
+        let tx = Transaction::new_signed_with_payer(&[ix], Some(&context.payer.pubkey()), &[&context.payer, &authority], blockhash);
+        context.banks_client.process_transaction(tx).await.expect("close should succeed");
+
+        assert!(context.banks_client.get_account(manager_address).await.unwrap().is_none(), "manager should be closed");
+        assert!(context.banks_client.get_account(deployer_address).await.unwrap().is_none(), "deployer should be closed");
+        let authority_after = context.banks_client.get_balance(authority.pubkey()).await.unwrap();
+        assert_eq!(authority_after, authority_before + manager_rent + deployer_rent);
     }
-}
 
-mod test_mm_create_miner {
-    use super::*;
+    #[tokio::test]
+    async fn test_close_manager_wrong_authority() {
+        let mut program_test = setup_programs();
+
+        let authority = Keypair::new();
+        let imposter = Keypair::new();
+        let manager_address = Keypair::new().pubkey();
+        add_manager_account(&mut program_test, manager_address, authority.pubkey());
+        add_autodeploy_balance(&mut program_test, imposter.pubkey(), 1_000_000_000);
+
+        let mut context = program_test.start_with_context().await;
+
+        let ix = evore::instruction::close_manager(imposter.pubkey(), manager_address, 1);
+        let blockhash = context.get_new_latest_blockhash().await.unwrap();
+        let tx = Transaction::new_signed_with_payer(&[ix], Some(&context.payer.pubkey()), &[&context.payer, &imposter], blockhash);
+        let err = context.banks_client.process_transaction(tx).await.unwrap_err().unwrap();
+        assert_eq!(
+            err,
+            TransactionError::InstructionError(0, InstructionError::Custom(evore::error::EvoreError::NotAuthorized as u32)),
+        );
+    }
 
     #[tokio::test]
-    async fn test_success() {
+    async fn test_close_manager_not_initialized() {
         let mut program_test = setup_programs();
-        
-        // Setup manager
-        let manager = Keypair::new();
+
         let authority = Keypair::new();
-        add_manager_account(&mut program_test, manager.pubkey(), authority.pubkey());
-        
-        let auth_id = 0u64;
-        let (managed_miner_auth, _) = managed_miner_auth_pda(manager.pubkey(), auth_id);
-        
-        // Fund authority to pay for transaction and miner rent
+        let manager_address = Keypair::new().pubkey();
+        add_autodeploy_balance(&mut program_test, authority.pubkey(), 1_000_000_000);
+        // Manager left uninitialized (empty system account)
         program_test.add_account(
-            authority.pubkey(),
+            manager_address,
             Account {
-                lamports: 10_000_000_000, // 10 SOL
+                lamports: 1_000_000,
                 data: vec![],
                 owner: solana_sdk::system_program::id(),
                 executable: false,
@@ -3531,345 +5804,659 @@ mod test_mm_create_miner {
             },
         );
 
-        let ctx = program_test.start_with_context().await;
+        let mut context = program_test.start_with_context().await;
 
-        // Build and send MMCreateMiner instruction
-        let ix = evore::instruction::mm_create_miner(
-            authority.pubkey(),
-            manager.pubkey(),
-            auth_id,
+        let ix = evore::instruction::close_manager(authority.pubkey(), manager_address, 1);
+        let blockhash = context.get_new_latest_blockhash().await.unwrap();
+        let tx = Transaction::new_signed_with_payer(&[ix], Some(&context.payer.pubkey()), &[&context.payer, &authority], blockhash);
+        let err = context.banks_client.process_transaction(tx).await.unwrap_err().unwrap();
+        assert_eq!(
+            err,
+            TransactionError::InstructionError(0, InstructionError::Custom(evore::error::EvoreError::ManagerNotInitialized as u32)),
+        );
+    }
+}
+
+// ============================================================================
+// SetMaxDeployPerTx Tests
+// ============================================================================
+
+mod deploy_ceiling {
+    use super::*;
+    use solana_program_test::{BanksClientError, ProgramTestContext};
+    use solana_sdk::{instruction::InstructionError, transaction::TransactionError};
+
+    /// Manager owned by `authority` with a deployer run by `deploy_authority`, ready to autodeploy
+    async fn setup_ceiling_test(authority: &Keypair, deploy_authority: &Keypair) -> (ProgramTestContext, Pubkey) {
+        let mut program_test = setup_programs();
+
+        let manager_address = Keypair::new().pubkey();
+        let (managed_miner_auth_addr, _) = managed_miner_auth_pda(manager_address, 0);
+        let (deployer_pda_addr, _) = deployer_pda(manager_address);
+
+        add_manager_account(&mut program_test, manager_address, authority.pubkey());
+        add_deployer_account(
+            &mut program_test,
+            deployer_pda_addr,
+            manager_address,
+            deploy_authority.pubkey(),
+            0, 0, 0, 0,
+        );
+
+        let current_slot = 1000;
+        let _board = setup_deploy_test_accounts(&mut program_test, TEST_ROUND_ID, current_slot, 100);
+        add_ore_miner_account(
+            &mut program_test,
+            managed_miner_auth_addr,
+            [0u64; 25],
+            0, 0,
+            TEST_ROUND_ID - 1,
+            TEST_ROUND_ID - 1,
         );
+        add_autodeploy_balance(&mut program_test, managed_miner_auth_addr, 10_000_000_000);
+
+        let mut context = program_test.start_with_context().await;
+        let _ = context.warp_to_slot(current_slot + 3);
+
+        let payer = context.payer.pubkey();
+        let fund = [
+            system_instruction::transfer(&payer, &FEE_COLLECTOR, 1_000_000),
+            system_instruction::transfer(&payer, &authority.pubkey(), 100_000_000),
+            system_instruction::transfer(&payer, &deploy_authority.pubkey(), 100_000_000),
+        ];
+        let blockhash = context.banks_client.get_latest_blockhash().await.unwrap();
+        let tx = Transaction::new_signed_with_payer(&fund, Some(&payer), &[&context.payer], blockhash);
+        context.banks_client.process_transaction(tx).await.unwrap();
+
+        (context, manager_address)
+    }
 
+    async fn set_ceiling(
+        context: &mut ProgramTestContext,
+        signer: &Keypair,
+        manager: Pubkey,
+        max_deploy_per_tx: u64,
+    ) -> Result<(), BanksClientError> {
+        let ix = evore::instruction::set_max_deploy_per_tx(signer.pubkey(), manager, max_deploy_per_tx);
+        let blockhash = context.get_new_latest_blockhash().await.unwrap();
+        let tx = Transaction::new_signed_with_payer(&[ix], Some(&signer.pubkey()), &[signer], blockhash);
+        context.banks_client.process_transaction(tx).await
+    }
+
+    async fn autodeploy(
+        context: &mut ProgramTestContext,
+        deploy_authority: &Keypair,
+        manager: Pubkey,
+        amount_per_square: u64,
+        squares_mask: u32,
+    ) -> Result<(), BanksClientError> {
+        let cu_limit_ix = ComputeBudgetInstruction::set_compute_unit_limit(1_400_000);
+        let ix = evore::instruction::mm_autodeploy(
+            deploy_authority.pubkey(),
+            manager,
+            0,
+            TEST_ROUND_ID,
+            amount_per_square,
+            squares_mask,
+        );
+        let blockhash = context.get_new_latest_blockhash().await.unwrap();
         let tx = Transaction::new_signed_with_payer(
-            &[ix],
-            Some(&authority.pubkey()),
-            &[&authority],
-            ctx.last_blockhash,
+            &[cu_limit_ix, ix],
+            Some(&deploy_authority.pubkey()),
+            &[deploy_authority],
+            blockhash,
         );
+        context.banks_client.process_transaction(tx).await
+    }
 
-        ctx.banks_client.process_transaction(tx).await.unwrap();
+    /// 5 x 100_000 breaks a 400_000 ceiling; 4 x 100_000 lands exactly on it
+    #[tokio::test]
+    async fn test_autodeploy_over_ceiling_fails() {
+        let authority = Keypair::new();
+        let deploy_authority = Keypair::new();
+        let (mut context, manager) = setup_ceiling_test(&authority, &deploy_authority).await;
 
-        // Verify miner account was created
-        let (miner_address, _) = miner_pda(managed_miner_auth);
-        let miner_account = ctx.banks_client.get_account(miner_address).await.unwrap();
-        assert!(miner_account.is_some(), "Miner account should exist");
-        
-        // Verify automation account was closed
-        let automation_address = ore_api::automation_pda(managed_miner_auth).0;
-        let automation_account = ctx.banks_client.get_account(automation_address).await.unwrap();
-        assert!(automation_account.is_none(), "Automation account should be closed");
+        set_ceiling(&mut context, &authority, manager, 400_000).await.expect("authority sets the ceiling");
+        let manager_account = context.banks_client.get_account(manager).await.unwrap().unwrap();
+        assert_eq!(Manager::try_from_bytes(&manager_account.data).unwrap().max_deploy_per_tx, 400_000);
+
+        let err = autodeploy(&mut context, &deploy_authority, manager, 100_000, 0b11111)
+            .await
+            .unwrap_err()
+            .unwrap();
+        assert_eq!(
+            err,
+            TransactionError::InstructionError(1, InstructionError::Custom(evore::error::EvoreError::DeployCeilingExceeded as u32)),
+        );
+
+        autodeploy(&mut context, &deploy_authority, manager, 100_000, 0b1111)
+            .await
+            .expect("deploy at the ceiling should succeed");
+    }
+
+    /// A manager without a ceiling (the default) deploys as before
+    #[tokio::test]
+    async fn test_autodeploy_without_ceiling_unchanged() {
+        let authority = Keypair::new();
+        let deploy_authority = Keypair::new();
+        let (mut context, manager) = setup_ceiling_test(&authority, &deploy_authority).await;
+
+        autodeploy(&mut context, &deploy_authority, manager, 100_000_000, 0b11111)
+            .await
+            .expect("no ceiling by default");
+    }
+
+    #[tokio::test]
+    async fn test_set_ceiling_wrong_authority_fails() {
+        let authority = Keypair::new();
+        let deploy_authority = Keypair::new();
+        let (mut context, manager) = setup_ceiling_test(&authority, &deploy_authority).await;
+
+        // The deploy authority can't lift the ceiling it is bound by
+        let err = set_ceiling(&mut context, &deploy_authority, manager, 0).await.unwrap_err().unwrap();
+        assert_eq!(
+            err,
+            TransactionError::InstructionError(0, InstructionError::Custom(evore::error::EvoreError::NotAuthorized as u32)),
+        );
     }
 }
 
 // ============================================================================
-// WithdrawTokens Tests
+// Two-step TransferManager Tests
 // ============================================================================
 
-mod withdraw_tokens {
+mod transfer_manager_handoff {
     use super::*;
-    use solana_program::program_pack::Pack;
-    use spl_token::state::Mint as SplMint;
-    use spl_token::state::Account as SplTokenAccount;
+    use solana_program_test::{BanksClientError, ProgramTestContext};
+    use solana_sdk::{instruction::{Instruction, InstructionError}, transaction::TransactionError};
 
-    /// Helper: add a pre-serialized SPL Mint account to ProgramTest
-    fn add_spl_mint_account(program_test: &mut ProgramTest, mint_address: Pubkey) {
-        let mut mint_data = vec![0u8; SplMint::LEN];
-        let mint_state = SplMint {
-            mint_authority: solana_program::program_option::COption::None,
-            supply: 1_000_000_000,
-            decimals: 9,
-            is_initialized: true,
-            freeze_authority: solana_program::program_option::COption::None,
-        };
-        SplMint::pack(mint_state, &mut mint_data).unwrap();
+    /// Manager owned by `authority`, with every keypair in `signers` funded for fees
+    async fn setup_handoff_test(authority: &Keypair, signers: &[&Keypair]) -> (ProgramTestContext, Pubkey) {
+        let mut program_test = setup_programs();
+        let manager_address = Keypair::new().pubkey();
+        add_manager_account(&mut program_test, manager_address, authority.pubkey());
 
-        program_test.add_account(
-            mint_address,
-            Account {
-                lamports: Rent::default().minimum_balance(SplMint::LEN),
-                data: mint_data,
-                owner: spl_token::id(),
-                executable: false,
-                rent_epoch: 0,
-            },
-        );
+        let context = program_test.start_with_context().await;
+        let payer = context.payer.pubkey();
+        let fund: Vec<_> = std::iter::once(authority)
+            .chain(signers.iter().copied())
+            .map(|k| system_instruction::transfer(&payer, &k.pubkey(), 100_000_000))
+            .collect();
+        let blockhash = context.banks_client.get_latest_blockhash().await.unwrap();
+        let tx = Transaction::new_signed_with_payer(&fund, Some(&payer), &[&context.payer], blockhash);
+        context.banks_client.process_transaction(tx).await.unwrap();
+
+        (context, manager_address)
     }
 
-    /// Helper: add a pre-serialized SPL Token Account (ATA) with a given balance
-    fn add_spl_token_account(
-        program_test: &mut ProgramTest,
-        ata_address: Pubkey,
-        mint: Pubkey,
-        owner: Pubkey,
-        amount: u64,
-    ) {
-        let mut token_data = vec![0u8; SplTokenAccount::LEN];
-        let token_state = SplTokenAccount {
-            mint,
-            owner,
-            amount,
-            delegate: solana_program::program_option::COption::None,
-            state: spl_token::state::AccountState::Initialized,
-            is_native: solana_program::program_option::COption::None,
-            delegated_amount: 0,
-            close_authority: solana_program::program_option::COption::None,
-        };
-        SplTokenAccount::pack(token_state, &mut token_data).unwrap();
+    async fn send(context: &mut ProgramTestContext, ix: Instruction, signer: &Keypair) -> Result<(), BanksClientError> {
+        let blockhash = context.get_new_latest_blockhash().await.unwrap();
+        let tx = Transaction::new_signed_with_payer(&[ix], Some(&signer.pubkey()), &[signer], blockhash);
+        context.banks_client.process_transaction(tx).await
+    }
 
-        program_test.add_account(
-            ata_address,
-            Account {
-                lamports: Rent::default().minimum_balance(SplTokenAccount::LEN),
-                data: token_data,
-                owner: spl_token::id(),
-                executable: false,
-                rent_epoch: 0,
-            },
-        );
+    async fn get_manager(context: &mut ProgramTestContext, manager: Pubkey) -> Manager {
+        let account = context.banks_client.get_account(manager).await.unwrap().unwrap();
+        *Manager::try_from_bytes(&account.data).unwrap()
+    }
+
+    fn not_authorized() -> TransactionError {
+        TransactionError::InstructionError(0, InstructionError::Custom(evore::error::EvoreError::NotAuthorized as u32))
     }
 
     #[tokio::test]
-    async fn test_withdraw_tokens_success() {
-        let mut program_test = setup_programs();
+    async fn test_initiate_then_accept_transfers_authority() {
+        let authority = Keypair::new();
+        let new_authority = Keypair::new();
+        let (mut context, manager) = setup_handoff_test(&authority, &[&new_authority]).await;
 
-        // Setup authority and manager
+        let ix = evore::instruction::initiate_transfer_manager(authority.pubkey(), manager, new_authority.pubkey());
+        send(&mut context, ix, &authority).await.expect("authority nominates a new authority");
+
+        // Nothing changes hands until the nominee accepts
+        let state = get_manager(&mut context, manager).await;
+        assert_eq!(state.authority, authority.pubkey());
+        assert_eq!(state.pending_authority, new_authority.pubkey());
+
+        let ix = evore::instruction::accept_transfer_manager(new_authority.pubkey(), manager);
+        send(&mut context, ix, &new_authority).await.expect("nominee accepts");
+
+        let state = get_manager(&mut context, manager).await;
+        assert_eq!(state.authority, new_authority.pubkey());
+        assert_eq!(state.pending_authority, Pubkey::default(), "pending transfer is cleared");
+
+        // The old authority has lost control
+        let ix = evore::instruction::initiate_transfer_manager(authority.pubkey(), manager, authority.pubkey());
+        assert_eq!(send(&mut context, ix, &authority).await.unwrap_err().unwrap(), not_authorized());
+    }
+
+    #[tokio::test]
+    async fn test_accept_by_wrong_signer_fails() {
+        let authority = Keypair::new();
+        let new_authority = Keypair::new();
+        let stranger = Keypair::new();
+        let (mut context, manager) = setup_handoff_test(&authority, &[&new_authority, &stranger]).await;
+
+        // Nothing pending yet: even the future nominee can't accept
+        let ix = evore::instruction::accept_transfer_manager(new_authority.pubkey(), manager);
+        assert_eq!(send(&mut context, ix, &new_authority).await.unwrap_err().unwrap(), not_authorized());
+
+        let ix = evore::instruction::initiate_transfer_manager(authority.pubkey(), manager, new_authority.pubkey());
+        send(&mut context, ix, &authority).await.unwrap();
+
+        let ix = evore::instruction::accept_transfer_manager(stranger.pubkey(), manager);
+        assert_eq!(send(&mut context, ix, &stranger).await.unwrap_err().unwrap(), not_authorized());
+
+        // Nor can the current authority accept on the nominee's behalf
+        let ix = evore::instruction::accept_transfer_manager(authority.pubkey(), manager);
+        assert_eq!(send(&mut context, ix, &authority).await.unwrap_err().unwrap(), not_authorized());
+
+        let state = get_manager(&mut context, manager).await;
+        assert_eq!(state.authority, authority.pubkey());
+        assert_eq!(state.pending_authority, new_authority.pubkey());
+    }
+
+    #[tokio::test]
+    async fn test_initiate_again_overwrites_pending_transfer() {
+        let authority = Keypair::new();
+        let typo = Keypair::new();
+        let intended = Keypair::new();
+        let (mut context, manager) = setup_handoff_test(&authority, &[&typo, &intended]).await;
+
+        let ix = evore::instruction::initiate_transfer_manager(authority.pubkey(), manager, typo.pubkey());
+        send(&mut context, ix, &authority).await.unwrap();
+        let ix = evore::instruction::initiate_transfer_manager(authority.pubkey(), manager, intended.pubkey());
+        send(&mut context, ix, &authority).await.expect("authority replaces the nominee");
+        assert_eq!(get_manager(&mut context, manager).await.pending_authority, intended.pubkey());
+
+        // The replaced nominee can no longer accept
+        let ix = evore::instruction::accept_transfer_manager(typo.pubkey(), manager);
+        assert_eq!(send(&mut context, ix, &typo).await.unwrap_err().unwrap(), not_authorized());
+
+        let ix = evore::instruction::accept_transfer_manager(intended.pubkey(), manager);
+        send(&mut context, ix, &intended).await.unwrap();
+        assert_eq!(get_manager(&mut context, manager).await.authority, intended.pubkey());
+    }
+
+    /// The one-step TransferManager still works and cancels a pending handoff
+    #[tokio::test]
+    async fn test_direct_transfer_clears_pending_transfer() {
         let authority = Keypair::new();
-        let manager = Keypair::new();
-        let manager_address = manager.pubkey();
-        let auth_id = 0u64;
+        let nominee = Keypair::new();
+        let direct = Keypair::new();
+        let (mut context, manager) = setup_handoff_test(&authority, &[&nominee]).await;
 
-        add_manager_account(&mut program_test, manager_address, authority.pubkey());
+        let ix = evore::instruction::initiate_transfer_manager(authority.pubkey(), manager, nominee.pubkey());
+        send(&mut context, ix, &authority).await.unwrap();
+        let ix = evore::instruction::transfer_manager(authority.pubkey(), manager, direct.pubkey());
+        send(&mut context, ix, &authority).await.expect("direct transfer still supported");
 
-        // Create a test SPL mint
-        let mint_keypair = Keypair::new();
-        let mint_address = mint_keypair.pubkey();
-        add_spl_mint_account(&mut program_test, mint_address);
+        let state = get_manager(&mut context, manager).await;
+        assert_eq!(state.authority, direct.pubkey());
+        assert_eq!(state.pending_authority, Pubkey::default());
 
-        // Derive managed_miner_auth PDA
-        let (managed_miner_auth_address, _bump) = managed_miner_auth_pda(manager_address, auth_id);
+        let ix = evore::instruction::accept_transfer_manager(nominee.pubkey(), manager);
+        assert_eq!(send(&mut context, ix, &nominee).await.unwrap_err().unwrap(), not_authorized());
+    }
+}
 
-        // Create source ATA (managed_miner_auth's token account) with balance
-        let source_ata = spl_associated_token_account::get_associated_token_address(
-            &managed_miner_auth_address,
-            &mint_address,
-        );
-        let token_amount = 500_000_000u64; // 0.5 tokens
-        add_spl_token_account(
-            &mut program_test,
-            source_ata,
-            mint_address,
-            managed_miner_auth_address,
-            token_amount,
-        );
+// ============================================================================
+// Deploy Authority Allowlist Tests
+// ============================================================================
 
-        // Fund authority
-        program_test.add_account(
-            authority.pubkey(),
-            Account {
-                lamports: 10_000_000_000,
-                data: vec![],
-                owner: solana_sdk::system_program::id(),
-                executable: false,
-                rent_epoch: 0,
-            },
-        );
+mod deploy_authority_allowlist {
+    use super::*;
+    use evore::{
+        error::EvoreError,
+        instruction::{
+            add_deploy_authority, create_deployer, create_strat_deployer, remove_deploy_authority,
+            update_deployer, update_strat_deployer,
+        },
+        state::{deploy_authority_allowlist_pda, strategy_deployer_pda, DeployAuthorityAllowlist, StrategyDeployer},
+    };
+    use solana_sdk::{instruction::InstructionError, transaction::TransactionError};
 
-        let ctx = program_test.start_with_context().await;
+    /// An allowlisted deploy authority can still be used for new deployers
+    #[tokio::test]
+    async fn test_allowlisted_authority_can_create_deployer() {
+        let mut program_test = setup_programs();
 
-        // Build and send WithdrawTokens instruction
-        let ix = evore::instruction::withdraw_tokens(
-            authority.pubkey(),
-            manager_address,
-            auth_id,
-            mint_address,
-        );
+        let manager_authority = Keypair::new();
+        let deploy_authority = Keypair::new().pubkey();
+        let manager_address = Keypair::new().pubkey();
+
+        add_manager_account(&mut program_test, manager_address, manager_authority.pubkey());
+        add_autodeploy_balance(&mut program_test, manager_authority.pubkey(), 1_000_000_000);
+
+        let ctx = program_test.start_with_context().await;
 
         let tx = Transaction::new_signed_with_payer(
-            &[ix],
-            Some(&authority.pubkey()),
-            &[&authority],
+            &[
+                add_deploy_authority(manager_authority.pubkey(), manager_address, deploy_authority),
+                create_deployer(manager_authority.pubkey(), manager_address, deploy_authority, 0, 5_000, 0),
+            ],
+            Some(&manager_authority.pubkey()),
+            &[&manager_authority],
             ctx.last_blockhash,
         );
+        ctx.banks_client.process_transaction(tx).await.expect("allowlisted authority should be accepted");
 
-        ctx.banks_client.process_transaction(tx).await.unwrap();
+        let (allowlist_address, _) = deploy_authority_allowlist_pda(manager_address);
+        let allowlist_account = ctx.banks_client.get_account(allowlist_address).await.unwrap().unwrap();
+        let allowlist = DeployAuthorityAllowlist::try_from_bytes(&allowlist_account.data).unwrap();
+        assert_eq!(allowlist.manager_key, manager_address);
+        assert_eq!(allowlist.entries(), &[deploy_authority]);
 
-        // Verify destination ATA was created and received all tokens
-        let destination_ata = spl_associated_token_account::get_associated_token_address(
-            &authority.pubkey(),
-            &mint_address,
-        );
-        let dest_account = ctx
-            .banks_client
-            .get_account(destination_ata)
-            .await
-            .unwrap()
-            .expect("destination ATA should exist");
+        let deployer_account = ctx.banks_client.get_account(deployer_pda(manager_address).0).await.unwrap().unwrap();
+        let deployer = Deployer::try_from_bytes(&deployer_account.data).unwrap();
+        assert_eq!(deployer.deploy_authority, deploy_authority);
+    }
 
-        let dest_token = SplTokenAccount::unpack(&dest_account.data).unwrap();
-        assert_eq!(
-            dest_token.amount, token_amount,
-            "destination ATA should have the full token balance"
+    /// Once an allowlist exists, unlisted authorities are rejected for both deployer kinds
+    #[tokio::test]
+    async fn test_unlisted_authority_is_rejected() {
+        let mut program_test = setup_programs();
+
+        let manager_authority = Keypair::new();
+        let allowed_authority = Keypair::new().pubkey();
+        let other_authority = Keypair::new().pubkey();
+        let manager_address = Keypair::new().pubkey();
+
+        add_manager_account(&mut program_test, manager_address, manager_authority.pubkey());
+        add_autodeploy_balance(&mut program_test, manager_authority.pubkey(), 1_000_000_000);
+
+        let ctx = program_test.start_with_context().await;
+
+        let tx = Transaction::new_signed_with_payer(
+            &[add_deploy_authority(manager_authority.pubkey(), manager_address, allowed_authority)],
+            Some(&manager_authority.pubkey()),
+            &[&manager_authority],
+            ctx.last_blockhash,
         );
+        ctx.banks_client.process_transaction(tx).await.unwrap();
 
-        // Verify source ATA is now empty
-        let src_account = ctx
-            .banks_client
-            .get_account(source_ata)
-            .await
-            .unwrap()
-            .expect("source ATA should still exist");
+        let rejected = [
+            create_deployer(manager_authority.pubkey(), manager_address, other_authority, 0, 5_000, 0),
+            create_strat_deployer(
+                manager_authority.pubkey(),
+                manager_address,
+                other_authority,
+                0,
+                5_000,
+                0,
+                3,        // Split, no motherlode bounds
+                [0u8; 64],
+                0,
+                0,
+                0,
+            ),
+        ];
+        for ix in rejected {
+            let tx = Transaction::new_signed_with_payer(
+                &[ix],
+                Some(&manager_authority.pubkey()),
+                &[&manager_authority],
+                ctx.last_blockhash,
+            );
+            let err = ctx.banks_client.process_transaction(tx).await.unwrap_err().unwrap();
+            assert_eq!(
+                err,
+                TransactionError::InstructionError(
+                    0,
+                    InstructionError::Custom(EvoreError::AuthorityNotAllowlisted as u32)
+                )
+            );
+        }
 
-        let src_token = SplTokenAccount::unpack(&src_account.data).unwrap();
-        assert_eq!(src_token.amount, 0, "source ATA should be empty after withdrawal");
+        assert!(ctx.banks_client.get_account(deployer_pda(manager_address).0).await.unwrap().is_none());
+        assert!(ctx.banks_client.get_account(strategy_deployer_pda(manager_address).0).await.unwrap().is_none());
     }
 
+    /// Removing an authority blocks it again while other entries stay usable
     #[tokio::test]
-    async fn test_withdraw_tokens_wrong_authority() {
+    async fn test_removed_authority_is_rejected() {
         let mut program_test = setup_programs();
 
-        // Setup real authority and an imposter
-        let real_authority = Keypair::new();
-        let imposter = Keypair::new();
-        let manager = Keypair::new();
-        let manager_address = manager.pubkey();
-        let auth_id = 0u64;
+        let manager_authority = Keypair::new();
+        let removed_authority = Keypair::new().pubkey();
+        let kept_authority = Keypair::new().pubkey();
+        let manager_address = Keypair::new().pubkey();
 
-        add_manager_account(&mut program_test, manager_address, real_authority.pubkey());
+        add_manager_account(&mut program_test, manager_address, manager_authority.pubkey());
+        add_autodeploy_balance(&mut program_test, manager_authority.pubkey(), 1_000_000_000);
 
-        // Create a test SPL mint
-        let mint_keypair = Keypair::new();
-        let mint_address = mint_keypair.pubkey();
-        add_spl_mint_account(&mut program_test, mint_address);
+        let ctx = program_test.start_with_context().await;
 
-        // Derive managed_miner_auth PDA
-        let (managed_miner_auth_address, _bump) = managed_miner_auth_pda(manager_address, auth_id);
+        let tx = Transaction::new_signed_with_payer(
+            &[
+                add_deploy_authority(manager_authority.pubkey(), manager_address, removed_authority),
+                add_deploy_authority(manager_authority.pubkey(), manager_address, kept_authority),
+                remove_deploy_authority(manager_authority.pubkey(), manager_address, removed_authority),
+            ],
+            Some(&manager_authority.pubkey()),
+            &[&manager_authority],
+            ctx.last_blockhash,
+        );
+        ctx.banks_client.process_transaction(tx).await.unwrap();
 
-        // Create source ATA with balance
-        let source_ata = spl_associated_token_account::get_associated_token_address(
-            &managed_miner_auth_address,
-            &mint_address,
+        let (allowlist_address, _) = deploy_authority_allowlist_pda(manager_address);
+        let allowlist_account = ctx.banks_client.get_account(allowlist_address).await.unwrap().unwrap();
+        let allowlist = DeployAuthorityAllowlist::try_from_bytes(&allowlist_account.data).unwrap();
+        assert_eq!(allowlist.entries(), &[kept_authority]);
+
+        let tx = Transaction::new_signed_with_payer(
+            &[create_deployer(manager_authority.pubkey(), manager_address, removed_authority, 0, 5_000, 0)],
+            Some(&manager_authority.pubkey()),
+            &[&manager_authority],
+            ctx.last_blockhash,
         );
-        let token_amount = 500_000_000u64;
-        add_spl_token_account(
-            &mut program_test,
-            source_ata,
-            mint_address,
-            managed_miner_auth_address,
-            token_amount,
+        let err = ctx.banks_client.process_transaction(tx).await.unwrap_err().unwrap();
+        assert_eq!(
+            err,
+            TransactionError::InstructionError(
+                0,
+                InstructionError::Custom(EvoreError::AuthorityNotAllowlisted as u32)
+            )
         );
+    }
 
-        // Fund imposter (not the real authority)
-        program_test.add_account(
-            imposter.pubkey(),
-            Account {
-                lamports: 10_000_000_000,
-                data: vec![],
-                owner: solana_sdk::system_program::id(),
-                executable: false,
-                rent_epoch: 0,
-            },
-        );
+    /// Neither the manager nor the current deploy authority can rotate a deployer to an unlisted key
+    #[tokio::test]
+    async fn test_update_to_unlisted_authority_is_rejected() {
+        let mut program_test = setup_programs();
+
+        let manager_authority = Keypair::new();
+        let deploy_authority = Keypair::new();
+        let listed_authority = Keypair::new().pubkey();
+        let unlisted_authority = Keypair::new().pubkey();
+        let manager_address = Keypair::new().pubkey();
+
+        add_manager_account(&mut program_test, manager_address, manager_authority.pubkey());
+        add_autodeploy_balance(&mut program_test, manager_authority.pubkey(), 1_000_000_000);
 
         let ctx = program_test.start_with_context().await;
 
-        // Build instruction with imposter as signer - should fail
-        let ix = evore::instruction::withdraw_tokens(
-            imposter.pubkey(),
-            manager_address,
-            auth_id,
-            mint_address,
+        let tx = Transaction::new_signed_with_payer(
+            &[
+                add_deploy_authority(manager_authority.pubkey(), manager_address, deploy_authority.pubkey()),
+                add_deploy_authority(manager_authority.pubkey(), manager_address, listed_authority),
+                create_deployer(manager_authority.pubkey(), manager_address, deploy_authority.pubkey(), 0, 5_000, 0),
+                create_strat_deployer(
+                    manager_authority.pubkey(),
+                    manager_address,
+                    deploy_authority.pubkey(),
+                    0,
+                    5_000,
+                    0,
+                    3,        // Split, no motherlode bounds
+                    [0u8; 64],
+                    0,
+                    0,
+                    0,
+                ),
+            ],
+            Some(&manager_authority.pubkey()),
+            &[&manager_authority],
+            ctx.last_blockhash,
+        );
+        ctx.banks_client.process_transaction(tx).await.unwrap();
+
+        let update = |signer: Pubkey, new_authority: Pubkey| update_deployer(signer, manager_address, new_authority, 0, 5_000, 0, 5_000, 0);
+        let update_strat = |signer: Pubkey, new_authority: Pubkey| update_strat_deployer(
+            signer, manager_address, new_authority, 0, 5_000, 0, 5_000, 0, 3, [0u8; 64], 0, 0, 0,
         );
+        let rejected = [
+            (update(deploy_authority.pubkey(), unlisted_authority), &deploy_authority),
+            (update(manager_authority.pubkey(), unlisted_authority), &manager_authority),
+            (update_strat(deploy_authority.pubkey(), unlisted_authority), &deploy_authority),
+            (update_strat(manager_authority.pubkey(), unlisted_authority), &manager_authority),
+        ];
+        for (ix, signer) in rejected {
+            let tx = Transaction::new_signed_with_payer(
+                &[ix],
+                Some(&ctx.payer.pubkey()),
+                &[&ctx.payer, signer],
+                ctx.last_blockhash,
+            );
+            let err = ctx.banks_client.process_transaction(tx).await.unwrap_err().unwrap();
+            assert_eq!(
+                err,
+                TransactionError::InstructionError(
+                    0,
+                    InstructionError::Custom(EvoreError::AuthorityNotAllowlisted as u32)
+                )
+            );
+        }
 
+        // Leaving out the allowlist can't skip the check
+        let mut ix = update(deploy_authority.pubkey(), unlisted_authority);
+        ix.accounts.pop();
         let tx = Transaction::new_signed_with_payer(
             &[ix],
-            Some(&imposter.pubkey()),
-            &[&imposter],
+            Some(&ctx.payer.pubkey()),
+            &[&ctx.payer, &deploy_authority],
             ctx.last_blockhash,
         );
+        let err = ctx.banks_client.process_transaction(tx).await.unwrap_err().unwrap();
+        assert_eq!(err, TransactionError::InstructionError(0, InstructionError::NotEnoughAccountKeys));
 
-        let result = ctx.banks_client.process_transaction(tx).await;
-        assert!(
-            result.is_err(),
-            "transaction should fail when signer is not the manager authority"
+        let deployer_account = ctx.banks_client.get_account(deployer_pda(manager_address).0).await.unwrap().unwrap();
+        let deployer = Deployer::try_from_bytes(&deployer_account.data).unwrap();
+        assert_eq!(deployer.deploy_authority, deploy_authority.pubkey());
+
+        // Rotating to another listed authority still works
+        let tx = Transaction::new_signed_with_payer(
+            &[update(deploy_authority.pubkey(), listed_authority), update_strat(manager_authority.pubkey(), listed_authority)],
+            Some(&ctx.payer.pubkey()),
+            &[&ctx.payer, &deploy_authority, &manager_authority],
+            ctx.last_blockhash,
         );
+        ctx.banks_client.process_transaction(tx).await.unwrap();
+
+        let deployer_account = ctx.banks_client.get_account(deployer_pda(manager_address).0).await.unwrap().unwrap();
+        let deployer = Deployer::try_from_bytes(&deployer_account.data).unwrap();
+        assert_eq!(deployer.deploy_authority, listed_authority);
+        let strat_account = ctx.banks_client.get_account(strategy_deployer_pda(manager_address).0).await.unwrap().unwrap();
+        assert_eq!(StrategyDeployer::from_account_data(&strat_account.data).unwrap().deploy_authority, listed_authority);
     }
+}
 
-    #[tokio::test]
-    async fn test_withdraw_tokens_manager_not_initialized() {
-        let mut program_test = setup_programs();
+// ============================================================================
+// Deploy Schedule Tests
+// ============================================================================
 
-        let authority = Keypair::new();
-        let manager = Keypair::new();
-        let manager_address = manager.pubkey();
-        let auth_id = 0u64;
+mod deploy_schedule {
+    use super::*;
+    use evore::{
+        error::EvoreError,
+        instruction::set_deploy_schedule,
+        state::{deploy_schedule_pda, DeploySchedule},
+    };
+    use solana_sdk::{instruction::InstructionError, transaction::TransactionError};
 
-        // Do NOT add a manager account - leave it uninitialized (empty)
-        program_test.add_account(
-            manager_address,
-            Account {
-                lamports: 1_000_000,
-                data: vec![],
-                owner: solana_sdk::system_program::id(),
-                executable: false,
-                rent_epoch: 0,
-            },
-        );
+    /// Consecutive rounds cycle through the stored amounts, and a second set overwrites the first
+    #[tokio::test]
+    async fn test_consecutive_rounds_use_scheduled_amounts() {
+        let mut program_test = setup_programs();
 
-        // Create a test SPL mint
-        let mint_keypair = Keypair::new();
-        let mint_address = mint_keypair.pubkey();
-        add_spl_mint_account(&mut program_test, mint_address);
+        let manager_authority = Keypair::new();
+        let manager_address = Keypair::new().pubkey();
 
-        // Derive managed_miner_auth PDA
-        let (managed_miner_auth_address, _bump) = managed_miner_auth_pda(manager_address, auth_id);
+        add_manager_account(&mut program_test, manager_address, manager_authority.pubkey());
+        add_autodeploy_balance(&mut program_test, manager_authority.pubkey(), 1_000_000_000);
 
-        // Create source ATA with balance
-        let source_ata = spl_associated_token_account::get_associated_token_address(
-            &managed_miner_auth_address,
-            &mint_address,
-        );
-        let token_amount = 500_000_000u64;
-        add_spl_token_account(
-            &mut program_test,
-            source_ata,
-            mint_address,
-            managed_miner_auth_address,
-            token_amount,
-        );
+        let ctx = program_test.start_with_context().await;
+        let (schedule_address, _) = deploy_schedule_pda(manager_address);
 
-        // Fund authority
-        program_test.add_account(
-            authority.pubkey(),
-            Account {
-                lamports: 10_000_000_000,
-                data: vec![],
-                owner: solana_sdk::system_program::id(),
-                executable: false,
-                rent_epoch: 0,
-            },
+        let tx = Transaction::new_signed_with_payer(
+            &[set_deploy_schedule(manager_authority.pubkey(), manager_address, &[1_000, 2_000, 0])],
+            Some(&manager_authority.pubkey()),
+            &[&manager_authority],
+            ctx.last_blockhash,
         );
+        ctx.banks_client.process_transaction(tx).await.expect("set schedule should succeed");
 
-        let ctx = program_test.start_with_context().await;
+        let account = ctx.banks_client.get_account(schedule_address).await.unwrap().unwrap();
+        let schedule = DeploySchedule::try_from_bytes(&account.data).unwrap();
+        assert_eq!(schedule.manager_key, manager_address);
+        assert_eq!(schedule.len, 3);
 
-        // Build instruction - should fail because manager is not initialized
-        let ix = evore::instruction::withdraw_tokens(
-            authority.pubkey(),
-            manager_address,
-            auth_id,
-            mint_address,
-        );
+        // TEST_ROUND_ID % 3 == 0
+        assert_eq!(schedule.amount_for_round(TEST_ROUND_ID), Some(1_000));
+        assert_eq!(schedule.amount_for_round(TEST_ROUND_ID + 1), Some(2_000));
+        assert_eq!(schedule.amount_for_round(TEST_ROUND_ID + 2), Some(0));
+        assert_eq!(schedule.amount_for_round(TEST_ROUND_ID + 3), Some(1_000));
 
         let tx = Transaction::new_signed_with_payer(
-            &[ix],
-            Some(&authority.pubkey()),
-            &[&authority],
+            &[set_deploy_schedule(manager_authority.pubkey(), manager_address, &[5_000])],
+            Some(&manager_authority.pubkey()),
+            &[&manager_authority],
             ctx.last_blockhash,
         );
+        ctx.banks_client.process_transaction(tx).await.expect("schedule update should succeed");
+
+        let account = ctx.banks_client.get_account(schedule_address).await.unwrap().unwrap();
+        let schedule = DeploySchedule::try_from_bytes(&account.data).unwrap();
+        assert_eq!(schedule.len, 1);
+        assert_eq!(schedule.amounts[1..], [0u64; 15], "stale entries must be cleared");
+        assert_eq!(schedule.amount_for_round(TEST_ROUND_ID), Some(5_000));
+        assert_eq!(schedule.amount_for_round(TEST_ROUND_ID + 1), Some(5_000));
+    }
 
-        let result = ctx.banks_client.process_transaction(tx).await;
-        assert!(
-            result.is_err(),
-            "transaction should fail when manager is not initialized"
-        );
+    /// Only the manager authority can set the schedule, and it can't exceed 16 entries
+    #[tokio::test]
+    async fn test_set_schedule_rejects_non_authority_and_oversized() {
+        let mut program_test = setup_programs();
+
+        let manager_authority = Keypair::new();
+        let other = Keypair::new();
+        let manager_address = Keypair::new().pubkey();
+
+        add_manager_account(&mut program_test, manager_address, manager_authority.pubkey());
+        add_autodeploy_balance(&mut program_test, manager_authority.pubkey(), 1_000_000_000);
+        add_autodeploy_balance(&mut program_test, other.pubkey(), 1_000_000_000);
+
+        let ctx = program_test.start_with_context().await;
+
+        let cases = [
+            (&other, vec![1_000u64], EvoreError::NotAuthorized),
+            (&manager_authority, vec![1_000u64; 17], EvoreError::InvalidDeploySchedule),
+        ];
+        for (signer, amounts, expected) in cases {
+            let tx = Transaction::new_signed_with_payer(
+                &[set_deploy_schedule(signer.pubkey(), manager_address, &amounts)],
+                Some(&signer.pubkey()),
+                &[signer],
+                ctx.last_blockhash,
+            );
+            let err = ctx.banks_client.process_transaction(tx).await.unwrap_err().unwrap();
+            assert_eq!(
+                err,
+                TransactionError::InstructionError(0, InstructionError::Custom(expected as u32))
+            );
+        }
+
+        let (schedule_address, _) = deploy_schedule_pda(manager_address);
+        assert!(ctx.banks_client.get_account(schedule_address).await.unwrap().is_none());
     }
-}
\ No newline at end of file
+}
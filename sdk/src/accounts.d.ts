@@ -3,6 +3,8 @@ import { PublicKey } from "@solana/web3.js";
 // Account Types
 export interface Manager {
   authority: PublicKey;
+  maxDeployPerTx: bigint;
+  pendingAuthority: PublicKey | null;
 }
 
 export interface Deployer {
@@ -29,6 +31,9 @@ export interface StrategyDeployer {
   maxPerRound: bigint;
   strategyType: number;
   strategyData: Buffer;
+  maxSquaresPerDeploy: number;
+  blockedSquaresMask: number;
+  maxLossPerRound: bigint;
 }
 
 // Account Decoders
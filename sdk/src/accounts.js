@@ -8,13 +8,20 @@ const { LAMPORTS_PER_SOL } = require("./constants");
 /**
  * Decodes a Manager account from raw account data
  * @param {Buffer|Uint8Array} data - Raw account data from getAccountInfo
- * @returns {{ authority: PublicKey }} - Decoded manager data
+ * Legacy 40-byte managers (not yet migrated) decode with maxDeployPerTx = 0
+ * pendingAuthority is null unless a two-step transfer is awaiting acceptance
+ * @returns {{ authority: PublicKey, maxDeployPerTx: bigint, pendingAuthority: PublicKey|null }} - Decoded manager data
  */
 function decodeManager(data) {
+  const buffer = Buffer.from(data);
   // Skip 8-byte discriminator
-  const authorityBytes = data.slice(8, 40);
+  const authorityBytes = buffer.slice(8, 40);
   const authority = new PublicKey(authorityBytes);
-  return { authority };
+  const maxDeployPerTx = buffer.length >= 48 ? buffer.readBigUInt64LE(40) : 0n;
+  const pendingBytes = buffer.length >= 80 ? buffer.slice(48, 80) : null;
+  const pendingAuthority =
+    pendingBytes && pendingBytes.some((b) => b !== 0) ? new PublicKey(pendingBytes) : null;
+  return { authority, maxDeployPerTx, pendingAuthority };
 }
 
 /**
@@ -40,9 +47,10 @@ function decodeDeployer(data) {
 
 /**
  * Decodes a StrategyDeployer account from raw account data
- * Size: 185 bytes (8 discriminator + 32 manager_key + 32 deploy_authority + 8 bps_fee + 8 flat_fee + 8 expected_bps_fee + 8 expected_flat_fee + 8 max_per_round + 1 strategy_type + 64 strategy_data + 8 padding)
+ * Size: 192 bytes (8 discriminator + 32 manager_key + 32 deploy_authority + 8 bps_fee + 8 flat_fee + 8 expected_bps_fee + 8 expected_flat_fee + 8 max_per_round + 1 strategy_type + 64 strategy_data + 1 max_squares_per_deploy + 4 blocked_squares_mask + 2 padding + 8 max_loss_per_round)
+ * Legacy 184-byte accounts decode with maxLossPerRound = 0
  * @param {Buffer|Uint8Array} data - Raw account data from getAccountInfo
- * @returns {{ managerKey: PublicKey, deployAuthority: PublicKey, bpsFee: bigint, flatFee: bigint, expectedBpsFee: bigint, expectedFlatFee: bigint, maxPerRound: bigint, strategyType: number, strategyData: Buffer }}
+ * @returns {{ managerKey: PublicKey, deployAuthority: PublicKey, bpsFee: bigint, flatFee: bigint, expectedBpsFee: bigint, expectedFlatFee: bigint, maxPerRound: bigint, strategyType: number, strategyData: Buffer, maxSquaresPerDeploy: number, blockedSquaresMask: number, maxLossPerRound: bigint }}
  */
 function decodeStrategyDeployer(data) {
   const buffer = Buffer.from(data);
@@ -56,8 +64,11 @@ function decodeStrategyDeployer(data) {
   const maxPerRound = buffer.readBigUInt64LE(104);
   const strategyType = buffer[112];
   const strategyData = Buffer.from(buffer.slice(113, 177));
+  const maxSquaresPerDeploy = buffer[177];
+  const blockedSquaresMask = buffer.readUInt32LE(178);
+  const maxLossPerRound = buffer.length >= 192 ? buffer.readBigUInt64LE(184) : 0n;
   
-  return { managerKey, deployAuthority, bpsFee, flatFee, expectedBpsFee, expectedFlatFee, maxPerRound, strategyType, strategyData };
+  return { managerKey, deployAuthority, bpsFee, flatFee, expectedBpsFee, expectedFlatFee, maxPerRound, strategyType, strategyData, maxSquaresPerDeploy, blockedSquaresMask, maxLossPerRound };
 }
 
 /**
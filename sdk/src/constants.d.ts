@@ -7,6 +7,7 @@ export declare const DEPLOY_FEE: bigint;
 export declare const MANAGED_MINER_AUTH_SEED: string;
 export declare const DEPLOYER_SEED: string;
 export declare const STRATEGY_DEPLOYER_SEED: string;
+export declare const DEPLOY_AUTHORITY_ALLOWLIST_SEED: string;
 
 // ORE Program
 export declare const ORE_PROGRAM_ID: PublicKey;
@@ -58,6 +59,8 @@ export declare const EvoreInstruction: {
   MMStratFullAutodeploy: number;
   MMStratAutocheckpoint: number;
   RecycleStratSol: number;
+  AddDeployAuthority: number;
+  RemoveDeployAuthority: number;
 };
 
 // Strategy Types
@@ -68,6 +71,10 @@ export declare const StrategyType: {
   Split: number;
   DynamicSplitPercentage: number;
   DynamicEv: number;
+  MirrorTopMiner: number;
+  LoserPool: number;
+  Momentum: number;
+  Weighted: number;
 };
 
 // Helpers
@@ -20,6 +20,7 @@ const DEPLOY_FEE = 1000n;
 const MANAGED_MINER_AUTH_SEED = "managed-miner-auth";
 const DEPLOYER_SEED = "deployer";
 const STRATEGY_DEPLOYER_SEED = "strategy-deployer";
+const DEPLOY_AUTHORITY_ALLOWLIST_SEED = "deploy-authority-allowlist";
 
 // =============================================================================
 // ORE Program (v3)
@@ -112,6 +113,8 @@ const EvoreInstruction = {
   MMStratFullAutodeploy: 19,
   MMStratAutocheckpoint: 20,
   RecycleStratSol: 21,
+  AddDeployAuthority: 22,
+  RemoveDeployAuthority: 23,
 };
 
 /** Strategy type discriminators (must match program) */
@@ -122,6 +125,10 @@ const StrategyType = {
   Split: 3,
   DynamicSplitPercentage: 4,
   DynamicEv: 5,
+  MirrorTopMiner: 6,
+  LoserPool: 7,
+  Momentum: 8,
+  Weighted: 9,
 };
 
 // =============================================================================
@@ -145,6 +152,7 @@ module.exports = {
   MANAGED_MINER_AUTH_SEED,
   DEPLOYER_SEED,
   STRATEGY_DEPLOYER_SEED,
+  DEPLOY_AUTHORITY_ALLOWLIST_SEED,
   
   // ORE
   ORE_PROGRAM_ID,
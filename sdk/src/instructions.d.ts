@@ -48,6 +48,9 @@ export declare function splitDeployInstruction(
   allowMultiDeploy?: boolean
 ): TransactionInstruction;
 
+export declare function deployPreviewInstruction(instruction: TransactionInstruction): TransactionInstruction;
+export declare function decodeDeployPreview(returnData: Buffer): bigint[];
+
 // Checkpoint & Claim (manager authority)
 export declare function mmCheckpointInstruction(
   signer: PublicKey,
@@ -97,6 +100,20 @@ export declare function updateDeployerInstruction(
   newMaxPerRound?: bigint
 ): TransactionInstruction;
 
+// Deploy Authority Allowlist (manager authority)
+// Once the allowlist has entries, deployers can only be created for or rotated to listed authorities
+export declare function addDeployAuthorityInstruction(
+  signer: PublicKey,
+  manager: PublicKey,
+  deployAuthority: PublicKey
+): TransactionInstruction;
+
+export declare function removeDeployAuthorityInstruction(
+  signer: PublicKey,
+  manager: PublicKey,
+  deployAuthority: PublicKey
+): TransactionInstruction;
+
 // Autodeploy Balance (manager authority)
 export declare function depositAutodeployBalanceInstruction(
   signer: PublicKey,
@@ -169,7 +186,10 @@ export declare function createStratDeployerInstruction(
   flatFee?: bigint,
   maxPerRound?: bigint,
   strategyType?: number,
-  strategyData?: Buffer
+  strategyData?: Buffer,
+  maxSquaresPerDeploy?: number,
+  blockedSquaresMask?: number,
+  maxLossPerRound?: bigint
 ): TransactionInstruction;
 
 export declare function updateStratDeployerInstruction(
@@ -182,7 +202,10 @@ export declare function updateStratDeployerInstruction(
   newExpectedFlatFee?: bigint,
   newMaxPerRound?: bigint,
   strategyType?: number,
-  strategyData?: Buffer
+  strategyData?: Buffer,
+  maxSquaresPerDeploy?: number,
+  blockedSquaresMask?: number,
+  maxLossPerRound?: bigint
 ): TransactionInstruction;
 
 // Strategy Autodeploy (deploy authority - for executors)
@@ -15,6 +15,7 @@ const {
   getManagedMinerAuthPda,
   getDeployerPda,
   getStrategyDeployerPda,
+  getDeployAuthorityAllowlistPda,
   getOreMinerPda,
   getOreBoardPda,
   getOreRoundPda,
@@ -264,6 +265,37 @@ function splitDeployInstruction(
   });
 }
 
+/**
+ * Turns any deploy instruction above into a preview: the program runs the same
+ * checks and strategy math, then returns the planned per-square lamports as
+ * return data instead of moving funds or calling ORE. Send it through
+ * simulateTransaction and read the result with decodeDeployPreview.
+ * @param {TransactionInstruction} instruction - ev/percentage/manual/split deploy instruction
+ * @returns {TransactionInstruction}
+ */
+function deployPreviewInstruction(instruction) {
+  const data = Buffer.from(instruction.data);
+  data[11] = 1; // MMDeploy.simulate
+  return new TransactionInstruction({
+    programId: instruction.programId,
+    keys: instruction.keys,
+    data,
+  });
+}
+
+/**
+ * Decodes a deploy preview's return data into the 25 planned amounts
+ * @param {Buffer} returnData - Return data bytes (200 bytes, 25 x u64 LE)
+ * @returns {bigint[]}
+ */
+function decodeDeployPreview(returnData) {
+  const amounts = [];
+  for (let i = 0; i < 25; i++) {
+    amounts.push(returnData.readBigUInt64LE(i * 8));
+  }
+  return amounts;
+}
+
 // =============================================================================
 // Checkpoint & Claim Instructions (Manager Authority Required)
 // =============================================================================
@@ -391,6 +423,7 @@ function mmClaimOreInstruction(signer, manager, authId = 0n) {
  * @param {bigint} bpsFee - Max bps fee the user accepts (deployer can charge up to this)
  * @param {bigint} flatFee - Max flat fee in lamports the user accepts (deployer can charge up to this)
  * @param {bigint} maxPerRound - Maximum lamports to deploy per round (0 = unlimited)
+ * Fails with AuthorityNotAllowlisted if the manager has an allowlist that doesn't list deployAuthority
  * @returns {TransactionInstruction}
  */
 function createDeployerInstruction(signer, manager, deployAuthority, bpsFee, flatFee = 0n, maxPerRound = 1_000_000_000n) {
@@ -410,6 +443,7 @@ function createDeployerInstruction(signer, manager, deployAuthority, bpsFee, fla
       { pubkey: deployerPda, isSigner: false, isWritable: true },
       { pubkey: deployAuthority, isSigner: false, isWritable: false },
       { pubkey: SYSTEM_PROGRAM_ID, isSigner: false, isWritable: false },
+      { pubkey: getDeployAuthorityAllowlistPda(manager)[0], isSigner: false, isWritable: false },
     ],
     data,
   });
@@ -427,6 +461,7 @@ function createDeployerInstruction(signer, manager, deployAuthority, bpsFee, fla
  * @param {bigint} newExpectedBpsFee - Max bps fee user accepts (manager only, 0 = accept any)
  * @param {bigint} newExpectedFlatFee - Max flat fee user accepts (manager only, 0 = accept any)
  * @param {bigint} newMaxPerRound - Maximum lamports to deploy per round (manager only, 0 = unlimited)
+ * A changed deploy authority must be on the manager's allowlist (if it has one)
  * @returns {TransactionInstruction}
  */
 function updateDeployerInstruction(
@@ -457,6 +492,65 @@ function updateDeployerInstruction(
       { pubkey: deployerPda, isSigner: false, isWritable: true },
       { pubkey: newDeployAuthority, isSigner: false, isWritable: false },
       { pubkey: SYSTEM_PROGRAM_ID, isSigner: false, isWritable: false },
+      { pubkey: getDeployAuthorityAllowlistPda(manager)[0], isSigner: false, isWritable: false },
+    ],
+    data,
+  });
+}
+
+// =============================================================================
+// Deploy Authority Allowlist Instructions (Manager Authority Required)
+// =============================================================================
+
+/**
+ * Creates an AddDeployAuthority instruction
+ * Allowlists a deploy authority for the manager's deployers, creating the allowlist on first use.
+ * Once it has entries, deployers can only be created for or rotated to listed authorities.
+ * @param {PublicKey} signer - Manager authority (pays for the allowlist account)
+ * @param {PublicKey} manager - Manager account
+ * @param {PublicKey} deployAuthority - Deploy authority to allow
+ * @returns {TransactionInstruction}
+ */
+function addDeployAuthorityInstruction(signer, manager, deployAuthority) {
+  const [allowlistPda] = getDeployAuthorityAllowlistPda(manager);
+
+  const data = Buffer.alloc(1);
+  data[0] = EvoreInstruction.AddDeployAuthority;
+
+  return new TransactionInstruction({
+    programId: EVORE_PROGRAM_ID,
+    keys: [
+      { pubkey: signer, isSigner: true, isWritable: true },
+      { pubkey: manager, isSigner: false, isWritable: false },
+      { pubkey: allowlistPda, isSigner: false, isWritable: true },
+      { pubkey: deployAuthority, isSigner: false, isWritable: false },
+      { pubkey: SYSTEM_PROGRAM_ID, isSigner: false, isWritable: false },
+    ],
+    data,
+  });
+}
+
+/**
+ * Creates a RemoveDeployAuthority instruction
+ * Existing deployers are not affected; removing the last entry lifts the restriction.
+ * @param {PublicKey} signer - Manager authority
+ * @param {PublicKey} manager - Manager account
+ * @param {PublicKey} deployAuthority - Deploy authority to remove
+ * @returns {TransactionInstruction}
+ */
+function removeDeployAuthorityInstruction(signer, manager, deployAuthority) {
+  const [allowlistPda] = getDeployAuthorityAllowlistPda(manager);
+
+  const data = Buffer.alloc(1);
+  data[0] = EvoreInstruction.RemoveDeployAuthority;
+
+  return new TransactionInstruction({
+    programId: EVORE_PROGRAM_ID,
+    keys: [
+      { pubkey: signer, isSigner: true, isWritable: true },
+      { pubkey: manager, isSigner: false, isWritable: false },
+      { pubkey: allowlistPda, isSigner: false, isWritable: true },
+      { pubkey: deployAuthority, isSigner: false, isWritable: false },
     ],
     data,
   });
@@ -890,6 +984,9 @@ function withdrawTokensInstruction(signer, manager, authId = 0n, mint = ORE_MINT
  * @param {bigint} maxPerRound - Maximum lamports to deploy per round (0 = unlimited)
  * @param {number} strategyType - Strategy type discriminator (see StrategyType constants)
  * @param {Buffer} strategyData - Strategy-specific configuration data (up to 64 bytes)
+ * @param {number} maxSquaresPerDeploy - Most squares one deploy may fund, 0-25 (0 = no cap)
+ * @param {number} blockedSquaresMask - Squares no deploy may fund (bit i = square i)
+ * @param {bigint} maxLossPerRound - Most lamports a miner may have at risk per round (0 = no cap)
  * @returns {TransactionInstruction}
  */
 function createStratDeployerInstruction(
@@ -900,18 +997,24 @@ function createStratDeployerInstruction(
   flatFee = 0n,
   maxPerRound = 1_000_000_000n,
   strategyType = 0,
-  strategyData = Buffer.alloc(64)
+  strategyData = Buffer.alloc(64),
+  maxSquaresPerDeploy = 0,
+  blockedSquaresMask = 0,
+  maxLossPerRound = 0n
 ) {
   const [stratDeployerPda] = getStrategyDeployerPda(manager);
 
-  const data = Buffer.alloc(96);
+  const data = Buffer.alloc(105);
   data[0] = EvoreInstruction.CreateStratDeployer;
   data.writeBigUInt64LE(bpsFee, 1);
   data.writeBigUInt64LE(flatFee, 9);
   data.writeBigUInt64LE(maxPerRound, 17);
   data[25] = strategyType;
   strategyData.copy(data, 26, 0, Math.min(strategyData.length, 64));
-  // _pad: [u8; 7] at bytes 90-96 (already zeros)
+  data[90] = maxSquaresPerDeploy;
+  data.writeUInt32LE(blockedSquaresMask, 91);
+  // _pad: [u8; 2] at bytes 95-97 (already zeros)
+  data.writeBigUInt64LE(maxLossPerRound, 97);
 
   return new TransactionInstruction({
     programId: EVORE_PROGRAM_ID,
@@ -921,6 +1024,7 @@ function createStratDeployerInstruction(
       { pubkey: stratDeployerPda, isSigner: false, isWritable: true },
       { pubkey: deployAuthority, isSigner: false, isWritable: false },
       { pubkey: SYSTEM_PROGRAM_ID, isSigner: false, isWritable: false },
+      { pubkey: getDeployAuthorityAllowlistPda(manager)[0], isSigner: false, isWritable: false },
     ],
     data,
   });
@@ -940,6 +1044,9 @@ function createStratDeployerInstruction(
  * @param {bigint} newMaxPerRound - Max lamports per round (manager only)
  * @param {number} strategyType - Strategy type discriminator (manager only)
  * @param {Buffer} strategyData - Strategy-specific configuration data (manager only)
+ * @param {number} maxSquaresPerDeploy - Most squares one deploy may fund, 0-25 (0 = no cap; manager only)
+ * @param {number} blockedSquaresMask - Squares no deploy may fund (bit i = square i; manager only)
+ * @param {bigint} maxLossPerRound - Most lamports a miner may have at risk per round (0 = no cap; manager only)
  * @returns {TransactionInstruction}
  */
 function updateStratDeployerInstruction(
@@ -952,11 +1059,14 @@ function updateStratDeployerInstruction(
   newExpectedFlatFee = 0n,
   newMaxPerRound = 1_000_000_000n,
   strategyType = 0,
-  strategyData = Buffer.alloc(64)
+  strategyData = Buffer.alloc(64),
+  maxSquaresPerDeploy = 0,
+  blockedSquaresMask = 0,
+  maxLossPerRound = 0n
 ) {
   const [stratDeployerPda] = getStrategyDeployerPda(manager);
 
-  const data = Buffer.alloc(104);
+  const data = Buffer.alloc(121);
   data[0] = EvoreInstruction.UpdateStratDeployer;
   data.writeBigUInt64LE(newBpsFee, 1);
   data.writeBigUInt64LE(newFlatFee, 9);
@@ -965,7 +1075,10 @@ function updateStratDeployerInstruction(
   data.writeBigUInt64LE(newMaxPerRound, 33);
   data[41] = strategyType;
   strategyData.copy(data, 42, 0, Math.min(strategyData.length, 64));
-  // _pad: [u8; 7] at bytes 106-112 (already zeros)
+  data[106] = maxSquaresPerDeploy;
+  data.writeUInt32LE(blockedSquaresMask, 107);
+  // _pad: [u8; 2] at bytes 111-113 (already zeros)
+  data.writeBigUInt64LE(maxLossPerRound, 113);
 
   return new TransactionInstruction({
     programId: EVORE_PROGRAM_ID,
@@ -975,6 +1088,7 @@ function updateStratDeployerInstruction(
       { pubkey: stratDeployerPda, isSigner: false, isWritable: true },
       { pubkey: newDeployAuthority, isSigner: false, isWritable: false },
       { pubkey: SYSTEM_PROGRAM_ID, isSigner: false, isWritable: false },
+      { pubkey: getDeployAuthorityAllowlistPda(manager)[0], isSigner: false, isWritable: false },
     ],
     data,
   });
@@ -1177,6 +1291,8 @@ module.exports = {
   percentageDeployInstruction,
   manualDeployInstruction,
   splitDeployInstruction,
+  deployPreviewInstruction,
+  decodeDeployPreview,
 
   // Checkpoint & Claim (manager authority)
   mmCheckpointInstruction,
@@ -1187,6 +1303,10 @@ module.exports = {
   createDeployerInstruction,
   updateDeployerInstruction,
 
+  // Deploy Authority Allowlist (manager authority)
+  addDeployAuthorityInstruction,
+  removeDeployAuthorityInstruction,
+
   // Autodeploy Balance (manager authority)
   depositAutodeployBalanceInstruction,
   withdrawAutodeployBalanceInstruction,
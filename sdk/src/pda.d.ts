@@ -7,6 +7,7 @@ export declare function bigintToLeBytes(value: bigint): Buffer;
 export declare function getManagedMinerAuthPda(manager: PublicKey, authId: bigint): [PublicKey, number];
 export declare function getDeployerPda(manager: PublicKey): [PublicKey, number];
 export declare function getStrategyDeployerPda(manager: PublicKey): [PublicKey, number];
+export declare function getDeployAuthorityAllowlistPda(manager: PublicKey): [PublicKey, number];
 
 // ORE PDAs
 export declare function getOreMinerPda(authority: PublicKey): [PublicKey, number];
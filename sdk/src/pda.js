@@ -9,6 +9,7 @@ const {
   MANAGED_MINER_AUTH_SEED,
   DEPLOYER_SEED,
   STRATEGY_DEPLOYER_SEED,
+  DEPLOY_AUTHORITY_ALLOWLIST_SEED,
   ORE_MINER_SEED,
   ORE_BOARD_SEED,
   ORE_ROUND_SEED,
@@ -80,6 +81,21 @@ function getStrategyDeployerPda(manager) {
   );
 }
 
+/**
+ * Derives the deploy authority allowlist PDA for a manager
+ * @param {PublicKey} manager - The manager account address
+ * @returns {[PublicKey, number]} - [PDA address, bump seed]
+ */
+function getDeployAuthorityAllowlistPda(manager) {
+  return PublicKey.findProgramAddressSync(
+    [
+      Buffer.from(DEPLOY_AUTHORITY_ALLOWLIST_SEED),
+      manager.toBuffer(),
+    ],
+    EVORE_PROGRAM_ID
+  );
+}
+
 // =============================================================================
 // ORE PDAs
 // =============================================================================
@@ -219,6 +235,7 @@ module.exports = {
   getManagedMinerAuthPda,
   getDeployerPda,
   getStrategyDeployerPda,
+  getDeployAuthorityAllowlistPda,
   
   // ORE PDAs
   getOreMinerPda,